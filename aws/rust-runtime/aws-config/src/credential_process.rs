@@ -7,13 +7,19 @@
 
 //! Credentials Provider for external process
 
-use crate::json_credentials::{json_parse_loop, InvalidJsonCredentials, RefreshableCredentials};
+use crate::json_credentials::{json_parse_loop, InvalidJsonCredentials};
+use crate::provider_config::ProviderConfig;
 use crate::sensitive_command::CommandWithSensitiveArgs;
 use aws_credential_types::provider::{self, error::CredentialsError, future, ProvideCredentials};
 use aws_credential_types::Credentials;
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
 use aws_smithy_json::deserialize::Token;
+use std::borrow::Cow;
+use std::fmt;
+use std::path::PathBuf;
 use std::process::Command;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
@@ -50,9 +56,28 @@ use time::OffsetDateTime;
 /// [profile example]
 /// credential_process = /path/to/my/process --some --arguments
 /// ```
+///
+/// It can also be constructed in code with a [`Builder`], which runs the program directly
+/// rather than through a shell:
+///
+/// ```no_run
+/// use aws_config::credential_process::CredentialProcessProvider;
+/// use std::time::Duration;
+///
+/// let provider = CredentialProcessProvider::builder()
+///     .program("/path/to/my/process")
+///     .args(["--some", "--arguments"])
+///     .env("HELPER_MODE", "aws")
+///     .timeout(Duration::from_secs(10))
+///     .build();
+/// ```
 #[derive(Debug)]
 pub struct CredentialProcessProvider {
-    command: CommandWithSensitiveArgs<String>,
+    command: ProcessCommand,
+    env: Vec<(String, String)>,
+    current_dir: Option<PathBuf>,
+    timeout: Option<Duration>,
+    sleep_impl: Option<SharedAsyncSleep>,
 }
 
 impl ProvideCredentials for CredentialProcessProvider {
@@ -66,15 +91,30 @@ impl ProvideCredentials for CredentialProcessProvider {
 
 impl CredentialProcessProvider {
     /// Create new [`CredentialProcessProvider`] with the `command` needed to execute the external process.
+    ///
+    /// The command is run through the system shell, `sh` or `cmd.exe`.
     pub fn new(command: String) -> Self {
-        Self {
-            command: CommandWithSensitiveArgs::new(command),
-        }
+        Self::from_process_command(ProcessCommand::Shell(CommandWithSensitiveArgs::new(
+            command,
+        )))
+    }
+
+    /// Creates a builder to construct a [`CredentialProcessProvider`] that runs a program directly.
+    pub fn builder() -> Builder {
+        Builder::default()
     }
 
     pub(crate) fn from_command(command: &CommandWithSensitiveArgs<&str>) -> Self {
+        Self::from_process_command(ProcessCommand::Shell(command.to_owned_string()))
+    }
+
+    fn from_process_command(command: ProcessCommand) -> Self {
         Self {
-            command: command.to_owned_string(),
+            command,
+            env: Vec::new(),
+            current_dir: None,
+            timeout: None,
+            sleep_impl: None,
         }
     }
 
@@ -82,24 +122,28 @@ impl CredentialProcessProvider {
         // Security: command arguments must be redacted at debug level
         tracing::debug!(command = %self.command, "loading credentials from external process");
 
-        let command = if cfg!(windows) {
-            let mut command = Command::new("cmd.exe");
-            command.args(["/C", self.command.unredacted()]);
-            command
-        } else {
-            let mut command = Command::new("sh");
-            command.args(["-c", self.command.unredacted()]);
-            command
-        };
-        let output = tokio::process::Command::from(command)
-            .output()
-            .await
-            .map_err(|e| {
-                CredentialsError::provider_error(format!(
-                    "Error retrieving credentials from external process: {}",
-                    e
-                ))
-            })?;
+        let mut command = self.command.to_command();
+        command.envs(self.env.iter().map(|(key, value)| (key, value)));
+        if let Some(current_dir) = &self.current_dir {
+            command.current_dir(current_dir);
+        }
+        let mut command = tokio::process::Command::from(command);
+        // Don't leave the process running if it times out, or if credentials are no longer needed
+        command.kill_on_drop(true);
+        let output = match (self.timeout, &self.sleep_impl) {
+            (Some(timeout), Some(sleep_impl)) => {
+                Timeout::new(command.output(), sleep_impl.sleep(timeout))
+                    .await
+                    .map_err(|_| CredentialsError::provider_timed_out(timeout))?
+            }
+            _ => command.output().await,
+        }
+        .map_err(|e| {
+            CredentialsError::provider_error(format!(
+                "Error retrieving credentials from external process: {}",
+                e
+            ))
+        })?;
 
         // Security: command arguments can be logged at trace level
         tracing::trace!(command = ?self.command, status = ?output.status, "executed command (unredacted)");
@@ -121,17 +165,16 @@ impl CredentialProcessProvider {
         })?;
 
         match parse_credential_process_json_credentials(output) {
-            Ok(RefreshableCredentials {
+            Ok(ProcessCredentials {
                 access_key_id,
                 secret_access_key,
                 session_token,
                 expiration,
-                ..
             }) => Ok(Credentials::new(
                 access_key_id,
                 secret_access_key,
-                Some(session_token.to_string()),
-                expiration.into(),
+                session_token.map(Cow::into_owned),
+                expiration,
                 "CredentialProcess",
             )),
             Err(invalid) => Err(CredentialsError::provider_error(format!(
@@ -142,6 +185,169 @@ impl CredentialProcessProvider {
     }
 }
 
+/// Builder for [`CredentialProcessProvider`]
+///
+/// The provider runs the program directly with the given arguments, without a shell.
+#[derive(Debug, Default)]
+pub struct Builder {
+    program: Option<String>,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    current_dir: Option<PathBuf>,
+    timeout: Option<Duration>,
+    provider_config: Option<ProviderConfig>,
+}
+
+impl Builder {
+    /// Sets the program to run.
+    ///
+    /// The program is looked up on the `PATH` if it isn't a path.
+    pub fn program(mut self, program: impl Into<String>) -> Self {
+        self.program = Some(program.into());
+        self
+    }
+
+    /// Adds an argument to pass to the program.
+    ///
+    /// Arguments are redacted from logs, since they can be sensitive.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Adds arguments to pass to the program.
+    ///
+    /// Arguments are redacted from logs, since they can be sensitive.
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets an environment variable for the program, in addition to the ones it inherits.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets the working directory of the program.
+    pub fn current_dir(mut self, current_dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(current_dir.into());
+        self
+    }
+
+    /// Sets how long the program may run before it's killed.
+    ///
+    /// By default, the program isn't timed out by the provider.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Override the configuration used for this provider.
+    ///
+    /// The sleep implementation that times out the program is taken from the provider config.
+    pub fn configure(mut self, provider_config: &ProviderConfig) -> Self {
+        self.provider_config = Some(provider_config.clone());
+        self
+    }
+
+    /// Builds a [`CredentialProcessProvider`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if no [`program`](Self::program) was set.
+    pub fn build(self) -> CredentialProcessProvider {
+        let program = self
+            .program
+            .expect("a program is required to build a CredentialProcessProvider");
+        let sleep_impl = self.provider_config.unwrap_or_default().sleep_impl();
+        if self.timeout.is_some() && sleep_impl.is_none() {
+            tracing::warn!("the credential process timeout is ignored because no sleep implementation is configured");
+        }
+        CredentialProcessProvider {
+            command: ProcessCommand::Program {
+                program,
+                args: self.args,
+            },
+            env: self.env,
+            current_dir: self.current_dir,
+            timeout: self.timeout,
+            sleep_impl,
+        }
+    }
+}
+
+/// The process that a [`CredentialProcessProvider`] runs.
+enum ProcessCommand {
+    /// A command line that is run through the system shell
+    Shell(CommandWithSensitiveArgs<String>),
+    /// A program that is run directly
+    Program { program: String, args: Vec<String> },
+}
+
+impl ProcessCommand {
+    fn to_command(&self) -> Command {
+        match self {
+            ProcessCommand::Shell(command) if cfg!(windows) => {
+                let mut cmd = Command::new("cmd.exe");
+                cmd.args(["/C", command.unredacted()]);
+                cmd
+            }
+            ProcessCommand::Shell(command) => {
+                let mut cmd = Command::new("sh");
+                cmd.args(["-c", command.unredacted()]);
+                cmd
+            }
+            ProcessCommand::Program { program, args } => {
+                let mut cmd = Command::new(program);
+                cmd.args(args);
+                cmd
+            }
+        }
+    }
+}
+
+impl fmt::Display for ProcessCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessCommand::Shell(command) => write!(f, "{}", command),
+            // Security: The arguments for command must be redacted since they can be sensitive
+            ProcessCommand::Program { program, args } if args.is_empty() => {
+                write!(f, "{}", program)
+            }
+            ProcessCommand::Program { program, .. } => {
+                write!(f, "{} ** arguments redacted **", program)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ProcessCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", format!("{}", self))
+    }
+}
+
+/// Credentials parsed from the output of a credential process
+#[derive(PartialEq, Eq)]
+pub(crate) struct ProcessCredentials<'a> {
+    pub(crate) access_key_id: Cow<'a, str>,
+    pub(crate) secret_access_key: Cow<'a, str>,
+    pub(crate) session_token: Option<Cow<'a, str>>,
+    pub(crate) expiration: Option<SystemTime>,
+}
+
+impl<'a> fmt::Debug for ProcessCredentials<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"** redacted **")
+            .field("session_token", &"** redacted **")
+            .field("expiration", &self.expiration)
+            .finish()
+    }
+}
+
 /// Deserialize a credential_process response from a string
 ///
 /// Returns an error if the response cannot be successfully parsed or is missing keys.
@@ -149,7 +355,7 @@ impl CredentialProcessProvider {
 /// Keys are case insensitive.
 pub(crate) fn parse_credential_process_json_credentials(
     credentials_response: &str,
-) -> Result<RefreshableCredentials<'_>, InvalidJsonCredentials> {
+) -> Result<ProcessCredentials<'_>, InvalidJsonCredentials> {
     let mut version = None;
     let mut access_key_id = None;
     let mut secret_access_key = None;
@@ -197,8 +403,8 @@ pub(crate) fn parse_credential_process_json_credentials(
         None => return Err(InvalidJsonCredentials::MissingField("Version")),
         Some(version) => {
             return Err(InvalidJsonCredentials::InvalidField {
-                field: "version",
-                err: format!("unknown version number: {}", version).into(),
+                field: "Version",
+                err: format!("unknown version number: {}, expected 1", version).into(),
             })
         }
     }
@@ -206,21 +412,38 @@ pub(crate) fn parse_credential_process_json_credentials(
     let access_key_id = access_key_id.ok_or(InvalidJsonCredentials::MissingField("AccessKeyId"))?;
     let secret_access_key =
         secret_access_key.ok_or(InvalidJsonCredentials::MissingField("SecretAccessKey"))?;
-    let session_token = session_token.ok_or(InvalidJsonCredentials::MissingField("Token"))?;
-    let expiration = expiration.ok_or(InvalidJsonCredentials::MissingField("Expiration"))?;
-    let expiration =
-        SystemTime::try_from(OffsetDateTime::parse(&expiration, &Rfc3339).map_err(|err| {
-            InvalidJsonCredentials::InvalidField {
-                field: "Expiration",
-                err: err.into(),
-            }
-        })?)
-        .map_err(|_| {
-            InvalidJsonCredentials::Other(
-                "credential expiration time cannot be represented by a DateTime".into(),
-            )
-        })?;
-    Ok(RefreshableCredentials {
+    for (field, value) in [
+        ("AccessKeyId", &access_key_id),
+        ("SecretAccessKey", &secret_access_key),
+    ] {
+        if value.is_empty() {
+            return Err(InvalidJsonCredentials::InvalidField {
+                field,
+                err: "the value must not be empty".into(),
+            });
+        }
+    }
+    let session_token = session_token.filter(|token| !token.is_empty());
+    let expiration = expiration
+        .map(|expiration| {
+            let expiration = OffsetDateTime::parse(&expiration, &Rfc3339).map_err(|err| {
+                InvalidJsonCredentials::InvalidField {
+                    field: "Expiration",
+                    err: format!(
+                        "`{}` is not an RFC 3339 date time, like `2022-05-26T12:34:56.789Z`: {}",
+                        expiration, err
+                    )
+                    .into(),
+                }
+            })?;
+            SystemTime::try_from(expiration).map_err(|_| {
+                InvalidJsonCredentials::Other(
+                    "credential expiration time cannot be represented by a DateTime".into(),
+                )
+            })
+        })
+        .transpose()?;
+    Ok(ProcessCredentials {
         access_key_id,
         secret_access_key,
         session_token,
@@ -230,7 +453,10 @@ pub(crate) fn parse_credential_process_json_credentials(
 
 #[cfg(test)]
 mod test {
-    use crate::credential_process::CredentialProcessProvider;
+    use crate::credential_process::{
+        parse_credential_process_json_credentials, CredentialProcessProvider,
+    };
+    use aws_credential_types::provider::error::CredentialsError;
     use aws_credential_types::provider::ProvideCredentials;
     use std::time::{Duration, SystemTime};
     use time::format_description::well_known::Rfc3339;
@@ -265,4 +491,66 @@ mod test {
             .await
             .expect_err("timeout forced");
     }
+
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn builder_runs_program_with_args_and_env() {
+        let provider = CredentialProcessProvider::builder()
+            .program("sh")
+            .args([
+                "-c",
+                r#"echo "{ \"Version\": 1, \"AccessKeyId\": \"$KEY_ID\", \"SecretAccessKey\": \"$1\" }""#,
+                "sh",
+                "TESTSECRETKEY",
+            ])
+            .env("KEY_ID", "ASIARTESTID")
+            .timeout(Duration::from_secs(5))
+            .build();
+        let creds = provider.provide_credentials().await.expect("valid creds");
+        assert_eq!(creds.access_key_id(), "ASIARTESTID");
+        assert_eq!(creds.secret_access_key(), "TESTSECRETKEY");
+        assert_eq!(creds.session_token(), None);
+        assert_eq!(creds.expiry(), None);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn builder_timeout() {
+        let provider = CredentialProcessProvider::builder()
+            .program("sleep")
+            .arg("1000")
+            .timeout(Duration::from_millis(10))
+            .build();
+        let err = provider.provide_credentials().await.expect_err("timed out");
+        assert!(
+            matches!(err, CredentialsError::ProviderTimedOut(_)),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn output_validation() {
+        let err = |output: &str| {
+            parse_credential_process_json_credentials(output)
+                .expect_err("invalid")
+                .to_string()
+        };
+        assert_eq!(
+            "Invalid field in response: `AccessKeyId`. the value must not be empty",
+            err(r#"{ "Version": 1, "AccessKeyId": "", "SecretAccessKey": "secret" }"#)
+        );
+        assert_eq!(
+            "Expected field `SecretAccessKey` in response but it was missing",
+            err(r#"{ "Version": 1, "AccessKeyId": "akid" }"#)
+        );
+        assert_eq!(
+            "Invalid field in response: `Version`. unknown version number: 2, expected 1",
+            err(r#"{ "Version": 2, "AccessKeyId": "akid", "SecretAccessKey": "secret" }"#)
+        );
+        assert!(err(
+            r#"{ "Version": 1, "AccessKeyId": "akid", "SecretAccessKey": "secret", "Expiration": "tomorrow" }"#
+        )
+        .contains("`tomorrow` is not an RFC 3339 date time"));
+    }
 }
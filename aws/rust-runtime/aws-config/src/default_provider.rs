@@ -47,3 +47,6 @@ pub mod use_fips;
 
 /// Default dual-stack provider chain
 pub mod use_dual_stack;
+
+/// Default account ID endpoint mode provider chain
+pub mod account_id_endpoint_mode;
@@ -0,0 +1,111 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::provider_config::ProviderConfig;
+use crate::standard_property::StandardProperty;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_types::endpoint_config::AccountIdEndpointMode;
+use std::str::FromStr;
+
+mod env {
+    pub(super) const ACCOUNT_ID_ENDPOINT_MODE: &str = "AWS_ACCOUNT_ID_ENDPOINT_MODE";
+}
+
+mod profile_key {
+    pub(super) const ACCOUNT_ID_ENDPOINT_MODE: &str = "account_id_endpoint_mode";
+}
+
+/// Load the value for "account ID endpoint mode"
+///
+/// This checks the following sources:
+/// 1. The environment variable `AWS_ACCOUNT_ID_ENDPOINT_MODE=preferred/disabled/required`
+/// 2. The profile key `account_id_endpoint_mode=preferred/disabled/required`
+///
+/// If invalid values are found, the provider will return None and an error will be logged.
+pub async fn account_id_endpoint_mode_provider(
+    provider_config: &ProviderConfig,
+) -> Option<AccountIdEndpointMode> {
+    StandardProperty::new()
+        .env(env::ACCOUNT_ID_ENDPOINT_MODE)
+        .profile(profile_key::ACCOUNT_ID_ENDPOINT_MODE)
+        .validate(provider_config, AccountIdEndpointMode::from_str)
+        .await
+        .map_err(
+            |err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for account ID endpoint mode setting"),
+        )
+        .unwrap_or(None)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::default_provider::account_id_endpoint_mode::account_id_endpoint_mode_provider;
+    use crate::profile::profile_file::{ProfileFileKind, ProfileFiles};
+    use crate::provider_config::ProviderConfig;
+    use aws_types::endpoint_config::AccountIdEndpointMode;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_value() {
+        let conf = ProviderConfig::empty().with_env(Env::from_slice(&[(
+            "AWS_ACCOUNT_ID_ENDPOINT_MODE",
+            "sometimes",
+        )]));
+        assert_eq!(account_id_endpoint_mode_provider(&conf).await, None);
+        assert!(logs_contain(
+            "invalid value for account ID endpoint mode setting"
+        ));
+        assert!(logs_contain("AWS_ACCOUNT_ID_ENDPOINT_MODE"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn environment_priority() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[(
+                "AWS_ACCOUNT_ID_ENDPOINT_MODE",
+                "required",
+            )]))
+            .with_profile_config(
+                Some(
+                    ProfileFiles::builder()
+                        .with_file(ProfileFileKind::Config, "conf")
+                        .build(),
+                ),
+                None,
+            )
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\naccount_id_endpoint_mode = disabled",
+            )]));
+        assert_eq!(
+            account_id_endpoint_mode_provider(&conf).await,
+            Some(AccountIdEndpointMode::Required)
+        );
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn load_from_profile() {
+        let conf = ProviderConfig::empty()
+            .with_profile_config(
+                Some(
+                    ProfileFiles::builder()
+                        .with_file(ProfileFileKind::Config, "conf")
+                        .build(),
+                ),
+                None,
+            )
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\naccount_id_endpoint_mode = disabled",
+            )]));
+        assert_eq!(
+            account_id_endpoint_mode_provider(&conf).await,
+            Some(AccountIdEndpointMode::Disabled)
+        );
+    }
+}
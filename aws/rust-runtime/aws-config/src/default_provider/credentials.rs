@@ -10,7 +10,7 @@ use aws_credential_types::Credentials;
 use tracing::Instrument;
 
 use crate::environment::credentials::EnvironmentVariableCredentialsProvider;
-use crate::meta::credentials::CredentialsProviderChain;
+use crate::meta::credentials::{CredentialsDiagnostics, CredentialsProviderChain};
 use crate::meta::region::ProvideRegion;
 use crate::provider_config::ProviderConfig;
 
@@ -69,6 +69,11 @@ impl DefaultCredentialsChain {
         Builder::default()
     }
 
+    /// Returns a handle for finding out which provider in the chain supplied credentials, and when.
+    pub fn diagnostics(&self) -> CredentialsDiagnostics {
+        self.provider_chain.diagnostics()
+    }
+
     async fn credentials(&self) -> provider::Result {
         self.provider_chain
             .provide_credentials()
@@ -15,6 +15,7 @@ use aws_types::os_shim_internal::Env;
 /// - `AWS_ACCESS_KEY_ID`
 /// - `AWS_SECRET_ACCESS_KEY` with fallback to `SECRET_ACCESS_KEY`
 /// - `AWS_SESSION_TOKEN`
+/// - `AWS_ACCOUNT_ID`
 #[derive(Debug, Clone)]
 pub struct EnvironmentVariableCredentialsProvider {
     env: Env,
@@ -42,13 +43,17 @@ impl EnvironmentVariableCredentialsProvider {
                     s if s.is_empty() => None,
                     s => Some(s.to_string()),
                 });
-        Ok(Credentials::new(
-            access_key,
-            secret_key,
-            session_token,
-            None,
-            ENV_PROVIDER,
-        ))
+        let account_id = self
+            .env
+            .get("AWS_ACCOUNT_ID")
+            .ok()
+            .filter(|account_id| !account_id.trim().is_empty());
+        let credentials =
+            Credentials::new(access_key, secret_key, session_token, None, ENV_PROVIDER);
+        Ok(match account_id {
+            Some(account_id) => credentials.with_account_id(account_id),
+            None => credentials,
+        })
     }
 }
 
@@ -145,6 +150,23 @@ mod test {
         assert_eq!(creds.session_token().unwrap(), "token");
         assert_eq!(creds.access_key_id(), "access");
         assert_eq!(creds.secret_access_key(), "secret");
+        assert_eq!(creds.account_id(), None);
+    }
+
+    #[test]
+    fn valid_with_account_id() {
+        let provider = make_provider(&[
+            ("AWS_ACCESS_KEY_ID", "access"),
+            ("AWS_SECRET_ACCESS_KEY", "secret"),
+            ("AWS_ACCOUNT_ID", "123456789012"),
+        ]);
+
+        let creds = provider
+            .provide_credentials()
+            .now_or_never()
+            .unwrap()
+            .expect("valid credentials");
+        assert_eq!(creds.account_id(), Some("123456789012"));
     }
 
     #[test]
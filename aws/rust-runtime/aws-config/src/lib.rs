@@ -156,6 +156,7 @@ pub async fn load_from_env() -> aws_types::SdkConfig {
 }
 
 mod loader {
+    use crate::default_provider::account_id_endpoint_mode::account_id_endpoint_mode_provider;
     use crate::default_provider::use_dual_stack::use_dual_stack_provider;
     use crate::default_provider::use_fips::use_fips_provider;
     use crate::default_provider::{app_name, credentials, region, retry_config, timeout_config};
@@ -172,8 +173,10 @@ mod loader {
     use aws_smithy_types::timeout::TimeoutConfig;
     use aws_types::app_name::AppName;
     use aws_types::docs_for;
+    use aws_types::endpoint_config::AccountIdEndpointMode;
     use aws_types::os_shim_internal::{Env, Fs};
     use aws_types::sdk_config::SharedHttpClient;
+    use aws_types::user_agent::UserAgentMetadata;
     use aws_types::SdkConfig;
 
     #[derive(Default, Debug)]
@@ -209,6 +212,8 @@ mod loader {
         profile_files_override: Option<ProfileFiles>,
         use_fips: Option<bool>,
         use_dual_stack: Option<bool>,
+        account_id_endpoint_mode: Option<AccountIdEndpointMode>,
+        user_agent_metadata: Option<UserAgentMetadata>,
         time_source: Option<SharedTimeSource>,
         env: Option<Env>,
         fs: Option<Fs>,
@@ -450,6 +455,29 @@ mod loader {
             self
         }
 
+        /// Override the user agent metadata used to build [`SdkConfig`](aws_types::SdkConfig).
+        ///
+        /// The product tokens and feature flags are appended to the user agent that gets sent
+        /// along with requests, so that frameworks built on the SDK can tag their traffic.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// use aws_types::user_agent::{ProductToken, UserAgentMetadata};
+        /// let metadata = UserAgentMetadata::builder()
+        ///     .product(ProductToken::new("my-platform").with_version("2.1.0"))
+        ///     .build()
+        ///     .expect("valid user agent metadata");
+        /// let config = aws_config::from_env()
+        ///     .user_agent_metadata(metadata)
+        ///     .load().await;
+        /// # }
+        /// ```
+        pub fn user_agent_metadata(mut self, user_agent_metadata: UserAgentMetadata) -> Self {
+            self.user_agent_metadata = Some(user_agent_metadata);
+            self
+        }
+
         /// Provides the ability to programmatically override the profile files that get loaded by the SDK.
         ///
         /// The [`Default`] for `ProfileFiles` includes the default SDK config and credential files located in
@@ -555,6 +583,15 @@ mod loader {
             self
         }
 
+        #[doc = docs_for!(account_id_endpoint_mode)]
+        pub fn account_id_endpoint_mode(
+            mut self,
+            account_id_endpoint_mode: AccountIdEndpointMode,
+        ) -> Self {
+            self.account_id_endpoint_mode = Some(account_id_endpoint_mode);
+            self
+        }
+
         /// Set configuration for all sub-loaders (credentials, region etc.)
         ///
         /// Update the `ProviderConfig` used for all nested loaders. This can be used to override
@@ -634,6 +671,13 @@ mod loader {
                 use_dual_stack_provider(&conf).await
             };
 
+            let account_id_endpoint_mode =
+                if let Some(account_id_endpoint_mode) = self.account_id_endpoint_mode {
+                    Some(account_id_endpoint_mode)
+                } else {
+                    account_id_endpoint_mode_provider(&conf).await
+                };
+
             let conf = conf
                 .with_use_fips(use_fips)
                 .with_use_dual_stack(use_dual_stack);
@@ -700,6 +744,8 @@ mod loader {
             builder.set_endpoint_url(self.endpoint_url);
             builder.set_use_fips(use_fips);
             builder.set_use_dual_stack(use_dual_stack);
+            builder.set_account_id_endpoint_mode(account_id_endpoint_mode);
+            builder.set_user_agent_metadata(self.user_agent_metadata);
             builder.build()
         }
     }
@@ -726,6 +772,7 @@ mod loader {
         use aws_smithy_async::rt::sleep::TokioSleep;
         use aws_smithy_runtime::client::http::test_util::{infallible_client_fn, NeverClient};
         use aws_types::app_name::AppName;
+        use aws_types::endpoint_config::AccountIdEndpointMode;
         use aws_types::os_shim_internal::{Env, Fs};
         use std::sync::atomic::{AtomicUsize, Ordering};
         use std::sync::Arc;
@@ -806,6 +853,30 @@ mod loader {
             assert_eq!(None, conf.use_dual_stack());
         }
 
+        #[tokio::test]
+        async fn load_account_id_endpoint_mode() {
+            let conf = base_conf()
+                .account_id_endpoint_mode(AccountIdEndpointMode::Disabled)
+                .load()
+                .await;
+            assert_eq!(
+                Some(AccountIdEndpointMode::Disabled),
+                conf.account_id_endpoint_mode()
+            );
+
+            let conf = base_conf()
+                .env(Env::from_slice(&[(
+                    "AWS_ACCOUNT_ID_ENDPOINT_MODE",
+                    "required",
+                )]))
+                .load()
+                .await;
+            assert_eq!(
+                Some(AccountIdEndpointMode::Required),
+                conf.account_id_endpoint_mode()
+            );
+        }
+
         #[tokio::test]
         async fn app_name() {
             let app_name = AppName::new("my-app-name").unwrap();
@@ -3,14 +3,27 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::provider_config::ProviderConfig;
 use aws_credential_types::{
     provider::{self, error::CredentialsError, future, ProvideCredentials},
     Credentials,
 };
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_runtime::expiring_cache::ExpiringCache;
 use aws_smithy_types::error::display::DisplayErrorContext;
 use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use tracing::Instrument;
 
+/// Expiration of cached credentials that don't have an expiration time
+const DEFAULT_CACHE_EXPIRATION: Duration = Duration::from_secs(15 * 60);
+
+/// Amount of time before cached credentials expire when they're loaded again
+const CACHE_BUFFER_TIME: Duration = Duration::from_secs(10);
+
 /// Credentials provider that checks a series of inner providers
 ///
 /// Each provider will be evaluated in order:
@@ -33,9 +46,36 @@ use tracing::Instrument;
 ///     .or_else("Profile", ProfileFileCredentialsProvider::builder().build());
 /// # }
 /// ```
+///
+/// Use the [builder](CredentialsProviderChain::builder) to give providers in the chain a timeout,
+/// or to cache their credentials:
+///
+/// ```no_run
+/// # fn example() {
+/// use aws_config::meta::credentials::{CredentialsProviderChain, ProviderOptions};
+/// use aws_config::environment::credentials::EnvironmentVariableCredentialsProvider;
+/// use aws_config::imds::credentials::ImdsCredentialsProvider;
+/// use std::time::Duration;
+///
+/// let provider = CredentialsProviderChain::builder()
+///     .provider("Environment", EnvironmentVariableCredentialsProvider::new())
+///     .provider_with_options(
+///         "Ec2InstanceMetadata",
+///         ImdsCredentialsProvider::builder().build(),
+///         ProviderOptions::new().timeout(Duration::from_secs(2)).cache(true),
+///     )
+///     .build();
+/// # }
+/// ```
+///
+/// Use [`diagnostics`](CredentialsProviderChain::diagnostics) to find out which provider supplied
+/// the credentials that are in use, and when they expire.
 #[derive(Debug)]
 pub struct CredentialsProviderChain {
-    providers: Vec<(Cow<'static, str>, Box<dyn ProvideCredentials>)>,
+    providers: Vec<ChainedProvider>,
+    time_source: SharedTimeSource,
+    sleep_impl: Option<SharedAsyncSleep>,
+    diagnostics: CredentialsDiagnostics,
 }
 
 impl CredentialsProviderChain {
@@ -45,7 +85,14 @@ impl CredentialsProviderChain {
         provider: impl ProvideCredentials + 'static,
     ) -> Self {
         CredentialsProviderChain {
-            providers: vec![(name.into(), Box::new(provider))],
+            providers: vec![ChainedProvider::new(
+                name.into(),
+                Box::new(provider),
+                ProviderOptions::new(),
+            )],
+            time_source: SharedTimeSource::default(),
+            sleep_impl: default_async_sleep(),
+            diagnostics: CredentialsDiagnostics::default(),
         }
     }
 
@@ -55,10 +102,26 @@ impl CredentialsProviderChain {
         name: impl Into<Cow<'static, str>>,
         provider: impl ProvideCredentials + 'static,
     ) -> Self {
-        self.providers.push((name.into(), Box::new(provider)));
+        self.providers.push(ChainedProvider::new(
+            name.into(),
+            Box::new(provider),
+            ProviderOptions::new(),
+        ));
         self
     }
 
+    /// Returns a handle for inspecting how this chain resolved credentials.
+    ///
+    /// The handle stays up to date after the chain is moved into a client's config.
+    pub fn diagnostics(&self) -> CredentialsDiagnostics {
+        self.diagnostics.clone()
+    }
+
+    /// Creates a builder to assemble a credentials provider chain with per-provider options.
+    pub fn builder() -> CredentialsProviderChainBuilder {
+        CredentialsProviderChainBuilder::default()
+    }
+
     /// Add a fallback to the default provider chain
     #[cfg(feature = "rustls")]
     pub async fn or_default_provider(self) -> Self {
@@ -78,15 +141,35 @@ impl CredentialsProviderChain {
     }
 
     async fn credentials(&self) -> provider::Result {
-        for (name, provider) in &self.providers {
+        let mut skipped = Vec::new();
+        for provider in &self.providers {
+            let name = &provider.name;
             let span = tracing::debug_span!("load_credentials", provider = %name);
-            match provider.provide_credentials().instrument(span).await {
+            match provider
+                .provide_credentials(&self.time_source, self.sleep_impl.as_ref())
+                .instrument(span)
+                .await
+            {
                 Ok(credentials) => {
-                    tracing::debug!(provider = %name, "loaded credentials");
+                    let resolution = CredentialsResolution {
+                        provider: name.clone(),
+                        resolved_at: self.time_source.now(),
+                        expiry: credentials.expiry(),
+                        skipped,
+                    };
+                    tracing::debug!(
+                        provider = %name,
+                        expiry = ?resolution.expiry,
+                        ttl = ?resolution.ttl(),
+                        skipped = ?resolution.skipped,
+                        "loaded credentials"
+                    );
+                    *self.diagnostics.last_resolution.lock().unwrap() = Some(resolution);
                     return Ok(credentials);
                 }
                 Err(err @ CredentialsError::CredentialsNotLoaded(_)) => {
                     tracing::debug!(provider = %name, context = %DisplayErrorContext(&err), "provider in chain did not provide credentials");
+                    skipped.push(name.clone());
                 }
                 Err(err) => {
                     tracing::warn!(provider = %name, error = %DisplayErrorContext(&err), "provider failed to provide credentials");
@@ -109,8 +192,8 @@ impl ProvideCredentials for CredentialsProviderChain {
     }
 
     fn fallback_on_interrupt(&self) -> Option<Credentials> {
-        for (_, provider) in &self.providers {
-            match provider.fallback_on_interrupt() {
+        for provider in &self.providers {
+            match provider.provider.fallback_on_interrupt() {
                 creds @ Some(_) => return creds,
                 None => {}
             }
@@ -119,8 +202,243 @@ impl ProvideCredentials for CredentialsProviderChain {
     }
 }
 
+/// A handle for inspecting how a [`CredentialsProviderChain`] resolved credentials.
+///
+/// # Examples
+/// ```no_run
+/// # async fn example() {
+/// use aws_config::default_provider::credentials::DefaultCredentialsChain;
+///
+/// let provider = DefaultCredentialsChain::builder().build().await;
+/// let diagnostics = provider.diagnostics();
+/// let config = aws_config::from_env().credentials_provider(provider).load().await;
+/// // ...
+/// if let Some(resolution) = diagnostics.last_resolution() {
+///     println!(
+///         "credentials from {} were resolved at {:?} and expire in {:?}",
+///         resolution.provider(),
+///         resolution.resolved_at(),
+///         resolution.ttl(),
+///     );
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CredentialsDiagnostics {
+    last_resolution: Arc<Mutex<Option<CredentialsResolution>>>,
+}
+
+impl CredentialsDiagnostics {
+    /// Returns the last time that the chain resolved credentials, or `None` if it hasn't yet.
+    pub fn last_resolution(&self) -> Option<CredentialsResolution> {
+        self.last_resolution.lock().unwrap().clone()
+    }
+}
+
+/// Which provider in a [`CredentialsProviderChain`] supplied credentials, and when
+#[derive(Clone, Debug)]
+pub struct CredentialsResolution {
+    provider: Cow<'static, str>,
+    resolved_at: SystemTime,
+    expiry: Option<SystemTime>,
+    skipped: Vec<Cow<'static, str>>,
+}
+
+impl CredentialsResolution {
+    /// Returns the name of the provider that supplied the credentials.
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Returns when the chain resolved the credentials.
+    ///
+    /// This is when the provider was last asked for credentials. Providers that
+    /// [cache](ProviderOptions::cache) their credentials may have loaded them earlier.
+    pub fn resolved_at(&self) -> SystemTime {
+        self.resolved_at
+    }
+
+    /// Returns when the credentials expire, if they expire.
+    pub fn expiry(&self) -> Option<SystemTime> {
+        self.expiry
+    }
+
+    /// Returns how long the credentials were valid for when they were resolved, if they expire.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.expiry
+            .map(|expiry| expiry.duration_since(self.resolved_at).unwrap_or_default())
+    }
+
+    /// Returns the names of the providers earlier in the chain that didn't provide credentials.
+    pub fn skipped_providers(&self) -> impl Iterator<Item = &str> {
+        self.skipped.iter().map(|name| name.as_ref())
+    }
+}
+
+/// Options for a provider in a [`CredentialsProviderChain`].
+#[derive(Clone, Debug, Default)]
+pub struct ProviderOptions {
+    timeout: Option<Duration>,
+    cache: bool,
+}
+
+impl ProviderOptions {
+    /// Creates options for a provider that has no timeout and isn't cached.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long the provider may take to provide credentials.
+    ///
+    /// If the provider takes longer, its [fallback credentials](ProvideCredentials::fallback_on_interrupt)
+    /// are used if it has any. Otherwise, the chain moves on to the next provider.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether the provider's credentials are cached until shortly before they expire.
+    ///
+    /// Credentials that don't have an expiration time are cached for 15 minutes. Clients already
+    /// cache the credentials that they resolve, so this is mostly useful for providers that are
+    /// slow or rate limited, and that are shared by several clients without a shared identity cache.
+    pub fn cache(mut self, cache: bool) -> Self {
+        self.cache = cache;
+        self
+    }
+}
+
+/// Builder for [`CredentialsProviderChain`].
+///
+/// Providers are evaluated in the order that they're added in.
+#[derive(Debug, Default)]
+pub struct CredentialsProviderChainBuilder {
+    providers: Vec<ChainedProvider>,
+    provider_config: Option<ProviderConfig>,
+}
+
+impl CredentialsProviderChainBuilder {
+    /// Adds a provider to the end of the chain.
+    pub fn provider(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        provider: impl ProvideCredentials + 'static,
+    ) -> Self {
+        self.provider_with_options(name, provider, ProviderOptions::new())
+    }
+
+    /// Adds a provider to the end of the chain with the given options.
+    pub fn provider_with_options(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        provider: impl ProvideCredentials + 'static,
+        options: ProviderOptions,
+    ) -> Self {
+        self.providers.push(ChainedProvider::new(
+            name.into(),
+            Box::new(provider),
+            options,
+        ));
+        self
+    }
+
+    /// Override the configuration used for provider timeouts and caching.
+    ///
+    /// The time source and sleep implementation are taken from the provider config.
+    pub fn configure(mut self, provider_config: &ProviderConfig) -> Self {
+        self.provider_config = Some(provider_config.clone());
+        self
+    }
+
+    /// Builds the credentials provider chain.
+    pub fn build(self) -> CredentialsProviderChain {
+        let provider_config = self.provider_config.unwrap_or_default();
+        CredentialsProviderChain {
+            providers: self.providers,
+            time_source: provider_config.time_source(),
+            sleep_impl: provider_config.sleep_impl(),
+            diagnostics: CredentialsDiagnostics::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ChainedProvider {
+    name: Cow<'static, str>,
+    provider: Box<dyn ProvideCredentials>,
+    timeout: Option<Duration>,
+    cache: Option<ExpiringCache<Credentials, CredentialsError>>,
+}
+
+impl ChainedProvider {
+    fn new(
+        name: Cow<'static, str>,
+        provider: Box<dyn ProvideCredentials>,
+        options: ProviderOptions,
+    ) -> Self {
+        Self {
+            name,
+            provider,
+            timeout: options.timeout,
+            cache: options.cache.then(|| ExpiringCache::new(CACHE_BUFFER_TIME)),
+        }
+    }
+
+    async fn provide_credentials(
+        &self,
+        time_source: &SharedTimeSource,
+        sleep_impl: Option<&SharedAsyncSleep>,
+    ) -> provider::Result {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.load(sleep_impl).await,
+        };
+        let now = time_source.now();
+        if let Some(credentials) = cache.yield_or_clear_if_expired(now).await {
+            tracing::debug!("loaded credentials from the provider's cache");
+            return Ok(credentials);
+        }
+        cache
+            .get_or_load(|| async move {
+                let credentials = self.load(sleep_impl).await?;
+                let expiration = credentials
+                    .expiry()
+                    .unwrap_or(now + DEFAULT_CACHE_EXPIRATION);
+                Ok((credentials, expiration))
+            })
+            .await
+    }
+
+    async fn load(&self, sleep_impl: Option<&SharedAsyncSleep>) -> provider::Result {
+        let (timeout, sleep_impl) = match (self.timeout, sleep_impl) {
+            (Some(timeout), Some(sleep_impl)) => (timeout, sleep_impl),
+            (Some(_), None) => {
+                tracing::warn!(provider = %self.name, "the provider's timeout is ignored because no sleep implementation is configured");
+                return self.provider.provide_credentials().await;
+            }
+            (None, _) => return self.provider.provide_credentials().await,
+        };
+        match Timeout::new(
+            self.provider.provide_credentials(),
+            sleep_impl.sleep(timeout),
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(_timed_out) => match self.provider.fallback_on_interrupt() {
+                Some(credentials) => Ok(credentials),
+                None => Err(CredentialsError::not_loaded(
+                    CredentialsError::provider_timed_out(timeout),
+                )),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
     use std::time::Duration;
 
     use aws_credential_types::{
@@ -130,7 +448,8 @@ mod tests {
     };
     use aws_smithy_async::future::timeout::Timeout;
 
-    use crate::meta::credentials::CredentialsProviderChain;
+    use crate::meta::credentials::{CredentialsProviderChain, ProviderOptions};
+    use crate::provider_config::ProviderConfig;
 
     #[derive(Debug)]
     struct FallbackCredentials(Credentials);
@@ -216,4 +535,91 @@ mod tests {
             },
         };
     }
+
+    #[tokio::test]
+    async fn timed_out_provider_moves_on_to_the_next_provider() {
+        let chain = CredentialsProviderChain::builder()
+            .provider_with_options(
+                "slow",
+                provide_credentials_fn(|| async {
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                    Ok(Credentials::for_tests_with_session_token())
+                }),
+                ProviderOptions::new().timeout(Duration::from_millis(50)),
+            )
+            .provider("fast", Credentials::for_tests())
+            .build();
+
+        assert_eq!(
+            Credentials::for_tests(),
+            chain.provide_credentials().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn diagnostics_record_the_provider_that_supplied_credentials() {
+        use aws_smithy_async::test_util::ManualTimeSource;
+        use aws_smithy_async::time::SharedTimeSource;
+        use std::time::UNIX_EPOCH;
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let expiry = now + Duration::from_secs(3600);
+        let chain = CredentialsProviderChain::builder()
+            .provider(
+                "empty",
+                provide_credentials_fn(|| async {
+                    Err(CredentialsError::not_loaded("no credentials here"))
+                }),
+            )
+            .provider(
+                "expiring",
+                Credentials::new("akid", "secret", None, Some(expiry), "test"),
+            )
+            .configure(
+                &ProviderConfig::no_configuration()
+                    .with_time_source(SharedTimeSource::new(ManualTimeSource::new(now))),
+            )
+            .build();
+        let diagnostics = chain.diagnostics();
+        assert!(diagnostics.last_resolution().is_none());
+
+        chain.provide_credentials().await.unwrap();
+        let resolution = diagnostics
+            .last_resolution()
+            .expect("credentials were resolved");
+        assert_eq!("expiring", resolution.provider());
+        assert_eq!(now, resolution.resolved_at());
+        assert_eq!(Some(expiry), resolution.expiry());
+        assert_eq!(Some(Duration::from_secs(3600)), resolution.ttl());
+        assert_eq!(
+            vec!["empty"],
+            resolution.skipped_providers().collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn cached_provider_is_only_called_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let chain = CredentialsProviderChain::builder()
+            .provider_with_options(
+                "cached",
+                provide_credentials_fn({
+                    let calls = calls.clone();
+                    move || {
+                        let calls = calls.clone();
+                        async move {
+                            calls.fetch_add(1, Ordering::Relaxed);
+                            Ok(Credentials::for_tests())
+                        }
+                    }
+                }),
+                ProviderOptions::new().cache(true),
+            )
+            .build();
+
+        for _ in 0..3 {
+            chain.provide_credentials().await.unwrap();
+        }
+        assert_eq!(1, calls.load(Ordering::Relaxed));
+    }
 }
@@ -6,4 +6,7 @@
 //! Credential providers that augment an existing credentials providers to add functionality
 
 mod chain;
-pub use chain::CredentialsProviderChain;
+pub use chain::{
+    CredentialsDiagnostics, CredentialsProviderChain, CredentialsProviderChainBuilder,
+    CredentialsResolution, ProviderOptions,
+};
@@ -226,6 +226,7 @@ mod static_credentials {
     pub(super) const AWS_ACCESS_KEY_ID: &str = "aws_access_key_id";
     pub(super) const AWS_SECRET_ACCESS_KEY: &str = "aws_secret_access_key";
     pub(super) const AWS_SESSION_TOKEN: &str = "aws_session_token";
+    pub(super) const AWS_ACCOUNT_ID: &str = "aws_account_id";
 }
 
 mod credential_process {
@@ -378,13 +379,17 @@ fn static_creds_from_profile(profile: &Profile) -> Result<Credentials, ProfileFi
         message: "profile missing aws_secret_access_key".into(),
     })?;
     // There might not be an active session token so we don't error out if it's missing
-    Ok(Credentials::new(
+    let credentials = Credentials::new(
         access_key,
         secret_key,
         session_token.map(|s| s.to_string()),
         None,
         PROVIDER_NAME,
-    ))
+    );
+    Ok(match profile.get(AWS_ACCOUNT_ID) {
+        Some(account_id) => credentials.with_account_id(account_id),
+        None => credentials,
+    })
 }
 
 /// Load credentials from `credential_process`
@@ -4,6 +4,7 @@
  */
 
 use crate::fs_util::{home_dir, Os};
+use aws_credential_types::secret::SecretString;
 use aws_smithy_json::deserialize::token::skip_value;
 use aws_smithy_json::deserialize::Token;
 use aws_smithy_json::deserialize::{json_token_iter, EscapeError};
@@ -20,13 +21,13 @@ use std::time::SystemTime;
 use zeroize::Zeroizing;
 
 #[cfg_attr(test, derive(Eq, PartialEq))]
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub(super) struct CachedSsoToken {
-    pub(super) access_token: Zeroizing<String>,
+    pub(super) access_token: SecretString,
     pub(super) client_id: Option<String>,
-    pub(super) client_secret: Option<Zeroizing<String>>,
+    pub(super) client_secret: Option<SecretString>,
     pub(super) expires_at: SystemTime,
-    pub(super) refresh_token: Option<Zeroizing<String>>,
+    pub(super) refresh_token: Option<SecretString>,
     pub(super) region: Option<String>,
     pub(super) registration_expires_at: Option<SystemTime>,
     pub(super) start_url: Option<String>,
@@ -44,21 +45,6 @@ impl CachedSsoToken {
     }
 }
 
-impl fmt::Debug for CachedSsoToken {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("CachedSsoToken")
-            .field("access_token", &"** redacted **")
-            .field("client_id", &self.client_id)
-            .field("client_secret", &"** redacted **")
-            .field("expires_at", &self.expires_at)
-            .field("refresh_token", &"** redacted **")
-            .field("region", &self.region)
-            .field("registration_expires_at", &self.registration_expires_at)
-            .field("start_url", &self.start_url)
-            .finish()
-    }
-}
-
 #[derive(Debug)]
 pub(super) enum CachedSsoTokenError {
     FailedToFormatDateTime {
@@ -198,7 +184,7 @@ fn parse_cached_token(
             "startUrl": "https://d-abc123.awsapps.com/start"
             */
             (key, Token::ValueString { value, .. }) if key.eq_ignore_ascii_case("accessToken") => {
-                access_token = Some(Zeroizing::new(value.to_unescaped()?.into_owned()));
+                access_token = Some(SecretString::new(value.to_unescaped()?.into_owned()));
             }
             (key, Token::ValueString { value, .. }) if key.eq_ignore_ascii_case("expiresAt") => {
                 expires_at = Some(value.to_unescaped()?);
@@ -207,10 +193,10 @@ fn parse_cached_token(
                 client_id = Some(value.to_unescaped()?);
             }
             (key, Token::ValueString { value, .. }) if key.eq_ignore_ascii_case("clientSecret") => {
-                client_secret = Some(Zeroizing::new(value.to_unescaped()?.into_owned()));
+                client_secret = Some(SecretString::new(value.to_unescaped()?.into_owned()));
             }
             (key, Token::ValueString { value, .. }) if key.eq_ignore_ascii_case("refreshToken") => {
-                refresh_token = Some(Zeroizing::new(value.to_unescaped()?.into_owned()));
+                refresh_token = Some(SecretString::new(value.to_unescaped()?.into_owned()));
             }
             (key, Token::ValueString { value, .. }) if key.eq_ignore_ascii_case("region") => {
                 region = Some(value.to_unescaped()?.into_owned());
@@ -321,16 +307,22 @@ pub(super) async fn save_cached_token(
 
     let mut out = Zeroizing::new(String::new());
     let mut writer = JsonObjectWriter::new(&mut out);
-    writer.key("accessToken").string(&token.access_token);
+    writer
+        .key("accessToken")
+        .string(token.access_token.expose_secret());
     writer.key("expiresAt").string(&expires_at);
     if let Some(refresh_token) = &token.refresh_token {
-        writer.key("refreshToken").string(refresh_token);
+        writer
+            .key("refreshToken")
+            .string(refresh_token.expose_secret());
     }
     if let Some(client_id) = &token.client_id {
         writer.key("clientId").string(client_id);
     }
     if let Some(client_secret) = &token.client_secret {
-        writer.key("clientSecret").string(client_secret);
+        writer
+            .key("clientSecret")
+            .string(client_secret.expose_secret());
     }
     if let Some(registration_expires_at) = registration_expires_at {
         writer
@@ -366,11 +358,11 @@ mod tests {
     #[test]
     fn redact_fields_in_token_debug() {
         let token = CachedSsoToken {
-            access_token: Zeroizing::new("!!SENSITIVE!!".into()),
+            access_token: SecretString::new("!!SENSITIVE!!"),
             client_id: Some("clientid".into()),
-            client_secret: Some(Zeroizing::new("!!SENSITIVE!!".into())),
+            client_secret: Some(SecretString::new("!!SENSITIVE!!")),
             expires_at: SystemTime::now(),
-            refresh_token: Some(Zeroizing::new("!!SENSITIVE!!".into())),
+            refresh_token: Some(SecretString::new("!!SENSITIVE!!")),
             region: Some("region".into()),
             registration_expires_at: Some(SystemTime::now()),
             start_url: Some("starturl".into()),
@@ -395,7 +387,7 @@ mod tests {
         }
         "#;
         let cached = parse_cached_token(file_contents.as_bytes()).expect("success");
-        assert_eq!("cachedtoken", cached.access_token.as_str());
+        assert_eq!("cachedtoken", cached.access_token.expose_secret());
         assert_eq!(
             SystemTime::UNIX_EPOCH + Duration::from_secs(1640467800),
             cached.expires_at
@@ -406,14 +398,14 @@ mod tests {
             cached
                 .client_secret
                 .expect("client secret is present")
-                .as_str()
+                .expose_secret()
         );
         assert_eq!(
             "cachedrefreshtoken",
             cached
                 .refresh_token
                 .expect("refresh token is present")
-                .as_str()
+                .expose_secret()
         );
         assert_eq!(
             SystemTime::UNIX_EPOCH + Duration::from_secs(1671975000),
@@ -438,7 +430,7 @@ mod tests {
         }
         "#;
         let cached = parse_cached_token(file_contents.as_bytes()).expect("success");
-        assert_eq!("cachedtoken", cached.access_token.as_str());
+        assert_eq!("cachedtoken", cached.access_token.expose_secret());
         assert_eq!(
             SystemTime::UNIX_EPOCH + Duration::from_secs(1640467800),
             cached.expires_at
@@ -539,11 +531,11 @@ mod tests {
         let expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(50_000_000);
         let reg_expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(100_000_000);
         let token = CachedSsoToken {
-            access_token: Zeroizing::new("access-token".into()),
+            access_token: SecretString::new("access-token"),
             client_id: Some("client-id".into()),
-            client_secret: Some(Zeroizing::new("client-secret".into())),
+            client_secret: Some(SecretString::new("client-secret")),
             expires_at,
-            refresh_token: Some(Zeroizing::new("refresh-token".into())),
+            refresh_token: Some(SecretString::new("refresh-token")),
             region: Some("region".into()),
             registration_expires_at: Some(reg_expires_at),
             start_url: Some("start-url".into()),
@@ -571,11 +563,11 @@ mod tests {
         let expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(50_000_000);
         let reg_expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(100_000_000);
         let original = CachedSsoToken {
-            access_token: Zeroizing::new("access-token".into()),
+            access_token: SecretString::new("access-token"),
             client_id: Some("client-id".into()),
-            client_secret: Some(Zeroizing::new("client-secret".into())),
+            client_secret: Some(SecretString::new("client-secret")),
             expires_at,
-            refresh_token: Some(Zeroizing::new("refresh-token".into())),
+            refresh_token: Some(SecretString::new("refresh-token")),
             region: Some("region".into()),
             registration_expires_at: Some(reg_expires_at),
             start_url: Some("start-url".into()),
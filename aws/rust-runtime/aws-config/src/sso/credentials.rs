@@ -260,7 +260,7 @@ async fn load_sso_credentials(
     let resp = client
         .get_role_credentials()
         .role_name(&sso_provider_config.role_name)
-        .access_token(&*token.access_token)
+        .access_token(token.access_token.expose_secret())
         .account_id(&sso_provider_config.account_id)
         .send()
         .await
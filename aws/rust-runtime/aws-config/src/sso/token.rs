@@ -14,6 +14,7 @@ use crate::identity::IdentityCache;
 use crate::sso::cache::{
     load_cached_token, save_cached_token, CachedSsoToken, CachedSsoTokenError,
 };
+use aws_credential_types::secret::SecretString;
 use aws_sdk_ssooidc::error::DisplayErrorContext;
 use aws_sdk_ssooidc::operation::create_token::CreateTokenOutput;
 use aws_sdk_ssooidc::Client as SsoOidcClient;
@@ -30,7 +31,6 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
-use zeroize::Zeroizing;
 
 const REFRESH_BUFFER_TIME: Duration = Duration::from_secs(5 * 60 /* 5 minutes */);
 const MIN_TIME_BETWEEN_REFRESH: Duration = Duration::from_secs(30);
@@ -38,9 +38,15 @@ const MIN_TIME_BETWEEN_REFRESH: Duration = Duration::from_secs(30);
 /// SSO Token Provider
 ///
 /// This token provider will use cached SSO tokens stored in `~/.aws/sso/cache/<hash>.json`.
-/// `<hash>` is computed based on the configured [`session_namej`](Builder::session_name).
+/// `<hash>` is computed based on the configured [`session_name`](Builder::session_name).
 ///
 /// If possible, the cached token will be refreshed when it gets close to expiring.
+/// A token is refreshable when the cache file has a refresh token and an unexpired client
+/// registration, which `aws sso login` stores for `sso-session` profiles. The token is refreshed
+/// with SSO OIDC within 5 minutes of its expiration, at most once every 30 seconds, and the
+/// refreshed token is written back to the cache file so that other tools use it too. If the
+/// refresh fails, the cached token is used until it expires, after which `aws sso login` must
+/// be run again.
 #[derive(Debug)]
 pub struct SsoTokenProvider {
     inner: Arc<Inner>,
@@ -93,14 +99,14 @@ impl SsoTokenProvider {
                     .client_secret
                     .as_ref()
                     .expect("required for token refresh")
-                    .as_str(),
+                    .expose_secret(),
             )
             .refresh_token(
                 cached_token
                     .refresh_token
                     .as_ref()
                     .expect("required for token refresh")
-                    .as_str(),
+                    .expose_secret(),
             )
             .send()
             .await;
@@ -112,7 +118,7 @@ impl SsoTokenProvider {
                 ..
             }) => {
                 let refreshed_token = CachedSsoToken {
-                    access_token: Zeroizing::new(access_token),
+                    access_token: SecretString::new(access_token),
                     client_id: cached_token.client_id.clone(),
                     client_secret: cached_token.client_secret.clone(),
                     expires_at: now
@@ -121,7 +127,7 @@ impl SsoTokenProvider {
                                 .map_err(|_| SsoTokenProviderError::BadExpirationTimeFromSsoOidc)?,
                         ),
                     refresh_token: refresh_token
-                        .map(Zeroizing::new)
+                        .map(SecretString::new)
                         .or_else(|| cached_token.refresh_token.clone()),
                     region: Some(inner.region.to_string()),
                     registration_expires_at: cached_token.registration_expires_at,
@@ -236,7 +242,7 @@ impl ResolveIdentity for SsoTokenProvider {
         IdentityFuture::new(Box::pin(async move {
             let token = token_future.await?;
             Ok(Identity::new(
-                Token::new(token.access_token.as_str(), Some(token.expires_at)),
+                Token::new(token.access_token.expose_secret(), Some(token.expires_at)),
                 Some(token.expires_at),
             ))
         }))
@@ -447,7 +453,7 @@ mod tests {
                 .resolve_token(self.time_source.clone())
                 .await
                 .unwrap();
-            assert_eq!(value, token.access_token.as_str());
+            assert_eq!(value, token.access_token.expose_secret());
             assert_eq!(time(expires_at), token.expires_at);
             token
         }
@@ -609,7 +615,7 @@ mod tests {
         assert_eq!(returned_token, cached_token);
         assert_eq!(
             "newrefreshtoken",
-            returned_token.refresh_token.unwrap().as_str()
+            returned_token.refresh_token.unwrap().expose_secret()
         );
         assert_eq!(
             "https://d-123.awsapps.com/start",
@@ -619,7 +625,7 @@ mod tests {
         assert_eq!("clientid", returned_token.client_id.unwrap());
         assert_eq!(
             "YSBzZWNyZXQ=",
-            returned_token.client_secret.unwrap().as_str()
+            returned_token.client_secret.unwrap().expose_secret()
         );
         assert_eq!(
             SystemTime::UNIX_EPOCH + Duration::from_secs(1_671_975_000),
@@ -730,7 +736,7 @@ mod tests {
         assert_eq!(returned_token, cached_token);
         assert_eq!(
             "cachedrefreshtoken",
-            returned_token.refresh_token.unwrap().as_str(),
+            returned_token.refresh_token.unwrap().expose_secret(),
             "it should have kept the old refresh token"
         );
 
@@ -5,7 +5,7 @@
 
 //! Credential provider augmentation through the AWS Security Token Service (STS).
 
-pub use assume_role::{AssumeRoleProvider, AssumeRoleProviderBuilder};
+pub use assume_role::{AssumeRoleProvider, AssumeRoleProviderBuilder, MfaToken};
 
 mod assume_role;
 pub(crate) mod util;
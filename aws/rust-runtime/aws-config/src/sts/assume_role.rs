@@ -12,11 +12,16 @@ use aws_sdk_sts::operation::assume_role::builders::AssumeRoleFluentBuilder;
 use aws_sdk_sts::operation::assume_role::AssumeRoleError;
 use aws_sdk_sts::types::PolicyDescriptorType;
 use aws_sdk_sts::Client as StsClient;
+use aws_smithy_async::future::BoxFuture;
 use aws_smithy_runtime::client::identity::IdentityCache;
+use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_types::error::display::DisplayErrorContext;
 use aws_types::region::Region;
 use aws_types::SdkConfig;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::Instrument;
 
@@ -66,6 +71,20 @@ use tracing::Instrument;
 /// }
 /// ```
 ///
+/// Create an AssumeRoleProvider for a role that requires MFA, which prompts for the MFA code:
+/// ```no_run
+/// use aws_config::sts::{AssumeRoleProvider, MfaToken};
+/// # fn prompt(_: &str) -> String { unimplemented!() }
+/// # async fn docs() {
+/// let provider = AssumeRoleProvider::builder("arn:aws:iam::123456789012:role/demo")
+///   .mfa_token_fn(|| async {
+///       let code = prompt("Enter the MFA code: ");
+///       Ok(MfaToken::new("arn:aws:iam::123456789012:mfa/user", code))
+///   })
+///   .build().await;
+/// }
+/// ```
+///
 #[derive(Debug)]
 pub struct AssumeRoleProvider {
     inner: Inner,
@@ -74,6 +93,57 @@ pub struct AssumeRoleProvider {
 #[derive(Debug)]
 struct Inner {
     fluent_builder: AssumeRoleFluentBuilder,
+    mfa_token_fn: Option<MfaTokenFn>,
+}
+
+/// The identification number of an MFA device and the code that it currently displays.
+///
+/// Returned by the callback that is set with [`AssumeRoleProviderBuilder::mfa_token_fn`].
+#[derive(Clone)]
+pub struct MfaToken {
+    serial_number: String,
+    token_code: String,
+}
+
+impl MfaToken {
+    /// Creates an MFA token.
+    ///
+    /// The `serial_number` is the serial number of a hardware device, or the Amazon Resource Name
+    /// (ARN) of a virtual device, like `arn:aws:iam::123456789012:mfa/user`.
+    pub fn new(serial_number: impl Into<String>, token_code: impl Into<String>) -> Self {
+        Self {
+            serial_number: serial_number.into(),
+            token_code: token_code.into(),
+        }
+    }
+
+    /// Returns the serial number or ARN of the MFA device.
+    pub fn serial_number(&self) -> &str {
+        &self.serial_number
+    }
+
+    /// Returns the code from the MFA device.
+    pub fn token_code(&self) -> &str {
+        &self.token_code
+    }
+}
+
+impl fmt::Debug for MfaToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MfaToken")
+            .field("serial_number", &self.serial_number)
+            .field("token_code", &"** redacted **")
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+struct MfaTokenFn(Arc<dyn Fn() -> BoxFuture<'static, MfaToken, BoxError> + Send + Sync>);
+
+impl fmt::Debug for MfaTokenFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("** MFA token callback **")
+    }
 }
 
 impl AssumeRoleProvider {
@@ -102,6 +172,7 @@ pub struct AssumeRoleProviderBuilder {
     policy_arns: Option<Vec<PolicyDescriptorType>>,
     region_override: Option<Region>,
     sdk_config: Option<SdkConfig>,
+    mfa_token_fn: Option<MfaTokenFn>,
 }
 
 impl AssumeRoleProviderBuilder {
@@ -122,6 +193,7 @@ impl AssumeRoleProviderBuilder {
             policy_arns: None,
             sdk_config: None,
             region_override: None,
+            mfa_token_fn: None,
         }
     }
 
@@ -183,6 +255,24 @@ impl AssumeRoleProviderBuilder {
         self
     }
 
+    /// Set a callback that provides an MFA token when the role is assumed.
+    ///
+    /// Roles whose trust policy requires multi-factor authentication (MFA) can only be assumed
+    /// with a code from the MFA device. The callback is called every time that the provider
+    /// assumes the role, so that a CLI can prompt for the current code. If the callback fails,
+    /// the provider fails to provide credentials with its error.
+    ///
+    /// Since the provider doesn't cache credentials, clients should cache them with an identity
+    /// cache, which is the default, so that the user isn't prompted for every request.
+    pub fn mfa_token_fn<F, Fut>(mut self, mfa_token_fn: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<MfaToken, BoxError>> + Send + 'static,
+    {
+        self.mfa_token_fn = Some(MfaTokenFn(Arc::new(move || Box::pin(mfa_token_fn()))));
+        self
+    }
+
     /// Set the region to assume the role in.
     ///
     /// This dictates which STS endpoint the AssumeRole action is invoked on. This will override
@@ -253,7 +343,10 @@ impl AssumeRoleProviderBuilder {
             .set_duration_seconds(self.session_length.map(|dur| dur.as_secs() as i32));
 
         AssumeRoleProvider {
-            inner: Inner { fluent_builder },
+            inner: Inner {
+                fluent_builder,
+                mfa_token_fn: self.mfa_token_fn,
+            },
         }
     }
 
@@ -279,7 +372,20 @@ impl Inner {
     async fn credentials(&self) -> provider::Result {
         tracing::debug!("retrieving assumed credentials");
 
-        let assumed = self.fluent_builder.clone().send().in_current_span().await;
+        let mut fluent_builder = self.fluent_builder.clone();
+        if let Some(mfa_token_fn) = &self.mfa_token_fn {
+            let mfa_token = (mfa_token_fn.0)().await.map_err(|err| {
+                CredentialsError::provider_error(format!(
+                    "failed to get an MFA token to assume the role: {}",
+                    DisplayErrorContext(&*err)
+                ))
+            })?;
+            tracing::debug!(serial_number = %mfa_token.serial_number, "assuming role with MFA");
+            fluent_builder = fluent_builder
+                .serial_number(mfa_token.serial_number)
+                .token_code(mfa_token.token_code);
+        }
+        let assumed = fluent_builder.send().in_current_span().await;
         match assumed {
             Ok(assumed) => {
                 tracing::debug!(
@@ -323,7 +429,7 @@ impl ProvideCredentials for AssumeRoleProvider {
 
 #[cfg(test)]
 mod test {
-    use crate::sts::AssumeRoleProvider;
+    use crate::sts::{AssumeRoleProvider, MfaToken};
     use aws_credential_types::credential_fn::provide_credentials_fn;
     use aws_credential_types::provider::{ProvideCredentials, SharedCredentialsProvider};
     use aws_credential_types::Credentials;
@@ -335,6 +441,7 @@ mod test {
     };
     use aws_smithy_runtime::test_util::capture_test_logs::capture_test_logs;
     use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::error::display::DisplayErrorContext;
     use aws_types::os_shim_internal::Env;
     use aws_types::region::Region;
     use aws_types::SdkConfig;
@@ -367,6 +474,62 @@ mod test {
         assert_eq!(req.uri(), "https://sts.us-east-1.amazonaws.com/");
     }
 
+    #[tokio::test]
+    async fn assumes_role_with_mfa_token() {
+        let (http_client, request) = capture_request(None);
+        let sdk_config = SdkConfig::builder()
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .time_source(StaticTimeSource::new(
+                UNIX_EPOCH + Duration::from_secs(1234567890 - 120),
+            ))
+            .http_client(http_client)
+            .region(Region::from_static("us-east-1"))
+            .build();
+        let provider = AssumeRoleProvider::builder("myrole")
+            .configure(&sdk_config)
+            .mfa_token_fn(|| async { Ok(MfaToken::new("GAHT12345678", "123456")) })
+            .build_from_provider(provide_credentials_fn(|| async {
+                Ok(Credentials::for_tests())
+            }))
+            .await;
+        let _ = provider.provide_credentials().await;
+        let req = request.expect_request();
+        let str_body = std::str::from_utf8(req.body().bytes().unwrap()).unwrap();
+        assert!(
+            str_body.contains("SerialNumber=GAHT12345678"),
+            "{}",
+            str_body
+        );
+        assert!(str_body.contains("TokenCode=123456"), "{}", str_body);
+    }
+
+    #[tokio::test]
+    async fn mfa_token_failure_fails_credentials() {
+        let (http_client, _request) = capture_request(None);
+        let sdk_config = SdkConfig::builder()
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .time_source(StaticTimeSource::new(UNIX_EPOCH))
+            .http_client(http_client)
+            .region(Region::from_static("us-east-1"))
+            .build();
+        let provider = AssumeRoleProvider::builder("myrole")
+            .configure(&sdk_config)
+            .mfa_token_fn(|| async { Err("the user cancelled the prompt".into()) })
+            .build_from_provider(provide_credentials_fn(|| async {
+                Ok(Credentials::for_tests())
+            }))
+            .await;
+        let err = provider
+            .provide_credentials()
+            .await
+            .expect_err("no MFA token");
+        assert!(
+            format!("{}", DisplayErrorContext(&err)).contains("the user cancelled the prompt"),
+            "{}",
+            DisplayErrorContext(&err)
+        );
+    }
+
     #[tokio::test]
     async fn loads_region_from_sdk_config() {
         let (http_client, request) = capture_request(None);
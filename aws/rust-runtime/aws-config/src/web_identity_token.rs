@@ -5,10 +5,16 @@
 
 //! Load Credentials from Web Identity Tokens
 //!
-//! Web identity tokens can be loaded from file. The path may be set in one of three ways:
+//! Web identity tokens can be loaded from file. The path may be set in one of four ways:
 //! 1. [Environment Variables](#environment-variable-configuration)
 //! 2. [AWS profile](#aws-profile-configuration) defined in `~/.aws/config`
 //! 3. Static configuration via [`static_configuration`](Builder::static_configuration)
+//! 4. [Programmatic configuration](#programmatic-configuration) on the [`Builder`], which can also
+//!    supply tokens from memory rather than from a file
+//!
+//! The token file is read again every time that credentials are loaded, so tokens that are
+//! rotated in place, like projected service account tokens in Kubernetes, are picked up
+//! automatically.
 //!
 //! _Note: [WebIdentityTokenCredentialsProvider] is part of the [default provider chain](crate::default_provider).
 //! Unless you need specific behavior or configuration overrides, it is recommended to use the
@@ -60,16 +66,56 @@
 //!     .build();
 //! # }
 //! ```
+//!
+//! ## Programmatic Configuration
+//! The role and token can be configured on the builder instead of with environment variables:
+//!
+//! ```no_run
+//! # async fn test() {
+//! use aws_config::web_identity_token::WebIdentityTokenCredentialsProvider;
+//! use aws_config::provider_config::ProviderConfig;
+//! use std::time::Duration;
+//!
+//! let provider = WebIdentityTokenCredentialsProvider::builder()
+//!     .configure(&ProviderConfig::with_default_region().await)
+//!     .role_arn("arn:aws:iam::123456789012:role/web-identity")
+//!     .session_name("my-session")
+//!     .web_identity_token_file("/var/run/secrets/eks.amazonaws.com/serviceaccount/token")
+//!     .session_length(Duration::from_secs(3600))
+//!     .build();
+//! # }
+//! ```
+//!
+//! Tokens that are held in memory, for example, tokens that an application gets from its own
+//! identity provider, can be supplied with a callback instead:
+//!
+//! ```no_run
+//! # async fn fetch_oidc_token() -> Result<String, Box<dyn std::error::Error + Send + Sync>> { todo!() }
+//! # async fn test() {
+//! use aws_config::web_identity_token::WebIdentityTokenCredentialsProvider;
+//!
+//! let provider = WebIdentityTokenCredentialsProvider::builder()
+//!     .role_arn("arn:aws:iam::123456789012:role/web-identity")
+//!     .web_identity_token_fn(|| async { fetch_oidc_token().await })
+//!     .build();
+//! # }
+//! ```
 
 use crate::provider_config::ProviderConfig;
 use crate::sts;
 use aws_credential_types::provider::{self, error::CredentialsError, future, ProvideCredentials};
 use aws_sdk_sts::Client as StsClient;
+use aws_smithy_async::future::BoxFuture;
 use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_types::error::display::DisplayErrorContext;
 use aws_types::os_shim_internal::{Env, Fs};
 use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 const ENV_VAR_TOKEN_FILE: &str = "AWS_WEB_IDENTITY_TOKEN_FILE";
 const ENV_VAR_ROLE_ARN: &str = "AWS_ROLE_ARN";
@@ -81,6 +127,7 @@ const ENV_VAR_SESSION_NAME: &str = "AWS_ROLE_SESSION_NAME";
 #[derive(Debug)]
 pub struct WebIdentityTokenCredentialsProvider {
     source: Source,
+    session_length: Option<Duration>,
     time_source: SharedTimeSource,
     fs: Fs,
     sts_client: StsClient,
@@ -97,6 +144,30 @@ impl WebIdentityTokenCredentialsProvider {
 enum Source {
     Env(Env),
     Static(StaticConfiguration),
+    Builder(BuilderConfiguration),
+}
+
+/// Configuration that was set with the builder's role and token methods
+#[derive(Debug, Default)]
+struct BuilderConfiguration {
+    token: Option<TokenSource>,
+    role_arn: Option<String>,
+    session_name: Option<String>,
+}
+
+#[derive(Debug)]
+enum TokenSource {
+    File(PathBuf),
+    Fn(WebIdentityTokenFn),
+}
+
+#[derive(Clone)]
+struct WebIdentityTokenFn(Arc<dyn Fn() -> BoxFuture<'static, String, BoxError> + Send + Sync>);
+
+impl fmt::Debug for WebIdentityTokenFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("** web identity token callback **")
+    }
 }
 
 /// Statically configured WebIdentityToken configuration
@@ -143,16 +214,49 @@ impl WebIdentityTokenCredentialsProvider {
                 }))
             }
             Source::Static(conf) => Ok(Cow::Borrowed(conf)),
+            Source::Builder(_) => unreachable!("handled by `credentials`"),
         }
     }
+
     async fn credentials(&self) -> provider::Result {
-        let conf = self.source()?;
+        let (token, role_arn, session_name) = match &self.source {
+            Source::Builder(conf) => {
+                let role_arn = conf.role_arn.clone().ok_or_else(|| {
+                    CredentialsError::invalid_configuration(
+                        "a role ARN must be set to assume a role with a web identity token",
+                    )
+                })?;
+                let token = match &conf.token {
+                    Some(TokenSource::File(path)) => read_token_file(&self.fs, path).await?,
+                    Some(TokenSource::Fn(token_fn)) => (token_fn.0)().await.map_err(|err| {
+                        CredentialsError::provider_error(format!(
+                            "failed to get a web identity token: {}",
+                            DisplayErrorContext(&*err)
+                        ))
+                    })?,
+                    None => {
+                        return Err(CredentialsError::invalid_configuration(
+                            "a web identity token file or token callback must be set to assume a role with a web identity token",
+                        ))
+                    }
+                };
+                let session_name = conf.session_name.clone().unwrap_or_else(|| {
+                    sts::util::default_session_name("web-identity-token", self.time_source.now())
+                });
+                (token, role_arn, session_name)
+            }
+            _ => {
+                let conf = self.source()?;
+                let token = read_token_file(&self.fs, &conf.web_identity_token_file).await?;
+                (token, conf.role_arn.clone(), conf.session_name.clone())
+            }
+        };
         load_credentials(
-            &self.fs,
             &self.sts_client,
-            &conf.web_identity_token_file,
-            &conf.role_arn,
-            &conf.session_name,
+            token,
+            &role_arn,
+            &session_name,
+            self.session_length,
         )
         .await
     }
@@ -162,6 +266,7 @@ impl WebIdentityTokenCredentialsProvider {
 #[derive(Debug, Default)]
 pub struct Builder {
     source: Option<Source>,
+    session_length: Option<Duration>,
     config: Option<ProviderConfig>,
 }
 
@@ -193,6 +298,67 @@ impl Builder {
         self
     }
 
+    fn builder_configuration(&mut self) -> &mut BuilderConfiguration {
+        if !matches!(self.source, Some(Source::Builder(_))) {
+            self.source = Some(Source::Builder(BuilderConfiguration::default()));
+        }
+        match &mut self.source {
+            Some(Source::Builder(conf)) => conf,
+            _ => unreachable!("set above"),
+        }
+    }
+
+    /// Set the ARN of the role to assume.
+    ///
+    /// Setting the role, the token or the session name on the builder replaces the configuration
+    /// from environment variables or [`static_configuration`](Self::static_configuration).
+    /// A role ARN and a token source are both required.
+    pub fn role_arn(mut self, role_arn: impl Into<String>) -> Self {
+        self.builder_configuration().role_arn = Some(role_arn.into());
+        self
+    }
+
+    /// Set the session name to use when assuming the role.
+    ///
+    /// Defaults to a session name that is generated from the current time.
+    pub fn session_name(mut self, session_name: impl Into<String>) -> Self {
+        self.builder_configuration().session_name = Some(session_name.into());
+        self
+    }
+
+    /// Set the path of the file that contains the web identity token.
+    ///
+    /// The file is read every time that credentials are loaded, so it may be rotated in place.
+    pub fn web_identity_token_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.builder_configuration().token = Some(TokenSource::File(path.into()));
+        self
+    }
+
+    /// Set a callback that supplies the web identity token.
+    ///
+    /// The callback is called every time that credentials are loaded, so it should return
+    /// a token that is currently valid.
+    pub fn web_identity_token_fn<F, Fut>(mut self, token_fn: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, BoxError>> + Send + 'static,
+    {
+        self.builder_configuration().token =
+            Some(TokenSource::Fn(WebIdentityTokenFn(Arc::new(move || {
+                Box::pin(token_fn())
+            }))));
+        self
+    }
+
+    /// Set how long the role session lasts.
+    ///
+    /// When unset, STS defaults to 1 hour. The value can range from 15 minutes up to the maximum
+    /// session duration that is set for the role.
+    pub fn session_length(mut self, length: Duration) -> Self {
+        self.session_length = Some(length);
+        self
+    }
+
     /// Build a [`WebIdentityTokenCredentialsProvider`]
     ///
     /// ## Panics
@@ -203,6 +369,7 @@ impl Builder {
         let source = self.source.unwrap_or_else(|| Source::Env(conf.env()));
         WebIdentityTokenCredentialsProvider {
             source,
+            session_length: self.session_length,
             fs: conf.fs(),
             sts_client: StsClient::new(&conf.client_config()),
             time_source: conf.time_source(),
@@ -210,25 +377,30 @@ impl Builder {
     }
 }
 
-async fn load_credentials(
+async fn read_token_file(
     fs: &Fs,
-    sts_client: &StsClient,
     token_file: impl AsRef<Path>,
-    role_arn: &str,
-    session_name: &str,
-) -> provider::Result {
+) -> Result<String, CredentialsError> {
     let token = fs
         .read_to_end(token_file)
         .await
         .map_err(CredentialsError::provider_error)?;
-    let token = String::from_utf8(token).map_err(|_utf_8_error| {
-        CredentialsError::unhandled("WebIdentityToken was not valid UTF-8")
-    })?;
+    String::from_utf8(token)
+        .map_err(|_utf_8_error| CredentialsError::unhandled("WebIdentityToken was not valid UTF-8"))
+}
 
+async fn load_credentials(
+    sts_client: &StsClient,
+    token: String,
+    role_arn: &str,
+    session_name: &str,
+    session_length: Option<Duration>,
+) -> provider::Result {
     let resp = sts_client.assume_role_with_web_identity()
         .role_arn(role_arn)
         .role_session_name(session_name)
         .web_identity_token(token)
+        .set_duration_seconds(session_length.map(|length| length.as_secs() as i32))
         .send()
         .await
         .map_err(|sdk_error| {
@@ -247,10 +419,12 @@ mod test {
     };
     use aws_credential_types::provider::error::CredentialsError;
     use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_runtime::client::http::test_util::capture_request;
     use aws_smithy_types::error::display::DisplayErrorContext;
     use aws_types::os_shim_internal::{Env, Fs};
     use aws_types::region::Region;
     use std::collections::HashMap;
+    use std::time::Duration;
 
     #[tokio::test]
     async fn unloaded_provider() {
@@ -324,4 +498,50 @@ mod test {
             _ => panic!("incorrect error variant"),
         }
     }
+
+    #[tokio::test]
+    async fn builder_configuration() {
+        let (http_client, request) = capture_request(None);
+        let provider = Builder::default()
+            .configure(
+                &ProviderConfig::empty()
+                    .with_sleep_impl(TokioSleep::new())
+                    .with_http_client(http_client)
+                    .with_region(Some(Region::new("us-east-1")))
+                    .with_env(Env::from_slice(&[])),
+            )
+            .role_arn("arn:aws:iam::123456789123:role/test-role")
+            .session_name("test-session")
+            .web_identity_token_fn(|| async { Ok("in-memory-token".to_string()) })
+            .session_length(Duration::from_secs(3600))
+            .build();
+        let _ = provider.credentials().await;
+        let req = request.expect_request();
+        let body = std::str::from_utf8(req.body().bytes().unwrap()).unwrap();
+        for expected in [
+            "WebIdentityToken=in-memory-token",
+            "RoleSessionName=test-session",
+            "DurationSeconds=3600",
+        ] {
+            assert!(body.contains(expected), "{} not in {}", expected, body);
+        }
+    }
+
+    #[tokio::test]
+    async fn builder_configuration_requires_role_arn() {
+        let provider = Builder::default()
+            .configure(
+                &ProviderConfig::empty()
+                    .with_sleep_impl(TokioSleep::new())
+                    .with_http_client(no_traffic_client())
+                    .with_region(Some(Region::new("us-east-1"))),
+            )
+            .web_identity_token_file("/token.jwt")
+            .build();
+        let err = provider.credentials().await.expect_err("no role ARN");
+        match err {
+            CredentialsError::InvalidConfiguration { .. } => { /* ok */ }
+            _ => panic!("incorrect error variant"),
+        }
+    }
 }
@@ -10,6 +10,7 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use zeroize::Zeroizing;
 
+use crate::secret::SecretString;
 use aws_smithy_runtime_api::client::identity::Identity;
 
 /// AWS SDK Credentials
@@ -25,8 +26,8 @@ pub struct Credentials(Arc<Inner>);
 #[derive(Clone, Eq, PartialEq)]
 struct Inner {
     access_key_id: Zeroizing<String>,
-    secret_access_key: Zeroizing<String>,
-    session_token: Zeroizing<Option<String>>,
+    secret_access_key: SecretString,
+    session_token: Option<SecretString>,
 
     /// Credential Expiry
     ///
@@ -37,6 +38,11 @@ struct Inner {
     /// If these credentials never expire, this value will be set to `None`
     expires_after: Option<SystemTime>,
 
+    /// ID of the AWS account the credentials belong to, if known
+    ///
+    /// Services with account-specific endpoints use it to route requests.
+    account_id: Option<String>,
+
     provider_name: &'static str,
 }
 
@@ -46,7 +52,10 @@ impl Debug for Credentials {
         creds
             .field("provider_name", &self.0.provider_name)
             .field("access_key_id", &self.0.access_key_id.as_str())
-            .field("secret_access_key", &"** redacted **");
+            .field("secret_access_key", &self.0.secret_access_key);
+        if let Some(account_id) = self.account_id() {
+            creds.field("account_id", &account_id);
+        }
         if let Some(expiry) = self.expiry() {
             if let Some(formatted) = expiry.duration_since(UNIX_EPOCH).ok().and_then(|dur| {
                 aws_smithy_types::DateTime::from_secs(dur.as_secs() as _)
@@ -79,9 +88,10 @@ impl Credentials {
     ) -> Self {
         Credentials(Arc::new(Inner {
             access_key_id: Zeroizing::new(access_key_id.into()),
-            secret_access_key: Zeroizing::new(secret_access_key.into()),
-            session_token: Zeroizing::new(session_token),
+            secret_access_key: SecretString::new(secret_access_key),
+            session_token: session_token.map(SecretString::new),
             expires_after,
+            account_id: None,
             provider_name,
         }))
     }
@@ -149,7 +159,7 @@ impl Credentials {
 
     /// Returns the secret access key.
     pub fn secret_access_key(&self) -> &str {
-        &self.0.secret_access_key
+        self.0.secret_access_key.expose_secret()
     }
 
     /// Returns the time when the credentials will expire.
@@ -164,7 +174,21 @@ impl Credentials {
 
     /// Returns the session token.
     pub fn session_token(&self) -> Option<&str> {
-        self.0.session_token.as_deref()
+        self.0
+            .session_token
+            .as_ref()
+            .map(SecretString::expose_secret)
+    }
+
+    /// Returns the ID of the AWS account the credentials belong to, if known.
+    pub fn account_id(&self) -> Option<&str> {
+        self.0.account_id.as_deref()
+    }
+
+    /// Sets the ID of the AWS account the credentials belong to.
+    pub fn with_account_id(mut self, account_id: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.0).account_id = Some(account_id.into());
+        self
     }
 }
 
@@ -219,4 +243,17 @@ mod test {
             r#"Credentials { provider_name: "debug tester", access_key_id: "akid", secret_access_key: "** redacted **", expires_after: "2009-02-13T23:31:30Z" }"#
         );
     }
+
+    #[test]
+    fn account_id() {
+        let creds = Credentials::new("akid", "secret", None, None, "test");
+        assert_eq!(creds.account_id(), None);
+
+        let creds = creds.with_account_id("123456789012");
+        assert_eq!(creds.account_id(), Some("123456789012"));
+        assert_eq!(
+            format!("{:?}", creds),
+            r#"Credentials { provider_name: "test", access_key_id: "akid", secret_access_key: "** redacted **", account_id: "123456789012" }"#
+        );
+    }
 }
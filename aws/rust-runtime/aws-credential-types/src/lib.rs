@@ -20,5 +20,8 @@
 pub mod credential_fn;
 mod credentials_impl;
 pub mod provider;
+pub mod secret;
+mod token_impl;
 
 pub use credentials_impl::Credentials;
+pub use token_impl::Token;
@@ -77,6 +77,8 @@ use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 use std::sync::Arc;
 
+pub mod token;
+
 /// Credentials provider errors
 pub mod error {
     use std::error::Error;
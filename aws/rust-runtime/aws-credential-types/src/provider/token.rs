@@ -0,0 +1,196 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Token providers
+//!
+//! Token providers are [`ProvideToken`] implementations, which are wrapped in a
+//! [`SharedTokenProvider`] to be used as the identity resolver of a bearer auth scheme.
+//! [`provide_token_fn`] creates a token provider that loads [tokens](Token) from an external
+//! identity provider, such as an OIDC provider.
+//!
+//! ```no_run
+//! use aws_credential_types::provider::token::{provide_token_fn, SharedTokenProvider};
+//! use aws_credential_types::Token;
+//! use std::time::{Duration, SystemTime};
+//!
+//! async fn fetch_access_token() -> (String, u64) {
+//!     // Request a token from the identity provider here
+//!     # unimplemented!()
+//! }
+//!
+//! let provider = SharedTokenProvider::new(provide_token_fn(|| async {
+//!     let (access_token, expires_in) = fetch_access_token().await;
+//!     Ok(Token::new(
+//!         access_token,
+//!         Some(SystemTime::now() + Duration::from_secs(expires_in)),
+//!     ))
+//! }));
+//! // Pass `provider` as the identity resolver of a bearer auth scheme, for example
+//! // with `.identity_resolver(HTTP_BEARER_AUTH_SCHEME_ID, provider)` on a service config
+//! ```
+
+use crate::Token;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+
+pub use aws_smithy_runtime_api::client::identity::http::{
+    ProvideToken, SharedTokenProvider, TokenFuture,
+};
+
+/// Token provider errors
+pub mod error {
+    use std::error::Error;
+    use std::fmt;
+
+    /// Details for [`TokenError::TokenNotLoaded`]
+    #[derive(Debug)]
+    pub struct TokenNotLoaded {
+        source: Box<dyn Error + Send + Sync + 'static>,
+    }
+
+    /// Details for [`TokenError::ProviderError`]
+    #[derive(Debug)]
+    pub struct ProviderError {
+        source: Box<dyn Error + Send + Sync + 'static>,
+    }
+
+    /// Error returned when a token failed to load.
+    #[derive(Debug)]
+    #[non_exhaustive]
+    pub enum TokenError {
+        /// No token was available from this provider
+        TokenNotLoaded(TokenNotLoaded),
+
+        /// The provider experienced an error while loading the token
+        ProviderError(ProviderError),
+    }
+
+    impl TokenError {
+        /// The token provider did not provide a token
+        pub fn not_loaded(source: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+            Self::TokenNotLoaded(TokenNotLoaded {
+                source: source.into(),
+            })
+        }
+
+        /// The token provider returned an error
+        pub fn provider_error(source: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+            Self::ProviderError(ProviderError {
+                source: source.into(),
+            })
+        }
+    }
+
+    impl fmt::Display for TokenError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TokenError::TokenNotLoaded(_) => write!(f, "the token provider was not enabled"),
+                TokenError::ProviderError(_) => {
+                    write!(f, "an error occurred while loading a token")
+                }
+            }
+        }
+    }
+
+    impl Error for TokenError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                TokenError::TokenNotLoaded(details) => Some(details.source.as_ref() as _),
+                TokenError::ProviderError(details) => Some(details.source.as_ref() as _),
+            }
+        }
+    }
+}
+
+/// Result type for token providers.
+pub type Result = std::result::Result<Token, error::TokenError>;
+
+/// A [`ProvideToken`] implemented by a closure.
+///
+/// See [`provide_token_fn`] for more details.
+#[derive(Copy, Clone)]
+pub struct ProvideTokenFn<'c, T> {
+    f: T,
+    phantom: PhantomData<&'c T>,
+}
+
+impl<T> fmt::Debug for ProvideTokenFn<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ProvideTokenFn")
+    }
+}
+
+impl<'c, T, F> ProvideToken for ProvideTokenFn<'c, T>
+where
+    T: Fn() -> F + Send + Sync + 'c,
+    F: Future<Output = Result> + Send + 'static,
+{
+    fn provide_token<'a>(&'a self) -> TokenFuture<'a> {
+        let loading = (self.f)();
+        TokenFuture::new(async move { Ok(loading.await?.into()) })
+    }
+}
+
+/// Returns a new token provider built with the given closure. This allows you
+/// to create a [`ProvideToken`] implementation from an async block that returns
+/// a [`Result`].
+pub fn provide_token_fn<'c, T, F>(f: T) -> ProvideTokenFn<'c, T>
+where
+    T: Fn() -> F + Send + Sync + 'c,
+    F: Future<Output = Result> + Send + 'static,
+{
+    ProvideTokenFn {
+        f,
+        phantom: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::provider::token::error::TokenError;
+    use crate::provider::token::{provide_token_fn, SharedTokenProvider};
+    use crate::Token;
+    use aws_smithy_runtime_api::client::identity::http::Token as HttpToken;
+    use aws_smithy_runtime_api::client::identity::ResolveIdentity;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::ConfigBag;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[tokio::test]
+    async fn resolves_bearer_token_identity() {
+        let expiration = UNIX_EPOCH + Duration::from_secs(1234567890);
+        let provider = SharedTokenProvider::new(provide_token_fn(move || async move {
+            Ok(Token::new("oidc-token", Some(expiration)))
+        }));
+        let components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+
+        let identity = provider
+            .resolve_identity(&components, &ConfigBag::base())
+            .await
+            .expect("success");
+        assert_eq!(Some(expiration), identity.expiration());
+        assert_eq!(
+            "oidc-token",
+            identity.data::<HttpToken>().expect("bearer token").token()
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_errors_are_returned() {
+        let provider = SharedTokenProvider::new(provide_token_fn(|| async {
+            Err(TokenError::provider_error(
+                "identity provider is unavailable",
+            ))
+        }));
+        let components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+
+        let err = provider
+            .resolve_identity(&components, &ConfigBag::base())
+            .await
+            .expect_err("provider error");
+        assert!(err.downcast_ref::<TokenError>().is_some(), "{}", err);
+    }
+}
@@ -0,0 +1,65 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Types for secrets, such as secret access keys and tokens.
+
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// A string that holds a secret, such as a secret access key or a token.
+///
+/// The secret is zeroed in memory when the `SecretString` is dropped, and it's always redacted
+/// in `Debug` output, so that types containing secrets can derive `Debug` without leaking them.
+///
+/// ```rust
+/// use aws_credential_types::secret::SecretString;
+///
+/// #[derive(Debug)]
+/// struct Login {
+///     user: String,
+///     password: SecretString,
+/// }
+///
+/// let login = Login { user: "user".into(), password: "hunter2".into() };
+/// assert_eq!(
+///     r#"Login { user: "user", password: "** redacted **" }"#,
+///     format!("{:?}", login),
+/// );
+/// assert_eq!("hunter2", login.password.expose_secret());
+/// ```
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    /// Creates a new `SecretString`.
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(Zeroizing::new(secret.into()))
+    }
+
+    /// Returns the secret.
+    ///
+    /// Take care not to log or otherwise leak the returned value.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt("** redacted **", f)
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self::new(secret)
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::secret::SecretString;
+use aws_smithy_runtime_api::client::identity::http::{
+    ProvideToken, Token as HttpToken, TokenFuture,
+};
+use aws_smithy_runtime_api::client::identity::Identity;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Access token, such as an OIDC or JWT token from an external identity provider.
+///
+/// Tokens are used as the identity of bearer auth schemes. Like [`Credentials`](crate::Credentials),
+/// the token is zeroed in memory when it's dropped, and it's redacted in `Debug` output.
+///
+/// Besides the token itself, a `Token` can hold arbitrary properties, such as claims that the
+/// identity provider returned along with it. Properties are for the consumers of a
+/// [token provider](crate::provider::token::ProvideToken), and aren't sent with requests.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Token(Arc<Inner>);
+
+#[derive(Clone, Eq, PartialEq)]
+struct Inner {
+    token: SecretString,
+    expiration: Option<SystemTime>,
+    properties: HashMap<String, String>,
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Token")
+            .field("token", &self.0.token)
+            .field("expiration", &self.0.expiration)
+            .field("properties", &self.0.properties)
+            .finish()
+    }
+}
+
+impl Token {
+    /// Creates a `Token` that expires at the given time, if any.
+    pub fn new(token: impl Into<String>, expiration: Option<SystemTime>) -> Self {
+        Self(Arc::new(Inner {
+            token: SecretString::new(token),
+            expiration,
+            properties: HashMap::new(),
+        }))
+    }
+
+    /// Adds a property to this token, replacing any previous value of the property.
+    ///
+    /// Properties are for information such as token claims, and must not hold secrets, as
+    /// they're included in `Debug` output.
+    pub fn with_property(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.0)
+            .properties
+            .insert(name.into(), value.into());
+        self
+    }
+
+    /// Returns the token.
+    pub fn token(&self) -> &str {
+        self.0.token.expose_secret()
+    }
+
+    /// Returns the time that the token expires at, if any.
+    pub fn expiration(&self) -> Option<SystemTime> {
+        self.0.expiration
+    }
+
+    /// Returns the value of the given property, if it's set.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.0.properties.get(name).map(String::as_str)
+    }
+
+    /// Returns an iterator over the names and values of this token's properties.
+    pub fn properties(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl From<Token> for HttpToken {
+    /// Converts the token to the HTTP auth token that bearer auth schemes sign requests with.
+    ///
+    /// The HTTP auth token only holds the token and its expiration.
+    fn from(token: Token) -> Self {
+        HttpToken::new(token.token(), token.expiration())
+    }
+}
+
+impl From<Token> for Identity {
+    /// Converts the token to an identity for bearer auth schemes.
+    ///
+    /// The identity's data is an HTTP auth [token](HttpToken).
+    fn from(token: Token) -> Self {
+        let expiration = token.expiration();
+        Identity::new(HttpToken::from(token), expiration)
+    }
+}
+
+impl ProvideToken for Token {
+    fn provide_token<'a>(&'a self) -> TokenFuture<'a> {
+        TokenFuture::ready(Ok(self.clone().into()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Token;
+    use aws_smithy_runtime_api::client::identity::http::{
+        ProvideToken, SharedTokenProvider, Token as HttpToken,
+    };
+    use aws_smithy_runtime_api::client::identity::Identity;
+    use aws_smithy_runtime_api::client::identity::ResolveIdentity;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::ConfigBag;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn debug_impl() {
+        let token = Token::new("secret-token", None).with_property("sub", "user");
+        let debug = format!("{:?}", token);
+        assert!(!debug.contains("secret-token"), "{}", debug);
+        assert!(debug.contains(r#""sub": "user""#), "{}", debug);
+    }
+
+    #[test]
+    fn into_identity() {
+        let expiration = UNIX_EPOCH + Duration::from_secs(1234567890);
+        let token = Token::new("token", Some(expiration)).with_property("sub", "user");
+        assert_eq!(Some("user"), token.property("sub"));
+
+        let identity = Identity::from(token);
+        assert_eq!(Some(expiration), identity.expiration());
+        let http_token = identity.data::<HttpToken>().expect("bearer token");
+        assert_eq!("token", http_token.token());
+        assert_eq!(Some(expiration), http_token.expiration());
+    }
+
+    #[tokio::test]
+    async fn static_token_provider() {
+        let expiration = UNIX_EPOCH + Duration::from_secs(1234567890);
+        let token = Token::new("token", Some(expiration));
+        let http_token = token.provide_token().await.expect("static token");
+        assert_eq!("token", http_token.token());
+
+        let components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let identity = SharedTokenProvider::new(token)
+            .resolve_identity(&components, &ConfigBag::base())
+            .await
+            .expect("success");
+        assert_eq!(Some(expiration), identity.expiration());
+    }
+}
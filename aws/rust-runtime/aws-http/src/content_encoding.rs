@@ -3,17 +3,27 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use bytes::{Bytes, BytesMut};
 use http::{HeaderMap, HeaderValue};
 use http_body::{Body, SizeHint};
 use pin_project_lite::pin_project;
 
+use std::fmt;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
 
 const CRLF: &str = "\r\n";
 const CHUNK_TERMINATOR: &str = "0\r\n";
 const TRAILER_SEPARATOR: &[u8] = b":";
+const CHUNK_SIGNATURE_PREFIX: &str = ";chunk-signature=";
+const TRAILER_SIGNATURE_NAME: &str = "x-amz-trailer-signature";
+/// The length of a hex-encoded SigV4 signature.
+const SIGNATURE_LENGTH: u64 = 64;
+/// The size of the chunks of signed bodies, when no chunk size was set.
+const DEFAULT_SIGNED_CHUNK_SIZE: u64 = 64 * 1024;
 
 /// Content encoding header value constants
 pub mod header_value {
@@ -21,17 +31,71 @@ pub mod header_value {
     pub const AWS_CHUNKED: &str = "aws-chunked";
 }
 
+/// Signs the chunks and trailers of an [`AwsChunkedBody`].
+///
+/// Each signature incorporates the signature before it, starting with the signature of the
+/// request that the body is sent with, so implementations keep track of the last signature.
+pub trait SignChunk: fmt::Debug + Send + Sync {
+    /// Returns the signature of the next chunk. The last chunk is empty.
+    fn chunk_signature(&mut self, chunk: &[u8]) -> Result<String, BoxError>;
+
+    /// Returns the signature of the trailers, which are signed after the last chunk.
+    fn trailer_signature(&mut self, trailers: &HeaderMap) -> Result<String, BoxError>;
+}
+
+/// A [chunk signer](SignChunk) that's set once the request has been signed.
+///
+/// Chunk signatures incorporate the signature of the request, so the chunk signer can only be
+/// created after the body was. Store this in the config bag of the operation, and pass it to
+/// [`AwsChunkedBodyOptions::with_chunk_signer`]. The request signer sets the chunk signer for
+/// every request attempt, and each body takes the chunk signer that was set last when it's first
+/// polled.
+#[derive(Clone, Default)]
+pub struct DeferredChunkSigner {
+    signer: Arc<Mutex<Option<Box<dyn SignChunk>>>>,
+}
+
+impl DeferredChunkSigner {
+    /// Creates a new `DeferredChunkSigner` that doesn't have a chunk signer yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the chunk signer for the body of the request that was just signed.
+    pub fn set(&self, signer: Box<dyn SignChunk>) {
+        *self.signer.lock().unwrap() = Some(signer);
+    }
+
+    fn take(&self) -> Option<Box<dyn SignChunk>> {
+        self.signer.lock().unwrap().take()
+    }
+}
+
+impl fmt::Debug for DeferredChunkSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeferredChunkSigner")
+            .finish_non_exhaustive()
+    }
+}
+
+impl Storable for DeferredChunkSigner {
+    type Storer = StoreReplace<Self>;
+}
+
 /// Options used when constructing an [`AwsChunkedBody`][AwsChunkedBody].
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct AwsChunkedBodyOptions {
-    /// The total size of the stream. Because we only support unsigned encoding
-    /// this implies that there will only be a single chunk containing the
-    /// underlying payload.
+    /// The total size of the stream. Unless a chunk size is set, unsigned bodies are sent as a
+    /// single chunk containing the underlying payload.
     stream_length: u64,
     /// The length of each trailer sent within an `AwsChunkedBody`. Necessary in
     /// order to correctly calculate the total size of the body accurately.
     trailer_lengths: Vec<u64>,
+    /// The size of the chunks that the stream is split into.
+    chunk_size: Option<u64>,
+    /// Signs the chunks and trailers of the body, if set.
+    chunk_signer: Option<DeferredChunkSigner>,
 }
 
 impl AwsChunkedBodyOptions {
@@ -40,6 +104,8 @@ impl AwsChunkedBodyOptions {
         Self {
             stream_length,
             trailer_lengths,
+            chunk_size: None,
+            chunk_signer: None,
         }
     }
 
@@ -54,16 +120,63 @@ impl AwsChunkedBodyOptions {
         self.trailer_lengths.push(trailer_len);
         self
     }
+
+    /// Splits the stream into chunks of the given size. The last chunk may be smaller.
+    ///
+    /// Unsigned bodies are sent as a single chunk by default, and signed bodies in chunks of 64 KiB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk size is zero.
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        assert!(chunk_size > 0, "the chunk size must be greater than zero");
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Signs the chunks and trailers of the body with the given chunk signer.
+    ///
+    /// Signed chunks are buffered in memory before they're sent, so that they can be signed.
+    pub fn with_chunk_signer(mut self, chunk_signer: DeferredChunkSigner) -> Self {
+        self.chunk_signer = Some(chunk_signer);
+        self
+    }
+
+    fn is_signed(&self) -> bool {
+        self.chunk_signer.is_some()
+    }
+
+    fn chunk_size(&self) -> u64 {
+        match self.chunk_size {
+            Some(chunk_size) => chunk_size,
+            None if self.is_signed() => DEFAULT_SIGNED_CHUNK_SIZE,
+            None => self.stream_length.max(1),
+        }
+    }
+
+    /// The length of a chunk with the given data length, including its framing.
+    fn encoded_chunk_length(&self, data_length: u64) -> u64 {
+        let mut length = int_log16(data_length).max(1) + CRLF.len() as u64;
+        if self.is_signed() {
+            length += CHUNK_SIGNATURE_PREFIX.len() as u64 + SIGNATURE_LENGTH;
+        }
+        if data_length != 0 {
+            length += data_length + CRLF.len() as u64;
+        }
+        length
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
 enum AwsChunkedBodyState {
-    /// Write out the size of the chunk that will follow. Then, transition into the
-    /// `WritingChunk` state.
+    /// Write out the size of the next chunk. For signed bodies, the whole chunk is read from the
+    /// inner body, signed, and written out with its size. Otherwise, transition into the
+    /// `WritingChunk` state. Once the stream was written out, write out the last chunk and
+    /// transition into the `WritingTrailers` state.
     WritingChunkSize,
-    /// Write out the next chunk of data. Multiple polls of the inner body may need to occur before
-    /// all data is written out. Once there is no more data to write, transition into the
-    /// `WritingTrailers` state.
+    /// Write out the data of an unsigned chunk. Multiple polls of the inner body may need to
+    /// occur before all data is written out. Once the chunk's data was written out, write out a
+    /// CRLF and transition into the `WritingChunkSize` state.
     WritingChunk,
     /// Write out all trailers associated with this `AwsChunkedBody` and then transition into the
     /// `Closed` state.
@@ -73,8 +186,11 @@ enum AwsChunkedBodyState {
 }
 
 pin_project! {
-    /// A request body compatible with `Content-Encoding: aws-chunked`. This implementation is only
-    /// capable of writing a single chunk and does not support signed chunks.
+    /// A request body compatible with `Content-Encoding: aws-chunked`.
+    ///
+    /// Unless [a chunk size is set](AwsChunkedBodyOptions::with_chunk_size), the body is written
+    /// as a single chunk. When [a chunk signer is set](AwsChunkedBodyOptions::with_chunk_signer),
+    /// every chunk and the trailers are signed.
     ///
     /// Chunked-Body grammar is defined in [ABNF] as:
     ///
@@ -84,11 +200,13 @@ pin_project! {
     ///                   chunked-trailer
     ///                   CRLF
     ///
-    /// chunk           = chunk-size CRLF chunk-data CRLF
+    /// chunk           = chunk-size [ chunk-signature ] CRLF chunk-data CRLF
     /// chunk-size      = 1*HEXDIG
-    /// last-chunk      = 1*("0") CRLF
-    /// chunked-trailer = *( entity-header CRLF )
+    /// chunk-signature = ";chunk-signature=" 64HEXDIG
+    /// last-chunk      = 1*("0") [ chunk-signature ] CRLF
+    /// chunked-trailer = *( entity-header CRLF ) [ trailer-signature CRLF ]
     /// entity-header   = field-name ":" OWS field-value OWS
+    /// trailer-signature = "x-amz-trailer-signature:" 64HEXDIG
     /// ```
     /// For more info on what the abbreviations mean, see https://datatracker.ietf.org/doc/html/rfc7230#section-1.2
     ///
@@ -100,7 +218,14 @@ pin_project! {
         #[pin]
         state: AwsChunkedBodyState,
         options: AwsChunkedBodyOptions,
-        inner_body_bytes_read_so_far: usize,
+        inner_body_bytes_read_so_far: u64,
+        // Data of the current unsigned chunk that hasn't been written out yet
+        chunk_bytes_remaining: u64,
+        // Data that was read from the inner body for the next chunk
+        pending: Option<Bytes>,
+        // The signed chunk that is being read from the inner body
+        buffer: BytesMut,
+        signer: Option<Box<dyn SignChunk>>,
     }
 }
 
@@ -112,22 +237,38 @@ impl<Inner> AwsChunkedBody<Inner> {
             state: AwsChunkedBodyState::WritingChunkSize,
             options,
             inner_body_bytes_read_so_far: 0,
+            chunk_bytes_remaining: 0,
+            pending: None,
+            buffer: BytesMut::new(),
+            signer: None,
         }
     }
 
     fn encoded_length(&self) -> u64 {
+        let options = &self.options;
+        let chunk_size = options.chunk_size();
         let mut length = 0;
-        if self.options.stream_length != 0 {
-            length += get_unsigned_chunk_bytes_length(self.options.stream_length);
+
+        // Chunks
+        length += options.stream_length / chunk_size * options.encoded_chunk_length(chunk_size);
+        let last_chunk_length = options.stream_length % chunk_size;
+        if last_chunk_length > 0 {
+            length += options.encoded_chunk_length(last_chunk_length);
         }
 
         // End chunk
-        length += CHUNK_TERMINATOR.len() as u64;
+        length += options.encoded_chunk_length(0);
 
         // Trailers
-        for len in self.options.trailer_lengths.iter() {
+        for len in options.trailer_lengths.iter() {
             length += len + CRLF.len() as u64;
         }
+        if options.is_signed() {
+            length += TRAILER_SIGNATURE_NAME.len() as u64
+                + TRAILER_SEPARATOR.len() as u64
+                + SIGNATURE_LENGTH
+                + CRLF.len() as u64;
+        }
 
         // Encoding terminator
         length += CRLF.len() as u64;
@@ -136,11 +277,6 @@ impl<Inner> AwsChunkedBody<Inner> {
     }
 }
 
-fn get_unsigned_chunk_bytes_length(payload_length: u64) -> u64 {
-    let hex_repr_len = int_log16(payload_length);
-    hex_repr_len + CRLF.len() as u64 + payload_length + CRLF.len() as u64
-}
-
 /// Writes trailers out into a `string` and then converts that `String` to a `Bytes` before
 /// returning.
 ///
@@ -197,6 +333,21 @@ fn total_rendered_length_of_trailers(trailer_map: Option<&HeaderMap>) -> u64 {
     }
 }
 
+/// Polls the data that was read ahead for the next chunk, or else the inner body.
+fn poll_next_data<Inner>(
+    pending: &mut Option<Bytes>,
+    inner: Pin<&mut Inner>,
+    cx: &mut Context<'_>,
+) -> Poll<Option<Result<Bytes, aws_smithy_types::body::Error>>>
+where
+    Inner: Body<Data = Bytes, Error = aws_smithy_types::body::Error>,
+{
+    match pending.take() {
+        Some(data) => Poll::Ready(Some(Ok(data))),
+        None => inner.poll_data(cx),
+    }
+}
+
 impl<Inner> Body for AwsChunkedBody<Inner>
 where
     Inner: Body<Data = Bytes, Error = aws_smithy_types::body::Error>,
@@ -210,75 +361,164 @@ where
     ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
         tracing::trace!(state = ?self.state, "polling AwsChunkedBody");
         let mut this = self.project();
+        let expected_stream_length = this.options.stream_length;
+
+        if let (Some(deferred), None) = (&this.options.chunk_signer, &this.signer) {
+            match deferred.take() {
+                Some(signer) => *this.signer = Some(signer),
+                None => {
+                    return Poll::Ready(Some(Err(AwsChunkedBodyError::MissingChunkSigner.into())))
+                }
+            }
+        }
 
         match *this.state {
             AwsChunkedBodyState::WritingChunkSize => {
-                if this.options.stream_length == 0 {
-                    // If the stream is empty, we skip to writing trailers after writing the CHUNK_TERMINATOR.
+                let chunk_length = this
+                    .options
+                    .chunk_size()
+                    .min(expected_stream_length - *this.inner_body_bytes_read_so_far);
+
+                // Read the whole chunk for signed bodies, or make sure that the inner body was
+                // read completely before writing out the last chunk
+                let is_signed = this.options.is_signed();
+                while chunk_length == 0 || (is_signed && (this.buffer.len() as u64) < chunk_length)
+                {
+                    let read_so_far = *this.inner_body_bytes_read_so_far + this.buffer.len() as u64;
+                    match ready!(poll_next_data(this.pending, this.inner.as_mut(), cx)) {
+                        Some(Ok(data)) if data.is_empty() => continue,
+                        Some(Ok(data)) if chunk_length == 0 => {
+                            let err = AwsChunkedBodyError::StreamLengthMismatch {
+                                actual: read_so_far + data.len() as u64,
+                                expected: expected_stream_length,
+                            };
+                            return Poll::Ready(Some(Err(err.into())));
+                        }
+                        Some(Ok(mut data)) => {
+                            let wanted = (chunk_length - this.buffer.len() as u64) as usize;
+                            if data.len() > wanted {
+                                *this.pending = Some(data.split_off(wanted));
+                            }
+                            this.buffer.extend_from_slice(&data);
+                        }
+                        Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                        None if chunk_length == 0 => break,
+                        None => {
+                            let err = AwsChunkedBodyError::StreamLengthMismatch {
+                                actual: read_so_far,
+                                expected: expected_stream_length,
+                            };
+                            return Poll::Ready(Some(Err(err.into())));
+                        }
+                    }
+                }
+
+                if chunk_length == 0 {
                     *this.state = AwsChunkedBodyState::WritingTrailers;
-                    tracing::trace!("stream is empty, writing chunk terminator");
-                    Poll::Ready(Some(Ok(Bytes::from([CHUNK_TERMINATOR].concat()))))
-                } else {
-                    *this.state = AwsChunkedBodyState::WritingChunk;
-                    // A chunk must be prefixed by chunk size in hexadecimal
-                    let chunk_size = format!("{:X?}{CRLF}", this.options.stream_length);
-                    tracing::trace!(%chunk_size, "writing chunk size");
-                    let chunk_size = Bytes::from(chunk_size);
-                    Poll::Ready(Some(Ok(chunk_size)))
+                }
+                match this.signer.as_mut() {
+                    Some(signer) => {
+                        let chunk = this.buffer.split().freeze();
+                        let signature = match signer.chunk_signature(&chunk) {
+                            Ok(signature) => signature,
+                            Err(e) => return Poll::Ready(Some(Err(e))),
+                        };
+                        tracing::trace!(len = chunk.len(), "writing signed chunk");
+                        *this.inner_body_bytes_read_so_far += chunk.len() as u64;
+
+                        let mut encoded = BytesMut::with_capacity(
+                            this.options.encoded_chunk_length(chunk.len() as u64) as usize,
+                        );
+                        encoded.extend_from_slice(
+                            format!("{:X}{CHUNK_SIGNATURE_PREFIX}{signature}{CRLF}", chunk.len())
+                                .as_bytes(),
+                        );
+                        if !chunk.is_empty() {
+                            encoded.extend_from_slice(&chunk);
+                            encoded.extend_from_slice(CRLF.as_bytes());
+                        }
+                        Poll::Ready(Some(Ok(encoded.freeze())))
+                    }
+                    None if chunk_length == 0 => {
+                        tracing::trace!("stream was written out, writing chunk terminator");
+                        Poll::Ready(Some(Ok(Bytes::from(CHUNK_TERMINATOR))))
+                    }
+                    None => {
+                        *this.state = AwsChunkedBodyState::WritingChunk;
+                        *this.chunk_bytes_remaining = chunk_length;
+                        // A chunk must be prefixed by chunk size in hexadecimal
+                        let chunk_size = format!("{chunk_length:X}{CRLF}");
+                        tracing::trace!(%chunk_size, "writing chunk size");
+                        Poll::Ready(Some(Ok(Bytes::from(chunk_size))))
+                    }
                 }
             }
-            AwsChunkedBodyState::WritingChunk => match this.inner.poll_data(cx) {
-                Poll::Ready(Some(Ok(data))) => {
-                    tracing::trace!(len = data.len(), "writing chunk data");
-                    *this.inner_body_bytes_read_so_far += data.len();
-                    Poll::Ready(Some(Ok(data)))
+            AwsChunkedBodyState::WritingChunk => {
+                if *this.chunk_bytes_remaining == 0 {
+                    tracing::trace!("no more chunk data, writing CRLF");
+                    *this.state = AwsChunkedBodyState::WritingChunkSize;
+                    return Poll::Ready(Some(Ok(Bytes::from(CRLF))));
                 }
-                Poll::Ready(None) => {
-                    let actual_stream_length = *this.inner_body_bytes_read_so_far as u64;
-                    let expected_stream_length = this.options.stream_length;
-                    if actual_stream_length != expected_stream_length {
-                        let err = Box::new(AwsChunkedBodyError::StreamLengthMismatch {
-                            actual: actual_stream_length,
+                match ready!(poll_next_data(this.pending, this.inner.as_mut(), cx)) {
+                    Some(Ok(mut data)) => {
+                        if data.len() as u64 > *this.chunk_bytes_remaining {
+                            *this.pending =
+                                Some(data.split_off(*this.chunk_bytes_remaining as usize));
+                        }
+                        tracing::trace!(len = data.len(), "writing chunk data");
+                        *this.chunk_bytes_remaining -= data.len() as u64;
+                        *this.inner_body_bytes_read_so_far += data.len() as u64;
+                        Poll::Ready(Some(Ok(data)))
+                    }
+                    Some(Err(e)) => Poll::Ready(Some(Err(e))),
+                    None => {
+                        let err = AwsChunkedBodyError::StreamLengthMismatch {
+                            actual: *this.inner_body_bytes_read_so_far,
                             expected: expected_stream_length,
-                        });
-                        return Poll::Ready(Some(Err(err)));
-                    };
-
-                    tracing::trace!("no more chunk data, writing CRLF and chunk terminator");
-                    *this.state = AwsChunkedBodyState::WritingTrailers;
-                    // Since we wrote chunk data, we end it with a CRLF and since we only write
-                    // a single chunk, we write the CHUNK_TERMINATOR immediately after
-                    Poll::Ready(Some(Ok(Bytes::from([CRLF, CHUNK_TERMINATOR].concat()))))
+                        };
+                        Poll::Ready(Some(Err(err.into())))
+                    }
                 }
-                Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
-                Poll::Pending => Poll::Pending,
-            },
+            }
             AwsChunkedBodyState::WritingTrailers => {
-                return match this.inner.poll_trailers(cx) {
-                    Poll::Ready(Ok(trailers)) => {
-                        *this.state = AwsChunkedBodyState::Closed;
-                        let expected_length = total_rendered_length_of_trailers(trailers.as_ref());
-                        let actual_length = this.options.total_trailer_length();
-
-                        if expected_length != actual_length {
-                            let err =
-                                Box::new(AwsChunkedBodyError::ReportedTrailerLengthMismatch {
-                                    actual: actual_length,
-                                    expected: expected_length,
-                                });
-                            return Poll::Ready(Some(Err(err)));
-                        }
-
-                        let mut trailers =
-                            trailers_as_aws_chunked_bytes(trailers, actual_length + 1);
-                        // Insert the final CRLF to close the body
-                        trailers.extend_from_slice(CRLF.as_bytes());
+                let trailers = match ready!(this.inner.poll_trailers(cx)) {
+                    Ok(trailers) => trailers,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                };
+                *this.state = AwsChunkedBodyState::Closed;
+                let expected_length = total_rendered_length_of_trailers(trailers.as_ref());
+                let actual_length = this.options.total_trailer_length();
+
+                if expected_length != actual_length {
+                    let err = Box::new(AwsChunkedBodyError::ReportedTrailerLengthMismatch {
+                        actual: actual_length,
+                        expected: expected_length,
+                    });
+                    return Poll::Ready(Some(Err(err)));
+                }
 
-                        Poll::Ready(Some(Ok(trailers.into())))
+                let trailer_signature = match this.signer.as_mut() {
+                    Some(signer) => {
+                        let empty = HeaderMap::new();
+                        match signer.trailer_signature(trailers.as_ref().unwrap_or(&empty)) {
+                            Ok(signature) => Some(signature),
+                            Err(e) => return Poll::Ready(Some(Err(e))),
+                        }
                     }
-                    Poll::Pending => Poll::Pending,
-                    Poll::Ready(Err(e)) => Poll::Ready(Some(Err(e))),
+                    None => None,
                 };
+
+                let mut trailers = trailers_as_aws_chunked_bytes(trailers, actual_length + 1);
+                if let Some(signature) = trailer_signature {
+                    trailers.extend_from_slice(TRAILER_SIGNATURE_NAME.as_bytes());
+                    trailers.extend_from_slice(TRAILER_SEPARATOR);
+                    trailers.extend_from_slice(signature.as_bytes());
+                    trailers.extend_from_slice(CRLF.as_bytes());
+                }
+                // Insert the final CRLF to close the body
+                trailers.extend_from_slice(CRLF.as_bytes());
+
+                Poll::Ready(Some(Ok(trailers.into())))
             }
             AwsChunkedBodyState::Closed => Poll::Ready(None),
         }
@@ -313,6 +553,9 @@ enum AwsChunkedBodyError {
     /// equal to the actual length of the body returned by the inner `http_body::Body` implementor.
     /// `stream_length` must be correct in order to set an accurate content length header.
     StreamLengthMismatch { actual: u64, expected: u64 },
+    /// Error that occurs when a signed `AwsChunkedBody` is polled before a chunk signer was set,
+    /// which happens when the request that the body is sent with wasn't signed.
+    MissingChunkSigner,
 }
 
 impl std::fmt::Display for AwsChunkedBodyError {
@@ -324,6 +567,9 @@ impl std::fmt::Display for AwsChunkedBodyError {
             Self::StreamLengthMismatch { actual, expected } => {
                 write!(f, "When creating this AwsChunkedBody, stream length was reported as {expected}. However, when double checking during body encoding, length was found to be {actual} instead.")
             }
+            Self::MissingChunkSigner => {
+                write!(f, "This AwsChunkedBody has signed chunks, but no chunk signer was set. The request that it's sent with must be signed before the body is sent.")
+            }
         }
     }
 }
@@ -351,8 +597,9 @@ where
 mod tests {
     use super::{
         total_rendered_length_of_trailers, trailers_as_aws_chunked_bytes, AwsChunkedBody,
-        AwsChunkedBodyOptions, CHUNK_TERMINATOR, CRLF,
+        AwsChunkedBodyOptions, DeferredChunkSigner, SignChunk, CHUNK_TERMINATOR, CRLF,
     };
+    use aws_smithy_runtime_api::box_error::BoxError;
 
     use aws_smithy_types::body::SdkBody;
     use bytes::{Buf, Bytes};
@@ -610,4 +857,138 @@ mod tests {
 
         assert_eq!(expected_length, actual_length);
     }
+
+    pin_project! {
+        struct BodyWithTrailers {
+            #[pin]
+            inner: SdkBody,
+            trailers: Option<HeaderMap>,
+        }
+    }
+
+    impl Body for BodyWithTrailers {
+        type Data = Bytes;
+        type Error = aws_smithy_types::body::Error;
+
+        fn poll_data(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            self.project().inner.poll_data(cx)
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<HeaderMap<HeaderValue>>, Self::Error>> {
+            Poll::Ready(Ok(self.project().trailers.take()))
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestSigner {
+        signatures: usize,
+    }
+
+    impl TestSigner {
+        fn next_signature(&mut self) -> String {
+            self.signatures += 1;
+            format!("{:064}", self.signatures)
+        }
+    }
+
+    impl SignChunk for TestSigner {
+        fn chunk_signature(&mut self, _chunk: &[u8]) -> Result<String, BoxError> {
+            Ok(self.next_signature())
+        }
+
+        fn trailer_signature(&mut self, _trailers: &HeaderMap) -> Result<String, BoxError> {
+            Ok(self.next_signature())
+        }
+    }
+
+    async fn collect_to_string<B: Body<Data = Bytes>>(body: B) -> String
+    where
+        B::Error: std::fmt::Debug,
+    {
+        let mut body = Box::pin(body);
+        let mut output = SegmentedBuf::new();
+        while let Some(buf) = body.data().await {
+            output.push(buf.unwrap());
+        }
+        let mut actual_output = String::new();
+        output
+            .reader()
+            .read_to_string(&mut actual_output)
+            .expect("Doesn't cause IO errors");
+        actual_output
+    }
+
+    #[tokio::test]
+    async fn test_aws_chunked_encoding_with_chunk_size() {
+        let input = SputteringBody {
+            parts: vec![
+                Some(Bytes::from_static(b"chunk 1, ")),
+                None,
+                Some(Bytes::from_static(b"chunk 2")),
+            ],
+            cursor: 0,
+            delay_in_millis: 10,
+        };
+        let opts = AwsChunkedBodyOptions::new(input.len() as u64, Vec::new()).with_chunk_size(4);
+        let body = AwsChunkedBody::new(input, opts);
+        let expected_length = body.size_hint().exact().unwrap();
+
+        let actual_output = collect_to_string(body).await;
+
+        let expected_output = "4\r\nchun\r\n4\r\nk 1,\r\n4\r\n chu\r\n4\r\nnk 2\r\n0\r\n\r\n";
+        assert_eq!(expected_output, actual_output);
+        assert_eq!(expected_length, actual_output.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_aws_chunked_encoding_signed() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-amz-checksum-crc32", HeaderValue::from_static("i9aeUg=="));
+        let trailer_len = total_rendered_length_of_trailers(Some(&trailers)) - CRLF.len() as u64;
+        let input = BodyWithTrailers {
+            inner: SdkBody::from("Hello world"),
+            trailers: Some(trailers),
+        };
+
+        let deferred = DeferredChunkSigner::new();
+        let opts = AwsChunkedBodyOptions::new(11, vec![trailer_len])
+            .with_chunk_size(8)
+            .with_chunk_signer(deferred.clone());
+        let body = AwsChunkedBody::new(input, opts);
+        let expected_length = body.size_hint().exact().unwrap();
+        deferred.set(Box::new(TestSigner { signatures: 0 }));
+
+        let actual_output = collect_to_string(body).await;
+
+        let signature = |n: usize| format!("{n:064}");
+        let expected_output = format!(
+            "8;chunk-signature={}\r\nHello wo\r\n\
+            3;chunk-signature={}\r\nrld\r\n\
+            0;chunk-signature={}\r\n\
+            x-amz-checksum-crc32:i9aeUg==\r\n\
+            x-amz-trailer-signature:{}\r\n\r\n",
+            signature(1),
+            signature(2),
+            signature(3),
+            signature(4),
+        );
+        assert_eq!(expected_output, actual_output);
+        assert_eq!(expected_length, actual_output.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_aws_chunked_encoding_signed_without_signer() {
+        let opts = AwsChunkedBodyOptions::new(11, Vec::new())
+            .with_chunk_signer(DeferredChunkSigner::new());
+        let mut body = AwsChunkedBody::new(SdkBody::from("Hello world"), opts);
+
+        let err = body.data().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("no chunk signer was set"), "{err}");
+    }
 }
@@ -7,6 +7,7 @@ use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use aws_types::app_name::AppName;
 use aws_types::build_metadata::{OsFamily, BUILD_METADATA};
 use aws_types::os_shim_internal::Env;
+use aws_types::user_agent::UserAgentMetadata;
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
@@ -26,6 +27,7 @@ pub struct AwsUserAgent {
     feature_metadata: Vec<FeatureMetadata>,
     config_metadata: Vec<ConfigMetadata>,
     framework_metadata: Vec<FrameworkMetadata>,
+    user_agent_metadata: Option<UserAgentMetadata>,
     app_name: Option<AppName>,
 }
 
@@ -62,6 +64,7 @@ impl AwsUserAgent {
             feature_metadata: Default::default(),
             config_metadata: Default::default(),
             framework_metadata: Default::default(),
+            user_agent_metadata: Default::default(),
             app_name: Default::default(),
         }
     }
@@ -92,6 +95,7 @@ impl AwsUserAgent {
             feature_metadata: Vec::new(),
             config_metadata: Vec::new(),
             framework_metadata: Vec::new(),
+            user_agent_metadata: None,
             app_name: None,
         }
     }
@@ -138,6 +142,18 @@ impl AwsUserAgent {
         self
     }
 
+    /// Sets the custom product tokens and feature flags for the user agent.
+    pub fn with_user_agent_metadata(mut self, metadata: UserAgentMetadata) -> Self {
+        self.user_agent_metadata = Some(metadata);
+        self
+    }
+
+    /// Sets the custom product tokens and feature flags for the user agent.
+    pub fn set_user_agent_metadata(&mut self, metadata: UserAgentMetadata) -> &mut Self {
+        self.user_agent_metadata = Some(metadata);
+        self
+    }
+
     /// Sets the app name for the user agent.
     pub fn with_app_name(mut self, app_name: AppName) -> Self {
         self.app_name = Some(app_name);
@@ -179,12 +195,26 @@ impl AwsUserAgent {
         for feature in &self.feature_metadata {
             write!(ua_value, "{} ", feature).unwrap();
         }
+        // Custom feature flags and product tokens were validated when the metadata was built
+        let custom = self.user_agent_metadata.as_ref();
+        for flag in custom
+            .into_iter()
+            .flat_map(UserAgentMetadata::feature_flags)
+        {
+            write!(ua_value, "ft/{} ", flag).unwrap();
+        }
         for config in &self.config_metadata {
             write!(ua_value, "{} ", config).unwrap();
         }
         for framework in &self.framework_metadata {
             write!(ua_value, "{} ", framework).unwrap();
         }
+        for product in custom.into_iter().flat_map(UserAgentMetadata::products) {
+            match product.version() {
+                Some(version) => write!(ua_value, "lib/{}/{} ", product.name(), version).unwrap(),
+                None => write!(ua_value, "lib/{} ", product.name()).unwrap(),
+            }
+        }
         if let Some(app_name) = &self.app_name {
             write!(ua_value, "app/{}", app_name).unwrap();
         }
@@ -523,6 +553,7 @@ mod test {
     use aws_types::app_name::AppName;
     use aws_types::build_metadata::OsFamily;
     use aws_types::os_shim_internal::Env;
+    use aws_types::user_agent::ProductToken;
     use std::borrow::Cow;
 
     fn make_deterministic(ua: &mut AwsUserAgent) {
@@ -644,6 +675,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn generate_a_valid_ua_with_user_agent_metadata() {
+        let api_metadata = ApiMetadata {
+            service_id: "dynamodb".into(),
+            version: "123",
+        };
+        let metadata = UserAgentMetadata::builder()
+            .product(ProductToken::new("my-platform").with_version("2.1.0"))
+            .product(ProductToken::new("plugin"))
+            .feature_flag("batching")
+            .build()
+            .unwrap();
+        let mut ua = AwsUserAgent::new_from_environment(Env::from_slice(&[]), api_metadata)
+            .with_feature_metadata(FeatureMetadata::new("test-feature", None).unwrap())
+            .with_framework_metadata(FrameworkMetadata::new("other", None).unwrap())
+            .with_user_agent_metadata(metadata)
+            .with_app_name(AppName::new("my_app").unwrap());
+        make_deterministic(&mut ua);
+        assert_eq!(
+            ua.aws_ua_header(),
+            "aws-sdk-rust/0.1 api/dynamodb/123 os/macos/1.15 lang/rust/1.50.0 ft/test-feature ft/batching lib/other lib/my-platform/2.1.0 lib/plugin app/my_app"
+        );
+        assert_eq!(
+            ua.ua_header(),
+            "aws-sdk-rust/0.1 os/macos/1.15 lang/rust/1.50.0"
+        );
+    }
+
     #[test]
     fn generate_a_valid_ua_with_app_name() {
         let api_metadata = ApiMetadata {
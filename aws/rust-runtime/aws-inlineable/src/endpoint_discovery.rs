@@ -9,9 +9,11 @@ use aws_smithy_async::future::BoxFuture;
 use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
 use aws_smithy_async::time::SharedTimeSource;
 use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::cancellation::CancellationToken;
 use aws_smithy_runtime_api::client::endpoint::{
     EndpointFuture, EndpointResolverParams, ResolveEndpoint,
 };
+use aws_smithy_runtime_api::client::shutdown::ClientShutdown;
 use aws_smithy_types::endpoint::Endpoint;
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
@@ -27,6 +29,7 @@ pub struct ReloadEndpoint {
     endpoint: Arc<Mutex<Option<ExpiringEndpoint>>>,
     error: Arc<Mutex<Option<BoxError>>>,
     rx: Receiver<()>,
+    shutdown: Option<CancellationToken>,
     sleep: SharedAsyncSleep,
     time: SharedTimeSource,
 }
@@ -49,17 +52,35 @@ impl ReloadEndpoint {
         }
     }
 
+    /// Stop reloading the endpoint once the given client shutdown starts
+    pub(crate) fn stop_on_shutdown(mut self, shutdown: &ClientShutdown) -> Self {
+        self.shutdown = Some(shutdown.background_task_token().clone());
+        self
+    }
+
     /// An infinite loop task that will reload the endpoint
     ///
-    /// This task will terminate when the corresponding [`Client`](crate::Client) is dropped.
+    /// This task will terminate when the corresponding [`Client`](crate::Client) is dropped or
+    /// shut down.
     pub async fn reload_task(mut self) {
         loop {
             match self.rx.try_recv() {
                 Ok(_) | Err(TryRecvError::Closed) => break,
                 _ => {}
             }
-            self.reload_increment(self.time.now()).await;
-            self.sleep.sleep(Duration::from_secs(60)).await;
+            let sleep = async {
+                self.reload_increment(self.time.now()).await;
+                self.sleep.sleep(Duration::from_secs(60)).await;
+            };
+            match &self.shutdown {
+                Some(shutdown) => {
+                    if shutdown.run_until_cancelled(sleep).await.is_none() {
+                        tracing::debug!("client was shut down, no longer reloading the endpoint");
+                        break;
+                    }
+                }
+                None => sleep.await,
+            }
         }
     }
 
@@ -129,6 +150,7 @@ where
         endpoint: endpoint_holder,
         error: error_holder,
         rx,
+        shutdown: None,
         sleep,
         time,
     };
@@ -163,6 +185,7 @@ mod test {
     use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
     use aws_smithy_async::test_util::controlled_time_and_sleep;
     use aws_smithy_async::time::{SharedTimeSource, SystemTimeSource, TimeSource};
+    use aws_smithy_runtime_api::client::shutdown::ClientShutdown;
     use aws_smithy_types::endpoint::Endpoint;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
@@ -298,4 +321,33 @@ mod test {
             .expect("task finishes successfully")
             .expect("finishes");
     }
+
+    #[tokio::test]
+    async fn reload_task_stops_on_shutdown() {
+        let expiry = UNIX_EPOCH + Duration::from_secs(123456789);
+        let (time, sleep, mut gate) = controlled_time_and_sleep(expiry - Duration::from_secs(239));
+        let (_cache, reloader) = create_cache(
+            move || async move { Ok((Endpoint::builder().url("http://foo.com").build(), expiry)) },
+            SharedAsyncSleep::new(sleep.clone()),
+            SharedTimeSource::new(time.clone()),
+        )
+        .await
+        .expect("first load success");
+        let shutdown = ClientShutdown::new();
+        let reload_task = tokio::spawn(reloader.stop_on_shutdown(&shutdown).reload_task());
+        assert_eq!(
+            gate.expect_sleep().await.duration(),
+            Duration::from_secs(60)
+        );
+
+        // The cache is still alive, but the task stops without waiting for the sleep
+        shutdown
+            .shutdown(Duration::from_secs(5), None)
+            .await
+            .expect("nothing in flight");
+        timeout(Duration::from_secs(1), reload_task)
+            .await
+            .expect("task finishes successfully")
+            .expect("finishes");
+    }
 }
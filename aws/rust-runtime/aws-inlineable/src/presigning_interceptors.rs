@@ -31,14 +31,20 @@ use std::borrow::Cow;
 
 /// Interceptor that tells the SigV4 signer to add the signature to query params,
 /// and sets the request expiration time from the presigning config.
+///
+/// Without a payload override, the serialized request body is signed, and streaming request
+/// bodies are signed with `UNSIGNED-PAYLOAD`.
 #[derive(Debug)]
 pub(crate) struct SigV4PresigningInterceptor {
     config: PresigningConfig,
-    payload_override: SignableBody<'static>,
+    payload_override: Option<SignableBody<'static>>,
 }
 
 impl SigV4PresigningInterceptor {
-    pub(crate) fn new(config: PresigningConfig, payload_override: SignableBody<'static>) -> Self {
+    pub(crate) fn new(
+        config: PresigningConfig,
+        payload_override: Option<SignableBody<'static>>,
+    ) -> Self {
         Self {
             config,
             payload_override,
@@ -75,7 +81,9 @@ impl Intercept for SigV4PresigningInterceptor {
         if let Some(mut config) = cfg.load::<SigV4OperationSigningConfig>().cloned() {
             config.signing_options.expires_in = Some(self.config.expires());
             config.signing_options.signature_type = HttpSignatureType::HttpRequestQueryParams;
-            config.signing_options.payload_override = Some(self.payload_override.clone());
+            if let Some(payload_override) = &self.payload_override {
+                config.signing_options.payload_override = Some(payload_override.clone());
+            }
             cfg.interceptor_state()
                 .store_put::<SigV4OperationSigningConfig>(config);
             Ok(())
@@ -95,7 +103,10 @@ pub(crate) struct SigV4PresigningRuntimePlugin {
 }
 
 impl SigV4PresigningRuntimePlugin {
-    pub(crate) fn new(config: PresigningConfig, payload_override: SignableBody<'static>) -> Self {
+    pub(crate) fn new(
+        config: PresigningConfig,
+        payload_override: Option<SignableBody<'static>>,
+    ) -> Self {
         let time_source = SharedTimeSource::new(StaticTimeSource::new(config.start_time()));
         Self {
             runtime_components: RuntimeComponentsBuilder::new("SigV4PresigningRuntimePlugin")
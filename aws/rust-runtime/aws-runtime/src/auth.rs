@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+pub use aws_sigv4::http_request::SigningDetails;
 use aws_sigv4::http_request::{
     PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignableBody, SignatureLocation,
     SigningInstructions, SigningSettings, UriPathNormalizationMode,
@@ -26,6 +27,9 @@ pub mod sigv4;
 /// Auth implementations for SigV4a.
 pub mod sigv4a;
 
+/// Auth implementations for SigV4 with session credentials.
+pub mod session;
+
 /// Type of SigV4 signature.
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum HttpSignatureType {
@@ -58,6 +62,11 @@ pub struct SigningOptions {
     pub signing_optional: bool,
     /// Optional expiration (for presigning)
     pub expires_in: Option<Duration>,
+    /// Capture the canonical request and string-to-sign of each signed request.
+    ///
+    /// When enabled, the [`SigningDetails`] are added to the extensions of the signed request,
+    /// where interceptors can read them with `request.extension::<SigningDetails>()`.
+    pub capture_signing_details: bool,
 }
 
 impl Default for SigningOptions {
@@ -71,6 +80,7 @@ impl Default for SigningOptions {
             signature_type: HttpSignatureType::HttpRequestHeaders,
             signing_optional: false,
             expires_in: None,
+            capture_signing_details: false,
         }
     }
 }
@@ -99,6 +109,117 @@ impl Storable for SigV4OperationSigningConfig {
     type Storer = StoreReplace<Self>;
 }
 
+/// Overrides for the SigV4 signing options of an operation.
+///
+/// Options that aren't set keep the value that the operation signs with by default. This is
+/// usually set for a single operation with a config override, for example to send a large
+/// streaming upload with an `UNSIGNED-PAYLOAD` payload hash.
+///
+/// The override can also be stored in the config bag by an interceptor, before the request is
+/// signed. The signing name and region of the override take precedence over the ones from the
+/// endpoint, which is useful when requests are sent to a custom domain that fronts a service.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SigningOptionsOverride {
+    signing_name: Option<SigningName>,
+    signing_region: Option<SigningRegion>,
+    unsigned_payload: Option<bool>,
+    double_uri_encode: Option<bool>,
+    normalize_uri_path: Option<bool>,
+    omit_session_token: Option<bool>,
+    expires_in: Option<Duration>,
+    capture_signing_details: Option<bool>,
+}
+
+impl SigningOptionsOverride {
+    /// Creates an override that doesn't change any signing options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signs the request for the given service name, rather than the one from the endpoint.
+    pub fn signing_name(mut self, signing_name: SigningName) -> Self {
+        self.signing_name = Some(signing_name);
+        self
+    }
+
+    /// Signs the request for the given region, rather than the one from the endpoint.
+    ///
+    /// This doesn't apply to SigV4a, which signs for a region set instead.
+    pub fn signing_region(mut self, signing_region: SigningRegion) -> Self {
+        self.signing_region = Some(signing_region);
+        self
+    }
+
+    /// Signs the request with an `UNSIGNED-PAYLOAD` payload hash instead of hashing the body.
+    pub fn unsigned_payload(mut self, unsigned_payload: bool) -> Self {
+        self.unsigned_payload = Some(unsigned_payload);
+        self
+    }
+
+    /// Applies URI encoding twice (the default for most services) or once.
+    pub fn double_uri_encode(mut self, double_uri_encode: bool) -> Self {
+        self.double_uri_encode = Some(double_uri_encode);
+        self
+    }
+
+    /// Normalizes the URI path before signing.
+    pub fn normalize_uri_path(mut self, normalize_uri_path: bool) -> Self {
+        self.normalize_uri_path = Some(normalize_uri_path);
+        self
+    }
+
+    /// Omits the session token from the signature, and adds it to the request after signing.
+    pub fn omit_session_token(mut self, omit_session_token: bool) -> Self {
+        self.omit_session_token = Some(omit_session_token);
+        self
+    }
+
+    /// Sets how long the signature is valid for.
+    pub fn expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = Some(expires_in);
+        self
+    }
+
+    /// Captures the canonical request and string-to-sign of each signed request.
+    ///
+    /// This is useful to diagnose `SignatureDoesNotMatch` errors. See
+    /// [`SigningOptions::capture_signing_details`] for how to retrieve them.
+    pub fn capture_signing_details(mut self, capture_signing_details: bool) -> Self {
+        self.capture_signing_details = Some(capture_signing_details);
+        self
+    }
+
+    /// Applies the overrides to the given signing options.
+    pub fn apply(&self, signing_options: &mut SigningOptions) {
+        if let Some(unsigned_payload) = self.unsigned_payload {
+            signing_options.payload_override = if unsigned_payload {
+                Some(SignableBody::UnsignedPayload)
+            } else {
+                None
+            };
+        }
+        if let Some(double_uri_encode) = self.double_uri_encode {
+            signing_options.double_uri_encode = double_uri_encode;
+        }
+        if let Some(normalize_uri_path) = self.normalize_uri_path {
+            signing_options.normalize_uri_path = normalize_uri_path;
+        }
+        if let Some(omit_session_token) = self.omit_session_token {
+            signing_options.omit_session_token = omit_session_token;
+        }
+        if let Some(expires_in) = self.expires_in {
+            signing_options.expires_in = Some(expires_in);
+        }
+        if let Some(capture_signing_details) = self.capture_signing_details {
+            signing_options.capture_signing_details = capture_signing_details;
+        }
+    }
+}
+
+impl Storable for SigningOptionsOverride {
+    type Storer = StoreReplace<Self>;
+}
+
 fn settings(operation_config: &SigV4OperationSigningConfig) -> SigningSettings {
     let mut settings = SigningSettings::default();
     settings.percent_encoding_mode = if operation_config.signing_options.double_uri_encode {
@@ -126,6 +247,7 @@ fn settings(operation_config: &SigV4OperationSigningConfig) -> SigningSettings {
         HttpSignatureType::HttpRequestQueryParams => SignatureLocation::QueryParams,
     };
     settings.expires_in = operation_config.signing_options.expires_in;
+    settings.capture_signing_details = operation_config.signing_options.capture_signing_details;
     settings
 }
 
@@ -203,6 +325,13 @@ fn apply_signing_instructions(
     instructions: SigningInstructions,
     request: &mut HttpRequest,
 ) -> Result<(), BoxError> {
+    if let Some(details) = instructions.signing_details() {
+        tracing::debug!(
+            string_to_sign = details.string_to_sign(),
+            "captured signing details"
+        );
+        request.add_extension(details.clone());
+    }
     let (new_headers, new_query) = instructions.into_parts();
     for header in new_headers.into_iter() {
         let mut value = http::HeaderValue::from_str(header.value()).unwrap();
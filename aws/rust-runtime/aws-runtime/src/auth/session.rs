@@ -0,0 +1,181 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::auth::sigv4::SigV4Signer;
+use crate::auth::SigV4SigningError;
+use aws_credential_types::Credentials;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::{
+    AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, Sign,
+};
+use aws_smithy_runtime_api::client::identity::{Identity, SharedIdentityResolver};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::runtime_components::{GetIdentityResolver, RuntimeComponents};
+use aws_smithy_types::config_bag::ConfigBag;
+
+/// SigV4 auth scheme for session credentials whose session token is sent in a dedicated header.
+///
+/// Session credentials are short-lived credentials that are created by a bootstrap operation,
+/// usually for a single resource, and resolved by an identity resolver such as
+/// `aws_smithy_runtime::client::identity::session::SessionIdentityResolver`. Requests are signed
+/// with SigV4, but the session token is sent in the given header rather than in the
+/// `x-amz-security-token` header.
+#[derive(Debug)]
+pub struct SigV4SessionAuthScheme {
+    scheme_id: AuthSchemeId,
+    signer: SigV4SessionSigner,
+}
+
+impl SigV4SessionAuthScheme {
+    /// Creates a new `SigV4SessionAuthScheme` that sends session tokens in `session_token_header`.
+    pub fn new(scheme_id: AuthSchemeId, session_token_header: &'static str) -> Self {
+        Self {
+            scheme_id,
+            signer: SigV4SessionSigner::new(session_token_header),
+        }
+    }
+}
+
+impl AuthScheme for SigV4SessionAuthScheme {
+    fn scheme_id(&self) -> AuthSchemeId {
+        self.scheme_id
+    }
+
+    fn identity_resolver(
+        &self,
+        identity_resolvers: &dyn GetIdentityResolver,
+    ) -> Option<SharedIdentityResolver> {
+        identity_resolvers.identity_resolver(self.scheme_id())
+    }
+
+    fn signer(&self) -> &dyn Sign {
+        &self.signer
+    }
+}
+
+/// SigV4 signer for session credentials whose session token is sent in a dedicated header.
+#[derive(Debug)]
+pub struct SigV4SessionSigner {
+    session_token_header: &'static str,
+}
+
+impl SigV4SessionSigner {
+    /// Creates a new signer that sends session tokens in `session_token_header`.
+    pub fn new(session_token_header: &'static str) -> Self {
+        Self {
+            session_token_header,
+        }
+    }
+}
+
+impl Sign for SigV4SessionSigner {
+    fn sign_http_request(
+        &self,
+        request: &mut HttpRequest,
+        identity: &Identity,
+        auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+        runtime_components: &RuntimeComponents,
+        config_bag: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        let creds = identity
+            .data::<Credentials>()
+            .ok_or_else(|| SigV4SigningError::WrongIdentityType(identity.clone()))?;
+        let session_token = creds
+            .session_token()
+            .ok_or("session credentials must have a session token")?;
+        // The session token header is set before signing, so that it's signed
+        request
+            .headers_mut()
+            .try_insert(self.session_token_header, session_token.to_owned())?;
+
+        let signing_creds = Credentials::new(
+            creds.access_key_id(),
+            creds.secret_access_key(),
+            None,
+            creds.expiry(),
+            "SessionCredentials",
+        );
+        let signing_identity = Identity::new(signing_creds, identity.expiration());
+        SigV4Signer::new().sign_http_request(
+            request,
+            &signing_identity,
+            auth_scheme_endpoint_config,
+            runtime_components,
+            config_bag,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::SigV4OperationSigningConfig;
+    use aws_smithy_async::time::{SharedTimeSource, StaticTimeSource};
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::Layer;
+    use aws_types::region::SigningRegion;
+    use aws_types::SigningName;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn session_token_is_sent_in_dedicated_header() {
+        let mut layer = Layer::new("test");
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("s3")),
+            ..Default::default()
+        });
+        let config_bag = ConfigBag::of_layers(vec![layer]);
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(SharedTimeSource::new(StaticTimeSource::new(
+                UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            ))))
+            .build()
+            .unwrap();
+        let identity = Credentials::for_tests_with_session_token().into();
+
+        let mut request = HttpRequest::new(SdkBody::empty());
+        request.set_uri("https://bucket.example.com/key").unwrap();
+        SigV4SessionSigner::new("x-amz-example-session-token")
+            .sign_http_request(
+                &mut request,
+                &identity,
+                AuthSchemeEndpointConfig::empty(),
+                &runtime_components,
+                &config_bag,
+            )
+            .unwrap();
+
+        assert_eq!(
+            Some("notarealsessiontoken"),
+            request.headers().get("x-amz-example-session-token")
+        );
+        assert_eq!(None, request.headers().get("x-amz-security-token"));
+        let authorization = request.headers().get("authorization").unwrap();
+        assert!(
+            authorization.contains("x-amz-example-session-token"),
+            "{}",
+            authorization
+        );
+    }
+
+    #[test]
+    fn credentials_without_session_token_are_rejected() {
+        let runtime_components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let identity = Credentials::for_tests().into();
+        let mut request = HttpRequest::new(SdkBody::empty());
+        let err = SigV4SessionSigner::new("x-amz-example-session-token")
+            .sign_http_request(
+                &mut request,
+                &identity,
+                AuthSchemeEndpointConfig::empty(),
+                &runtime_components,
+                &ConfigBag::base(),
+            )
+            .expect_err("no session token");
+        assert!(format!("{}", err).contains("session token"), "{}", err);
+    }
+}
@@ -6,9 +6,10 @@
 use crate::auth;
 use crate::auth::{
     extract_endpoint_auth_scheme_signing_name, extract_endpoint_auth_scheme_signing_region,
-    SigV4OperationSigningConfig, SigV4SigningError,
+    SigV4OperationSigningConfig, SigV4SigningError, SigningOptionsOverride,
 };
 use aws_credential_types::Credentials;
+use aws_http::content_encoding::DeferredChunkSigner;
 use aws_sigv4::http_request::{
     sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
 };
@@ -125,18 +126,30 @@ impl SigV4Signer {
             .load::<SigV4OperationSigningConfig>()
             .ok_or(SigV4SigningError::MissingOperationSigningConfig)?;
 
-        let name = extract_endpoint_auth_scheme_signing_name(&auth_scheme_endpoint_config)?
-            .or(config_bag.load::<SigningName>().cloned());
+        let options_override = config_bag.load::<SigningOptionsOverride>();
 
-        let region = extract_endpoint_auth_scheme_signing_region(&auth_scheme_endpoint_config)?
-            .or(config_bag.load::<SigningRegion>().cloned());
+        // The signing name and region of an override take precedence over the endpoint's
+        let name = match options_override.and_then(|o| o.signing_name.clone()) {
+            Some(name) => Some(name),
+            None => extract_endpoint_auth_scheme_signing_name(&auth_scheme_endpoint_config)?
+                .or(config_bag.load::<SigningName>().cloned()),
+        };
+
+        let region = match options_override.and_then(|o| o.signing_region.clone()) {
+            Some(region) => Some(region),
+            None => extract_endpoint_auth_scheme_signing_region(&auth_scheme_endpoint_config)?
+                .or(config_bag.load::<SigningRegion>().cloned()),
+        };
 
-        match (region, name) {
-            (None, None) => Ok(Cow::Borrowed(operation_config)),
-            (region, name) => {
+        match (region, name, options_override) {
+            (None, None, None) => Ok(Cow::Borrowed(operation_config)),
+            (region, name, options_override) => {
                 let mut operation_config = operation_config.clone();
                 operation_config.region = region.or(operation_config.region);
                 operation_config.name = name.or(operation_config.name);
+                if let Some(options_override) = options_override {
+                    options_override.apply(&mut operation_config.signing_options);
+                }
                 Ok(Cow::Owned(operation_config))
             }
         }
@@ -164,16 +177,23 @@ impl Sign for SigV4Signer {
         let signing_params =
             Self::signing_params(settings, identity, &operation_config, request_time)?;
 
-        let (signing_instructions, _signature) = {
+        // Bodies with signed `aws-chunked` encoding are signed chunk by chunk, after the request
+        let chunk_signer = config_bag.load::<DeferredChunkSigner>();
+
+        let (signing_instructions, signature) = {
             // A body that is already in memory can be signed directly. A body that is not in memory
             // (any sort of streaming body or presigned request) will be signed via UNSIGNED-PAYLOAD.
-            let signable_body = operation_config
-                .signing_options
-                .payload_override
-                .as_ref()
-                // the payload_override is a cheap clone because it contains either a
-                // reference or a short checksum (we're not cloning the entire body)
-                .cloned()
+            let signable_body = chunk_signer
+                .map(|_| SignableBody::StreamingSignedPayloadTrailer)
+                .or_else(|| {
+                    operation_config
+                        .signing_options
+                        .payload_override
+                        .as_ref()
+                        // the payload_override is a cheap clone because it contains either a
+                        // reference or a short checksum (we're not cloning the entire body)
+                        .cloned()
+                })
                 .unwrap_or_else(|| {
                     request
                         .body()
@@ -192,11 +212,21 @@ impl Sign for SigV4Signer {
         }
         .into_parts();
 
+        if let Some(chunk_signer) = chunk_signer {
+            chunk_signer.set(Box::new(chunk::SigV4ChunkSigner::new(
+                signature.clone(),
+                identity.clone(),
+                operation_config.region.clone().unwrap(),
+                operation_config.name.clone().unwrap(),
+                request_time,
+            )));
+        }
+
         // If this is an event stream operation, set up the event stream signer
         #[cfg(feature = "event-stream")]
         {
             use aws_smithy_eventstream::frame::DeferredSignerSender;
-            use event_stream::SigV4MessageSigner;
+            use event_stream::{SharedEventStreamMessageSigner, SigV4MessageSigner};
 
             if let Some(signer_sender) = config_bag.load::<DeferredSignerSender>() {
                 let time_source = runtime_components.time_source().unwrap_or_default();
@@ -204,11 +234,15 @@ impl Sign for SigV4Signer {
                 let name = operation_config.name.clone().unwrap();
                 signer_sender
                     .send(Box::new(SigV4MessageSigner::new(
-                        _signature,
+                        signature,
                         identity.clone(),
                         region,
                         name,
                         time_source,
+                        config_bag
+                            .load::<SharedEventStreamMessageSigner>()
+                            .cloned()
+                            .unwrap_or_default(),
                     )) as _)
                     .expect("failed to send deferred signer");
             }
@@ -218,34 +252,35 @@ impl Sign for SigV4Signer {
     }
 }
 
-#[cfg(feature = "event-stream")]
-mod event_stream {
-    use aws_sigv4::event_stream::{sign_empty_message, sign_message};
+mod chunk {
+    use aws_http::content_encoding::SignChunk;
+    use aws_sigv4::chunk::{sign_chunk, sign_trailers};
     use aws_sigv4::sign::v4;
-    use aws_smithy_async::time::SharedTimeSource;
-    use aws_smithy_eventstream::frame::{SignMessage, SignMessageError};
+    use aws_smithy_runtime_api::box_error::BoxError;
     use aws_smithy_runtime_api::client::identity::Identity;
-    use aws_smithy_types::event_stream::Message;
     use aws_types::region::SigningRegion;
     use aws_types::SigningName;
+    use http::HeaderMap;
+    use std::time::SystemTime;
 
-    /// Event Stream SigV4 signing implementation.
+    /// `aws-chunked` body SigV4 signing implementation.
     #[derive(Debug)]
-    pub(super) struct SigV4MessageSigner {
+    pub(super) struct SigV4ChunkSigner {
         last_signature: String,
         identity: Identity,
         signing_region: SigningRegion,
         signing_name: SigningName,
-        time: SharedTimeSource,
+        // Chunks are signed with the time that the request was signed with
+        time: SystemTime,
     }
 
-    impl SigV4MessageSigner {
+    impl SigV4ChunkSigner {
         pub(super) fn new(
             last_signature: String,
             identity: Identity,
             signing_region: SigningRegion,
             signing_name: SigningName,
-            time: SharedTimeSource,
+            time: SystemTime,
         ) -> Self {
             Self {
                 last_signature,
@@ -261,41 +296,279 @@ mod event_stream {
                 .identity(&self.identity)
                 .region(self.signing_region.as_ref())
                 .name(self.signing_name.as_ref())
-                .time(self.time.now())
+                .time(self.time)
                 .settings(());
             builder.build().unwrap()
         }
     }
 
-    impl SignMessage for SigV4MessageSigner {
-        fn sign(&mut self, message: Message) -> Result<Message, SignMessageError> {
+    impl SignChunk for SigV4ChunkSigner {
+        fn chunk_signature(&mut self, chunk: &[u8]) -> Result<String, BoxError> {
+            let (_, signature) =
+                sign_chunk(chunk, &self.last_signature, &self.signing_params())?.into_parts();
+            self.last_signature = signature.clone();
+            Ok(signature)
+        }
+
+        fn trailer_signature(&mut self, trailers: &HeaderMap) -> Result<String, BoxError> {
+            let trailers = trailers
+                .iter()
+                .map(|(name, value)| Ok((name.as_str(), value.to_str()?)))
+                .collect::<Result<Vec<_>, BoxError>>()?;
+            let (_, signature) =
+                sign_trailers(trailers, &self.last_signature, &self.signing_params())?.into_parts();
+            self.last_signature = signature.clone();
+            Ok(signature)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::auth::sigv4::chunk::SigV4ChunkSigner;
+        use aws_credential_types::Credentials;
+        use aws_http::content_encoding::SignChunk;
+        use aws_types::region::SigningRegion;
+        use aws_types::SigningName;
+        use http::{HeaderMap, HeaderValue};
+        use std::time::{Duration, UNIX_EPOCH};
+
+        fn check_send_sync<T: Send + Sync>(value: T) -> T {
+            value
+        }
+
+        #[test]
+        fn sign_chunks() {
+            let mut signer = check_send_sync(SigV4ChunkSigner::new(
+                "initial-signature".into(),
+                Credentials::for_tests_with_session_token().into(),
+                SigningRegion::from_static("us-east-1"),
+                SigningName::from_static("s3"),
+                UNIX_EPOCH + Duration::new(1611160427, 0),
+            ));
+            let mut signatures = Vec::new();
+            for _ in 0..3 {
+                signatures.push(signer.chunk_signature(b"identical chunk").unwrap());
+            }
+            signatures.push(signer.chunk_signature(b"").unwrap());
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-amz-checksum-crc32", HeaderValue::from_static("i9aeUg=="));
+            signatures.push(signer.trailer_signature(&trailers).unwrap());
+
+            for i in 1..signatures.len() {
+                assert_eq!(64, signatures[i].len());
+                assert_ne!(signatures[i - 1], signatures[i]);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "event-stream")]
+/// Event stream message signing.
+///
+/// Messages of event stream operations are signed with SigV4 by default. A different
+/// [message signer](SignEventStreamMessage) can be stored in the config bag, for example with a
+/// service config's `event_stream_message_signer` method, to sign messages with a different
+/// scheme, or to make message signatures predictable in tests.
+pub mod event_stream {
+    use aws_sigv4::event_stream::{sign_empty_message, sign_message};
+    use aws_sigv4::sign::v4;
+    use aws_sigv4::SigningOutput;
+    use aws_smithy_async::time::SharedTimeSource;
+    use aws_smithy_eventstream::frame::{SignMessage, SignMessageError};
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::identity::Identity;
+    use aws_smithy_types::config_bag::{Storable, StoreReplace};
+    use aws_smithy_types::event_stream::Message;
+    use aws_types::region::SigningRegion;
+    use aws_types::SigningName;
+    use std::fmt;
+    use std::sync::Arc;
+    use std::time::SystemTime;
+
+    /// What an event stream message is signed with.
+    #[derive(Debug)]
+    pub struct MessageSigningContext<'a> {
+        prior_signature: &'a str,
+        time: SystemTime,
+        identity: &'a Identity,
+        signing_region: &'a SigningRegion,
+        signing_name: &'a SigningName,
+    }
+
+    impl<'a> MessageSigningContext<'a> {
+        /// Returns the signature of the previous message, or the signature of the request for
+        /// the first message.
+        pub fn prior_signature(&self) -> &'a str {
+            self.prior_signature
+        }
+
+        /// Returns the time to sign the message at.
+        ///
+        /// This is the time of the client's time source, which is also used to sign the request.
+        pub fn time(&self) -> SystemTime {
+            self.time
+        }
+
+        /// Returns the identity that the request was signed with.
+        pub fn identity(&self) -> &'a Identity {
+            self.identity
+        }
+
+        /// Returns the region that the request was signed for.
+        pub fn signing_region(&self) -> &'a SigningRegion {
+            self.signing_region
+        }
+
+        /// Returns the service name that the request was signed for.
+        pub fn signing_name(&self) -> &'a SigningName {
+            self.signing_name
+        }
+    }
+
+    /// Signs the messages of an event stream.
+    pub trait SignEventStreamMessage: fmt::Debug + Send + Sync {
+        /// Signs the given message, or the empty message that ends the stream if `message` is
+        /// `None`.
+        ///
+        /// Returns the signed message, and the signature that the next message is signed with.
+        fn sign_message(
+            &self,
+            message: Option<Message>,
+            context: &MessageSigningContext<'_>,
+        ) -> Result<SigningOutput<Message>, BoxError>;
+    }
+
+    /// Event stream message signer that can be shared and stored in the config bag.
+    #[derive(Clone, Debug)]
+    pub struct SharedEventStreamMessageSigner(Arc<dyn SignEventStreamMessage>);
+
+    impl SharedEventStreamMessageSigner {
+        /// Creates a new `SharedEventStreamMessageSigner`.
+        pub fn new(signer: impl SignEventStreamMessage + 'static) -> Self {
+            Self(Arc::new(signer))
+        }
+    }
+
+    impl SignEventStreamMessage for SharedEventStreamMessageSigner {
+        fn sign_message(
+            &self,
+            message: Option<Message>,
+            context: &MessageSigningContext<'_>,
+        ) -> Result<SigningOutput<Message>, BoxError> {
+            self.0.sign_message(message, context)
+        }
+    }
+
+    impl Storable for SharedEventStreamMessageSigner {
+        type Storer = StoreReplace<Self>;
+    }
+
+    impl Default for SharedEventStreamMessageSigner {
+        fn default() -> Self {
+            Self::new(SigV4EventStreamMessageSigner::new())
+        }
+    }
+
+    /// Signs event stream messages with SigV4.
+    #[derive(Debug, Default)]
+    #[non_exhaustive]
+    pub struct SigV4EventStreamMessageSigner;
+
+    impl SigV4EventStreamMessageSigner {
+        /// Creates a new `SigV4EventStreamMessageSigner`.
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    impl SignEventStreamMessage for SigV4EventStreamMessageSigner {
+        fn sign_message(
+            &self,
+            message: Option<Message>,
+            context: &MessageSigningContext<'_>,
+        ) -> Result<SigningOutput<Message>, BoxError> {
+            let params = v4::SigningParams::builder()
+                .identity(context.identity)
+                .region(context.signing_region.as_ref())
+                .name(context.signing_name.as_ref())
+                .time(context.time)
+                .settings(())
+                .build()?;
+            Ok(match message {
+                Some(message) => sign_message(&message, context.prior_signature, &params)?,
+                None => sign_empty_message(context.prior_signature, &params)?,
+            })
+        }
+    }
+
+    /// Event Stream signing implementation that tracks the signature of the previous message.
+    #[derive(Debug)]
+    pub(super) struct SigV4MessageSigner {
+        last_signature: String,
+        identity: Identity,
+        signing_region: SigningRegion,
+        signing_name: SigningName,
+        time: SharedTimeSource,
+        signer: SharedEventStreamMessageSigner,
+    }
+
+    impl SigV4MessageSigner {
+        pub(super) fn new(
+            last_signature: String,
+            identity: Identity,
+            signing_region: SigningRegion,
+            signing_name: SigningName,
+            time: SharedTimeSource,
+            signer: SharedEventStreamMessageSigner,
+        ) -> Self {
+            Self {
+                last_signature,
+                identity,
+                signing_region,
+                signing_name,
+                time,
+                signer,
+            }
+        }
+
+        fn sign_message(&mut self, message: Option<Message>) -> Result<Message, BoxError> {
             let (signed_message, signature) = {
-                let params = self.signing_params();
-                sign_message(&message, &self.last_signature, &params)?.into_parts()
+                let context = MessageSigningContext {
+                    prior_signature: &self.last_signature,
+                    time: self.time.now(),
+                    identity: &self.identity,
+                    signing_region: &self.signing_region,
+                    signing_name: &self.signing_name,
+                };
+                self.signer.sign_message(message, &context)?.into_parts()
             };
             self.last_signature = signature;
             Ok(signed_message)
         }
+    }
+
+    impl SignMessage for SigV4MessageSigner {
+        fn sign(&mut self, message: Message) -> Result<Message, SignMessageError> {
+            self.sign_message(Some(message))
+        }
 
         fn sign_empty(&mut self) -> Option<Result<Message, SignMessageError>> {
-            let (signed_message, signature) = {
-                let params = self.signing_params();
-                sign_empty_message(&self.last_signature, &params)
-                    .ok()?
-                    .into_parts()
-            };
-            self.last_signature = signature;
-            Some(Ok(signed_message))
+            self.sign_message(None).ok().map(Ok)
         }
     }
 
     #[cfg(test)]
     mod tests {
-        use crate::auth::sigv4::event_stream::SigV4MessageSigner;
+        use crate::auth::sigv4::event_stream::{
+            MessageSigningContext, SharedEventStreamMessageSigner, SigV4MessageSigner,
+            SignEventStreamMessage,
+        };
         use aws_credential_types::Credentials;
+        use aws_sigv4::SigningOutput;
         use aws_smithy_async::time::SharedTimeSource;
         use aws_smithy_eventstream::frame::SignMessage;
-        use aws_smithy_types::event_stream::{HeaderValue, Message};
+        use aws_smithy_runtime_api::box_error::BoxError;
+        use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
 
         use aws_types::region::Region;
         use aws_types::region::SigningRegion;
@@ -315,6 +588,7 @@ mod event_stream {
                 SigningRegion::from(region),
                 SigningName::from_static("transcribe"),
                 SharedTimeSource::new(UNIX_EPOCH + Duration::new(1611160427, 0)),
+                SharedEventStreamMessageSigner::default(),
             ));
             let mut signatures = Vec::new();
             for _ in 0..5 {
@@ -337,6 +611,60 @@ mod event_stream {
                 assert_ne!(signatures[i - 1], signatures[i]);
             }
         }
+
+        /// Signs messages with a header that contains the prior signature and the signing time.
+        #[derive(Debug)]
+        struct TestMessageSigner;
+
+        impl SignEventStreamMessage for TestMessageSigner {
+            fn sign_message(
+                &self,
+                message: Option<Message>,
+                context: &MessageSigningContext<'_>,
+            ) -> Result<SigningOutput<Message>, BoxError> {
+                let time = context.time().duration_since(UNIX_EPOCH)?.as_secs();
+                let signature = format!("{}+{}", context.prior_signature(), time);
+                let message = message
+                    .unwrap_or_else(|| Message::new(&b""[..]))
+                    .add_header(Header::new(
+                        "test-signature",
+                        HeaderValue::String(signature.clone().into()),
+                    ));
+                Ok(SigningOutput::new(message, signature))
+            }
+        }
+
+        #[test]
+        fn sign_message_with_custom_signer() {
+            let mut signer = SigV4MessageSigner::new(
+                "initial-signature".into(),
+                Credentials::for_tests().into(),
+                SigningRegion::from_static("us-east-1"),
+                SigningName::from_static("transcribe"),
+                SharedTimeSource::new(UNIX_EPOCH + Duration::new(1611160427, 0)),
+                SharedEventStreamMessageSigner::new(TestMessageSigner),
+            );
+            let test_signature = |message: &Message| {
+                message
+                    .headers()
+                    .iter()
+                    .find(|h| h.name().as_str() == "test-signature")
+                    .unwrap()
+                    .value()
+                    .as_string()
+                    .unwrap()
+                    .as_str()
+                    .to_owned()
+            };
+
+            let first = signer.sign(Message::new(&b"message"[..])).unwrap();
+            assert_eq!("initial-signature+1611160427", test_signature(&first));
+            let last = signer.sign_empty().unwrap().unwrap();
+            assert_eq!(
+                "initial-signature+1611160427+1611160427",
+                test_signature(&last)
+            );
+        }
     }
 }
 
@@ -383,6 +711,7 @@ mod tests {
                 signing_optional: false,
                 expires_in: None,
                 payload_override: None,
+                capture_signing_details: false,
             },
             ..Default::default()
         };
@@ -430,6 +759,95 @@ mod tests {
         assert!(matches!(result, Cow::Owned(_)));
     }
 
+    #[test]
+    fn captured_signing_details_are_added_to_the_request() {
+        use crate::auth::SigningDetails;
+        use aws_smithy_async::time::{SharedTimeSource, StaticTimeSource};
+        use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+        use aws_smithy_types::body::SdkBody;
+
+        let mut layer = Layer::new("test");
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("qldb")),
+            ..Default::default()
+        });
+        layer.store_put(SigningOptionsOverride::new().capture_signing_details(true));
+        let config_bag = ConfigBag::of_layers(vec![layer]);
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(SharedTimeSource::new(StaticTimeSource::new(
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            ))))
+            .build()
+            .unwrap();
+
+        let mut request = HttpRequest::new(SdkBody::from("{}"));
+        request.set_uri("https://example.com/path").unwrap();
+        SigV4Signer::new()
+            .sign_http_request(
+                &mut request,
+                &Credentials::for_tests().into(),
+                AuthSchemeEndpointConfig::empty(),
+                &runtime_components,
+                &config_bag,
+            )
+            .unwrap();
+
+        let details = request
+            .extension::<SigningDetails>()
+            .expect("signing details are captured");
+        assert!(
+            details.canonical_request().starts_with("GET\n/path\n"),
+            "{}",
+            details.canonical_request()
+        );
+        assert!(
+            details
+                .string_to_sign()
+                .starts_with("AWS4-HMAC-SHA256\n20231114T221320Z\n20231114/us-east-1/qldb/"),
+            "{}",
+            details.string_to_sign()
+        );
+    }
+
+    #[test]
+    fn signing_name_and_region_override_takes_precedence_over_endpoint() {
+        let mut layer = Layer::new("test");
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("qldb")),
+            ..Default::default()
+        });
+        layer.store_put(
+            SigningOptionsOverride::new()
+                .signing_name(SigningName::from_static("custom-name"))
+                .signing_region(SigningRegion::from_static("custom-region")),
+        );
+        let config = Document::Object({
+            let mut out = HashMap::new();
+            out.insert("name".to_string(), "sigv4".to_string().into());
+            out.insert(
+                "signingName".to_string(),
+                "endpoint-name".to_string().into(),
+            );
+            out.insert(
+                "signingRegion".to_string(),
+                "endpoint-region".to_string().into(),
+            );
+            out
+        });
+        let config = AuthSchemeEndpointConfig::from(Some(&config));
+
+        let cfg = ConfigBag::of_layers(vec![layer]);
+        let result = SigV4Signer::extract_operation_config(config, &cfg).expect("success");
+
+        assert_eq!(
+            result.region,
+            Some(SigningRegion::from_static("custom-region"))
+        );
+        assert_eq!(result.name, Some(SigningName::from_static("custom-name")));
+    }
+
     #[test]
     fn endpoint_config_supports_fallback_when_region_or_service_are_unset() {
         let mut layer = Layer::new("test");
@@ -447,4 +865,37 @@ mod tests {
         assert_eq!(result.name, Some(SigningName::from_static("qldb")));
         assert!(matches!(result, Cow::Borrowed(_)));
     }
+
+    #[test]
+    fn signing_options_override_is_applied() {
+        let mut layer = Layer::new("test");
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("s3")),
+            signing_options: SigningOptions {
+                double_uri_encode: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        layer.store_put(
+            SigningOptionsOverride::new()
+                .unsigned_payload(true)
+                .expires_in(Duration::from_secs(60)),
+        );
+        let cfg = ConfigBag::of_layers(vec![layer]);
+        let config = AuthSchemeEndpointConfig::empty();
+
+        let result = SigV4Signer::extract_operation_config(config, &cfg).expect("success");
+
+        let options = &result.signing_options;
+        assert_eq!(
+            Some(SignableBody::UnsignedPayload),
+            options.payload_override
+        );
+        assert_eq!(Some(Duration::from_secs(60)), options.expires_in);
+        assert!(!options.double_uri_encode);
+        assert_eq!(result.region, Some(SigningRegion::from_static("us-east-1")));
+        assert!(matches!(result, Cow::Owned(_)));
+    }
 }
@@ -5,7 +5,7 @@
 
 use crate::auth::{
     apply_signing_instructions, extract_endpoint_auth_scheme_signing_name,
-    SigV4OperationSigningConfig, SigV4SigningError,
+    SigV4OperationSigningConfig, SigV4SigningError, SigningOptionsOverride,
 };
 use aws_credential_types::Credentials;
 use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
@@ -119,19 +119,28 @@ impl SigV4aSigner {
             .load::<SigV4OperationSigningConfig>()
             .ok_or(SigV4SigningError::MissingOperationSigningConfig)?;
 
-        let name = extract_endpoint_auth_scheme_signing_name(&auth_scheme_endpoint_config)?
-            .or(config_bag.load::<SigningName>().cloned());
+        let options_override = config_bag.load::<SigningOptionsOverride>();
+
+        // The signing name of an override takes precedence over the endpoint's
+        let name = match options_override.and_then(|o| o.signing_name.clone()) {
+            Some(name) => Some(name),
+            None => extract_endpoint_auth_scheme_signing_name(&auth_scheme_endpoint_config)?
+                .or(config_bag.load::<SigningName>().cloned()),
+        };
 
         let region_set =
             extract_endpoint_auth_scheme_signing_region_set(&auth_scheme_endpoint_config)?
                 .or(config_bag.load::<SigningRegionSet>().cloned());
 
-        match (region_set, name) {
-            (None, None) => Ok(Cow::Borrowed(operation_config)),
-            (region_set, name) => {
+        match (region_set, name, options_override) {
+            (None, None, None) => Ok(Cow::Borrowed(operation_config)),
+            (region_set, name, options_override) => {
                 let mut operation_config = operation_config.clone();
                 operation_config.region_set = region_set.or(operation_config.region_set);
                 operation_config.name = name.or(operation_config.name);
+                if let Some(options_override) = options_override {
+                    options_override.apply(&mut operation_config.signing_options);
+                }
                 Ok(Cow::Owned(operation_config))
             }
         }
@@ -255,6 +264,7 @@ mod tests {
                 signing_optional: false,
                 expires_in: None,
                 payload_override: None,
+                capture_signing_details: false,
             },
             ..Default::default()
         };
@@ -0,0 +1,578 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client-side monitoring (CSM) events for every API call and attempt.
+//!
+//! The [`ClientSideMonitoringInterceptor`] sends an event for every attempt of an API call, and
+//! another event once the call completes, to a local agent over UDP or a Unix datagram socket.
+//! This lets the agent aggregate the behavior of every SDK client on a host, such as latencies,
+//! retries, and error rates, without changing the application.
+//!
+//! Events are sent on a best-effort basis: they're dropped if the socket can't accept them right
+//! away, and failing to send an event never fails the API call.
+//!
+//! # Event schema
+//!
+//! Every event is a JSON object that is sent as a single datagram. Fields that are unknown for
+//! an event are left out.
+//!
+//! Fields of every event:
+//!
+//! | Field       | Type   | Description                                                      |
+//! |-------------|--------|------------------------------------------------------------------|
+//! | `Type`      | string | `ApiCallAttempt` or `ApiCall`                                    |
+//! | `Version`   | number | Version of the schema, currently `1`                             |
+//! | `ClientId`  | string | The [client ID](ClientSideMonitoringInterceptor::with_client_id) |
+//! | `Service`   | string | Name of the service that was called                              |
+//! | `Api`       | string | Name of the operation that was called                            |
+//! | `Timestamp` | number | When the attempt or call started, in milliseconds since the epoch |
+//! | `Region`    | string | Region that the client is configured with                        |
+//!
+//! Additional fields of `ApiCallAttempt` events:
+//!
+//! | Field                 | Type   | Description                                                 |
+//! |-----------------------|--------|-------------------------------------------------------------|
+//! | `AttemptLatency`      | number | Duration of the attempt in milliseconds                     |
+//! | `Fqdn`                | string | Host that the request was sent to                           |
+//! | `UserAgent`           | string | User agent of the request                                   |
+//! | `HttpStatusCode`      | number | Status code of the response                                 |
+//! | `XAmzRequestId`       | string | Request ID of the response                                  |
+//! | `AwsException`        | string | Error code that the service responded with                  |
+//! | `AwsExceptionMessage` | string | Error that the service responded with                       |
+//! | `SdkException`        | string | Kind of error when the service didn't respond with an error |
+//! | `SdkExceptionMessage` | string | Error when the service didn't respond with an error         |
+//!
+//! Additional fields of `ApiCall` events:
+//!
+//! | Field                      | Type   | Description                                               |
+//! |----------------------------|--------|-----------------------------------------------------------|
+//! | `AttemptCount`             | number | Number of attempts that were made                         |
+//! | `Latency`                  | number | Duration of the call in milliseconds                      |
+//! | `FinalHttpStatusCode`      | number | `HttpStatusCode` of the last attempt                      |
+//! | `FinalAwsException`        | string | `AwsException` of the last attempt                        |
+//! | `FinalAwsExceptionMessage` | string | `AwsExceptionMessage` of the last attempt                 |
+//! | `FinalSdkException`        | string | `SdkException` of the last attempt                        |
+//! | `FinalSdkExceptionMessage` | string | `SdkExceptionMessage` of the last attempt                 |
+//! | `MaxRetriesExceeded`       | number | `1` if the call failed after the maximum number of attempts, `0` otherwise |
+//!
+//! `AwsException` is read from the `x-amzn-errortype` response header, which services with JSON
+//! protocols set. `SdkException` is one of `TimeoutError`, `DispatchFailure`, `ResponseError`,
+//! `InterceptorError`, or `ConstructionFailure`. Messages are truncated to 512 bytes, and the user
+//! agent is truncated to 256 bytes.
+
+use aws_smithy_http::operation::Metadata;
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeSerializationInterceptorContextRef, BeforeTransmitInterceptorContextRef, Error,
+    FinalizerInterceptorContextRef,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::request_id::RequestId;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::Number;
+use aws_types::region::Region;
+use std::borrow::Cow;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The port that client-side monitoring agents listen on by default.
+pub const DEFAULT_PORT: u16 = 31000;
+
+const SCHEMA_VERSION: u64 = 1;
+const MAX_MESSAGE_LEN: usize = 512;
+const MAX_USER_AGENT_LEN: usize = 256;
+
+#[derive(Debug)]
+enum Destination {
+    Udp {
+        socket: UdpSocket,
+        address: SocketAddr,
+    },
+    #[cfg(unix)]
+    Unix { socket: UnixDatagram, path: PathBuf },
+}
+
+impl Destination {
+    fn send(&self, event: &str) -> io::Result<usize> {
+        match self {
+            Destination::Udp { socket, address } => socket.send_to(event.as_bytes(), address),
+            #[cfg(unix)]
+            Destination::Unix { socket, path } => socket.send_to(event.as_bytes(), path),
+        }
+    }
+}
+
+/// Interceptor that sends client-side monitoring events for every API call and attempt.
+///
+/// See the [module docs](crate::client_side_monitoring) for the schema of the events.
+///
+/// # Examples
+/// ```no_run
+/// use aws_runtime::client_side_monitoring::{ClientSideMonitoringInterceptor, DEFAULT_PORT};
+///
+/// let interceptor = ClientSideMonitoringInterceptor::udp(("127.0.0.1", DEFAULT_PORT))
+///     .expect("valid address")
+///     .with_client_id("checkout-service");
+/// // Add the interceptor to a client's config with `interceptor(...)`
+/// ```
+#[derive(Debug)]
+pub struct ClientSideMonitoringInterceptor {
+    client_id: Cow<'static, str>,
+    destination: Destination,
+}
+
+impl ClientSideMonitoringInterceptor {
+    /// Creates an interceptor that sends events to the given UDP address.
+    ///
+    /// If the address resolves to more than one socket address, the first one is used.
+    pub fn udp(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "no socket address was given")
+        })?;
+        let local_address = match address {
+            SocketAddr::V4(_) => "0.0.0.0:0",
+            SocketAddr::V6(_) => "[::]:0",
+        };
+        let socket = UdpSocket::bind(local_address)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self::new(Destination::Udp { socket, address }))
+    }
+
+    /// Creates an interceptor that sends events to the Unix datagram socket at the given path.
+    #[cfg(unix)]
+    pub fn unix_socket(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.set_nonblocking(true)?;
+        Ok(Self::new(Destination::Unix {
+            socket,
+            path: path.into(),
+        }))
+    }
+
+    fn new(destination: Destination) -> Self {
+        Self {
+            client_id: Cow::Borrowed(""),
+            destination,
+        }
+    }
+
+    /// Sets the ID that's sent with every event, so that the agent can tell applications apart.
+    ///
+    /// Defaults to an empty string.
+    pub fn with_client_id(mut self, client_id: impl Into<Cow<'static, str>>) -> Self {
+        self.client_id = client_id.into();
+        self
+    }
+
+    fn send(&self, event: String) {
+        if let Err(err) = self.destination.send(&event) {
+            tracing::trace!(err = %err, "failed to send client-side monitoring event");
+        }
+    }
+
+    fn start_event<'a>(
+        &self,
+        output: &'a mut String,
+        kind: &str,
+        timestamp: SystemTime,
+        cfg: &ConfigBag,
+    ) -> JsonObjectWriter<'a> {
+        let mut event = JsonObjectWriter::new(output);
+        event.key("Type").string(kind);
+        event.key("Version").number(Number::PosInt(SCHEMA_VERSION));
+        event.key("ClientId").string(&self.client_id);
+        if let Some(metadata) = cfg.load::<Metadata>() {
+            event.key("Service").string(metadata.service());
+            event.key("Api").string(metadata.name());
+        }
+        event.key("Timestamp").number(millis(
+            timestamp.duration_since(UNIX_EPOCH).unwrap_or_default(),
+        ));
+        if let Some(region) = cfg.load::<Region>() {
+            event.key("Region").string(region.as_ref());
+        }
+        event
+    }
+}
+
+/// State of the API call that's being monitored.
+#[derive(Clone, Debug)]
+struct MonitoredCall {
+    started: SystemTime,
+    attempt_started: SystemTime,
+    attempts: u32,
+    last_attempt: AttemptResult,
+}
+
+impl MonitoredCall {
+    fn new(started: SystemTime) -> Self {
+        Self {
+            started,
+            attempt_started: started,
+            attempts: 0,
+            last_attempt: Default::default(),
+        }
+    }
+}
+
+impl Storable for MonitoredCall {
+    type Storer = StoreReplace<Self>;
+}
+
+/// The result of an attempt, as it's reported in events.
+#[derive(Clone, Debug, Default)]
+struct AttemptResult {
+    http_status_code: Option<u16>,
+    aws_exception: Option<String>,
+    aws_exception_message: Option<String>,
+    sdk_exception: Option<&'static str>,
+    sdk_exception_message: Option<String>,
+}
+
+impl AttemptResult {
+    fn new(
+        response: Option<&HttpResponse>,
+        output_or_error: Option<Result<(), &OrchestratorError<Error>>>,
+    ) -> Self {
+        let mut result = AttemptResult {
+            http_status_code: response.map(|response| response.status().as_u16()),
+            ..Default::default()
+        };
+        let error = match output_or_error {
+            Some(Err(error)) => error,
+            _ => return result,
+        };
+        let message = Some(truncate(
+            &DisplayErrorContext(error).to_string(),
+            MAX_MESSAGE_LEN,
+        ));
+        if error.is_operation_error() {
+            result.aws_exception = response
+                .and_then(|response| response.headers().get("x-amzn-errortype"))
+                .map(|error_type| error_type.split(':').next().unwrap_or_default().to_string());
+            result.aws_exception_message = message;
+        } else {
+            result.sdk_exception = Some(if error.is_timeout_error() {
+                "TimeoutError"
+            } else if error.is_connector_error() {
+                "DispatchFailure"
+            } else if error.is_response_error() {
+                "ResponseError"
+            } else if error.is_interceptor_error() {
+                "InterceptorError"
+            } else {
+                "ConstructionFailure"
+            });
+            result.sdk_exception_message = message;
+        }
+        result
+    }
+
+    fn is_error(&self) -> bool {
+        self.aws_exception_message.is_some() || self.sdk_exception.is_some()
+    }
+
+    fn write(&self, event: &mut JsonObjectWriter<'_>, prefix: &str) {
+        let key = |name: &str| format!("{prefix}{name}");
+        if let Some(status) = self.http_status_code {
+            event
+                .key(&key("HttpStatusCode"))
+                .number(Number::PosInt(status.into()));
+        }
+        let fields = [
+            ("AwsException", self.aws_exception.as_deref()),
+            ("AwsExceptionMessage", self.aws_exception_message.as_deref()),
+            ("SdkException", self.sdk_exception),
+            ("SdkExceptionMessage", self.sdk_exception_message.as_deref()),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                event.key(&key(name)).string(value);
+            }
+        }
+    }
+}
+
+fn now(runtime_components: &RuntimeComponents) -> Result<SystemTime, BoxError> {
+    Ok(runtime_components
+        .time_source()
+        .ok_or("a time source is required (client-side monitoring)")?
+        .now())
+}
+
+fn millis(duration: Duration) -> Number {
+    Number::PosInt(duration.as_millis().try_into().unwrap_or(u64::MAX))
+}
+
+fn truncate(value: &str, max_len: usize) -> String {
+    let mut end = value.len().min(max_len);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    value[..end].to_string()
+}
+
+fn host(request: &HttpRequest) -> Option<String> {
+    let uri: http::Uri = request.uri().parse().ok()?;
+    uri.host().map(ToString::to_string)
+}
+
+impl Intercept for ClientSideMonitoringInterceptor {
+    fn name(&self) -> &'static str {
+        "ClientSideMonitoringInterceptor"
+    }
+
+    fn read_before_serialization(
+        &self,
+        _context: &BeforeSerializationInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let started = now(runtime_components)?;
+        cfg.interceptor_state()
+            .store_put(MonitoredCall::new(started));
+        Ok(())
+    }
+
+    fn read_before_attempt(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let attempt_started = now(runtime_components)?;
+        let call = cfg.get_mut_or_else(|| MonitoredCall::new(attempt_started));
+        call.attempt_started = attempt_started;
+        call.attempts += 1;
+        Ok(())
+    }
+
+    fn read_after_attempt(
+        &self,
+        context: &FinalizerInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let finished = now(runtime_components)?;
+        let result = AttemptResult::new(
+            context.response(),
+            context.output_or_error().map(|result| result.map(|_| ())),
+        );
+        let attempt_started = match cfg.load::<MonitoredCall>() {
+            Some(call) => call.attempt_started,
+            None => return Ok(()),
+        };
+
+        let mut output = String::new();
+        let mut event = self.start_event(&mut output, "ApiCallAttempt", attempt_started, cfg);
+        event.key("AttemptLatency").number(millis(
+            finished.duration_since(attempt_started).unwrap_or_default(),
+        ));
+        if let Some(request) = context.request() {
+            if let Some(host) = host(request) {
+                event.key("Fqdn").string(&host);
+            }
+            if let Some(user_agent) = request.headers().get("user-agent") {
+                event
+                    .key("UserAgent")
+                    .string(&truncate(user_agent, MAX_USER_AGENT_LEN));
+            }
+        }
+        if let Some(request_id) = context.response().and_then(RequestId::request_id) {
+            event.key("XAmzRequestId").string(request_id);
+        }
+        result.write(&mut event, "");
+        event.finish();
+        self.send(output);
+
+        if let Some(call) = cfg.get_mut::<MonitoredCall>() {
+            call.last_attempt = result;
+        }
+        Ok(())
+    }
+
+    fn read_after_execution(
+        &self,
+        _context: &FinalizerInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let finished = now(runtime_components)?;
+        let call = cfg
+            .load::<MonitoredCall>()
+            .cloned()
+            .unwrap_or_else(|| MonitoredCall::new(finished));
+        let max_retries_exceeded = call.last_attempt.is_error()
+            && cfg
+                .load::<RetryConfig>()
+                .map(|retry_config| call.attempts >= retry_config.max_attempts())
+                .unwrap_or(false);
+
+        let mut output = String::new();
+        let mut event = self.start_event(&mut output, "ApiCall", call.started, cfg);
+        event
+            .key("AttemptCount")
+            .number(Number::PosInt(call.attempts.into()));
+        event.key("Latency").number(millis(
+            finished.duration_since(call.started).unwrap_or_default(),
+        ));
+        call.last_attempt.write(&mut event, "Final");
+        event
+            .key("MaxRetriesExceeded")
+            .number(Number::PosInt(max_retries_exceeded.into()));
+        event.finish();
+        self.send(output);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClientSideMonitoringInterceptor;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_http::operation::Metadata;
+    use aws_smithy_runtime_api::client::interceptors::context::{
+        Error, Input, InterceptorContext, Output,
+    };
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::{
+        HttpRequest, HttpResponse, OrchestratorError,
+    };
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::{ConfigBag, Layer};
+    use aws_smithy_types::error::ErrorMetadata;
+    use aws_smithy_types::retry::RetryConfig;
+    use aws_types::region::Region;
+    use serde_json::{json, Value};
+    use std::net::UdpSocket;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn receive(socket: &UdpSocket) -> Value {
+        let mut buf = [0; 8192];
+        let len = socket.recv(&mut buf).expect("an event was sent");
+        serde_json::from_slice(&buf[..len]).expect("events are valid JSON")
+    }
+
+    #[test]
+    fn sends_an_event_for_every_attempt_and_call() {
+        let agent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        agent
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let interceptor = ClientSideMonitoringInterceptor::udp(agent.local_addr().unwrap())
+            .unwrap()
+            .with_client_id("test-client");
+
+        let time_source = ManualTimeSource::new(UNIX_EPOCH + Duration::from_secs(1));
+        let rc = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time_source.clone()))
+            .build()
+            .unwrap();
+        let mut layer = Layer::new("test");
+        layer.store_put(Metadata::new("GetItem", "dynamodb"));
+        layer.store_put(Region::new("us-west-2"));
+        layer.store_put(RetryConfig::standard().with_max_attempts(2));
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        interceptor
+            .read_before_serialization(&(&context).into(), &rc, &mut cfg)
+            .unwrap();
+        let mut request = HttpRequest::empty();
+        request
+            .set_uri("https://dynamodb.us-west-2.amazonaws.com/")
+            .unwrap();
+        request.headers_mut().insert("user-agent", "aws-sdk-rust");
+        context.set_request(request);
+
+        // The first attempt fails with a service error
+        time_source.advance(Duration::from_millis(5));
+        interceptor
+            .read_before_attempt(&(&context).into(), &rc, &mut cfg)
+            .unwrap();
+        time_source.advance(Duration::from_millis(20));
+        let response = http::Response::builder()
+            .status(503)
+            .header(
+                "x-amzn-errortype",
+                "ServiceUnavailable:http://internal.amazon.com/",
+            )
+            .header("x-amzn-requestid", "request-1")
+            .body(SdkBody::empty())
+            .unwrap();
+        context.set_response(HttpResponse::try_from(response).unwrap());
+        let error = ErrorMetadata::builder().message("slow down").build();
+        context.set_output_or_error(Err(OrchestratorError::operation(Error::erase(error))));
+        interceptor
+            .read_after_attempt(&(&context).into(), &rc, &mut cfg)
+            .unwrap();
+
+        let attempt = receive(&agent);
+        assert_eq!("ApiCallAttempt", attempt["Type"]);
+        assert_eq!(json!(1005), attempt["Timestamp"]);
+        assert_eq!(json!(20), attempt["AttemptLatency"]);
+        assert_eq!("dynamodb.us-west-2.amazonaws.com", attempt["Fqdn"]);
+        assert_eq!("aws-sdk-rust", attempt["UserAgent"]);
+        assert_eq!(json!(503), attempt["HttpStatusCode"]);
+        assert_eq!("request-1", attempt["XAmzRequestId"]);
+        assert_eq!("ServiceUnavailable", attempt["AwsException"]);
+        assert!(attempt["AwsExceptionMessage"]
+            .as_str()
+            .unwrap()
+            .contains("slow down"));
+        assert_eq!(Value::Null, attempt["SdkException"]);
+
+        // The second attempt succeeds
+        interceptor
+            .read_before_attempt(&(&context).into(), &rc, &mut cfg)
+            .unwrap();
+        time_source.advance(Duration::from_millis(10));
+        let response = http::Response::builder()
+            .status(200)
+            .body(SdkBody::empty())
+            .unwrap();
+        context.set_response(HttpResponse::try_from(response).unwrap());
+        context.set_output_or_error(Ok(Output::doesnt_matter()));
+        interceptor
+            .read_after_attempt(&(&context).into(), &rc, &mut cfg)
+            .unwrap();
+        interceptor
+            .read_after_execution(&(&context).into(), &rc, &mut cfg)
+            .unwrap();
+
+        let attempt = receive(&agent);
+        assert_eq!(json!(200), attempt["HttpStatusCode"]);
+        assert_eq!(Value::Null, attempt["AwsException"]);
+
+        let call = receive(&agent);
+        assert_eq!(
+            json!({
+                "Type": "ApiCall",
+                "Version": 1,
+                "ClientId": "test-client",
+                "Service": "dynamodb",
+                "Api": "GetItem",
+                "Timestamp": 1000,
+                "Region": "us-west-2",
+                "AttemptCount": 2,
+                "Latency": 35,
+                "FinalHttpStatusCode": 200,
+                "MaxRetriesExceeded": 0,
+            }),
+            call
+        );
+    }
+}
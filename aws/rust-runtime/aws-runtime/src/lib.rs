@@ -33,3 +33,6 @@ pub mod request_info;
 
 /// Interceptor that determines the clock skew between the client and service.
 pub mod service_clock_skew;
+
+/// Supporting code for client-side monitoring in the AWS SDK.
+pub mod client_side_monitoring;
@@ -8,9 +8,14 @@ use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
 use aws_smithy_runtime_api::client::retries::classifiers::{
     ClassifyRetry, RetryAction, RetryClassifierPriority, RetryReason,
 };
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use aws_smithy_types::error::metadata::ProvideErrorMetadata;
 use aws_smithy_types::retry::ErrorKind;
+use std::borrow::Cow;
 use std::error::Error as StdError;
+use std::fmt;
 use std::marker::PhantomData;
 
 /// AWS error codes that represent throttling errors.
@@ -34,9 +39,42 @@ pub const THROTTLING_ERRORS: &[&str] = &[
 /// AWS error codes that represent transient errors.
 pub const TRANSIENT_ERRORS: &[&str] = &["RequestTimeout", "RequestTimeoutException"];
 
+/// Error codes that represent throttling errors for a particular client, in addition to
+/// the [`THROTTLING_ERRORS`] that are recognized for every client.
+#[derive(Clone, Debug, Default)]
+pub struct AdditionalThrottlingErrors {
+    codes: Vec<Cow<'static, str>>,
+}
+
+impl AdditionalThrottlingErrors {
+    /// Creates a new empty `AdditionalThrottlingErrors`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error code that represents a throttling error.
+    pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        let code = code.into();
+        if !self.codes.contains(&code) {
+            self.codes.push(code);
+        }
+        self
+    }
+
+    /// Returns the additional throttling error codes.
+    pub fn codes(&self) -> impl Iterator<Item = &str> {
+        self.codes.iter().map(|code| code.as_ref())
+    }
+}
+
+impl Storable for AdditionalThrottlingErrors {
+    type Storer = StoreReplace<Self>;
+}
+
 /// A retry classifier for determining if the response sent by an AWS service requires a retry.
 #[derive(Debug, Default)]
 pub struct AwsErrorCodeClassifier<E> {
+    additional_throttling_errors: Vec<Cow<'static, str>>,
     _inner: PhantomData<E>,
 }
 
@@ -44,9 +82,29 @@ impl<E> AwsErrorCodeClassifier<E> {
     /// Create a new AwsErrorCodeClassifier
     pub fn new() -> Self {
         Self {
+            additional_throttling_errors: Vec::new(),
             _inner: PhantomData,
         }
     }
+
+    /// Classifies errors with the given codes as throttling errors, in addition to the
+    /// [`THROTTLING_ERRORS`].
+    pub fn with_additional_throttling_errors(
+        mut self,
+        additional_throttling_errors: &AdditionalThrottlingErrors,
+    ) -> Self {
+        self.additional_throttling_errors
+            .extend(additional_throttling_errors.codes.iter().cloned());
+        self
+    }
+
+    fn is_throttling_error(&self, error_code: &str) -> bool {
+        THROTTLING_ERRORS.contains(&error_code)
+            || self
+                .additional_throttling_errors
+                .iter()
+                .any(|code| code == error_code)
+    }
 }
 
 impl<E> ClassifyRetry for AwsErrorCodeClassifier<E>
@@ -73,7 +131,7 @@ where
             .and_then(|err| err.code());
 
         if let Some(error_code) = error_code {
-            if THROTTLING_ERRORS.contains(&error_code) {
+            if self.is_throttling_error(error_code) {
                 return RetryAction::RetryIndicated(RetryReason::RetryableError {
                     kind: ErrorKind::ThrottlingError,
                     retry_after,
@@ -106,9 +164,64 @@ where
     }
 }
 
+/// Runtime plugin that classifies an operation's [`AdditionalThrottlingErrors`] as throttling errors.
+///
+/// The [`AwsErrorCodeClassifier`] that is registered for every operation only knows about the
+/// [`THROTTLING_ERRORS`]. This plugin registers another one that also knows about the
+/// additional error codes set in client config.
+pub struct AdditionalThrottlingErrorsRuntimePlugin<E> {
+    additional_throttling_errors: Option<AdditionalThrottlingErrors>,
+    _inner: PhantomData<E>,
+}
+
+impl<E> fmt::Debug for AdditionalThrottlingErrorsRuntimePlugin<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdditionalThrottlingErrorsRuntimePlugin")
+            .field(
+                "additional_throttling_errors",
+                &self.additional_throttling_errors,
+            )
+            .finish()
+    }
+}
+
+impl<E> AdditionalThrottlingErrorsRuntimePlugin<E> {
+    /// Creates a new `AdditionalThrottlingErrorsRuntimePlugin`.
+    ///
+    /// If no additional throttling errors are given, this plugin does nothing.
+    pub fn new(additional_throttling_errors: Option<AdditionalThrottlingErrors>) -> Self {
+        Self {
+            additional_throttling_errors,
+            _inner: PhantomData,
+        }
+    }
+}
+
+impl<E> RuntimePlugin for AdditionalThrottlingErrorsRuntimePlugin<E>
+where
+    E: StdError + ProvideErrorMetadata + Send + Sync + 'static,
+{
+    fn runtime_components(
+        &self,
+        _current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        let mut components = RuntimeComponentsBuilder::new("AdditionalThrottlingErrors");
+        match &self.additional_throttling_errors {
+            Some(additional) if !additional.codes.is_empty() => {
+                components = components.with_retry_classifier(
+                    AwsErrorCodeClassifier::<E>::new()
+                        .with_additional_throttling_errors(additional),
+                );
+            }
+            _ => {}
+        }
+        Cow::Owned(components)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::retries::classifiers::AwsErrorCodeClassifier;
+    use crate::retries::classifiers::{AdditionalThrottlingErrors, AwsErrorCodeClassifier};
     use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
     use aws_smithy_runtime_api::client::interceptors::context::{Error, Input};
     use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
@@ -164,6 +277,22 @@ mod test {
         assert_eq!(policy.classify_retry(&ctx), RetryAction::transient_error())
     }
 
+    #[test]
+    fn classify_by_additional_throttling_error_code() {
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.set_output_or_error(Err(OrchestratorError::operation(Error::erase(
+            CodedError::new("SubscriberLimitExceeded"),
+        ))));
+
+        let policy = AwsErrorCodeClassifier::<CodedError>::new();
+        assert_eq!(policy.classify_retry(&ctx), RetryAction::NoActionIndicated);
+
+        let additional = AdditionalThrottlingErrors::new().with_code("SubscriberLimitExceeded");
+        let policy = AwsErrorCodeClassifier::<CodedError>::new()
+            .with_additional_throttling_errors(&additional);
+        assert_eq!(policy.classify_retry(&ctx), RetryAction::throttling_error());
+    }
+
     #[test]
     fn classify_generic() {
         let policy = AwsErrorCodeClassifier::<ErrorMetadata>::new();
@@ -11,6 +11,7 @@ use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::ConfigBag;
 use aws_types::app_name::AppName;
 use aws_types::os_shim_internal::Env;
+use aws_types::user_agent::UserAgentMetadata;
 use http::header::{InvalidHeaderValue, USER_AGENT};
 use http::{HeaderName, HeaderValue};
 use std::borrow::Cow;
@@ -99,6 +100,9 @@ impl Intercept for UserAgentInterceptor {
                 if let Some(app_name) = maybe_app_name {
                     ua.set_app_name(app_name.clone());
                 }
+                if let Some(metadata) = cfg.load::<UserAgentMetadata>() {
+                    ua.set_user_agent_metadata(metadata.clone());
+                }
                 Ok(Cow::Owned(ua))
             })?;
 
@@ -119,6 +123,7 @@ mod tests {
     use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
     use aws_smithy_types::config_bag::{ConfigBag, Layer};
     use aws_smithy_types::error::display::DisplayErrorContext;
+    use aws_types::user_agent::ProductToken;
 
     fn expect_header<'a>(context: &'a InterceptorContext, header_name: &str) -> &'a str {
         context
@@ -226,6 +231,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_user_agent_metadata() {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut context = context();
+
+        let api_metadata = ApiMetadata::new("some-service", "some-version");
+        let mut layer = Layer::new("test");
+        layer.store_put(api_metadata);
+        layer.store_put(
+            UserAgentMetadata::builder()
+                .product(ProductToken::new("my-platform").with_version("2.1.0"))
+                .feature_flag("batching")
+                .build()
+                .unwrap(),
+        );
+        let mut config = ConfigBag::of_layers(vec![layer]);
+
+        let interceptor = UserAgentInterceptor::new();
+        let mut ctx = Into::into(&mut context);
+        interceptor
+            .modify_before_signing(&mut ctx, &rc, &mut config)
+            .unwrap();
+
+        let header = expect_header(&context, "user-agent");
+        assert!(!header.contains("my-platform"), "{header}");
+
+        let header = expect_header(&context, "x-amz-user-agent");
+        for expected in ["ft/batching", "lib/my-platform/2.1.0"] {
+            assert!(
+                header.contains(expected),
+                "expected `{header}` to contain `{expected}`"
+            );
+        }
+    }
+
     #[test]
     fn test_api_metadata_missing() {
         let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
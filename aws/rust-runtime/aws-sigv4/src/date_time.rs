@@ -45,6 +45,25 @@ pub(crate) fn format_date_time(time: SystemTime) -> String {
     )
 }
 
+/// Parses a time in `YYYYMMDD'T'HHMMSS'Z'` format, as sent in the `x-amz-date` header.
+pub(crate) fn parse_x_amz_date(date_time: &str) -> Option<SystemTime> {
+    let bytes = date_time.as_bytes();
+    if bytes.len() != 16 || bytes[8] != b'T' || bytes[15] != b'Z' {
+        return None;
+    }
+    let num = |range: std::ops::Range<usize>| -> Option<u32> {
+        let digits = date_time.get(range)?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+    let month = time::Month::try_from(num(4..6)? as u8).ok()?;
+    let date = time::Date::from_calendar_date(num(0..4)? as i32, month, num(6..8)? as u8).ok()?;
+    let time = Time::from_hms(num(9..11)? as u8, num(11..13)? as u8, num(13..15)? as u8).ok()?;
+    Some(time::PrimitiveDateTime::new(date, time).assume_utc().into())
+}
+
 /// Parse functions that are only needed for unit tests.
 #[cfg(test)]
 pub(crate) mod test_parsers {
@@ -129,6 +148,22 @@ mod tests {
         assert_eq!("20150830T123600Z", format_date_time(time));
     }
 
+    #[test]
+    fn x_amz_date_parsing() {
+        assert_eq!(
+            parse_date_time("20150830T123600Z").ok(),
+            parse_x_amz_date("20150830T123600Z")
+        );
+        for invalid in [
+            "",
+            "20150830T123600",
+            "2015083OT123600Z",
+            "20151330T123600Z",
+        ] {
+            assert_eq!(None, parse_x_amz_date(invalid), "{}", invalid);
+        }
+    }
+
     #[test]
     fn date_roundtrip() {
         let time = parse_date("20150830").unwrap();
@@ -53,6 +53,58 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Example: Signing an HTTP request for multiple regions with SigV4a
+//!
+//! **Note**: This requires `sigv4a` and `http0-compat` to be enabled.
+//!
+//! SigV4a signatures are calculated with an ECDSA key that's derived from the credentials, and
+//! they're valid in every region of the region set, so they can be used for multi-region
+//! endpoints, like S3 Multi-Region Access Points.
+//!
+//! ```rust
+//! # use aws_credential_types::Credentials;
+//! # use aws_sigv4::http_request::SignableBody;
+//! #[cfg(all(feature = "sigv4a", feature = "http0-compat"))]
+//! fn test() -> Result<(), aws_sigv4::http_request::SigningError> {
+//! use aws_sigv4::http_request::{sign, SigningSettings, SignableRequest};
+//! use aws_sigv4::sign::v4a;
+//! use std::time::SystemTime;
+//!
+//! let identity = Credentials::new(
+//!     "AKIDEXAMPLE",
+//!     "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+//!     None,
+//!     None,
+//!     "hardcoded-credentials"
+//! ).into();
+//! let signing_params = v4a::SigningParams::builder()
+//!     .identity(&identity)
+//!     // A comma-separated list of regions, which may include wildcards like `*`
+//!     .region_set("us-east-1,us-west-2")
+//!     .name("s3")
+//!     .time(SystemTime::now())
+//!     .settings(SigningSettings::default())
+//!     .build()
+//!     .unwrap()
+//!     .into();
+//! let signable_request = SignableRequest::new(
+//!     "GET",
+//!     "https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com",
+//!     std::iter::empty(),
+//!     SignableBody::Bytes(&[])
+//! ).expect("signable request");
+//!
+//! let mut my_req = http::Request::new("...");
+//! let (signing_instructions, _signature) = sign(signable_request, &signing_params)?.into_parts();
+//! signing_instructions.apply_to_request(&mut my_req);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Generated clients for services that support SigV4a sign with it when the `sigv4a` feature
+//! is enabled and the endpoint's auth schemes ask for it, using the region set from the
+//! endpoint or from the client's configuration.
 
 mod canonical_request;
 mod error;
@@ -60,6 +112,7 @@ mod settings;
 mod sign;
 mod uri_path_normalization;
 mod url_escape;
+mod verify;
 
 #[cfg(test)]
 pub(crate) mod test;
@@ -74,8 +127,9 @@ pub use settings::{
     PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignatureLocation, SigningSettings,
     UriPathNormalizationMode,
 };
-pub use sign::{sign, SignableBody, SignableRequest, SigningInstructions};
+pub use sign::{sign, SignableBody, SignableRequest, SigningDetails, SigningInstructions};
 use std::time::SystemTime;
+pub use verify::{RequestSignature, VerificationError};
 
 // Individual Debug impls are responsible for redacting sensitive fields.
 #[derive(Debug)]
@@ -48,6 +48,7 @@ pub(crate) const HMAC_256: &str = "AWS4-HMAC-SHA256";
 
 const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
 const STREAMING_UNSIGNED_PAYLOAD_TRAILER: &str = "STREAMING-UNSIGNED-PAYLOAD-TRAILER";
+const STREAMING_SIGNED_PAYLOAD_TRAILER: &str = "STREAMING-AWS4-HMAC-SHA256-PAYLOAD-TRAILER";
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct HeaderValues<'a> {
@@ -310,6 +311,7 @@ impl<'a> CanonicalRequest<'a> {
         // - use `UnsignedPayload`
         // - use `UnsignedPayload` for streaming requests
         // - use `StreamingUnsignedPayloadTrailer` for streaming requests with trailers
+        // - use `StreamingSignedPayloadTrailer` for streaming requests with signed chunks and trailers
         match body {
             SignableBody::Bytes(data) => Cow::Owned(sha256_hex_string(data)),
             SignableBody::Precomputed(digest) => Cow::Borrowed(digest.as_str()),
@@ -317,6 +319,9 @@ impl<'a> CanonicalRequest<'a> {
             SignableBody::StreamingUnsignedPayloadTrailer => {
                 Cow::Borrowed(STREAMING_UNSIGNED_PAYLOAD_TRAILER)
             }
+            SignableBody::StreamingSignedPayloadTrailer => {
+                Cow::Borrowed(STREAMING_SIGNED_PAYLOAD_TRAILER)
+            }
         }
     }
 
@@ -37,6 +37,10 @@ pub struct SigningSettings {
     /// canonical request. Other services require only it to be added after
     /// calculating the signature.
     pub session_token_mode: SessionTokenMode,
+
+    /// Captures the canonical request and string-to-sign in the
+    /// [`SigningInstructions`](super::SigningInstructions), to help diagnose signature mismatches.
+    pub capture_signing_details: bool,
 }
 
 /// HTTP payload checksum type
@@ -133,6 +137,7 @@ impl Default for SigningSettings {
             excluded_headers,
             uri_path_normalization_mode: UriPathNormalizationMode::Enabled,
             session_token_mode: SessionTokenMode::Include,
+            capture_signing_details: false,
         }
     }
 }
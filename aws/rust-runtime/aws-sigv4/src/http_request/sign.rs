@@ -91,6 +91,12 @@ pub enum SignableBody<'a> {
 
     /// Set when a streaming body has checksum trailers.
     StreamingUnsignedPayloadTrailer,
+
+    /// Set when a streaming body is sent with `aws-chunked` encoding, with signed chunks and
+    /// signed trailers.
+    ///
+    /// The chunks and trailers are signed with the functions in [`chunk`](crate::chunk).
+    StreamingSignedPayloadTrailer,
 }
 
 /// Instructions for applying a signature to an HTTP request.
@@ -98,6 +104,42 @@ pub enum SignableBody<'a> {
 pub struct SigningInstructions {
     headers: Vec<Header>,
     params: Vec<(&'static str, Cow<'static, str>)>,
+    details: Option<SigningDetails>,
+}
+
+/// The intermediate values that a signature was calculated from.
+///
+/// These are only captured when [`SigningSettings::capture_signing_details`](super::SigningSettings::capture_signing_details)
+/// is enabled. Comparing them with the canonical request and string-to-sign that a service returns
+/// in a `SignatureDoesNotMatch` error shows which part of the request didn't match.
+///
+/// The canonical request contains the values of the signed headers, which may include the
+/// session token, so it's redacted from the `Debug` output.
+#[derive(Clone)]
+pub struct SigningDetails {
+    canonical_request: String,
+    string_to_sign: String,
+}
+
+impl SigningDetails {
+    /// Returns the canonical request that was signed.
+    pub fn canonical_request(&self) -> &str {
+        &self.canonical_request
+    }
+
+    /// Returns the string-to-sign that the signature was calculated from.
+    pub fn string_to_sign(&self) -> &str {
+        &self.string_to_sign
+    }
+}
+
+impl Debug for SigningDetails {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningDetails")
+            .field("canonical_request", &"** redacted **")
+            .field("string_to_sign", &self.string_to_sign)
+            .finish()
+    }
 }
 
 /// Header representation for use in [`SigningInstructions`]
@@ -140,7 +182,16 @@ impl Header {
 
 impl SigningInstructions {
     fn new(headers: Vec<Header>, params: Vec<(&'static str, Cow<'static, str>)>) -> Self {
-        Self { headers, params }
+        Self {
+            headers,
+            params,
+            details: None,
+        }
+    }
+
+    fn with_details(mut self, details: Option<SigningDetails>) -> Self {
+        self.details = details;
+        self
     }
 
     /// Returns the headers and query params that should be applied to this request
@@ -160,6 +211,11 @@ impl SigningInstructions {
         self.params.as_slice()
     }
 
+    /// Returns the canonical request and string-to-sign, if they were captured while signing.
+    pub fn signing_details(&self) -> Option<&SigningDetails> {
+        self.details.as_ref()
+    }
+
     #[cfg(any(feature = "http0-compat", test))]
     /// Applies the instructions to the given `request`.
     pub fn apply_to_request<B>(self, request: &mut http::Request<B>) {
@@ -189,17 +245,17 @@ pub fn sign<'a>(
     tracing::trace!(request = ?request, params = ?params, "signing request");
     match params.settings().signature_location {
         SignatureLocation::Headers => {
-            let (signing_headers, signature) =
-                calculate_signing_headers(&request, params)?.into_parts();
+            let (signing_headers, details, signature) =
+                calculate_signing_headers(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(signing_headers, vec![]),
+                SigningInstructions::new(signing_headers, vec![]).with_details(details),
                 signature,
             ))
         }
         SignatureLocation::QueryParams => {
-            let (params, signature) = calculate_signing_params(&request, params)?;
+            let (params, details, signature) = calculate_signing_params(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(vec![], params),
+                SigningInstructions::new(vec![], params).with_details(details),
                 signature,
             ))
         }
@@ -208,13 +264,28 @@ pub fn sign<'a>(
 
 type CalculatedParams = Vec<(&'static str, Cow<'static, str>)>;
 
+fn capture_details(
+    params: &SigningParams<'_>,
+    creq: String,
+    string_to_sign: &str,
+) -> Option<SigningDetails> {
+    params
+        .settings()
+        .capture_signing_details
+        .then(|| SigningDetails {
+            canonical_request: creq,
+            string_to_sign: string_to_sign.to_owned(),
+        })
+}
+
 fn calculate_signing_params<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<(CalculatedParams, String), SigningError> {
+) -> Result<(CalculatedParams, Option<SigningDetails>, String), SigningError> {
     let creds = params.credentials()?;
     let creq = CanonicalRequest::from(request, params)?;
-    let encoded_creq = &v4::sha256_hex_string(creq.to_string().as_bytes());
+    let creq_string = creq.to_string();
+    let encoded_creq = &v4::sha256_hex_string(creq_string.as_bytes());
 
     let (signature, string_to_sign) = match params {
         SigningParams::V4(params) => {
@@ -243,6 +314,7 @@ fn calculate_signing_params<'a>(
         }
     };
     tracing::trace!(canonical_request = %creq, string_to_sign = %string_to_sign, "calculated signing parameters");
+    let details = capture_details(params, creq_string, &string_to_sign);
 
     let values = creq.values.into_query_params().expect("signing with query");
     let mut signing_params = vec![
@@ -274,7 +346,7 @@ fn calculate_signing_params<'a>(
         ));
     }
 
-    Ok((signing_params, signature))
+    Ok((signing_params, details, signature))
 }
 
 /// Calculates the signature headers that need to get added to the given `request`.
@@ -286,17 +358,18 @@ fn calculate_signing_params<'a>(
 fn calculate_signing_headers<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<SigningOutput<Vec<Header>>, SigningError> {
+) -> Result<(Vec<Header>, Option<SigningDetails>, String), SigningError> {
     let creds = params.credentials()?;
 
     // Step 1: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-create-canonical-request.html.
     let creq = CanonicalRequest::from(request, params)?;
     // Step 2: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-create-string-to-sign.html.
-    let encoded_creq = v4::sha256_hex_string(creq.to_string().as_bytes());
+    let creq_string = creq.to_string();
+    let encoded_creq = v4::sha256_hex_string(creq_string.as_bytes());
     tracing::trace!(canonical_request = %creq);
     let mut headers = vec![];
 
-    let signature = match params {
+    let (signature, string_to_sign) = match params {
         SigningParams::V4(params) => {
             let sts = StringToSign::new_v4(
                 params.time,
@@ -312,7 +385,8 @@ fn calculate_signing_headers<'a>(
                 params.region,
                 params.name,
             );
-            let signature = v4::calculate_signature(signing_key, sts.to_string().as_bytes());
+            let string_to_sign = sts.to_string();
+            let signature = v4::calculate_signature(signing_key, string_to_sign.as_bytes());
 
             // Step 4: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-add-signature-to-request.html
             let values = creq.values.as_headers().expect("signing with headers");
@@ -345,7 +419,7 @@ fn calculate_signing_headers<'a>(
                     true,
                 );
             }
-            signature
+            (signature, string_to_sign)
         }
         #[cfg(feature = "sigv4a")]
         SigningParams::V4a(params) => {
@@ -358,7 +432,8 @@ fn calculate_signing_headers<'a>(
 
             let signing_key =
                 v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
-            let signature = v4a::calculate_signature(&signing_key, sts.to_string().as_bytes());
+            let string_to_sign = sts.to_string();
+            let signature = v4a::calculate_signature(&signing_key, string_to_sign.as_bytes());
 
             let values = creq.values.as_headers().expect("signing with headers");
             add_header(&mut headers, header::X_AMZ_DATE, &values.date_time, false);
@@ -397,11 +472,12 @@ fn calculate_signing_headers<'a>(
                     true,
                 );
             }
-            signature
+            (signature, string_to_sign)
         }
     };
+    let details = capture_details(params, creq_string, &string_to_sign);
 
-    Ok(SigningOutput::new(headers, signature))
+    Ok((headers, details, signature))
 }
 
 fn add_header(map: &mut Vec<Header>, key: &'static str, value: &str, sensitive: bool) {
@@ -816,6 +892,43 @@ mod tests {
         assert_req_eq!(expected, signed);
     }
 
+    #[test]
+    fn test_capture_signing_details() {
+        let identity = &Credentials::for_tests().into();
+        let mut settings = SigningSettings::default();
+        let params = v4::SigningParams {
+            identity,
+            region: "us-east-1",
+            name: "service",
+            time: parse_date_time("20150830T123600Z").unwrap(),
+            settings: SigningSettings::default(),
+        }
+        .into();
+        let original = test::v4::test_request("get-vanilla-query-order-key-case");
+        let out = sign(SignableRequest::from(&original), &params).unwrap();
+        assert!(out.output.signing_details().is_none());
+
+        settings.capture_signing_details = true;
+        let params = v4::SigningParams {
+            identity,
+            region: "us-east-1",
+            name: "service",
+            time: parse_date_time("20150830T123600Z").unwrap(),
+            settings,
+        }
+        .into();
+        let out = sign(SignableRequest::from(&original), &params).unwrap();
+        let details = out.output.signing_details().expect("captured");
+        assert_eq!(
+            test::v4::test_canonical_request("get-vanilla-query-order-key-case"),
+            details.canonical_request()
+        );
+        assert_eq!(
+            test::v4::test_sts("get-vanilla-query-order-key-case").trim(),
+            details.string_to_sign()
+        );
+    }
+
     #[test]
     fn test_sign_headers_utf8() {
         let settings = SigningSettings::default();
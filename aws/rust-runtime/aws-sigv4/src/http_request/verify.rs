@@ -0,0 +1,511 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use super::canonical_request::{header, CanonicalRequest, StringToSign, HMAC_256};
+use super::error::SigningError;
+use super::sign::{SignableBody, SignableRequest};
+use super::{PayloadChecksumKind, SessionTokenMode, SignatureLocation, SigningSettings};
+use crate::date_time::{format_date, parse_x_amz_date};
+use crate::sign::v4;
+use aws_credential_types::Credentials;
+use aws_smithy_runtime_api::client::identity::Identity;
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+const AUTHORIZATION: &str = "authorization";
+const HOST: &str = "host";
+
+/// The SigV4 signature of an incoming HTTP request.
+///
+/// The signature is parsed from the `authorization` and `x-amz-date` headers of the request with
+/// [`RequestSignature::from_request`], and can then be checked against the secret access key of
+/// the access key ID that it claims with [`RequestSignature::verify`].
+///
+/// # Example
+///
+/// ```rust
+/// use aws_sigv4::http_request::{RequestSignature, SignableBody, SignableRequest, SigningSettings};
+/// use std::time::{Duration, SystemTime};
+///
+/// # fn lookup_secret_access_key(_access_key_id: &str) -> String { unimplemented!() }
+/// fn authenticate(method: &str, uri: &str, headers: &[(&str, &str)], body: &[u8]) -> bool {
+///     let request = match SignableRequest::new(
+///         method,
+///         uri,
+///         headers.iter().copied(),
+///         SignableBody::Bytes(body),
+///     ) {
+///         Ok(request) => request,
+///         Err(_) => return false,
+///     };
+///     let signature = match RequestSignature::from_request(&request) {
+///         Ok(signature) => signature,
+///         Err(_) => return false,
+///     };
+///     let secret_access_key = lookup_secret_access_key(signature.access_key_id());
+///     signature
+///         .verify(
+///             &request,
+///             &secret_access_key,
+///             SigningSettings::default(),
+///             SystemTime::now(),
+///             Duration::from_secs(5 * 60),
+///         )
+///         .is_ok()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RequestSignature {
+    access_key_id: String,
+    time: SystemTime,
+    region: String,
+    name: String,
+    signed_headers: String,
+    signature: String,
+}
+
+impl RequestSignature {
+    /// Parses the signature of the given request from its `authorization` and `x-amz-date` headers.
+    pub fn from_request(request: &SignableRequest<'_>) -> Result<Self, VerificationError> {
+        let authorization = find_header(request, AUTHORIZATION)
+            .ok_or(VerificationError::missing_header(AUTHORIZATION))?;
+        let x_amz_date = find_header(request, header::X_AMZ_DATE)
+            .ok_or(VerificationError::missing_header(header::X_AMZ_DATE))?;
+        let time = parse_x_amz_date(x_amz_date).ok_or(VerificationError::malformed(
+            "the `x-amz-date` header is invalid",
+        ))?;
+
+        let fields = authorization
+            .strip_prefix(HMAC_256)
+            .and_then(|fields| fields.strip_prefix(' '))
+            .ok_or(VerificationError::malformed(
+                "the signing algorithm isn't supported",
+            ))?;
+        let (mut credential, mut signed_headers, mut signature) = (None, None, None);
+        for field in fields.split(',') {
+            match field.trim().split_once('=') {
+                Some(("Credential", value)) => credential = Some(value),
+                Some(("SignedHeaders", value)) => signed_headers = Some(value),
+                Some(("Signature", value)) => signature = Some(value),
+                _ => return Err(VerificationError::malformed("unexpected field")),
+            }
+        }
+        let credential = credential.ok_or(VerificationError::malformed("missing `Credential`"))?;
+        let signed_headers =
+            signed_headers.ok_or(VerificationError::malformed("missing `SignedHeaders`"))?;
+        let signature = signature.ok_or(VerificationError::malformed("missing `Signature`"))?;
+
+        let scope: Vec<&str> = credential.split('/').collect();
+        let [access_key_id, date, region, name, "aws4_request"] = scope[..] else {
+            return Err(VerificationError::malformed(
+                "the credential scope is invalid",
+            ));
+        };
+        if date != format_date(time) {
+            return Err(VerificationError::malformed(
+                "the credential scope date doesn't match `x-amz-date`",
+            ));
+        }
+
+        Ok(Self {
+            access_key_id: access_key_id.into(),
+            time,
+            region: region.into(),
+            name: name.into(),
+            signed_headers: signed_headers.into(),
+            signature: signature.into(),
+        })
+    }
+
+    /// Returns the access key ID that the request was signed with.
+    pub fn access_key_id(&self) -> &str {
+        &self.access_key_id
+    }
+
+    /// Returns the region from the credential scope of the signature.
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Returns the signing name from the credential scope of the signature.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the time that the request was signed at.
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Returns the names of the headers that were signed.
+    pub fn signed_headers(&self) -> impl Iterator<Item = &str> {
+        self.signed_headers.split(';')
+    }
+
+    /// Verifies the signature of `request` with the given secret access key.
+    ///
+    /// The canonical request is reconstructed from the signed headers of `request`, using the
+    /// percent encoding and URI path normalization modes of `settings`, and the signature is
+    /// recomputed and compared with the one that was sent. The request is rejected if it was
+    /// signed more than `max_clock_skew` before or after `now`.
+    pub fn verify(
+        &self,
+        request: &SignableRequest<'_>,
+        secret_access_key: &str,
+        mut settings: SigningSettings,
+        now: SystemTime,
+        max_clock_skew: Duration,
+    ) -> Result<(), VerificationError> {
+        let skew = match now.duration_since(self.time) {
+            Ok(skew) => skew,
+            Err(err) => err.duration(),
+        };
+        if skew > max_clock_skew {
+            return Err(VerificationError::clock_skew(skew));
+        }
+
+        let is_signed = |name: &str| {
+            self.signed_headers()
+                .any(|signed| signed.eq_ignore_ascii_case(name))
+        };
+        if !is_signed(HOST) {
+            return Err(VerificationError::malformed(
+                "the `host` header must be signed",
+            ));
+        }
+        let content_sha256 = if is_signed(header::X_AMZ_CONTENT_SHA_256) {
+            Some(find_header(request, header::X_AMZ_CONTENT_SHA_256).ok_or(
+                VerificationError::missing_header(header::X_AMZ_CONTENT_SHA_256),
+            )?)
+        } else {
+            None
+        };
+        let session_token = if is_signed(header::X_AMZ_SECURITY_TOKEN) {
+            Some(find_header(request, header::X_AMZ_SECURITY_TOKEN).ok_or(
+                VerificationError::missing_header(header::X_AMZ_SECURITY_TOKEN),
+            )?)
+        } else {
+            None
+        };
+
+        // The date, session token, and content hash headers are added back while signing
+        let headers = request.headers().iter().copied().filter(|(name, _)| {
+            is_signed(name)
+                && ![
+                    header::X_AMZ_DATE,
+                    header::X_AMZ_SECURITY_TOKEN,
+                    header::X_AMZ_CONTENT_SHA_256,
+                ]
+                .iter()
+                .any(|added| name.eq_ignore_ascii_case(added))
+        });
+        let body = match (content_sha256, request.body()) {
+            // A signed content hash only authenticates the body if the body matches it
+            (Some(hash), SignableBody::Bytes(data)) if v4::sha256_hex_string(data) != hash => {
+                return Err(VerificationError::signature_mismatch());
+            }
+            (Some(hash), _) => SignableBody::Precomputed(hash.to_owned()),
+            (None, body) => body.clone(),
+        };
+        let uri = request.uri().to_string();
+        let unsigned = SignableRequest::new(request.method(), uri, headers, body)?;
+
+        settings.payload_checksum_kind = match content_sha256 {
+            Some(_) => PayloadChecksumKind::XAmzSha256,
+            None => PayloadChecksumKind::NoHeader,
+        };
+        settings.session_token_mode = SessionTokenMode::Include;
+        settings.signature_location = SignatureLocation::Headers;
+        settings.expires_in = None;
+        settings.excluded_headers = None;
+
+        let identity: Identity = Credentials::new(
+            &self.access_key_id,
+            secret_access_key,
+            session_token.map(str::to_owned),
+            None,
+            "SignatureVerification",
+        )
+        .into();
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.region)
+            .name(&self.name)
+            .time(self.time)
+            .settings(settings)
+            .build()
+            .expect("all required fields set")
+            .into();
+        let creq = CanonicalRequest::from(&unsigned, &params).map_err(SigningError::from)?;
+        if creq.values.signed_headers().as_str() != self.signed_headers {
+            return Err(VerificationError::signed_headers_mismatch());
+        }
+
+        let encoded_creq = v4::sha256_hex_string(creq.to_string().as_bytes());
+        let string_to_sign =
+            StringToSign::new_v4(self.time, &self.region, &self.name, &encoded_creq).to_string();
+        let signing_key =
+            v4::generate_signing_key(secret_access_key, self.time, &self.region, &self.name);
+        let expected = v4::calculate_signature(signing_key, string_to_sign.as_bytes());
+        if !constant_time_eq(expected.as_bytes(), self.signature.as_bytes()) {
+            return Err(VerificationError::signature_mismatch());
+        }
+        Ok(())
+    }
+}
+
+fn find_header<'a>(request: &'a SignableRequest<'_>, name: &str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| *value)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[derive(Debug)]
+enum VerificationErrorKind {
+    MissingHeader { name: &'static str },
+    MalformedSignature { reason: &'static str },
+    ClockSkew { skew: Duration },
+    SignedHeadersMismatch,
+    SignatureMismatch,
+    InvalidRequest { source: SigningError },
+}
+
+/// Error verifying the signature of a request
+#[derive(Debug)]
+pub struct VerificationError {
+    kind: VerificationErrorKind,
+}
+
+impl VerificationError {
+    fn missing_header(name: &'static str) -> Self {
+        Self {
+            kind: VerificationErrorKind::MissingHeader { name },
+        }
+    }
+
+    fn malformed(reason: &'static str) -> Self {
+        Self {
+            kind: VerificationErrorKind::MalformedSignature { reason },
+        }
+    }
+
+    fn clock_skew(skew: Duration) -> Self {
+        Self {
+            kind: VerificationErrorKind::ClockSkew { skew },
+        }
+    }
+
+    fn signed_headers_mismatch() -> Self {
+        Self {
+            kind: VerificationErrorKind::SignedHeadersMismatch,
+        }
+    }
+
+    fn signature_mismatch() -> Self {
+        Self {
+            kind: VerificationErrorKind::SignatureMismatch,
+        }
+    }
+
+    /// Returns true if the request was well formed, but its signature doesn't match.
+    pub fn is_signature_mismatch(&self) -> bool {
+        matches!(self.kind, VerificationErrorKind::SignatureMismatch)
+    }
+
+    /// Returns true if the request was signed outside of the allowed clock skew window.
+    pub fn is_clock_skew(&self) -> bool {
+        matches!(self.kind, VerificationErrorKind::ClockSkew { .. })
+    }
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            VerificationErrorKind::MissingHeader { name } => {
+                write!(f, "the request is missing the `{name}` header")
+            }
+            VerificationErrorKind::MalformedSignature { reason } => {
+                write!(f, "the request signature is malformed: {reason}")
+            }
+            VerificationErrorKind::ClockSkew { skew } => write!(
+                f,
+                "the request was signed {}s away from the current time",
+                skew.as_secs()
+            ),
+            VerificationErrorKind::SignedHeadersMismatch => {
+                write!(f, "the signed headers aren't all present in the request")
+            }
+            VerificationErrorKind::SignatureMismatch => {
+                write!(f, "the request signature doesn't match")
+            }
+            VerificationErrorKind::InvalidRequest { .. } => {
+                write!(f, "failed to create canonical request")
+            }
+        }
+    }
+}
+
+impl Error for VerificationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            VerificationErrorKind::InvalidRequest { source } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<SigningError> for VerificationError {
+    fn from(source: SigningError) -> Self {
+        Self {
+            kind: VerificationErrorKind::InvalidRequest { source },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_request::{sign, SigningParams};
+    use std::time::UNIX_EPOCH;
+
+    const SECRET: &str = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+
+    fn signing_time() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_440_938_160)
+    }
+
+    fn signed_headers(body: &[u8]) -> Vec<(String, String)> {
+        let identity =
+            Credentials::new("AKIDEXAMPLE", SECRET, Some("token".into()), None, "test").into();
+        let settings = SigningSettings {
+            payload_checksum_kind: PayloadChecksumKind::XAmzSha256,
+            ..Default::default()
+        };
+        let params: SigningParams<'_> = v4::SigningParams::builder()
+            .identity(&identity)
+            .region("us-east-1")
+            .name("service")
+            .time(signing_time())
+            .settings(settings)
+            .build()
+            .unwrap()
+            .into();
+        let request = SignableRequest::new(
+            "POST",
+            "https://example.amazonaws.com/path?b=2&a=1",
+            [("content-type", "application/json"), ("x-custom", "value")].into_iter(),
+            SignableBody::Bytes(body),
+        )
+        .unwrap();
+        let (instructions, _) = sign(request, &params).unwrap().into_parts();
+        let mut headers = vec![
+            ("content-type".to_owned(), "application/json".to_owned()),
+            ("x-custom".to_owned(), "value".to_owned()),
+        ];
+        headers.extend(
+            instructions
+                .headers()
+                .map(|(name, value)| (name.to_owned(), value.to_owned())),
+        );
+        headers
+    }
+
+    fn verify(
+        headers: &[(String, String)],
+        body: &[u8],
+        secret: &str,
+        now: SystemTime,
+    ) -> Result<(), VerificationError> {
+        let request = SignableRequest::new(
+            "POST",
+            "https://example.amazonaws.com/path?b=2&a=1",
+            headers.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+            SignableBody::Bytes(body),
+        )
+        .unwrap();
+        let signature = RequestSignature::from_request(&request)?;
+        assert_eq!("AKIDEXAMPLE", signature.access_key_id());
+        signature.verify(
+            &request,
+            secret,
+            SigningSettings::default(),
+            now,
+            Duration::from_secs(300),
+        )
+    }
+
+    #[test]
+    fn verifies_signed_request() {
+        let headers = signed_headers(b"{}");
+        verify(
+            &headers,
+            b"{}",
+            SECRET,
+            signing_time() + Duration::from_secs(60),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_tampered_request() {
+        let mut headers = signed_headers(b"{}");
+        let err = verify(&headers, b"{}", "wrong-secret", signing_time()).unwrap_err();
+        assert!(err.is_signature_mismatch(), "{}", err);
+        let err = verify(&headers, b"{\"a\":1}", SECRET, signing_time()).unwrap_err();
+        assert!(err.is_signature_mismatch(), "{}", err);
+
+        headers
+            .iter_mut()
+            .find(|(name, _)| name == "x-custom")
+            .unwrap()
+            .1 = "tampered".into();
+        let err = verify(&headers, b"{}", SECRET, signing_time()).unwrap_err();
+        assert!(err.is_signature_mismatch(), "{}", err);
+
+        headers.retain(|(name, _)| name != "x-custom");
+        let err = verify(&headers, b"{}", SECRET, signing_time()).unwrap_err();
+        assert!(
+            matches!(err.kind, VerificationErrorKind::SignedHeadersMismatch),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn rejects_requests_outside_clock_skew_window() {
+        let headers = signed_headers(b"{}");
+        let err = verify(
+            &headers,
+            b"{}",
+            SECRET,
+            signing_time() - Duration::from_secs(301),
+        )
+        .unwrap_err();
+        assert!(err.is_clock_skew(), "{}", err);
+    }
+
+    #[test]
+    fn rejects_malformed_authorization() {
+        let mut headers = signed_headers(b"{}");
+        for (name, value) in headers.iter_mut() {
+            if name == "authorization" {
+                *value = value.replace("/aws4_request", "/aws5_request");
+            }
+        }
+        let err = verify(&headers, b"{}", SECRET, signing_time()).unwrap_err();
+        assert!(
+            matches!(err.kind, VerificationErrorKind::MalformedSignature { .. }),
+            "{}",
+            err
+        );
+    }
+}
@@ -21,6 +21,9 @@ pub mod sign;
 
 mod date_time;
 
+#[cfg(feature = "sign-http")]
+pub mod chunk;
+
 #[cfg(feature = "sign-eventstream")]
 pub mod event_stream;
 
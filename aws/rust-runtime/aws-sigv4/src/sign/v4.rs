@@ -6,9 +6,17 @@
 use crate::date_time::format_date;
 use aws_smithy_runtime_api::client::identity::Identity;
 use hmac::{digest::FixedOutput, Hmac, Mac};
+use once_cell::sync::Lazy;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
 use std::time::SystemTime;
 
+const SIGNING_KEY_CACHE_CAPACITY: usize = 64;
+
+static SIGNING_KEY_CACHE: Lazy<Mutex<SigningKeyCache>> =
+    Lazy::new(|| Mutex::new(SigningKeyCache::new(SIGNING_KEY_CACHE_CAPACITY)));
+
 /// HashedPayload = Lowercase(HexEncode(Hash(requestPayload)))
 #[allow(dead_code)] // Unused when compiling without certain features
 pub(crate) fn sha256_hex_string(bytes: impl AsRef<[u8]>) -> String {
@@ -26,12 +34,33 @@ pub fn calculate_signature(signing_key: impl AsRef<[u8]>, string_to_sign: &[u8])
 }
 
 /// Generates a signing key for Sigv4
+///
+/// Signing keys only change per secret, date, region, and service, so recently derived keys are
+/// cached and reused by later requests.
 pub fn generate_signing_key(
     secret: &str,
     time: SystemTime,
     region: &str,
     service: &str,
 ) -> impl AsRef<[u8]> {
+    let date = format_date(time);
+    let cache_key = SigningKeyCache::key(secret, &date, region, service);
+    if let Some(signing_key) = SIGNING_KEY_CACHE
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.get(&cache_key))
+    {
+        return signing_key;
+    }
+
+    let signing_key = derive_signing_key(secret, &date, region, service);
+    if let Ok(mut cache) = SIGNING_KEY_CACHE.lock() {
+        cache.insert(cache_key, signing_key);
+    }
+    signing_key
+}
+
+fn derive_signing_key(secret: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
     // kSecret = your secret access key
     // kDate = HMAC("AWS4" + kSecret, Date)
     // kRegion = HMAC(kDate, Region)
@@ -41,7 +70,7 @@ pub fn generate_signing_key(
     let secret = format!("AWS4{}", secret);
     let mut mac =
         Hmac::<Sha256>::new_from_slice(secret.as_ref()).expect("HMAC can take key of any size");
-    mac.update(format_date(time).as_bytes());
+    mac.update(date.as_bytes());
     let tag = mac.finalize_fixed();
 
     // sign region
@@ -57,7 +86,59 @@ pub fn generate_signing_key(
     // sign request
     let mut mac = Hmac::<Sha256>::new_from_slice(&tag).expect("HMAC can take key of any size");
     mac.update("aws4_request".as_bytes());
-    mac.finalize_fixed()
+    mac.finalize_fixed().into()
+}
+
+/// A least-recently-used cache of derived signing keys.
+///
+/// Entries are keyed on a digest of the secret, date, region, and service, so that secrets
+/// aren't retained by the cache.
+struct SigningKeyCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<[u8; 32], ([u8; 32], u64)>,
+}
+
+impl SigningKeyCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::with_capacity(capacity),
+        }
+    }
+
+    fn key(secret: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for part in [secret, date, region, service] {
+            // Length-prefix each part so that different tuples can't produce the same input
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part.as_bytes());
+        }
+        hasher.finalize_fixed().into()
+    }
+
+    fn get(&mut self, key: &[u8; 32]) -> Option<[u8; 32]> {
+        self.tick += 1;
+        let (signing_key, last_used) = self.entries.get_mut(key)?;
+        *last_used = self.tick;
+        Some(*signing_key)
+    }
+
+    fn insert(&mut self, key: [u8; 32], signing_key: [u8; 32]) {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let least_recently_used = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| *key);
+            if let Some(least_recently_used) = least_recently_used {
+                self.entries.remove(&least_recently_used);
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(key, (signing_key, self.tick));
+    }
 }
 
 /// Parameters to use when signing.
@@ -194,7 +275,10 @@ pub mod signing_params {
 
 #[cfg(test)]
 mod tests {
-    use super::{calculate_signature, generate_signing_key, sha256_hex_string};
+    use super::{
+        calculate_signature, derive_signing_key, generate_signing_key, sha256_hex_string,
+        SigningKeyCache,
+    };
     use crate::date_time::test_parsers::parse_date_time;
     use crate::http_request::test;
 
@@ -217,4 +301,34 @@ mod tests {
         let actual = sha256_hex_string([]);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn signing_key_cache_evicts_least_recently_used() {
+        let key = |service: &str| SigningKeyCache::key("secret", "20150830", "us-east-1", service);
+        let mut cache = SigningKeyCache::new(2);
+        cache.insert(key("a"), [1; 32]);
+        cache.insert(key("b"), [2; 32]);
+        assert_eq!(Some([1; 32]), cache.get(&key("a")));
+
+        cache.insert(key("c"), [3; 32]);
+        assert_eq!(None, cache.get(&key("b")));
+        assert_eq!(Some([1; 32]), cache.get(&key("a")));
+        assert_eq!(Some([3; 32]), cache.get(&key("c")));
+        assert_ne!(
+            key("c"),
+            SigningKeyCache::key("secret", "20150830", "us-east-1c", "")
+        );
+    }
+
+    #[test]
+    fn cached_signing_keys_match_derived_keys() {
+        let time = parse_date_time("20150830T123600Z").unwrap();
+        let secret = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+        for _ in 0..2 {
+            assert_eq!(
+                derive_signing_key(secret, "20150830", "us-east-1", "iam"),
+                generate_signing_key(secret, time, "us-east-1", "iam").as_ref()
+            );
+        }
+    }
 }
@@ -22,6 +22,7 @@ pub mod os_shim_internal;
 pub mod region;
 pub mod request_id;
 pub mod sdk_config;
+pub mod user_agent;
 pub use sdk_config::SdkConfig;
 
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
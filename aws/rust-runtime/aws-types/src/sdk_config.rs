@@ -11,7 +11,9 @@
 
 use crate::app_name::AppName;
 use crate::docs_for;
+use crate::endpoint_config::AccountIdEndpointMode;
 use crate::region::Region;
+use crate::user_agent::UserAgentMetadata;
 
 pub use aws_credential_types::provider::SharedCredentialsProvider;
 use aws_smithy_async::rt::sleep::AsyncSleep;
@@ -42,6 +44,12 @@ If no dual-stack endpoint is available the request MAY return an error.
 **Note**: Some services do not offer dual-stack as a configurable parameter (e.g. Code Catalyst). For
 these services, this setting has no effect"
         };
+        (account_id_endpoint_mode) => {
+"Controls whether the account ID from the resolved credentials is used to route requests.
+
+This only applies to services with account-specific endpoints. When set to `required`, requests
+fail if the credentials don't include an account ID. Defaults to `preferred`."
+        };
 
         (time_source) => { "The time source use to use for this client. This only needs to be required for creating deterministic tests or platforms where `SystemTime::now()` is not supported." };
     }
@@ -62,6 +70,8 @@ pub struct SdkConfig {
     http_client: Option<SharedHttpClient>,
     use_fips: Option<bool>,
     use_dual_stack: Option<bool>,
+    account_id_endpoint_mode: Option<AccountIdEndpointMode>,
+    user_agent_metadata: Option<UserAgentMetadata>,
 }
 
 /// Builder for AWS Shared Configuration
@@ -83,6 +93,8 @@ pub struct Builder {
     http_client: Option<SharedHttpClient>,
     use_fips: Option<bool>,
     use_dual_stack: Option<bool>,
+    account_id_endpoint_mode: Option<AccountIdEndpointMode>,
+    user_agent_metadata: Option<UserAgentMetadata>,
 }
 
 impl Builder {
@@ -427,6 +439,27 @@ impl Builder {
         self
     }
 
+    /// Sets product tokens and feature flags to append to the user agent of requests.
+    ///
+    /// This lets frameworks built on the SDK tag the traffic they send, so that it can be
+    /// attributed on the server side. See [`UserAgentMetadata`] for the validation rules.
+    pub fn user_agent_metadata(mut self, user_agent_metadata: UserAgentMetadata) -> Self {
+        self.set_user_agent_metadata(Some(user_agent_metadata));
+        self
+    }
+
+    /// Sets product tokens and feature flags to append to the user agent of requests.
+    ///
+    /// This lets frameworks built on the SDK tag the traffic they send, so that it can be
+    /// attributed on the server side. See [`UserAgentMetadata`] for the validation rules.
+    pub fn set_user_agent_metadata(
+        &mut self,
+        user_agent_metadata: Option<UserAgentMetadata>,
+    ) -> &mut Self {
+        self.user_agent_metadata = user_agent_metadata;
+        self
+    }
+
     /// Sets the HTTP client to use when making requests.
     ///
     /// ## Examples
@@ -524,6 +557,24 @@ impl Builder {
         self
     }
 
+    #[doc = docs_for!(account_id_endpoint_mode)]
+    pub fn account_id_endpoint_mode(
+        mut self,
+        account_id_endpoint_mode: AccountIdEndpointMode,
+    ) -> Self {
+        self.set_account_id_endpoint_mode(Some(account_id_endpoint_mode));
+        self
+    }
+
+    #[doc = docs_for!(account_id_endpoint_mode)]
+    pub fn set_account_id_endpoint_mode(
+        &mut self,
+        account_id_endpoint_mode: Option<AccountIdEndpointMode>,
+    ) -> &mut Self {
+        self.account_id_endpoint_mode = account_id_endpoint_mode;
+        self
+    }
+
     #[doc = docs_for!(time_source)]
     pub fn time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
         self.set_time_source(Some(SharedTimeSource::new(time_source)));
@@ -550,6 +601,8 @@ impl Builder {
             http_client: self.http_client,
             use_fips: self.use_fips,
             use_dual_stack: self.use_dual_stack,
+            account_id_endpoint_mode: self.account_id_endpoint_mode,
+            user_agent_metadata: self.user_agent_metadata,
             time_source: self.time_source,
         }
     }
@@ -602,6 +655,11 @@ impl SdkConfig {
         self.app_name.as_ref()
     }
 
+    /// Configured user agent metadata
+    pub fn user_agent_metadata(&self) -> Option<&UserAgentMetadata> {
+        self.user_agent_metadata.as_ref()
+    }
+
     /// Configured HTTP client
     pub fn http_client(&self) -> Option<SharedHttpClient> {
         self.http_client.clone()
@@ -617,6 +675,11 @@ impl SdkConfig {
         self.use_dual_stack
     }
 
+    /// Configured account ID endpoint mode
+    pub fn account_id_endpoint_mode(&self) -> Option<AccountIdEndpointMode> {
+        self.account_id_endpoint_mode
+    }
+
     /// Config builder
     ///
     /// _Important:_ Using the `aws-config` crate to configure the SDK is preferred to invoking this
@@ -646,6 +709,8 @@ impl SdkConfig {
             http_client: self.http_client,
             use_fips: self.use_fips,
             use_dual_stack: self.use_dual_stack,
+            account_id_endpoint_mode: self.account_id_endpoint_mode,
+            user_agent_metadata: self.user_agent_metadata,
         }
     }
 }
@@ -0,0 +1,341 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Custom metadata that can be appended to the user agent of an AWS SDK client.
+
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// Maximum length of a single product name, product version, or feature flag.
+const MAX_TOKEN_LEN: usize = 64;
+/// Maximum number of product tokens.
+const MAX_PRODUCTS: usize = 8;
+/// Maximum number of feature flags.
+const MAX_FEATURE_FLAGS: usize = 16;
+/// Maximum length of all of the metadata once it's rendered into the user agent.
+const MAX_RENDERED_LEN: usize = 512;
+
+/// A product name and optional version that identifies a framework or library built on the SDK
+///
+/// Product tokens are sent as `lib/<name>[/<version>]` in the `x-amz-user-agent` header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProductToken {
+    name: Cow<'static, str>,
+    version: Option<Cow<'static, str>>,
+}
+
+impl ProductToken {
+    /// Creates a new product token without a version.
+    ///
+    /// The name and version are validated when the [`UserAgentMetadata`] is built.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+        }
+    }
+
+    /// Sets the product version.
+    pub fn with_version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Returns the product name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the product version, if one was given.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    fn rendered_len(&self) -> usize {
+        "lib/".len() + self.name.len() + self.version.as_ref().map_or(0, |v| v.len() + 1)
+    }
+}
+
+/// Product tokens and feature flags that get appended to the user agent of requests
+///
+/// Platform teams that build frameworks on top of the SDK can use this to tag the traffic that
+/// their framework sends, so that it can be attributed on the server side. Product tokens are
+/// sent as `lib/<name>[/<version>]`, and feature flags are sent as `ft/<flag>`.
+///
+/// Names, versions, and flags may only have alphanumeric characters and any of these characters:
+/// ```text
+/// !#$%&'*+-.^_`|~
+/// ```
+/// Each of them can be at most 64 characters long. Up to 8 product tokens and 16 feature flags
+/// can be given, and all of the metadata together can take up at most 512 characters of the
+/// user agent.
+///
+/// # Examples
+/// ```rust
+/// use aws_types::user_agent::{ProductToken, UserAgentMetadata};
+///
+/// let metadata = UserAgentMetadata::builder()
+///     .product(ProductToken::new("my-platform").with_version("2.1.0"))
+///     .feature_flag("batching")
+///     .build()
+///     .expect("valid user agent metadata");
+/// assert_eq!(1, metadata.products().count());
+/// assert_eq!(vec!["batching"], metadata.feature_flags().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UserAgentMetadata {
+    products: Vec<ProductToken>,
+    feature_flags: Vec<Cow<'static, str>>,
+}
+
+impl UserAgentMetadata {
+    /// Returns a builder for `UserAgentMetadata`.
+    pub fn builder() -> UserAgentMetadataBuilder {
+        UserAgentMetadataBuilder::default()
+    }
+
+    /// Returns the product tokens in the order they were added.
+    pub fn products(&self) -> impl Iterator<Item = &ProductToken> {
+        self.products.iter()
+    }
+
+    /// Returns the feature flags in the order they were added.
+    pub fn feature_flags(&self) -> impl Iterator<Item = &str> {
+        self.feature_flags.iter().map(|flag| flag.as_ref())
+    }
+}
+
+impl Storable for UserAgentMetadata {
+    type Storer = StoreReplace<UserAgentMetadata>;
+}
+
+/// Builder for [`UserAgentMetadata`]
+#[derive(Clone, Debug, Default)]
+pub struct UserAgentMetadataBuilder {
+    products: Vec<ProductToken>,
+    feature_flags: Vec<Cow<'static, str>>,
+}
+
+impl UserAgentMetadataBuilder {
+    /// Adds a product token.
+    pub fn product(mut self, product: ProductToken) -> Self {
+        self.products.push(product);
+        self
+    }
+
+    /// Sets the product tokens, replacing any that were added before.
+    pub fn set_products(&mut self, products: Vec<ProductToken>) -> &mut Self {
+        self.products = products;
+        self
+    }
+
+    /// Adds a feature flag.
+    pub fn feature_flag(mut self, feature_flag: impl Into<Cow<'static, str>>) -> Self {
+        self.feature_flags.push(feature_flag.into());
+        self
+    }
+
+    /// Sets the feature flags, replacing any that were added before.
+    pub fn set_feature_flags(&mut self, feature_flags: Vec<Cow<'static, str>>) -> &mut Self {
+        self.feature_flags = feature_flags;
+        self
+    }
+
+    /// Builds the `UserAgentMetadata`, validating the characters and lengths of every token.
+    pub fn build(self) -> Result<UserAgentMetadata, InvalidUserAgentMetadata> {
+        if self.products.len() > MAX_PRODUCTS {
+            return Err(InvalidUserAgentMetadataKind::TooManyProducts {
+                count: self.products.len(),
+            }
+            .into());
+        }
+        if self.feature_flags.len() > MAX_FEATURE_FLAGS {
+            return Err(InvalidUserAgentMetadataKind::TooManyFeatureFlags {
+                count: self.feature_flags.len(),
+            }
+            .into());
+        }
+        for product in &self.products {
+            validate_token(&product.name)?;
+            if let Some(version) = &product.version {
+                validate_token(version)?;
+            }
+        }
+        for flag in &self.feature_flags {
+            validate_token(flag)?;
+        }
+
+        // Every token is separated from the previous one by a space
+        let rendered_len = self
+            .products
+            .iter()
+            .map(|product| product.rendered_len() + 1)
+            .chain(
+                self.feature_flags
+                    .iter()
+                    .map(|flag| "ft/".len() + flag.len() + 1),
+            )
+            .sum::<usize>();
+        if rendered_len > MAX_RENDERED_LEN {
+            return Err(InvalidUserAgentMetadataKind::TooLong { rendered_len }.into());
+        }
+
+        Ok(UserAgentMetadata {
+            products: self.products,
+            feature_flags: self.feature_flags,
+        })
+    }
+}
+
+fn validate_token(token: &str) -> Result<(), InvalidUserAgentMetadata> {
+    fn valid_character(c: char) -> bool {
+        match c {
+            _ if c.is_ascii_alphanumeric() => true,
+            '!' | '#' | '$' | '%' | '&' | '\'' | '*' | '+' | '-' | '.' | '^' | '_' | '`' | '|'
+            | '~' => true,
+            _ => false,
+        }
+    }
+    if token.is_empty() {
+        return Err(InvalidUserAgentMetadataKind::EmptyToken.into());
+    }
+    if token.len() > MAX_TOKEN_LEN {
+        return Err(InvalidUserAgentMetadataKind::TokenTooLong {
+            token: token.to_string(),
+        }
+        .into());
+    }
+    if !token.chars().all(valid_character) {
+        return Err(InvalidUserAgentMetadataKind::InvalidCharacter {
+            token: token.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+enum InvalidUserAgentMetadataKind {
+    EmptyToken,
+    TokenTooLong { token: String },
+    InvalidCharacter { token: String },
+    TooManyProducts { count: usize },
+    TooManyFeatureFlags { count: usize },
+    TooLong { rendered_len: usize },
+}
+
+/// Failure to build [`UserAgentMetadata`]
+#[derive(Debug)]
+pub struct InvalidUserAgentMetadata {
+    kind: InvalidUserAgentMetadataKind,
+}
+
+impl From<InvalidUserAgentMetadataKind> for InvalidUserAgentMetadata {
+    fn from(kind: InvalidUserAgentMetadataKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for InvalidUserAgentMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use InvalidUserAgentMetadataKind::*;
+        match &self.kind {
+            EmptyToken => write!(f, "user agent metadata can't be empty"),
+            TokenTooLong { token } => write!(
+                f,
+                "user agent metadata `{token}` is longer than {MAX_TOKEN_LEN} characters"
+            ),
+            InvalidCharacter { token } => write!(
+                f,
+                "user agent metadata `{token}` can only have alphanumeric characters, or any of \
+                 '!' |  '#' |  '$' |  '%' |  '&' |  '\\'' |  '*' |  '+' |  '-' | \
+                 '.' |  '^' |  '_' |  '`' |  '|' |  '~'"
+            ),
+            TooManyProducts { count } => write!(
+                f,
+                "{count} product tokens were given, but at most {MAX_PRODUCTS} are allowed"
+            ),
+            TooManyFeatureFlags { count } => write!(
+                f,
+                "{count} feature flags were given, but at most {MAX_FEATURE_FLAGS} are allowed"
+            ),
+            TooLong { rendered_len } => write!(
+                f,
+                "user agent metadata would add {rendered_len} characters to the user agent, \
+                 but at most {MAX_RENDERED_LEN} are allowed"
+            ),
+        }
+    }
+}
+
+impl Error for InvalidUserAgentMetadata {}
+
+#[cfg(test)]
+mod test {
+    use super::{ProductToken, UserAgentMetadata};
+
+    #[test]
+    fn user_agent_metadata_validation() {
+        let build = |products: Vec<ProductToken>, flags: Vec<&'static str>| {
+            let mut builder = UserAgentMetadata::builder();
+            builder.set_products(products);
+            builder.set_feature_flags(flags.into_iter().map(Into::into).collect());
+            builder.build().map_err(|err| err.to_string())
+        };
+
+        let metadata = build(
+            vec![
+                ProductToken::new("my-platform").with_version("2.1.0"),
+                ProductToken::new("plugin"),
+            ],
+            vec!["batching", "v2_paths"],
+        )
+        .unwrap();
+        assert_eq!(
+            vec![("my-platform", Some("2.1.0")), ("plugin", None)],
+            metadata
+                .products()
+                .map(|p| (p.name(), p.version()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["batching", "v2_paths"],
+            metadata.feature_flags().collect::<Vec<_>>()
+        );
+
+        for (products, flags, message) in [
+            (vec![ProductToken::new("")], vec![], "empty"),
+            (
+                vec![ProductToken::new("my platform")],
+                vec![],
+                "alphanumeric",
+            ),
+            (vec![], vec!["a/b"], "alphanumeric"),
+            (
+                vec![ProductToken::new("a".repeat(65))],
+                vec![],
+                "longer than 64",
+            ),
+            (
+                (0..9).map(|i| ProductToken::new(format!("p{i}"))).collect(),
+                vec![],
+                "at most 8",
+            ),
+            (vec![], vec!["flag"; 17], "at most 16"),
+            (
+                (0..8)
+                    .map(|i| ProductToken::new(format!("{i}").repeat(60)).with_version("1.0"))
+                    .collect(),
+                vec![],
+                "at most 512",
+            ),
+        ] {
+            let err = build(products, flags).unwrap_err();
+            assert!(err.contains(message), "{err}");
+        }
+    }
+}
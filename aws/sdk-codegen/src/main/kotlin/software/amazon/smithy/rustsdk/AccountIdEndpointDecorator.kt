@@ -0,0 +1,213 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import software.amazon.smithy.model.node.Node
+import software.amazon.smithy.rulesengine.language.syntax.parameters.Parameter
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.memberName
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.extendIf
+import software.amazon.smithy.rust.codegen.core.util.thenSingletonListOf
+
+private const val ACCOUNT_ID = "AWS::Auth::AccountId"
+private const val ACCOUNT_ID_ENDPOINT_MODE = "AWS::Auth::AccountIdEndpointMode"
+
+/**
+ * Routes requests to account-specific endpoints for services whose endpoint rules use the `AWS::Auth::AccountId`
+ * built-in.
+ *
+ * The account ID isn't known until the identity is resolved, so this asks the orchestrator to resolve the identity
+ * before the endpoint (unless `account_id_endpoint_mode` is `disabled`), and copies the account ID from the resolved
+ * credentials into the endpoint params. The endpoint rules decide what to do with it based on the
+ * `AWS::Auth::AccountIdEndpointMode` built-in.
+ */
+class AccountIdEndpointDecorator : ClientCodegenDecorator {
+    override val name: String = "AccountIdEndpoint"
+    override val order: Byte = 0
+
+    private fun usesAccountId(codegenContext: ClientCodegenContext) = codegenContext.getBuiltIn(ACCOUNT_ID) != null
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> {
+        return baseCustomizations.extendIf(usesAccountId(codegenContext)) {
+            AccountIdEndpointModeConfig(codegenContext)
+        }
+    }
+
+    override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> {
+        return usesAccountId(codegenContext).thenSingletonListOf {
+            SdkConfigCustomization.copyField("account_id_endpoint_mode", null)
+        }
+    }
+
+    override fun endpointCustomizations(codegenContext: ClientCodegenContext): List<EndpointCustomization> {
+        val accountIdParam = codegenContext.getBuiltIn(ACCOUNT_ID) ?: return listOf()
+        val runtimeConfig = codegenContext.runtimeConfig
+        val codegenScope = arrayOf(
+            *preludeScope,
+            "AccountIdEndpointMode" to AwsRuntimeType.awsTypes(runtimeConfig)
+                .resolve("endpoint_config::AccountIdEndpointMode"),
+            "Credentials" to AwsRuntimeType.awsCredentialTypes(runtimeConfig).resolve("Credentials"),
+            "Identity" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::identity::Identity"),
+            "TestCredentials" to AwsRuntimeType.awsCredentialTypesTestUtil(runtimeConfig).resolve("Credentials"),
+        )
+        return listOf(
+            object : EndpointCustomization {
+                override fun loadBuiltInFromServiceConfig(parameter: Parameter, configRef: String): Writable? =
+                    when (parameter.builtIn.orElse(null)) {
+                        ACCOUNT_ID_ENDPOINT_MODE -> writable {
+                            rustTemplate(
+                                "$configRef.load::<#{AccountIdEndpointMode}>().map(|mode| mode.to_string())",
+                                *codegenScope,
+                            )
+                        }
+
+                        else -> null
+                    }
+
+                override fun setBuiltInOnServiceConfig(name: String, value: Node, configBuilderRef: String): Writable? =
+                    when (name) {
+                        ACCOUNT_ID -> writable {
+                            rustTemplate(
+                                """
+                                let $configBuilderRef = $configBuilderRef.credentials_provider(
+                                    #{TestCredentials}::for_tests().with_account_id(${value.expectStringNode().value.dq()}),
+                                );
+                                """,
+                                *codegenScope,
+                            )
+                        }
+
+                        ACCOUNT_ID_ENDPOINT_MODE -> writable {
+                            rustTemplate(
+                                """
+                                let $configBuilderRef = $configBuilderRef.account_id_endpoint_mode(
+                                    ${value.expectStringNode().value.dq()}.parse::<#{AccountIdEndpointMode}>().expect("valid mode"),
+                                );
+                                """,
+                                *codegenScope,
+                            )
+                        }
+
+                        else -> null
+                    }
+
+                override fun finalizeParams(paramsRef: String, resolverParamsRef: String): Writable = writable {
+                    rustTemplate(
+                        """
+                        if let #{Some}(account_id) = $resolverParamsRef
+                            .get_property::<#{Identity}>()
+                            .and_then(|identity| identity.data::<#{Credentials}>())
+                            .and_then(|credentials| credentials.account_id())
+                        {
+                            $paramsRef.${accountIdParam.memberName()} = #{Some}(account_id.to_owned());
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+            },
+        )
+    }
+}
+
+private class AccountIdEndpointModeConfig(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "AccountIdEndpointMode" to configReexport(
+            AwsRuntimeType.awsTypes(runtimeConfig).resolve("endpoint_config::AccountIdEndpointMode"),
+        ),
+        "ResolveIdentityBeforeEndpoint" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+            .resolve("client::endpoint::ResolveIdentityBeforeEndpoint"),
+    )
+
+    override fun section(section: ServiceConfig) = writable {
+        when (section) {
+            ServiceConfig.ConfigImpl -> {
+                rustTemplate(
+                    """
+                    /// Returns the account ID endpoint mode, if it was set.
+                    pub fn account_id_endpoint_mode(&self) -> #{Option}<#{AccountIdEndpointMode}> {
+                        self.config.load::<#{AccountIdEndpointMode}>().copied()
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            ServiceConfig.BuilderImpl -> {
+                rustTemplate(
+                    """
+                    /// Controls whether the account ID from the resolved credentials is used to route requests.
+                    ///
+                    /// When the credentials include an account ID, requests are sent to an account-specific
+                    /// endpoint by default. Set this to `disabled` to always use the regional endpoint, or to
+                    /// `required` to fail requests when the credentials don't include an account ID.
+                    pub fn account_id_endpoint_mode(mut self, account_id_endpoint_mode: #{AccountIdEndpointMode}) -> Self {
+                        self.set_account_id_endpoint_mode(#{Some}(account_id_endpoint_mode));
+                        self
+                    }
+
+                    /// Controls whether the account ID from the resolved credentials is used to route requests.
+                    pub fn set_account_id_endpoint_mode(
+                        &mut self,
+                        account_id_endpoint_mode: #{Option}<#{AccountIdEndpointMode}>,
+                    ) -> &mut Self {
+                        self.config.store_or_unset(account_id_endpoint_mode);
+                        self
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            ServiceConfig.BuilderBuild -> {
+                rustTemplate(
+                    """
+                    if layer.load::<#{AccountIdEndpointMode}>() != #{Some}(&#{AccountIdEndpointMode}::Disabled) {
+                        layer.store_put(#{ResolveIdentityBeforeEndpoint}::new());
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            is ServiceConfig.OperationConfigOverride -> {
+                rustTemplate(
+                    """
+                    match resolver.config_mut().load::<#{AccountIdEndpointMode}>() {
+                        #{Some}(#{AccountIdEndpointMode}::Disabled) => {
+                            resolver.config_mut().unset::<#{ResolveIdentityBeforeEndpoint}>();
+                        }
+                        #{Some}(_) => {
+                            resolver.config_mut().store_put(#{ResolveIdentityBeforeEndpoint}::new());
+                        }
+                        #{None} => {}
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            else -> {}
+        }
+    }
+}
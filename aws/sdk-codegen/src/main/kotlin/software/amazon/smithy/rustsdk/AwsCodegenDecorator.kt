@@ -46,6 +46,9 @@ val DECORATORS: List<ClientCodegenDecorator> = listOf(
         AwsCrateDocsDecorator(),
         AwsEndpointsStdLib(),
         *PromotedBuiltInsDecorators,
+        AccountIdEndpointDecorator(),
+        LocalEndpointDecorator(),
+        EndpointUrlValidationDecorator(),
         GenericSmithySdkConfigSettings(),
         OperationInputTestDecorator(),
         AwsRequestIdDecorator(),
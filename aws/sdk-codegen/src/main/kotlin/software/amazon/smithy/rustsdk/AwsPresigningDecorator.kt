@@ -5,9 +5,11 @@
 
 package software.amazon.smithy.rustsdk
 
+import software.amazon.smithy.aws.traits.auth.SigV4Trait
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.knowledge.HttpBinding
 import software.amazon.smithy.model.knowledge.HttpBindingIndex
+import software.amazon.smithy.model.knowledge.TopDownIndex
 import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.ServiceShape
@@ -35,6 +37,8 @@ import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.pre
 import software.amazon.smithy.rust.codegen.core.smithy.contextName
 import software.amazon.smithy.rust.codegen.core.util.cloneOperation
 import software.amazon.smithy.rust.codegen.core.util.expectTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.isEventStream
 import software.amazon.smithy.rustsdk.traits.PresignableTrait
 import kotlin.streams.toList
 
@@ -46,6 +50,9 @@ private val presigningTypes: List<Pair<String, Any>> = listOf(
 internal enum class PayloadSigningType {
     EMPTY,
     UNSIGNED_PAYLOAD,
+
+    /** Sign the serialized request body, or sign streaming request bodies with `UNSIGNED-PAYLOAD`. */
+    REQUEST_BODY,
 }
 
 private fun syntheticShapeId(shape: ToShapeId): ShapeId =
@@ -82,6 +89,25 @@ internal val PRESIGNABLE_OPERATIONS by lazy {
     )
 }
 
+/** Presigning for operations of SigV4 services that don't have hand-written presigning support. */
+private val GENERIC_PRESIGNABLE_OPERATION = PresignableOperation(PayloadSigningType.REQUEST_BODY)
+
+/**
+ * Returns how the given operation is presigned, or null if it can't be presigned.
+ *
+ * Operations with hand-written presigning support are presigned as configured. Any other operation of a SigV4
+ * service can be presigned as well, except for event stream operations.
+ */
+internal fun presignableOperation(
+    model: Model,
+    service: ServiceShape,
+    operation: OperationShape,
+    presignableOperations: Map<ShapeId, PresignableOperation> = PRESIGNABLE_OPERATIONS,
+): PresignableOperation? =
+    presignableOperations[operation.id] ?: GENERIC_PRESIGNABLE_OPERATION.takeIf {
+        service.hasTrait<SigV4Trait>() && !operation.isEventStream(model)
+    }
+
 class AwsPresigningDecorator internal constructor(
     private val presignableOperations: Map<ShapeId, PresignableOperation> = PRESIGNABLE_OPERATIONS,
 ) : ClientCodegenDecorator {
@@ -93,14 +119,18 @@ class AwsPresigningDecorator internal constructor(
     override val order: Byte = ORDER
 
     /**
-     * Adds presignable trait to known presignable operations and creates synthetic presignable shapes for codegen
+     * Adds presignable trait to presignable operations and creates synthetic presignable shapes for codegen
      */
     override fun transformModel(service: ServiceShape, model: Model, settings: ClientRustSettings): Model {
         val modelWithSynthetics = addSyntheticOperations(model)
+        val serviceOperations = TopDownIndex.of(model).getContainedOperations(service).map { it.id }.toSet()
         val presignableTransforms = mutableListOf<PresignModelTransform>()
         val intermediate = ModelTransformer.create().mapShapes(modelWithSynthetics) { shape ->
-            if (shape is OperationShape && presignableOperations.containsKey(shape.id)) {
-                presignableTransforms.addAll(presignableOperations.getValue(shape.id).modelTransforms)
+            val presignableOp = (shape as? OperationShape)
+                ?.takeIf { serviceOperations.contains(it.id) || presignableOperations.containsKey(it.id) }
+                ?.let { presignableOperation(model, service, it, presignableOperations) }
+            if (presignableOp != null) {
+                presignableTransforms.addAll(presignableOp.modelTransforms)
                 shape.toBuilder().addTrait(PresignableTrait(syntheticShapeId(shape))).build()
             } else {
                 shape
@@ -157,7 +187,8 @@ class AwsPresignedFluentBuilderMethod(
         }
 
     private fun RustWriter.renderPresignedMethodBody(section: FluentClientSection.FluentBuilderImpl) {
-        val presignableOp = PRESIGNABLE_OPERATIONS.getValue(section.operationShape.id)
+        val presignableOp =
+            presignableOperation(codegenContext.model, codegenContext.serviceShape, section.operationShape)!!
         val operationShape = if (presignableOp.hasModelTransforms()) {
             codegenContext.model.expectShape(syntheticShapeId(section.operationShape.id), OperationShape::class.java)
         } else {
@@ -228,14 +259,21 @@ class AwsPresignedFluentBuilderMethod(
                 }
             },
             "payload_override" to writable {
-                rustTemplate(
-                    "#{aws_sigv4}::http_request::SignableBody::" +
-                        when (presignableOp.payloadSigningType) {
-                            PayloadSigningType.EMPTY -> "Bytes(b\"\")"
-                            PayloadSigningType.UNSIGNED_PAYLOAD -> "UnsignedPayload"
-                        },
-                    "aws_sigv4" to AwsRuntimeType.awsSigv4(runtimeConfig),
-                )
+                when (presignableOp.payloadSigningType) {
+                    PayloadSigningType.EMPTY -> "Bytes(b\"\")"
+                    PayloadSigningType.UNSIGNED_PAYLOAD -> "UnsignedPayload"
+                    PayloadSigningType.REQUEST_BODY -> null
+                }.let { signableBody ->
+                    if (signableBody == null) {
+                        rustTemplate("#{None}", *preludeScope)
+                    } else {
+                        rustTemplate(
+                            "#{Some}(#{aws_sigv4}::http_request::SignableBody::$signableBody)",
+                            *preludeScope,
+                            "aws_sigv4" to AwsRuntimeType.awsSigv4(runtimeConfig),
+                        )
+                    }
+                }
             },
         )
     }
@@ -344,7 +382,8 @@ private fun RustWriter.documentPresignedMethod(hasConfigArg: Boolean) {
         amount of time the request should be valid for after creation.
 
         Presigned requests can be given to other users or applications to access a resource or perform
-        an operation without having access to the AWS security credentials.
+        an operation without having access to the AWS security credentials. If the operation has a request
+        body, the same body must be sent with the presigned request.
 
         _Important:_ If you're using credentials that can expire, such as those from STS AssumeRole or SSO, then
         the presigned request can only be valid for as long as the credentials used to create it are.
@@ -18,6 +18,9 @@ class AwsRequestIdDecorator : BaseRequestIdDecorator() {
     override val fieldName: String = "request_id"
     override val accessorFunctionName: String = "request_id"
 
+    // Outputs get their request ID from the client codegen, which reads it with the same `RequestId` trait
+    override val outputFieldAddedByClientCodegen: Boolean = true
+
     private fun requestIdModule(codegenContext: ClientCodegenContext): RuntimeType =
         AwsRuntimeType.awsTypes(codegenContext.runtimeConfig).resolve("request_id")
 
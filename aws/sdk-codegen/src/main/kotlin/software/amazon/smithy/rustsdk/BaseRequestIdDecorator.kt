@@ -42,6 +42,12 @@ abstract class BaseRequestIdDecorator : ClientCodegenDecorator {
     abstract fun accessorTrait(codegenContext: ClientCodegenContext): RuntimeType
     abstract fun applyToError(codegenContext: ClientCodegenContext): RuntimeType
 
+    /**
+     * Whether the client codegen already adds this ID to outputs and re-exports its accessor trait, in which case
+     * this decorator only adds it to errors.
+     */
+    open val outputFieldAddedByClientCodegen: Boolean = false
+
     override fun operationCustomizations(
         codegenContext: ClientCodegenContext,
         operation: OperationShape,
@@ -62,14 +68,23 @@ abstract class BaseRequestIdDecorator : ClientCodegenDecorator {
     override fun structureCustomizations(
         codegenContext: ClientCodegenContext,
         baseCustomizations: List<StructureCustomization>,
-    ): List<StructureCustomization> = baseCustomizations + listOf(RequestIdStructureCustomization(codegenContext))
+    ): List<StructureCustomization> = when (outputFieldAddedByClientCodegen) {
+        true -> baseCustomizations
+        false -> baseCustomizations + listOf(RequestIdStructureCustomization(codegenContext))
+    }
 
     override fun builderCustomizations(
         codegenContext: ClientCodegenContext,
         baseCustomizations: List<BuilderCustomization>,
-    ): List<BuilderCustomization> = baseCustomizations + listOf(RequestIdBuilderCustomization())
+    ): List<BuilderCustomization> = when (outputFieldAddedByClientCodegen) {
+        true -> baseCustomizations
+        false -> baseCustomizations + listOf(RequestIdBuilderCustomization())
+    }
 
     override fun extras(codegenContext: ClientCodegenContext, rustCrate: RustCrate) {
+        if (outputFieldAddedByClientCodegen) {
+            return
+        }
         rustCrate.withModule(ClientRustModule.Operation) {
             // Re-export RequestId in generated crate
             rust("pub use #T;", accessorTrait(codegenContext))
@@ -91,7 +106,7 @@ abstract class BaseRequestIdDecorator : ClientCodegenDecorator {
                     )
                 }
 
-                is OperationSection.MutateOutput -> {
+                is OperationSection.MutateOutput -> if (!outputFieldAddedByClientCodegen) {
                     rust(
                         "output._set_$fieldName(#T::$accessorFunctionName(${section.responseHeadersName}).map(str::to_string));",
                         accessorTrait(codegenContext),
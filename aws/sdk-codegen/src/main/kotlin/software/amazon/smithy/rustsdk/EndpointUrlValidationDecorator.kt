@@ -0,0 +1,77 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import software.amazon.smithy.rulesengine.language.syntax.parameters.BuiltIns
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.util.extendIf
+
+/**
+ * Validates and normalizes the `endpoint_url` config setting when the config is built, so that an invalid URL fails
+ * client construction instead of the first request.
+ */
+class EndpointUrlValidationDecorator : ClientCodegenDecorator {
+    override val name: String = "EndpointUrlValidation"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> {
+        return baseCustomizations.extendIf(codegenContext.getBuiltIn(BuiltIns.SDK_ENDPOINT) != null) {
+            EndpointUrlValidationConfig(codegenContext)
+        }
+    }
+}
+
+private class EndpointUrlValidationConfig(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "EndpointUrl" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("endpoint_config::EndpointUrl"),
+        "SharedConfigValidator" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+            .resolve("client::runtime_components::SharedConfigValidator"),
+    )
+
+    override fun section(section: ServiceConfig) = writable {
+        when (section) {
+            ServiceConfig.BuilderBuild -> {
+                rustTemplate(
+                    """
+                    if let #{Some}(endpoint_url) = layer.load::<#{EndpointUrl}>().map(|url| #{EndpointUrl}::normalize(&url.0)) {
+                        match endpoint_url {
+                            #{Ok}(endpoint_url) => {
+                                layer.store_put(endpoint_url);
+                            }
+                            // The URL is validated again when the client is constructed, which fails with this error
+                            #{Err}(_) => {
+                                self.runtime_components.push_config_validator(
+                                    #{SharedConfigValidator}::base_client_config_fn(|_, cfg| {
+                                        if let #{Some}(endpoint_url) = cfg.load::<#{EndpointUrl}>() {
+                                            #{EndpointUrl}::normalize(&endpoint_url.0)?;
+                                        }
+                                        #{Ok}(())
+                                    }),
+                                );
+                            }
+                        }
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            else -> {}
+        }
+    }
+}
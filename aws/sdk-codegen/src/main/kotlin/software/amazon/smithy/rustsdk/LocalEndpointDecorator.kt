@@ -0,0 +1,100 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import software.amazon.smithy.rulesengine.language.syntax.parameters.BuiltIns
+import software.amazon.smithy.rulesengine.traits.ClientContextParamsTrait
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.util.extendIf
+import software.amazon.smithy.rust.codegen.core.util.getTrait
+
+/**
+ * Adds a `local_endpoint` setter to the config of services with a configurable endpoint URL, for sending requests
+ * to local emulators like LocalStack or moto.
+ */
+class LocalEndpointDecorator : ClientCodegenDecorator {
+    override val name: String = "LocalEndpoint"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> {
+        return baseCustomizations.extendIf(codegenContext.getBuiltIn(BuiltIns.SDK_ENDPOINT) != null) {
+            LocalEndpointConfig(codegenContext)
+        }
+    }
+}
+
+private class LocalEndpointConfig(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val supportsPathStyle = codegenContext.serviceShape.getTrait<ClientContextParamsTrait>()
+        ?.parameters.orEmpty().containsKey("ForcePathStyle")
+    private val pathStyleDocs = if (supportsPathStyle) " It also turns on path-style addressing." else ""
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "DisableHostPrefix" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+            .resolve("client::endpoint::DisableHostPrefix"),
+        "LocalEndpoint" to configReexport(
+            AwsRuntimeType.awsTypes(runtimeConfig).resolve("endpoint_config::LocalEndpoint"),
+        ),
+    )
+
+    override fun section(section: ServiceConfig) = writable {
+        when (section) {
+            ServiceConfig.BuilderImpl -> {
+                rustTemplate(
+                    """
+                    /// Sends requests to a local emulator, such as LocalStack, moto, or a custom fake.
+                    ///
+                    /// This sets the endpoint URL and leaves host prefixes off of operations that have them, so that
+                    /// every request goes to the configured host and port.$pathStyleDocs
+                    /// Credentials and a region are still required, but emulators generally accept any values.
+                    ///
+                    /// ## Examples
+                    /// ```no_run
+                    /// use $moduleUseName::config::LocalEndpoint;
+                    ///
+                    /// let local_endpoint = LocalEndpoint::builder()
+                    ///     .url("http://localhost:4566")
+                    ///     // Emulators usually don't serve TLS
+                    ///     .allow_http(true)
+                    ///     .build()
+                    ///     .expect("valid local endpoint");
+                    /// let config = $moduleUseName::Config::builder()
+                    ///     .local_endpoint(local_endpoint)
+                    ///     .build();
+                    /// ```
+                    pub fn local_endpoint(mut self, local_endpoint: #{LocalEndpoint}) -> Self {
+                        self.set_endpoint_url(#{Some}(local_endpoint.url().to_string()));
+                        #{path_style}
+                        self.config.store_put(#{DisableHostPrefix}::new());
+                        self.config.store_put(local_endpoint);
+                        self
+                    }
+                    """,
+                    *codegenScope,
+                    "path_style" to writable {
+                        if (supportsPathStyle) {
+                            rustTemplate("self.set_force_path_style(#{Some}(true));", *preludeScope)
+                        }
+                    },
+                )
+            }
+
+            else -> {}
+        }
+    }
+}
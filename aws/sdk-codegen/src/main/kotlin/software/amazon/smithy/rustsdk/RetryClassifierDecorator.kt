@@ -10,8 +10,12 @@ import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 
 class RetryClassifierDecorator : ClientCodegenDecorator {
     override val name: String = "RetryPolicy"
@@ -23,6 +27,12 @@ class RetryClassifierDecorator : ClientCodegenDecorator {
         baseCustomizations: List<OperationCustomization>,
     ): List<OperationCustomization> = baseCustomizations +
         OperationRetryClassifiersFeature(codegenContext, operation)
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> =
+        baseCustomizations + ThrottlingErrorsConfigCustomization(codegenContext)
 }
 
 class OperationRetryClassifiersFeature(
@@ -31,13 +41,33 @@ class OperationRetryClassifiersFeature(
 ) : OperationCustomization() {
     private val runtimeConfig = codegenContext.runtimeConfig
     private val symbolProvider = codegenContext.symbolProvider
+    private val classifiers = AwsRuntimeType.awsRuntime(runtimeConfig).resolve("retries::classifiers")
 
     override fun section(section: OperationSection) = when (section) {
         is OperationSection.RetryClassifiers -> writable {
             section.registerRetryClassifier(this) {
                 rustTemplate(
                     "#{AwsErrorCodeClassifier}::<#{OperationError}>::new()",
-                    "AwsErrorCodeClassifier" to AwsRuntimeType.awsRuntime(runtimeConfig).resolve("retries::classifiers::AwsErrorCodeClassifier"),
+                    "AwsErrorCodeClassifier" to classifiers.resolve("AwsErrorCodeClassifier"),
+                    "OperationError" to symbolProvider.symbolForOperationError(operation),
+                )
+            }
+        }
+
+        is OperationSection.AdditionalRuntimePlugins -> writable {
+            section.addOperationRuntimePlugin(this) {
+                rustTemplate(
+                    """
+                    #{AdditionalThrottlingErrorsRuntimePlugin}::<#{OperationError}>::new(
+                        config_override
+                            .as_ref()
+                            .and_then(|config_override| config_override.config.load::<#{AdditionalThrottlingErrors}>())
+                            .or_else(|| client_config.config.load::<#{AdditionalThrottlingErrors}>())
+                            .cloned()
+                    )
+                    """,
+                    "AdditionalThrottlingErrors" to classifiers.resolve("AdditionalThrottlingErrors"),
+                    "AdditionalThrottlingErrorsRuntimePlugin" to classifiers.resolve("AdditionalThrottlingErrorsRuntimePlugin"),
                     "OperationError" to symbolProvider.symbolForOperationError(operation),
                 )
             }
@@ -46,3 +76,77 @@ class OperationRetryClassifiersFeature(
         else -> emptySection
     }
 }
+
+private class ThrottlingErrorsConfigCustomization(
+    codegenContext: ClientCodegenContext,
+) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "AdditionalThrottlingErrors" to AwsRuntimeType.awsRuntime(codegenContext.runtimeConfig)
+            .resolve("retries::classifiers::AdditionalThrottlingErrors"),
+    )
+
+    override fun section(section: ServiceConfig): Writable = writable {
+        when (section) {
+            is ServiceConfig.BuilderImpl -> {
+                rustTemplate(
+                    """
+                    /// Adds an error code that should be treated as a throttling error.
+                    ///
+                    /// Errors with this code are retried with throttling backoff, and are counted as throttling
+                    /// errors by adaptive retry. This is in addition to the throttling error codes that are
+                    /// recognized by default.
+                    ///
+                    /// ## Examples
+                    /// ```no_run
+                    /// use $moduleUseName::config::Config;
+                    ///
+                    /// let config = Config::builder()
+                    ///     .throttling_error_code("SubscriberLimitExceeded")
+                    ///     .build();
+                    /// ```
+                    pub fn throttling_error_code(mut self, code: impl #{Into}<::std::borrow::Cow<'static, str>>) -> Self {
+                        self.push_throttling_error_code(code);
+                        self
+                    }
+
+                    /// Adds an error code that should be treated as a throttling error.
+                    ///
+                    /// Errors with this code are retried with throttling backoff, and are counted as throttling
+                    /// errors by adaptive retry. This is in addition to the throttling error codes that are
+                    /// recognized by default.
+                    pub fn push_throttling_error_code(&mut self, code: impl #{Into}<::std::borrow::Cow<'static, str>>) -> &mut Self {
+                        let throttling_errors = self
+                            .config
+                            .load::<#{AdditionalThrottlingErrors}>()
+                            .cloned()
+                            .unwrap_or_default()
+                            .with_code(code);
+                        self.config.store_put(throttling_errors);
+                        self
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            is ServiceConfig.ConfigImpl -> {
+                rustTemplate(
+                    """
+                    /// Returns the error codes that are treated as throttling errors in addition to the defaults.
+                    pub fn throttling_error_codes(&self) -> impl #{Iterator}<Item = &str> {
+                        self.config
+                            .load::<#{AdditionalThrottlingErrors}>()
+                            .into_iter()
+                            .flat_map(|throttling_errors| throttling_errors.codes())
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            else -> {}
+        }
+    }
+}
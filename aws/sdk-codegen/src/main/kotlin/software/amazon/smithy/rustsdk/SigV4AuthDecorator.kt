@@ -12,6 +12,7 @@ import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.ServiceShape
 import software.amazon.smithy.model.shapes.ShapeId
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
 import software.amazon.smithy.rust.codegen.client.smithy.customize.AuthSchemeOption
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.supportedAuthSchemes
@@ -84,7 +85,9 @@ class SigV4AuthDecorator : ClientCodegenDecorator {
         codegenContext: ClientCodegenContext,
         baseCustomizations: List<ConfigCustomization>,
     ): List<ConfigCustomization> =
-        baseCustomizations + SigV4SigningConfig(codegenContext.runtimeConfig, codegenContext.serviceShape.getTrait())
+        baseCustomizations + SigV4SigningConfig(codegenContext, codegenContext.serviceShape.getTrait()) +
+            codegenContext.serviceShape.hasEventStreamOperations(codegenContext.model)
+                .thenSingletonListOf { EventStreamSigningConfig(codegenContext) }
 
     override fun extras(codegenContext: ClientCodegenContext, rustCrate: RustCrate) {
         if (codegenContext.serviceShape.supportedAuthSchemes().contains("sigv4a")) {
@@ -95,20 +98,26 @@ class SigV4AuthDecorator : ClientCodegenDecorator {
 }
 
 private class SigV4SigningConfig(
-    runtimeConfig: RuntimeConfig,
+    codegenContext: ClientCodegenContext,
     private val sigV4Trait: SigV4Trait?,
 ) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val moduleUseName = codegenContext.moduleUseName()
     private val codegenScope = arrayOf(
         "Region" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("region::Region"),
         "SigningName" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("SigningName"),
         "SigningRegion" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("region::SigningRegion"),
+        "SigningOptionsOverride" to configReexport(
+            AwsRuntimeType.awsRuntime(runtimeConfig).resolve("auth::SigningOptionsOverride"),
+        ),
+        *RuntimeType.preludeScope,
     )
 
     override fun section(section: ServiceConfig): Writable = writable {
         if (sigV4Trait != null) {
             when (section) {
                 ServiceConfig.ConfigImpl -> {
-                    rust(
+                    rustTemplate(
                         """
                         /// The signature version 4 service signing name to use in the credential scope when signing requests.
                         ///
@@ -117,7 +126,48 @@ private class SigV4SigningConfig(
                         pub fn signing_name(&self) -> &'static str {
                             ${sigV4Trait.name.dq()}
                         }
+
+                        /// Returns the overrides for the SigV4 signing options, if any were set.
+                        pub fn signing_options_override(&self) -> #{Option}<&#{SigningOptionsOverride}> {
+                            self.config.load::<#{SigningOptionsOverride}>()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Overrides SigV4 signing options, like signing with an unsigned payload.
+                        ///
+                        /// This is usually set as a config override for individual operations. The override can
+                        /// also set the signing name and region, which then take precedence over the ones from the
+                        /// endpoint, for example when requests are sent to a custom domain that fronts the service.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::{Config, SigningOptionsOverride};
+                        ///
+                        /// // Pass this to `customize().config_override(...)` on an operation
+                        /// let config_override = Config::builder()
+                        ///     .signing_options_override(SigningOptionsOverride::new().unsigned_payload(true));
+                        /// ```
+                        pub fn signing_options_override(mut self, signing_options_override: #{SigningOptionsOverride}) -> Self {
+                            self.set_signing_options_override(#{Some}(signing_options_override));
+                            self
+                        }
+
+                        /// Overrides SigV4 signing options, like signing with an unsigned payload.
+                        pub fn set_signing_options_override(
+                            &mut self,
+                            signing_options_override: #{Option}<#{SigningOptionsOverride}>,
+                        ) -> &mut Self {
+                            self.config.store_or_unset(signing_options_override);
+                            self
+                        }
                         """,
+                        *codegenScope,
                     )
                 }
 
@@ -149,6 +199,47 @@ private class SigV4SigningConfig(
     }
 }
 
+private class EventStreamSigningConfig(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val eventStream = AwsRuntimeType.awsRuntime(runtimeConfig).resolve("auth::sigv4::event_stream")
+    private val codegenScope = arrayOf(
+        *RuntimeType.preludeScope,
+        "SharedEventStreamMessageSigner" to configReexport(eventStream.resolve("SharedEventStreamMessageSigner")),
+        "SignEventStreamMessage" to configReexport(eventStream.resolve("SignEventStreamMessage")),
+    )
+
+    override fun section(section: ServiceConfig): Writable = writable {
+        when (section) {
+            ServiceConfig.BuilderImpl -> {
+                rustTemplate(
+                    """
+                    /// Sets the signer for the messages of event streams.
+                    ///
+                    /// Messages are signed with SigV4 by default. A different signer can sign messages with
+                    /// a different scheme, or make message signatures predictable in tests.
+                    pub fn event_stream_message_signer(mut self, signer: impl #{SignEventStreamMessage} + 'static) -> Self {
+                        self.set_event_stream_message_signer(#{Some}(#{SharedEventStreamMessageSigner}::new(signer)));
+                        self
+                    }
+
+                    /// Sets the signer for the messages of event streams.
+                    pub fn set_event_stream_message_signer(
+                        &mut self,
+                        signer: #{Option}<#{SharedEventStreamMessageSigner}>,
+                    ) -> &mut Self {
+                        self.config.store_or_unset(signer);
+                        self
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            else -> {}
+        }
+    }
+}
+
 private class AuthServiceRuntimePluginCustomization(private val codegenContext: ClientCodegenContext) :
     ServiceRuntimePluginCustomization() {
     private val runtimeConfig = codegenContext.runtimeConfig
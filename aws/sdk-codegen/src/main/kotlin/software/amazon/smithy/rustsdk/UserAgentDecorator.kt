@@ -49,6 +49,9 @@ class UserAgentDecorator : ClientCodegenDecorator {
         return listOf(
             adhocCustomization<SdkConfigSection.CopySdkConfigToClientConfig> { section ->
                 rust("${section.serviceConfigBuilder}.set_app_name(${section.sdkConfig}.app_name().cloned());")
+                rust(
+                    "${section.serviceConfigBuilder}.set_user_agent_metadata(${section.sdkConfig}.user_agent_metadata().cloned());",
+                )
             },
         )
     }
@@ -75,11 +78,16 @@ class UserAgentDecorator : ClientCodegenDecorator {
         }
 
         rustCrate.withModule(ClientRustModule.config) {
-            // Re-export the app name so that it can be specified in config programmatically without an explicit dependency
+            // Re-export the app name and user agent metadata so that they can be specified in config programmatically
+            // without an explicit dependency
             rustTemplate(
                 "pub use #{AppName};",
                 "AppName" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("app_name::AppName"),
             )
+            rustTemplate(
+                "pub use #{user_agent}::{ProductToken, UserAgentMetadata};",
+                "user_agent" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("user_agent"),
+            )
         }
     }
 
@@ -106,6 +114,7 @@ class UserAgentDecorator : ClientCodegenDecorator {
             *preludeScope,
             "AppName" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("app_name::AppName"),
             "AwsUserAgent" to AwsRuntimeType.awsHttp(runtimeConfig).resolve("user_agent::AwsUserAgent"),
+            "UserAgentMetadata" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("user_agent::UserAgentMetadata"),
         )
 
         override fun section(section: ServiceConfig): Writable =
@@ -138,6 +147,32 @@ class UserAgentDecorator : ClientCodegenDecorator {
                         """,
                         *codegenScope,
                     )
+
+                    rustTemplate(
+                        """
+                        /// Sets product tokens and feature flags to append to the user agent of requests.
+                        ///
+                        /// This lets frameworks built on the SDK tag the traffic they send, so that it can be
+                        /// attributed on the server side.
+                        pub fn user_agent_metadata(mut self, user_agent_metadata: #{UserAgentMetadata}) -> Self {
+                            self.set_user_agent_metadata(Some(user_agent_metadata));
+                            self
+                        }
+
+                        /// Sets product tokens and feature flags to append to the user agent of requests.
+                        ///
+                        /// This lets frameworks built on the SDK tag the traffic they send, so that it can be
+                        /// attributed on the server side.
+                        pub fn set_user_agent_metadata(
+                            &mut self,
+                            user_agent_metadata: #{Option}<#{UserAgentMetadata}>,
+                        ) -> &mut Self {
+                            self.config.store_or_unset(user_agent_metadata);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
                 }
 
                 is ServiceConfig.BuilderBuild -> writable {
@@ -154,6 +189,11 @@ class UserAgentDecorator : ClientCodegenDecorator {
                         pub fn app_name(&self) -> #{Option}<&#{AppName}> {
                            self.config.load::<#{AppName}>()
                         }
+
+                        /// Returns the product tokens and feature flags appended to the user agent, if they were provided.
+                        pub fn user_agent_metadata(&self) -> #{Option}<&#{UserAgentMetadata}> {
+                            self.config.load::<#{UserAgentMetadata}>()
+                        }
                         """,
                         *codegenScope,
                     )
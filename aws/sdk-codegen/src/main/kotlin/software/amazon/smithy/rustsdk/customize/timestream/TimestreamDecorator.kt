@@ -95,6 +95,8 @@ class TimestreamDecorator : ClientCodegenDecorator {
                         conf.set_endpoint_resolver(Some(resolver.into_shared()));
 
                         let client_with_discovery = crate::Client::from_conf(conf.build());
+                        // Stop reloading the endpoint once the client with discovery is shut down
+                        let reloader = reloader.stop_on_shutdown(&client_with_discovery.handle.shutdown);
                         Ok((client_with_discovery, reloader))
                     }
                 }
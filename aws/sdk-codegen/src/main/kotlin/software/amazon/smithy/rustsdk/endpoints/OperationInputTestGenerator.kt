@@ -15,6 +15,7 @@ import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointTypesGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.generators.ClientInstantiator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientGenerator
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.AttributeKind
 import software.amazon.smithy.rust.codegen.core.rustlang.escape
@@ -170,9 +171,9 @@ class OperationInputTestGenerator(_ctx: ClientCodegenContext, private val test:
     }
 
     private fun operationInvocation(testOperationInput: EndpointTestOperationInput) = writable {
-        rust("client.${testOperationInput.operationName.toSnakeCase()}()")
-        val operationInput =
-            model.expectShape(ctx.operationId(testOperationInput), OperationShape::class.java).inputShape(model)
+        val operationShape = model.expectShape(ctx.operationId(testOperationInput), OperationShape::class.java)
+        rust("client.${FluentClientGenerator.clientOperationFnName(operationShape, ctx.symbolProvider)}()")
+        val operationInput = operationShape.inputShape(model)
         testOperationInput.operationParams.members.forEach { (key, value) ->
             val member = operationInput.expectMember(key.value)
             rustTemplate(
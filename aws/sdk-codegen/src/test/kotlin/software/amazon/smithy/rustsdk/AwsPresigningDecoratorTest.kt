@@ -27,10 +27,15 @@ class AwsPresigningDecoratorTest {
         testTransform("com.amazonaws.s3", "GetObject", presignable = true)
     }
 
-    private fun testTransform(namespace: String, name: String, presignable: Boolean) {
+    @Test
+    fun `it should add the synthetic presignable trait to operations of sigv4 services`() {
+        testTransform("some.service", "Presignable", presignable = true, sigv4 = true)
+    }
+
+    private fun testTransform(namespace: String, name: String, presignable: Boolean, sigv4: Boolean = false) {
         val settings = testClientRustSettings()
         val decorator = AwsPresigningDecorator()
-        val model = testOperation(namespace, name)
+        val model = testOperation(namespace, name, sigv4)
         val transformed = decorator.transformModel(serviceShape(model), model, settings)
         hasPresignableTrait(transformed, namespace, name) shouldBe presignable
     }
@@ -44,12 +49,14 @@ class AwsPresigningDecoratorTest {
     private fun serviceShape(model: Model): ServiceShape =
         model.shapes().filter { shape -> shape is ServiceShape }.findFirst().orNull()!! as ServiceShape
 
-    private fun testOperation(namespace: String, name: String): Model =
+    private fun testOperation(namespace: String, name: String, sigv4: Boolean): Model =
         """
         namespace $namespace
+        use aws.auth#sigv4
         use aws.protocols#restJson1
 
         @restJson1
+        ${if (sigv4) "@sigv4(name: \"test\")" else ""}
         service TestService {
             version: "2019-12-16",
             operations: ["$name"],
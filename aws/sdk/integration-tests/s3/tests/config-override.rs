@@ -67,3 +67,31 @@ async fn operation_overrides_dual_stack() {
         "https://test-bucket.s3.dualstack.us-west-2.amazonaws.com/?list-type=2"
     );
 }
+
+#[tokio::test]
+async fn operation_overrides_can_disable_fips_and_dual_stack() {
+    let (http_client, captured_request) = capture_request(None);
+    let sdk_config = SdkConfig::builder()
+        .credentials_provider(SharedCredentialsProvider::new(Credentials::for_tests()))
+        .region(Region::new("us-west-2"))
+        .use_fips(true)
+        .use_dual_stack(true)
+        .http_client(http_client)
+        .build();
+    let client = Client::new(&sdk_config);
+    let _ = client
+        .list_objects_v2()
+        .bucket("test-bucket")
+        .customize()
+        .config_override(
+            aws_sdk_s3::config::Config::builder()
+                .use_fips(false)
+                .use_dual_stack(false),
+        )
+        .send()
+        .await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "https://test-bucket.s3.us-west-2.amazonaws.com/?list-type=2"
+    );
+}
@@ -0,0 +1,40 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sdk_s3::config::{Credentials, LocalEndpoint, Region};
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_runtime::client::http::test_util::capture_request;
+
+#[tokio::test]
+async fn local_endpoint_uses_path_style_and_keeps_the_port() {
+    let (http_client, captured_request) = capture_request(None);
+    let local_endpoint = LocalEndpoint::builder()
+        .url("http://localhost:4566")
+        .allow_http(true)
+        .build()
+        .expect("valid local endpoint");
+    let config = Config::builder()
+        .credentials_provider(Credentials::for_tests())
+        .region(Region::new("us-east-1"))
+        .http_client(http_client)
+        .local_endpoint(local_endpoint)
+        .build();
+    let client = Client::from_conf(config);
+
+    let _ = client.list_objects_v2().bucket("test-bucket").send().await;
+    assert_eq!(
+        captured_request.expect_request().uri().to_string(),
+        "http://localhost:4566/test-bucket/?list-type=2"
+    );
+}
+
+#[test]
+fn local_endpoint_requires_acknowledging_http() {
+    let err = LocalEndpoint::builder()
+        .url("http://localhost:4566")
+        .build()
+        .expect_err("plain HTTP wasn't allowed");
+    assert!(err.to_string().contains("allow_http(true)"));
+}
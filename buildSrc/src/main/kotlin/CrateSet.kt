@@ -38,6 +38,8 @@ object CrateSet {
         Crate("aws-smithy-http-auth", UNSTABLE_VERSION_PROP_NAME),
         Crate("aws-smithy-http-tower", UNSTABLE_VERSION_PROP_NAME),
         Crate("aws-smithy-json", UNSTABLE_VERSION_PROP_NAME),
+        Crate("aws-smithy-observability", UNSTABLE_VERSION_PROP_NAME),
+        Crate("aws-smithy-observability-otel", UNSTABLE_VERSION_PROP_NAME),
         Crate("aws-smithy-protocol-test", UNSTABLE_VERSION_PROP_NAME),
         Crate("aws-smithy-query", UNSTABLE_VERSION_PROP_NAME),
         Crate("aws-smithy-runtime", UNSTABLE_VERSION_PROP_NAME),
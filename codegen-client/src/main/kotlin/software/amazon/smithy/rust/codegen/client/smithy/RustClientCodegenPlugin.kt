@@ -98,6 +98,8 @@ class RustClientCodegenPlugin : ClientDecoratableBuildPlugin() {
                 .let { BaseSymbolMetadataProvider(it, additionalAttributes = listOf(NonExhaustive)) }
                 // Streaming shapes need different derives (e.g. they cannot derive `PartialEq`)
                 .let { StreamingShapeMetadataProvider(it) }
+                // Structures need custom `Debug` impls to redact members that are marked as sensitive at runtime
+                .let { RuntimeSensitivityMetadataProvider(it) }
                 // Rename shapes that clash with Rust reserved words & and other SDK specific features e.g. `send()` cannot
                 // be the name of an operation input
                 .let { RustReservedWordSymbolProvider(it, ClientReservedWords) }
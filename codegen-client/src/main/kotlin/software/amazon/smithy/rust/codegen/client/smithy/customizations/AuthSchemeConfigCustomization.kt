@@ -0,0 +1,161 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+
+/**
+ * Adds config methods to register custom auth schemes and identity resolvers at runtime, to set the order
+ * in which auth schemes are preferred, and to send requests anonymously.
+ */
+class AuthSchemeConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val runtimeApi = RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "AnonymousAuth" to runtimeApi.resolve("client::auth::AnonymousAuth"),
+        "AuthScheme" to configReexport(runtimeApi.resolve("client::auth::AuthScheme")),
+        "AuthSchemeId" to configReexport(runtimeApi.resolve("client::auth::AuthSchemeId")),
+        "AuthSchemePreference" to configReexport(runtimeApi.resolve("client::auth::AuthSchemePreference")),
+        "ResolveIdentity" to configReexport(runtimeApi.resolve("client::identity::ResolveIdentity")),
+        "SharedAuthScheme" to configReexport(runtimeApi.resolve("client::auth::SharedAuthScheme")),
+        "SharedIdentityResolver" to configReexport(runtimeApi.resolve("client::identity::SharedIdentityResolver")),
+    )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                is ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns the order in which auth schemes are preferred, if one was set.
+                        pub fn auth_scheme_preference(&self) -> #{Option}<&#{AuthSchemePreference}> {
+                            self.config.load::<#{AuthSchemePreference}>()
+                        }
+
+                        /// Returns true if requests are sent anonymously, without authentication.
+                        pub fn anonymous_auth(&self) -> bool {
+                            self.config.load::<#{AnonymousAuth}>().map(#{AnonymousAuth}::is_enabled).unwrap_or_default()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Registers a custom [auth scheme](#{AuthScheme}).
+                        ///
+                        /// The auth scheme is used for operations whose auth scheme options include its scheme ID,
+                        /// such as operations that the model marks with a custom auth trait, where the scheme ID is
+                        /// the shape ID of the trait. The service's built-in auth schemes take precedence over
+                        /// custom auth schemes that have the same scheme ID.
+                        ///
+                        /// The auth scheme's identity resolver is usually registered with [`identity_resolver`](Self::identity_resolver).
+                        pub fn auth_scheme(mut self, auth_scheme: impl #{AuthScheme} + 'static) -> Self {
+                            self.push_auth_scheme(#{SharedAuthScheme}::new(auth_scheme));
+                            self
+                        }
+
+                        /// Registers a custom [auth scheme](#{AuthScheme}).
+                        ///
+                        /// The auth scheme is used for operations whose auth scheme options include its scheme ID,
+                        /// such as operations that the model marks with a custom auth trait, where the scheme ID is
+                        /// the shape ID of the trait. The service's built-in auth schemes take precedence over
+                        /// custom auth schemes that have the same scheme ID.
+                        pub fn push_auth_scheme(&mut self, auth_scheme: #{SharedAuthScheme}) -> &mut Self {
+                            self.runtime_components.push_auth_scheme(auth_scheme);
+                            self
+                        }
+
+                        /// Registers an [identity resolver](#{ResolveIdentity}) for the auth scheme with the given ID.
+                        pub fn identity_resolver(
+                            mut self,
+                            scheme_id: impl #{Into}<#{AuthSchemeId}>,
+                            identity_resolver: impl #{ResolveIdentity} + 'static,
+                        ) -> Self {
+                            self.push_identity_resolver(scheme_id.into(), #{SharedIdentityResolver}::new(identity_resolver));
+                            self
+                        }
+
+                        /// Registers an [identity resolver](#{ResolveIdentity}) for the auth scheme with the given ID.
+                        pub fn push_identity_resolver(
+                            &mut self,
+                            scheme_id: #{AuthSchemeId},
+                            identity_resolver: #{SharedIdentityResolver},
+                        ) -> &mut Self {
+                            self.runtime_components.push_identity_resolver(scheme_id, identity_resolver);
+                            self
+                        }
+
+                        /// Sets the order in which auth schemes are preferred when an operation supports several.
+                        ///
+                        /// Preferred auth schemes are tried first, in the given order, and the operation's other
+                        /// auth schemes are tried after them. Auth schemes that an operation doesn't support are
+                        /// never used for it.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::{AuthSchemeId, Config};
+                        ///
+                        /// let config = Config::builder()
+                        ///     .auth_scheme_preference([AuthSchemeId::new("com.example##hmacAuth")])
+                        ///     .build();
+                        /// ```
+                        pub fn auth_scheme_preference(
+                            mut self,
+                            preference: impl #{IntoIterator}<Item = impl #{Into}<#{AuthSchemeId}>>,
+                        ) -> Self {
+                            self.set_auth_scheme_preference(#{Some}(#{AuthSchemePreference}::new(preference)));
+                            self
+                        }
+
+                        /// Sets the order in which auth schemes are preferred when an operation supports several.
+                        pub fn set_auth_scheme_preference(&mut self, preference: #{Option}<#{AuthSchemePreference}>) -> &mut Self {
+                            self.config.store_or_unset(preference);
+                            self
+                        }
+
+                        /// Sends requests anonymously, without resolving an identity or signing them.
+                        ///
+                        /// This is mostly useful as a config override for individual operations, such as
+                        /// operations on public endpoints, or calls that bootstrap authentication, when the
+                        /// client has credentials configured.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::Config;
+                        ///
+                        /// // Pass this to `customize().config_override(...)` on an operation
+                        /// let config_override = Config::builder().anonymous_auth(true);
+                        /// ```
+                        pub fn anonymous_auth(mut self, anonymous_auth: bool) -> Self {
+                            self.set_anonymous_auth(#{Some}(anonymous_auth));
+                            self
+                        }
+
+                        /// Sends requests anonymously, without resolving an identity or signing them.
+                        pub fn set_anonymous_auth(&mut self, anonymous_auth: #{Option}<bool>) -> &mut Self {
+                            self.config.store_or_unset(anonymous_auth.map(#{AnonymousAuth}::new));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
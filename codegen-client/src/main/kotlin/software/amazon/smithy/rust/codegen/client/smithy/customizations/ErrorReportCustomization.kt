@@ -0,0 +1,130 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+
+/**
+ * Adds an `on_error` setter to the service config, for a hook that's called with a structured report of every
+ * failed operation.
+ */
+class ErrorReportConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val errorReport = RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+        .resolve("client::error_report")
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "ErrorReport" to errorReport.resolve("ErrorReport"),
+        "OnError" to errorReport.resolve("OnError"),
+    )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns the hook that's called when an operation fails, if one was set.
+                        pub fn on_error(&self) -> #{Option}<&#{OnError}> {
+                            self.config.load::<#{OnError}>()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets a hook that's called with a report of every operation that fails.
+                        ///
+                        /// The report has the kind of failure, the error code and message that the service responded
+                        /// with, the request ID, the number of attempts, and the endpoint, so that errors can be
+                        /// logged or recorded consistently without parsing their `Display` output.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::FailureKind;
+                        ///
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     .on_error(|report| {
+                        ///         if report.kind() == FailureKind::ServiceError {
+                        ///             eprintln!(
+                        ///                 "{} failed with {:?} (request ID {:?}, {} attempts)",
+                        ///                 report.operation(),
+                        ///                 report.code(),
+                        ///                 report.request_id(),
+                        ///                 report.attempts(),
+                        ///             );
+                        ///         }
+                        ///     })
+                        ///     .build();
+                        /// ```
+                        pub fn on_error(mut self, on_error: impl Fn(&#{ErrorReport}<'_>) + #{Send} + #{Sync} + 'static) -> Self {
+                            self.set_on_error(#{Some}(#{OnError}::new(on_error)));
+                            self
+                        }
+
+                        /// Sets a hook that's called with a report of every operation that fails.
+                        pub fn set_on_error(&mut self, on_error: #{Option}<#{OnError}>) -> &mut Self {
+                            self.config.store_or_unset(on_error);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+/** Lets the orchestrator read the error code and message of the operation's errors for error reports. */
+class ErrorReportOperationCustomization(
+    private val codegenContext: ClientCodegenContext,
+    private val operation: OperationShape,
+) : OperationCustomization() {
+    override fun section(section: OperationSection): Writable = writable {
+        when (section) {
+            is OperationSection.AdditionalRuntimePluginConfig -> {
+                rustTemplate(
+                    "${section.newLayerName}.store_put(#{OperationErrorMetadata}::of::<#{OperationError}>());",
+                    "OperationErrorMetadata" to RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+                        .resolve("client::error_report::OperationErrorMetadata"),
+                    "OperationError" to codegenContext.symbolProvider.symbolForOperationError(operation),
+                )
+            }
+
+            else -> {}
+        }
+    }
+}
+
+class ErrorReportReExportCustomization(codegenContext: ClientCodegenContext) {
+    private val runtimeConfig = codegenContext.runtimeConfig
+
+    fun extras(rustCrate: RustCrate) {
+        rustCrate.withModule(ClientRustModule.config) {
+            rustTemplate(
+                "pub use #{error_report}::{ErrorReport, FailureKind, OnError};",
+                "error_report" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::error_report"),
+            )
+        }
+    }
+}
@@ -38,6 +38,9 @@ private fun codegenScope(runtimeConfig: RuntimeConfig): Array<Pair<String, Any>>
     val authHttpApi = smithyRuntimeApi.resolve("client::auth::http")
     return arrayOf(
         "Token" to configReexport(smithyRuntimeApi.resolve("client::identity::http::Token")),
+        "ProvideToken" to configReexport(smithyRuntimeApi.resolve("client::identity::http::ProvideToken")),
+        "SharedTokenProvider" to configReexport(smithyRuntimeApi.resolve("client::identity::http::SharedTokenProvider")),
+        "TokenFuture" to configReexport(smithyRuntimeApi.resolve("client::identity::http::TokenFuture")),
         "Login" to configReexport(smithyRuntimeApi.resolve("client::identity::http::Login")),
         "ResolveIdentity" to configReexport(smithyRuntimeApi.resolve("client::identity::ResolveIdentity")),
 
@@ -205,6 +208,7 @@ private class HttpAuthConfigCustomization(
     codegenContext: ClientCodegenContext,
     private val authSchemes: HttpAuthSchemes,
 ) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
     private val codegenScope = codegenScope(codegenContext.runtimeConfig)
 
     override fun section(section: ServiceConfig): Writable = writable {
@@ -246,6 +250,35 @@ private class HttpAuthConfigCustomization(
                             );
                             self
                         }
+
+                        /// Sets a [token provider](#{ProvideToken}) that will be used for HTTP bearer auth.
+                        ///
+                        /// The provider returns a [`TokenFuture`](#{TokenFuture}) that resolves to a token. Tokens are
+                        /// cached until shortly before they expire, and the provider is called again to refresh them.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::{Config, ProvideToken, Token, TokenFuture};
+                        ///
+                        /// ##[derive(Debug)]
+                        /// struct OAuthTokenProvider;
+                        ///
+                        /// impl ProvideToken for OAuthTokenProvider {
+                        ///     fn provide_token<'a>(&'a self) -> TokenFuture<'a> {
+                        ///         TokenFuture::new(async move {
+                        ///             // Fetch or refresh an access token here
+                        ///             Ok(Token::new("access-token", None))
+                        ///         })
+                        ///     }
+                        /// }
+                        ///
+                        /// let config = Config::builder()
+                        ///     .bearer_token_provider(OAuthTokenProvider)
+                        ///     .build();
+                        /// ```
+                        pub fn bearer_token_provider(self, bearer_token_provider: impl #{ProvideToken} + 'static) -> Self {
+                            self.bearer_token_resolver(#{SharedTokenProvider}::new(bearer_token_provider))
+                        }
                         """,
                         *codegenScope,
                     )
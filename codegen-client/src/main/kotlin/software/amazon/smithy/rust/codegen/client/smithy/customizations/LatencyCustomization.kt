@@ -0,0 +1,101 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderSection
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureSection
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+
+private fun latencyTypes(runtimeConfig: RuntimeConfig) = arrayOf(
+    *preludeScope,
+    "LatencyBreakdown" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::latency::LatencyBreakdown"),
+    "ProvideLatency" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::latency::ProvideLatency"),
+)
+
+/**
+ * Adds the operation's latency breakdown to operation outputs.
+ *
+ * The orchestrator adds the breakdown to the final response, and the operation's `orchestrate` function copies it
+ * onto the output with `_set_latency`. Errors expose it through the raw response of the `SdkError`.
+ */
+class LatencyStructureCustomization(codegenContext: ClientCodegenContext) : StructureCustomization() {
+    private val codegenScope = latencyTypes(codegenContext.runtimeConfig)
+
+    override fun section(section: StructureSection): Writable = writable {
+        if (section.shape.hasTrait<SyntheticOutputTrait>()) {
+            when (section) {
+                is StructureSection.AdditionalFields -> {
+                    rustTemplate("_latency: #{Option}<#{LatencyBreakdown}>,", *codegenScope)
+                }
+
+                is StructureSection.AdditionalTraitImpls -> {
+                    rustTemplate(
+                        """
+                        impl #{ProvideLatency} for ${section.structName} {
+                            fn latency(&self) -> #{Option}<&#{LatencyBreakdown}> {
+                                self._latency.as_ref()
+                            }
+                        }
+
+                        impl ${section.structName} {
+                            pub(crate) fn _set_latency(&mut self, latency: #{Option}<#{LatencyBreakdown}>) -> &mut Self {
+                                self._latency = latency;
+                                self
+                            }
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is StructureSection.AdditionalDebugFields -> {
+                    rust("""${section.formatterName}.field("_latency", &self._latency);""")
+                }
+            }
+        }
+    }
+}
+
+/** Leaves the latency breakdown unset when building outputs, since it's only known once the operation completes. */
+class LatencyBuilderCustomization : BuilderCustomization() {
+    override fun section(section: BuilderSection): Writable = writable {
+        if (section.shape.hasTrait<SyntheticOutputTrait>()) {
+            when (section) {
+                is BuilderSection.AdditionalFieldsInBuild -> {
+                    rust("_latency: None,")
+                }
+
+                else -> {}
+            }
+        }
+    }
+}
+
+class LatencyReExportCustomization(codegenContext: ClientCodegenContext) {
+    private val runtimeConfig = codegenContext.runtimeConfig
+
+    fun extras(rustCrate: RustCrate) {
+        rustCrate.withModule(ClientRustModule.Operation) {
+            rustTemplate(
+                "pub use #{latency}::{AttemptLatency, LatencyBreakdown, ProvideLatency};",
+                "latency" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::latency"),
+            )
+        }
+    }
+}
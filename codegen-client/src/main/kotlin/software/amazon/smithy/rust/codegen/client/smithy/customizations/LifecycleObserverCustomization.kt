@@ -0,0 +1,110 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+
+/**
+ * Adds a `lifecycle_observer` setter to the service config, for an observer that's told when operations and their
+ * attempts start and end.
+ */
+class LifecycleObserverConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val lifecycle = RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+        .resolve("client::lifecycle")
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "ObserveLifecycle" to lifecycle.resolve("ObserveLifecycle"),
+        "SharedLifecycleObserver" to lifecycle.resolve("SharedLifecycleObserver"),
+    )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns the observer of operations and their attempts, if one was set.
+                        pub fn lifecycle_observer(&self) -> #{Option}<#{SharedLifecycleObserver}> {
+                            self.config.load::<#{SharedLifecycleObserver}>().cloned()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets an observer that's told when operations and their attempts start and end.
+                        ///
+                        /// The observer is told how long each operation and attempt took, and how it failed,
+                        /// if it did. It's a simpler alternative to an interceptor when requests and responses
+                        /// don't need to be modified.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::{AttemptEnd, ObserveLifecycle};
+                        ///
+                        /// ##[derive(Debug)]
+                        /// struct LogFailedAttempts;
+                        ///
+                        /// impl ObserveLifecycle for LogFailedAttempts {
+                        ///     fn on_attempt_end(&self, attempt: &AttemptEnd<'_>) {
+                        ///         if let Some(failure) = attempt.failure() {
+                        ///             eprintln!("attempt #{} of {} failed: {:?}", attempt.attempt(), attempt.operation(), failure);
+                        ///         }
+                        ///     }
+                        /// }
+                        ///
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     .lifecycle_observer(LogFailedAttempts)
+                        ///     .build();
+                        /// ```
+                        pub fn lifecycle_observer(mut self, lifecycle_observer: impl #{ObserveLifecycle} + 'static) -> Self {
+                            self.set_lifecycle_observer(#{Some}(#{SharedLifecycleObserver}::new(lifecycle_observer)));
+                            self
+                        }
+
+                        /// Sets an observer that's told when operations and their attempts start and end.
+                        pub fn set_lifecycle_observer(&mut self, lifecycle_observer: #{Option}<#{SharedLifecycleObserver}>) -> &mut Self {
+                            self.config.store_or_unset(lifecycle_observer);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+class LifecycleObserverReExportCustomization(codegenContext: ClientCodegenContext) {
+    private val runtimeConfig = codegenContext.runtimeConfig
+
+    fun extras(rustCrate: RustCrate) {
+        rustCrate.withModule(ClientRustModule.config) {
+            rustTemplate(
+                """
+                pub use #{lifecycle}::{
+                    AttemptEnd, AttemptStart, ObserveLifecycle, OperationEnd, OperationStart, SharedLifecycleObserver,
+                };
+                """,
+                "lifecycle" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::lifecycle"),
+            )
+        }
+    }
+}
@@ -0,0 +1,184 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderSection
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureSection
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+
+/**
+ * Adds `request_id_extractor` and `invocation_id_header` setters to the service config, for services that return
+ * their request ID in a non-default header, or that accept a correlation ID from clients.
+ */
+class RequestIdConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "InvocationIdHeaderInterceptor" to RuntimeType.smithyRuntime(runtimeConfig)
+            .resolve("client::invocation_id::InvocationIdHeaderInterceptor"),
+        "RequestIdExtractor" to configReexport(
+            RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::request_id::RequestIdExtractor"),
+        ),
+        "SharedInterceptor" to RuntimeType.sharedInterceptor(runtimeConfig),
+    )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns the extractor that request IDs are read from responses with, if one was set.
+                        pub fn request_id_extractor(&self) -> #{Option}<&#{RequestIdExtractor}> {
+                            self.config.load::<#{RequestIdExtractor}>()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets the response headers that request IDs are read from.
+                        ///
+                        /// By default, the request ID is read from the `x-amzn-requestid`, `x-amz-request-id`, or
+                        /// `x-request-id` header. Services that return their request ID in a different header can set
+                        /// this so that `RequestId` returns it for outputs and errors, and so that it's recorded on the
+                        /// `attempt` tracing span.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::RequestIdExtractor;
+                        ///
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     .request_id_extractor(RequestIdExtractor::new(["x-correlation-id"]))
+                        ///     .build();
+                        /// ```
+                        pub fn request_id_extractor(mut self, request_id_extractor: #{RequestIdExtractor}) -> Self {
+                            self.set_request_id_extractor(#{Some}(request_id_extractor));
+                            self
+                        }
+
+                        /// Sets the response headers that request IDs are read from.
+                        pub fn set_request_id_extractor(&mut self, request_id_extractor: #{Option}<#{RequestIdExtractor}>) -> &mut Self {
+                            self.config.store_or_unset(request_id_extractor);
+                            self
+                        }
+
+                        /// Sends a client-generated invocation ID in the request header with the given name.
+                        ///
+                        /// A random UUID is generated for each operation and sent with every attempt of it, so that
+                        /// the service can correlate retries with the original request. No invocation ID header is
+                        /// sent by default.
+                        ///
+                        /// ## Panics
+                        /// This panics if `header_name` is not a valid HTTP header name.
+                        pub fn invocation_id_header(mut self, header_name: impl #{AsRef}<str>) -> Self {
+                            self.push_interceptor(#{SharedInterceptor}::new(#{InvocationIdHeaderInterceptor}::new(header_name)));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
+
+/**
+ * Adds the request ID to operation outputs.
+ *
+ * The operation's `orchestrate` function reads the request ID from the final response with `RequestId`, which uses
+ * the configured `RequestIdExtractor`, and sets it with `_set_request_id`.
+ */
+class RequestIdStructureCustomization(codegenContext: ClientCodegenContext) : StructureCustomization() {
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "RequestId" to RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+            .resolve("client::request_id::RequestId"),
+    )
+
+    override fun section(section: StructureSection): Writable = writable {
+        if (section.shape.hasTrait<SyntheticOutputTrait>()) {
+            when (section) {
+                is StructureSection.AdditionalFields -> {
+                    rustTemplate("_request_id: #{Option}<#{String}>,", *codegenScope)
+                }
+
+                is StructureSection.AdditionalTraitImpls -> {
+                    rustTemplate(
+                        """
+                        impl #{RequestId} for ${section.structName} {
+                            fn request_id(&self) -> #{Option}<&str> {
+                                self._request_id.as_deref()
+                            }
+                        }
+
+                        impl ${section.structName} {
+                            pub(crate) fn _set_request_id(&mut self, request_id: #{Option}<#{String}>) -> &mut Self {
+                                self._request_id = request_id;
+                                self
+                            }
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is StructureSection.AdditionalDebugFields -> {
+                    rust("""${section.formatterName}.field("_request_id", &self._request_id);""")
+                }
+            }
+        }
+    }
+}
+
+/** Leaves the request ID unset when building outputs, since it's read from the response once the operation completes. */
+class RequestIdBuilderCustomization : BuilderCustomization() {
+    override fun section(section: BuilderSection): Writable = writable {
+        if (section.shape.hasTrait<SyntheticOutputTrait>()) {
+            when (section) {
+                is BuilderSection.AdditionalFieldsInBuild -> {
+                    rust("_request_id: None,")
+                }
+
+                else -> {}
+            }
+        }
+    }
+}
+
+class RequestIdReExportCustomization(codegenContext: ClientCodegenContext) {
+    private val runtimeConfig = codegenContext.runtimeConfig
+
+    fun extras(rustCrate: RustCrate) {
+        rustCrate.withModule(ClientRustModule.Operation) {
+            rustTemplate(
+                "pub use #{RequestId};",
+                "RequestId" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::request_id::RequestId"),
+            )
+        }
+    }
+}
@@ -42,6 +42,7 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
         "StandardRetryStrategy" to configReexport(retries.resolve("strategy::StandardRetryStrategy")),
         "SystemTime" to RuntimeType.std.resolve("time::SystemTime"),
         "TimeoutConfig" to timeoutModule.resolve("TimeoutConfig"),
+        "TokenBucket" to retries.resolve("TokenBucket"),
     )
 
     override fun section(section: ServiceConfig) =
@@ -65,6 +66,11 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
                             self.config.load::<#{TimeoutConfig}>()
                         }
 
+                        /// Return a reference to the retry token bucket contained in this config, if any.
+                        pub fn token_bucket(&self) -> #{Option}<&#{TokenBucket}> {
+                            self.config.load::<#{TokenBucket}>()
+                        }
+
                         ##[doc(hidden)]
                         /// Returns a reference to the retry partition contained in this config, if any.
                         ///
@@ -248,6 +254,43 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
                         *codegenScope,
                     )
 
+                    rustTemplate(
+                        """
+                        /// Set the token bucket that limits how many retries this client can make.
+                        ///
+                        /// `TokenBucket` is a handle to shared state, so clones of it draw from the same quota.
+                        /// Passing the same token bucket to clients that are frequently rebuilt keeps the retry
+                        /// quota intact across rebuilds, rather than starting every new client with a full bucket.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::Config;
+                        /// use $moduleUseName::config::retry::{RetryConfig, TokenBucket};
+                        ///
+                        /// // Owned by the application, and outlives any one client
+                        /// let token_bucket = TokenBucket::new(500);
+                        ///
+                        /// let config = Config::builder()
+                        ///     .retry_config(RetryConfig::standard())
+                        ///     .token_bucket(token_bucket.clone())
+                        ///     .build();
+                        /// ```
+                        pub fn token_bucket(mut self, token_bucket: #{TokenBucket}) -> Self {
+                            self.set_token_bucket(Some(token_bucket));
+                            self
+                        }
+
+                        /// Set the token bucket that limits how many retries this client can make.
+                        ///
+                        /// `TokenBucket` is a handle to shared state, so clones of it draw from the same quota.
+                        pub fn set_token_bucket(&mut self, token_bucket: #{Option}<#{TokenBucket}>) -> &mut Self {
+                            token_bucket.map(|t| self.config.store_put(t));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
                     Attribute.DocHidden.render(this)
                     rustTemplate(
                         """
@@ -299,13 +342,13 @@ class ResiliencyReExportCustomization(codegenContext: ClientCodegenContext) {
             )
 
             rustTemplate(
-                "pub use #{types_retry}::RetryPartition;",
+                "pub use #{types_retry}::{RetryPartition, TokenBucket};",
                 "types_retry" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::retries"),
             )
         }
         rustCrate.withModule(ClientRustModule.Config.timeout) {
             rustTemplate(
-                "pub use #{timeout}::{TimeoutConfig, TimeoutConfigBuilder};",
+                "pub use #{timeout}::{Deadline, TimeoutConfig, TimeoutConfigBuilder};",
                 "timeout" to RuntimeType.smithyTypes(runtimeConfig).resolve("timeout"),
             )
         }
@@ -0,0 +1,88 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+
+class StalledStreamProtectionConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "StalledStreamProtectionConfig" to configReexport(
+            RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
+                .resolve("client::stalled_stream_protection::StalledStreamProtectionConfig"),
+        ),
+    )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                is ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Return a reference to the stalled stream protection configuration contained in this config, if any.
+                        pub fn stalled_stream_protection(&self) -> #{Option}<&#{StalledStreamProtectionConfig}> {
+                            self.config.load::<#{StalledStreamProtectionConfig}>()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Set the [`StalledStreamProtectionConfig`](#{StalledStreamProtectionConfig})
+                        /// to configure protection for stalled streams.
+                        ///
+                        /// When enabled, uploads and downloads that transfer less than the minimum throughput
+                        /// fail with a retryable error, rather than hanging until the operation timeout.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// ## use std::time::Duration;
+                        /// use $moduleUseName::config::{Config, StalledStreamProtectionConfig};
+                        ///
+                        /// let stalled_stream_protection = StalledStreamProtectionConfig::enabled()
+                        ///     .minimum_throughput(1024, Duration::from_secs(10))
+                        ///     .build();
+                        /// let config = Config::builder()
+                        ///     .stalled_stream_protection(stalled_stream_protection)
+                        ///     .build();
+                        /// ```
+                        pub fn stalled_stream_protection(
+                            mut self,
+                            stalled_stream_protection_config: #{StalledStreamProtectionConfig}
+                        ) -> Self {
+                            self.set_stalled_stream_protection(#{Some}(stalled_stream_protection_config));
+                            self
+                        }
+
+                        /// Set the [`StalledStreamProtectionConfig`](#{StalledStreamProtectionConfig})
+                        /// to configure protection for stalled streams.
+                        pub fn set_stalled_stream_protection(
+                            &mut self,
+                            stalled_stream_protection_config: #{Option}<#{StalledStreamProtectionConfig}>
+                        ) -> &mut Self {
+                            self.config.store_or_unset(stalled_stream_protection_config);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
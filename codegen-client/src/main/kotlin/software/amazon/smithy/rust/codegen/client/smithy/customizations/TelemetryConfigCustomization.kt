@@ -0,0 +1,183 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+
+/**
+ * Adds `meter_provider`, `tracer_provider`, and `telemetry_provider` setters to the service config, which turn on
+ * the metrics and spans that the orchestrator records, a `log_sampling` setter that limits how many operations
+ * are logged in full, and a `subsystem_tracing` setter that sets separate log levels for retries, signing, endpoint
+ * resolution, and the connector.
+ */
+class TelemetryConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val observability = RuntimeType.smithyObservability(codegenContext.runtimeConfig)
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "LogSampling" to configReexport(
+            RuntimeType.smithyRuntime(codegenContext.runtimeConfig).resolve("client::log_sampling::LogSampling"),
+        ),
+        "MeterProvider" to configReexport(observability.resolve("meter::MeterProvider")),
+        "SharedMeterProvider" to configReexport(observability.resolve("meter::SharedMeterProvider")),
+        "SharedTracerProvider" to configReexport(observability.resolve("tracer::SharedTracerProvider")),
+        "Subsystem" to configReexport(
+            RuntimeType.smithyRuntime(codegenContext.runtimeConfig).resolve("client::subsystem_tracing::Subsystem"),
+        ),
+        "SubsystemTracing" to configReexport(
+            RuntimeType.smithyRuntime(codegenContext.runtimeConfig)
+                .resolve("client::subsystem_tracing::SubsystemTracing"),
+        ),
+        "TelemetryProvider" to configReexport(observability.resolve("provider::TelemetryProvider")),
+        "TracerProvider" to configReexport(observability.resolve("tracer::TracerProvider")),
+    )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns the meter provider that metrics are recorded with, if one was set.
+                        pub fn meter_provider(&self) -> #{Option}<#{SharedMeterProvider}> {
+                            self.config.load::<#{SharedMeterProvider}>().cloned()
+                        }
+
+                        /// Returns the tracer provider that spans are started with, if one was set.
+                        pub fn tracer_provider(&self) -> #{Option}<#{SharedTracerProvider}> {
+                            self.config.load::<#{SharedTracerProvider}>().cloned()
+                        }
+
+                        /// Returns the log sampling settings, if they were set.
+                        pub fn log_sampling(&self) -> #{Option}<&#{LogSampling}> {
+                            self.config.load::<#{LogSampling}>()
+                        }
+
+                        /// Returns the log levels of the client's subsystems, if they were set.
+                        pub fn subsystem_tracing(&self) -> #{Option}<&#{SubsystemTracing}> {
+                            self.config.load::<#{SubsystemTracing}>()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets the meter provider that metrics are recorded with.
+                        ///
+                        /// When a meter provider is set, each operation records its call duration, the
+                        /// duration of each attempt, and the kind of error that it failed with, if any.
+                        /// No metrics are recorded by default.
+                        pub fn meter_provider(mut self, meter_provider: impl #{MeterProvider} + 'static) -> Self {
+                            self.set_meter_provider(#{Some}(#{SharedMeterProvider}::new(meter_provider)));
+                            self
+                        }
+
+                        /// Sets the meter provider that metrics are recorded with.
+                        pub fn set_meter_provider(&mut self, meter_provider: #{Option}<#{SharedMeterProvider}>) -> &mut Self {
+                            self.config.store_or_unset(meter_provider);
+                            self
+                        }
+
+                        /// Sets the tracer provider that spans are started with.
+                        ///
+                        /// When a tracer provider is set, each operation starts a span that covers the whole
+                        /// call, with a child span for each attempt. No spans are started by default.
+                        pub fn tracer_provider(mut self, tracer_provider: impl #{TracerProvider} + 'static) -> Self {
+                            self.set_tracer_provider(#{Some}(#{SharedTracerProvider}::new(tracer_provider)));
+                            self
+                        }
+
+                        /// Sets the tracer provider that spans are started with.
+                        pub fn set_tracer_provider(&mut self, tracer_provider: #{Option}<#{SharedTracerProvider}>) -> &mut Self {
+                            self.config.store_or_unset(tracer_provider);
+                            self
+                        }
+
+                        /// Sets both the meter provider and the tracer provider from a [`TelemetryProvider`](#{TelemetryProvider}).
+                        ///
+                        /// A provider that the telemetry provider doesn't have is unset.
+                        pub fn telemetry_provider(mut self, telemetry_provider: #{TelemetryProvider}) -> Self {
+                            self.set_meter_provider(telemetry_provider.meter_provider().cloned());
+                            self.set_tracer_provider(telemetry_provider.tracer_provider().cloned());
+                            self
+                        }
+
+                        /// Sets how many operations are logged in full.
+                        ///
+                        /// Only one in every N operations emits its `debug` and `trace` logs. The rest only emit
+                        /// logs at the unsampled level or above, which is `info` by default, and log their error
+                        /// at the `debug` level when they fail. Every operation is logged in full by default.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::LogSampling;
+                        ///
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     // Log every 100th operation in full
+                        ///     .log_sampling(LogSampling::one_in(100))
+                        ///     .build();
+                        /// ```
+                        pub fn log_sampling(mut self, log_sampling: #{LogSampling}) -> Self {
+                            self.set_log_sampling(#{Some}(log_sampling));
+                            self
+                        }
+
+                        /// Sets how many operations are logged in full.
+                        pub fn set_log_sampling(&mut self, log_sampling: #{Option}<#{LogSampling}>) -> &mut Self {
+                            self.config.store_or_unset(log_sampling);
+                            self
+                        }
+
+                        /// Sets separate log levels for retries, signing, endpoint resolution, and the connector.
+                        ///
+                        /// While an operation runs, the spans and events of each subsystem are only logged at
+                        /// its level or above. This can only make logging less verbose than the filter of the
+                        /// installed subscriber. Nothing is filtered by default.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::{Subsystem, SubsystemTracing};
+                        /// use tracing::level_filters::LevelFilter;
+                        ///
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     // Trace retries, but only log warnings from the connector
+                        ///     .subsystem_tracing(
+                        ///         SubsystemTracing::builder()
+                        ///             .level(Subsystem::Retries, LevelFilter::TRACE)
+                        ///             .level(Subsystem::Connector, LevelFilter::WARN)
+                        ///             .build()
+                        ///     )
+                        ///     .build();
+                        /// ```
+                        pub fn subsystem_tracing(mut self, subsystem_tracing: #{SubsystemTracing}) -> Self {
+                            self.set_subsystem_tracing(#{Some}(subsystem_tracing));
+                            self
+                        }
+
+                        /// Sets separate log levels for retries, signing, endpoint resolution, and the connector.
+                        pub fn set_subsystem_tracing(&mut self, subsystem_tracing: #{Option}<#{SubsystemTracing}>) -> &mut Self {
+                            self.config.store_or_unset(subsystem_tracing);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
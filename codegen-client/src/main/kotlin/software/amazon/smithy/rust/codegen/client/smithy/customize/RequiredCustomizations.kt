@@ -8,16 +8,31 @@ package software.amazon.smithy.rust.codegen.client.smithy.customize
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.AuthSchemeConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.ConnectionPoisoningRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.ErrorReportConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.ErrorReportOperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.ErrorReportReExportCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.HttpChecksumRequiredGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.IdentityCacheConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.InterceptorConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.LatencyBuilderCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.LatencyReExportCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.LatencyStructureCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.LifecycleObserverConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.LifecycleObserverReExportCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.MetadataCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.RequestIdBuilderCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.RequestIdConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.RequestIdReExportCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.RequestIdStructureCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.ResiliencyConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.ResiliencyReExportCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.RetryClassifierConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.RetryClassifierOperationCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.RetryClassifierServiceRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.StalledStreamProtectionConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.TelemetryConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.TimeSourceCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
@@ -30,7 +45,9 @@ import software.amazon.smithy.rust.codegen.core.smithy.customizations.AllowLints
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.CrateVersionCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.pubUseSmithyPrimitives
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.pubUseSmithyPrimitivesEventStream
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.generators.LibRsCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.generators.operationBuildError
 
 val TestUtilFeature = Feature("test-util", false, listOf())
@@ -52,7 +69,8 @@ class RequiredCustomizations : ClientCodegenDecorator {
         baseCustomizations +
             MetadataCustomization(codegenContext, operation) +
             HttpChecksumRequiredGenerator(codegenContext, operation) +
-            RetryClassifierOperationCustomization(codegenContext, operation)
+            RetryClassifierOperationCustomization(codegenContext, operation) +
+            ErrorReportOperationCustomization(codegenContext, operation)
 
     override fun configCustomizations(
         codegenContext: ClientCodegenContext,
@@ -61,8 +79,14 @@ class RequiredCustomizations : ClientCodegenDecorator {
         ResiliencyConfigCustomization(codegenContext) +
         IdentityCacheConfigCustomization(codegenContext) +
         InterceptorConfigCustomization(codegenContext) +
+        AuthSchemeConfigCustomization(codegenContext) +
         TimeSourceCustomization(codegenContext) +
-        RetryClassifierConfigCustomization(codegenContext)
+        RetryClassifierConfigCustomization(codegenContext) +
+        StalledStreamProtectionConfigCustomization(codegenContext) +
+        TelemetryConfigCustomization(codegenContext) +
+        RequestIdConfigCustomization(codegenContext) +
+        ErrorReportConfigCustomization(codegenContext) +
+        LifecycleObserverConfigCustomization(codegenContext)
 
     override fun libRsCustomizations(
         codegenContext: ClientCodegenContext,
@@ -70,6 +94,20 @@ class RequiredCustomizations : ClientCodegenDecorator {
     ): List<LibRsCustomization> =
         baseCustomizations + AllowLintsCustomization()
 
+    override fun structureCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<StructureCustomization>,
+    ): List<StructureCustomization> = baseCustomizations +
+        LatencyStructureCustomization(codegenContext) +
+        RequestIdStructureCustomization(codegenContext)
+
+    override fun builderCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<BuilderCustomization>,
+    ): List<BuilderCustomization> = baseCustomizations +
+        LatencyBuilderCustomization() +
+        RequestIdBuilderCustomization()
+
     override fun extras(codegenContext: ClientCodegenContext, rustCrate: RustCrate) {
         val rc = codegenContext.runtimeConfig
 
@@ -87,6 +125,18 @@ class RequiredCustomizations : ClientCodegenDecorator {
         // Re-export resiliency types
         ResiliencyReExportCustomization(codegenContext).extras(rustCrate)
 
+        // Re-export latency types
+        LatencyReExportCustomization(codegenContext).extras(rustCrate)
+
+        // Re-export the `RequestId` trait
+        RequestIdReExportCustomization(codegenContext).extras(rustCrate)
+
+        // Re-export error report types
+        ErrorReportReExportCustomization(codegenContext).extras(rustCrate)
+
+        // Re-export lifecycle observer types
+        LifecycleObserverReExportCustomization(codegenContext).extras(rustCrate)
+
         rustCrate.withModule(ClientRustModule.primitives) {
             pubUseSmithyPrimitives(codegenContext, codegenContext.model, rustCrate)(this)
         }
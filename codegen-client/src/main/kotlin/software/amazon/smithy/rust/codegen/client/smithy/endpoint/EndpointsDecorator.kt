@@ -67,6 +67,22 @@ interface EndpointCustomization {
 
     fun setBuiltInOnServiceConfig(name: String, value: Node, configBuilderRef: String): Writable? = null
 
+    /**
+     * Update the endpoint params right before they are passed to the service-specific endpoint resolver.
+     *
+     * [paramsRef] is a mutable copy of the service's `Params`, and [resolverParamsRef] is the `EndpointResolverParams`
+     * they were taken from, so that properties set during endpoint orchestration (such as the resolved identity) can be
+     * read. If no customization returns a [Writable], the params are passed to the resolver as-is.
+     *
+     * Example:
+     * ```kotlin
+     * override fun finalizeParams(paramsRef: String, resolverParamsRef: String): Writable = writable {
+     *     rust("if let Some(tenant) = $resolverParamsRef.get_property::<Tenant>() { $paramsRef.tenant = Some(tenant.0.clone()); }")
+     * }
+     * ```
+     */
+    fun finalizeParams(paramsRef: String, resolverParamsRef: String): Writable? = null
+
     /**
      * Provide a list of additional endpoints standard library functions that rules can use
      */
@@ -27,6 +27,7 @@ import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rulesgen.Owner
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rustName
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.allow
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.comment
@@ -138,7 +139,7 @@ internal class EndpointResolverGenerator(
         "ResolveEndpointError" to types.resolveEndpointError,
         "EndpointError" to types.resolveEndpointError,
         "ServiceSpecificEndpointResolver" to codegenContext.serviceSpecificEndpointResolver(),
-        "DiagnosticCollector" to endpointsLib("diagnostic").toType().resolve("DiagnosticCollector"),
+        "DiagnosticCollector" to endpointsLib("diagnostic", CargoDependency.Tracing).toType().resolve("DiagnosticCollector"),
     )
 
     private val allowLintsForResolver = listOf(
@@ -193,8 +194,9 @@ internal class EndpointResolverGenerator(
 
                     fn resolve_endpoint(&self, params: &#{Params}) -> Result<#{SmithyEndpoint}, #{BoxError}> {
                         let mut diagnostic_collector = #{DiagnosticCollector}::new();
-                        Ok(#{resolver_fn}(params, &mut diagnostic_collector, #{additional_args})
-                            .map_err(|err|err.with_source(diagnostic_collector.take_last_error()))?)
+                        let result = #{resolver_fn}(params, &mut diagnostic_collector, #{additional_args});
+                        diagnostic_collector.emit_trace(&result);
+                        Ok(result.map_err(|err|err.with_source(diagnostic_collector.take_last_error()))?)
                     }
                 }
 
@@ -302,7 +304,10 @@ internal class EndpointResolverGenerator(
                 val resultName =
                     (condition.result.orNull() ?: (fn as? Reference)?.name)?.rustName() ?: "_"
                 val target = generator.generate(fn)
-                val next = generateRuleInternal(rule, rest)
+                val next = writable {
+                    rust("$DiagnosticCollector.record_condition(${escape(condition.toString()).dq()});")
+                    generateRuleInternal(rule, rest)(this)
+                }
                 when {
                     fn.type() is OptionalType -> {
                         Attribute.AllowUnusedVariables.render(this)
@@ -339,7 +344,7 @@ internal class EndpointResolverGenerator(
                             #{next:W}
                             """,
                             "target" to generator.generate(fn),
-                            "next" to generateRuleInternal(rule, rest),
+                            "next" to next,
                         )
                     }
                 }
@@ -382,8 +387,31 @@ internal class EndpointResolverGenerator(
 
 fun ClientCodegenContext.serviceSpecificEndpointResolver(): RuntimeType {
     val generator = EndpointTypesGenerator.fromContext(this)
+    val finalizers = rootDecorator.endpointCustomizations(this).mapNotNull { it.finalizeParams("endpoint_params", "params") }
     return RuntimeType.forInlineFun("ResolveEndpoint", ClientRustModule.Config.endpoint) {
-        val ctx = arrayOf(*preludeScope, "Params" to generator.paramsStruct(), *Types(runtimeConfig).toArray(), "Debug" to RuntimeType.Debug)
+        val ctx = arrayOf(
+            *preludeScope,
+            "Params" to generator.paramsStruct(),
+            *Types(runtimeConfig).toArray(),
+            "Debug" to RuntimeType.Debug,
+            "resolve" to writable {
+                if (finalizers.isEmpty()) {
+                    rust("Some(params) => self.0.resolve_endpoint(params),")
+                } else {
+                    rustTemplate(
+                        """
+                        Some(endpoint_params) => {
+                            let mut endpoint_params = endpoint_params.clone();
+                            #{finalizers:W}
+                            #{EndpointFuture}::new(async move { self.0.resolve_endpoint(&endpoint_params).await })
+                        }
+                        """,
+                        *Types(runtimeConfig).toArray(),
+                        "finalizers" to finalizers.join("\n"),
+                    )
+                }
+            },
+        )
         rustTemplate(
             """
             /// Endpoint resolver trait specific to ${serviceShape.serviceNameOrDefault("this service")}
@@ -410,7 +438,7 @@ fun ClientCodegenContext.serviceSpecificEndpointResolver(): RuntimeType {
             {
                 fn resolve_endpoint<'a>(&'a self, params: &'a #{EndpointResolverParams}) -> #{EndpointFuture}<'a> {
                     let ep = match params.get::<#{Params}>() {
-                        Some(params) => self.0.resolve_endpoint(params),
+                        #{resolve:W}
                         None => #{EndpointFuture}::ready(Err("params of expected type was not present".into())),
                     };
                     ep
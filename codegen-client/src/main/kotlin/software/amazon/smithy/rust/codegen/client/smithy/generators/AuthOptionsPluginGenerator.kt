@@ -18,6 +18,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.util.PANIC
+import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import java.util.logging.Logger
 
@@ -57,10 +58,18 @@ class AuthOptionsPluginGenerator(private val codegenContext: ClientCodegenContex
                 out.addAll(optionsForScheme.flatMap { (it as AuthSchemeOption.StaticAuthSchemeOption).constructor })
                 noSupportedAuthSchemes = false
             } else {
+                // Custom auth schemes can be registered at runtime under the shape ID of their trait
                 logger.warning(
                     "No auth scheme implementation available for $schemeShapeId. " +
-                        "The generated client will not attempt to use this auth scheme.",
+                        "The generated client will only use this auth scheme if one is registered at runtime.",
                 )
+                out += writable {
+                    rustTemplate(
+                        "#{AuthSchemeId}::new(${schemeShapeId.toString().dq()})",
+                        "AuthSchemeId" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                            .resolve("client::auth::AuthSchemeId"),
+                    )
+                }
             }
         }
         if (operationShape.hasTrait<OptionalAuthTrait>() || noSupportedAuthSchemes) {
@@ -126,8 +126,19 @@ open class OperationGenerator(
                     let context = Self::orchestrate_with_stop_point(runtime_plugins, input, #{StopPoint}::None)
                         .await
                         .map_err(map_err)?;
+                    let latency = context
+                        .response()
+                        .and_then(|response| response.extension::<#{LatencyBreakdown}>())
+                        .cloned();
+                    let request_id = context
+                        .response()
+                        .and_then(#{RequestId}::request_id)
+                        .map(str::to_string);
                     let output = context.finalize().map_err(map_err)?;
-                    #{Ok}(output.downcast::<#{OperationOutput}>().expect("correct output type"))
+                    let mut output = output.downcast::<#{OperationOutput}>().expect("correct output type");
+                    output._set_latency(latency);
+                    output._set_request_id(request_id);
+                    #{Ok}(output)
                 }
 
                 pub(crate) async fn orchestrate_with_stop_point(
@@ -166,8 +177,12 @@ open class OperationGenerator(
                 *codegenScope,
                 "Error" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::interceptors::context::Error"),
                 "InterceptorContext" to RuntimeType.interceptorContext(runtimeConfig),
+                "LatencyBreakdown" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                    .resolve("client::latency::LatencyBreakdown"),
                 "OrchestratorError" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
                     .resolve("client::orchestrator::error::OrchestratorError"),
+                "RequestId" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                    .resolve("client::request_id::RequestId"),
                 "RuntimePlugin" to RuntimeType.runtimePlugin(runtimeConfig),
                 "RuntimePlugins" to RuntimeType.runtimePlugins(runtimeConfig),
                 "StopPoint" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::orchestrator::StopPoint"),
@@ -5,8 +5,10 @@
 
 package software.amazon.smithy.rust.codegen.client.smithy.generators.client
 
+import software.amazon.smithy.rulesengine.language.syntax.parameters.BuiltIns
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointRulesetIndex
 import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.Visibility
@@ -27,13 +29,22 @@ class CustomizableOperationGenerator(
 ) {
     private val runtimeConfig = codegenContext.runtimeConfig
 
+    // The config builder has a `set_endpoint_url` setter either when it's explicitly enabled, or when the
+    // endpoint rules take an `SDK::Endpoint` built-in (which the SDK exposes as `endpoint_url`)
+    private val hasEndpointUrl = codegenContext.settings.codegenConfig.includeEndpointUrlConfig ||
+        EndpointRulesetIndex.of(codegenContext.model).endpointRulesForService(codegenContext.serviceShape)
+            ?.parameters?.toList()?.any { it.builtIn == BuiltIns.SDK_ENDPOINT.builtIn } == true
+
     fun render(crate: RustCrate) {
         val codegenScope = arrayOf(
             *preludeScope,
+            "CancellationToken" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                .resolve("client::cancellation::CancellationToken"),
             "CustomizableOperation" to ClientRustModule.Client.customize.toType()
                 .resolve("CustomizableOperation"),
             "CustomizableSend" to ClientRustModule.Client.customize.toType()
                 .resolve("internal::CustomizableSend"),
+            "Deadline" to RuntimeType.smithyTypes(runtimeConfig).resolve("timeout::Deadline"),
             "HttpRequest" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
                 .resolve("client::orchestrator::HttpRequest"),
             "HttpResponse" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
@@ -52,11 +63,13 @@ class CustomizableOperationGenerator(
             "SdkError" to RuntimeType.sdkError(runtimeConfig),
             "SharedInterceptor" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
                 .resolve("client::interceptors::SharedInterceptor"),
+            "TimeoutConfig" to RuntimeType.smithyTypes(runtimeConfig).resolve("timeout::TimeoutConfig"),
         )
 
         val customizeModule = ClientRustModule.Client.customize
         crate.withModule(customizeModule) {
             renderConvenienceAliases(customizeModule, this)
+            rustTemplate("pub use #{CancellationToken};", *codegenScope)
 
             rustTemplate(
                 """
@@ -64,6 +77,10 @@ class CustomizableOperationGenerator(
                 pub struct CustomizableOperation<T, E, B> {
                     customizable_send: B,
                     config_override: #{Option}<crate::config::Builder>,
+                    timeout_config: #{Option}<#{TimeoutConfig}>,
+                    deadline: #{Option}<#{Deadline}>,
+                    cancellation_token: #{Option}<#{CancellationToken}>,
+                    #{endpoint_url_field:W}
                     interceptors: Vec<#{SharedInterceptor}>,
                     runtime_plugins: Vec<#{SharedRuntimePlugin}>,
                     _output: #{PhantomData}<T>,
@@ -77,6 +94,10 @@ class CustomizableOperationGenerator(
                             Self {
                                 customizable_send,
                                 config_override: #{None},
+                                timeout_config: #{None},
+                                deadline: #{None},
+                                cancellation_token: #{None},
+                                #{endpoint_url_init:W}
                                 interceptors: vec![],
                                 runtime_plugins: vec![],
                                 _output: #{PhantomData},
@@ -151,6 +172,79 @@ class CustomizableOperationGenerator(
                         self
                     }
 
+                    /// Overrides the timeout configuration for a single operation invocation.
+                    ///
+                    /// The client's timeout configuration is left untouched, so this can be used to give an
+                    /// individual call a tighter (or looser) budget without building another client.
+                    ///
+                    /// The given `timeout_config` replaces the client's timeout configuration for this invocation,
+                    /// so any timeout that isn't set in it is disabled. To only change some of the timeouts, start from
+                    /// the client's timeout configuration:
+                    ///
+                    /// ```ignore
+                    /// let timeout_config = client
+                    ///     .config()
+                    ///     .timeout_config()
+                    ///     .map(|timeout_config| timeout_config.to_builder())
+                    ///     .unwrap_or_default()
+                    ///     .operation_timeout(Duration::from_secs(2))
+                    ///     .build();
+                    /// let output = client
+                    ///     .some_operation()
+                    ///     .customize()
+                    ///     .timeout_config(timeout_config)
+                    ///     .send()
+                    ///     .await?;
+                    /// ```
+                    ///
+                    /// This takes precedence over a timeout configuration set with `config_override`.
+                    pub fn timeout_config(mut self, timeout_config: #{TimeoutConfig}) -> Self {
+                        self.timeout_config = #{Some}(timeout_config);
+                        self
+                    }
+
+                    /// Sets an absolute deadline by which this operation invocation must complete.
+                    ///
+                    /// If the deadline passes before the operation completes, including any retries, then it fails
+                    /// with a timeout error. This is useful for bounding the total time spent on several operations
+                    /// by giving each of them the same deadline. If an operation timeout is also configured, then
+                    /// whichever ends first applies.
+                    ///
+                    /// The time remaining until the deadline can be read from interceptors by loading the
+                    /// `Deadline` from the config bag, which also accounts for the operation timeout.
+                    pub fn deadline(mut self, deadline: impl #{Into}<#{Deadline}>) -> Self {
+                        self.deadline = #{Some}(deadline.into());
+                        self
+                    }
+
+                    /// Sets a token that can be used to cancel this operation invocation while it's in flight.
+                    ///
+                    /// When the token is cancelled, the in-flight attempt is aborted, its connection is released,
+                    /// and the operation fails with `SdkError::Cancelled`. This is useful when the future for the
+                    /// operation can't easily be dropped, such as when it's owned by a task deep inside an application.
+                    /// Unlike dropping the future, this still runs the interceptors that are called once the operation
+                    /// completes.
+                    ///
+                    /// ```ignore
+                    /// let token = CancellationToken::new();
+                    /// let handle = token.clone();
+                    /// let task = tokio::spawn(
+                    ///     client
+                    ///         .some_operation()
+                    ///         .customize()
+                    ///         .cancellation_token(token)
+                    ///         .send(),
+                    /// );
+                    /// // Later...
+                    /// handle.cancel();
+                    /// ```
+                    pub fn cancellation_token(mut self, token: #{CancellationToken}) -> Self {
+                        self.cancellation_token = #{Some}(token);
+                        self
+                    }
+
+                    #{endpoint_url_method:W}
+
                     /// Sends the request and returns the response.
                     pub async fn send(
                         self,
@@ -160,6 +254,16 @@ class CustomizableOperationGenerator(
                         B: #{CustomizableSend}<T, E>,
                     {
                         let mut config_override = self.config_override.unwrap_or_default();
+                        if let #{Some}(timeout_config) = self.timeout_config {
+                            config_override.set_timeout_config(#{Some}(timeout_config));
+                        }
+                        if let #{Some}(deadline) = self.deadline {
+                            config_override.config.store_put(deadline);
+                        }
+                        if let #{Some}(cancellation_token) = self.cancellation_token {
+                            config_override.config.store_put(cancellation_token);
+                        }
+                        #{apply_endpoint_url:W}
                         self.interceptors.into_iter().for_each(|interceptor| {
                             config_override.push_interceptor(interceptor);
                         });
@@ -174,6 +278,48 @@ class CustomizableOperationGenerator(
                 }
                 """,
                 *codegenScope,
+                "endpoint_url_field" to writable {
+                    if (hasEndpointUrl) {
+                        rustTemplate("endpoint_url: #{Option}<#{String}>,", *codegenScope)
+                    }
+                },
+                "endpoint_url_init" to writable {
+                    if (hasEndpointUrl) {
+                        rustTemplate("endpoint_url: #{None},", *codegenScope)
+                    }
+                },
+                "endpoint_url_method" to writable {
+                    if (hasEndpointUrl) {
+                        rustTemplate(
+                            """
+                            /// Sends this operation invocation to the given endpoint URL instead of the one the client resolves.
+                            ///
+                            /// Everything else about the client, such as its credentials and retry configuration, is left
+                            /// as is, so this can be used to send a single call to a different host (for instance, a regional
+                            /// replica or a local mock) without building another client.
+                            ///
+                            /// This takes precedence over an endpoint URL set with `config_override`.
+                            pub fn endpoint_url(mut self, endpoint_url: impl #{Into}<#{String}>) -> Self {
+                                self.endpoint_url = #{Some}(endpoint_url.into());
+                                self
+                            }
+                            """,
+                            *codegenScope,
+                        )
+                    }
+                },
+                "apply_endpoint_url" to writable {
+                    if (hasEndpointUrl) {
+                        rustTemplate(
+                            """
+                            if let #{Some}(endpoint_url) = self.endpoint_url {
+                                config_override.set_endpoint_url(#{Some}(endpoint_url));
+                            }
+                            """,
+                            *codegenScope,
+                        )
+                    }
+                },
                 "additional_methods" to writable {
                     writeCustomizations(
                         customizations,
@@ -62,8 +62,18 @@ class FluentClientGenerator(
     private val customizations: List<FluentClientCustomization> = emptyList(),
 ) {
     companion object {
-        fun clientOperationFnName(operationShape: OperationShape, symbolProvider: RustSymbolProvider): String =
-            RustReservedWords.escapeIfNeeded(symbolProvider.toSymbol(operationShape).name.toSnakeCase())
+        // To avoid conflicts with the methods on the generated client. Renaming an operation's method breaks the
+        // code that calls it, so names are only added here along with the client method they collide with, and the
+        // renamed method's docs point that out. Every use of an operation's method name must go through
+        // `clientOperationFnName` so that it picks up these renames.
+        private val clientMethodNameMap = mapOf(
+            "shutdown" to "shutdown_operation",
+        )
+
+        fun clientOperationFnName(operationShape: OperationShape, symbolProvider: RustSymbolProvider): String {
+            val fnName = symbolProvider.toSymbol(operationShape).name.toSnakeCase()
+            return clientMethodNameMap[fnName] ?: RustReservedWords.escapeIfNeeded(fnName)
+        }
 
         fun clientOperationModuleName(operationShape: OperationShape, symbolProvider: RustSymbolProvider): String =
             RustReservedWords.escapeIfNeeded(
@@ -102,6 +112,7 @@ class FluentClientGenerator(
                     pub(crate) conf: crate::Config,
                     ##[allow(dead_code)] // unused when a service does not provide any operations
                     pub(crate) runtime_plugins: #{RuntimePlugins},
+                    pub(crate) shutdown: #{ClientShutdown},
                 }
 
                 #{client_docs:W}
@@ -122,9 +133,14 @@ class FluentClientGenerator(
                     ///
                     /// The panic message for each of these will have instructions on how to resolve them.
                     pub fn from_conf(conf: crate::Config) -> Self {
+                        let shutdown = #{ClientShutdown}::new();
+                        let mut shutdown_layer = #{Layer}::new("ClientShutdown");
+                        shutdown_layer.store_put(shutdown.clone());
                         let handle = Handle {
                             conf: conf.clone(),
-                            runtime_plugins: #{base_client_runtime_plugins}(conf),
+                            runtime_plugins: #{base_client_runtime_plugins}(conf)
+                                .with_client_plugin(#{StaticRuntimePlugin}::new().with_config(shutdown_layer.freeze())),
+                            shutdown,
                         };
                         if let Err(err) = Self::validate_config(&handle) {
                             panic!("Invalid client configuration: {err}");
@@ -139,6 +155,30 @@ class FluentClientGenerator(
                         &self.handle.conf
                     }
 
+                    /// Shuts down the client gracefully.
+                    ///
+                    /// The client stops accepting new operations, stops its background tasks, such as refreshing
+                    /// discovered endpoints, and waits up to `timeout` for its in-flight operations to complete.
+                    /// Operations that are still in flight once the timeout elapses are cancelled, and an error is
+                    /// returned. Afterwards, the idle connections held by the client's HTTP client are released.
+                    /// When other clients share the HTTP client, their idle connections are released too, and they
+                    /// reconnect on their next request.
+                    ///
+                    /// This affects every clone of the client. Any other resources owned by the client, such as
+                    /// its identity cache, are released once every clone has been dropped.
+                    ///
+                    /// A `Shutdown` operation of the service, if it has one, is sent with `shutdown_operation` instead.
+                    pub async fn shutdown(&self, timeout: ::std::time::Duration) -> #{Result}<(), #{ShutdownError}> {
+                        let mut cfg = #{ConfigBag}::base();
+                        let components = self.handle.runtime_plugins.apply_client_configuration(&mut cfg).ok();
+                        let sleep_impl = components.as_ref().and_then(|c| c.sleep_impl());
+                        let result = self.handle.shutdown.shutdown(timeout, sleep_impl).await;
+                        if let #{Some}(http_client) = components.as_ref().and_then(|c| c.http_client()) {
+                            #{HttpClient}::release_idle_connections(&http_client);
+                        }
+                        result
+                    }
+
                     fn validate_config(handle: &Handle) -> Result<(), #{BoxError}> {
                         let mut cfg = #{ConfigBag}::base();
                         handle.runtime_plugins
@@ -161,8 +201,16 @@ class FluentClientGenerator(
                         )(this)
                     }
                 },
+                "ClientShutdown" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                    .resolve("client::shutdown::ClientShutdown"),
                 "ConfigBag" to RuntimeType.configBag(runtimeConfig),
+                "HttpClient" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::http::HttpClient"),
+                "Layer" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::Layer"),
                 "RuntimePlugins" to RuntimeType.runtimePlugins(runtimeConfig),
+                "ShutdownError" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                    .resolve("client::shutdown::ShutdownError"),
+                "StaticRuntimePlugin" to RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                    .resolve("client::runtime_plugin::StaticRuntimePlugin"),
                 "tracing" to CargoDependency.Tracing.toType(),
             )
         }
@@ -205,9 +253,15 @@ class FluentClientGenerator(
                         outputFieldsHead += " with field(s):\n"
                     }
 
+                    val clientMethodName = name.toSnakeCase()
+                    val maybeRenamed = clientMethodNameMap[clientMethodName]?.let {
+                        "\n///\n/// This method is named `$it` because [`$clientMethodName`](crate::Client::$clientMethodName) " +
+                            "is a method of the client itself."
+                    } ?: ""
+
                     rustTemplate(
                         """
-                        /// Constructs a fluent builder for the [`$name`]($fullPath) operation.$maybePaginated
+                        /// Constructs a fluent builder for the [`$name`]($fullPath) operation.$maybePaginated$maybeRenamed
                         ///
                         /// - $inputFieldsHead$inputFieldsBody
                         /// - $outputFieldsHead$outputFieldsBody
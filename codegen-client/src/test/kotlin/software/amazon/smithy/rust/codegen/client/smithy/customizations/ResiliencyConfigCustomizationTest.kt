@@ -9,14 +9,21 @@ import org.junit.jupiter.api.Test
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenConfig
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
 import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginGenerator
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.client.testutil.clientRustSettings
 import software.amazon.smithy.rust.codegen.client.testutil.stubConfigProject
 import software.amazon.smithy.rust.codegen.client.testutil.testClientCodegenContext
 import software.amazon.smithy.rust.codegen.core.smithy.transformers.OperationNormalizer
 import software.amazon.smithy.rust.codegen.core.smithy.transformers.RecursiveShapeBoxer
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency.Companion.smithyRuntimeApiTestUtil
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.testutil.TestWorkspace
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 
 internal class ResiliencyConfigCustomizationTest {
     private val baseModel = """
@@ -51,4 +58,93 @@ internal class ResiliencyConfigCustomizationTest {
         ResiliencyReExportCustomization(codegenContext).extras(project)
         project.compileAndTest()
     }
+
+    @Test
+    fun `the standard retry strategy draws from the configured token bucket`() {
+        val model = """
+            namespace com.example
+            use aws.protocols#awsJson1_0
+
+            @awsJson1_0
+            service HelloService {
+                operations: [SayHello],
+                version: "1"
+            }
+
+            @optionalAuth
+            operation SayHello {}
+        """.asSmithyModel()
+
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            val runtimeConfig = codegenContext.runtimeConfig
+            val codegenScope = arrayOf(
+                *preludeScope,
+                "AlwaysRetry" to RuntimeType.smithyRuntimeApi(runtimeConfig)
+                    .resolve("client::retries::AlwaysRetry"),
+                "ConfigBag" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::ConfigBag"),
+                "ErrorKind" to RuntimeType.smithyTypes(runtimeConfig).resolve("retry::ErrorKind"),
+                "Input" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("client::interceptors::context::Input"),
+                "InterceptorContext" to RuntimeType.interceptorContext(runtimeConfig),
+                "Layer" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::Layer"),
+                "OrchestratorError" to RuntimeType.smithyRuntimeApi(runtimeConfig)
+                    .resolve("client::orchestrator::OrchestratorError"),
+                "RequestAttempts" to smithyRuntimeApiTestUtil(runtimeConfig).toType()
+                    .resolve("client::retries::RequestAttempts"),
+                "RetryConfig" to RuntimeType.smithyTypes(runtimeConfig).resolve("retry::RetryConfig"),
+                "RuntimeComponentsBuilder" to RuntimeType.runtimeComponentsBuilder(runtimeConfig),
+                "ShouldAttempt" to RuntimeType.smithyRuntimeApi(runtimeConfig)
+                    .resolve("client::retries::ShouldAttempt"),
+                "StandardRetryStrategy" to RuntimeType.smithyRuntime(runtimeConfig)
+                    .resolve("client::retries::strategy::StandardRetryStrategy"),
+            )
+            rustCrate.testModule {
+                unitTest("test_standard_retry_strategy_draws_from_configured_token_bucket") {
+                    rustTemplate(
+                        """
+                        use ::aws_smithy_runtime_api::client::retries::RetryStrategy;
+
+                        let token_bucket = crate::config::retry::TokenBucket::new(500);
+                        let client_config = crate::config::Config::builder()
+                            .retry_config(#{RetryConfig}::standard().with_max_attempts(3))
+                            .token_bucket(token_bucket.clone())
+                            .build();
+
+                        let mut ctx = #{InterceptorContext}::new(#{Input}::doesnt_matter());
+                        ctx.set_output_or_error(#{Err}(#{OrchestratorError}::other("doesn't matter")));
+
+                        let mut layer = #{Layer}::new("test");
+                        layer.store_put(#{RequestAttempts}::new(1));
+                        let mut cfg = #{ConfigBag}::of_layers(vec![layer]);
+                        cfg.push_shared_layer(client_config.config.clone());
+
+                        // Emulate the merging of runtime components from runtime plugins that the orchestrator does
+                        let runtime_components = #{RuntimeComponentsBuilder}::for_tests()
+                            .with_retry_strategy(#{Some}(#{StandardRetryStrategy}::new()))
+                            .with_retry_classifier(#{AlwaysRetry}(#{ErrorKind}::ServerError))
+                            .merge_from(&client_config.runtime_components)
+                            .build()
+                            .unwrap();
+
+                        let retry = runtime_components.retry_strategy();
+                        assert!(matches!(
+                            retry.should_attempt_retry(&ctx, &runtime_components, &cfg).unwrap(),
+                            #{ShouldAttempt}::YesAfterDelay(_)
+                        ));
+                        assert!(token_bucket.available_permits() < 500);
+
+                        // A rebuilt client that's given the same token bucket starts from the drained quota
+                        let rebuilt_config = crate::config::Config::builder()
+                            .token_bucket(token_bucket.clone())
+                            .build();
+                        assert_eq!(
+                            token_bucket.available_permits(),
+                            rebuilt_config.token_bucket().unwrap().available_permits()
+                        );
+                        """,
+                        *codegenScope,
+                    )
+                }
+            }
+        }
+    }
 }
@@ -243,4 +243,54 @@ internal class ConfigOverrideRuntimePluginGeneratorTest {
             }
         }
     }
+
+    @Test
+    fun `operation overrides timeout config`() {
+        clientIntegrationTest(model) { clientCodegenContext, rustCrate ->
+            val runtimeConfig = clientCodegenContext.runtimeConfig
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency().withFeature("test-util"))
+                tokioTest("test_operation_overrides_timeout_config") {
+                    rustTemplate(
+                        """
+                        let client_config = crate::config::Config::builder()
+                            .endpoint_url("http://localhost:1234/")
+                            .http_client(#{NeverClient}::new())
+                            .timeout_config(
+                                #{TimeoutConfig}::builder()
+                                    .operation_timeout(::std::time::Duration::from_secs(600))
+                                    .build(),
+                            )
+                            .build();
+                        let client = crate::client::Client::from_conf(client_config);
+
+                        let err = client
+                            .say_hello()
+                            .customize()
+                            .timeout_config(
+                                #{TimeoutConfig}::builder()
+                                    .operation_timeout(::std::time::Duration::from_millis(100))
+                                    .build(),
+                            )
+                            .send()
+                            .await
+                            .expect_err("the operation timeout override should be hit");
+                        assert!(matches!(err, #{SdkError}::TimeoutError(_)), "{err:?}");
+
+                        // The client's own timeout config is left untouched
+                        assert_eq!(
+                            #{Some}(::std::time::Duration::from_secs(600)),
+                            client.config().timeout_config().unwrap().operation_timeout(),
+                        );
+                        """,
+                        *preludeScope,
+                        "NeverClient" to CargoDependency.smithyRuntimeTestUtil(runtimeConfig).toType()
+                            .resolve("client::http::test_util::NeverClient"),
+                        "SdkError" to RuntimeType.sdkError(runtimeConfig),
+                        "TimeoutConfig" to RuntimeType.smithyTypes(runtimeConfig).resolve("timeout::TimeoutConfig"),
+                    )
+                }
+            }
+        }
+    }
 }
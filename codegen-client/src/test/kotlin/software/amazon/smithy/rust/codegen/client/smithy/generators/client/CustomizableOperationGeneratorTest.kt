@@ -8,8 +8,10 @@ package software.amazon.smithy.rust.codegen.client.smithy.generators.client
 import org.junit.jupiter.api.Test
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
@@ -58,4 +60,43 @@ class CustomizableOperationGeneratorTest {
         }
         clientIntegrationTest(model, test = test)
     }
+
+    @Test
+    fun `endpoint URL can be overridden for a single operation invocation`() {
+        val test: (ClientCodegenContext, RustCrate) -> Unit = { codegenContext, rustCrate ->
+            rustCrate.integrationTest("customizable_operation_endpoint_url") {
+                val moduleName = codegenContext.moduleUseName()
+                Attribute.TokioTest.render(this)
+                rustTemplate(
+                    """
+                    async fn endpoint_url_override() {
+                        let (http_client, req) = #{capture_request}(None);
+                        let config = $moduleName::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+
+                        let _ = client
+                            .say_hello()
+                            .customize()
+                            .endpoint_url("http://replica.localhost:5678")
+                            .send()
+                            .await;
+                        assert_eq!("http://replica.localhost:5678/", req.expect_request().uri());
+
+                        let (http_client, req) = #{capture_request}(None);
+                        let client = $moduleName::Client::from_conf(
+                            client.config().to_builder().http_client(http_client).build(),
+                        );
+                        let _ = client.say_hello().send().await;
+                        assert_eq!("http://localhost:1234/", req.expect_request().uri());
+                    }
+                    """,
+                    "capture_request" to RuntimeType.captureRequest(codegenContext.runtimeConfig),
+                )
+            }
+        }
+        clientIntegrationTest(model, test = test)
+    }
 }
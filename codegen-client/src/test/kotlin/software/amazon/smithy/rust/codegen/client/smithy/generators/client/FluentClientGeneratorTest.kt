@@ -119,6 +119,48 @@ class FluentClientGeneratorTest {
         }
     }
 
+    @Test
+    fun `operations named like client methods don't collide with them`() {
+        val model = """
+            namespace com.example
+            use aws.protocols#awsJson1_0
+
+            @awsJson1_0
+            service HelloService {
+                operations: [Shutdown],
+                version: "1"
+            }
+
+            @optionalAuth
+            operation Shutdown { input: ShutdownInput }
+            structure ShutdownInput {}
+        """.asSmithyModel()
+
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            rustCrate.integrationTest("client_method_collision") {
+                val moduleName = codegenContext.moduleUseName()
+                rustTemplate(
+                    """
+                    fn check_send<T: Send>(_: T) {}
+
+                    ##[test]
+                    fn test() {
+                        let config = $moduleName::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(#{NeverClient}::new())
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+                        check_send(client.shutdown_operation().send());
+                        check_send(client.shutdown(::std::time::Duration::from_secs(1)));
+                    }
+                    """,
+                    "NeverClient" to CargoDependency.smithyRuntimeTestUtil(codegenContext.runtimeConfig).toType()
+                        .resolve("client::http::test_util::NeverClient"),
+                )
+            }
+        }
+    }
+
     @Test
     fun `dead-code warning should not be issued when a service has no operations`() {
         val model = """
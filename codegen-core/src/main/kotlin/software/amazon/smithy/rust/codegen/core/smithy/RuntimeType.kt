@@ -321,6 +321,7 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
         fun smithyEventStream(runtimeConfig: RuntimeConfig) = CargoDependency.smithyEventStream(runtimeConfig).toType()
         fun smithyHttp(runtimeConfig: RuntimeConfig) = CargoDependency.smithyHttp(runtimeConfig).toType()
         fun smithyJson(runtimeConfig: RuntimeConfig) = CargoDependency.smithyJson(runtimeConfig).toType()
+        fun smithyObservability(runtimeConfig: RuntimeConfig) = CargoDependency.smithyObservability(runtimeConfig).toType()
         fun smithyQuery(runtimeConfig: RuntimeConfig) = CargoDependency.smithyQuery(runtimeConfig).toType()
         fun smithyRuntime(runtimeConfig: RuntimeConfig) = CargoDependency.smithyRuntime(runtimeConfig).toType()
         fun smithyRuntimeApi(runtimeConfig: RuntimeConfig) = CargoDependency.smithyRuntimeApi(runtimeConfig).toType()
@@ -406,6 +407,9 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
         fun document(runtimeConfig: RuntimeConfig): RuntimeType = smithyTypes(runtimeConfig).resolve("Document")
         fun format(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("date_time::Format")
         fun retryErrorKind(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("retry::ErrorKind")
+        fun isSensitive(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("sensitive::is_sensitive")
+        fun redactIfSensitive(runtimeConfig: RuntimeConfig) =
+            smithyTypes(runtimeConfig).resolve("sensitive::redact_if_sensitive")
         fun eventStreamReceiver(runtimeConfig: RuntimeConfig): RuntimeType =
             smithyHttp(runtimeConfig).resolve("event_stream::Receiver")
 
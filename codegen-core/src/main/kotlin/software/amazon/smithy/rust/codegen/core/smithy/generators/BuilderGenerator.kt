@@ -45,10 +45,11 @@ import software.amazon.smithy.rust.codegen.core.smithy.isOptional
 import software.amazon.smithy.rust.codegen.core.smithy.makeOptional
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrait
+import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.letIf
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 
 // TODO(https://github.com/smithy-lang/smithy-rs/issues/1401) This builder generator is only used by the client.
@@ -321,11 +322,15 @@ class BuilderGenerator(
                 rust("""let mut formatter = f.debug_struct(${builderName.dq()});""")
                 members.forEach { member ->
                     val memberName = symbolProvider.toMemberName(member)
-                    val fieldValue = member.redactIfNecessary(model, "self.$memberName")
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
-                    )
+                    if (member.shouldRedact(model)) {
+                        rust("formatter.field(${memberName.dq()}, &$REDACTION);")
+                    } else {
+                        // Members can also be marked as sensitive at runtime
+                        rust(
+                            "formatter.field(${memberName.dq()}, #T(${structureSymbol.name.dq()}, ${member.memberName.dq()}, &self.$memberName));",
+                            RuntimeType.redactIfSensitive(runtimeConfig),
+                        )
+                    }
                 }
                 writeCustomizations(customizations, BuilderSection.AdditionalDebugFields(shape, "formatter"))
                 rust("formatter.finish()")
@@ -33,9 +33,10 @@ import software.amazon.smithy.rust.codegen.core.smithy.customize.writeCustomizat
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
 import software.amazon.smithy.rust.codegen.core.smithy.renamedFrom
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
+import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.getTrait
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 
 /** StructureGenerator customization sections */
 sealed class StructureSection(name: String) : Section(name) {
@@ -118,11 +119,15 @@ open class StructureGenerator(
                 rust("""let mut formatter = f.debug_struct(${name.dq()});""")
                 members.forEach { member ->
                     val memberName = symbolProvider.toMemberName(member)
-                    val fieldValue = member.redactIfNecessary(model, "self.$memberName")
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
-                    )
+                    if (member.shouldRedact(model)) {
+                        rust("formatter.field(${memberName.dq()}, &$REDACTION);")
+                    } else {
+                        // Members can also be marked as sensitive at runtime
+                        rust(
+                            "formatter.field(${memberName.dq()}, #T(${name.dq()}, ${member.memberName.dq()}, &self.$memberName));",
+                            RuntimeType.redactIfSensitive(symbolProvider.config.runtimeConfig),
+                        )
+                    }
                 }
                 writeCustomizations(customizations, StructureSection.AdditionalDebugFields(shape, "formatter"))
                 rust("formatter.finish()")
@@ -161,8 +161,17 @@ class ErrorImplGenerator(
                     if (it.shouldRedact(model)) {
                         write("""::std::write!(f, ": {}", $REDACTION)?;""")
                     } else {
-                        ifSet(it, symbolProvider.toSymbol(it), ValueExpression.Reference("&self.message")) { field ->
-                            write("""::std::write!(f, ": {}", ${field.asRef()})?;""")
+                        // The message can also be marked as sensitive at runtime
+                        rustBlock(
+                            "if #T(${symbol.name.dq()}, ${it.memberName.dq()})",
+                            RuntimeType.isSensitive(runtimeConfig),
+                        ) {
+                            write("""::std::write!(f, ": {}", $REDACTION)?;""")
+                        }
+                        rustBlock("else") {
+                            ifSet(it, symbolProvider.toSymbol(it), ValueExpression.Reference("&self.message")) { field ->
+                                write("""::std::write!(f, ": {}", ${field.asRef()})?;""")
+                            }
                         }
                     }
                 }
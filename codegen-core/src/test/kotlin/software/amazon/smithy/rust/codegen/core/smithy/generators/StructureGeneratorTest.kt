@@ -167,6 +167,29 @@ class StructureGeneratorTest {
         }.compileAndTest()
     }
 
+    @Test
+    fun `redact members that are marked as sensitive at runtime`() {
+        val provider = testSymbolProvider(model, rustReservedWordConfig = rustReservedWordConfig)
+        TestWorkspace.testProject().unitTest {
+            structureGenerator(model, provider, this, credentials).render()
+
+            this.unitTest(
+                "runtime_sensitive_fields_redacted",
+                """
+                aws_smithy_types::sensitive::mark_sensitive(
+                    aws_smithy_types::sensitive::MemberPath::new("Credentials", "username"),
+                );
+                let creds = Credentials {
+                    username: Some("don't leak me either".to_owned()),
+                    password: Some("don't leak me".to_owned()),
+                    secret_key: Some("don't leak me".to_owned())
+                };
+                assert_eq!(format!("{:?}", creds), "Credentials { username: \"*** Sensitive Data Redacted ***\", password: \"*** Sensitive Data Redacted ***\", secret_key: \"*** Sensitive Data Redacted ***\" }");
+                """,
+            )
+        }.compileAndTest()
+    }
+
     @Test
     fun `generate a custom debug implementation when the sensitive trait is applied to the struct`() {
         val provider = testSymbolProvider(model, rustReservedWordConfig = rustReservedWordConfig)
@@ -0,0 +1,232 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Provides [`ConcurrentFutures`] for running an operation for many items with bounded concurrency.
+
+use crate::future::pagination_stream::fn_stream::FnStream;
+use crate::future::pagination_stream::PaginationStream;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Poll;
+
+type ShouldRetry<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// Runs an operation for each item of an iterator, with a limit on how many run at once.
+///
+/// Results are yielded as a [`PaginationStream`] in the order that the operations complete, which
+/// isn't necessarily the order of the items. Items are only taken from the iterator when there's
+/// room for another operation to run, so the iterator can be arbitrarily long.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() {
+/// use aws_smithy_async::future::concurrent::ConcurrentFutures;
+///
+/// async fn fetch(key: &str) -> Result<usize, std::io::Error> {
+///     // make a request...
+/// #   Ok(key.len())
+/// }
+///
+/// let keys = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+/// let mut results = ConcurrentFutures::new(2)
+///     .retry_if(3, |result: &Result<usize, std::io::Error>| result.is_err())
+///     .send_all(keys, |key| {
+///         let key = key.clone();
+///         async move { fetch(&key).await }
+///     });
+/// while let Some(result) = results.next().await {
+///     // handle `result`
+/// }
+/// # }
+/// ```
+pub struct ConcurrentFutures<T> {
+    limit: usize,
+    max_attempts: u32,
+    should_retry: Option<ShouldRetry<T>>,
+}
+
+impl<T> fmt::Debug for ConcurrentFutures<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrentFutures")
+            .field("limit", &self.limit)
+            .field("max_attempts", &self.max_attempts)
+            .finish()
+    }
+}
+
+impl<T> ConcurrentFutures<T>
+where
+    T: Send + 'static,
+{
+    /// Creates a new `ConcurrentFutures` that runs at most `limit` operations at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn new(limit: usize) -> Self {
+        assert!(limit > 0, "the concurrency limit must be non-zero");
+        Self {
+            limit,
+            max_attempts: 1,
+            should_retry: None,
+        }
+    }
+
+    /// Runs the operation for an item again when `should_retry` returns true for its result, up to
+    /// `max_attempts` times in total.
+    ///
+    /// Retries are made immediately, and take the place of the item they retry, so they don't
+    /// count against the concurrency limit more than once. Note that clients will usually already
+    /// retry transient failures, so this is for retrying failures that the client doesn't.
+    pub fn retry_if(
+        mut self,
+        max_attempts: u32,
+        should_retry: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.max_attempts = max_attempts;
+        self.should_retry = Some(Arc::new(should_retry));
+        self
+    }
+
+    /// Runs `operation` for each item in `items`, yielding the results as they complete.
+    ///
+    /// The operations only make progress while the returned stream is being polled.
+    pub fn send_all<I, F, Fut>(self, items: I, operation: F) -> PaginationStream<T>
+    where
+        I: IntoIterator,
+        I::IntoIter: Send + 'static,
+        I::Item: Send + 'static,
+        F: Fn(&I::Item) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let operation = Arc::new(operation);
+        let mut items = items.into_iter();
+        PaginationStream::new(FnStream::new(move |tx| {
+            Box::pin(async move {
+                let mut running: Vec<Pin<Box<dyn Future<Output = T> + Send>>> =
+                    Vec::with_capacity(self.limit);
+                loop {
+                    while running.len() < self.limit {
+                        let Some(item) = items.next() else {
+                            break;
+                        };
+                        running.push(Box::pin(run_with_retries(
+                            item,
+                            operation.clone(),
+                            self.max_attempts,
+                            self.should_retry.clone(),
+                        )));
+                    }
+                    if running.is_empty() {
+                        break;
+                    }
+                    let output = poll_fn(|cx| {
+                        for index in 0..running.len() {
+                            if let Poll::Ready(output) = running[index].as_mut().poll(cx) {
+                                drop(running.swap_remove(index));
+                                return Poll::Ready(output);
+                            }
+                        }
+                        Poll::Pending
+                    })
+                    .await;
+                    if tx.send(output).await.is_err() {
+                        // The stream was dropped, so nobody is interested in the remaining results
+                        break;
+                    }
+                }
+            }) as Pin<Box<dyn Future<Output = ()> + Send>>
+        }))
+    }
+}
+
+async fn run_with_retries<Item, F, Fut, T>(
+    item: Item,
+    operation: Arc<F>,
+    max_attempts: u32,
+    should_retry: Option<ShouldRetry<T>>,
+) -> T
+where
+    F: Fn(&Item) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut attempt = 1;
+    loop {
+        let output = operation(&item).await;
+        match &should_retry {
+            Some(should_retry) if attempt < max_attempts && should_retry(&output) => attempt += 1,
+            _ => return output,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_concurrency() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let results = ConcurrentFutures::new(3)
+            .send_all(0..10u64, {
+                let (running, max_running) = (running.clone(), max_running.clone());
+                move |&item| {
+                    let (running, max_running) = (running.clone(), max_running.clone());
+                    async move {
+                        let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_running.fetch_max(now_running, Ordering::SeqCst);
+                        // Later items finish first
+                        tokio::time::sleep(Duration::from_secs(10 - item)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        item
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(3, max_running.load(Ordering::SeqCst));
+        assert_eq!(vec![2, 1, 0], results[..3]);
+        let mut results = results;
+        results.sort();
+        assert_eq!((0..10).collect::<Vec<_>>(), results);
+    }
+
+    #[tokio::test]
+    async fn retries_items() {
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let results = ConcurrentFutures::new(2)
+            .retry_if(3, |result: &Result<&str, &str>| result.is_err())
+            .send_all(["ok", "flaky", "broken"], {
+                let attempts = attempts.clone();
+                move |&item| {
+                    let mut attempts = attempts.lock().unwrap();
+                    attempts.push(item);
+                    let flaky_attempts = attempts.iter().filter(|&&a| a == "flaky").count();
+                    let result = match item {
+                        "flaky" if flaky_attempts > 1 => Ok(item),
+                        "ok" => Ok(item),
+                        _ => Err(item),
+                    };
+                    async move { result }
+                }
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(vec![Ok("ok"), Ok("flaky"), Err("broken")], results);
+        let attempts = attempts.lock().unwrap();
+        assert_eq!(1, attempts.iter().filter(|&&a| a == "ok").count());
+        assert_eq!(2, attempts.iter().filter(|&&a| a == "flaky").count());
+        assert_eq!(3, attempts.iter().filter(|&&a| a == "broken").count());
+    }
+}
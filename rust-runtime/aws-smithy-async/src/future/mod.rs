@@ -8,6 +8,7 @@
 use futures_util::Future;
 use std::pin::Pin;
 
+pub mod concurrent;
 pub mod never;
 pub mod now_or_later;
 pub mod pagination_stream;
@@ -14,7 +14,17 @@
 //! Future utilities and runtime-agnostic abstractions for smithy-rs.
 //!
 //! Async runtime specific code is abstracted behind async traits, and implementations are
-//! provided via feature flag. For now, only Tokio runtime implementations are provided.
+//! provided via feature flag. Implementations are provided for Tokio (`rt-tokio`), async-std
+//! (`rt-async-std`), smol (`rt-smol`), and for `wasm32-unknown-unknown` environments that provide
+//! JavaScript timers, such as browsers and edge runtimes (`rt-wasm`).
+
+#[cfg(all(
+    feature = "rt-wasm",
+    target_arch = "wasm32",
+    target_os = "unknown",
+    target_feature = "atomics"
+))]
+compile_error!("the `rt-wasm` feature doesn't support multi-threaded WebAssembly");
 
 pub mod future;
 pub mod rt;
@@ -68,15 +68,20 @@ impl AsyncSleep for SharedAsyncSleep {
     }
 }
 
-#[cfg(feature = "rt-tokio")]
-/// Returns a default sleep implementation based on the features enabled
-pub fn default_async_sleep() -> Option<SharedAsyncSleep> {
-    Some(SharedAsyncSleep::from(sleep_tokio()))
-}
-
-#[cfg(not(feature = "rt-tokio"))]
 /// Returns a default sleep implementation based on the features enabled
+///
+/// When more than one runtime feature is enabled, then the first of the following is used:
+/// `rt-wasm` (only on `wasm32-unknown-unknown`), `rt-tokio`, `rt-async-std`, `rt-smol`.
+#[allow(unreachable_code)]
 pub fn default_async_sleep() -> Option<SharedAsyncSleep> {
+    #[cfg(all(feature = "rt-wasm", target_arch = "wasm32", target_os = "unknown"))]
+    return Some(SharedAsyncSleep::new(WasmSleep::new()));
+    #[cfg(feature = "rt-tokio")]
+    return Some(SharedAsyncSleep::from(sleep_tokio()));
+    #[cfg(feature = "rt-async-std")]
+    return Some(SharedAsyncSleep::new(AsyncStdSleep::new()));
+    #[cfg(feature = "rt-smol")]
+    return Some(SharedAsyncSleep::new(SmolSleep::new()));
     None
 }
 
@@ -133,3 +138,163 @@ impl AsyncSleep for TokioSleep {
 fn sleep_tokio() -> Arc<dyn AsyncSleep> {
     Arc::new(TokioSleep::new())
 }
+
+/// Implementation of [`AsyncSleep`] for async-std.
+#[non_exhaustive]
+#[cfg(feature = "rt-async-std")]
+#[derive(Debug, Default)]
+pub struct AsyncStdSleep;
+
+#[cfg(feature = "rt-async-std")]
+impl AsyncStdSleep {
+    /// Create a new [`AsyncSleep`] implementation using async-std's timers
+    pub fn new() -> AsyncStdSleep {
+        Default::default()
+    }
+}
+
+#[cfg(feature = "rt-async-std")]
+impl AsyncSleep for AsyncStdSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Sleep::new(async_std::task::sleep(duration))
+    }
+}
+
+/// Implementation of [`AsyncSleep`] for smol.
+#[non_exhaustive]
+#[cfg(feature = "rt-smol")]
+#[derive(Debug, Default)]
+pub struct SmolSleep;
+
+#[cfg(feature = "rt-smol")]
+impl SmolSleep {
+    /// Create a new [`AsyncSleep`] implementation using smol's timers
+    pub fn new() -> SmolSleep {
+        Default::default()
+    }
+}
+
+#[cfg(feature = "rt-smol")]
+impl AsyncSleep for SmolSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let timer = smol::Timer::after(duration);
+        Sleep::new(async move {
+            timer.await;
+        })
+    }
+}
+
+/// Implementation of [`AsyncSleep`] for `wasm32-unknown-unknown` based on JavaScript's `setTimeout`.
+///
+/// This works anywhere a global `setTimeout` function is available, which includes browsers,
+/// web workers, and most edge runtimes.
+#[non_exhaustive]
+#[cfg(all(feature = "rt-wasm", target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Debug, Default)]
+pub struct WasmSleep;
+
+#[cfg(all(feature = "rt-wasm", target_arch = "wasm32", target_os = "unknown"))]
+impl WasmSleep {
+    /// Create a new [`AsyncSleep`] implementation using JavaScript timers
+    pub fn new() -> WasmSleep {
+        Default::default()
+    }
+}
+
+#[cfg(all(feature = "rt-wasm", target_arch = "wasm32", target_os = "unknown"))]
+impl AsyncSleep for WasmSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Sleep::new(wasm::Timeout::new(duration))
+    }
+}
+
+#[cfg(all(feature = "rt-wasm", target_arch = "wasm32", target_os = "unknown"))]
+mod wasm {
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::task::{Context, Poll, Waker};
+    use std::time::Duration;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::prelude::wasm_bindgen;
+    use wasm_bindgen::{JsCast, JsValue};
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = setTimeout)]
+        fn set_timeout(handler: &js_sys::Function, timeout: i32) -> JsValue;
+
+        #[wasm_bindgen(js_name = clearTimeout)]
+        fn clear_timeout(handle: &JsValue);
+    }
+
+    #[derive(Default)]
+    struct State {
+        fired: bool,
+        waker: Option<Waker>,
+    }
+
+    /// A future that completes when a JavaScript timer fires.
+    pub(super) struct Timeout {
+        state: Rc<RefCell<State>>,
+        handle: JsValue,
+        // Kept alive until the timer is cleared, since JavaScript holds a reference to it
+        _callback: Closure<dyn FnMut()>,
+    }
+
+    // SAFETY: `wasm32-unknown-unknown` without the `atomics` target feature (which is rejected with a
+    // compile error in lib.rs) is single threaded, so the `Rc` and JavaScript values can never be
+    // accessed from more than one thread.
+    unsafe impl Send for Timeout {}
+    unsafe impl Sync for Timeout {}
+
+    impl Timeout {
+        pub(super) fn new(duration: Duration) -> Self {
+            let state = Rc::new(RefCell::new(State::default()));
+            let callback = Closure::wrap(Box::new({
+                let state = state.clone();
+                move || {
+                    let waker = {
+                        let mut state = state.borrow_mut();
+                        state.fired = true;
+                        state.waker.take()
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }) as Box<dyn FnMut()>);
+            // Round up so that the timer never fires early. `setTimeout` takes a signed 32-bit delay,
+            // and fires immediately if given anything larger, so clamp to that.
+            let millis = (duration.as_nanos() + 999_999) / 1_000_000;
+            let millis = millis.min(i32::MAX as u128) as i32;
+            let handle = set_timeout(callback.as_ref().unchecked_ref(), millis);
+            Self {
+                state,
+                handle,
+                _callback: callback,
+            }
+        }
+    }
+
+    impl Future for Timeout {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let mut state = self.state.borrow_mut();
+            if state.fired {
+                Poll::Ready(())
+            } else {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    impl Drop for Timeout {
+        fn drop(&mut self) {
+            clear_timeout(&self.handle);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Provides a [`Spawn`] trait for running background tasks and blocking work, and implementations
+//! of `Spawn` for different async runtimes.
+
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// A boxed future that can be spawned onto an async runtime.
+pub type SpawnedFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A boxed function that can be run on a thread where blocking is acceptable.
+pub type BlockingTask = Box<dyn FnOnce() + Send + 'static>;
+
+/// Trait for spawning tasks onto an async runtime.
+pub trait Spawn: Debug + Send + Sync {
+    /// Spawns a future to run in the background to completion.
+    fn spawn(&self, future: SpawnedFuture);
+
+    /// Runs a blocking function on a thread where blocking is acceptable.
+    fn spawn_blocking(&self, task: BlockingTask);
+}
+
+impl<T> Spawn for Arc<T>
+where
+    T: Spawn + ?Sized,
+{
+    fn spawn(&self, future: SpawnedFuture) {
+        T::spawn(self, future)
+    }
+
+    fn spawn_blocking(&self, task: BlockingTask) {
+        T::spawn_blocking(self, task)
+    }
+}
+
+/// Wrapper type for sharable `Spawn`
+#[derive(Clone, Debug)]
+pub struct SharedSpawn(Arc<dyn Spawn>);
+
+impl SharedSpawn {
+    /// Create a new `SharedSpawn` from `Spawn`
+    pub fn new(spawn: impl Spawn + 'static) -> Self {
+        Self(Arc::new(spawn))
+    }
+
+    /// Runs a blocking function on a thread where blocking is acceptable, and returns its result.
+    ///
+    /// The returned future resolves to an error if the function panics.
+    pub fn run_blocking<F, T>(&self, f: F) -> impl Future<Output = Result<T, BlockingTaskError>>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.0.spawn_blocking(Box::new(move || {
+            // The receiver may have been dropped if the caller is no longer interested
+            let _ = tx.send(f());
+        }));
+        async move { rx.await.map_err(|_| BlockingTaskError { _private: () }) }
+    }
+}
+
+impl Spawn for SharedSpawn {
+    fn spawn(&self, future: SpawnedFuture) {
+        self.0.spawn(future)
+    }
+
+    fn spawn_blocking(&self, task: BlockingTask) {
+        self.0.spawn_blocking(task)
+    }
+}
+
+/// Error returned by [`SharedSpawn::run_blocking`] when the blocking function didn't complete.
+#[derive(Debug)]
+pub struct BlockingTaskError {
+    _private: (),
+}
+
+impl fmt::Display for BlockingTaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the blocking task panicked or was cancelled")
+    }
+}
+
+impl std::error::Error for BlockingTaskError {}
+
+/// Returns a default spawn implementation based on the features enabled
+///
+/// When more than one runtime feature is enabled, then the first of the following is used:
+/// `rt-tokio`, `rt-async-std`, `rt-smol`.
+#[allow(unreachable_code)]
+pub fn default_spawn() -> Option<SharedSpawn> {
+    #[cfg(feature = "rt-tokio")]
+    return Some(SharedSpawn::new(TokioSpawn::new()));
+    #[cfg(feature = "rt-async-std")]
+    return Some(SharedSpawn::new(AsyncStdSpawn::new()));
+    #[cfg(feature = "rt-smol")]
+    return Some(SharedSpawn::new(SmolSpawn::new()));
+    None
+}
+
+/// Implementation of [`Spawn`] for Tokio.
+///
+/// Tasks are spawned onto the Tokio runtime that's current when they're spawned.
+#[non_exhaustive]
+#[cfg(feature = "rt-tokio")]
+#[derive(Debug, Default)]
+pub struct TokioSpawn;
+
+#[cfg(feature = "rt-tokio")]
+impl TokioSpawn {
+    /// Create a new [`Spawn`] implementation for Tokio
+    pub fn new() -> TokioSpawn {
+        Default::default()
+    }
+}
+
+#[cfg(feature = "rt-tokio")]
+impl Spawn for TokioSpawn {
+    fn spawn(&self, future: SpawnedFuture) {
+        tokio::spawn(future);
+    }
+
+    fn spawn_blocking(&self, task: BlockingTask) {
+        tokio::task::spawn_blocking(task);
+    }
+}
+
+/// Implementation of [`Spawn`] for async-std.
+#[non_exhaustive]
+#[cfg(feature = "rt-async-std")]
+#[derive(Debug, Default)]
+pub struct AsyncStdSpawn;
+
+#[cfg(feature = "rt-async-std")]
+impl AsyncStdSpawn {
+    /// Create a new [`Spawn`] implementation for async-std
+    pub fn new() -> AsyncStdSpawn {
+        Default::default()
+    }
+}
+
+#[cfg(feature = "rt-async-std")]
+impl Spawn for AsyncStdSpawn {
+    fn spawn(&self, future: SpawnedFuture) {
+        async_std::task::spawn(future);
+    }
+
+    fn spawn_blocking(&self, task: BlockingTask) {
+        async_std::task::spawn_blocking(task);
+    }
+}
+
+/// Implementation of [`Spawn`] for smol.
+///
+/// Tasks are spawned onto smol's global executor.
+#[non_exhaustive]
+#[cfg(feature = "rt-smol")]
+#[derive(Debug, Default)]
+pub struct SmolSpawn;
+
+#[cfg(feature = "rt-smol")]
+impl SmolSpawn {
+    /// Create a new [`Spawn`] implementation for smol
+    pub fn new() -> SmolSpawn {
+        Default::default()
+    }
+}
+
+#[cfg(feature = "rt-smol")]
+impl Spawn for SmolSpawn {
+    fn spawn(&self, future: SpawnedFuture) {
+        smol::spawn(future).detach();
+    }
+
+    fn spawn_blocking(&self, task: BlockingTask) {
+        smol::unblock(task).detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn tokio_run_blocking() {
+        let spawn = SharedSpawn::new(TokioSpawn::new());
+        assert_eq!(4, spawn.run_blocking(|| 2 + 2).await.unwrap());
+        assert!(spawn
+            .run_blocking(|| panic!("this is expected"))
+            .await
+            .is_err());
+    }
+
+    #[cfg(feature = "rt-async-std")]
+    #[test]
+    fn async_std_run_blocking() {
+        let spawn = SharedSpawn::new(AsyncStdSpawn::new());
+        let result = async_std::task::block_on(spawn.run_blocking(|| 2 + 2));
+        assert_eq!(4, result.unwrap());
+    }
+
+    #[cfg(feature = "rt-smol")]
+    #[test]
+    fn smol_run_blocking() {
+        let spawn = SharedSpawn::new(SmolSpawn::new());
+        let result = smol::block_on(spawn.run_blocking(|| 2 + 2));
+        assert_eq!(4, result.unwrap());
+    }
+}
@@ -0,0 +1,304 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A coarse [`AsyncSleep`] implementation backed by a shared hashed timer wheel.
+//!
+//! Clients making tens of thousands of requests per second create a timer for every attempt
+//! timeout and retry backoff, most of which are cancelled before they fire. [`TimerWheelSleep`]
+//! trades timer precision for lower overhead: every sleep in the same tick shares a single
+//! wakeup, and only one underlying timer is ever active at a time.
+
+use crate::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+use crate::rt::spawn::{SharedSpawn, Spawn};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+const SLOTS: usize = 512;
+
+struct Entry {
+    id: u64,
+    deadline: u64,
+    waker: Waker,
+}
+
+struct State {
+    current_tick: u64,
+    next_id: u64,
+    slots: Vec<Vec<Entry>>,
+    pending: usize,
+    driver_running: bool,
+}
+
+struct Wheel {
+    resolution: Duration,
+    sleep_impl: SharedAsyncSleep,
+    spawn: SharedSpawn,
+    state: Mutex<State>,
+}
+
+impl Wheel {
+    fn current_tick(&self) -> u64 {
+        self.state.lock().unwrap().current_tick
+    }
+
+    /// Advances the wheel by one tick, waking the sleeps that are due. Returns false once there
+    /// are no more sleeps to drive.
+    fn tick(&self) -> bool {
+        let (wakers, keep_running) = {
+            let mut state = self.state.lock().unwrap();
+            state.current_tick += 1;
+            let tick = state.current_tick;
+            let slot = &mut state.slots[(tick % SLOTS as u64) as usize];
+            let mut wakers = Vec::new();
+            let mut index = 0;
+            while index < slot.len() {
+                if slot[index].deadline <= tick {
+                    wakers.push(slot.swap_remove(index).waker);
+                } else {
+                    index += 1;
+                }
+            }
+            state.pending -= wakers.len();
+            state.driver_running = state.pending > 0;
+            (wakers, state.driver_running)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+        keep_running
+    }
+}
+
+/// An [`AsyncSleep`] implementation that rounds sleeps up to a configurable resolution so they can
+/// share wakeups.
+///
+/// The wheel is driven by a single background task that's spawned when there are sleeps pending,
+/// and stops when there aren't any. That task uses the given `AsyncSleep` to wait for each tick.
+/// Sleeps complete up to one resolution later than requested (plus any delay in ticking), but
+/// never earlier. A sleep that's created while the wheel is idle starts counting when it's first
+/// polled.
+///
+/// Clones share the same wheel.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[cfg(feature = "rt-tokio")]
+/// # fn example() {
+/// use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
+/// use aws_smithy_async::rt::spawn::TokioSpawn;
+/// use aws_smithy_async::rt::timer_wheel::TimerWheelSleep;
+/// use std::time::Duration;
+///
+/// let sleep_impl = SharedAsyncSleep::new(TimerWheelSleep::new(
+///     Duration::from_millis(10),
+///     TokioSleep::new(),
+///     TokioSpawn::new(),
+/// ));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TimerWheelSleep {
+    wheel: Arc<Wheel>,
+}
+
+impl fmt::Debug for TimerWheelSleep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerWheelSleep")
+            .field("resolution", &self.wheel.resolution)
+            .finish()
+    }
+}
+
+impl TimerWheelSleep {
+    /// Creates a new timer wheel that ticks every `resolution`.
+    ///
+    /// `sleep_impl` is used to wait for each tick, and `spawn` is used to run the task that
+    /// drives the wheel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resolution` is zero.
+    pub fn new(
+        resolution: Duration,
+        sleep_impl: impl AsyncSleep + 'static,
+        spawn: impl Spawn + 'static,
+    ) -> Self {
+        assert!(
+            !resolution.is_zero(),
+            "the timer resolution must be non-zero"
+        );
+        Self {
+            wheel: Arc::new(Wheel {
+                resolution,
+                sleep_impl: SharedAsyncSleep::new(sleep_impl),
+                spawn: SharedSpawn::new(spawn),
+                state: Mutex::new(State {
+                    current_tick: 0,
+                    next_id: 0,
+                    slots: (0..SLOTS).map(|_| Vec::new()).collect(),
+                    pending: 0,
+                    driver_running: false,
+                }),
+            }),
+        }
+    }
+
+    /// Returns the resolution of this timer wheel.
+    pub fn resolution(&self) -> Duration {
+        self.wheel.resolution
+    }
+}
+
+impl AsyncSleep for TimerWheelSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let ticks = if duration.is_zero() {
+            0
+        } else {
+            // Round up, and add a tick since the current tick is already partially over
+            let (duration, resolution) = (duration.as_nanos(), self.wheel.resolution.as_nanos());
+            duration / resolution + u128::from(duration % resolution != 0) + 1
+        };
+        Sleep::new(WheelSleep {
+            wheel: self.wheel.clone(),
+            deadline: self
+                .wheel
+                .current_tick()
+                .saturating_add(u64::try_from(ticks).unwrap_or(u64::MAX)),
+            registered: None,
+        })
+    }
+}
+
+struct WheelSleep {
+    wheel: Arc<Wheel>,
+    deadline: u64,
+    registered: Option<u64>,
+}
+
+impl Future for WheelSleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let wheel = self.wheel.clone();
+        let mut state = wheel.state.lock().unwrap();
+        if state.current_tick >= self.deadline {
+            self.registered = None;
+            return Poll::Ready(());
+        }
+        let slot = &mut state.slots[(self.deadline % SLOTS as u64) as usize];
+        if let Some(id) = self.registered {
+            if let Some(entry) = slot.iter_mut().find(|entry| entry.id == id) {
+                if !entry.waker.will_wake(cx.waker()) {
+                    entry.waker = cx.waker().clone();
+                }
+                return Poll::Pending;
+            }
+        }
+        let id = state.next_id;
+        state.next_id += 1;
+        state.slots[(self.deadline % SLOTS as u64) as usize].push(Entry {
+            id,
+            deadline: self.deadline,
+            waker: cx.waker().clone(),
+        });
+        state.pending += 1;
+        self.registered = Some(id);
+        if !state.driver_running {
+            state.driver_running = true;
+            let driver = wheel.clone();
+            wheel.spawn.spawn(Box::pin(async move {
+                loop {
+                    driver.sleep_impl.sleep(driver.resolution).await;
+                    if !driver.tick() {
+                        break;
+                    }
+                }
+            }));
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for WheelSleep {
+    fn drop(&mut self) {
+        // Remove cancelled sleeps so they don't keep the wheel running
+        if let Some(id) = self.registered {
+            let mut state = self.wheel.state.lock().unwrap();
+            let slot = &mut state.slots[(self.deadline % SLOTS as u64) as usize];
+            if let Some(index) = slot.iter().position(|entry| entry.id == id) {
+                slot.swap_remove(index);
+                state.pending -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rt-tokio"))]
+mod tests {
+    use super::*;
+    use crate::rt::sleep::TokioSleep;
+    use crate::rt::spawn::TokioSpawn;
+    use tokio::time::Instant;
+
+    fn wheel() -> TimerWheelSleep {
+        TimerWheelSleep::new(
+            Duration::from_millis(10),
+            TokioSleep::new(),
+            TokioSpawn::new(),
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sleeps_are_rounded_up() {
+        let wheel = wheel();
+        let start = Instant::now();
+        wheel.sleep(Duration::from_millis(1001)).await;
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(1001) && elapsed <= Duration::from_millis(1020),
+            "{elapsed:?}"
+        );
+
+        let start = Instant::now();
+        wheel.sleep(Duration::ZERO).await;
+        assert_eq!(Duration::ZERO, start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn many_sleeps_share_the_wheel() {
+        let wheel = wheel();
+        let start = Instant::now();
+        let sleeps: Vec<_> = (1..=100u64)
+            .map(|i| tokio::spawn(wheel.sleep(Duration::from_millis(i * 20))))
+            .collect();
+        for sleep in sleeps {
+            sleep.await.unwrap();
+        }
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= Duration::from_secs(2) && elapsed <= Duration::from_millis(2020),
+            "{elapsed:?}"
+        );
+        assert_eq!(0, wheel.wheel.state.lock().unwrap().pending);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_sleeps_stop_the_driver() {
+        let wheel = wheel();
+        let sleep = wheel.sleep(Duration::from_secs(60));
+        assert!(tokio::time::timeout(Duration::from_millis(100), sleep)
+            .await
+            .is_err());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let state = wheel.wheel.state.lock().unwrap();
+        assert_eq!(0, state.pending);
+        assert!(!state.driver_running);
+    }
+}
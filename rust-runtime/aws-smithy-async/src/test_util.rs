@@ -5,7 +5,11 @@
 
 //! Test utilities for time and sleep
 
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
 use std::time::{Duration, SystemTime};
 
 use tokio::sync::oneshot;
@@ -307,6 +311,237 @@ impl From<ManualTimeSource> for SharedTimeSource {
     }
 }
 
+/// A clock that only advances when the future it drives is blocked, and then jumps straight to the
+/// end of the next sleep.
+///
+/// This is similar to Tokio's paused time with auto-advance, but works with any async runtime.
+/// `AutoAdvancingClock` implements both [`TimeSource`] and [`AsyncSleep`], and sleeps created from it
+/// only complete once the clock is advanced past their end. Futures that should experience time
+/// passing are driven with [`AutoAdvancingClock::run`]. Whenever the driven future can't make progress,
+/// the clock advances to the end of the earliest pending sleep. This lets tests of retries, backoff,
+/// and waiters run instantly while still observing realistic timestamps.
+///
+/// The clock can't tell whether the future is waiting on something other than a sleep (such as a
+/// response from another task), so it advances time in that case too.
+///
+/// # Examples
+///
+/// ```rust
+/// # async {
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use aws_smithy_async::rt::sleep::AsyncSleep;
+/// use aws_smithy_async::test_util::AutoAdvancingClock;
+/// use aws_smithy_async::time::TimeSource;
+///
+/// let clock = AutoAdvancingClock::new(UNIX_EPOCH);
+/// clock
+///     .run(async {
+///         clock.sleep(Duration::from_secs(60)).await;
+///     })
+///     .await;
+/// assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(60));
+/// # };
+/// ```
+#[derive(Clone, Debug)]
+pub struct AutoAdvancingClock {
+    state: Arc<Mutex<ClockState>>,
+}
+
+#[derive(Debug)]
+struct ClockState {
+    now: SystemTime,
+    next_id: u64,
+    pending: Vec<PendingSleep>,
+    log: Vec<Duration>,
+}
+
+#[derive(Debug)]
+struct PendingSleep {
+    id: u64,
+    until: SystemTime,
+    waker: Option<Waker>,
+}
+
+impl AutoAdvancingClock {
+    /// Creates a new `AutoAdvancingClock` starting at `start_time`.
+    pub fn new(start_time: SystemTime) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ClockState {
+                now: start_time,
+                next_id: 0,
+                pending: Vec::new(),
+                log: Vec::new(),
+            })),
+        }
+    }
+
+    /// Drives `future` to completion, advancing the clock whenever it's blocked.
+    pub fn run<F: Future>(&self, future: F) -> AutoAdvance<F> {
+        AutoAdvance {
+            clock: self.clone(),
+            future,
+        }
+    }
+
+    /// Advances the clock to the end of the earliest pending sleep, and wakes every sleep that ends by then.
+    ///
+    /// Returns `false` if there are no pending sleeps, in which case the clock isn't changed.
+    pub fn advance_to_next_sleep(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let Some(next) = state.pending.iter().map(|sleep| sleep.until).min() else {
+            return false;
+        };
+        state.now = state.now.max(next);
+        let now = state.now;
+        state
+            .pending
+            .iter_mut()
+            .filter(|sleep| sleep.until <= now)
+            .filter_map(|sleep| sleep.waker.take())
+            .for_each(Waker::wake);
+        true
+    }
+
+    /// Returns the durations of every sleep that has been started with this clock.
+    pub fn logs(&self) -> Vec<Duration> {
+        self.state.lock().unwrap().log.clone()
+    }
+
+    fn poll_sleep(&self, id: u64, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        let index = state
+            .pending
+            .iter()
+            .position(|sleep| sleep.id == id)
+            .expect("pending sleeps are only removed when they complete or are dropped");
+        if state.pending[index].until <= now {
+            state.pending.swap_remove(index);
+            Poll::Ready(())
+        } else {
+            state.pending[index].waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn cancel_sleep(&self, id: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.pending.retain(|sleep| sleep.id != id);
+    }
+}
+
+impl TimeSource for AutoAdvancingClock {
+    fn now(&self) -> SystemTime {
+        self.state.lock().unwrap().now
+    }
+}
+
+impl AsyncSleep for AutoAdvancingClock {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let id = {
+            let mut state = self.state.lock().unwrap();
+            let id = state.next_id;
+            state.next_id += 1;
+            let until = state.now + duration;
+            state.log.push(duration);
+            state.pending.push(PendingSleep {
+                id,
+                until,
+                waker: None,
+            });
+            id
+        };
+        Sleep::new(ClockSleep {
+            clock: self.clone(),
+            id,
+            done: false,
+        })
+    }
+}
+
+impl From<AutoAdvancingClock> for SharedTimeSource {
+    fn from(value: AutoAdvancingClock) -> Self {
+        SharedTimeSource::new(value)
+    }
+}
+
+struct ClockSleep {
+    clock: AutoAdvancingClock,
+    id: u64,
+    done: bool,
+}
+
+impl Future for ClockSleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let result = self.clock.poll_sleep(self.id, cx);
+        self.done = result.is_ready();
+        result
+    }
+}
+
+impl Drop for ClockSleep {
+    fn drop(&mut self) {
+        // Sleeps that are dropped early (such as a timeout that didn't fire) shouldn't hold the clock back
+        if !self.done {
+            self.clock.cancel_sleep(self.id);
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Future returned by [`AutoAdvancingClock::run`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AutoAdvance<F> {
+        clock: AutoAdvancingClock,
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for AutoAdvance<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let woken = Arc::new(WakeFlag {
+                woken: AtomicBool::new(false),
+                inner: cx.waker().clone(),
+            });
+            let waker = Waker::from(woken.clone());
+            if let Poll::Ready(output) = this.future.as_mut().poll(&mut Context::from_waker(&waker))
+            {
+                return Poll::Ready(output);
+            }
+            if woken.woken.load(Ordering::SeqCst) {
+                // The future can make progress on its own, so give other tasks a chance to run first
+                return Poll::Pending;
+            }
+            if !this.clock.advance_to_next_sleep() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+struct WakeFlag {
+    woken: AtomicBool,
+    inner: Waker,
+}
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::SeqCst);
+        self.inner.wake_by_ref();
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::rt::sleep::AsyncSleep;
@@ -351,4 +586,43 @@ mod test {
             .expect("no timeout")
             .expect("successful completion");
     }
+
+    #[tokio::test]
+    async fn auto_advancing_clock() {
+        use crate::future::timeout::Timeout;
+        use crate::test_util::AutoAdvancingClock;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let clock = AutoAdvancingClock::new(UNIX_EPOCH);
+        let result = clock
+            .run(async {
+                // Exponential backoff between three attempts
+                for backoff in [1, 2, 4] {
+                    clock.sleep(Duration::from_secs(backoff)).await;
+                }
+                // Concurrent sleeps only take as long as the longest one
+                tokio::join!(
+                    clock.sleep(Duration::from_secs(10)),
+                    clock.sleep(Duration::from_secs(5))
+                );
+                // A timeout that doesn't fire doesn't hold the clock back
+                Timeout::new(
+                    clock.sleep(Duration::from_secs(1)),
+                    clock.sleep(Duration::from_secs(3600)),
+                )
+                .await
+                .expect("no timeout");
+                // And one that does fire advances the clock to when it fires
+                Timeout::new(
+                    crate::future::never::Never::new(),
+                    clock.sleep(Duration::from_secs(30)),
+                )
+                .await
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(48), clock.now());
+        assert_eq!(8, clock.logs().len());
+    }
 }
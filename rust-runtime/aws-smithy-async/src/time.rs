@@ -15,6 +15,9 @@ pub trait TimeSource: Debug + Send + Sync {
 }
 
 /// Time source that delegates to [`SystemTime::now`]
+///
+/// `SystemTime::now` isn't supported on `wasm32-unknown-unknown`, so when the `rt-wasm` feature is
+/// enabled, this uses JavaScript's `Date.now()` on that target instead.
 #[non_exhaustive]
 #[derive(Debug, Default)]
 pub struct SystemTimeSource;
@@ -27,11 +30,18 @@ impl SystemTimeSource {
 }
 
 impl TimeSource for SystemTimeSource {
+    #[cfg(not(all(feature = "rt-wasm", target_arch = "wasm32", target_os = "unknown")))]
     fn now(&self) -> SystemTime {
         // this is the one OK usage
         #[allow(clippy::disallowed_methods)]
         SystemTime::now()
     }
+
+    #[cfg(all(feature = "rt-wasm", target_arch = "wasm32", target_os = "unknown"))]
+    fn now(&self) -> SystemTime {
+        // `Date.now()` returns the number of milliseconds since the UNIX epoch
+        UNIX_EPOCH + Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+    }
 }
 
 impl Default for SharedTimeSource {
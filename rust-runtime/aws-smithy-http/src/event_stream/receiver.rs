@@ -6,6 +6,7 @@
 use aws_smithy_eventstream::frame::{
     DecodedFrame, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage,
 };
+use aws_smithy_runtime_api::client::buffered_memory::BufferedMemory;
 use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
 use aws_smithy_types::body::SdkBody;
 use aws_smithy_types::event_stream::{Message, RawMessage};
@@ -42,6 +43,14 @@ impl RecvBuf {
         }
     }
 
+    /// Returns the number of bytes that are buffered.
+    fn remaining(&self) -> usize {
+        match self {
+            RecvBuf::Empty | RecvBuf::Terminated => 0,
+            RecvBuf::Partial(segments) | RecvBuf::EosPartial(segments) => segments.remaining(),
+        }
+    }
+
     /// Returns true if the stream has ended.
     fn is_eos(&self) -> bool {
         matches!(self, RecvBuf::EosPartial(_) | RecvBuf::Terminated)
@@ -115,6 +124,8 @@ pub struct Receiver<T, E> {
     unmarshaller: Box<dyn UnmarshallMessage<Output = T, Error = E> + Send + Sync>,
     decoder: MessageFrameDecoder,
     buffer: RecvBuf,
+    /// Counts the data in `buffer` towards the runtime's buffered memory.
+    buffered_memory: BufferedMemory,
     body: SdkBody,
     /// Event Stream has optional initial response frames an with `:message-type` of
     /// `initial-response`. If `try_recv_initial()` is called and the next message isn't an
@@ -134,6 +145,7 @@ impl<T, E> Receiver<T, E> {
             unmarshaller: Box::new(unmarshaller),
             decoder: MessageFrameDecoder::new(),
             buffer: RecvBuf::Empty,
+            buffered_memory: BufferedMemory::new(),
             body,
             buffered_message: None,
             _phantom: Default::default(),
@@ -166,6 +178,9 @@ impl<T, E> Receiver<T, E> {
             } else {
                 self.buffer = buffer.ended();
             }
+            self.buffered_memory
+                .resize(self.buffer.remaining())
+                .map_err(|err| SdkError::response_error(err, RawMessage::Invalid(None)))?;
         }
         Ok(())
     }
@@ -184,6 +199,8 @@ impl<T, E> Receiver<T, E> {
                         )
                     })?
                 {
+                    // Decoded messages are no longer buffered, so this only shrinks
+                    let _ = self.buffered_memory.resize(self.buffer.remaining());
                     trace!(message = ?message, "received complete event stream message");
                     return Ok(Some(message));
                 }
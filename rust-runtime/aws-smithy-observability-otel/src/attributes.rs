@@ -0,0 +1,52 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_observability::attributes::{AttributeValue, Attributes};
+use opentelemetry::{KeyValue, Value};
+
+/// Converts a value to an OpenTelemetry value, or returns `None` for kinds of values that
+/// OpenTelemetry doesn't support.
+pub(crate) fn to_value(value: &AttributeValue) -> Option<Value> {
+    Some(match value {
+        AttributeValue::I64(value) => Value::I64(*value),
+        AttributeValue::F64(value) => Value::F64(*value),
+        AttributeValue::String(value) => Value::String(value.clone().into()),
+        AttributeValue::Bool(value) => Value::Bool(*value),
+        _ => return None,
+    })
+}
+
+/// Converts attributes to OpenTelemetry key-values.
+pub(crate) fn to_key_values(attributes: &Attributes) -> Vec<KeyValue> {
+    attributes
+        .iter()
+        .filter_map(|(key, value)| Some(KeyValue::new(key, to_value(value)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_key_values;
+    use aws_smithy_observability::attributes::Attributes;
+    use opentelemetry::{KeyValue, Value};
+
+    #[test]
+    fn attributes_are_converted_to_key_values() {
+        let attributes = Attributes::new()
+            .with("rpc.service", "S3")
+            .with("attempt", 2_i64)
+            .with("duration", 0.5)
+            .with("retryable", true);
+        assert_eq!(
+            vec![
+                KeyValue::new("rpc.service", Value::String("S3".into())),
+                KeyValue::new("attempt", 2_i64),
+                KeyValue::new("duration", 0.5),
+                KeyValue::new("retryable", true),
+            ],
+            to_key_values(&attributes)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![allow(clippy::derive_partial_eq_without_eq)]
+#![warn(
+    missing_docs,
+    rustdoc::missing_crate_level_docs,
+    unreachable_pub,
+    rust_2018_idioms
+)]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+
+//! [OpenTelemetry](https://opentelemetry.io/) implementation of the `aws-smithy-observability`
+//! telemetry traits.
+//!
+//! Set the [`TelemetryProvider`] returned by [`global_telemetry_provider`] on a client's config
+//! to export the metrics and spans of all of its calls with the globally registered OpenTelemetry
+//! providers, or build one from [`OtelMeterProvider`](meter::OtelMeterProvider) and
+//! [`OtelTracerProvider`](tracer::OtelTracerProvider) to use specific providers.
+
+use aws_smithy_observability::provider::TelemetryProvider;
+
+#[cfg(any(feature = "metrics", feature = "traces"))]
+mod attributes;
+#[cfg(feature = "metrics")]
+pub mod meter;
+#[cfg(feature = "traces")]
+pub mod tracer;
+
+/// Returns a [`TelemetryProvider`] that records telemetry with the OpenTelemetry providers that
+/// are registered globally.
+///
+/// The meter provider is looked up when this is called, so the global meter provider should be
+/// registered first. Spans are always started with the current global tracer provider.
+pub fn global_telemetry_provider() -> TelemetryProvider {
+    #[allow(unused_mut)]
+    let mut builder = TelemetryProvider::builder();
+    #[cfg(feature = "metrics")]
+    builder.set_meter_provider(Some(
+        aws_smithy_observability::meter::SharedMeterProvider::new(
+            meter::OtelMeterProvider::global(),
+        ),
+    ));
+    #[cfg(feature = "traces")]
+    builder.set_tracer_provider(Some(
+        aws_smithy_observability::tracer::SharedTracerProvider::new(
+            tracer::OtelTracerProvider::global(),
+        ),
+    ));
+    builder.build()
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [`MeterProvider`] that records measurements with OpenTelemetry instruments.
+
+use crate::attributes::to_key_values;
+use aws_smithy_observability::attributes::Attributes;
+use aws_smithy_observability::meter::{
+    Instrument, InstrumentDescriptor, InstrumentKind, Meter, MeterProvider,
+};
+use opentelemetry::metrics::{self as otel, Counter, Gauge, Histogram, UpDownCounter};
+use std::fmt;
+use std::sync::Arc;
+
+/// A [`MeterProvider`] that records measurements with the instruments of an OpenTelemetry meter
+/// provider.
+#[derive(Clone)]
+pub struct OtelMeterProvider {
+    provider: Arc<dyn otel::MeterProvider + Send + Sync>,
+}
+
+impl OtelMeterProvider {
+    /// Creates a new `OtelMeterProvider` that records measurements with `provider`.
+    pub fn new(provider: impl otel::MeterProvider + Send + Sync + 'static) -> Self {
+        Self {
+            provider: Arc::new(provider),
+        }
+    }
+
+    /// Creates a new `OtelMeterProvider` that records measurements with the meter provider that's
+    /// currently registered globally.
+    pub fn global() -> Self {
+        Self {
+            provider: opentelemetry::global::meter_provider(),
+        }
+    }
+}
+
+impl fmt::Debug for OtelMeterProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtelMeterProvider").finish_non_exhaustive()
+    }
+}
+
+impl MeterProvider for OtelMeterProvider {
+    fn meter(&self, scope: &'static str) -> Arc<dyn Meter> {
+        Arc::new(OtelMeter(self.provider.meter(scope)))
+    }
+}
+
+struct OtelMeter(otel::Meter);
+
+impl fmt::Debug for OtelMeter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OtelMeter").field(&self.0).finish()
+    }
+}
+
+impl Meter for OtelMeter {
+    fn instrument(&self, descriptor: &InstrumentDescriptor) -> Arc<dyn Instrument> {
+        let name = descriptor.name();
+        let unit = descriptor.unit().unwrap_or_default();
+        let description = descriptor.description().unwrap_or_default();
+        let instrument = match descriptor.kind() {
+            InstrumentKind::MonotonicCounter => OtelInstrument::Counter(
+                self.0
+                    .f64_counter(name)
+                    .with_unit(unit)
+                    .with_description(description)
+                    .build(),
+            ),
+            InstrumentKind::UpDownCounter => OtelInstrument::UpDownCounter(
+                self.0
+                    .f64_up_down_counter(name)
+                    .with_unit(unit)
+                    .with_description(description)
+                    .build(),
+            ),
+            InstrumentKind::Gauge => OtelInstrument::Gauge(
+                self.0
+                    .f64_gauge(name)
+                    .with_unit(unit)
+                    .with_description(description)
+                    .build(),
+            ),
+            // Histograms keep every measurement, so they're the safest fallback for kinds of
+            // instruments that are added later
+            _ => OtelInstrument::Histogram(
+                self.0
+                    .f64_histogram(name)
+                    .with_unit(unit)
+                    .with_description(description)
+                    .build(),
+            ),
+        };
+        Arc::new(instrument)
+    }
+}
+
+enum OtelInstrument {
+    Histogram(Histogram<f64>),
+    Counter(Counter<f64>),
+    UpDownCounter(UpDownCounter<f64>),
+    Gauge(Gauge<f64>),
+}
+
+impl fmt::Debug for OtelInstrument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Histogram(_) => "Histogram",
+            Self::Counter(_) => "Counter",
+            Self::UpDownCounter(_) => "UpDownCounter",
+            Self::Gauge(_) => "Gauge",
+        };
+        f.debug_tuple("OtelInstrument").field(&kind).finish()
+    }
+}
+
+impl Instrument for OtelInstrument {
+    fn record(&self, value: f64, attributes: &Attributes) {
+        let attributes = to_key_values(attributes);
+        match self {
+            Self::Histogram(histogram) => histogram.record(value, &attributes),
+            Self::Counter(counter) => counter.add(value, &attributes),
+            Self::UpDownCounter(counter) => counter.add(value, &attributes),
+            Self::Gauge(gauge) => gauge.record(value, &attributes),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [`TracerProvider`] that starts OpenTelemetry spans.
+
+use crate::attributes::{to_key_values, to_value};
+use aws_smithy_observability::attributes::{AttributeValue, Attributes};
+use aws_smithy_observability::tracer::{Span, Tracer, TracerProvider};
+use opentelemetry::global::GlobalTracerProvider;
+use opentelemetry::trace::{self as otel, Status, TraceContextExt};
+use opentelemetry::{Context, KeyValue};
+use std::fmt;
+use std::sync::Arc;
+
+/// A [`TracerProvider`] that starts spans with the tracers of an OpenTelemetry tracer provider.
+#[derive(Clone)]
+pub struct OtelTracerProvider<P = GlobalTracerProvider> {
+    provider: P,
+}
+
+impl<P> OtelTracerProvider<P> {
+    /// Creates a new `OtelTracerProvider` that starts spans with `provider`.
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+}
+
+impl OtelTracerProvider {
+    /// Creates a new `OtelTracerProvider` that starts spans with the tracer provider that's
+    /// registered globally when each span is started.
+    pub fn global() -> Self {
+        Self::new(opentelemetry::global::tracer_provider())
+    }
+}
+
+impl<P> fmt::Debug for OtelTracerProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtelTracerProvider").finish_non_exhaustive()
+    }
+}
+
+impl<P> TracerProvider for OtelTracerProvider<P>
+where
+    P: otel::TracerProvider + Send + Sync,
+    P::Tracer: Send + Sync + 'static,
+    <P::Tracer as otel::Tracer>::Span: Send + Sync + 'static,
+{
+    fn tracer(&self, scope: &'static str) -> Arc<dyn Tracer> {
+        Arc::new(OtelTracer(Arc::new(self.provider.tracer(scope))))
+    }
+}
+
+struct OtelTracer<T>(Arc<T>);
+
+impl<T> fmt::Debug for OtelTracer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtelTracer").finish_non_exhaustive()
+    }
+}
+
+impl<T> Tracer for OtelTracer<T>
+where
+    T: otel::Tracer + Send + Sync + 'static,
+    T::Span: Send + Sync + 'static,
+{
+    fn start_span(&self, name: &'static str, attributes: &Attributes) -> Arc<dyn Span> {
+        OtelSpan::start(self.0.clone(), name, attributes, &Context::current())
+    }
+}
+
+/// An OpenTelemetry span, held in the context that its children are started in.
+struct OtelSpan<T> {
+    tracer: Arc<T>,
+    context: Context,
+}
+
+impl<T> OtelSpan<T>
+where
+    T: otel::Tracer + Send + Sync + 'static,
+    T::Span: Send + Sync + 'static,
+{
+    fn start(
+        tracer: Arc<T>,
+        name: &'static str,
+        attributes: &Attributes,
+        parent: &Context,
+    ) -> Arc<dyn Span> {
+        let span = tracer
+            .span_builder(name)
+            .with_kind(otel::SpanKind::Client)
+            .with_attributes(to_key_values(attributes))
+            .start_with_context(tracer.as_ref(), parent);
+        Arc::new(Self {
+            context: parent.with_span(span),
+            tracer,
+        })
+    }
+}
+
+impl<T> fmt::Debug for OtelSpan<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtelSpan")
+            .field("span_context", self.context.span().span_context())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Span for OtelSpan<T>
+where
+    T: otel::Tracer + Send + Sync + 'static,
+    T::Span: Send + Sync + 'static,
+{
+    fn start_child(&self, name: &'static str, attributes: &Attributes) -> Arc<dyn Span> {
+        Self::start(self.tracer.clone(), name, attributes, &self.context)
+    }
+
+    fn set_attribute(&self, key: &'static str, value: AttributeValue) {
+        if let Some(value) = to_value(&value) {
+            self.context.span().set_attribute(KeyValue::new(key, value));
+        }
+    }
+
+    fn set_error(&self, description: &str) {
+        self.context
+            .span()
+            .set_status(Status::error(description.to_string()));
+    }
+
+    fn end(&self) {
+        self.context.span().end();
+    }
+}
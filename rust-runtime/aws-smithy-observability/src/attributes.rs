@@ -0,0 +1,130 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Attributes (key-value pairs) that describe a measurement.
+
+use std::borrow::Cow;
+
+/// The value of an attribute.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeValue {
+    /// A signed integer value
+    I64(i64),
+    /// A floating point value
+    F64(f64),
+    /// A string value
+    String(Cow<'static, str>),
+    /// A boolean value
+    Bool(bool),
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::I64(value)
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        Self::F64(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&'static str> for AttributeValue {
+    fn from(value: &'static str) -> Self {
+        Self::String(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(Cow::Owned(value))
+    }
+}
+
+/// A set of attributes that describe a measurement, such as the service and operation it was
+/// recorded for.
+///
+/// Attributes keep the order they were first set in, and setting an existing key replaces its value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Attributes {
+    attributes: Vec<(&'static str, AttributeValue)>,
+}
+
+impl Attributes {
+    /// Creates an empty set of attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the attribute `key` to `value`.
+    pub fn set(&mut self, key: &'static str, value: impl Into<AttributeValue>) -> &mut Self {
+        let value = value.into();
+        match self.attributes.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.attributes.push((key, value)),
+        }
+        self
+    }
+
+    /// Sets the attribute `key` to `value`.
+    pub fn with(mut self, key: &'static str, value: impl Into<AttributeValue>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Returns the value of the attribute `key`, if it was set.
+    pub fn get(&self, key: &str) -> Option<&AttributeValue> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the attributes.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &AttributeValue)> {
+        self.attributes.iter().map(|(key, value)| (*key, value))
+    }
+
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns true if there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttributeValue, Attributes};
+
+    #[test]
+    fn setting_an_existing_key_replaces_it() {
+        let mut attributes = Attributes::new()
+            .with("rpc.service", "s3")
+            .with("rpc.method", "GetObject");
+        attributes.set("rpc.service", String::from("dynamodb"));
+
+        assert_eq!(2, attributes.len());
+        assert_eq!(
+            Some(&AttributeValue::from("dynamodb")),
+            attributes.get("rpc.service")
+        );
+        assert_eq!(
+            vec!["rpc.service", "rpc.method"],
+            attributes.iter().map(|(key, _)| key).collect::<Vec<_>>()
+        );
+    }
+}
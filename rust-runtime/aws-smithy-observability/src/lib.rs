@@ -0,0 +1,26 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![allow(clippy::derive_partial_eq_without_eq)]
+#![warn(
+    missing_docs,
+    rustdoc::missing_crate_level_docs,
+    unreachable_pub,
+    rust_2018_idioms
+)]
+
+//! Vendor-neutral telemetry abstractions for smithy-rs.
+//!
+//! The orchestrator records metrics such as call and attempt durations with the instruments
+//! created by a [`MeterProvider`](meter::MeterProvider), and traces operations and their attempts
+//! with the spans started by a [`TracerProvider`](tracer::TracerProvider). Implement the traits in
+//! [`meter`] and [`tracer`] to send telemetry to any backend, and set a
+//! [`TelemetryProvider`](provider::TelemetryProvider) on a client's config to turn it on.
+
+pub mod attributes;
+pub mod meter;
+pub mod noop;
+pub mod provider;
+pub mod tracer;
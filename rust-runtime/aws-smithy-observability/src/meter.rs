@@ -0,0 +1,125 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Traits for creating instruments and recording measurements with them.
+
+use crate::attributes::Attributes;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::fmt;
+use std::sync::Arc;
+
+/// Provides [`Meter`]s, and is the entry point for plugging a metrics backend into a client.
+pub trait MeterProvider: Send + Sync + fmt::Debug {
+    /// Returns a meter for the given instrumentation scope, such as the name of the crate that
+    /// records the measurements.
+    fn meter(&self, scope: &'static str) -> Arc<dyn Meter>;
+}
+
+/// Creates [`Instrument`]s.
+///
+/// Implementations should return the same instrument when they're asked for an instrument with
+/// the same name more than once, since instruments may be created for every operation.
+pub trait Meter: Send + Sync + fmt::Debug {
+    /// Creates an instrument with the given description.
+    fn instrument(&self, descriptor: &InstrumentDescriptor) -> Arc<dyn Instrument>;
+}
+
+/// Records measurements.
+///
+/// How a measurement is aggregated depends on the [`InstrumentKind`] that the instrument was
+/// created with.
+pub trait Instrument: Send + Sync + fmt::Debug {
+    /// Records `value` with the given attributes.
+    fn record(&self, value: f64, attributes: &Attributes);
+}
+
+/// The kind of an [`Instrument`], which determines what its measurements mean.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InstrumentKind {
+    /// Each measurement is a sample in a distribution, such as the duration of a call.
+    Histogram,
+    /// Each measurement is added to a sum that never decreases, such as a number of errors.
+    MonotonicCounter,
+    /// Each measurement is added to a sum that may increase or decrease, such as a number of
+    /// in-flight requests.
+    UpDownCounter,
+    /// Each measurement replaces the current value, such as the amount of memory in use.
+    Gauge,
+}
+
+/// Describes an [`Instrument`] to create.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstrumentDescriptor {
+    name: &'static str,
+    kind: InstrumentKind,
+    unit: Option<&'static str>,
+    description: Option<&'static str>,
+}
+
+impl InstrumentDescriptor {
+    /// Creates a descriptor for an instrument with the given name and kind.
+    pub const fn new(name: &'static str, kind: InstrumentKind) -> Self {
+        Self {
+            name,
+            kind,
+            unit: None,
+            description: None,
+        }
+    }
+
+    /// Sets the unit of the instrument's measurements, such as `s` or `By`.
+    pub const fn with_unit(mut self, unit: &'static str) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Sets a human-readable description of the instrument.
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Returns the name of the instrument.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the kind of the instrument.
+    pub fn kind(&self) -> InstrumentKind {
+        self.kind
+    }
+
+    /// Returns the unit of the instrument's measurements, if it has one.
+    pub fn unit(&self) -> Option<&'static str> {
+        self.unit
+    }
+
+    /// Returns the description of the instrument, if it has one.
+    pub fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+}
+
+/// A [`MeterProvider`] that can be shared between clients and stored in the config bag.
+#[derive(Clone, Debug)]
+pub struct SharedMeterProvider(Arc<dyn MeterProvider>);
+
+impl SharedMeterProvider {
+    /// Creates a new `SharedMeterProvider`.
+    pub fn new(meter_provider: impl MeterProvider + 'static) -> Self {
+        Self(Arc::new(meter_provider))
+    }
+}
+
+impl MeterProvider for SharedMeterProvider {
+    fn meter(&self, scope: &'static str) -> Arc<dyn Meter> {
+        self.0.meter(scope)
+    }
+}
+
+impl Storable for SharedMeterProvider {
+    type Storer = StoreReplace<Self>;
+}
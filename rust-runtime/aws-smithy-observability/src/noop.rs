@@ -0,0 +1,87 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Providers that discard all telemetry.
+
+use crate::attributes::{AttributeValue, Attributes};
+use crate::meter::{Instrument, InstrumentDescriptor, Meter, MeterProvider};
+use crate::tracer::{Span, Tracer, TracerProvider};
+use std::sync::Arc;
+
+/// A [`MeterProvider`] whose instruments discard every measurement.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct NoopMeterProvider;
+
+impl NoopMeterProvider {
+    /// Creates a new `NoopMeterProvider`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MeterProvider for NoopMeterProvider {
+    fn meter(&self, _scope: &'static str) -> Arc<dyn Meter> {
+        Arc::new(NoopMeter)
+    }
+}
+
+#[derive(Debug)]
+struct NoopMeter;
+
+impl Meter for NoopMeter {
+    fn instrument(&self, _descriptor: &InstrumentDescriptor) -> Arc<dyn Instrument> {
+        Arc::new(NoopInstrument)
+    }
+}
+
+#[derive(Debug)]
+struct NoopInstrument;
+
+impl Instrument for NoopInstrument {
+    fn record(&self, _value: f64, _attributes: &Attributes) {}
+}
+
+/// A [`TracerProvider`] whose spans are discarded.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct NoopTracerProvider;
+
+impl NoopTracerProvider {
+    /// Creates a new `NoopTracerProvider`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TracerProvider for NoopTracerProvider {
+    fn tracer(&self, _scope: &'static str) -> Arc<dyn Tracer> {
+        Arc::new(NoopTracer)
+    }
+}
+
+#[derive(Debug)]
+struct NoopTracer;
+
+impl Tracer for NoopTracer {
+    fn start_span(&self, _name: &'static str, _attributes: &Attributes) -> Arc<dyn Span> {
+        Arc::new(NoopSpan)
+    }
+}
+
+#[derive(Debug)]
+struct NoopSpan;
+
+impl Span for NoopSpan {
+    fn start_child(&self, _name: &'static str, _attributes: &Attributes) -> Arc<dyn Span> {
+        Arc::new(NoopSpan)
+    }
+
+    fn set_attribute(&self, _key: &'static str, _value: AttributeValue) {}
+
+    fn set_error(&self, _description: &str) {}
+
+    fn end(&self) {}
+}
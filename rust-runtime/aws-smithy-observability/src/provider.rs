@@ -0,0 +1,80 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A bundle of the providers that a client records its telemetry with.
+
+use crate::meter::{MeterProvider, SharedMeterProvider};
+use crate::tracer::{SharedTracerProvider, TracerProvider};
+
+/// The meter provider and tracer provider that a client records its telemetry with.
+///
+/// Adapters for telemetry backends typically return a `TelemetryProvider` with both set, so that
+/// a client can be configured with a single call.
+#[derive(Clone, Debug, Default)]
+pub struct TelemetryProvider {
+    meter_provider: Option<SharedMeterProvider>,
+    tracer_provider: Option<SharedTracerProvider>,
+}
+
+impl TelemetryProvider {
+    /// Returns a builder for `TelemetryProvider`.
+    pub fn builder() -> TelemetryProviderBuilder {
+        TelemetryProviderBuilder::default()
+    }
+
+    /// Returns the meter provider, if one was set.
+    pub fn meter_provider(&self) -> Option<&SharedMeterProvider> {
+        self.meter_provider.as_ref()
+    }
+
+    /// Returns the tracer provider, if one was set.
+    pub fn tracer_provider(&self) -> Option<&SharedTracerProvider> {
+        self.tracer_provider.as_ref()
+    }
+}
+
+/// Builder for [`TelemetryProvider`]
+#[derive(Clone, Debug, Default)]
+pub struct TelemetryProviderBuilder {
+    meter_provider: Option<SharedMeterProvider>,
+    tracer_provider: Option<SharedTracerProvider>,
+}
+
+impl TelemetryProviderBuilder {
+    /// Sets the meter provider that metrics are recorded with.
+    pub fn meter_provider(mut self, meter_provider: impl MeterProvider + 'static) -> Self {
+        self.set_meter_provider(Some(SharedMeterProvider::new(meter_provider)));
+        self
+    }
+
+    /// Sets the meter provider that metrics are recorded with.
+    pub fn set_meter_provider(&mut self, meter_provider: Option<SharedMeterProvider>) -> &mut Self {
+        self.meter_provider = meter_provider;
+        self
+    }
+
+    /// Sets the tracer provider that spans are created with.
+    pub fn tracer_provider(mut self, tracer_provider: impl TracerProvider + 'static) -> Self {
+        self.set_tracer_provider(Some(SharedTracerProvider::new(tracer_provider)));
+        self
+    }
+
+    /// Sets the tracer provider that spans are created with.
+    pub fn set_tracer_provider(
+        &mut self,
+        tracer_provider: Option<SharedTracerProvider>,
+    ) -> &mut Self {
+        self.tracer_provider = tracer_provider;
+        self
+    }
+
+    /// Builds the `TelemetryProvider`.
+    pub fn build(self) -> TelemetryProvider {
+        TelemetryProvider {
+            meter_provider: self.meter_provider,
+            tracer_provider: self.tracer_provider,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Traits for creating spans that trace the execution of operations.
+
+use crate::attributes::{AttributeValue, Attributes};
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::fmt;
+use std::sync::Arc;
+
+/// Provides [`Tracer`]s, and is the entry point for plugging a tracing backend into a client.
+pub trait TracerProvider: Send + Sync + fmt::Debug {
+    /// Returns a tracer for the given instrumentation scope, such as the name of the crate that
+    /// creates the spans.
+    fn tracer(&self, scope: &'static str) -> Arc<dyn Tracer>;
+}
+
+/// Starts [`Span`]s.
+pub trait Tracer: Send + Sync + fmt::Debug {
+    /// Starts a span whose parent is the current span, if there is one.
+    fn start_span(&self, name: &'static str, attributes: &Attributes) -> Arc<dyn Span>;
+}
+
+/// A unit of work, such as an operation or a single attempt of an operation.
+///
+/// Spans are ended explicitly with [`Span::end`]. Nothing that's recorded on a span after it has
+/// ended is exported.
+pub trait Span: Send + Sync + fmt::Debug {
+    /// Starts a span that's a child of this span.
+    fn start_child(&self, name: &'static str, attributes: &Attributes) -> Arc<dyn Span>;
+
+    /// Sets the attribute `key` to `value` on this span.
+    fn set_attribute(&self, key: &'static str, value: AttributeValue);
+
+    /// Marks this span as failed, with a description of the error.
+    fn set_error(&self, description: &str);
+
+    /// Ends this span.
+    fn end(&self);
+
+    /// Returns the identity of this span, which is propagated to downstream services in trace
+    /// headers, or `None` if the span can't be propagated.
+    fn context(&self) -> Option<SpanContext> {
+        None
+    }
+}
+
+/// The identity of a [`Span`], as it's propagated to downstream services.
+///
+/// This carries the same information as the [W3C Trace Context](https://www.w3.org/TR/trace-context/)
+/// `traceparent` and `tracestate` headers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpanContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    sampled: bool,
+    trace_state: Option<String>,
+}
+
+impl SpanContext {
+    /// Creates a sampled span context with the given trace and span IDs.
+    pub fn new(trace_id: [u8; 16], span_id: [u8; 8]) -> Self {
+        Self {
+            trace_id,
+            span_id,
+            sampled: true,
+            trace_state: None,
+        }
+    }
+
+    /// Sets whether the trace is being recorded.
+    pub fn with_sampled(mut self, sampled: bool) -> Self {
+        self.sampled = sampled;
+        self
+    }
+
+    /// Sets the vendor-specific trace state, in the format of the `tracestate` header.
+    pub fn with_trace_state(mut self, trace_state: impl Into<String>) -> Self {
+        self.trace_state = Some(trace_state.into());
+        self
+    }
+
+    /// Returns the ID of the trace that the span belongs to.
+    pub fn trace_id(&self) -> [u8; 16] {
+        self.trace_id
+    }
+
+    /// Returns the ID of the span.
+    pub fn span_id(&self) -> [u8; 8] {
+        self.span_id
+    }
+
+    /// Returns `true` if the trace is being recorded.
+    pub fn is_sampled(&self) -> bool {
+        self.sampled
+    }
+
+    /// Returns the vendor-specific trace state, if there is any.
+    pub fn trace_state(&self) -> Option<&str> {
+        self.trace_state.as_deref()
+    }
+
+    /// Returns `true` if neither the trace ID nor the span ID is all zeroes.
+    ///
+    /// Span contexts that aren't valid aren't propagated.
+    pub fn is_valid(&self) -> bool {
+        self.trace_id != [0; 16] && self.span_id != [0; 8]
+    }
+}
+
+impl Storable for SpanContext {
+    type Storer = StoreReplace<Self>;
+}
+
+/// A [`TracerProvider`] that can be shared between clients and stored in the config bag.
+#[derive(Clone, Debug)]
+pub struct SharedTracerProvider(Arc<dyn TracerProvider>);
+
+impl SharedTracerProvider {
+    /// Creates a new `SharedTracerProvider`.
+    pub fn new(tracer_provider: impl TracerProvider + 'static) -> Self {
+        Self(Arc::new(tracer_provider))
+    }
+}
+
+impl TracerProvider for SharedTracerProvider {
+    fn tracer(&self, scope: &'static str) -> Arc<dyn Tracer> {
+        self.0.tracer(scope)
+    }
+}
+
+impl Storable for SharedTracerProvider {
+    type Storer = StoreReplace<Self>;
+}
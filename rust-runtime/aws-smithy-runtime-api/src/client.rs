@@ -93,12 +93,18 @@ macro_rules! new_type_future {
 
 pub mod auth;
 
+pub mod buffered_memory;
+
+pub mod cancellation;
+
 pub mod connection;
 
 pub mod dns;
 
 pub mod endpoint;
 
+pub mod error_report;
+
 pub mod http;
 
 /// Smithy identity used by auth and signing.
@@ -106,8 +112,14 @@ pub mod identity;
 
 pub mod interceptors;
 
+pub mod latency;
+
+pub mod lifecycle;
+
 pub mod orchestrator;
 
+pub mod request_id;
+
 pub mod result;
 
 pub mod retries;
@@ -117,3 +129,7 @@ pub mod runtime_components;
 pub mod runtime_plugin;
 
 pub mod ser_de;
+
+pub mod shutdown;
+
+pub mod stalled_stream_protection;
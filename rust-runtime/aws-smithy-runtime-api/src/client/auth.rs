@@ -81,6 +81,68 @@ impl Storable for AuthSchemeOptionResolverParams {
     type Storer = StoreReplace<Self>;
 }
 
+/// The order in which auth schemes should be preferred when an operation supports several.
+///
+/// The auth scheme options that are resolved for an operation are reordered so that the preferred
+/// schemes come first, in the order of this list. Options that aren't in the list keep their
+/// relative order after the preferred ones, and schemes that the operation doesn't support are
+/// never used, even when they're preferred.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AuthSchemePreference {
+    preferred: Vec<AuthSchemeId>,
+}
+
+impl AuthSchemePreference {
+    /// Creates a preference for the given auth schemes, from the most to the least preferred.
+    pub fn new(preferred: impl IntoIterator<Item = impl Into<AuthSchemeId>>) -> Self {
+        Self {
+            preferred: preferred.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the preferred auth schemes, from the most to the least preferred.
+    pub fn iter(&self) -> impl Iterator<Item = &AuthSchemeId> {
+        self.preferred.iter()
+    }
+}
+
+impl<T: Into<AuthSchemeId>> FromIterator<T> for AuthSchemePreference {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl Storable for AuthSchemePreference {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Whether requests are sent anonymously, without authentication.
+///
+/// When this is enabled, the operation's auth scheme options are ignored, no identity is resolved,
+/// and requests aren't signed, even when the client has credentials configured. This is useful for
+/// operations on public endpoints, or for calls that bootstrap authentication. The client must
+/// have the "no auth" auth scheme registered, which generated clients always do.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AnonymousAuth {
+    enabled: bool,
+}
+
+impl AnonymousAuth {
+    /// Creates a new [`AnonymousAuth`] setting.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Returns true if requests are sent anonymously.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Storable for AnonymousAuth {
+    type Storer = StoreReplace<Self>;
+}
+
 #[deprecated(note = "Renamed to ResolveAuthSchemeOptions.")]
 pub use ResolveAuthSchemeOptions as AuthSchemeOptionResolver;
 
@@ -0,0 +1,184 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Accounting of the memory that's buffered by the runtime.
+//!
+//! The runtime counts the bytes that it holds in memory on behalf of operations: response bodies
+//! that are read into memory before they're deserialized, request bodies that are kept so that
+//! they can be retried, and event stream data that has been received but not yet decoded.
+//! [`buffered_bytes`] returns the total for the whole process, and [`set_limit`] caps it, so that
+//! operations fail instead of buffering more than the cap.
+//!
+//! # Examples
+//! ```rust
+//! use aws_smithy_runtime_api::client::buffered_memory;
+//!
+//! // Fail operations rather than buffer more than 256 MiB at once
+//! buffered_memory::set_limit(Some(256 * 1024 * 1024));
+//! println!("{} bytes are buffered", buffered_memory::buffered_bytes());
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static BUFFERED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIMIT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Returns the number of bytes that are currently buffered by the runtime.
+pub fn buffered_bytes() -> usize {
+    BUFFERED_BYTES.load(Ordering::Relaxed)
+}
+
+/// Sets the maximum number of bytes that the runtime may buffer at once, or removes the limit.
+///
+/// There's no limit by default. Lowering the limit doesn't release memory that's already
+/// buffered; it only causes later attempts to buffer more to fail with [`BufferLimitExceeded`].
+pub fn set_limit(limit: Option<usize>) {
+    LIMIT.store(limit.unwrap_or(usize::MAX), Ordering::Relaxed);
+}
+
+/// Returns the maximum number of bytes that the runtime may buffer at once, if there's a limit.
+pub fn limit() -> Option<usize> {
+    match LIMIT.load(Ordering::Relaxed) {
+        usize::MAX => None,
+        limit => Some(limit),
+    }
+}
+
+/// Bytes that are counted as buffered until this is dropped.
+#[derive(Debug, Default)]
+pub struct BufferedMemory {
+    bytes: usize,
+}
+
+impl BufferedMemory {
+    /// Creates a `BufferedMemory` that doesn't count any bytes yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts the given number of bytes as buffered, or fails if that would exceed the limit.
+    pub fn reserve(bytes: usize) -> Result<Self, BufferLimitExceeded> {
+        let mut buffered_memory = Self::new();
+        buffered_memory.grow(bytes)?;
+        Ok(buffered_memory)
+    }
+
+    /// Returns the number of bytes that this counts as buffered.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Counts another `additional` bytes as buffered, or fails if that would exceed the limit.
+    pub fn grow(&mut self, additional: usize) -> Result<(), BufferLimitExceeded> {
+        let limit = LIMIT.load(Ordering::Relaxed);
+        BUFFERED_BYTES
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |buffered| {
+                buffered
+                    .checked_add(additional)
+                    .filter(|&total| total <= limit)
+            })
+            .map_err(|buffered| BufferLimitExceeded {
+                requested: additional,
+                buffered,
+                limit,
+            })?;
+        self.bytes += additional;
+        Ok(())
+    }
+
+    /// Stops counting `bytes` of the bytes that this counts as buffered.
+    pub fn shrink(&mut self, bytes: usize) {
+        let bytes = bytes.min(self.bytes);
+        BUFFERED_BYTES.fetch_sub(bytes, Ordering::Relaxed);
+        self.bytes -= bytes;
+    }
+
+    /// Grows or shrinks this to count exactly `bytes` bytes as buffered.
+    ///
+    /// Fails without changing what's counted if growing would exceed the limit.
+    pub fn resize(&mut self, bytes: usize) -> Result<(), BufferLimitExceeded> {
+        if bytes > self.bytes {
+            self.grow(bytes - self.bytes)
+        } else {
+            self.shrink(self.bytes - bytes);
+            Ok(())
+        }
+    }
+}
+
+impl Drop for BufferedMemory {
+    fn drop(&mut self) {
+        self.shrink(self.bytes);
+    }
+}
+
+/// Error returned when buffering more bytes would exceed the limit set with [`set_limit`].
+#[derive(Debug)]
+pub struct BufferLimitExceeded {
+    requested: usize,
+    buffered: usize,
+    limit: usize,
+}
+
+impl BufferLimitExceeded {
+    /// Returns the number of bytes that couldn't be buffered.
+    pub fn requested(&self) -> usize {
+        self.requested
+    }
+
+    /// Returns the number of bytes that were already buffered.
+    pub fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// Returns the limit on the number of buffered bytes.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for BufferLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "buffering {} more bytes would exceed the limit of {} buffered bytes ({} bytes are already buffered)",
+            self.requested, self.limit, self.buffered
+        )
+    }
+}
+
+impl StdError for BufferLimitExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffered_bytes_are_counted_until_dropped() {
+        let mut first = BufferedMemory::reserve(100).unwrap();
+        first.grow(50).unwrap();
+        let second = BufferedMemory::reserve(10).unwrap();
+        assert_eq!(160, buffered_bytes());
+        first.resize(20).unwrap();
+        assert_eq!(20, first.bytes());
+        assert_eq!(30, buffered_bytes());
+
+        set_limit(Some(40));
+        let err = BufferedMemory::reserve(11).expect_err("exceeds the limit");
+        assert_eq!((11, 30, 40), (err.requested(), err.buffered(), err.limit()));
+        first.grow(10).unwrap();
+        assert!(first.grow(1).is_err());
+        assert_eq!(30, first.bytes());
+        set_limit(None);
+        assert_eq!(None, limit());
+
+        drop(second);
+        assert_eq!(30, buffered_bytes());
+        drop(first);
+        assert_eq!(0, buffered_bytes());
+    }
+}
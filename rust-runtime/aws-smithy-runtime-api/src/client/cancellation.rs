@@ -0,0 +1,137 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Cooperative cancellation of in-flight operations.
+//!
+//! Dropping an operation's future is the simplest way to cancel it, but that isn't always possible
+//! when the future is owned by a task deep inside an application. A [`CancellationToken`] can be
+//! placed in an operation's config instead. When the token is cancelled, the in-flight attempt is
+//! aborted, its connection is released, and the operation fails with
+//! [`SdkError::Cancelled`](crate::client::result::SdkError::Cancelled). Unlike dropping the
+//! future, this still runs the interceptors that are called once an attempt or the operation
+//! completes.
+
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+/// A token that can be used to cancel one or more in-flight operations.
+///
+/// Clones of a token share the same state, so a clone can be kept to cancel the operations that
+/// were given the token.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    /// Creates a new token that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every operation using this token.
+    ///
+    /// Operations started with this token after it was cancelled fail immediately.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        let wakers = std::mem::take(&mut *self.inner.wakers.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Returns true if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Runs `future` to completion, unless this token is cancelled first.
+    ///
+    /// The returned future resolves to `None` if the token was cancelled, in which case `future`
+    /// is dropped without being polled again.
+    pub fn run_until_cancelled<F: Future>(&self, future: F) -> RunUntilCancelled<F> {
+        RunUntilCancelled {
+            token: self.clone(),
+            future,
+        }
+    }
+
+    fn poll_cancelled(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.is_cancelled() {
+            return Poll::Ready(());
+        }
+        let mut wakers = self.inner.wakers.lock().unwrap();
+        // Check again while holding the lock so that a concurrent `cancel` can't be missed
+        if self.is_cancelled() {
+            return Poll::Ready(());
+        }
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl Storable for CancellationToken {
+    type Storer = StoreReplace<Self>;
+}
+
+pin_project! {
+    /// Future returned by [`CancellationToken::run_until_cancelled`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    #[derive(Debug)]
+    pub struct RunUntilCancelled<F> {
+        token: CancellationToken,
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for RunUntilCancelled<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if this.token.poll_cancelled(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        this.future.poll(cx).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn completes_when_not_cancelled() {
+        let token = CancellationToken::new();
+        assert_eq!(Some(5), token.run_until_cancelled(async { 5 }).await);
+        assert!(!token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn cancel_wakes_pending_future() {
+        let token = CancellationToken::new();
+        let (_tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let run = tokio::spawn(token.run_until_cancelled(rx));
+        tokio::task::yield_now().await;
+        token.clone().cancel();
+        assert_eq!(None, run.await.unwrap());
+        assert!(token.is_cancelled());
+        assert_eq!(None, token.run_until_cancelled(async { 5 }).await);
+    }
+}
@@ -8,15 +8,29 @@
 use std::fmt::{Debug, Formatter};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Metadata that tracks the state of an active connection.
 #[derive(Clone)]
 pub struct ConnectionMetadata {
     is_proxied: bool,
     remote_addr: Option<SocketAddr>,
+    // Kept behind a pointer so that errors carrying connection metadata stay small
+    details: Arc<ConnectionDetails>,
     poison_fn: Arc<dyn Fn() + Send + Sync>,
 }
 
+#[derive(Debug, Default)]
+struct ConnectionDetails {
+    local_addr: Option<SocketAddr>,
+    is_reused: Option<bool>,
+    tls_version: Option<String>,
+    alpn_protocol: Option<String>,
+    dns_duration: Option<Duration>,
+    connect_duration: Option<Duration>,
+    tls_handshake_duration: Option<Duration>,
+}
+
 impl ConnectionMetadata {
     /// Poison this connection, ensuring that it won't be reused.
     pub fn poison(&self) {
@@ -33,14 +47,75 @@ impl ConnectionMetadata {
         Self {
             is_proxied,
             remote_addr,
+            details: Default::default(),
             poison_fn: Arc::new(poison),
         }
     }
 
+    /// Returns a builder for [`ConnectionMetadata`].
+    pub fn builder() -> ConnectionMetadataBuilder {
+        ConnectionMetadataBuilder::default()
+    }
+
+    /// Returns true if this connection is to a proxy.
+    pub fn is_proxied(&self) -> bool {
+        self.is_proxied
+    }
+
     /// Get the remote address for this connection, if one is set.
     pub fn remote_addr(&self) -> Option<SocketAddr> {
         self.remote_addr
     }
+
+    /// Get the local address for this connection, if one is set.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.details.local_addr
+    }
+
+    /// Returns whether the connection was reused from the connection pool, if that's known.
+    ///
+    /// A connection that was reused had already been established before the request was sent,
+    /// so the request didn't wait on name resolution, connecting, or TLS negotiation.
+    pub fn is_reused(&self) -> Option<bool> {
+        self.details.is_reused
+    }
+
+    /// Get the TLS version that was negotiated for this connection, like `TLSv1.3`.
+    ///
+    /// This is only set for TLS connections, and only when the connector reports it.
+    pub fn tls_version(&self) -> Option<&str> {
+        self.details.tls_version.as_deref()
+    }
+
+    /// Get the protocol that was negotiated with ALPN for this connection, like `h2`.
+    ///
+    /// This is only set when a protocol was negotiated, and only when the connector reports it.
+    pub fn alpn_protocol(&self) -> Option<&str> {
+        self.details.alpn_protocol.as_deref()
+    }
+
+    /// Get how long it took to resolve the name of the host that this connection is to.
+    ///
+    /// This is only set for new connections, and only when the connector resolves names
+    /// separately from connecting. Otherwise, name resolution is part of the
+    /// [`connect_duration`](Self::connect_duration).
+    pub fn dns_duration(&self) -> Option<Duration> {
+        self.details.dns_duration
+    }
+
+    /// Get how long it took to establish the TCP connection.
+    ///
+    /// This is only set for new connections, and only when the connector reports it.
+    pub fn connect_duration(&self) -> Option<Duration> {
+        self.details.connect_duration
+    }
+
+    /// Get how long the TLS handshake took.
+    ///
+    /// This is only set for new TLS connections, and only when the connector reports it.
+    pub fn tls_handshake_duration(&self) -> Option<Duration> {
+        self.details.tls_handshake_duration
+    }
 }
 
 impl Debug for ConnectionMetadata {
@@ -48,6 +123,237 @@ impl Debug for ConnectionMetadata {
         f.debug_struct("SmithyConnection")
             .field("is_proxied", &self.is_proxied)
             .field("remote_addr", &self.remote_addr)
+            .field("local_addr", &self.details.local_addr)
+            .field("is_reused", &self.details.is_reused)
+            .field("tls_version", &self.details.tls_version)
+            .field("alpn_protocol", &self.details.alpn_protocol)
+            .field("dns_duration", &self.details.dns_duration)
+            .field("connect_duration", &self.details.connect_duration)
+            .field(
+                "tls_handshake_duration",
+                &self.details.tls_handshake_duration,
+            )
             .finish()
     }
 }
+
+/// Builder for [`ConnectionMetadata`].
+#[derive(Default)]
+pub struct ConnectionMetadataBuilder {
+    is_proxied: Option<bool>,
+    remote_addr: Option<SocketAddr>,
+    local_addr: Option<SocketAddr>,
+    is_reused: Option<bool>,
+    tls_version: Option<String>,
+    alpn_protocol: Option<String>,
+    dns_duration: Option<Duration>,
+    connect_duration: Option<Duration>,
+    tls_handshake_duration: Option<Duration>,
+    poison_fn: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl Debug for ConnectionMetadataBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionMetadataBuilder")
+            .field("is_proxied", &self.is_proxied)
+            .field("remote_addr", &self.remote_addr)
+            .field("local_addr", &self.local_addr)
+            .field("is_reused", &self.is_reused)
+            .field("tls_version", &self.tls_version)
+            .field("alpn_protocol", &self.alpn_protocol)
+            .field("dns_duration", &self.dns_duration)
+            .field("connect_duration", &self.connect_duration)
+            .field("tls_handshake_duration", &self.tls_handshake_duration)
+            .finish()
+    }
+}
+
+impl ConnectionMetadataBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the connection is to a proxy.
+    pub fn proxied(mut self, proxied: bool) -> Self {
+        self.set_proxied(Some(proxied));
+        self
+    }
+
+    /// Set whether the connection is to a proxy.
+    pub fn set_proxied(&mut self, proxied: Option<bool>) -> &mut Self {
+        self.is_proxied = proxied;
+        self
+    }
+
+    /// Set the remote address of the connection.
+    pub fn remote_addr(mut self, remote_addr: SocketAddr) -> Self {
+        self.set_remote_addr(Some(remote_addr));
+        self
+    }
+
+    /// Set the remote address of the connection.
+    pub fn set_remote_addr(&mut self, remote_addr: Option<SocketAddr>) -> &mut Self {
+        self.remote_addr = remote_addr;
+        self
+    }
+
+    /// Set the local address of the connection.
+    pub fn local_addr(mut self, local_addr: SocketAddr) -> Self {
+        self.set_local_addr(Some(local_addr));
+        self
+    }
+
+    /// Set the local address of the connection.
+    pub fn set_local_addr(&mut self, local_addr: Option<SocketAddr>) -> &mut Self {
+        self.local_addr = local_addr;
+        self
+    }
+
+    /// Set whether the connection was reused from the connection pool.
+    pub fn reused(mut self, reused: bool) -> Self {
+        self.set_reused(Some(reused));
+        self
+    }
+
+    /// Set whether the connection was reused from the connection pool.
+    pub fn set_reused(&mut self, reused: Option<bool>) -> &mut Self {
+        self.is_reused = reused;
+        self
+    }
+
+    /// Set the TLS version that was negotiated for the connection.
+    pub fn tls_version(mut self, tls_version: impl Into<String>) -> Self {
+        self.set_tls_version(Some(tls_version.into()));
+        self
+    }
+
+    /// Set the TLS version that was negotiated for the connection.
+    pub fn set_tls_version(&mut self, tls_version: Option<String>) -> &mut Self {
+        self.tls_version = tls_version;
+        self
+    }
+
+    /// Set the protocol that was negotiated with ALPN for the connection.
+    pub fn alpn_protocol(mut self, alpn_protocol: impl Into<String>) -> Self {
+        self.set_alpn_protocol(Some(alpn_protocol.into()));
+        self
+    }
+
+    /// Set the protocol that was negotiated with ALPN for the connection.
+    pub fn set_alpn_protocol(&mut self, alpn_protocol: Option<String>) -> &mut Self {
+        self.alpn_protocol = alpn_protocol;
+        self
+    }
+
+    /// Set how long it took to resolve the name of the host that the connection is to.
+    pub fn dns_duration(mut self, dns_duration: Duration) -> Self {
+        self.set_dns_duration(Some(dns_duration));
+        self
+    }
+
+    /// Set how long it took to resolve the name of the host that the connection is to.
+    pub fn set_dns_duration(&mut self, dns_duration: Option<Duration>) -> &mut Self {
+        self.dns_duration = dns_duration;
+        self
+    }
+
+    /// Set how long it took to establish the TCP connection.
+    pub fn connect_duration(mut self, connect_duration: Duration) -> Self {
+        self.set_connect_duration(Some(connect_duration));
+        self
+    }
+
+    /// Set how long it took to establish the TCP connection.
+    pub fn set_connect_duration(&mut self, connect_duration: Option<Duration>) -> &mut Self {
+        self.connect_duration = connect_duration;
+        self
+    }
+
+    /// Set how long the TLS handshake took.
+    pub fn tls_handshake_duration(mut self, tls_handshake_duration: Duration) -> Self {
+        self.set_tls_handshake_duration(Some(tls_handshake_duration));
+        self
+    }
+
+    /// Set how long the TLS handshake took.
+    pub fn set_tls_handshake_duration(
+        &mut self,
+        tls_handshake_duration: Option<Duration>,
+    ) -> &mut Self {
+        self.tls_handshake_duration = tls_handshake_duration;
+        self
+    }
+
+    /// Set the function that poisons the connection, ensuring that it won't be reused.
+    pub fn poison_fn(mut self, poison_fn: impl Fn() + Send + Sync + 'static) -> Self {
+        self.poison_fn = Some(Arc::new(poison_fn));
+        self
+    }
+
+    /// Build a [`ConnectionMetadata`].
+    ///
+    /// # Panics
+    ///
+    /// If `proxied` or `poison_fn` weren't set.
+    pub fn build(self) -> ConnectionMetadata {
+        ConnectionMetadata {
+            is_proxied: self
+                .is_proxied
+                .expect("is_proxied should be set for ConnectionMetadata"),
+            remote_addr: self.remote_addr,
+            details: Arc::new(ConnectionDetails {
+                local_addr: self.local_addr,
+                is_reused: self.is_reused,
+                tls_version: self.tls_version,
+                alpn_protocol: self.alpn_protocol,
+                dns_duration: self.dns_duration,
+                connect_duration: self.connect_duration,
+                tls_handshake_duration: self.tls_handshake_duration,
+            }),
+            poison_fn: self
+                .poison_fn
+                .expect("poison_fn should be set for ConnectionMetadata"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn builder_sets_transport_facts() {
+        let poisoned = Arc::new(AtomicBool::new(false));
+        let metadata = ConnectionMetadata::builder()
+            .proxied(false)
+            .remote_addr(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 443))
+            .reused(true)
+            .tls_version("TLSv1.3")
+            .alpn_protocol("h2")
+            .connect_duration(Duration::from_millis(5))
+            .poison_fn({
+                let poisoned = poisoned.clone();
+                move || poisoned.store(true, Ordering::Relaxed)
+            })
+            .build();
+        assert!(!metadata.is_proxied());
+        assert_eq!(443, metadata.remote_addr().unwrap().port());
+        assert_eq!(None, metadata.local_addr());
+        assert_eq!(Some(true), metadata.is_reused());
+        assert_eq!(Some("TLSv1.3"), metadata.tls_version());
+        assert_eq!(Some("h2"), metadata.alpn_protocol());
+        assert_eq!(None, metadata.dns_duration());
+        assert_eq!(Some(Duration::from_millis(5)), metadata.connect_duration());
+        metadata.poison();
+        assert!(poisoned.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    #[should_panic(expected = "is_proxied should be set")]
+    fn builder_requires_proxied() {
+        ConnectionMetadata::builder().poison_fn(|| {}).build();
+    }
+}
@@ -11,11 +11,13 @@ use crate::impl_shared_conversions;
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use aws_smithy_types::endpoint::Endpoint;
 use aws_smithy_types::type_erasure::TypeErasedBox;
+use std::any::TypeId;
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
 new_type_future! {
-    #[doc = "Future for [`EndpointResolver::resolve_endpoint`]."]
+    #[doc = "Future for [`ResolveEndpoint::resolve_endpoint`]."]
     pub struct EndpointFuture<'a, Endpoint, BoxError>;
 }
 
@@ -24,18 +26,104 @@ new_type_future! {
 /// The actual endpoint parameters are code generated from the Smithy model, and thus,
 /// are not known to the runtime crates. Hence, this struct is really a new-type around
 /// a [`TypeErasedBox`] that holds the actual concrete parameters in it.
+///
+/// Values that aren't part of the ruleset, such as a tenant ID read from the operation input,
+/// can be attached to the parameters as properties for custom endpoint resolvers to use. The
+/// endpoint parameters are available in the config bag before the request is serialized, so
+/// interceptors can set properties from `modify_before_serialization`:
+///
+/// ```rust
+/// use aws_smithy_runtime_api::box_error::BoxError;
+/// use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+/// use aws_smithy_runtime_api::client::interceptors::context::BeforeSerializationInterceptorContextMut;
+/// use aws_smithy_runtime_api::client::interceptors::Intercept;
+/// use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+/// use aws_smithy_types::config_bag::ConfigBag;
+///
+/// #[derive(Clone, Debug)]
+/// struct TenantId(String);
+///
+/// #[derive(Debug)]
+/// struct TenantIdInterceptor;
+///
+/// impl Intercept for TenantIdInterceptor {
+///     fn name(&self) -> &'static str {
+///         "TenantIdInterceptor"
+///     }
+///
+///     fn modify_before_serialization(
+///         &self,
+///         _context: &mut BeforeSerializationInterceptorContextMut<'_>,
+///         _runtime_components: &RuntimeComponents,
+///         cfg: &mut ConfigBag,
+///     ) -> Result<(), BoxError> {
+///         // Read the tenant ID from the input here
+///         let tenant_id = TenantId("tenant-1".into());
+///         if let Some(params) = cfg.get_mut::<EndpointResolverParams>() {
+///             params.set_property(tenant_id);
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// Endpoint resolvers then read it with [`get_property`](Self::get_property).
 #[derive(Debug)]
-pub struct EndpointResolverParams(TypeErasedBox);
+pub struct EndpointResolverParams {
+    inner: Arc<TypeErasedBox>,
+    properties: HashMap<TypeId, TypeErasedBox>,
+}
+
+impl Clone for EndpointResolverParams {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            properties: self
+                .properties
+                .iter()
+                .map(|(id, property)| {
+                    let property = property
+                        .try_clone()
+                        .expect("properties are created with `TypeErasedBox::new_with_clone`");
+                    (*id, property)
+                })
+                .collect(),
+        }
+    }
+}
 
 impl EndpointResolverParams {
     /// Creates a new [`EndpointResolverParams`] from a concrete parameters instance.
     pub fn new<T: fmt::Debug + Send + Sync + 'static>(params: T) -> Self {
-        Self(TypeErasedBox::new(params))
+        Self {
+            inner: Arc::new(TypeErasedBox::new(params)),
+            properties: HashMap::new(),
+        }
     }
 
     /// Attempts to downcast the underlying concrete parameters to `T` and return it as a reference.
     pub fn get<T: fmt::Debug + Send + Sync + 'static>(&self) -> Option<&T> {
-        self.0.downcast_ref()
+        self.inner.downcast_ref()
+    }
+
+    /// Returns the property of type `T`, if one was set.
+    pub fn get_property<T: fmt::Debug + Send + Sync + 'static>(&self) -> Option<&T> {
+        self.properties
+            .get(&TypeId::of::<T>())
+            .and_then(|property| property.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the property of type `T`, if one was set.
+    pub fn get_property_mut<T: fmt::Debug + Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+        self.properties
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|property| property.downcast_mut())
+    }
+
+    /// Sets the property of type `T`, replacing the previous one.
+    pub fn set_property<T: fmt::Debug + Clone + Send + Sync + 'static>(&mut self, property: T) {
+        self.properties
+            .insert(TypeId::of::<T>(), TypeErasedBox::new_with_clone(property));
     }
 }
 
@@ -43,10 +131,75 @@ impl Storable for EndpointResolverParams {
     type Storer = StoreReplace<Self>;
 }
 
+/// Config bag marker that asks the orchestrator to resolve the identity before the endpoint.
+///
+/// When it's set, the resolved [`Identity`](crate::client::identity::Identity) is added to the
+/// [`EndpointResolverParams`] as a property, so that endpoint resolution can depend on it, such as
+/// on the account ID of the credentials. The identity is resolved again when the request is signed,
+/// which usually returns the identity from the identity cache.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct ResolveIdentityBeforeEndpoint;
+
+impl ResolveIdentityBeforeEndpoint {
+    /// Creates a new `ResolveIdentityBeforeEndpoint`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Storable for ResolveIdentityBeforeEndpoint {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Config bag marker that stops the orchestrator from adding host prefixes to the endpoint.
+///
+/// Operations modeled with the `@endpoint` trait normally have a prefix, such as `data.`, added to
+/// the host of the resolved endpoint. Local emulators and test fakes are usually only reachable
+/// at the exact host that was configured, so the prefix needs to be left off when calling them.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct DisableHostPrefix;
+
+impl DisableHostPrefix {
+    /// Creates a new `DisableHostPrefix`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Storable for DisableHostPrefix {
+    type Storer = StoreReplace<Self>;
+}
+
 #[deprecated(note = "Renamed to ResolveEndpoint.")]
 pub use ResolveEndpoint as EndpointResolver;
 
 /// Configurable endpoint resolver implementation.
+///
+/// Endpoint resolution is asynchronous, so resolvers can await I/O, such as a service discovery
+/// lookup, before returning an endpoint. Resolvers that don't need to await anything can return
+/// [`EndpointFuture::ready`] to avoid allocating a future.
+///
+/// ```rust
+/// use aws_smithy_runtime_api::client::endpoint::{
+///     EndpointFuture, EndpointResolverParams, ResolveEndpoint,
+/// };
+/// use aws_smithy_types::endpoint::Endpoint;
+///
+/// # async fn lookup_host(_service: &str) -> Result<String, std::io::Error> { unimplemented!() }
+/// #[derive(Debug)]
+/// struct DiscoveryEndpointResolver;
+///
+/// impl ResolveEndpoint for DiscoveryEndpointResolver {
+///     fn resolve_endpoint<'a>(&'a self, _params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+///         EndpointFuture::new(async move {
+///             let host = lookup_host("example-service").await?;
+///             Ok(Endpoint::builder().url(format!("https://{host}")).build())
+///         })
+///     }
+/// }
+/// ```
 pub trait ResolveEndpoint: Send + Sync + fmt::Debug {
     /// Asynchronously resolves an endpoint to use from the given endpoint parameters.
     fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a>;
@@ -0,0 +1,282 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Structured reports of failed operations.
+//!
+//! When an [`OnError`] hook is in the config bag, the orchestrator calls it with an [`ErrorReport`]
+//! for every operation that fails. The report has the classified error along with its error code,
+//! request ID, the number of attempts, and the endpoint, so that applications can emit consistent
+//! error events without parsing the `Display` output of errors.
+
+use crate::client::interceptors::context::Error;
+use crate::client::result::SdkError;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use aws_smithy_types::error::metadata::{ErrorMetadata, ProvideErrorMetadata};
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::Arc;
+
+/// How an operation failed
+///
+/// Each kind matches the [`SdkError`] variant that the operation returns.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FailureKind {
+    /// The request failed during construction, and wasn't sent.
+    ConstructionFailure,
+    /// The request timed out.
+    TimeoutError,
+    /// The request failed during dispatch, and no HTTP response was received.
+    DispatchFailure,
+    /// A response was received, but it couldn't be parsed.
+    ResponseError,
+    /// The service responded with an error.
+    ServiceError,
+    /// The operation was cancelled before it completed.
+    Cancelled,
+}
+
+impl<E, R> From<&SdkError<E, R>> for FailureKind {
+    fn from(error: &SdkError<E, R>) -> Self {
+        match error {
+            SdkError::ConstructionFailure(_) => FailureKind::ConstructionFailure,
+            SdkError::TimeoutError(_) => FailureKind::TimeoutError,
+            SdkError::DispatchFailure(_) => FailureKind::DispatchFailure,
+            SdkError::ResponseError(_) => FailureKind::ResponseError,
+            SdkError::ServiceError(_) => FailureKind::ServiceError,
+            SdkError::Cancelled(_) => FailureKind::Cancelled,
+        }
+    }
+}
+
+/// A failed operation, along with everything that's known about why it failed
+#[derive(Debug)]
+pub struct ErrorReport<'a> {
+    service: &'a str,
+    operation: &'a str,
+    kind: FailureKind,
+    error: &'a (dyn StdError + 'static),
+    code: Option<&'a str>,
+    message: Option<&'a str>,
+    request_id: Option<&'a str>,
+    attempts: u32,
+    endpoint: Option<&'a str>,
+}
+
+impl<'a> ErrorReport<'a> {
+    /// Creates a report of the given failure of an operation.
+    pub fn new(
+        service: &'a str,
+        operation: &'a str,
+        kind: FailureKind,
+        error: &'a (dyn StdError + 'static),
+    ) -> Self {
+        Self {
+            service,
+            operation,
+            kind,
+            error,
+            code: None,
+            message: None,
+            request_id: None,
+            attempts: 0,
+            endpoint: None,
+        }
+    }
+
+    /// Sets the error code and message from the metadata of a service error.
+    pub fn with_error_metadata(mut self, metadata: &'a ErrorMetadata) -> Self {
+        self.code = metadata.code();
+        self.message = metadata.message();
+        self
+    }
+
+    /// Sets the request ID of the last response.
+    pub fn with_request_id(mut self, request_id: Option<&'a str>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    /// Sets the number of attempts that were made.
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Sets the URL of the endpoint that requests were sent to.
+    pub fn with_endpoint(mut self, endpoint: Option<&'a str>) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// Returns the name of the service.
+    pub fn service(&self) -> &'a str {
+        self.service
+    }
+
+    /// Returns the name of the operation.
+    pub fn operation(&self) -> &'a str {
+        self.operation
+    }
+
+    /// Returns how the operation failed.
+    pub fn kind(&self) -> FailureKind {
+        self.kind
+    }
+
+    /// Returns the error that the operation failed with.
+    pub fn error(&self) -> &'a (dyn StdError + 'static) {
+        self.error
+    }
+
+    /// Returns the error code that the service responded with, if it responded with an error.
+    pub fn code(&self) -> Option<&'a str> {
+        self.code
+    }
+
+    /// Returns the error message that the service responded with, if it responded with an error.
+    pub fn message(&self) -> Option<&'a str> {
+        self.message
+    }
+
+    /// Returns the request ID of the last response, if a response was received.
+    pub fn request_id(&self) -> Option<&'a str> {
+        self.request_id
+    }
+
+    /// Returns the number of attempts that were made.
+    ///
+    /// This is 0 when the operation failed before its first attempt.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns the URL of the endpoint that requests were sent to, if it was resolved.
+    pub fn endpoint(&self) -> Option<&'a str> {
+        self.endpoint
+    }
+}
+
+/// A hook that's called with a report of every failed operation
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_runtime_api::client::error_report::OnError;
+///
+/// let on_error = OnError::new(|report| {
+///     tracing::warn!(
+///         service = report.service(),
+///         operation = report.operation(),
+///         kind = ?report.kind(),
+///         code = report.code(),
+///         request_id = report.request_id(),
+///         attempts = report.attempts(),
+///         "operation failed",
+///     );
+/// });
+/// ```
+#[derive(Clone)]
+pub struct OnError(Arc<dyn Fn(&ErrorReport<'_>) + Send + Sync>);
+
+impl OnError {
+    /// Creates a hook that calls the given function.
+    pub fn new(hook: impl Fn(&ErrorReport<'_>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    /// Calls the hook with the given report.
+    pub fn report(&self, report: &ErrorReport<'_>) {
+        (self.0)(report)
+    }
+}
+
+impl fmt::Debug for OnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnError")
+    }
+}
+
+impl Storable for OnError {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Reads the [`ErrorMetadata`] of an operation's errors once they've been type erased.
+///
+/// Generated clients store this in the config bag of every operation, so that the orchestrator
+/// can report the error code and message of service errors.
+#[derive(Clone, Copy, Debug)]
+pub struct OperationErrorMetadata(fn(&Error) -> Option<&ErrorMetadata>);
+
+impl OperationErrorMetadata {
+    /// Creates an `OperationErrorMetadata` for operations whose errors are an `E`.
+    pub fn of<E>() -> Self
+    where
+        E: ProvideErrorMetadata + StdError + fmt::Debug + Send + Sync + 'static,
+    {
+        fn downcast<E>(error: &Error) -> Option<&ErrorMetadata>
+        where
+            E: ProvideErrorMetadata + StdError + fmt::Debug + Send + Sync + 'static,
+        {
+            error.downcast_ref::<E>().map(ProvideErrorMetadata::meta)
+        }
+        Self(downcast::<E>)
+    }
+
+    /// Returns the metadata of the given error, if it's an error of the operation.
+    pub fn error_metadata<'a>(&self, error: &'a Error) -> Option<&'a ErrorMetadata> {
+        (self.0)(error)
+    }
+}
+
+impl Storable for OperationErrorMetadata {
+    type Storer = StoreReplace<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn reports_the_metadata_of_operation_errors() {
+        let metadata = ErrorMetadata::builder()
+            .code("ThrottlingException")
+            .message("slow down")
+            .build();
+        let error = Error::erase(metadata);
+        let extracted = OperationErrorMetadata::of::<ErrorMetadata>()
+            .error_metadata(&error)
+            .expect("the error is an `ErrorMetadata`");
+        assert_eq!(Some("ThrottlingException"), extracted.code());
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let on_error = OnError::new({
+            let reported = reported.clone();
+            move |report| {
+                reported.lock().unwrap().push((
+                    report.kind(),
+                    report.code().map(str::to_string),
+                    report.attempts(),
+                ))
+            }
+        });
+        on_error.report(
+            &ErrorReport::new("Test", "TestOperation", FailureKind::ServiceError, &error)
+                .with_error_metadata(extracted)
+                .with_attempts(3),
+        );
+        assert_eq!(
+            vec![(
+                FailureKind::ServiceError,
+                Some("ThrottlingException".to_string()),
+                3
+            )],
+            *reported.lock().unwrap()
+        );
+
+        let sdk_error = SdkError::<(), ()>::timeout_error("timed out");
+        assert_eq!(FailureKind::TimeoutError, FailureKind::from(&sdk_error));
+    }
+}
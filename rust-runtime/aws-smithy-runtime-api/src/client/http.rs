@@ -55,7 +55,9 @@ use crate::client::result::ConnectorError;
 use crate::client::runtime_components::sealed::ValidateConfig;
 use crate::client::runtime_components::RuntimeComponents;
 use crate::impl_shared_conversions;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -163,6 +165,15 @@ pub trait HttpClient: Send + Sync + fmt::Debug {
         settings: &HttpConnectorSettings,
         components: &RuntimeComponents,
     ) -> SharedHttpConnector;
+
+    /// Releases the idle connections held by this client's connection pools.
+    ///
+    /// This is called when a client using this HTTP client is shut down. Connections that are in
+    /// use aren't affected, and the HTTP client remains usable afterwards. Since the connection
+    /// pools belong to the HTTP client, this also releases the idle connections of every other
+    /// client that shares it, which then need to reconnect. The default implementation does
+    /// nothing.
+    fn release_idle_connections(&self) {}
 }
 
 /// Shared HTTP client for use across multiple clients and requests.
@@ -188,6 +199,10 @@ impl HttpClient for SharedHttpClient {
     ) -> SharedHttpConnector {
         self.selector.http_connector(settings, components)
     }
+
+    fn release_idle_connections(&self) {
+        self.selector.release_idle_connections()
+    }
 }
 
 impl ValidateConfig for SharedHttpClient {}
@@ -199,7 +214,12 @@ impl_shared_conversions!(convert SharedHttpClient from HttpClient using SharedHt
 #[derive(Default, Debug)]
 pub struct HttpConnectorSettingsBuilder {
     connect_timeout: Option<Duration>,
+    tls_negotiation_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
+    http2: Option<Http2Settings>,
+    happy_eyeballs: Option<HappyEyeballs>,
+    pool: Option<PoolSettings>,
+    tcp: Option<TcpSettings>,
 }
 
 impl HttpConnectorSettingsBuilder {
@@ -224,6 +244,27 @@ impl HttpConnectorSettingsBuilder {
         self
     }
 
+    /// Sets the TLS negotiation timeout that should be used.
+    ///
+    /// The TLS negotiation timeout is a limit on the amount of time it takes to complete the TLS
+    /// handshake once a socket connection has been established.
+    pub fn tls_negotiation_timeout(mut self, tls_negotiation_timeout: Duration) -> Self {
+        self.tls_negotiation_timeout = Some(tls_negotiation_timeout);
+        self
+    }
+
+    /// Sets the TLS negotiation timeout that should be used.
+    ///
+    /// The TLS negotiation timeout is a limit on the amount of time it takes to complete the TLS
+    /// handshake once a socket connection has been established.
+    pub fn set_tls_negotiation_timeout(
+        &mut self,
+        tls_negotiation_timeout: Option<Duration>,
+    ) -> &mut Self {
+        self.tls_negotiation_timeout = tls_negotiation_timeout;
+        self
+    }
+
     /// Sets the read timeout that should be used.
     ///
     /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
@@ -242,11 +283,64 @@ impl HttpConnectorSettingsBuilder {
         self
     }
 
+    /// Sets the HTTP/2 settings that should be used.
+    pub fn http2_settings(mut self, http2_settings: Http2Settings) -> Self {
+        self.http2 = Some(http2_settings);
+        self
+    }
+
+    /// Sets the HTTP/2 settings that should be used.
+    pub fn set_http2_settings(&mut self, http2_settings: Option<Http2Settings>) -> &mut Self {
+        self.http2 = http2_settings;
+        self
+    }
+
+    /// Sets how connection attempts to hosts with both IPv6 and IPv4 addresses are raced.
+    pub fn happy_eyeballs(mut self, happy_eyeballs: HappyEyeballs) -> Self {
+        self.happy_eyeballs = Some(happy_eyeballs);
+        self
+    }
+
+    /// Sets how connection attempts to hosts with both IPv6 and IPv4 addresses are raced.
+    pub fn set_happy_eyeballs(&mut self, happy_eyeballs: Option<HappyEyeballs>) -> &mut Self {
+        self.happy_eyeballs = happy_eyeballs;
+        self
+    }
+
+    /// Sets the connection pool settings that should be used.
+    pub fn pool_settings(mut self, pool_settings: PoolSettings) -> Self {
+        self.pool = Some(pool_settings);
+        self
+    }
+
+    /// Sets the connection pool settings that should be used.
+    pub fn set_pool_settings(&mut self, pool_settings: Option<PoolSettings>) -> &mut Self {
+        self.pool = pool_settings;
+        self
+    }
+
+    /// Sets the TCP socket settings that should be used.
+    pub fn tcp_settings(mut self, tcp_settings: TcpSettings) -> Self {
+        self.tcp = Some(tcp_settings);
+        self
+    }
+
+    /// Sets the TCP socket settings that should be used.
+    pub fn set_tcp_settings(&mut self, tcp_settings: Option<TcpSettings>) -> &mut Self {
+        self.tcp = tcp_settings;
+        self
+    }
+
     /// Builds the [`HttpConnectorSettings`].
     pub fn build(self) -> HttpConnectorSettings {
         HttpConnectorSettings {
             connect_timeout: self.connect_timeout,
+            tls_negotiation_timeout: self.tls_negotiation_timeout,
             read_timeout: self.read_timeout,
+            http2: self.http2,
+            happy_eyeballs: self.happy_eyeballs,
+            pool: self.pool,
+            tcp: self.tcp,
         }
     }
 }
@@ -256,7 +350,12 @@ impl HttpConnectorSettingsBuilder {
 #[derive(Clone, Default, Debug)]
 pub struct HttpConnectorSettings {
     connect_timeout: Option<Duration>,
+    tls_negotiation_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
+    http2: Option<Http2Settings>,
+    happy_eyeballs: Option<HappyEyeballs>,
+    pool: Option<PoolSettings>,
+    tcp: Option<TcpSettings>,
 }
 
 impl HttpConnectorSettings {
@@ -272,6 +371,15 @@ impl HttpConnectorSettings {
         self.connect_timeout
     }
 
+    /// Returns the TLS negotiation timeout that should be used.
+    ///
+    /// The TLS negotiation timeout is a limit on the amount of time it takes to complete the TLS
+    /// handshake once a socket connection has been established. When it isn't set, the connect
+    /// timeout should cover the TLS handshake as well.
+    pub fn tls_negotiation_timeout(&self) -> Option<Duration> {
+        self.tls_negotiation_timeout
+    }
+
     /// Returns the read timeout that should be used.
     ///
     /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
@@ -279,6 +387,740 @@ impl HttpConnectorSettings {
     pub fn read_timeout(&self) -> Option<Duration> {
         self.read_timeout
     }
+
+    /// Returns the HTTP/2 settings that should be used.
+    ///
+    /// When this is `None`, the connector's default HTTP/2 settings should be used.
+    pub fn http2_settings(&self) -> Option<&Http2Settings> {
+        self.http2.as_ref()
+    }
+
+    /// Returns how connection attempts to hosts with both IPv6 and IPv4 addresses should be raced.
+    ///
+    /// When this is `None`, the connector's default behavior should be used.
+    pub fn happy_eyeballs(&self) -> Option<HappyEyeballs> {
+        self.happy_eyeballs
+    }
+
+    /// Returns the connection pool settings that should be used.
+    ///
+    /// When this is `None`, the connector's default pool settings should be used.
+    pub fn pool_settings(&self) -> Option<&PoolSettings> {
+        self.pool.as_ref()
+    }
+
+    /// Returns the TCP socket settings that should be used.
+    ///
+    /// When this is `None`, the connector's default socket settings should be used.
+    pub fn tcp_settings(&self) -> Option<&TcpSettings> {
+        self.tcp.as_ref()
+    }
+}
+
+/// Builder for [`Http2Settings`].
+#[non_exhaustive]
+#[derive(Clone, Default, Debug)]
+pub struct Http2SettingsBuilder {
+    prior_knowledge: Option<bool>,
+    initial_stream_window_size: Option<u32>,
+    initial_connection_window_size: Option<u32>,
+    max_concurrent_streams: Option<u32>,
+    keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Option<Duration>,
+    adaptive_window: Option<bool>,
+}
+
+impl Http2SettingsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets whether HTTP/2 should be used with prior knowledge.
+    ///
+    /// When enabled, every connection uses HTTP/2 without negotiating it first, so this
+    /// should only be enabled when the server is known to support HTTP/2. Defaults to `false`.
+    pub fn prior_knowledge(mut self, prior_knowledge: bool) -> Self {
+        self.prior_knowledge = Some(prior_knowledge);
+        self
+    }
+
+    /// Sets whether HTTP/2 should be used with prior knowledge.
+    ///
+    /// When enabled, every connection uses HTTP/2 without negotiating it first, so this
+    /// should only be enabled when the server is known to support HTTP/2. Defaults to `false`.
+    pub fn set_prior_knowledge(&mut self, prior_knowledge: Option<bool>) -> &mut Self {
+        self.prior_knowledge = prior_knowledge;
+        self
+    }
+
+    /// Sets the initial flow control window size, in bytes, for each stream.
+    pub fn initial_stream_window_size(mut self, size: u32) -> Self {
+        self.initial_stream_window_size = Some(size);
+        self
+    }
+
+    /// Sets the initial flow control window size, in bytes, for each stream.
+    pub fn set_initial_stream_window_size(&mut self, size: Option<u32>) -> &mut Self {
+        self.initial_stream_window_size = size;
+        self
+    }
+
+    /// Sets the initial flow control window size, in bytes, for each connection.
+    pub fn initial_connection_window_size(mut self, size: u32) -> Self {
+        self.initial_connection_window_size = Some(size);
+        self
+    }
+
+    /// Sets the initial flow control window size, in bytes, for each connection.
+    pub fn set_initial_connection_window_size(&mut self, size: Option<u32>) -> &mut Self {
+        self.initial_connection_window_size = size;
+        self
+    }
+
+    /// Sets the maximum number of concurrent streams to open on each connection.
+    ///
+    /// Requests over the limit are queued like requests over
+    /// [`PoolSettings::max_requests_per_host`], since all of the requests to a host share its
+    /// HTTP/2 connection. The limit applies to each host even when HTTP/2 isn't negotiated.
+    pub fn max_concurrent_streams(mut self, max_concurrent_streams: u32) -> Self {
+        self.max_concurrent_streams = Some(max_concurrent_streams);
+        self
+    }
+
+    /// Sets the maximum number of concurrent streams to open on each connection.
+    ///
+    /// Requests over the limit are queued like requests over
+    /// [`PoolSettings::max_requests_per_host`], since all of the requests to a host share its
+    /// HTTP/2 connection. The limit applies to each host even when HTTP/2 isn't negotiated.
+    pub fn set_max_concurrent_streams(&mut self, max_concurrent_streams: Option<u32>) -> &mut Self {
+        self.max_concurrent_streams = max_concurrent_streams;
+        self
+    }
+
+    /// Sets the interval at which keep-alive pings are sent on idle connections.
+    ///
+    /// Keep-alive pings are disabled when this isn't set.
+    pub fn keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Sets the interval at which keep-alive pings are sent on idle connections.
+    ///
+    /// Keep-alive pings are disabled when this isn't set.
+    pub fn set_keep_alive_interval(&mut self, interval: Option<Duration>) -> &mut Self {
+        self.keep_alive_interval = interval;
+        self
+    }
+
+    /// Sets how long to wait for a keep-alive ping to be acknowledged before closing the connection.
+    pub fn keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long to wait for a keep-alive ping to be acknowledged before closing the connection.
+    pub fn set_keep_alive_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.keep_alive_timeout = timeout;
+        self
+    }
+
+    /// Sets whether the flow control window sizes should be adjusted based on the measured
+    /// bandwidth-delay product.
+    ///
+    /// When enabled, the initial window sizes are ignored. Defaults to `false`.
+    pub fn adaptive_window(mut self, adaptive_window: bool) -> Self {
+        self.adaptive_window = Some(adaptive_window);
+        self
+    }
+
+    /// Sets whether the flow control window sizes should be adjusted based on the measured
+    /// bandwidth-delay product.
+    ///
+    /// When enabled, the initial window sizes are ignored. Defaults to `false`.
+    pub fn set_adaptive_window(&mut self, adaptive_window: Option<bool>) -> &mut Self {
+        self.adaptive_window = adaptive_window;
+        self
+    }
+
+    /// Builds the [`Http2Settings`].
+    pub fn build(self) -> Http2Settings {
+        Http2Settings {
+            prior_knowledge: self.prior_knowledge.unwrap_or_default(),
+            initial_stream_window_size: self.initial_stream_window_size,
+            initial_connection_window_size: self.initial_connection_window_size,
+            max_concurrent_streams: self.max_concurrent_streams,
+            keep_alive_interval: self.keep_alive_interval,
+            keep_alive_timeout: self.keep_alive_timeout,
+            adaptive_window: self.adaptive_window.unwrap_or_default(),
+        }
+    }
+}
+
+/// HTTP/2 settings for HTTP connectors.
+///
+/// These can be put into the config bag to configure the HTTP/2 settings of the default
+/// connectors. Settings that aren't set use the connector's defaults.
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct Http2Settings {
+    prior_knowledge: bool,
+    initial_stream_window_size: Option<u32>,
+    initial_connection_window_size: Option<u32>,
+    max_concurrent_streams: Option<u32>,
+    keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Option<Duration>,
+    adaptive_window: bool,
+}
+
+impl Http2Settings {
+    /// Returns a builder for `Http2Settings`.
+    pub fn builder() -> Http2SettingsBuilder {
+        Default::default()
+    }
+
+    /// Returns true if HTTP/2 should be used with prior knowledge.
+    pub fn prior_knowledge(&self) -> bool {
+        self.prior_knowledge
+    }
+
+    /// Returns the initial flow control window size, in bytes, for each stream.
+    pub fn initial_stream_window_size(&self) -> Option<u32> {
+        self.initial_stream_window_size
+    }
+
+    /// Returns the initial flow control window size, in bytes, for each connection.
+    pub fn initial_connection_window_size(&self) -> Option<u32> {
+        self.initial_connection_window_size
+    }
+
+    /// Returns the maximum number of concurrent streams to open on each connection.
+    pub fn max_concurrent_streams(&self) -> Option<u32> {
+        self.max_concurrent_streams
+    }
+
+    /// Returns the interval at which keep-alive pings are sent on idle connections.
+    pub fn keep_alive_interval(&self) -> Option<Duration> {
+        self.keep_alive_interval
+    }
+
+    /// Returns how long to wait for a keep-alive ping to be acknowledged.
+    pub fn keep_alive_timeout(&self) -> Option<Duration> {
+        self.keep_alive_timeout
+    }
+
+    /// Returns true if the flow control window sizes should be adjusted automatically.
+    pub fn adaptive_window(&self) -> bool {
+        self.adaptive_window
+    }
+}
+
+impl Storable for Http2Settings {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Settings for racing connection attempts to hosts with both IPv6 and IPv4 addresses.
+///
+/// This implements the "Happy Eyeballs" algorithm from [RFC 8305]. Connecting starts with
+/// the preferred address family, which is usually IPv6. If that attempt hasn't succeeded
+/// after the delay, an attempt with the other address family is started, and whichever
+/// connection is established first is used. This keeps a broken IPv6 or IPv4 network path
+/// from delaying every connection until the connect timeout elapses.
+///
+/// These can be put into the config bag to configure the default connectors.
+///
+/// [RFC 8305]: https://www.rfc-editor.org/rfc/rfc8305
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct HappyEyeballs {
+    delay: Option<Duration>,
+}
+
+impl HappyEyeballs {
+    /// The default delay before falling back to the other address family.
+    pub const DEFAULT_DELAY: Duration = Duration::from_millis(300);
+
+    /// Races the address families, starting the fallback attempt after `delay`.
+    pub fn new(delay: Duration) -> Self {
+        Self { delay: Some(delay) }
+    }
+
+    /// Disables racing, so that addresses are tried one after another.
+    pub fn disabled() -> Self {
+        Self { delay: None }
+    }
+
+    /// Returns the delay before falling back to the other address family.
+    ///
+    /// Returns `None` when racing is disabled.
+    pub fn delay(&self) -> Option<Duration> {
+        self.delay
+    }
+}
+
+impl Default for HappyEyeballs {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DELAY)
+    }
+}
+
+impl Storable for HappyEyeballs {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Builder for [`PoolSettings`].
+#[non_exhaustive]
+#[derive(Clone, Default, Debug)]
+pub struct PoolSettingsBuilder {
+    max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    max_connections_per_host: Option<usize>,
+    max_connection_lifetime: Option<Duration>,
+    max_requests_per_host: Option<usize>,
+    request_queue_timeout: Option<Duration>,
+}
+
+impl PoolSettingsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the maximum number of idle connections to keep open to each host.
+    ///
+    /// Setting this to zero disables connection reuse.
+    pub fn max_idle_per_host(mut self, max_idle_per_host: usize) -> Self {
+        self.max_idle_per_host = Some(max_idle_per_host);
+        self
+    }
+
+    /// Sets the maximum number of idle connections to keep open to each host.
+    ///
+    /// Setting this to zero disables connection reuse.
+    pub fn set_max_idle_per_host(&mut self, max_idle_per_host: Option<usize>) -> &mut Self {
+        self.max_idle_per_host = max_idle_per_host;
+        self
+    }
+
+    /// Sets how long an idle connection is kept open before it's closed.
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
+
+    /// Sets how long an idle connection is kept open before it's closed.
+    pub fn set_pool_idle_timeout(&mut self, pool_idle_timeout: Option<Duration>) -> &mut Self {
+        self.pool_idle_timeout = pool_idle_timeout;
+        self
+    }
+
+    /// Sets the maximum number of connections, idle or in use, to open to each host.
+    ///
+    /// Once the limit is reached, new connections wait for an existing connection to close.
+    /// That wait counts against the connect timeout. Not every connector supports this setting,
+    /// but the hyper connectors do.
+    pub fn max_connections_per_host(mut self, max_connections_per_host: usize) -> Self {
+        self.max_connections_per_host = Some(max_connections_per_host);
+        self
+    }
+
+    /// Sets the maximum number of connections, idle or in use, to open to each host.
+    ///
+    /// Once the limit is reached, new connections wait for an existing connection to close.
+    /// That wait counts against the connect timeout. Not every connector supports this setting,
+    /// but the hyper connectors do.
+    pub fn set_max_connections_per_host(
+        &mut self,
+        max_connections_per_host: Option<usize>,
+    ) -> &mut Self {
+        self.max_connections_per_host = max_connections_per_host;
+        self
+    }
+
+    /// Sets how long a connection can be used for before it's closed instead of being reused.
+    ///
+    /// This forces clients to periodically reconnect, so that load balancers that add
+    /// capacity can spread the connections out. Requests that are in flight when a connection
+    /// expires aren't interrupted. Not every connector supports this setting, but the hyper
+    /// connectors do.
+    pub fn max_connection_lifetime(mut self, max_connection_lifetime: Duration) -> Self {
+        self.max_connection_lifetime = Some(max_connection_lifetime);
+        self
+    }
+
+    /// Sets how long a connection can be used for before it's closed instead of being reused.
+    ///
+    /// This forces clients to periodically reconnect, so that load balancers that add
+    /// capacity can spread the connections out. Requests that are in flight when a connection
+    /// expires aren't interrupted. Not every connector supports this setting, but the hyper
+    /// connectors do.
+    pub fn set_max_connection_lifetime(
+        &mut self,
+        max_connection_lifetime: Option<Duration>,
+    ) -> &mut Self {
+        self.max_connection_lifetime = max_connection_lifetime;
+        self
+    }
+
+    /// Sets the maximum number of requests that can be in flight to each host at once.
+    ///
+    /// Requests over the limit are queued until a request to the same host has read its whole
+    /// response body. Unlike [`max_connections_per_host`](Self::max_connections_per_host), this also
+    /// bounds the number of requests that are multiplexed over HTTP/2 connections.
+    pub fn max_requests_per_host(mut self, max_requests_per_host: usize) -> Self {
+        self.max_requests_per_host = Some(max_requests_per_host);
+        self
+    }
+
+    /// Sets the maximum number of requests that can be in flight to each host at once.
+    ///
+    /// Requests over the limit are queued until a request to the same host has read its whole
+    /// response body. Unlike [`max_connections_per_host`](Self::max_connections_per_host), this also
+    /// bounds the number of requests that are multiplexed over HTTP/2 connections.
+    pub fn set_max_requests_per_host(&mut self, max_requests_per_host: Option<usize>) -> &mut Self {
+        self.max_requests_per_host = max_requests_per_host;
+        self
+    }
+
+    /// Sets how long a request can be queued by
+    /// [`max_requests_per_host`](Self::max_requests_per_host) before it fails with a timeout.
+    ///
+    /// When this isn't set, requests are queued until they can be sent.
+    pub fn request_queue_timeout(mut self, request_queue_timeout: Duration) -> Self {
+        self.request_queue_timeout = Some(request_queue_timeout);
+        self
+    }
+
+    /// Sets how long a request can be queued by
+    /// [`max_requests_per_host`](Self::max_requests_per_host) before it fails with a timeout.
+    ///
+    /// When this isn't set, requests are queued until they can be sent.
+    pub fn set_request_queue_timeout(
+        &mut self,
+        request_queue_timeout: Option<Duration>,
+    ) -> &mut Self {
+        self.request_queue_timeout = request_queue_timeout;
+        self
+    }
+
+    /// Builds the [`PoolSettings`].
+    pub fn build(self) -> PoolSettings {
+        PoolSettings {
+            max_idle_per_host: self.max_idle_per_host,
+            pool_idle_timeout: self.pool_idle_timeout,
+            max_connections_per_host: self.max_connections_per_host,
+            max_connection_lifetime: self.max_connection_lifetime,
+            max_requests_per_host: self.max_requests_per_host,
+            request_queue_timeout: self.request_queue_timeout,
+        }
+    }
+}
+
+/// Connection pool settings for HTTP connectors.
+///
+/// These can be put into the config bag to bound the number of sockets that the default
+/// connectors keep open. Settings that aren't set use the connector's defaults.
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct PoolSettings {
+    max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    max_connections_per_host: Option<usize>,
+    max_connection_lifetime: Option<Duration>,
+    max_requests_per_host: Option<usize>,
+    request_queue_timeout: Option<Duration>,
+}
+
+impl PoolSettings {
+    /// Returns a builder for `PoolSettings`.
+    pub fn builder() -> PoolSettingsBuilder {
+        Default::default()
+    }
+
+    /// Returns the maximum number of idle connections to keep open to each host.
+    pub fn max_idle_per_host(&self) -> Option<usize> {
+        self.max_idle_per_host
+    }
+
+    /// Returns how long an idle connection is kept open before it's closed.
+    pub fn pool_idle_timeout(&self) -> Option<Duration> {
+        self.pool_idle_timeout
+    }
+
+    /// Returns the maximum number of connections to open to each host.
+    pub fn max_connections_per_host(&self) -> Option<usize> {
+        self.max_connections_per_host
+    }
+
+    /// Returns how long a connection can be used for before it's closed instead of being reused.
+    pub fn max_connection_lifetime(&self) -> Option<Duration> {
+        self.max_connection_lifetime
+    }
+
+    /// Returns the maximum number of requests that can be in flight to each host at once.
+    pub fn max_requests_per_host(&self) -> Option<usize> {
+        self.max_requests_per_host
+    }
+
+    /// Returns how long a request can be queued before it fails with a timeout.
+    pub fn request_queue_timeout(&self) -> Option<Duration> {
+        self.request_queue_timeout
+    }
+}
+
+impl Storable for PoolSettings {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Builder for [`TcpSettings`].
+#[non_exhaustive]
+#[derive(Clone, Default, Debug)]
+pub struct TcpSettingsBuilder {
+    keepalive_time: Option<Duration>,
+    keepalive_interval: Option<Duration>,
+    keepalive_retries: Option<u32>,
+    nodelay: Option<bool>,
+    send_buffer_size: Option<usize>,
+    recv_buffer_size: Option<usize>,
+}
+
+impl TcpSettingsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets how long a connection must be idle before TCP keep-alive probes are sent.
+    ///
+    /// Setting any of the keep-alive settings enables `SO_KEEPALIVE`. Keep-alive settings that
+    /// aren't set use the operating system's defaults.
+    pub fn keepalive_time(mut self, keepalive_time: Duration) -> Self {
+        self.keepalive_time = Some(keepalive_time);
+        self
+    }
+
+    /// Sets how long a connection must be idle before TCP keep-alive probes are sent.
+    ///
+    /// Setting any of the keep-alive settings enables `SO_KEEPALIVE`. Keep-alive settings that
+    /// aren't set use the operating system's defaults.
+    pub fn set_keepalive_time(&mut self, keepalive_time: Option<Duration>) -> &mut Self {
+        self.keepalive_time = keepalive_time;
+        self
+    }
+
+    /// Sets the interval between TCP keep-alive probes.
+    ///
+    /// This is ignored on platforms that don't support configuring it.
+    pub fn keepalive_interval(mut self, keepalive_interval: Duration) -> Self {
+        self.keepalive_interval = Some(keepalive_interval);
+        self
+    }
+
+    /// Sets the interval between TCP keep-alive probes.
+    ///
+    /// This is ignored on platforms that don't support configuring it.
+    pub fn set_keepalive_interval(&mut self, keepalive_interval: Option<Duration>) -> &mut Self {
+        self.keepalive_interval = keepalive_interval;
+        self
+    }
+
+    /// Sets the number of unacknowledged TCP keep-alive probes to send before the connection
+    /// is considered dead.
+    ///
+    /// This is ignored on platforms that don't support configuring it, including Windows.
+    pub fn keepalive_retries(mut self, keepalive_retries: u32) -> Self {
+        self.keepalive_retries = Some(keepalive_retries);
+        self
+    }
+
+    /// Sets the number of unacknowledged TCP keep-alive probes to send before the connection
+    /// is considered dead.
+    ///
+    /// This is ignored on platforms that don't support configuring it, including Windows.
+    pub fn set_keepalive_retries(&mut self, keepalive_retries: Option<u32>) -> &mut Self {
+        self.keepalive_retries = keepalive_retries;
+        self
+    }
+
+    /// Sets whether `TCP_NODELAY` is set on connections, which disables Nagle's algorithm.
+    pub fn nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = Some(nodelay);
+        self
+    }
+
+    /// Sets whether `TCP_NODELAY` is set on connections, which disables Nagle's algorithm.
+    pub fn set_nodelay(&mut self, nodelay: Option<bool>) -> &mut Self {
+        self.nodelay = nodelay;
+        self
+    }
+
+    /// Sets the size of the socket's send buffer (`SO_SNDBUF`), in bytes.
+    pub fn send_buffer_size(mut self, send_buffer_size: usize) -> Self {
+        self.send_buffer_size = Some(send_buffer_size);
+        self
+    }
+
+    /// Sets the size of the socket's send buffer (`SO_SNDBUF`), in bytes.
+    pub fn set_send_buffer_size(&mut self, send_buffer_size: Option<usize>) -> &mut Self {
+        self.send_buffer_size = send_buffer_size;
+        self
+    }
+
+    /// Sets the size of the socket's receive buffer (`SO_RCVBUF`), in bytes.
+    pub fn recv_buffer_size(mut self, recv_buffer_size: usize) -> Self {
+        self.recv_buffer_size = Some(recv_buffer_size);
+        self
+    }
+
+    /// Sets the size of the socket's receive buffer (`SO_RCVBUF`), in bytes.
+    pub fn set_recv_buffer_size(&mut self, recv_buffer_size: Option<usize>) -> &mut Self {
+        self.recv_buffer_size = recv_buffer_size;
+        self
+    }
+
+    /// Builds the [`TcpSettings`].
+    pub fn build(self) -> TcpSettings {
+        TcpSettings {
+            keepalive_time: self.keepalive_time,
+            keepalive_interval: self.keepalive_interval,
+            keepalive_retries: self.keepalive_retries,
+            nodelay: self.nodelay,
+            send_buffer_size: self.send_buffer_size,
+            recv_buffer_size: self.recv_buffer_size,
+        }
+    }
+}
+
+/// TCP socket settings for HTTP connectors.
+///
+/// These can be put into the config bag to tune the sockets opened by the default connectors.
+/// Settings that aren't set use the connector's defaults.
+#[non_exhaustive]
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct TcpSettings {
+    keepalive_time: Option<Duration>,
+    keepalive_interval: Option<Duration>,
+    keepalive_retries: Option<u32>,
+    nodelay: Option<bool>,
+    send_buffer_size: Option<usize>,
+    recv_buffer_size: Option<usize>,
+}
+
+impl TcpSettings {
+    /// Returns a builder for `TcpSettings`.
+    pub fn builder() -> TcpSettingsBuilder {
+        Default::default()
+    }
+
+    /// Returns true if any of the TCP keep-alive settings are set.
+    pub fn keepalive_enabled(&self) -> bool {
+        self.keepalive_time.is_some()
+            || self.keepalive_interval.is_some()
+            || self.keepalive_retries.is_some()
+    }
+
+    /// Returns how long a connection must be idle before TCP keep-alive probes are sent.
+    pub fn keepalive_time(&self) -> Option<Duration> {
+        self.keepalive_time
+    }
+
+    /// Returns the interval between TCP keep-alive probes.
+    pub fn keepalive_interval(&self) -> Option<Duration> {
+        self.keepalive_interval
+    }
+
+    /// Returns the number of unacknowledged TCP keep-alive probes to send.
+    pub fn keepalive_retries(&self) -> Option<u32> {
+        self.keepalive_retries
+    }
+
+    /// Returns whether `TCP_NODELAY` should be set on connections.
+    pub fn nodelay(&self) -> Option<bool> {
+        self.nodelay
+    }
+
+    /// Returns the size of the socket's send buffer, in bytes.
+    pub fn send_buffer_size(&self) -> Option<usize> {
+        self.send_buffer_size
+    }
+
+    /// Returns the size of the socket's receive buffer, in bytes.
+    pub fn recv_buffer_size(&self) -> Option<usize> {
+        self.recv_buffer_size
+    }
+}
+
+impl Storable for TcpSettings {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Counts the bytes of HTTP request and response bodies that were sent and received.
+///
+/// The orchestrator stores one of these in the config bag for every attempt, so that interceptors
+/// can load the counts for the current attempt, and an [`OperationBytesTransferred`] with the
+/// counts for all of the operation's attempts.
+///
+/// Bytes are counted as the HTTP client reads the request body, and as the response body is
+/// read, so the counts for a streaming response keep increasing after the operation has returned
+/// its output. Headers, and the framing and encryption overhead of the protocol, aren't counted.
+#[derive(Clone, Debug, Default)]
+pub struct BytesTransferred {
+    sent: Arc<AtomicU64>,
+    received: Arc<AtomicU64>,
+}
+
+impl BytesTransferred {
+    /// Creates a new counter, with no bytes sent or received.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of body bytes that were sent.
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of body bytes that were received.
+    pub fn received(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+
+    /// Records that `len` body bytes were sent.
+    pub fn record_sent(&self, len: u64) {
+        self.sent.fetch_add(len, Ordering::Relaxed);
+    }
+
+    /// Records that `len` body bytes were received.
+    pub fn record_received(&self, len: u64) {
+        self.received.fetch_add(len, Ordering::Relaxed);
+    }
+}
+
+impl Storable for BytesTransferred {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Counts the bytes of HTTP bodies that were sent and received by all of an operation's attempts.
+///
+/// See [`BytesTransferred`] for what's counted.
+#[derive(Clone, Debug, Default)]
+pub struct OperationBytesTransferred(BytesTransferred);
+
+impl OperationBytesTransferred {
+    /// Creates a new counter, with no bytes sent or received.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the counts for the operation.
+    pub fn bytes(&self) -> &BytesTransferred {
+        &self.0
+    }
+}
+
+impl Storable for OperationBytesTransferred {
+    type Storer = StoreReplace<Self>;
 }
 
 #[cfg(test)]
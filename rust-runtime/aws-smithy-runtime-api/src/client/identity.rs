@@ -54,6 +54,20 @@ impl IdentityCachePartition {
     }
 }
 
+/// Where identities resolved by an identity resolver are cached.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum IdentityCacheLocation {
+    /// Identities are cached by the [identity cache](ResolveCachedIdentity) in runtime components.
+    RuntimeComponents,
+    /// Identities are cached by the identity resolver itself, and the identity cache in runtime
+    /// components is bypassed.
+    ///
+    /// This is useful for resolvers whose identities depend on the request, such as session
+    /// credentials that are scoped to the resource a request is sent to.
+    IdentityResolver,
+}
+
 /// Caching resolver for identities.
 pub trait ResolveCachedIdentity: fmt::Debug + Send + Sync {
     /// Returns a cached identity, or resolves an identity and caches it if its not already cached.
@@ -94,6 +108,12 @@ pub trait ResolveCachedIdentity: fmt::Debug + Send + Sync {
         let _ = (runtime_components, cfg);
         Ok(())
     }
+
+    /// Discards all cached identities, so that the next request resolves a new identity.
+    ///
+    /// This is useful after credentials were rotated outside of the identity resolver's knowledge.
+    /// Caches that don't cache identities don't need to implement this.
+    fn invalidate(&self) {}
 }
 
 /// Shared identity cache.
@@ -117,6 +137,10 @@ impl ResolveCachedIdentity for SharedIdentityCache {
         self.0
             .resolve_cached_identity(resolver, runtime_components, config_bag)
     }
+
+    fn invalidate(&self) {
+        self.0.invalidate()
+    }
 }
 
 impl ValidateConfig for SharedIdentityCache {
@@ -172,6 +196,13 @@ pub trait ResolveIdentity: Send + Sync + Debug {
     fn fallback_on_interrupt(&self) -> Option<Identity> {
         None
     }
+
+    /// Returns where the identities resolved by this resolver are cached.
+    ///
+    /// Defaults to the identity cache in runtime components.
+    fn cache_location(&self) -> IdentityCacheLocation {
+        IdentityCacheLocation::RuntimeComponents
+    }
 }
 
 /// Container for a shared identity resolver.
@@ -207,6 +238,10 @@ impl ResolveIdentity for SharedIdentityResolver {
     ) -> IdentityFuture<'a> {
         self.inner.resolve_identity(runtime_components, config_bag)
     }
+
+    fn cache_location(&self) -> IdentityCacheLocation {
+        self.inner.cache_location()
+    }
 }
 
 impl_shared_conversions!(convert SharedIdentityResolver from ResolveIdentity using SharedIdentityResolver::new);
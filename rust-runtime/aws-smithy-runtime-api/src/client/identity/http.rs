@@ -5,8 +5,10 @@
 
 //! Identity types for HTTP auth
 
+use crate::box_error::BoxError;
 use crate::client::identity::{Identity, IdentityFuture, ResolveIdentity};
 use crate::client::runtime_components::RuntimeComponents;
+use crate::impl_shared_conversions;
 use aws_smithy_types::config_bag::ConfigBag;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -47,6 +49,11 @@ impl Token {
     pub fn token(&self) -> &str {
         &self.0.token
     }
+
+    /// Returns the expiration time of this token (if any)
+    pub fn expiration(&self) -> Option<SystemTime> {
+        self.0.expiration
+    }
 }
 
 impl From<&str> for Token {
@@ -74,6 +81,63 @@ impl ResolveIdentity for Token {
     }
 }
 
+new_type_future! {
+    #[doc = "Future for [`ProvideToken::provide_token`]."]
+    pub struct TokenFuture<'a, Token, BoxError>;
+}
+
+/// Provider for the tokens of Smithy's token-based HTTP auth schemes, such as OAuth access tokens
+/// or JWTs for `@httpBearerAuth`.
+///
+/// Providers are wrapped in a [`SharedTokenProvider`] to be used as identity resolvers. Resolved
+/// tokens are cached by the client's identity cache until shortly before they expire, so a
+/// provider is only called when there's no valid token, and it can fetch or refresh a token every
+/// time it's called. Tokens without an expiration are cached for the identity cache's default
+/// duration.
+pub trait ProvideToken: Send + Sync + Debug {
+    /// Returns a future that provides a token.
+    fn provide_token<'a>(&'a self) -> TokenFuture<'a>;
+}
+
+impl ProvideToken for Token {
+    fn provide_token<'a>(&'a self) -> TokenFuture<'a> {
+        TokenFuture::ready(Ok(self.clone()))
+    }
+}
+
+/// A shared token provider, which is also an identity resolver for the tokens it provides.
+#[derive(Clone, Debug)]
+pub struct SharedTokenProvider(Arc<dyn ProvideToken>);
+
+impl SharedTokenProvider {
+    /// Creates a new [`SharedTokenProvider`] from the given provider.
+    pub fn new(provider: impl ProvideToken + 'static) -> Self {
+        Self(Arc::new(provider))
+    }
+}
+
+impl ProvideToken for SharedTokenProvider {
+    fn provide_token<'a>(&'a self) -> TokenFuture<'a> {
+        self.0.provide_token()
+    }
+}
+
+impl ResolveIdentity for SharedTokenProvider {
+    fn resolve_identity<'a>(
+        &'a self,
+        _runtime_components: &'a RuntimeComponents,
+        _config_bag: &'a ConfigBag,
+    ) -> IdentityFuture<'a> {
+        IdentityFuture::new(async move {
+            let token = self.provide_token().await?;
+            let expiration = token.expiration();
+            Ok(Identity::new(token, expiration))
+        })
+    }
+}
+
+impl_shared_conversions!(convert SharedTokenProvider from ProvideToken using SharedTokenProvider::new);
+
 /// Identity type required to sign requests using Smithy's login-based HTTP auth schemes
 ///
 /// This `Login` type is used with Smithy's `@httpBasicAuth` and `@httpDigestAuth`
@@ -27,6 +27,7 @@
 //! recommended for storing request-specific information in your interceptor implementation.
 //! Use the [`ConfigBag`] instead.
 
+use crate::client::error_report::FailureKind;
 use crate::client::orchestrator::{HttpRequest, HttpResponse, OrchestratorError};
 use crate::client::result::SdkError;
 use aws_smithy_types::config_bag::ConfigBag;
@@ -383,6 +384,17 @@ where
             .map_err(|error| OrchestratorError::into_sdk_error(error, &phase, response))
     }
 
+    /// Returns how the operation failed, if it has failed.
+    ///
+    /// This is the kind of [`SdkError`] that [`finalize`](Self::finalize) returns.
+    #[doc(hidden)]
+    pub fn failure_kind(&self) -> Option<FailureKind> {
+        match &self.output_or_error {
+            Some(Err(error)) => Some(error.failure_kind(&self.phase, self.response.is_some())),
+            _ => None,
+        }
+    }
+
     /// Mark this context as failed due to errors during the operation. Any errors already contained
     /// by the context will be replaced by the given error.
     pub fn fail(&mut self, error: OrchestratorError<E>) {
@@ -0,0 +1,216 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Where the time of an operation's attempts was spent.
+//!
+//! The orchestrator records a [`LatencyBreakdown`] for every operation. It's added to the
+//! extensions of the operation's final HTTP response, so it can be read from errors that have a
+//! raw response with [`ProvideLatency`], and generated clients also make it available on their
+//! operation outputs.
+
+use crate::client::orchestrator::HttpResponse;
+use crate::client::result::SdkError;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::time::Duration;
+
+/// How long each part of a single attempt took.
+///
+/// Every duration is optional, since not every part happens on every attempt, and the connection
+/// timings are only known when the HTTP client reports them. When a pooled connection is reused,
+/// no time is spent on name resolution, connecting, or the TLS handshake, so those are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AttemptLatency {
+    dns: Option<Duration>,
+    connect: Option<Duration>,
+    tls_handshake: Option<Duration>,
+    time_to_first_byte: Option<Duration>,
+    deserialization: Option<Duration>,
+    total: Option<Duration>,
+}
+
+impl AttemptLatency {
+    /// Returns a builder for `AttemptLatency`.
+    pub fn builder() -> AttemptLatencyBuilder {
+        AttemptLatencyBuilder::default()
+    }
+
+    /// Returns how long it took to resolve the name of the host.
+    pub fn dns(&self) -> Option<Duration> {
+        self.dns
+    }
+
+    /// Returns how long it took to establish the TCP connection.
+    ///
+    /// This includes name resolution when the HTTP client doesn't report it separately.
+    pub fn connect(&self) -> Option<Duration> {
+        self.connect
+    }
+
+    /// Returns how long the TLS handshake took.
+    pub fn tls_handshake(&self) -> Option<Duration> {
+        self.tls_handshake
+    }
+
+    /// Returns how long it took from handing the request to the HTTP client until the response
+    /// headers were received, including the time it took to connect.
+    pub fn time_to_first_byte(&self) -> Option<Duration> {
+        self.time_to_first_byte
+    }
+
+    /// Returns how long it took to deserialize the response, including reading the response body
+    /// of operations that don't stream it.
+    pub fn deserialization(&self) -> Option<Duration> {
+        self.deserialization
+    }
+
+    /// Returns how long the whole attempt took.
+    pub fn total(&self) -> Option<Duration> {
+        self.total
+    }
+}
+
+/// Builder for [`AttemptLatency`]
+#[derive(Clone, Debug, Default)]
+pub struct AttemptLatencyBuilder {
+    latency: AttemptLatency,
+}
+
+macro_rules! latency_setters {
+    ($($field:ident, $set_field:ident, $doc:literal;)+) => {
+        $(
+            #[doc = $doc]
+            pub fn $field(mut self, $field: Duration) -> Self {
+                self.$set_field(Some($field));
+                self
+            }
+
+            #[doc = $doc]
+            pub fn $set_field(&mut self, $field: Option<Duration>) -> &mut Self {
+                self.latency.$field = $field;
+                self
+            }
+        )+
+    };
+}
+
+impl AttemptLatencyBuilder {
+    latency_setters! {
+        dns, set_dns, "Sets how long it took to resolve the name of the host.";
+        connect, set_connect, "Sets how long it took to establish the TCP connection.";
+        tls_handshake, set_tls_handshake, "Sets how long the TLS handshake took.";
+        time_to_first_byte, set_time_to_first_byte, "Sets how long it took to receive the response headers.";
+        deserialization, set_deserialization, "Sets how long it took to deserialize the response.";
+        total, set_total, "Sets how long the whole attempt took.";
+    }
+
+    /// Builds the `AttemptLatency`.
+    pub fn build(self) -> AttemptLatency {
+        self.latency
+    }
+}
+
+/// How long an operation took, and where the time of each of its attempts was spent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyBreakdown {
+    attempts: Vec<AttemptLatency>,
+    total: Option<Duration>,
+}
+
+impl LatencyBreakdown {
+    /// Creates an empty `LatencyBreakdown`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the latency of each attempt, in the order they were made.
+    pub fn attempts(&self) -> &[AttemptLatency] {
+        &self.attempts
+    }
+
+    /// Returns the latency of the last attempt, which is the one that the result came from.
+    pub fn last_attempt(&self) -> Option<&AttemptLatency> {
+        self.attempts.last()
+    }
+
+    /// Returns how long the whole operation took, including retries and the time spent waiting
+    /// between them.
+    pub fn total(&self) -> Option<Duration> {
+        self.total
+    }
+
+    /// Adds the latency of an attempt.
+    pub fn push_attempt(&mut self, attempt: AttemptLatency) {
+        self.attempts.push(attempt);
+    }
+
+    /// Sets how long the whole operation took.
+    pub fn set_total(&mut self, total: Option<Duration>) {
+        self.total = total;
+    }
+}
+
+impl Storable for LatencyBreakdown {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Provides the [`LatencyBreakdown`] of the operation that something came from.
+pub trait ProvideLatency {
+    /// Returns the latency breakdown of the operation, if it's known.
+    fn latency(&self) -> Option<&LatencyBreakdown>;
+}
+
+impl ProvideLatency for HttpResponse {
+    fn latency(&self) -> Option<&LatencyBreakdown> {
+        self.extension::<LatencyBreakdown>()
+    }
+}
+
+/// The latency breakdown is only available for errors that carry the raw response, so it isn't
+/// available for errors like timeouts or dispatch failures.
+impl<E> ProvideLatency for SdkError<E, HttpResponse> {
+    fn latency(&self) -> Option<&LatencyBreakdown> {
+        self.raw_response().and_then(ProvideLatency::latency)
+    }
+}
+
+impl<T: ProvideLatency> ProvideLatency for Option<T> {
+    fn latency(&self) -> Option<&LatencyBreakdown> {
+        self.as_ref().and_then(ProvideLatency::latency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_types::body::SdkBody;
+
+    #[test]
+    fn latency_is_read_from_the_response() {
+        let mut latency = LatencyBreakdown::new();
+        latency.push_attempt(
+            AttemptLatency::builder()
+                .time_to_first_byte(Duration::from_millis(20))
+                .total(Duration::from_millis(25))
+                .build(),
+        );
+        latency.set_total(Some(Duration::from_millis(30)));
+
+        let mut response = HttpResponse::new(
+            http::StatusCode::INTERNAL_SERVER_ERROR.into(),
+            SdkBody::empty(),
+        );
+        assert_eq!(None, response.latency());
+        response.add_extension(latency.clone());
+        assert_eq!(Some(&latency), response.latency());
+
+        let err = SdkError::<(), _>::response_error("failed", response);
+        let last_attempt = err.latency().and_then(LatencyBreakdown::last_attempt);
+        assert_eq!(
+            Some(Duration::from_millis(20)),
+            last_attempt.and_then(AttemptLatency::time_to_first_byte)
+        );
+        assert_eq!(None, last_attempt.and_then(AttemptLatency::dns));
+    }
+}
@@ -0,0 +1,272 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Observers of the lifecycle of operations and their attempts.
+//!
+//! An [`ObserveLifecycle`] implementation is told when each operation and attempt starts and
+//! ends, along with how long it took and whether it failed. It's a simpler alternative to
+//! implementing [`Intercept`](crate::client::interceptors::Intercept) for applications that only
+//! need to record telemetry, since observers can't modify requests or responses.
+
+use crate::client::error_report::FailureKind;
+use crate::impl_shared_conversions;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An operation that's starting
+#[derive(Debug)]
+pub struct OperationStart<'a> {
+    service: &'a str,
+    operation: &'a str,
+}
+
+impl<'a> OperationStart<'a> {
+    /// Creates a new `OperationStart`.
+    pub fn new(service: &'a str, operation: &'a str) -> Self {
+        Self { service, operation }
+    }
+
+    /// Returns the name of the service.
+    pub fn service(&self) -> &'a str {
+        self.service
+    }
+
+    /// Returns the name of the operation.
+    pub fn operation(&self) -> &'a str {
+        self.operation
+    }
+}
+
+/// An attempt of an operation that's starting
+#[derive(Debug)]
+pub struct AttemptStart<'a> {
+    service: &'a str,
+    operation: &'a str,
+    attempt: u32,
+}
+
+impl<'a> AttemptStart<'a> {
+    /// Creates a new `AttemptStart` for the given attempt, starting at 1.
+    pub fn new(service: &'a str, operation: &'a str, attempt: u32) -> Self {
+        Self {
+            service,
+            operation,
+            attempt,
+        }
+    }
+
+    /// Returns the name of the service.
+    pub fn service(&self) -> &'a str {
+        self.service
+    }
+
+    /// Returns the name of the operation.
+    pub fn operation(&self) -> &'a str {
+        self.operation
+    }
+
+    /// Returns the number of the attempt, starting at 1.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+/// An attempt of an operation that has ended
+#[derive(Debug)]
+pub struct AttemptEnd<'a> {
+    service: &'a str,
+    operation: &'a str,
+    attempt: u32,
+    duration: Duration,
+    failure: Option<FailureKind>,
+}
+
+impl<'a> AttemptEnd<'a> {
+    /// Creates a new `AttemptEnd` for the given attempt, starting at 1.
+    ///
+    /// `failure` is how the attempt failed, or `None` if it succeeded.
+    pub fn new(
+        service: &'a str,
+        operation: &'a str,
+        attempt: u32,
+        duration: Duration,
+        failure: Option<FailureKind>,
+    ) -> Self {
+        Self {
+            service,
+            operation,
+            attempt,
+            duration,
+            failure,
+        }
+    }
+
+    /// Returns the name of the service.
+    pub fn service(&self) -> &'a str {
+        self.service
+    }
+
+    /// Returns the name of the operation.
+    pub fn operation(&self) -> &'a str {
+        self.operation
+    }
+
+    /// Returns the number of the attempt, starting at 1.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Returns how long the attempt took.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns how the attempt failed, or `None` if it succeeded.
+    pub fn failure(&self) -> Option<FailureKind> {
+        self.failure
+    }
+}
+
+/// An operation that has ended
+#[derive(Debug)]
+pub struct OperationEnd<'a> {
+    service: &'a str,
+    operation: &'a str,
+    attempts: u32,
+    duration: Duration,
+    failure: Option<FailureKind>,
+}
+
+impl<'a> OperationEnd<'a> {
+    /// Creates a new `OperationEnd`.
+    ///
+    /// `failure` is how the operation failed, or `None` if it succeeded.
+    pub fn new(
+        service: &'a str,
+        operation: &'a str,
+        attempts: u32,
+        duration: Duration,
+        failure: Option<FailureKind>,
+    ) -> Self {
+        Self {
+            service,
+            operation,
+            attempts,
+            duration,
+            failure,
+        }
+    }
+
+    /// Returns the name of the service.
+    pub fn service(&self) -> &'a str {
+        self.service
+    }
+
+    /// Returns the name of the operation.
+    pub fn operation(&self) -> &'a str {
+        self.operation
+    }
+
+    /// Returns the number of attempts that were made.
+    ///
+    /// This is 0 when the operation failed before its first attempt.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns how long the operation took, including retries and backoff.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns how the operation failed, or `None` if it succeeded.
+    pub fn failure(&self) -> Option<FailureKind> {
+        self.failure
+    }
+}
+
+/// Observes when operations and their attempts start and end.
+///
+/// Every method does nothing by default, so implementations only need to implement the events
+/// they're interested in. Observers are called by the orchestrator while the operation runs, so
+/// they should return quickly.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_runtime_api::client::lifecycle::{ObserveLifecycle, OperationEnd};
+///
+/// #[derive(Debug)]
+/// struct LogSlowOperations;
+///
+/// impl ObserveLifecycle for LogSlowOperations {
+///     fn on_operation_end(&self, operation: &OperationEnd<'_>) {
+///         if operation.duration().as_secs() >= 1 {
+///             tracing::warn!(
+///                 operation = operation.operation(),
+///                 attempts = operation.attempts(),
+///                 "slow operation",
+///             );
+///         }
+///     }
+/// }
+/// ```
+pub trait ObserveLifecycle: Send + Sync + fmt::Debug {
+    /// Called when an operation starts, once its configuration has been applied.
+    fn on_operation_start(&self, operation: &OperationStart<'_>) {
+        let _ = operation;
+    }
+
+    /// Called when an attempt starts, after any backoff before it.
+    fn on_attempt_start(&self, attempt: &AttemptStart<'_>) {
+        let _ = attempt;
+    }
+
+    /// Called when an attempt ends, whether it succeeded or failed.
+    fn on_attempt_end(&self, attempt: &AttemptEnd<'_>) {
+        let _ = attempt;
+    }
+
+    /// Called when an operation ends, whether it succeeded or failed.
+    fn on_operation_end(&self, operation: &OperationEnd<'_>) {
+        let _ = operation;
+    }
+}
+
+/// A shared [`ObserveLifecycle`] implementation that can be stored in the config bag.
+#[derive(Clone, Debug)]
+pub struct SharedLifecycleObserver(Arc<dyn ObserveLifecycle>);
+
+impl SharedLifecycleObserver {
+    /// Creates a new `SharedLifecycleObserver`.
+    pub fn new(observer: impl ObserveLifecycle + 'static) -> Self {
+        Self(Arc::new(observer))
+    }
+}
+
+impl ObserveLifecycle for SharedLifecycleObserver {
+    fn on_operation_start(&self, operation: &OperationStart<'_>) {
+        self.0.on_operation_start(operation)
+    }
+
+    fn on_attempt_start(&self, attempt: &AttemptStart<'_>) {
+        self.0.on_attempt_start(attempt)
+    }
+
+    fn on_attempt_end(&self, attempt: &AttemptEnd<'_>) {
+        self.0.on_attempt_end(attempt)
+    }
+
+    fn on_operation_end(&self, operation: &OperationEnd<'_>) {
+        self.0.on_operation_end(operation)
+    }
+}
+
+impl Storable for SharedLifecycleObserver {
+    type Storer = StoreReplace<Self>;
+}
+
+impl_shared_conversions!(convert SharedLifecycleObserver from ObserveLifecycle using SharedLifecycleObserver::new);
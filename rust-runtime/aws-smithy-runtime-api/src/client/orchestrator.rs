@@ -17,6 +17,7 @@
 //! can read and modify the input, request, response, or output/error.
 
 use crate::box_error::BoxError;
+use crate::client::error_report::FailureKind;
 use crate::client::interceptors::context::phase::Phase;
 use crate::client::interceptors::context::Error;
 use crate::client::interceptors::InterceptorError;
@@ -56,6 +57,32 @@ impl Storable for LoadedRequestBody {
     type Storer = StoreReplace<Self>;
 }
 
+/// Marker type stored in the config bag to indicate that streaming response bodies should be
+/// loaded into memory before they are returned.
+///
+/// Normally, a streaming response body is handed to the caller as soon as the response headers
+/// arrive, so a connection failure while the body is being read surfaces to the caller without
+/// being retried. When this marker is present, the orchestrator reads the entire body within the
+/// attempt instead, so that a failure while reading it is classified as a transient response
+/// error and retried by the retry strategy.
+///
+/// Since the entire body is held in memory, this should only be enabled for operations whose
+/// responses are known to be reasonably small.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct BufferStreamingResponse;
+
+impl BufferStreamingResponse {
+    /// Creates a new `BufferStreamingResponse` marker.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Storable for BufferStreamingResponse {
+    type Storer = StoreReplace<Self>;
+}
+
 /// Marker type stored in the config bag to indicate that a response body should be redacted.
 #[derive(Debug)]
 pub struct SensitiveOutput;
@@ -72,6 +99,8 @@ enum ErrorKind<E> {
     Operation { err: E },
     /// An error that occurs when a request times out.
     Timeout { source: BoxError },
+    /// An error that occurs when an operation is cancelled.
+    Cancelled { source: BoxError },
     /// An error that occurs when request dispatch fails.
     Connector { source: ConnectorError },
     /// An error that occurs when a response can't be deserialized.
@@ -140,6 +169,18 @@ impl<E> OrchestratorError<E> {
         matches!(self.kind, ErrorKind::Timeout { .. })
     }
 
+    /// Create a cancellation error with the given source.
+    pub fn cancelled(source: BoxError) -> Self {
+        Self {
+            kind: ErrorKind::Cancelled { source },
+        }
+    }
+
+    /// True if the underlying error is a cancellation error.
+    pub fn is_cancelled_error(&self) -> bool {
+        matches!(self.kind, ErrorKind::Cancelled { .. })
+    }
+
     /// Create a response error with the given source.
     pub fn response(source: BoxError) -> Self {
         Self {
@@ -200,6 +241,7 @@ impl<E> OrchestratorError<E> {
             }
             ErrorKind::Connector { source } => SdkError::dispatch_failure(source),
             ErrorKind::Timeout { source } => SdkError::timeout_error(source),
+            ErrorKind::Cancelled { source } => SdkError::cancelled(source),
             ErrorKind::Response { source } => SdkError::response_error(source, response.unwrap()),
             ErrorKind::Other { source } => {
                 use Phase::*;
@@ -214,6 +256,31 @@ impl<E> OrchestratorError<E> {
         }
     }
 
+    /// Returns the kind of [`SdkError`] that [`into_sdk_error`](Self::into_sdk_error) converts this into.
+    pub(crate) fn failure_kind(&self, phase: &Phase, has_response: bool) -> FailureKind {
+        use Phase::*;
+        match &self.kind {
+            ErrorKind::Interceptor { .. } | ErrorKind::Other { .. } => match phase {
+                BeforeSerialization | Serialization => FailureKind::ConstructionFailure,
+                BeforeTransmit | Transmit => match &self.kind {
+                    ErrorKind::Other { source } if source.is::<ConnectorError>() => {
+                        FailureKind::DispatchFailure
+                    }
+                    _ if has_response => FailureKind::ResponseError,
+                    _ => FailureKind::DispatchFailure,
+                },
+                BeforeDeserialization | Deserialization | AfterDeserialization => {
+                    FailureKind::ResponseError
+                }
+            },
+            ErrorKind::Operation { .. } => FailureKind::ServiceError,
+            ErrorKind::Connector { .. } => FailureKind::DispatchFailure,
+            ErrorKind::Timeout { .. } => FailureKind::TimeoutError,
+            ErrorKind::Cancelled { .. } => FailureKind::Cancelled,
+            ErrorKind::Response { .. } => FailureKind::ResponseError,
+        }
+    }
+
     /// Maps the error type in `ErrorKind::Operation`
     #[doc(hidden)]
     pub fn map_operation_error<E2>(self, map: impl FnOnce(E) -> E2) -> OrchestratorError<E2> {
@@ -223,6 +290,7 @@ impl<E> OrchestratorError<E> {
             ErrorKind::Interceptor { source } => ErrorKind::Interceptor { source },
             ErrorKind::Response { source } => ErrorKind::Response { source },
             ErrorKind::Timeout { source } => ErrorKind::Timeout { source },
+            ErrorKind::Cancelled { source } => ErrorKind::Cancelled { source },
             ErrorKind::Other { source } => ErrorKind::Other { source },
         };
         OrchestratorError { kind }
@@ -240,6 +308,7 @@ where
             ErrorKind::Interceptor { source } => source as _,
             ErrorKind::Response { source } => source.as_ref(),
             ErrorKind::Timeout { source } => source.as_ref(),
+            ErrorKind::Cancelled { source } => source.as_ref(),
             ErrorKind::Other { source } => source.as_ref(),
         })
     }
@@ -253,6 +322,7 @@ impl<E> fmt::Display for OrchestratorError<E> {
             ErrorKind::Interceptor { .. } => "interceptor error",
             ErrorKind::Response { .. } => "response error",
             ErrorKind::Timeout { .. } => "timeout",
+            ErrorKind::Cancelled { .. } => "cancelled",
             ErrorKind::Other { .. } => "an unknown error occurred",
         })
     }
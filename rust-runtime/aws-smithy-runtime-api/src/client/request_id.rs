@@ -0,0 +1,275 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Request IDs that services return to correlate a response with their own logs.
+//!
+//! By default, the request ID is read from the first of the `x-amzn-requestid`, `x-amz-request-id`,
+//! and `x-request-id` response headers that is present. Services that return their request ID in
+//! a different header can configure a [`RequestIdExtractor`] with the names of those headers.
+
+use crate::client::orchestrator::HttpResponse;
+use crate::client::result::SdkError;
+use crate::http::{Headers, Response};
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use aws_smithy_types::error::metadata::{
+    Builder as ErrorMetadataBuilder, ErrorMetadata, ProvideErrorMetadata,
+};
+use aws_smithy_types::error::Unhandled;
+use std::borrow::Cow;
+
+/// Constant for the [`ErrorMetadata`] extra field that contains the request ID
+// This keeps its original name so that errors from older generated clients still report their request ID
+const REQUEST_ID: &str = "aws_request_id";
+
+/// Response headers that the request ID is read from when no [`RequestIdExtractor`] is configured.
+const DEFAULT_REQUEST_ID_HEADERS: &[&str] =
+    &["x-amzn-requestid", "x-amz-request-id", "x-request-id"];
+
+/// Implementers add a function to return a request ID
+pub trait RequestId {
+    /// Returns the request ID, or `None` if the service could not be reached.
+    fn request_id(&self) -> Option<&str>;
+}
+
+/// Reads the request ID from a configured list of response headers.
+///
+/// The headers are checked in order, and the value of the first one that is present is used.
+/// When this is in the config bag, the orchestrator reads the request ID of every response with
+/// it, so that the request ID of outputs, errors, and tracing spans comes from these headers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestIdExtractor {
+    header_names: Vec<Cow<'static, str>>,
+}
+
+impl Default for RequestIdExtractor {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUEST_ID_HEADERS.iter().copied())
+    }
+}
+
+impl RequestIdExtractor {
+    /// Creates a `RequestIdExtractor` that reads the request ID from the given headers, in order.
+    pub fn new(header_names: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self {
+        Self {
+            header_names: header_names.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the names of the headers that the request ID is read from.
+    pub fn header_names(&self) -> impl Iterator<Item = &str> {
+        self.header_names.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns the request ID from the given headers, if one of the configured headers is present.
+    pub fn extract<'a>(&self, headers: &'a Headers) -> Option<&'a str> {
+        self.header_names().find_map(|name| headers.get(name))
+    }
+
+    /// Reads the request ID from the given response, and records it on the response so that
+    /// [`RequestId`] returns it.
+    pub fn apply(&self, response: &mut HttpResponse) {
+        if let Some(request_id) = self.extract(response.headers()) {
+            let request_id = ExtractedRequestId(request_id.to_owned());
+            response.add_extension(request_id);
+        }
+    }
+
+    /// Applies the request ID from the given headers to a generic error builder.
+    pub fn apply_to_error(
+        &self,
+        builder: ErrorMetadataBuilder,
+        headers: &Headers,
+    ) -> ErrorMetadataBuilder {
+        match self.extract(headers) {
+            Some(request_id) => builder.custom(REQUEST_ID, request_id),
+            None => builder,
+        }
+    }
+}
+
+impl Storable for RequestIdExtractor {
+    type Storer = StoreReplace<Self>;
+}
+
+/// A request ID read from a response by a configured [`RequestIdExtractor`]
+#[derive(Clone, Debug)]
+struct ExtractedRequestId(String);
+
+impl<E> RequestId for SdkError<E, HttpResponse> {
+    fn request_id(&self) -> Option<&str> {
+        self.raw_response().and_then(RequestId::request_id)
+    }
+}
+
+impl RequestId for ErrorMetadata {
+    fn request_id(&self) -> Option<&str> {
+        self.extra(REQUEST_ID)
+    }
+}
+
+impl RequestId for Unhandled {
+    fn request_id(&self) -> Option<&str> {
+        self.meta().request_id()
+    }
+}
+
+impl<B> RequestId for Response<B> {
+    fn request_id(&self) -> Option<&str> {
+        match self.extension::<ExtractedRequestId>() {
+            Some(ExtractedRequestId(request_id)) => Some(request_id),
+            None => self.headers().request_id(),
+        }
+    }
+}
+
+impl RequestId for Headers {
+    fn request_id(&self) -> Option<&str> {
+        DEFAULT_REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| self.get(*name))
+    }
+}
+
+impl<O, E> RequestId for Result<O, E>
+where
+    O: RequestId,
+    E: RequestId,
+{
+    fn request_id(&self) -> Option<&str> {
+        match self {
+            Ok(ok) => ok.request_id(),
+            Err(err) => err.request_id(),
+        }
+    }
+}
+
+/// Applies a request ID to a generic error builder
+pub fn apply_request_id(builder: ErrorMetadataBuilder, headers: &Headers) -> ErrorMetadataBuilder {
+    if let Some(request_id) = headers.request_id() {
+        builder.custom(REQUEST_ID, request_id)
+    } else {
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_types::body::SdkBody;
+    use http::{HeaderValue, Response};
+
+    fn http_response(request_id_header: Option<(&'static str, &'static str)>) -> HttpResponse {
+        let mut builder = Response::builder();
+        if let Some((name, value)) = request_id_header {
+            builder = builder.header(name, HeaderValue::from_static(value));
+        }
+        HttpResponse::try_from(builder.body(SdkBody::empty()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_request_id_sdk_error() {
+        let without_request_id = || http_response(None);
+        let with_request_id = || http_response(Some(("x-amzn-requestid", "some-request-id")));
+        assert_eq!(
+            None,
+            SdkError::<(), _>::response_error("test", without_request_id()).request_id()
+        );
+        assert_eq!(
+            Some("some-request-id"),
+            SdkError::<(), _>::response_error("test", with_request_id()).request_id()
+        );
+        assert_eq!(
+            None,
+            SdkError::service_error((), without_request_id()).request_id()
+        );
+        assert_eq!(
+            Some("some-request-id"),
+            SdkError::service_error((), with_request_id()).request_id()
+        );
+    }
+
+    #[test]
+    fn test_extract_request_id() {
+        let mut headers = Headers::new();
+        assert_eq!(None, headers.request_id());
+
+        headers.append(
+            "x-amzn-requestid",
+            HeaderValue::from_static("some-request-id"),
+        );
+        assert_eq!(Some("some-request-id"), headers.request_id());
+
+        headers.append(
+            "x-amz-request-id",
+            HeaderValue::from_static("other-request-id"),
+        );
+        assert_eq!(Some("some-request-id"), headers.request_id());
+
+        headers.remove("x-amzn-requestid");
+        assert_eq!(Some("other-request-id"), headers.request_id());
+
+        headers.remove("x-amz-request-id");
+        headers.append(
+            "x-request-id",
+            HeaderValue::from_static("generic-request-id"),
+        );
+        assert_eq!(Some("generic-request-id"), headers.request_id());
+    }
+
+    #[test]
+    fn test_configured_request_id_headers() {
+        let extractor = RequestIdExtractor::new(["x-correlation-id", "x-trace-id"]);
+        let mut response = http_response(Some(("x-correlation-id", "correlation-id")));
+        response
+            .headers_mut()
+            .append("x-amzn-requestid", HeaderValue::from_static("ignored"));
+        assert_eq!(Some("ignored"), response.request_id());
+
+        extractor.apply(&mut response);
+        assert_eq!(Some("correlation-id"), response.request_id());
+        assert_eq!(
+            Some("correlation-id"),
+            SdkError::<(), _>::response_error("test", response).request_id()
+        );
+
+        let mut response = http_response(Some(("x-amzn-requestid", "not-configured")));
+        RequestIdExtractor::new(["x-correlation-id"]).apply(&mut response);
+        assert_eq!(Some("not-configured"), response.request_id());
+    }
+
+    #[test]
+    fn test_apply_request_id() {
+        let mut headers = Headers::new();
+        assert_eq!(
+            ErrorMetadata::builder().build(),
+            apply_request_id(ErrorMetadata::builder(), &headers).build(),
+        );
+
+        headers.append(
+            "x-amzn-requestid",
+            HeaderValue::from_static("some-request-id"),
+        );
+        assert_eq!(
+            ErrorMetadata::builder()
+                .custom(REQUEST_ID, "some-request-id")
+                .build(),
+            apply_request_id(ErrorMetadata::builder(), &headers).build(),
+        );
+        assert_eq!(
+            ErrorMetadata::builder().build(),
+            RequestIdExtractor::new(["x-correlation-id"])
+                .apply_to_error(ErrorMetadata::builder(), &headers)
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_error_metadata_request_id_impl() {
+        let err = ErrorMetadata::builder()
+            .custom(REQUEST_ID, "some-request-id")
+            .build();
+        assert_eq!(Some("some-request-id"), err.request_id());
+    }
+}
@@ -55,6 +55,7 @@ pub mod builders {
     source_only_error_builder!(ConstructionFailure, ConstructionFailureBuilder, BoxError);
     source_only_error_builder!(TimeoutError, TimeoutErrorBuilder, BoxError);
     source_only_error_builder!(DispatchFailure, DispatchFailureBuilder, ConnectorError);
+    source_only_error_builder!(Cancelled, CancelledBuilder, BoxError);
 
     /// Builder for [`ResponseError`](super::ResponseError).
     #[derive(Debug)]
@@ -215,6 +216,16 @@ impl DispatchFailure {
         self.source.is_timeout()
     }
 
+    /// Returns true if the error is a timeout that occurred while establishing a connection
+    pub fn is_connect_timeout(&self) -> bool {
+        self.source.is_connect_timeout()
+    }
+
+    /// Returns true if the error is a DNS resolution failure
+    pub fn is_dns(&self) -> bool {
+        self.source.is_dns()
+    }
+
     /// Returns true if the error is a user-caused error (e.g., invalid HTTP request)
     pub fn is_user(&self) -> bool {
         self.source.is_user()
@@ -236,6 +247,19 @@ impl DispatchFailure {
     }
 }
 
+/// Error context for [`SdkError::Cancelled`]
+#[derive(Debug)]
+pub struct Cancelled {
+    source: BoxError,
+}
+
+impl Cancelled {
+    /// Creates a builder for this error context type.
+    pub fn builder() -> builders::CancelledBuilder {
+        builders::CancelledBuilder::new()
+    }
+}
+
 /// Error context for [`SdkError::ResponseError`]
 #[derive(Debug)]
 pub struct ResponseError<R> {
@@ -334,6 +358,10 @@ pub enum SdkError<E, R> {
 
     /// An error response was received from the service
     ServiceError(ServiceError<E, R>),
+
+    /// The operation was cancelled with a [`CancellationToken`](crate::client::cancellation::CancellationToken)
+    /// before it completed. The request MAY have been sent and received.
+    Cancelled(Cancelled),
 }
 
 impl<E, R> SdkError<E, R> {
@@ -351,6 +379,13 @@ impl<E, R> SdkError<E, R> {
         })
     }
 
+    /// Construct a `SdkError` for an operation that was cancelled
+    pub fn cancelled(source: impl Into<BoxError>) -> Self {
+        Self::Cancelled(Cancelled {
+            source: source.into(),
+        })
+    }
+
     /// Construct a `SdkError` for a dispatch failure with a [`ConnectorError`]
     pub fn dispatch_failure(source: ConnectorError) -> Self {
         Self::DispatchFailure(DispatchFailure { source })
@@ -425,6 +460,7 @@ impl<E, R> SdkError<E, R> {
             SdkError::ResponseError(context) => Ok(context.source),
             SdkError::DispatchFailure(context) => Ok(context.source.into()),
             SdkError::ServiceError(context) => Ok(context.source.into()),
+            SdkError::Cancelled(context) => Ok(context.source),
         }
     }
 
@@ -451,6 +487,7 @@ impl<E, R> SdkError<E, R> {
             SdkError::DispatchFailure(context) => SdkError::<E2, R>::DispatchFailure(context),
             SdkError::ResponseError(context) => SdkError::<E2, R>::ResponseError(context),
             SdkError::TimeoutError(context) => SdkError::<E2, R>::TimeoutError(context),
+            SdkError::Cancelled(context) => SdkError::<E2, R>::Cancelled(context),
         }
     }
 }
@@ -463,6 +500,7 @@ impl<E, R> Display for SdkError<E, R> {
             SdkError::DispatchFailure(_) => write!(f, "dispatch failure"),
             SdkError::ResponseError(_) => write!(f, "response error"),
             SdkError::ServiceError(_) => write!(f, "service error"),
+            SdkError::Cancelled(_) => write!(f, "operation was cancelled"),
         }
     }
 }
@@ -479,6 +517,7 @@ where
             SdkError::ResponseError(context) => Some(context.source.as_ref()),
             SdkError::DispatchFailure(context) => Some(&context.source),
             SdkError::ServiceError(context) => Some(&context.source),
+            SdkError::Cancelled(context) => Some(context.source.as_ref()),
         }
     }
 }
@@ -500,6 +539,7 @@ where
             SdkError::DispatchFailure(_) => &EMPTY_ERROR_METADATA,
             SdkError::ResponseError(_) => &EMPTY_ERROR_METADATA,
             SdkError::ServiceError(err) => err.source.meta(),
+            SdkError::Cancelled(_) => &EMPTY_ERROR_METADATA,
         }
     }
 }
@@ -509,6 +549,12 @@ enum ConnectorErrorKind {
     /// A timeout occurred while processing the request
     Timeout,
 
+    /// A timeout occurred while establishing a connection
+    ConnectTimeout,
+
+    /// The remote host name could not be resolved
+    Dns,
+
     /// A user-caused error (e.g., invalid HTTP request)
     User,
 
@@ -551,6 +597,8 @@ impl Display for ConnectorError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self.kind {
             ConnectorErrorKind::Timeout => write!(f, "timeout"),
+            ConnectorErrorKind::ConnectTimeout => write!(f, "connect timeout"),
+            ConnectorErrorKind::Dns => write!(f, "dns error"),
             ConnectorErrorKind::User => write!(f, "user error"),
             ConnectorErrorKind::Io => write!(f, "io error"),
             ConnectorErrorKind::Other(_) => write!(f, "other"),
@@ -576,6 +624,29 @@ impl ConnectorError {
         }
     }
 
+    /// Construct a [`ConnectorError`] from a timeout that occurred while establishing a connection
+    ///
+    /// Connect timeouts are reported as timeouts by [`is_timeout`](ConnectorError::is_timeout),
+    /// but can be distinguished from other timeouts with [`is_connect_timeout`](ConnectorError::is_connect_timeout).
+    pub fn connect_timeout(source: BoxError) -> Self {
+        Self {
+            kind: ConnectorErrorKind::ConnectTimeout,
+            source,
+            connection: ConnectionStatus::NeverConnected,
+        }
+    }
+
+    /// Construct a [`ConnectorError`] from a failure to resolve the remote host name
+    ///
+    /// DNS failures are typically transient and retried.
+    pub fn dns(source: BoxError) -> Self {
+        Self {
+            kind: ConnectorErrorKind::Dns,
+            source,
+            connection: ConnectionStatus::NeverConnected,
+        }
+    }
+
     /// Include connection information along with this error
     pub fn with_connection(mut self, info: ConnectionMetadata) -> Self {
         self.connection = ConnectionStatus::Connected(info);
@@ -623,8 +694,23 @@ impl ConnectorError {
     }
 
     /// Returns true if the error is an timeout error
+    ///
+    /// This includes timeouts that occurred while establishing a connection.
     pub fn is_timeout(&self) -> bool {
-        matches!(self.kind, ConnectorErrorKind::Timeout)
+        matches!(
+            self.kind,
+            ConnectorErrorKind::Timeout | ConnectorErrorKind::ConnectTimeout
+        )
+    }
+
+    /// Returns true if the error is a timeout that occurred while establishing a connection
+    pub fn is_connect_timeout(&self) -> bool {
+        matches!(self.kind, ConnectorErrorKind::ConnectTimeout)
+    }
+
+    /// Returns true if the error is a DNS resolution failure
+    pub fn is_dns(&self) -> bool {
+        matches!(self.kind, ConnectorErrorKind::Dns)
     }
 
     /// Returns true if the error is a user-caused error (e.g., invalid HTTP request)
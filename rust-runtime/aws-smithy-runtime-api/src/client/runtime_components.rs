@@ -28,6 +28,7 @@ use crate::client::retries::{RetryStrategy, SharedRetryStrategy};
 use crate::impl_shared_conversions;
 use crate::shared::IntoShared;
 use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::rt::spawn::{SharedSpawn, Spawn};
 use aws_smithy_async::time::{SharedTimeSource, TimeSource};
 use aws_smithy_types::config_bag::ConfigBag;
 use std::fmt;
@@ -357,6 +358,8 @@ declare_runtime_components! {
 
         sleep_impl: Option<SharedAsyncSleep>,
 
+        spawn: Option<SharedSpawn>,
+
         config_validators: Vec<SharedConfigValidator>,
     }
 }
@@ -420,6 +423,11 @@ impl RuntimeComponents {
         self.time_source.as_ref().map(|s| s.value.clone())
     }
 
+    /// Returns the implementation that background tasks and blocking work are spawned with.
+    pub fn spawn(&self) -> Option<SharedSpawn> {
+        self.spawn.as_ref().map(|s| s.value.clone())
+    }
+
     /// Returns the config validators.
     pub fn config_validators(&self) -> impl Iterator<Item = SharedConfigValidator> + '_ {
         self.config_validators.iter().map(|s| s.value.clone())
@@ -727,6 +735,29 @@ impl RuntimeComponentsBuilder {
         self
     }
 
+    /// Returns the implementation that background tasks and blocking work are spawned with.
+    pub fn spawn(&self) -> Option<SharedSpawn> {
+        self.spawn.as_ref().map(|s| s.value.clone())
+    }
+
+    /// Sets the implementation that background tasks and blocking work are spawned with.
+    ///
+    /// HTTP clients spawn the tasks that drive their connections with it, so it must match the
+    /// async runtime that the client is used from.
+    pub fn set_spawn(&mut self, spawn: Option<SharedSpawn>) -> &mut Self {
+        self.spawn = spawn.map(|s| Tracked::new(self.builder_name, s));
+        self
+    }
+
+    /// Sets the implementation that background tasks and blocking work are spawned with.
+    ///
+    /// HTTP clients spawn the tasks that drive their connections with it, so it must match the
+    /// async runtime that the client is used from.
+    pub fn with_spawn(mut self, spawn: Option<impl Spawn + 'static>) -> Self {
+        self.spawn = spawn.map(|s| Tracked::new(self.builder_name, s.into_shared()));
+        self
+    }
+
     /// Returns the time source.
     pub fn time_source(&self) -> Option<SharedTimeSource> {
         self.time_source.as_ref().map(|s| s.value.clone())
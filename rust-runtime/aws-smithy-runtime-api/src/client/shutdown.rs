@@ -0,0 +1,266 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Graceful client shutdown.
+//!
+//! A [`ClientShutdown`] is shared by every operation made with a client. Shutting it down stops
+//! the client from starting new operations, stops its background tasks, and waits for the
+//! in-flight operations to complete, so that services can drain cleanly before they exit.
+
+use crate::client::cancellation::CancellationToken;
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+struct InFlight {
+    shut_down: bool,
+    count: usize,
+    wakers: Vec<Waker>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    in_flight: Mutex<InFlight>,
+    cancellation_token: CancellationToken,
+    background_task_token: CancellationToken,
+}
+
+/// Tracks the in-flight operations of a client so that it can be shut down gracefully.
+///
+/// Clones share the same state.
+#[derive(Clone, Debug, Default)]
+pub struct ClientShutdown {
+    inner: Arc<Inner>,
+}
+
+impl ClientShutdown {
+    /// Creates a new `ClientShutdown` for a client that hasn't been shut down.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the client has been shut down.
+    pub fn is_shut_down(&self) -> bool {
+        self.inner.in_flight.lock().unwrap().shut_down
+    }
+
+    /// Returns the number of operations that are currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.inner.in_flight.lock().unwrap().count
+    }
+
+    /// Records that an operation has started.
+    ///
+    /// The operation is in flight until the returned guard is dropped. Returns `None` if the
+    /// client has been shut down, in which case the operation must not be started.
+    pub fn start_operation(&self) -> Option<InFlightOperation> {
+        let mut in_flight = self.inner.in_flight.lock().unwrap();
+        if in_flight.shut_down {
+            return None;
+        }
+        in_flight.count += 1;
+        Some(InFlightOperation {
+            shutdown: self.clone(),
+        })
+    }
+
+    /// Shuts down the client, and waits up to `timeout` for its in-flight operations to complete.
+    ///
+    /// Operations that are started after this is called fail immediately, and the client's
+    /// background tasks are stopped right away. If operations are still
+    /// in flight once the timeout elapses, they are cancelled, and this returns an error. Cancelled
+    /// operations fail with [`SdkError::Cancelled`](crate::client::result::SdkError::Cancelled).
+    ///
+    /// If `sleep_impl` is `None`, then this waits for the in-flight operations without a timeout.
+    pub async fn shutdown(
+        &self,
+        timeout: Duration,
+        sleep_impl: Option<SharedAsyncSleep>,
+    ) -> Result<(), ShutdownError> {
+        self.inner.in_flight.lock().unwrap().shut_down = true;
+        self.inner.background_task_token.cancel();
+        let drained = match sleep_impl {
+            Some(sleep_impl) => Timeout::new(self.drained(), sleep_impl.sleep(timeout))
+                .await
+                .is_ok(),
+            None => {
+                self.drained().await;
+                true
+            }
+        };
+        if drained {
+            return Ok(());
+        }
+        let cancelled = self.in_flight();
+        tracing::debug!(
+            cancelled = cancelled,
+            "operations were still in flight when the shutdown timeout elapsed"
+        );
+        self.inner.cancellation_token.cancel();
+        Err(ShutdownError { cancelled, timeout })
+    }
+
+    /// Returns the token that cancels the in-flight operations when the shutdown times out.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.inner.cancellation_token
+    }
+
+    /// Returns the token that stops the client's background tasks when the client is shut down.
+    ///
+    /// Unlike the [`cancellation_token`](Self::cancellation_token), this is cancelled as soon as
+    /// the shutdown starts. Components that refresh state in the background, such as endpoint
+    /// discovery, must stop doing so once it's cancelled.
+    pub fn background_task_token(&self) -> &CancellationToken {
+        &self.inner.background_task_token
+    }
+
+    fn drained(&self) -> impl Future<Output = ()> + '_ {
+        poll_fn(|cx| {
+            let mut in_flight = self.inner.in_flight.lock().unwrap();
+            if in_flight.count == 0 {
+                return Poll::Ready(());
+            }
+            if !in_flight.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                in_flight.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+    }
+}
+
+impl Storable for ClientShutdown {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Guard that keeps an operation in flight until it's dropped.
+///
+/// Returned by [`ClientShutdown::start_operation`].
+#[derive(Debug)]
+pub struct InFlightOperation {
+    shutdown: ClientShutdown,
+}
+
+impl InFlightOperation {
+    /// Returns the token that cancels this operation when the shutdown times out.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        self.shutdown.cancellation_token()
+    }
+}
+
+impl Drop for InFlightOperation {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut in_flight = self.shutdown.inner.in_flight.lock().unwrap();
+            in_flight.count -= 1;
+            if in_flight.count > 0 {
+                return;
+            }
+            std::mem::take(&mut in_flight.wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Error returned by [`ClientShutdown::shutdown`] when operations were still in flight after the
+/// shutdown timeout elapsed.
+#[derive(Debug)]
+pub struct ShutdownError {
+    cancelled: usize,
+    timeout: Duration,
+}
+
+impl ShutdownError {
+    /// Returns the number of in-flight operations that were cancelled.
+    pub fn cancelled_operations(&self) -> usize {
+        self.cancelled
+    }
+}
+
+impl fmt::Display for ShutdownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} operation(s) were still in flight after waiting {:?} for the client to shut down, and were cancelled",
+            self.cancelled, self.timeout
+        )
+    }
+}
+
+impl std::error::Error for ShutdownError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::rt::sleep::Sleep;
+
+    /// A sleep that completes immediately, so that shutdown timeouts elapse right away
+    #[derive(Debug)]
+    struct ImmediateSleep;
+    impl AsyncSleep for ImmediateSleep {
+        fn sleep(&self, _duration: Duration) -> Sleep {
+            Sleep::new(std::future::ready(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_in_flight_operations() {
+        let shutdown = ClientShutdown::new();
+        let operation = shutdown.start_operation().expect("not shut down");
+        let finish = tokio::spawn(async move {
+            tokio::task::yield_now().await;
+            drop(operation);
+        });
+        shutdown
+            .shutdown(Duration::from_secs(5), None)
+            .await
+            .expect("drained");
+        finish.await.unwrap();
+        assert!(shutdown.is_shut_down());
+        assert!(shutdown.start_operation().is_none());
+    }
+
+    #[tokio::test]
+    async fn shutdown_stops_background_tasks_right_away() {
+        let shutdown = ClientShutdown::new();
+        let operation = shutdown.start_operation().expect("not shut down");
+        let background_task = shutdown
+            .background_task_token()
+            .run_until_cancelled(std::future::pending::<()>());
+        let (stopped, result, ()) = tokio::join!(
+            background_task,
+            shutdown.shutdown(Duration::from_secs(5), None),
+            async move {
+                tokio::task::yield_now().await;
+                drop(operation);
+            }
+        );
+        assert_eq!(None, stopped);
+        result.expect("drained");
+        assert!(!shutdown.cancellation_token().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn shutdown_cancels_operations_after_timeout() {
+        let shutdown = ClientShutdown::new();
+        let operation = shutdown.start_operation().expect("not shut down");
+        let err = shutdown
+            .shutdown(
+                Duration::from_secs(5),
+                Some(SharedAsyncSleep::new(ImmediateSleep)),
+            )
+            .await
+            .expect_err("the operation never completes");
+        assert_eq!(1, err.cancelled_operations());
+        assert!(operation.cancellation_token().is_cancelled());
+    }
+}
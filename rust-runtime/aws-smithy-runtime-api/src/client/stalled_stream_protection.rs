@@ -0,0 +1,130 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Stalled stream protection.
+//!
+//! When enabled, upload and download streams that transfer less data than the configured
+//! minimum throughput will be cancelled with a retryable error, rather than hanging until
+//! the operation timeout (if any) is reached.
+
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::time::Duration;
+
+const DEFAULT_MINIMUM_BYTES: u64 = 1;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(5);
+
+/// Configuration for stalled stream protection.
+///
+/// By default, a stream is considered stalled when less than one byte is transferred over
+/// a five second window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StalledStreamProtectionConfig {
+    upload_enabled: bool,
+    download_enabled: bool,
+    minimum_bytes: u64,
+    window: Duration,
+}
+
+impl StalledStreamProtectionConfig {
+    /// Creates a builder for stalled stream protection that's enabled for both uploads and downloads.
+    pub fn enabled() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns a config that disables stalled stream protection.
+    pub fn disabled() -> Self {
+        Self {
+            upload_enabled: false,
+            download_enabled: false,
+            minimum_bytes: DEFAULT_MINIMUM_BYTES,
+            window: DEFAULT_WINDOW,
+        }
+    }
+
+    /// Returns true if stalled stream protection is enabled for uploads or downloads.
+    pub fn is_enabled(&self) -> bool {
+        self.upload_enabled || self.download_enabled
+    }
+
+    /// Returns true if stalled stream protection is enabled for request bodies.
+    pub fn upload_enabled(&self) -> bool {
+        self.upload_enabled
+    }
+
+    /// Returns true if stalled stream protection is enabled for response bodies.
+    pub fn download_enabled(&self) -> bool {
+        self.download_enabled
+    }
+
+    /// Returns the minimum number of bytes that must be transferred in each window.
+    pub fn minimum_bytes(&self) -> u64 {
+        self.minimum_bytes
+    }
+
+    /// Returns the window over which throughput is measured.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+}
+
+impl Storable for StalledStreamProtectionConfig {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Builder for [`StalledStreamProtectionConfig`].
+#[derive(Clone, Debug)]
+pub struct Builder {
+    upload_enabled: bool,
+    download_enabled: bool,
+    minimum_bytes: u64,
+    window: Duration,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            upload_enabled: true,
+            download_enabled: true,
+            minimum_bytes: DEFAULT_MINIMUM_BYTES,
+            window: DEFAULT_WINDOW,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets whether stalled stream protection is enabled for request bodies.
+    pub fn upload_enabled(mut self, enabled: bool) -> Self {
+        self.upload_enabled = enabled;
+        self
+    }
+
+    /// Sets whether stalled stream protection is enabled for response bodies.
+    pub fn download_enabled(mut self, enabled: bool) -> Self {
+        self.download_enabled = enabled;
+        self
+    }
+
+    /// Sets the minimum throughput, as a number of bytes that must be transferred per window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    pub fn minimum_throughput(mut self, minimum_bytes: u64, window: Duration) -> Self {
+        assert!(!window.is_zero(), "the throughput window must be non-zero");
+        self.minimum_bytes = minimum_bytes;
+        self.window = window;
+        self
+    }
+
+    /// Builds the [`StalledStreamProtectionConfig`].
+    pub fn build(self) -> StalledStreamProtectionConfig {
+        StalledStreamProtectionConfig {
+            upload_enabled: self.upload_enabled,
+            download_enabled: self.download_enabled,
+            minimum_bytes: self.minimum_bytes,
+            window: self.window,
+        }
+    }
+}
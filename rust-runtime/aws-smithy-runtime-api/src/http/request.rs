@@ -48,6 +48,11 @@ impl Uri {
     /// An `endpoint` MAY contain a port and path.
     ///
     /// An `endpoint` MUST NOT contain a query
+    ///
+    /// When the `endpoint` has a path, it's kept as a base path prefix for the request path, with
+    /// exactly one `/` between them. For example, setting `https://gateway.example.com/api/v3` or
+    /// `https://gateway.example.com/api/v3/` as the endpoint for `/things?id=1` results in
+    /// `https://gateway.example.com/api/v3/things?id=1`.
     pub fn set_endpoint(&mut self, endpoint: &str) -> Result<(), HttpError> {
         let endpoint: http0::Uri = endpoint.parse().map_err(HttpError::invalid_uri)?;
         let endpoint = endpoint.into_parts();
@@ -244,6 +249,11 @@ impl<B> Request<B> {
     pub fn add_extension<T: Send + Sync + Clone + 'static>(&mut self, extension: T) {
         self.extensions.insert(extension);
     }
+
+    /// Returns a reference to the request extension of type `T`, if there is one
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.get::<T>()
+    }
 }
 
 impl Request<SdkBody> {
@@ -314,6 +324,69 @@ mod test {
         assert_eq!(request.headers().get("k"), Some("😹"))
     }
 
+    #[test]
+    fn set_endpoint_preserves_base_path() {
+        for (endpoint, request_uri, expected) in [
+            (
+                "https://gateway.example.com",
+                "/things",
+                "https://gateway.example.com/things",
+            ),
+            (
+                "https://gateway.example.com/",
+                "/things",
+                "https://gateway.example.com/things",
+            ),
+            (
+                "https://gateway.example.com/api/v3",
+                "/things",
+                "https://gateway.example.com/api/v3/things",
+            ),
+            (
+                "https://gateway.example.com/api/v3/",
+                "/things",
+                "https://gateway.example.com/api/v3/things",
+            ),
+            (
+                "https://gateway.example.com/api/v3",
+                "/things/",
+                "https://gateway.example.com/api/v3/things/",
+            ),
+            (
+                "https://gateway.example.com/api/v3",
+                "/",
+                "https://gateway.example.com/api/v3/",
+            ),
+            (
+                "https://gateway.example.com/api/v3/",
+                "/",
+                "https://gateway.example.com/api/v3/",
+            ),
+            (
+                "https://gateway.example.com/api/v3",
+                "/things?id=1",
+                "https://gateway.example.com/api/v3/things?id=1",
+            ),
+            (
+                "https://gateway.example.com/api/v3/",
+                "/?list-type=2",
+                "https://gateway.example.com/api/v3/?list-type=2",
+            ),
+            (
+                "https://gateway.example.com/api/v3",
+                "//leading-slash-key",
+                "https://gateway.example.com/api/v3//leading-slash-key",
+            ),
+        ] {
+            let mut uri = super::Uri::try_from(request_uri.to_string()).unwrap();
+            uri.set_endpoint(endpoint).unwrap();
+            assert_eq!(
+                uri.as_string, expected,
+                "endpoint: {endpoint}, request: {request_uri}"
+            );
+        }
+    }
+
     #[test]
     fn request_can_be_created() {
         let req = http::Request::builder()
@@ -160,6 +160,11 @@ impl<B> Response<B> {
     pub fn add_extension<T: Send + Sync + Clone + 'static>(&mut self, extension: T) {
         self.extensions.insert(extension);
     }
+
+    /// Returns a reference to the response extension of type `T`, if there is one
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.get::<T>()
+    }
 }
 
 impl Response<SdkBody> {
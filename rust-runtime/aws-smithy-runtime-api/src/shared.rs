@@ -184,8 +184,10 @@ macro_rules! impl_shared_conversions {
 // TODO(https://github.com/smithy-lang/smithy-rs/issues/3016): Move these impls once aws-smithy-async is merged into aws-smithy-runtime-api
 mod async_impls {
     use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+    use aws_smithy_async::rt::spawn::{SharedSpawn, Spawn};
     use aws_smithy_async::time::{SharedTimeSource, TimeSource};
     impl_shared_conversions!(convert SharedAsyncSleep from AsyncSleep using SharedAsyncSleep::new);
+    impl_shared_conversions!(convert SharedSpawn from Spawn using SharedSpawn::new);
     impl_shared_conversions!(convert SharedTimeSource from TimeSource using SharedTimeSource::new);
 }
 
@@ -10,6 +10,9 @@ pub mod defaults;
 
 pub mod dns;
 
+/// Endpoint resolution utilities.
+pub mod endpoint;
+
 /// Built-in Smithy HTTP clients and connectors.
 ///
 /// See the [module docs in `aws-smithy-runtime-api`](aws_smithy_runtime_api::client::http)
@@ -34,10 +37,31 @@ pub mod retries;
 #[cfg(feature = "test-util")]
 pub mod test_util;
 
+mod filtered_dispatch;
+
+mod lifecycle;
+
+mod metrics;
+
+mod telemetry;
+
 mod timeout;
 
+/// Stalled stream protection for request and response bodies.
+pub mod stalled_stream_protection;
+
 /// Smithy identity used by auth and signing.
 pub mod identity;
 
 /// Interceptors for Smithy clients.
 pub mod interceptors;
+
+pub mod invocation_id;
+
+pub mod log_sampling;
+
+pub mod subsystem_tracing;
+
+pub mod wire_logging;
+
+pub mod trace_context;
@@ -406,6 +406,61 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_bearer_auth_with_token_provider() {
+        use aws_smithy_runtime_api::client::identity::http::{
+            ProvideToken, SharedTokenProvider, TokenFuture,
+        };
+        use aws_smithy_runtime_api::client::identity::ResolveIdentity;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::{Duration, UNIX_EPOCH};
+
+        #[derive(Debug, Default)]
+        struct RefreshingProvider(AtomicUsize);
+
+        impl ProvideToken for RefreshingProvider {
+            fn provide_token<'a>(&'a self) -> TokenFuture<'a> {
+                TokenFuture::new(async move {
+                    let refreshes = self.0.fetch_add(1, Ordering::Relaxed);
+                    Ok(Token::new(
+                        format!("token-{refreshes}"),
+                        Some(UNIX_EPOCH + Duration::from_secs(1234)),
+                    ))
+                })
+            }
+        }
+
+        let config_bag = ConfigBag::base();
+        let runtime_components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let provider = SharedTokenProvider::new(RefreshingProvider::default());
+        let identity = provider
+            .resolve_identity(&runtime_components, &config_bag)
+            .await
+            .expect("success");
+        assert_eq!(
+            Some(UNIX_EPOCH + Duration::from_secs(1234)),
+            identity.expiration()
+        );
+        let mut request = http::Request::builder()
+            .body(SdkBody::empty())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        BearerAuthSigner
+            .sign_http_request(
+                &mut request,
+                &identity,
+                AuthSchemeEndpointConfig::empty(),
+                &runtime_components,
+                &config_bag,
+            )
+            .expect("success");
+        assert_eq!(
+            "Bearer token-0",
+            request.headers().get("Authorization").unwrap()
+        );
+    }
+
     #[test]
     fn test_bearer_auth_overwrite_existing_header() {
         let signer = BearerAuthSigner;
@@ -12,7 +12,9 @@
 use crate::client::identity::IdentityCache;
 use crate::client::retries::strategy::StandardRetryStrategy;
 use crate::client::retries::RetryPartition;
+use crate::client::stalled_stream_protection::StalledStreamProtectionInterceptor;
 use aws_smithy_async::rt::sleep::default_async_sleep;
+use aws_smithy_async::rt::spawn::default_spawn;
 use aws_smithy_async::time::SystemTimeSource;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::http::SharedHttpClient;
@@ -22,6 +24,7 @@ use aws_smithy_runtime_api::client::runtime_components::{
 use aws_smithy_runtime_api::client::runtime_plugin::{
     Order, SharedRuntimePlugin, StaticRuntimePlugin,
 };
+use aws_smithy_runtime_api::client::stalled_stream_protection::StalledStreamProtectionConfig;
 use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::config_bag::{ConfigBag, FrozenLayer, Layer};
 use aws_smithy_types::retry::RetryConfig;
@@ -51,6 +54,16 @@ pub fn default_http_client_plugin() -> Option<SharedRuntimePlugin> {
     let _default: Option<SharedHttpClient> = None;
     #[cfg(feature = "connector-hyper-0-14-x")]
     let _default = crate::client::http::hyper_014::default_client();
+    // The hyper 1.x client takes precedence when both are enabled
+    #[cfg(feature = "connector-hyper-1-x")]
+    let _default = crate::client::http::hyper_1::default_client();
+    // hyper can't be used on WebAssembly, so the WebAssembly client takes precedence there
+    #[cfg(all(
+        feature = "connector-wasm",
+        target_arch = "wasm32",
+        any(target_os = "unknown", target_os = "wasi")
+    ))]
+    let _default = crate::client::http::wasm::default_client();
 
     _default.map(|default| {
         default_plugin("default_http_client_plugin", |components| {
@@ -70,6 +83,16 @@ pub fn default_sleep_impl_plugin() -> Option<SharedRuntimePlugin> {
     })
 }
 
+/// Runtime plugin that provides a default implementation for spawning tasks.
+pub fn default_spawn_plugin() -> Option<SharedRuntimePlugin> {
+    default_spawn().map(|default| {
+        default_plugin("default_spawn_plugin", |components| {
+            components.with_spawn(Some(default))
+        })
+        .into_shared()
+    })
+}
+
 /// Runtime plugin that provides a default time source.
 pub fn default_time_source_plugin() -> Option<SharedRuntimePlugin> {
     Some(
@@ -163,6 +186,19 @@ pub fn default_identity_cache_plugin() -> Option<SharedRuntimePlugin> {
     )
 }
 
+/// Runtime plugin that registers stalled stream protection, disabled by default.
+pub fn default_stalled_stream_protection_plugin() -> Option<SharedRuntimePlugin> {
+    Some(
+        default_plugin("default_stalled_stream_protection_plugin", |components| {
+            components.with_interceptor(StalledStreamProtectionInterceptor::new())
+        })
+        .with_config(layer("default_stalled_stream_protection_config", |layer| {
+            layer.store_put(StalledStreamProtectionConfig::disabled());
+        }))
+        .into_shared(),
+    )
+}
+
 /// Arguments for the [`default_plugins`] method.
 ///
 /// This is a struct to enable adding new parameters in the future without breaking the API.
@@ -198,6 +234,8 @@ pub fn default_plugins(
                 .expect("retry_partition_name is required"),
         ),
         default_sleep_impl_plugin(),
+        default_spawn_plugin(),
+        default_stalled_stream_protection_plugin(),
         default_time_source_plugin(),
         default_timeout_config_plugin(),
     ]
@@ -5,6 +5,51 @@
 
 //! Built-in DNS resolver implementations.
 
+#[cfg(not(target_family = "wasm"))]
+mod blocking {
+    use aws_smithy_async::rt::spawn::SharedSpawn;
+    use aws_smithy_runtime_api::client::dns::{DnsFuture, ResolveDns, ResolveDnsError};
+    use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+    use std::net::ToSocketAddrs;
+
+    /// DNS resolver that resolves DNS using the standard library on a blocking task.
+    ///
+    /// Unlike [`TokioDnsResolver`](super::TokioDnsResolver), this works with any async runtime that
+    /// provides a [`Spawn`](aws_smithy_async::rt::spawn::Spawn) implementation.
+    ///
+    /// This implementation isn't available for WASM targets.
+    #[derive(Debug)]
+    pub struct BlockingDnsResolver {
+        spawn: SharedSpawn,
+    }
+
+    impl BlockingDnsResolver {
+        /// Creates a new DNS resolver that resolves names on blocking tasks spawned with `spawn`.
+        pub fn new(spawn: SharedSpawn) -> Self {
+            Self { spawn }
+        }
+    }
+
+    impl ResolveDns for BlockingDnsResolver {
+        fn resolve_dns<'a>(&'a self, name: &'a str) -> DnsFuture<'a> {
+            let name = name.to_string();
+            let result = self.spawn.run_blocking(move || (name, 0).to_socket_addrs());
+            DnsFuture::new(async move {
+                match result.await {
+                    Err(join_failure) => Err(ResolveDnsError::new(IoError::new(
+                        IoErrorKind::Other,
+                        join_failure,
+                    ))),
+                    Ok(Ok(dns_result)) => {
+                        Ok(dns_result.into_iter().map(|addr| addr.ip()).collect())
+                    }
+                    Ok(Err(dns_failure)) => Err(ResolveDnsError::new(dns_failure)),
+                }
+            })
+        }
+    }
+}
+
 #[cfg(all(feature = "rt-tokio", not(target_family = "wasm")))]
 mod tokio {
     use aws_smithy_runtime_api::client::dns::{DnsFuture, ResolveDns, ResolveDnsError};
@@ -45,5 +90,63 @@ mod tokio {
     }
 }
 
+#[cfg(all(feature = "dns-hickory", not(target_family = "wasm")))]
+mod hickory {
+    use aws_smithy_runtime_api::client::dns::{DnsFuture, ResolveDns, ResolveDnsError};
+    use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+    use hickory_resolver::TokioAsyncResolver;
+
+    /// DNS resolver backed by [hickory-dns](https://github.com/hickory-dns/hickory-dns).
+    ///
+    /// Unlike the resolvers that use the standard library, this resolves names asynchronously
+    /// without tying up blocking threads, and caches the records it looks up. The resolver's
+    /// [`ResolverConfig`] controls which name servers are queried, which makes it possible to
+    /// use DNS-over-HTTPS or DNS-over-TLS when the corresponding `hickory-resolver` features are
+    /// enabled.
+    ///
+    /// This implementation requires the Tokio runtime, and isn't available for WASM targets.
+    #[derive(Clone, Debug)]
+    pub struct HickoryDnsResolver {
+        resolver: TokioAsyncResolver,
+    }
+
+    impl HickoryDnsResolver {
+        /// Creates a new DNS resolver with the given configuration and options.
+        pub fn new(config: ResolverConfig, options: ResolverOpts) -> Self {
+            Self {
+                resolver: TokioAsyncResolver::tokio(config, options),
+            }
+        }
+
+        /// Creates a new DNS resolver from the system's configuration.
+        ///
+        /// On Unix, this reads `/etc/resolv.conf`. On Windows, this reads the registry.
+        pub fn from_system_conf() -> Result<Self, ResolveDnsError> {
+            let resolver =
+                TokioAsyncResolver::tokio_from_system_conf().map_err(ResolveDnsError::new)?;
+            Ok(Self { resolver })
+        }
+    }
+
+    impl ResolveDns for HickoryDnsResolver {
+        fn resolve_dns<'a>(&'a self, name: &'a str) -> DnsFuture<'a> {
+            DnsFuture::new(async move {
+                let lookup = self
+                    .resolver
+                    .lookup_ip(name)
+                    .await
+                    .map_err(ResolveDnsError::new)?;
+                Ok(lookup.iter().collect())
+            })
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub use self::blocking::BlockingDnsResolver;
+
+#[cfg(all(feature = "dns-hickory", not(target_family = "wasm")))]
+pub use self::hickory::HickoryDnsResolver;
+
 #[cfg(all(feature = "rt-tokio", not(target_family = "wasm")))]
 pub use self::tokio::TokioDnsResolver;
@@ -0,0 +1,13 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+/// Caching of resolved endpoints.
+pub mod cache;
+
+/// Endpoint discovery with a cache of discovered endpoints.
+pub mod discovery;
+
+/// Failover between several endpoints based on their health.
+pub mod failover;
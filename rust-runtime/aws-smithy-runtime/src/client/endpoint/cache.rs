@@ -0,0 +1,330 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Caching of resolved endpoints.
+//!
+//! Endpoint rules are evaluated for every request, even though the endpoint parameters of a
+//! client rarely change. The [`EndpointCachePlugin`] memoizes the endpoints resolved for each set
+//! of endpoint parameters, so that hot clients skip evaluating the rules again.
+
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::client::endpoint::{
+    EndpointFuture, EndpointResolverParams, ResolveEndpoint, SharedEndpointResolver,
+};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+use aws_smithy_runtime_api::client::runtime_plugin::{Order, RuntimePlugin};
+use aws_smithy_types::endpoint::Endpoint;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_MAX_ENTRIES: usize = 128;
+
+/// Runtime plugin that caches the endpoints resolved by the client's endpoint resolver.
+///
+/// Endpoints are cached by the endpoint parameters of type `P`, which is usually the `Params` type
+/// in the `config::endpoint` module of a generated client. Requests whose endpoint parameters are
+/// of another type are resolved without the cache. Errors are never cached.
+///
+/// The cache holds up to [`max_entries`](Self::max_entries) endpoints, evicting the least recently
+/// used one when it's full. By default, cached endpoints don't expire, since endpoint rules resolve
+/// the same parameters to the same endpoint. Set a [`ttl`](Self::ttl) when the endpoint resolver
+/// can return different endpoints over time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::endpoint::cache::EndpointCachePlugin;
+/// use std::time::Duration;
+///
+/// # #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+/// # struct Params;
+/// // `Params` is the endpoint parameters type of the client
+/// let plugin = EndpointCachePlugin::<Params>::new()
+///     .max_entries(16)
+///     .ttl(Duration::from_secs(3600));
+/// // Register `plugin` as a runtime plugin on the client config
+/// ```
+pub struct EndpointCachePlugin<P> {
+    max_entries: usize,
+    ttl: Option<Duration>,
+    cache: Arc<Mutex<Cache<P>>>,
+}
+
+impl<P> Clone for EndpointCachePlugin<P> {
+    fn clone(&self) -> Self {
+        Self {
+            max_entries: self.max_entries,
+            ttl: self.ttl,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<P> fmt::Debug for EndpointCachePlugin<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointCachePlugin")
+            .field("max_entries", &self.max_entries)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl<P> Default for EndpointCachePlugin<P> {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_MAX_ENTRIES,
+            ttl: None,
+            cache: Arc::new(Mutex::new(Cache {
+                entries: HashMap::new(),
+                tick: 0,
+            })),
+        }
+    }
+}
+
+impl<P> EndpointCachePlugin<P> {
+    /// Creates a new `EndpointCachePlugin`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of endpoints to cache.
+    ///
+    /// Defaults to 128. Setting it to zero disables the cache.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Sets how long cached endpoints can be used for.
+    ///
+    /// By default, cached endpoints don't expire.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+impl<P> RuntimePlugin for EndpointCachePlugin<P>
+where
+    P: Clone + Eq + Hash + Send + Sync + fmt::Debug + 'static,
+{
+    fn order(&self) -> Order {
+        Order::NestedComponents
+    }
+
+    fn runtime_components(
+        &self,
+        current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        let mut components = RuntimeComponentsBuilder::new("EndpointCachePlugin");
+        if let Some(inner) = current_components.endpoint_resolver() {
+            components.set_endpoint_resolver(Some(CachingEndpointResolver {
+                plugin: self.clone(),
+                inner,
+                time_source: current_components.time_source().unwrap_or_default(),
+            }));
+        }
+        Cow::Owned(components)
+    }
+}
+
+struct Cache<P> {
+    entries: HashMap<P, CacheEntry>,
+    /// Incremented on every lookup and insertion to track how recently entries were used.
+    tick: u64,
+}
+
+struct CacheEntry {
+    endpoint: Endpoint,
+    expires_at: Option<SystemTime>,
+    last_used: u64,
+}
+
+impl<P: Clone + Eq + Hash> Cache<P> {
+    fn get(&mut self, params: &P, now: SystemTime) -> Option<Endpoint> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(params)?;
+        if entry.expires_at.is_some_and(|expires_at| now >= expires_at) {
+            self.entries.remove(params);
+            return None;
+        }
+        entry.last_used = self.tick;
+        Some(entry.endpoint.clone())
+    }
+
+    fn insert(
+        &mut self,
+        params: P,
+        endpoint: Endpoint,
+        expires_at: Option<SystemTime>,
+        max_entries: usize,
+    ) {
+        if max_entries == 0 {
+            return;
+        }
+        if self.entries.len() >= max_entries && !self.entries.contains_key(&params) {
+            let least_recently_used = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(params, _)| params.clone());
+            if let Some(params) = least_recently_used {
+                self.entries.remove(&params);
+            }
+        }
+        self.tick += 1;
+        let entry = CacheEntry {
+            endpoint,
+            expires_at,
+            last_used: self.tick,
+        };
+        self.entries.insert(params, entry);
+    }
+}
+
+#[derive(Debug)]
+struct CachingEndpointResolver<P> {
+    plugin: EndpointCachePlugin<P>,
+    inner: SharedEndpointResolver,
+    time_source: SharedTimeSource,
+}
+
+impl<P> ResolveEndpoint for CachingEndpointResolver<P>
+where
+    P: Clone + Eq + Hash + Send + Sync + fmt::Debug + 'static,
+{
+    fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+        let Some(key) = params.get::<P>() else {
+            return self.inner.resolve_endpoint(params);
+        };
+        let now = self.time_source.now();
+        if let Some(endpoint) = self.plugin.cache.lock().unwrap().get(key, now) {
+            tracing::trace!(endpoint = ?endpoint, "using cached endpoint");
+            return EndpointFuture::ready(Ok(endpoint));
+        }
+        EndpointFuture::new(async move {
+            let endpoint = self.inner.resolve_endpoint(params).await?;
+            self.plugin.cache.lock().unwrap().insert(
+                key.clone(),
+                endpoint.clone(),
+                self.plugin.ttl.map(|ttl| now + ttl),
+                self.plugin.max_entries,
+            );
+            Ok(endpoint)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::UNIX_EPOCH;
+
+    #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+    struct TestParams(&'static str);
+
+    /// Resolves `TestParams` to an endpoint that includes the number of the resolution.
+    #[derive(Debug, Default)]
+    struct CountingResolver(AtomicUsize);
+    impl ResolveEndpoint for CountingResolver {
+        fn resolve_endpoint<'a>(
+            &'a self,
+            params: &'a EndpointResolverParams,
+        ) -> EndpointFuture<'a> {
+            let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            EndpointFuture::ready(match params.get::<TestParams>() {
+                Some(TestParams("fail")) => Err("resolution failed".into()),
+                Some(TestParams(name)) => Ok(Endpoint::builder()
+                    .url(format!("https://{name}-{count}"))
+                    .build()),
+                None => Ok(Endpoint::builder().url("https://other").build()),
+            })
+        }
+    }
+
+    fn test_resolver(
+        plugin: EndpointCachePlugin<TestParams>,
+        time_source: ManualTimeSource,
+    ) -> SharedEndpointResolver {
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(CountingResolver::default()))
+            .with_time_source(Some(time_source));
+        plugin
+            .runtime_components(&components)
+            .endpoint_resolver()
+            .unwrap()
+    }
+
+    async fn resolve(resolver: &SharedEndpointResolver, name: &'static str) -> String {
+        resolver
+            .resolve_endpoint(&EndpointResolverParams::new(TestParams(name)))
+            .await
+            .unwrap()
+            .url()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn endpoints_are_cached_per_params() {
+        let resolver = test_resolver(
+            EndpointCachePlugin::new(),
+            ManualTimeSource::new(UNIX_EPOCH),
+        );
+
+        assert_eq!("https://a-1", resolve(&resolver, "a").await);
+        assert_eq!("https://b-2", resolve(&resolver, "b").await);
+        assert_eq!("https://a-1", resolve(&resolver, "a").await);
+        assert_eq!("https://b-2", resolve(&resolver, "b").await);
+
+        // Other params types and errors aren't cached
+        let other = EndpointResolverParams::new(());
+        assert_eq!(
+            "https://other",
+            resolver.resolve_endpoint(&other).await.unwrap().url()
+        );
+        let fail = EndpointResolverParams::new(TestParams("fail"));
+        assert!(resolver.resolve_endpoint(&fail).await.is_err());
+        assert!(resolver.resolve_endpoint(&fail).await.is_err());
+        assert_eq!("https://c-6", resolve(&resolver, "c").await);
+    }
+
+    #[tokio::test]
+    async fn cached_endpoints_expire_after_the_ttl() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let resolver = test_resolver(
+            EndpointCachePlugin::new().ttl(Duration::from_secs(60)),
+            time_source.clone(),
+        );
+
+        assert_eq!("https://a-1", resolve(&resolver, "a").await);
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(59));
+        assert_eq!("https://a-1", resolve(&resolver, "a").await);
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(60));
+        assert_eq!("https://a-2", resolve(&resolver, "a").await);
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_endpoints_are_evicted() {
+        let resolver = test_resolver(
+            EndpointCachePlugin::new().max_entries(2),
+            ManualTimeSource::new(UNIX_EPOCH),
+        );
+
+        assert_eq!("https://a-1", resolve(&resolver, "a").await);
+        assert_eq!("https://b-2", resolve(&resolver, "b").await);
+        assert_eq!("https://a-1", resolve(&resolver, "a").await);
+        // Evicts `b`, which was used less recently than `a`
+        assert_eq!("https://c-3", resolve(&resolver, "c").await);
+        assert_eq!("https://a-1", resolve(&resolver, "a").await);
+        assert_eq!("https://b-4", resolve(&resolver, "b").await);
+    }
+}
@@ -0,0 +1,559 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Endpoint discovery.
+//!
+//! Some services return the endpoint that a request should be sent to from a discovery
+//! operation. The [`EndpointDiscoveryPlugin`] calls the discovery operation, caches the
+//! discovered endpoints per [`DiscoveryKey`] until their TTL elapses, and falls back to the
+//! endpoint resolver that the client was configured with when discovery fails.
+
+use crate::expiring_cache::ExpiringCache;
+use aws_smithy_async::future::BoxFuture;
+use aws_smithy_async::rt::spawn::{SharedSpawn, Spawn};
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::{
+    EndpointFuture, EndpointResolverParams, ResolveEndpoint, SharedEndpointResolver,
+};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+use aws_smithy_runtime_api::client::runtime_plugin::{Order, RuntimePlugin};
+use aws_smithy_runtime_api::client::shutdown::ClientShutdown;
+use aws_smithy_types::endpoint::Endpoint;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::Instrument;
+
+const DEFAULT_REFRESH_WINDOW: Duration = Duration::from_secs(60);
+
+/// The key that discovered endpoints are cached by.
+///
+/// Operations that discover endpoints per resource use the identifiers of that resource in the
+/// key, while other operations usually use a single key for the whole client.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DiscoveryKey(Cow<'static, str>);
+
+impl DiscoveryKey {
+    /// Creates a new `DiscoveryKey`.
+    pub fn new(key: impl Into<Cow<'static, str>>) -> Self {
+        Self(key.into())
+    }
+
+    /// Returns the key as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// An endpoint that was returned by a discovery operation.
+#[derive(Clone, Debug)]
+pub struct DiscoveredEndpoint {
+    endpoint: Endpoint,
+    ttl: Duration,
+}
+
+impl DiscoveredEndpoint {
+    /// Creates a new `DiscoveredEndpoint` that can be used for `ttl`.
+    pub fn new(endpoint: Endpoint, ttl: Duration) -> Self {
+        Self { endpoint, ttl }
+    }
+
+    /// Returns the discovered endpoint.
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Returns how long the endpoint can be used for.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+type Discover =
+    Arc<dyn Fn(DiscoveryKey) -> BoxFuture<'static, DiscoveredEndpoint, BoxError> + Send + Sync>;
+type GetDiscoveryKey = Arc<dyn Fn(&EndpointResolverParams) -> Option<DiscoveryKey> + Send + Sync>;
+
+/// Runtime plugin that resolves endpoints with endpoint discovery.
+///
+/// The plugin wraps the endpoint resolver that the client was configured with. Requests whose
+/// endpoint parameters have a [`DiscoveryKey`] are sent to the endpoint that was discovered for
+/// that key, and other requests, including the discovery operation itself, are resolved with
+/// the wrapped resolver.
+///
+/// Endpoints are discovered the first time that a key is used, and then cached until their TTL
+/// elapses. When a cached endpoint is within the [refresh window](Self::refresh_window) of its
+/// expiration, the first request to use it refreshes it in the background, and keeps using the
+/// cached endpoint in the meantime. The refresh is spawned with the client's
+/// [spawn](aws_smithy_runtime_api::client::runtime_components::RuntimeComponents::spawn)
+/// implementation; without one, the request that triggered it waits for it. If discovery fails,
+/// requests fall back to the wrapped resolver.
+///
+/// Once the client is [shut down](ClientShutdown), endpoints are no longer refreshed in the
+/// background, and refreshes that are in progress are stopped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::endpoint::discovery::{
+///     DiscoveredEndpoint, DiscoveryKey, EndpointDiscoveryPlugin,
+/// };
+/// use aws_smithy_types::endpoint::Endpoint;
+/// use std::time::Duration;
+///
+/// # #[derive(Debug)]
+/// # struct Params { table: Option<String> }
+/// let plugin = EndpointDiscoveryPlugin::new(
+///     |key| async move {
+///         // Call the discovery operation for `key` here
+///         let url = format!("https://{}.discovered.example.com", key.as_str());
+///         Ok(DiscoveredEndpoint::new(
+///             Endpoint::builder().url(url).build(),
+///             Duration::from_secs(600),
+///         ))
+///     },
+///     |params| {
+///         let table = params.get::<Params>()?.table.clone()?;
+///         Some(DiscoveryKey::new(table))
+///     },
+/// );
+/// // Register `plugin` as a runtime plugin on the client config
+/// ```
+#[derive(Clone)]
+pub struct EndpointDiscoveryPlugin {
+    discover: Discover,
+    discovery_key: GetDiscoveryKey,
+    refresh_window: Duration,
+    endpoints: Arc<Mutex<HashMap<DiscoveryKey, CachedEndpoint>>>,
+}
+
+impl fmt::Debug for EndpointDiscoveryPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointDiscoveryPlugin")
+            .field("refresh_window", &self.refresh_window)
+            .field("endpoints", &self.endpoints)
+            .finish()
+    }
+}
+
+impl EndpointDiscoveryPlugin {
+    /// Creates a new `EndpointDiscoveryPlugin`.
+    ///
+    /// `discover` calls the discovery operation for a key, and `discovery_key` returns the key
+    /// for the endpoint parameters of a request, or `None` if the request doesn't use discovered
+    /// endpoints. It must return `None` for the discovery operation itself.
+    pub fn new<D, Fut, K>(discover: D, discovery_key: K) -> Self
+    where
+        D: Fn(DiscoveryKey) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<DiscoveredEndpoint, BoxError>> + Send + 'static,
+        K: Fn(&EndpointResolverParams) -> Option<DiscoveryKey> + Send + Sync + 'static,
+    {
+        Self {
+            discover: Arc::new(move |key| Box::pin(discover(key))),
+            discovery_key: Arc::new(discovery_key),
+            refresh_window: DEFAULT_REFRESH_WINDOW,
+            endpoints: Default::default(),
+        }
+    }
+
+    /// Sets how long before their expiration that discovered endpoints are refreshed.
+    ///
+    /// Defaults to one minute.
+    pub fn refresh_window(mut self, refresh_window: Duration) -> Self {
+        self.refresh_window = refresh_window;
+        self
+    }
+
+    fn cached_endpoint(&self, key: &DiscoveryKey) -> CachedEndpoint {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_insert_with(|| CachedEndpoint {
+                cache: ExpiringCache::new(Duration::ZERO),
+                refreshing: Default::default(),
+            })
+            .clone()
+    }
+
+    /// Discovers the endpoint for a key, and returns it with the time that it expires at.
+    async fn discover(
+        &self,
+        key: DiscoveryKey,
+        now: SystemTime,
+    ) -> Result<((Endpoint, SystemTime), SystemTime), BoxError> {
+        let discovered = (self.discover)(key).await?;
+        let expiration = now + discovered.ttl;
+        tracing::debug!(endpoint = ?discovered.endpoint, ?expiration, "discovered endpoint");
+        Ok(((discovered.endpoint, expiration), expiration))
+    }
+
+    async fn refresh(self, key: DiscoveryKey, cached: CachedEndpoint, now: SystemTime) {
+        match self.discover(key, now).await {
+            Ok((value, expiration)) => cached.cache.set(value, expiration).await,
+            Err(err) => tracing::warn!(
+                error = %DisplayErrorContext(&*err),
+                "failed to refresh discovered endpoint; using the cached endpoint until it expires"
+            ),
+        }
+        cached.refreshing.store(false, Ordering::Release);
+    }
+
+    async fn refresh_in_background(
+        self,
+        key: DiscoveryKey,
+        cached: CachedEndpoint,
+        now: SystemTime,
+        shutdown: Option<ClientShutdown>,
+        spawn: Option<SharedSpawn>,
+    ) {
+        let refresh = self.refresh(key, cached.clone(), now);
+        let refresh = async move {
+            let Some(shutdown) = shutdown else {
+                return refresh.await;
+            };
+            let stopped = shutdown
+                .background_task_token()
+                .run_until_cancelled(refresh)
+                .await
+                .is_none();
+            if stopped {
+                tracing::debug!(
+                    "stopped refreshing the discovered endpoint because the client was shut down"
+                );
+                cached.refreshing.store(false, Ordering::Release);
+            }
+        }
+        .instrument(tracing::debug_span!("refresh_discovered_endpoint"));
+        match spawn {
+            Some(spawn) => spawn.spawn(Box::pin(refresh)),
+            None => refresh.await,
+        }
+    }
+}
+
+impl RuntimePlugin for EndpointDiscoveryPlugin {
+    fn order(&self) -> Order {
+        Order::NestedComponents
+    }
+
+    fn runtime_components(
+        &self,
+        current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        Cow::Owned(
+            RuntimeComponentsBuilder::new("EndpointDiscoveryPlugin").with_endpoint_resolver(Some(
+                DiscoveringEndpointResolver {
+                    plugin: self.clone(),
+                    fallback: current_components.endpoint_resolver(),
+                    time_source: current_components.time_source().unwrap_or_default(),
+                    spawn: current_components.spawn(),
+                },
+            )),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CachedEndpoint {
+    /// The discovered endpoint, with the time that it expires at.
+    cache: ExpiringCache<(Endpoint, SystemTime), BoxError>,
+    refreshing: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct DiscoveringEndpointResolver {
+    plugin: EndpointDiscoveryPlugin,
+    fallback: Option<SharedEndpointResolver>,
+    time_source: SharedTimeSource,
+    spawn: Option<SharedSpawn>,
+}
+
+impl DiscoveringEndpointResolver {
+    async fn fall_back(
+        &self,
+        params: &EndpointResolverParams,
+        err: BoxError,
+    ) -> Result<Endpoint, BoxError> {
+        match &self.fallback {
+            Some(fallback) => {
+                tracing::warn!(
+                    error = %DisplayErrorContext(&*err),
+                    "endpoint discovery failed; falling back to the default endpoint"
+                );
+                fallback.resolve_endpoint(params).await
+            }
+            None => Err(err),
+        }
+    }
+}
+
+impl ResolveEndpoint for DiscoveringEndpointResolver {
+    fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+        let key = match (self.plugin.discovery_key)(params) {
+            Some(key) => key,
+            None => {
+                return match &self.fallback {
+                    Some(fallback) => fallback.resolve_endpoint(params),
+                    None => EndpointFuture::ready(Err(
+                        "no endpoint resolver is configured for requests that don't use endpoint discovery".into(),
+                    )),
+                };
+            }
+        };
+        let now = self.time_source.now();
+        let cached = self.plugin.cached_endpoint(&key);
+        let shutdown = params.get_property::<ClientShutdown>().cloned();
+
+        EndpointFuture::new(async move {
+            if let Some((endpoint, expiration)) = cached.cache.yield_or_clear_if_expired(now).await
+            {
+                // Only one request refreshes the endpoint ahead of its expiration,
+                // while other requests keep using the cached endpoint.
+                if now + self.plugin.refresh_window >= expiration
+                    && !shutdown.as_ref().is_some_and(ClientShutdown::is_shut_down)
+                    && cached
+                        .refreshing
+                        .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                {
+                    self.plugin
+                        .clone()
+                        .refresh_in_background(
+                            key,
+                            cached.clone(),
+                            now,
+                            shutdown,
+                            self.spawn.clone(),
+                        )
+                        .await;
+                }
+                return Ok(endpoint);
+            }
+            let result = cached
+                .cache
+                .get_or_load(|| {
+                    self.plugin
+                        .discover(key, now)
+                        .instrument(tracing::debug_span!("discover_endpoint"))
+                })
+                .await;
+            match result {
+                Ok((endpoint, _)) => Ok(endpoint),
+                Err(err) => self.fall_back(params, err).await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::rt::spawn::TokioSpawn;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use std::sync::atomic::{AtomicBool, AtomicUsize};
+    use std::time::UNIX_EPOCH;
+
+    #[derive(Debug)]
+    struct TestParams(Option<&'static str>);
+
+    #[derive(Debug)]
+    struct DefaultResolver;
+    impl ResolveEndpoint for DefaultResolver {
+        fn resolve_endpoint<'a>(&'a self, _: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+            EndpointFuture::ready(Ok(Endpoint::builder().url("https://default").build()))
+        }
+    }
+
+    /// Returns a resolver whose discovery fails for the key `fail`, and a counter of the
+    /// discovery calls that it made.
+    fn test_resolver(time_source: ManualTimeSource) -> (SharedEndpointResolver, Arc<AtomicUsize>) {
+        let discovered = Arc::new(AtomicUsize::new(0));
+        let plugin = EndpointDiscoveryPlugin::new(
+            {
+                let discovered = discovered.clone();
+                move |key: DiscoveryKey| {
+                    let count = discovered.fetch_add(1, Ordering::SeqCst) + 1;
+                    async move {
+                        if key.as_str() == "fail" {
+                            return Err("discovery failed".into());
+                        }
+                        let url = format!("https://{}-{}", key.as_str(), count);
+                        Ok(DiscoveredEndpoint::new(
+                            Endpoint::builder().url(url).build(),
+                            Duration::from_secs(600),
+                        ))
+                    }
+                }
+            },
+            |params| Some(DiscoveryKey::new(params.get::<TestParams>()?.0?)),
+        );
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(DefaultResolver))
+            .with_time_source(Some(time_source))
+            .with_spawn(Some(TokioSpawn::new()));
+        let resolver = plugin
+            .runtime_components(&components)
+            .endpoint_resolver()
+            .unwrap();
+        (resolver, discovered)
+    }
+
+    async fn resolve(resolver: &SharedEndpointResolver, key: Option<&'static str>) -> String {
+        resolver
+            .resolve_endpoint(&EndpointResolverParams::new(TestParams(key)))
+            .await
+            .unwrap()
+            .url()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn endpoints_are_cached_per_key() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let (resolver, discovered) = test_resolver(time_source);
+
+        assert_eq!("https://a-1", resolve(&resolver, Some("a")).await);
+        assert_eq!("https://a-1", resolve(&resolver, Some("a")).await);
+        assert_eq!("https://b-2", resolve(&resolver, Some("b")).await);
+        assert_eq!("https://default", resolve(&resolver, None).await);
+        assert_eq!(2, discovered.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn expired_endpoints_are_rediscovered() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let (resolver, discovered) = test_resolver(time_source.clone());
+
+        assert_eq!("https://a-1", resolve(&resolver, Some("a")).await);
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(601));
+        assert_eq!("https://a-2", resolve(&resolver, Some("a")).await);
+        assert_eq!(2, discovered.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn endpoints_are_refreshed_before_they_expire() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let (resolver, discovered) = test_resolver(time_source.clone());
+
+        assert_eq!("https://a-1", resolve(&resolver, Some("a")).await);
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(550));
+        // The cached endpoint is used while it's refreshed
+        assert_eq!("https://a-1", resolve(&resolver, Some("a")).await);
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!("https://a-2", resolve(&resolver, Some("a")).await);
+        assert_eq!(2, discovered.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn endpoints_are_not_refreshed_after_shutdown() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let (resolver, discovered) = test_resolver(time_source.clone());
+        let shutdown = ClientShutdown::new();
+        let mut params = EndpointResolverParams::new(TestParams(Some("a")));
+        params.set_property(shutdown.clone());
+        let resolve = || async {
+            resolver
+                .resolve_endpoint(&params)
+                .await
+                .unwrap()
+                .url()
+                .to_string()
+        };
+
+        assert_eq!("https://a-1", resolve().await);
+        shutdown
+            .shutdown(Duration::from_secs(5), None)
+            .await
+            .expect("nothing in flight");
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(550));
+        assert_eq!("https://a-1", resolve().await);
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!("https://a-1", resolve().await);
+        assert_eq!(1, discovered.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn refreshes_in_progress_are_stopped_on_shutdown() {
+        /// Records that the refresh was dropped before it completed
+        struct SetOnDrop(Arc<AtomicBool>);
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let stopped = Arc::new(AtomicBool::new(false));
+        let discovered = Arc::new(AtomicUsize::new(0));
+        let plugin = EndpointDiscoveryPlugin::new(
+            {
+                let (stopped, discovered) = (stopped.clone(), discovered.clone());
+                move |_: DiscoveryKey| {
+                    let refreshing = discovered.fetch_add(1, Ordering::SeqCst) > 0;
+                    let guard = refreshing.then(|| SetOnDrop(stopped.clone()));
+                    async move {
+                        // Refreshes never complete
+                        if let Some(_guard) = guard {
+                            std::future::pending::<()>().await;
+                        }
+                        Ok(DiscoveredEndpoint::new(
+                            Endpoint::builder().url("https://discovered").build(),
+                            Duration::from_secs(600),
+                        ))
+                    }
+                }
+            },
+            |_| Some(DiscoveryKey::new("a")),
+        );
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(DefaultResolver))
+            .with_time_source(Some(time_source.clone()))
+            .with_spawn(Some(TokioSpawn::new()));
+        let resolver = plugin
+            .runtime_components(&components)
+            .endpoint_resolver()
+            .unwrap();
+        let shutdown = ClientShutdown::new();
+        let mut params = EndpointResolverParams::new(TestParams(None));
+        params.set_property(shutdown.clone());
+
+        resolver.resolve_endpoint(&params).await.unwrap();
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(550));
+        resolver.resolve_endpoint(&params).await.unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(2, discovered.load(Ordering::SeqCst));
+        assert!(!stopped.load(Ordering::SeqCst));
+
+        shutdown
+            .shutdown(Duration::from_secs(5), None)
+            .await
+            .expect("nothing in flight");
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert!(stopped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn discovery_errors_fall_back_to_the_default_endpoint() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let (resolver, discovered) = test_resolver(time_source);
+
+        assert_eq!("https://default", resolve(&resolver, Some("fail")).await);
+        // Failed discoveries aren't cached
+        assert_eq!("https://default", resolve(&resolver, Some("fail")).await);
+        assert_eq!(2, discovered.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,486 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Multi-endpoint failover.
+//!
+//! Active/passive deployments across several regions or cells can use the
+//! [`EndpointFailoverPlugin`] to send requests to the first healthy endpoint out of an ordered
+//! list of endpoint groups. Endpoints that fail with connection errors or with one of the
+//! configured status codes are taken out of rotation for a cool-down period, so that retries
+//! and later requests fail over to the next endpoint.
+
+use crate::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::endpoint::{
+    EndpointFuture, EndpointResolverParams, ResolveEndpoint,
+};
+use aws_smithy_runtime_api::client::interceptors::context::FinalizerInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::endpoint::Endpoint;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_COOL_DOWN: Duration = Duration::from_secs(30);
+const DEFAULT_FAILURE_THRESHOLD: u32 = 1;
+const DEFAULT_FAILOVER_STATUS_CODES: &[u16] = &[500, 502, 503, 504];
+
+/// Runtime plugin that fails over between several endpoints.
+///
+/// Endpoints are added in groups, in order of preference. Requests are sent to an endpoint of the
+/// first group that has a healthy endpoint, picked at random according to the endpoint weights.
+/// An endpoint becomes unhealthy for the [cool-down](Builder::cool_down) period after
+/// [`failure_threshold`](Builder::failure_threshold) consecutive attempts to it fail with a
+/// connection error or with one of the [failover status codes](Builder::failover_status_codes).
+/// Once the cool-down elapses, the endpoint is tried again, and a single success makes it healthy.
+/// When every endpoint is unhealthy, requests are sent to the one whose cool-down ends first.
+///
+/// The failover status codes are classified as retryable, so with a retry strategy that allows
+/// several attempts, a request that fails on one endpoint is retried on the next one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::endpoint::failover::EndpointFailoverPlugin;
+/// use aws_smithy_types::endpoint::Endpoint;
+/// use std::time::Duration;
+///
+/// let plugin = EndpointFailoverPlugin::builder()
+///     // The active region
+///     .endpoint(Endpoint::builder().url("https://service.us-east-1.example.com").build())
+///     // Passive cells, which share the traffic 3:1 when the active region is unhealthy
+///     .endpoint_group([
+///         (Endpoint::builder().url("https://cell-1.us-west-2.example.com").build(), 3),
+///         (Endpoint::builder().url("https://cell-2.us-west-2.example.com").build(), 1),
+///     ])
+///     .cool_down(Duration::from_secs(60))
+///     .build();
+/// // Register `plugin` as a runtime plugin on the client config
+/// ```
+#[derive(Clone, Debug)]
+pub struct EndpointFailoverPlugin {
+    inner: Arc<Inner>,
+}
+
+impl EndpointFailoverPlugin {
+    /// Returns a builder for `EndpointFailoverPlugin`.
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+/// Builder for [`EndpointFailoverPlugin`].
+#[derive(Debug, Default)]
+pub struct Builder {
+    groups: Vec<Vec<(Endpoint, u32)>>,
+    failover_status_codes: Option<Cow<'static, [u16]>>,
+    failure_threshold: Option<u32>,
+    cool_down: Option<Duration>,
+}
+
+impl Builder {
+    /// Adds a group with a single endpoint, which is preferred less than the groups added before it.
+    pub fn endpoint(self, endpoint: Endpoint) -> Self {
+        self.endpoint_group([(endpoint, 1)])
+    }
+
+    /// Adds a group of weighted endpoints, which is preferred less than the groups added before it.
+    ///
+    /// Requests to the group are spread across its healthy endpoints in proportion to their weights.
+    /// Endpoints with a weight of zero are only used when no other endpoint of the group is healthy.
+    pub fn endpoint_group(mut self, endpoints: impl IntoIterator<Item = (Endpoint, u32)>) -> Self {
+        let group: Vec<_> = endpoints.into_iter().collect();
+        if !group.is_empty() {
+            self.groups.push(group);
+        }
+        self
+    }
+
+    /// Sets the HTTP status codes that count as failures of an endpoint.
+    ///
+    /// Defaults to 500, 502, 503, and 504.
+    pub fn failover_status_codes(mut self, status_codes: impl Into<Cow<'static, [u16]>>) -> Self {
+        self.failover_status_codes = Some(status_codes.into());
+        self
+    }
+
+    /// Sets the number of consecutive failures after which an endpoint becomes unhealthy.
+    ///
+    /// Defaults to 1.
+    pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = Some(failure_threshold.max(1));
+        self
+    }
+
+    /// Sets how long an unhealthy endpoint is taken out of rotation for.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn cool_down(mut self, cool_down: Duration) -> Self {
+        self.cool_down = Some(cool_down);
+        self
+    }
+
+    /// Builds the [`EndpointFailoverPlugin`].
+    ///
+    /// # Panics
+    /// Panics if no endpoints were added.
+    pub fn build(self) -> EndpointFailoverPlugin {
+        assert!(
+            !self.groups.is_empty(),
+            "at least one endpoint is required for endpoint failover"
+        );
+        EndpointFailoverPlugin {
+            inner: Arc::new(Inner {
+                groups: self
+                    .groups
+                    .into_iter()
+                    .map(|group| {
+                        group
+                            .into_iter()
+                            .map(|(endpoint, weight)| FailoverEndpoint {
+                                endpoint,
+                                weight,
+                                health: Default::default(),
+                            })
+                            .collect()
+                    })
+                    .collect(),
+                failover_status_codes: self
+                    .failover_status_codes
+                    .unwrap_or(Cow::Borrowed(DEFAULT_FAILOVER_STATUS_CODES)),
+                failure_threshold: self.failure_threshold.unwrap_or(DEFAULT_FAILURE_THRESHOLD),
+                cool_down: self.cool_down.unwrap_or(DEFAULT_COOL_DOWN),
+            }),
+        }
+    }
+}
+
+impl RuntimePlugin for EndpointFailoverPlugin {
+    fn runtime_components(
+        &self,
+        current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        Cow::Owned(
+            RuntimeComponentsBuilder::new("EndpointFailoverPlugin")
+                .with_endpoint_resolver(Some(FailoverEndpointResolver {
+                    inner: self.inner.clone(),
+                    time_source: current_components.time_source().unwrap_or_default(),
+                }))
+                .with_interceptor(EndpointHealthInterceptor {
+                    inner: self.inner.clone(),
+                })
+                .with_retry_classifier(HttpStatusCodeClassifier::new_from_codes(
+                    self.inner.failover_status_codes.clone(),
+                )),
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    groups: Vec<Vec<FailoverEndpoint>>,
+    failover_status_codes: Cow<'static, [u16]>,
+    failure_threshold: u32,
+    cool_down: Duration,
+}
+
+impl Inner {
+    fn select(&self, now: SystemTime) -> &Endpoint {
+        for group in &self.groups {
+            let healthy: Vec<_> = group.iter().filter(|e| e.is_healthy(now)).collect();
+            if let Some(endpoint) = pick_weighted(&healthy) {
+                return endpoint;
+            }
+        }
+        let endpoint = self
+            .groups
+            .iter()
+            .flatten()
+            .min_by_key(|e| e.health.lock().unwrap().unhealthy_until)
+            .expect("there is at least one endpoint");
+        tracing::warn!(
+            endpoint = ?endpoint.endpoint,
+            "every endpoint is unhealthy; using the one whose cool-down ends first"
+        );
+        &endpoint.endpoint
+    }
+
+    fn record(&self, url: &str, failed: bool, now: SystemTime) {
+        let Some(endpoint) = self
+            .groups
+            .iter()
+            .flatten()
+            .find(|e| e.endpoint.url() == url)
+        else {
+            return;
+        };
+        let mut health = endpoint.health.lock().unwrap();
+        if !failed {
+            *health = Health::default();
+            return;
+        }
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= self.failure_threshold {
+            let unhealthy_until = now + self.cool_down;
+            tracing::debug!(endpoint = url, ?unhealthy_until, "endpoint is unhealthy");
+            health.unhealthy_until = Some(unhealthy_until);
+        }
+    }
+}
+
+/// Picks one of the candidates at random according to their weights.
+fn pick_weighted<'a>(candidates: &[&'a FailoverEndpoint]) -> Option<&'a Endpoint> {
+    let total: u64 = candidates.iter().map(|e| u64::from(e.weight)).sum();
+    if total == 0 {
+        return candidates.first().map(|e| &e.endpoint);
+    }
+    let mut pick = fastrand::u64(0..total);
+    for candidate in candidates {
+        let weight = u64::from(candidate.weight);
+        if pick < weight {
+            return Some(&candidate.endpoint);
+        }
+        pick -= weight;
+    }
+    unreachable!("the pick is less than the total weight")
+}
+
+#[derive(Debug)]
+struct FailoverEndpoint {
+    endpoint: Endpoint,
+    weight: u32,
+    health: Mutex<Health>,
+}
+
+impl FailoverEndpoint {
+    fn is_healthy(&self, now: SystemTime) -> bool {
+        match self.health.lock().unwrap().unhealthy_until {
+            Some(unhealthy_until) => now >= unhealthy_until,
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    unhealthy_until: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+struct FailoverEndpointResolver {
+    inner: Arc<Inner>,
+    time_source: SharedTimeSource,
+}
+
+impl ResolveEndpoint for FailoverEndpointResolver {
+    fn resolve_endpoint<'a>(&'a self, _params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+        let endpoint = self.inner.select(self.time_source.now());
+        EndpointFuture::ready(Ok(endpoint.clone()))
+    }
+}
+
+/// Records the outcome of each attempt against the health of the endpoint it was sent to.
+#[derive(Debug)]
+struct EndpointHealthInterceptor {
+    inner: Arc<Inner>,
+}
+
+impl Intercept for EndpointHealthInterceptor {
+    fn name(&self) -> &'static str {
+        "EndpointHealthInterceptor"
+    }
+
+    fn read_after_attempt(
+        &self,
+        context: &FinalizerInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(endpoint) = cfg.load::<Endpoint>() else {
+            return Ok(());
+        };
+        let failed = if let Some(response) = context.response() {
+            self.inner
+                .failover_status_codes
+                .contains(&response.status().as_u16())
+        } else if let Some(Err(err)) = context.output_or_error() {
+            err.is_connector_error() || err.is_timeout_error()
+        } else {
+            return Ok(());
+        };
+        let now = runtime_components.time_source().unwrap_or_default().now();
+        self.inner.record(endpoint.url(), failed, now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_async::time::TimeSource;
+    use std::time::UNIX_EPOCH;
+
+    fn endpoint(url: &'static str) -> Endpoint {
+        Endpoint::builder().url(url).build()
+    }
+
+    async fn resolve(resolver: &FailoverEndpointResolver) -> String {
+        resolver
+            .resolve_endpoint(&EndpointResolverParams::new(()))
+            .await
+            .unwrap()
+            .url()
+            .to_string()
+    }
+
+    fn test_resolver(
+        plugin: &EndpointFailoverPlugin,
+        time_source: ManualTimeSource,
+    ) -> FailoverEndpointResolver {
+        FailoverEndpointResolver {
+            inner: plugin.inner.clone(),
+            time_source: SharedTimeSource::new(time_source),
+        }
+    }
+
+    #[tokio::test]
+    async fn unhealthy_endpoints_fail_over_until_their_cool_down_ends() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let plugin = EndpointFailoverPlugin::builder()
+            .endpoint(endpoint("https://primary"))
+            .endpoint(endpoint("https://secondary"))
+            .cool_down(Duration::from_secs(30))
+            .build();
+        let resolver = test_resolver(&plugin, time_source.clone());
+        let inner = &plugin.inner;
+
+        assert_eq!("https://primary", resolve(&resolver).await);
+        inner.record("https://primary", true, UNIX_EPOCH);
+        assert_eq!("https://secondary", resolve(&resolver).await);
+
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(30));
+        assert_eq!("https://primary", resolve(&resolver).await);
+        // A failure after the cool-down makes the endpoint unhealthy again right away
+        inner.record("https://primary", true, time_source.now());
+        assert_eq!("https://secondary", resolve(&resolver).await);
+
+        time_source.set_time(UNIX_EPOCH + Duration::from_secs(60));
+        inner.record("https://primary", false, time_source.now());
+        inner.record("https://primary", true, time_source.now());
+        // A success resets the consecutive failures
+        assert_eq!("https://secondary", resolve(&resolver).await);
+    }
+
+    #[tokio::test]
+    async fn endpoints_become_unhealthy_after_the_failure_threshold() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let plugin = EndpointFailoverPlugin::builder()
+            .endpoint(endpoint("https://primary"))
+            .endpoint(endpoint("https://secondary"))
+            .failure_threshold(2)
+            .build();
+        let resolver = test_resolver(&plugin, time_source);
+
+        plugin.inner.record("https://primary", true, UNIX_EPOCH);
+        assert_eq!("https://primary", resolve(&resolver).await);
+        plugin.inner.record("https://primary", true, UNIX_EPOCH);
+        assert_eq!("https://secondary", resolve(&resolver).await);
+    }
+
+    #[tokio::test]
+    async fn requests_are_spread_across_a_group_by_weight() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let plugin = EndpointFailoverPlugin::builder()
+            .endpoint_group([
+                (endpoint("https://a"), 1),
+                (endpoint("https://b"), 1),
+                (endpoint("https://standby"), 0),
+            ])
+            .build();
+        let resolver = test_resolver(&plugin, time_source);
+
+        let mut resolved = std::collections::HashSet::new();
+        for _ in 0..100 {
+            resolved.insert(resolve(&resolver).await);
+        }
+        assert_eq!(
+            resolved,
+            ["https://a".to_string(), "https://b".to_string()].into()
+        );
+
+        plugin.inner.record("https://a", true, UNIX_EPOCH);
+        plugin.inner.record("https://b", true, UNIX_EPOCH);
+        assert_eq!("https://standby", resolve(&resolver).await);
+        plugin.inner.record("https://standby", true, UNIX_EPOCH);
+        // Every endpoint is unhealthy, so the one whose cool-down ends first is used
+        assert_eq!("https://a", resolve(&resolver).await);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test]
+    async fn failed_attempts_are_retried_on_the_next_endpoint() {
+        use crate::client::http::test_util::{ReplayEvent, StaticReplayClient};
+        use crate::client::orchestrator::operation::Operation;
+        use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, OrchestratorError};
+        use aws_smithy_types::body::SdkBody;
+        use aws_smithy_types::retry::RetryConfig;
+        use aws_smithy_types::timeout::TimeoutConfig;
+        use std::convert::Infallible;
+
+        let event = |uri: &str, status: u16| {
+            ReplayEvent::new(
+                http::Request::builder()
+                    .uri(uri)
+                    .body(SdkBody::empty())
+                    .unwrap(),
+                http::Response::builder()
+                    .status(status)
+                    .body(SdkBody::empty())
+                    .unwrap(),
+            )
+        };
+        let http_client = StaticReplayClient::new(vec![
+            event("https://primary/", 503),
+            event("https://secondary/", 200),
+            event("https://secondary/", 200),
+        ]);
+        let operation = Operation::builder()
+            .service_name("test")
+            .operation_name("test")
+            .http_client(http_client.clone())
+            .endpoint_url("https://unused")
+            .runtime_plugin(
+                EndpointFailoverPlugin::builder()
+                    .endpoint(endpoint("https://primary"))
+                    .endpoint(endpoint("https://secondary"))
+                    .build(),
+            )
+            .no_auth()
+            .standard_retry(&RetryConfig::standard().with_initial_backoff(Duration::from_millis(1)))
+            .timeout_config(TimeoutConfig::disabled())
+            .serializer(|_: ()| Ok(HttpRequest::new(SdkBody::empty())))
+            .deserializer::<_, Infallible>(|response| {
+                if response.status().is_success() {
+                    Ok(())
+                } else {
+                    Err(OrchestratorError::other("server error"))
+                }
+            })
+            .build();
+
+        operation.invoke(()).await.expect("success");
+        // The primary endpoint is still cooling down
+        operation.invoke(()).await.expect("success");
+        http_client.assert_requests_match(&[]);
+    }
+}
@@ -0,0 +1,283 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Filtering of the spans and events that an operation emits.
+//!
+//! An operation that's wrapped with [`FilteredFuture`] only forwards the spans and events that its
+//! filter allows to the dispatcher that was the default when it was polled. The dispatcher to
+//! forward to is looked up every time the operation is polled, so changing the default dispatcher
+//! later is respected. A single dispatcher is shared by every filtered operation, since registering
+//! a dispatcher rebuilds the interest of every callsite.
+
+use once_cell::sync::Lazy;
+use pin_project_lite::pin_project;
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Dispatch, Event, Metadata, Subscriber};
+
+/// Decides which of an operation's spans and events are emitted.
+pub(crate) trait FilterSpans: Send + Sync + fmt::Debug {
+    /// Returns true if spans and events with the given metadata should be emitted.
+    fn allows(&self, metadata: &Metadata<'_>) -> bool;
+}
+
+/// Allows the spans and events that both filters allow.
+#[derive(Debug)]
+struct Both(Arc<dyn FilterSpans>, Arc<dyn FilterSpans>);
+
+impl FilterSpans for Both {
+    fn allows(&self, metadata: &Metadata<'_>) -> bool {
+        self.0.allows(metadata) && self.1.allows(metadata)
+    }
+}
+
+/// Returns a filter that allows the spans and events that both filters allow.
+pub(crate) fn both(
+    first: Option<Arc<dyn FilterSpans>>,
+    second: Option<Arc<dyn FilterSpans>>,
+) -> Option<Arc<dyn FilterSpans>> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(Arc::new(Both(first, second))),
+        (first, second) => first.or(second),
+    }
+}
+
+/// The filter of the operation that's being polled, and the dispatcher to forward to.
+#[derive(Clone)]
+struct Scope {
+    filter: Arc<dyn FilterSpans>,
+    forward_to: Dispatch,
+}
+
+thread_local! {
+    static SCOPE: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+static DISPATCH: Lazy<Dispatch> = Lazy::new(|| Dispatch::new(Filtered));
+
+/// Returns the scope of the operation that's being polled, if any.
+fn current_scope() -> Option<Scope> {
+    SCOPE.with(|scope| scope.borrow().clone())
+}
+
+pin_project! {
+    /// A future that only emits the spans and events that its filter allows.
+    ///
+    /// Everything is emitted when there's no filter.
+    pub(crate) struct FilteredFuture<F> {
+        #[pin]
+        inner: F,
+        filter: Option<Arc<dyn FilterSpans>>,
+    }
+}
+
+impl<F> FilteredFuture<F> {
+    pub(crate) fn new(inner: F, filter: Option<Arc<dyn FilterSpans>>) -> Self {
+        Self { inner, filter }
+    }
+}
+
+impl<F: Future> Future for FilteredFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let filter = match this.filter {
+            Some(filter) => filter.clone(),
+            None => return this.inner.poll(cx),
+        };
+        let default = tracing::dispatcher::get_default(Dispatch::clone);
+        // When this is polled by another filtered operation, both filters apply
+        let scope = match current_scope() {
+            Some(outer) if default.is::<Filtered>() => Scope {
+                filter: Arc::new(Both(outer.filter, filter)),
+                forward_to: outer.forward_to,
+            },
+            _ => Scope {
+                filter,
+                forward_to: default,
+            },
+        };
+        let previous = SCOPE.with(|current| current.replace(Some(scope)));
+        let _restore = RestoreScope(previous);
+        let inner = this.inner;
+        tracing::dispatcher::with_default(&DISPATCH, || inner.poll(cx))
+    }
+}
+
+/// Restores the scope of the enclosing operation once polling is done, even if it panicked.
+struct RestoreScope(Option<Scope>);
+
+impl Drop for RestoreScope {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        SCOPE.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Forwards the spans and events that the current operation's filter allows.
+///
+/// Spans that outlive the poll that created them, such as the span of a future that's dropped
+/// after the operation completes, are forwarded to the default dispatcher at that time.
+struct Filtered;
+
+impl Filtered {
+    fn forward<R>(&self, mut f: impl FnMut(&Dispatch) -> R) -> R {
+        match current_scope() {
+            Some(scope) => f(&scope.forward_to),
+            None => tracing::dispatcher::get_default(f),
+        }
+    }
+}
+
+impl Subscriber for Filtered {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // The filter depends on the operation, so it has to be checked every time
+        Interest::sometimes()
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        None
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        match current_scope() {
+            Some(scope) => scope.filter.allows(metadata) && scope.forward_to.enabled(metadata),
+            None => tracing::dispatcher::get_default(|default| default.enabled(metadata)),
+        }
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.forward(|dispatch| dispatch.new_span(span))
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        self.forward(|dispatch| dispatch.record(span, values))
+    }
+
+    fn record_follows_from(&self, span: &Id, follows: &Id) {
+        self.forward(|dispatch| dispatch.record_follows_from(span, follows))
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        self.forward(|dispatch| dispatch.event(event))
+    }
+
+    fn enter(&self, span: &Id) {
+        self.forward(|dispatch| dispatch.enter(span))
+    }
+
+    fn exit(&self, span: &Id) {
+        self.forward(|dispatch| dispatch.exit(span))
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        self.forward(|dispatch| dispatch.clone_span(id))
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        self.forward(|dispatch| dispatch.try_close(id.clone()))
+    }
+
+    fn current_span(&self) -> tracing_core::span::Current {
+        self.forward(|dispatch| dispatch.current_span())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tracing::Level;
+
+    #[derive(Debug)]
+    struct MaxLevel(Level);
+
+    impl FilterSpans for MaxLevel {
+        fn allows(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= &self.0
+        }
+    }
+
+    /// Records the messages of the events that it receives.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(event.metadata().level().to_string());
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    async fn log_every_level() {
+        tracing::trace!("trace");
+        tracing::debug!("debug");
+        tracing::info!("info");
+        tracing::warn!("warn");
+    }
+
+    #[tokio::test]
+    async fn filters_apply_to_the_dispatcher_that_is_the_default_when_polled() {
+        for expected in [vec!["INFO", "WARN"], vec!["WARN"]] {
+            // A new default dispatcher each time, which the filter must forward to
+            let recorder = Recorder::default();
+            let dispatch = Dispatch::new(recorder.clone());
+            let level = if expected.len() == 2 {
+                Level::INFO
+            } else {
+                Level::WARN
+            };
+            let filtered = FilteredFuture::new(log_every_level(), Some(Arc::new(MaxLevel(level))));
+            tracing::dispatcher::with_default(&dispatch, || {
+                futures_util::FutureExt::now_or_never(filtered).unwrap()
+            });
+            assert_eq!(expected, *recorder.0.lock().unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn nested_filters_both_apply() {
+        let recorder = Recorder::default();
+        let dispatch = Dispatch::new(recorder.clone());
+        let inner = FilteredFuture::new(log_every_level(), Some(Arc::new(MaxLevel(Level::INFO))));
+        let outer = FilteredFuture::new(
+            async {
+                tracing::debug!("debug");
+                inner.await
+            },
+            Some(Arc::new(MaxLevel(Level::DEBUG))),
+        );
+        tracing::dispatcher::with_default(&dispatch, || {
+            futures_util::FutureExt::now_or_never(outer).unwrap()
+        });
+        assert_eq!(vec!["DEBUG", "INFO", "WARN"], *recorder.0.lock().unwrap());
+    }
+}
@@ -11,10 +11,49 @@ pub mod test_util;
 
 /// Default HTTP and TLS connectors that use hyper 0.14.x and rustls.
 ///
-/// This module is named after the hyper version number since we anticipate
-/// needing to provide equivalent functionality for hyper 1.x in the future.
+/// This module is named after the hyper version number, since equivalent
+/// functionality for hyper 1.x is provided by the [`hyper_1`] module.
 #[cfg(feature = "connector-hyper-0-14-x")]
 pub mod hyper_014;
 
+/// HTTP client that uses hyper 1.x, hyper-util, and rustls.
+///
+/// When the `connector-hyper-1-x` feature is enabled, this client is used as the default
+/// HTTP client instead of the hyper 0.14.x client.
+#[cfg(feature = "connector-hyper-1-x")]
+pub mod hyper_1;
+
+/// Experimental HTTP client that uses HTTP/3, with quinn and h3.
+///
+/// Requests fall back to the hyper 1.x client when a HTTP/3 connection can't be established.
+#[cfg(feature = "connector-http3-experimental")]
+pub mod http3;
+
+/// HTTP client that uses the system's libcurl.
+#[cfg(feature = "connector-curl")]
+pub mod curl;
+
+/// HTTP client for WebAssembly, that uses `fetch` or wasi-http.
+#[cfg(all(
+    feature = "connector-wasm",
+    target_arch = "wasm32",
+    any(target_os = "unknown", target_os = "wasi")
+))]
+pub mod wasm;
+
 /// HTTP body and body-wrapper types
 pub mod body;
+
+pub mod proxy;
+
+#[cfg(any(feature = "connector-hyper-0-14-x", feature = "connector-hyper-1-x"))]
+mod hyper_common;
+
+#[cfg(any(feature = "connector-hyper-0-14-x", feature = "connector-hyper-1-x"))]
+mod request_limit;
+
+pub mod tls;
+
+/// Adapters between HTTP clients and [tower](https://docs.rs/tower) services.
+#[cfg(feature = "tower")]
+pub mod tower;
@@ -3,4 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+pub(crate) mod counting;
+pub mod idle_timeout;
 pub mod minimum_throughput;
+pub mod resumable;
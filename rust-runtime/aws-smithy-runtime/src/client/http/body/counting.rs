@@ -0,0 +1,119 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A body-wrapping type that counts the bytes that are read from the body.
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::http::BytesTransferred;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Whether a body is sent or received.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Direction {
+    Sent,
+    Received,
+}
+
+pin_project_lite::pin_project! {
+    /// A body-wrapping type that records the bytes that are read from the body in counters.
+    pub(crate) struct CountingBody<B> {
+        direction: Direction,
+        counters: Vec<BytesTransferred>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> CountingBody<B> {
+    pub(crate) fn new(body: B, direction: Direction, counters: Vec<BytesTransferred>) -> Self {
+        Self {
+            direction,
+            counters,
+            inner: body,
+        }
+    }
+}
+
+/// Counts the bytes of `body` in `counters` as it's read.
+///
+/// The body stays retryable, and each copy of it counts its bytes separately.
+pub(crate) fn count_bytes(
+    body: SdkBody,
+    direction: Direction,
+    counters: Vec<BytesTransferred>,
+) -> SdkBody {
+    body.map_preserve_contents(move |body| {
+        SdkBody::from_body_0_4(CountingBody::new(body, direction, counters.clone()))
+    })
+}
+
+impl<B> http_body_0_4::Body for CountingBody<B>
+where
+    B: http_body_0_4::Body<Data = Bytes, Error = BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        let poll = this.inner.poll_data(cx);
+        if let Poll::Ready(Some(Ok(data))) = &poll {
+            let len = data.len() as u64;
+            for counter in this.counters.iter() {
+                match this.direction {
+                    Direction::Sent => counter.record_sent(len),
+                    Direction::Received => counter.record_received(len),
+                }
+            }
+        }
+        poll
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_smithy_types::byte_stream::ByteStream;
+
+    #[tokio::test]
+    async fn counts_bytes_of_every_copy() {
+        let attempt = BytesTransferred::new();
+        let operation = BytesTransferred::new();
+        let body = count_bytes(
+            SdkBody::from("hello"),
+            Direction::Sent,
+            vec![attempt.clone(), operation.clone()],
+        );
+        assert_eq!(Some(&b"hello"[..]), body.bytes());
+        let copy = body.try_clone().expect("retryable");
+        for body in [body, copy] {
+            ByteStream::new(body).collect().await.unwrap();
+        }
+        assert_eq!(10, attempt.sent());
+        assert_eq!(10, operation.sent());
+        assert_eq!(0, operation.received());
+    }
+}
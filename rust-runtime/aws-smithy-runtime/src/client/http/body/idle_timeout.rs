@@ -0,0 +1,208 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A body-wrapping type that fails if no data is received for too long while the body is being read.
+//!
+//! Unlike an overall timeout, this doesn't limit how long it takes to read the entire body, so long
+//! running streams are unaffected as long as data keeps arriving.
+
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+use aws_smithy_runtime_api::box_error::BoxError;
+use bytes::Bytes;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+pin_project_lite::pin_project! {
+    /// A body-wrapping type that fails if no data is received for too long while the body is being read.
+    ///
+    /// The timer only runs while the body is waiting on the inner body for data, so time spent by the
+    /// caller processing data between reads doesn't count against the timeout.
+    pub struct IdleTimeoutBody<B> {
+        async_sleep: SharedAsyncSleep,
+        timeout: Duration,
+        sleep_fut: Option<Sleep>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> IdleTimeoutBody<B> {
+    /// Creates a new `IdleTimeoutBody` that fails if `body` doesn't yield data for `timeout`.
+    pub fn new(async_sleep: SharedAsyncSleep, body: B, timeout: Duration) -> Self {
+        Self {
+            async_sleep,
+            timeout,
+            sleep_fut: None,
+            inner: body,
+        }
+    }
+}
+
+impl<B> fmt::Debug for IdleTimeoutBody<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleTimeoutBody")
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// The error returned by [`IdleTimeoutBody`] when no data is received within the timeout.
+#[derive(Debug)]
+pub struct IdleTimeoutError {
+    timeout: Duration,
+}
+
+impl IdleTimeoutError {
+    /// Returns the timeout that was exceeded.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl fmt::Display for IdleTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no data was received for {:?} while reading the body",
+            self.timeout
+        )
+    }
+}
+
+impl std::error::Error for IdleTimeoutError {}
+
+impl<B> http_body_0_4::Body for IdleTimeoutBody<B>
+where
+    B: http_body_0_4::Body<Data = Bytes, Error = BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        match this.inner.poll_data(cx) {
+            Poll::Pending => {}
+            res => {
+                // Data was received (or the body ended), so the next read gets a fresh timer
+                *this.sleep_fut = None;
+                return res;
+            }
+        }
+
+        let timeout = *this.timeout;
+        let async_sleep = this.async_sleep;
+        let sleep_fut = this
+            .sleep_fut
+            .get_or_insert_with(|| async_sleep.sleep(timeout));
+        match Pin::new(sleep_fut).poll(cx) {
+            Poll::Ready(()) => {
+                *this.sleep_fut = None;
+                Poll::Ready(Some(Err(IdleTimeoutError { timeout }.into())))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use std::collections::VecDeque;
+
+    // Yields each chunk after waiting for the paired delay.
+    struct DelayedBody {
+        chunks: VecDeque<(Duration, &'static str)>,
+        sleep_fut: Option<Sleep>,
+    }
+
+    impl http_body_0_4::Body for DelayedBody {
+        type Data = Bytes;
+        type Error = BoxError;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            let Some((delay, _)) = self.chunks.front().cloned() else {
+                return Poll::Ready(None);
+            };
+            let sleep_fut = self
+                .sleep_fut
+                .get_or_insert_with(|| TokioSleep::new().sleep(delay));
+            match Pin::new(sleep_fut).poll(cx) {
+                Poll::Ready(()) => {
+                    self.sleep_fut = None;
+                    let (_, data) = self.chunks.pop_front().unwrap();
+                    Poll::Ready(Some(Ok(Bytes::from_static(data.as_bytes()))))
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn body(chunks: &[(u64, &'static str)]) -> ByteStream {
+        let inner = DelayedBody {
+            chunks: chunks
+                .iter()
+                .map(|(secs, data)| (Duration::from_secs(*secs), *data))
+                .collect(),
+            sleep_fut: None,
+        };
+        ByteStream::new(SdkBody::from_body_0_4(IdleTimeoutBody::new(
+            SharedAsyncSleep::new(TokioSleep::new()),
+            inner,
+            Duration::from_secs(5),
+        )))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_but_steady_body_succeeds() {
+        // Takes longer than the timeout overall, but never goes idle for that long
+        let data = body(&[(4, "a"), (4, "b"), (4, "c"), (4, "d")])
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(b"abcd", &data.into_bytes()[..]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_body_fails() {
+        let err = body(&[(1, "a"), (10, "b")]).collect().await.unwrap_err();
+        let message = format!("{}", DisplayErrorContext(&err));
+        assert!(message.contains("no data was received for 5s"), "{message}");
+    }
+}
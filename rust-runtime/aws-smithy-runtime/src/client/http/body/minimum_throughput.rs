@@ -66,7 +66,7 @@ impl<B> MinimumThroughputBody<B> {
 }
 
 #[derive(Debug)]
-enum Error {
+pub(crate) enum Error {
     ThroughputBelowMinimum {
         expected: Throughput,
         actual: Throughput,
@@ -8,7 +8,7 @@ use std::fmt;
 use std::time::{Duration, SystemTime};
 
 #[derive(Debug, Clone, Copy)]
-pub(super) struct Throughput {
+pub(crate) struct Throughput {
     bytes_read: f64,
     per_time_elapsed: Duration,
 }
@@ -0,0 +1,648 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A body type that resumes ranged downloads from the last received byte when reading fails.
+//!
+//! This is intended for operations that accept an HTTP `Range` header, such as S3's `GetObject`.
+//! If the connection is lost partway through the response body, a new request is made for the
+//! remaining bytes, and its body is stitched onto what has already been read so that the caller
+//! sees one continuous stream.
+
+use crate::client::http::body::idle_timeout::IdleTimeoutError;
+use crate::client::http::body::minimum_throughput;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use bytes::Bytes;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+const DEFAULT_MAX_RESUMES: u32 = 3;
+
+type FetchFuture = Pin<Box<dyn Future<Output = Result<RangeResponse, BoxError>> + Send>>;
+type Fetch = Arc<dyn Fn(RangeRequest) -> FetchFuture + Send + Sync>;
+
+/// Returns the value of a `Range` header that requests every byte starting at `offset`.
+///
+/// # Examples
+///
+/// ```rust
+/// use aws_smithy_runtime::client::http::body::resumable::range_from;
+///
+/// assert_eq!("bytes=1024-", range_from(1024));
+/// ```
+pub fn range_from(offset: u64) -> String {
+    format!("bytes={offset}-")
+}
+
+/// A request for the part of a download that starts at a given offset.
+///
+/// Passed to the `fetch` function of a [`ResumableBody`].
+#[derive(Clone, Debug)]
+pub struct RangeRequest {
+    offset: u64,
+    if_match: Option<String>,
+}
+
+impl RangeRequest {
+    /// Returns the offset of the first byte to request.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the value of the `Range` header to send.
+    pub fn range(&self) -> String {
+        range_from(self.offset)
+    }
+
+    /// Returns the value of the `If-Match` header to send, if any.
+    ///
+    /// This is the ETag of the initial response, so that the download fails rather than mixing
+    /// the bytes of two versions of the object if it changes before the download is resumed.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+}
+
+/// A response to a [`RangeRequest`].
+///
+/// Returned by the `fetch` function of a [`ResumableBody`]. The status code and headers of the
+/// response are used to check that its body continues the download from the requested offset.
+#[derive(Debug)]
+pub struct RangeResponse {
+    status: u16,
+    content_range: Option<String>,
+    etag: Option<String>,
+    body: ByteStream,
+}
+
+impl RangeResponse {
+    /// Creates a new `RangeResponse` with the given status code and body.
+    pub fn new(status: u16, body: ByteStream) -> Self {
+        Self {
+            status,
+            content_range: None,
+            etag: None,
+            body,
+        }
+    }
+
+    /// Sets the value of the `Content-Range` header of the response.
+    pub fn content_range(mut self, content_range: impl Into<String>) -> Self {
+        self.set_content_range(Some(content_range.into()));
+        self
+    }
+
+    /// Sets the value of the `Content-Range` header of the response.
+    pub fn set_content_range(&mut self, content_range: Option<String>) -> &mut Self {
+        self.content_range = content_range;
+        self
+    }
+
+    /// Sets the value of the `ETag` header of the response.
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        self.set_etag(Some(etag.into()));
+        self
+    }
+
+    /// Sets the value of the `ETag` header of the response.
+    pub fn set_etag(&mut self, etag: Option<String>) -> &mut Self {
+        self.etag = etag;
+        self
+    }
+}
+
+/// Error returned when the response to a [`RangeRequest`] doesn't continue the download.
+#[derive(Debug)]
+pub struct ResumeError {
+    offset: u64,
+    kind: ResumeErrorKind,
+}
+
+#[derive(Debug)]
+enum ResumeErrorKind {
+    UnexpectedStatus(u16),
+    UnexpectedContentRange(Option<String>),
+    ETagMismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+impl ResumeError {
+    /// Returns the offset that the download was being resumed from.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to resume download at offset {}: ", self.offset)?;
+        match &self.kind {
+            ResumeErrorKind::UnexpectedStatus(status) => {
+                write!(f, "expected status 206, but got {status}")
+            }
+            ResumeErrorKind::UnexpectedContentRange(Some(content_range)) => {
+                write!(f, "the response has an unexpected content range ({content_range})")
+            }
+            ResumeErrorKind::UnexpectedContentRange(None) => {
+                write!(f, "the response doesn't have a content range")
+            }
+            ResumeErrorKind::ETagMismatch { expected, actual } => write!(
+                f,
+                "the object changed since the download started (expected ETag {expected}, but got {})",
+                actual.as_deref().unwrap_or("none")
+            ),
+        }
+    }
+}
+
+impl Error for ResumeError {}
+
+/// A body that resumes a ranged download from the last received offset when reading fails.
+///
+/// The `fetch` function is given a [`RangeRequest`] for the offset of the first byte that
+/// hasn't been received yet, and must return a future that resolves to a [`RangeResponse`]
+/// whose body starts at that offset. It's called with an offset of zero for the initial request.
+///
+/// Only transient I/O errors from the underlying body, such as the connection being reset or
+/// timing out, trigger a resume, up to the configured maximum number of resumes. Other errors,
+/// and errors returned by `fetch` itself, are passed through to the caller as-is, since retrying
+/// the request is the responsibility of the client making it.
+///
+/// Before the body of a resumed response is used, it's checked that:
+/// - the status code is `206 Partial Content`,
+/// - the `Content-Range` header starts at the requested offset, and
+/// - the `ETag` header matches the `ETag` of the initial response, if it had one.
+///
+/// Otherwise, a [`ResumeError`] is returned.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use aws_smithy_runtime::client::http::body::resumable::{RangeResponse, ResumableBody};
+///
+/// let body = ResumableBody::new(move |request| {
+///     let client = client.clone();
+///     async move {
+///         let mut builder = http::Request::get("https://example.com/large-file")
+///             .header("range", request.range());
+///         if let Some(etag) = request.if_match() {
+///             builder = builder.header("if-match", etag);
+///         }
+///         // `send` stands in for making the request with your HTTP client
+///         let response = client.send(builder.body(SdkBody::empty())?).await?;
+///         let header = |name| Some(response.headers().get(name)?.to_str().ok()?.to_string());
+///         let (content_range, etag) = (header("content-range"), header("etag"));
+///         let mut range_response = RangeResponse::new(
+///             response.status().as_u16(),
+///             ByteStream::new(response.into_body()),
+///         );
+///         range_response
+///             .set_content_range(content_range)
+///             .set_etag(etag);
+///         Ok::<_, BoxError>(range_response)
+///     }
+/// })
+/// .with_max_resumes(5)
+/// .into_byte_stream();
+/// let data = body.collect().await?;
+/// ```
+pub struct ResumableBody {
+    fetch: Fetch,
+    state: State,
+    offset: u64,
+    etag: Option<String>,
+    max_resumes: u32,
+    resumes: u32,
+}
+
+enum State {
+    // The future is wrapped in a mutex so that the body is `Sync` without requiring the same of
+    // the future. It's only ever accessed through `Mutex::get_mut`, so it's never actually locked.
+    Fetching(Mutex<FetchFuture>),
+    Streaming(SdkBody),
+    Done,
+}
+
+impl fmt::Debug for ResumableBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumableBody")
+            .field("offset", &self.offset)
+            .field("etag", &self.etag)
+            .field("max_resumes", &self.max_resumes)
+            .field("resumes", &self.resumes)
+            .finish()
+    }
+}
+
+impl ResumableBody {
+    /// Creates a new `ResumableBody` that makes requests with the given `fetch` function.
+    ///
+    /// By default, the download is resumed at most three times.
+    pub fn new<F, Fut, E>(fetch: F) -> Self
+    where
+        F: Fn(RangeRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<RangeResponse, E>> + Send + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        let fetch: Fetch = Arc::new(move |request| {
+            let fut = fetch(request);
+            Box::pin(async move { fut.await.map_err(Into::into) })
+        });
+        let initial_request = RangeRequest {
+            offset: 0,
+            if_match: None,
+        };
+        Self {
+            state: State::Fetching(Mutex::new(fetch(initial_request))),
+            fetch,
+            offset: 0,
+            etag: None,
+            max_resumes: DEFAULT_MAX_RESUMES,
+            resumes: 0,
+        }
+    }
+
+    /// Sets the maximum number of times the download will be resumed before the error from the
+    /// underlying body is returned to the caller.
+    pub fn with_max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+        self
+    }
+
+    /// Returns the number of bytes that have been received so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the number of times the download has been resumed so far.
+    pub fn resumes(&self) -> u32 {
+        self.resumes
+    }
+
+    /// Converts this body into a [`ByteStream`].
+    pub fn into_byte_stream(self) -> ByteStream {
+        ByteStream::new(SdkBody::from_body_0_4(self))
+    }
+
+    /// Checks that a response continues the download from the current offset, and returns its
+    /// body if it does.
+    fn accept(&mut self, response: RangeResponse) -> Result<SdkBody, ResumeError> {
+        let error = |kind| ResumeError {
+            offset: self.offset,
+            kind,
+        };
+        if self.resumes == 0 {
+            // The initial request may be answered with the whole object
+            if !matches!(response.status, 200 | 206) {
+                return Err(error(ResumeErrorKind::UnexpectedStatus(response.status)));
+            }
+            self.etag = response.etag;
+            return Ok(response.body.into_inner());
+        }
+        if response.status != 206 {
+            return Err(error(ResumeErrorKind::UnexpectedStatus(response.status)));
+        }
+        let start = response
+            .content_range
+            .as_deref()
+            .and_then(content_range_start);
+        if start != Some(self.offset) {
+            return Err(error(ResumeErrorKind::UnexpectedContentRange(
+                response.content_range,
+            )));
+        }
+        if let Some(expected) = &self.etag {
+            if response.etag.as_ref() != Some(expected) {
+                return Err(error(ResumeErrorKind::ETagMismatch {
+                    expected: expected.clone(),
+                    actual: response.etag,
+                }));
+            }
+        }
+        Ok(response.body.into_inner())
+    }
+}
+
+/// Returns the offset of the first byte of a `Content-Range` header, such as `bytes 6-19/20`.
+fn content_range_start(content_range: &str) -> Option<u64> {
+    let range = content_range.strip_prefix("bytes ")?;
+    let (start, _) = range.split_once('-')?;
+    start.trim().parse().ok()
+}
+
+/// Returns true if reading a body failed because of a transient I/O error, such as the
+/// connection being reset or timing out, which resuming the download can recover from.
+fn is_transient(err: &(dyn Error + 'static)) -> bool {
+    #[cfg(any(feature = "connector-hyper-0-14-x", feature = "connector-hyper-1-x"))]
+    if crate::client::http::hyper_common::is_interrupted_message_error(err) {
+        return true;
+    }
+    let mut next = Some(err);
+    while let Some(err) = next {
+        if err.is::<IdleTimeoutError>() || err.is::<minimum_throughput::Error>() {
+            return true;
+        }
+        if let Some(err) = err.downcast_ref::<std::io::Error>() {
+            return matches!(
+                err.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::NotConnected
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::UnexpectedEof
+                    | std::io::ErrorKind::Interrupted
+            );
+        }
+        next = err.source();
+    }
+    false
+}
+
+impl http_body_0_4::Body for ResumableBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        loop {
+            let this = &mut *self;
+            match &mut this.state {
+                State::Fetching(fut) => {
+                    let fut = fut.get_mut().expect("never locked, so never poisoned");
+                    match fut.as_mut().poll(cx) {
+                        Poll::Ready(Ok(response)) => match this.accept(response) {
+                            Ok(body) => this.state = State::Streaming(body),
+                            Err(err) => {
+                                this.state = State::Done;
+                                return Poll::Ready(Some(Err(err.into())));
+                            }
+                        },
+                        Poll::Ready(Err(err)) => {
+                            this.state = State::Done;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+                State::Streaming(body) => match Pin::new(body).poll_data(cx) {
+                    Poll::Ready(Some(Ok(data))) => {
+                        this.offset += data.len() as u64;
+                        return Poll::Ready(Some(Ok(data)));
+                    }
+                    Poll::Ready(Some(Err(err)))
+                        if this.resumes < this.max_resumes && is_transient(&*err) =>
+                    {
+                        this.resumes += 1;
+                        tracing::debug!(
+                            offset = this.offset,
+                            resumes = this.resumes,
+                            error = %err,
+                            "failed to read body; resuming download"
+                        );
+                        let request = RangeRequest {
+                            offset: this.offset,
+                            if_match: this.etag.clone(),
+                        };
+                        this.state = State::Fetching(Mutex::new((this.fetch)(request)));
+                    }
+                    Poll::Ready(Some(Err(err))) => {
+                        this.state = State::Done;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                    Poll::Ready(None) => {
+                        this.state = State::Done;
+                        return Poll::Ready(None);
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const DATA: &[u8] = b"0123456789abcdefghij";
+    const ETAG: &str = "\"v1\"";
+
+    fn connection_reset() -> BoxError {
+        std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset").into()
+    }
+
+    // Yields the given data in chunks of two bytes, failing after `fail_after` chunks.
+    struct FlakyBody {
+        chunks: VecDeque<Bytes>,
+        fail_after: Option<usize>,
+        error: fn() -> BoxError,
+    }
+
+    impl http_body_0_4::Body for FlakyBody {
+        type Data = Bytes;
+        type Error = BoxError;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            match self.fail_after {
+                Some(0) => Poll::Ready(Some(Err((self.error)()))),
+                _ => {
+                    if let Some(remaining) = self.fail_after.as_mut() {
+                        *remaining -= 1;
+                    }
+                    Poll::Ready(self.chunks.pop_front().map(Ok))
+                }
+            }
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn flaky_stream(offset: u64, fail_after: Option<usize>, error: fn() -> BoxError) -> ByteStream {
+        let chunks = DATA[offset as usize..]
+            .chunks(2)
+            .map(Bytes::copy_from_slice)
+            .collect();
+        ByteStream::new(SdkBody::from_body_0_4(FlakyBody {
+            chunks,
+            fail_after,
+            error,
+        }))
+    }
+
+    // A `206 Partial Content` response for the data starting at `offset`
+    fn partial(offset: u64, fail_after: Option<usize>) -> RangeResponse {
+        RangeResponse::new(206, flaky_stream(offset, fail_after, connection_reset))
+            .content_range(format!("bytes {offset}-{}/{}", DATA.len() - 1, DATA.len()))
+            .etag(ETAG)
+    }
+
+    async fn collect_error(body: ResumableBody) -> String {
+        let err = body.into_byte_stream().collect().await.unwrap_err();
+        format!("{}", DisplayErrorContext(&err))
+    }
+
+    #[tokio::test]
+    async fn resumes_from_last_offset() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let body = ResumableBody::new({
+            let requests = requests.clone();
+            move |request: RangeRequest| {
+                let mut requests = requests.lock().unwrap();
+                requests.push(request.clone());
+                // The first two requests fail after three chunks
+                let fail_after = if requests.len() <= 2 { Some(3) } else { None };
+                async move { Ok::<_, BoxError>(partial(request.offset(), fail_after)) }
+            }
+        });
+
+        let data = body.into_byte_stream().collect().await.unwrap();
+        assert_eq!(DATA, &data.into_bytes()[..]);
+        let requests = requests.lock().unwrap();
+        let ranges: Vec<_> = requests.iter().map(RangeRequest::range).collect();
+        assert_eq!(vec!["bytes=0-", "bytes=6-", "bytes=12-"], ranges);
+        let if_match: Vec<_> = requests.iter().map(RangeRequest::if_match).collect();
+        assert_eq!(vec![None, Some(ETAG), Some(ETAG)], if_match);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_resumes() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let body = ResumableBody::new({
+            let calls = calls.clone();
+            move |request: RangeRequest| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async move { Ok::<_, BoxError>(partial(request.offset(), Some(1))) }
+            }
+        })
+        .with_max_resumes(2);
+
+        assert!(collect_error(body).await.contains("connection reset"));
+        assert_eq!(3, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn fetch_errors_are_not_resumed() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let body = ResumableBody::new({
+            let calls = calls.clone();
+            move |_request| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async move { Err::<RangeResponse, _>("access denied") }
+            }
+        });
+
+        assert!(body.into_byte_stream().collect().await.is_err());
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn non_transient_errors_are_not_resumed() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let body = ResumableBody::new({
+            let calls = calls.clone();
+            move |_request| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let body = flaky_stream(0, Some(1), || "checksum mismatch".into());
+                async move { Ok::<_, BoxError>(RangeResponse::new(200, body)) }
+            }
+        });
+
+        assert!(collect_error(body).await.contains("checksum mismatch"));
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+    }
+
+    // Returns a body whose initial response fails after one chunk, and whose resumed response
+    // is the given one.
+    fn resumed_with(resumed: fn(u64) -> RangeResponse) -> ResumableBody {
+        ResumableBody::new(move |request: RangeRequest| {
+            let response = match request.offset() {
+                0 => partial(0, Some(1)),
+                offset => resumed(offset),
+            };
+            async move { Ok::<_, BoxError>(response) }
+        })
+    }
+
+    #[tokio::test]
+    async fn resumed_responses_must_be_partial_content() {
+        let body = resumed_with(|_| {
+            RangeResponse::new(200, flaky_stream(0, None, connection_reset)).etag(ETAG)
+        });
+        let err = collect_error(body).await;
+        assert!(err.contains("expected status 206, but got 200"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn resumed_responses_must_start_at_the_offset() {
+        let body = resumed_with(|offset| partial(offset - 1, None));
+        let err = collect_error(body).await;
+        assert!(
+            err.contains("unexpected content range (bytes 1-19/20)"),
+            "{err}"
+        );
+
+        let body = resumed_with(|offset| {
+            let mut response = partial(offset, None);
+            response.set_content_range(None);
+            response
+        });
+        let err = collect_error(body).await;
+        assert!(err.contains("doesn't have a content range"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn resumed_responses_must_be_for_the_same_object() {
+        let body = resumed_with(|offset| partial(offset, None).etag("\"v2\""));
+        let err = collect_error(body).await;
+        assert!(err.contains("the object changed"), "{err}");
+    }
+
+    #[test]
+    fn range_header() {
+        assert_eq!("bytes=0-", range_from(0));
+        assert_eq!("bytes=42-", range_from(42));
+    }
+
+    #[test]
+    fn content_range_header() {
+        assert_eq!(Some(6), content_range_start("bytes 6-19/20"));
+        assert_eq!(Some(6), content_range_start("bytes 6-19/*"));
+        assert_eq!(None, content_range_start("bytes */20"));
+        assert_eq!(None, content_range_start("6-19/20"));
+    }
+}
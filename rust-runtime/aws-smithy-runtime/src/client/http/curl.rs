@@ -0,0 +1,362 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! HTTP client that uses the system's libcurl, through [isahc](https://docs.rs/isahc).
+//!
+//! This client is an alternative to the hyper clients for environments that need the system's
+//! HTTP stack, like hosts where TLS is intercepted with certificates that are only configured
+//! for libcurl, proxies that only libcurl supports, or HTTP/3 with a libcurl that was built
+//! with HTTP/3 support. libcurl also reads its usual proxy environment variables, like
+//! `HTTPS_PROXY` and `NO_PROXY`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use aws_smithy_runtime::client::http::curl::CurlClientBuilder;
+//!
+//! let http_client = CurlClientBuilder::new().build();
+//! // Pass `http_client` to the `http_client` method of a service config builder
+//! ```
+//!
+//! Further configure libcurl with the isahc builder:
+//!
+//! ```no_run
+//! use aws_smithy_runtime::client::http::curl::CurlClientBuilder;
+//! use isahc::config::{Configurable, VersionNegotiation};
+//!
+//! let http_client = CurlClientBuilder::new()
+//!     .configure(|builder| builder.version_negotiation(VersionNegotiation::http3()))
+//!     .build();
+//! ```
+
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
+    SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use bytes::{Buf, Bytes};
+use isahc::config::Configurable;
+use isahc::error::ErrorKind;
+use isahc::AsyncBody;
+use pin_project_lite::pin_project;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+/// The size of the buffer that response bodies are read into.
+const RESPONSE_BUFFER_SIZE: usize = 8 * 1024;
+
+type ConfigureFn = Arc<dyn Fn(isahc::HttpClientBuilder) -> isahc::HttpClientBuilder + Send + Sync>;
+
+/// Builder for a [`HttpClient`] that uses the system's libcurl.
+///
+/// Connect timeouts are applied as libcurl's connect timeout. Read timeouts are applied as
+/// libcurl's low speed timeout, so a request times out when no data was received for the
+/// duration of the read timeout.
+///
+/// See the [module documentation](crate::client::http::curl) for examples.
+#[derive(Clone, Default)]
+pub struct CurlClientBuilder {
+    configure: Option<ConfigureFn>,
+}
+
+impl fmt::Debug for CurlClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CurlClientBuilder")
+            .field(
+                "configure",
+                &self.configure.as_ref().map(|_| "** configure fn **"),
+            )
+            .finish()
+    }
+}
+
+impl CurlClientBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Customizes the isahc client builder that clients are built with.
+    ///
+    /// The function is called every time that the client creates a connector, after the
+    /// connector's timeouts have been applied. Automatic response decompression is disabled
+    /// so that response checksums and content lengths can be validated, and it shouldn't be
+    /// enabled again.
+    pub fn configure(
+        mut self,
+        configure: impl Fn(isahc::HttpClientBuilder) -> isahc::HttpClientBuilder + Send + Sync + 'static,
+    ) -> Self {
+        self.configure = Some(Arc::new(configure));
+        self
+    }
+
+    /// Builds the HTTP client.
+    pub fn build(self) -> SharedHttpClient {
+        CurlClient {
+            connector_cache: RwLock::new(HashMap::new()),
+            configure: self.configure,
+        }
+        .into_shared()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct CacheKey {
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+}
+
+impl From<&HttpConnectorSettings> for CacheKey {
+    fn from(value: &HttpConnectorSettings) -> Self {
+        Self {
+            connect_timeout: value.connect_timeout(),
+            read_timeout: value.read_timeout(),
+        }
+    }
+}
+
+struct CurlClient {
+    connector_cache: RwLock<HashMap<CacheKey, SharedHttpConnector>>,
+    configure: Option<ConfigureFn>,
+}
+
+impl fmt::Debug for CurlClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CurlClient")
+            .field("connector_cache", &self.connector_cache)
+            .finish()
+    }
+}
+
+impl CurlClient {
+    fn connector(&self, key: &CacheKey) -> SharedHttpConnector {
+        let mut builder = isahc::HttpClient::builder().automatic_decompression(false);
+        if let Some(connect_timeout) = key.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(read_timeout) = key.read_timeout {
+            builder = builder.low_speed_timeout(1, read_timeout);
+        }
+        if let Some(configure) = &self.configure {
+            builder = configure(builder);
+        }
+        match builder.build() {
+            Ok(client) => CurlConnector { client }.into_shared(),
+            Err(err) => FailedConnector {
+                message: err.to_string(),
+            }
+            .into_shared(),
+        }
+    }
+}
+
+impl HttpClient for CurlClient {
+    fn http_connector(
+        &self,
+        settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        let key = CacheKey::from(settings);
+        let mut connector = self.connector_cache.read().unwrap().get(&key).cloned();
+        if connector.is_none() {
+            let mut cache = self.connector_cache.write().unwrap();
+            // Short-circuit if another thread already wrote a connector to the cache for this key
+            if !cache.contains_key(&key) {
+                let connector = self.connector(&key);
+                cache.insert(key.clone(), connector);
+            }
+            connector = cache.get(&key).cloned();
+        }
+
+        connector.expect("cache populated above")
+    }
+
+    fn release_idle_connections(&self) {
+        // Each connector owns its own libcurl connection cache, which is closed when it's dropped
+        self.connector_cache.write().unwrap().clear();
+    }
+}
+
+/// Connector that sends requests with libcurl.
+#[derive(Debug)]
+struct CurlConnector {
+    client: isahc::HttpClient,
+}
+
+impl HttpConnector for CurlConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let request = match request.try_into_http02x() {
+            Ok(request) => request.map(request_body),
+            Err(err) => {
+                return HttpConnectorFuture::ready(Err(ConnectorError::other(err.into(), None)));
+            }
+        };
+        let client = self.client.clone();
+        HttpConnectorFuture::new(async move {
+            let response = client
+                .send_async(request)
+                .await
+                .map_err(to_connector_error)?;
+            HttpResponse::try_from(
+                response.map(|body| SdkBody::from_body_0_4(ResponseBody::new(body))),
+            )
+            .map_err(|err| ConnectorError::other(err.into(), None))
+        })
+    }
+}
+
+/// Connector that fails every request, returned when libcurl can't be initialized.
+#[derive(Debug)]
+struct FailedConnector {
+    message: String,
+}
+
+impl HttpConnector for FailedConnector {
+    fn call(&self, _request: HttpRequest) -> HttpConnectorFuture {
+        HttpConnectorFuture::ready(Err(ConnectorError::other(
+            format!("failed to initialize libcurl: {}", self.message).into(),
+            None,
+        )))
+    }
+}
+
+fn request_body(body: SdkBody) -> AsyncBody {
+    if let Some(bytes) = body.bytes() {
+        return AsyncBody::from(bytes.to_vec());
+    }
+    match body.content_length() {
+        Some(length) => AsyncBody::from_reader_sized(RequestBody::new(body), length),
+        None => AsyncBody::from_reader(RequestBody::new(body)),
+    }
+}
+
+fn to_connector_error(err: isahc::Error) -> ConnectorError {
+    match err.kind() {
+        ErrorKind::Timeout => ConnectorError::timeout(err.into()),
+        ErrorKind::NameResolution => ConnectorError::dns(err.into()),
+        ErrorKind::ConnectionFailed | ErrorKind::Io => ConnectorError::io(err.into()),
+        ErrorKind::InvalidRequest | ErrorKind::RequestBodyNotRewindable => {
+            ConnectorError::user(err.into())
+        }
+        _ => ConnectorError::other(err.into(), None),
+    }
+}
+
+pin_project! {
+    /// Reads a streaming request body for libcurl.
+    struct RequestBody {
+        #[pin]
+        body: SdkBody,
+        chunk: Bytes,
+    }
+}
+
+impl RequestBody {
+    fn new(body: SdkBody) -> Self {
+        Self {
+            body,
+            chunk: Bytes::new(),
+        }
+    }
+}
+
+impl futures_io::AsyncRead for RequestBody {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        use http_body_0_4::Body;
+
+        let mut this = self.project();
+        loop {
+            if !this.chunk.is_empty() {
+                let len = buf.len().min(this.chunk.len());
+                buf[..len].copy_from_slice(&this.chunk[..len]);
+                this.chunk.advance(len);
+                return Poll::Ready(Ok(len));
+            }
+            match ready!(this.body.as_mut().poll_data(cx)) {
+                Some(Ok(data)) => *this.chunk = data,
+                Some(Err(err)) => {
+                    return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, err)))
+                }
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Streams a libcurl response body.
+    struct ResponseBody {
+        #[pin]
+        body: AsyncBody,
+        buffer: Box<[u8]>,
+    }
+}
+
+impl ResponseBody {
+    fn new(body: AsyncBody) -> Self {
+        Self {
+            body,
+            buffer: vec![0; RESPONSE_BUFFER_SIZE].into_boxed_slice(),
+        }
+    }
+}
+
+impl http_body_0_4::Body for ResponseBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        match ready!(futures_io::AsyncRead::poll_read(this.body, cx, this.buffer)) {
+            Ok(0) => Poll::Ready(None),
+            Ok(len) => Poll::Ready(Some(Ok(Bytes::copy_from_slice(&this.buffer[..len])))),
+            Err(err) => Poll::Ready(Some(Err(err))),
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        match self.body.len() {
+            Some(len) => http_body_0_4::SizeHint::with_exact(len),
+            None => http_body_0_4::SizeHint::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classifies_curl_errors() {
+        assert!(to_connector_error(ErrorKind::Timeout.into()).is_timeout());
+        assert!(to_connector_error(ErrorKind::NameResolution.into()).is_dns());
+        assert!(to_connector_error(ErrorKind::ConnectionFailed.into()).is_io());
+        assert!(to_connector_error(ErrorKind::InvalidRequest.into()).is_user());
+        assert!(to_connector_error(ErrorKind::BadServerCertificate.into()).is_other());
+    }
+}
@@ -0,0 +1,529 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::client::http::hyper_1::{
+    self, HttpTimeoutError, CONNECT_TIMEOUT_KIND, READ_TIMEOUT_KIND,
+};
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::rt::spawn::{SharedSpawn, Spawn};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
+    SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use bytes::{Buf, Bytes};
+use http_1x::Uri;
+use hyper_rustls_0_27 as hyper_rustls;
+use rustls_0_23 as rustls;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+type SendRequest = h3::client::SendRequest<h3_quinn::OpenStreams, Bytes>;
+type RequestStream = h3::client::RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>;
+
+// Creating a `with_native_roots` TLS config is expensive. Cache this so that we
+// don't need to repeatedly incur that cost.
+static QUIC_CLIENT_CONFIG: once_cell::sync::Lazy<quinn::ClientConfig> = once_cell::sync::Lazy::new(
+    || {
+        use hyper_rustls::ConfigBuilderExt;
+        // QUIC requires TLS 1.3
+        let mut tls_config = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .expect("Error with the TLS configuration. Please file a bug report under https://github.com/smithy-lang/smithy-rs/issues.")
+        .with_native_roots()
+        .expect("failed to load the platform's native root certificates")
+        .with_no_client_auth();
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+        let crypto = quinn::crypto::rustls::QuicClientConfig::try_from(tls_config)
+            .expect("the TLS configuration supports QUIC");
+        quinn::ClientConfig::new(Arc::new(crypto))
+    },
+);
+
+/// Builder for an experimental [`HttpClient`] implementation that uses HTTP/3.
+///
+/// Requests to `https` URLs are sent over HTTP/3 (QUIC) with quinn and h3. When a HTTP/3
+/// connection can't be established with a host, that request and every later request to the
+/// same host are sent with the fallback client instead, which negotiates HTTP/2 or HTTP/1.1.
+/// Requests to `http` URLs always use the fallback client. The fallback client defaults to the
+/// hyper 1.x client.
+///
+/// HTTP/3 is attempted without waiting for the service to advertise it with `Alt-Svc`, so this
+/// client should only be used with services that are known to expose HTTP/3 endpoints.
+///
+/// This client is experimental, and may change or be removed in a future release.
+///
+/// # Examples
+///
+/// ```no_run,ignore
+/// use aws_smithy_runtime::client::http::http3::Http3ClientBuilder;
+///
+/// let http_client = Http3ClientBuilder::new().build();
+///
+/// // This client can then be given to a generated service Config
+/// let config = my_service_client::Config::builder()
+///     .http_client(http_client)
+///     .build();
+/// let client = my_service_client::Client::from_conf(config);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Http3ClientBuilder {
+    fallback: Option<SharedHttpClient>,
+}
+
+impl Http3ClientBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the client used for requests that can't be sent over HTTP/3.
+    pub fn fallback_client(mut self, fallback: impl HttpClient + 'static) -> Self {
+        self.fallback = Some(fallback.into_shared());
+        self
+    }
+
+    /// Sets the client used for requests that can't be sent over HTTP/3.
+    pub fn set_fallback_client(&mut self, fallback: Option<SharedHttpClient>) -> &mut Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Create a [`SharedHttpClient`] that sends `https` requests over HTTP/3.
+    pub fn build(self) -> SharedHttpClient {
+        SharedHttpClient::new(Http3Client {
+            state: Arc::new(State::default()),
+            fallback: self
+                .fallback
+                .unwrap_or_else(|| hyper_1::HyperClientBuilder::new().build_https()),
+        })
+    }
+}
+
+/// Connection state that's shared by every connector created by a [`Http3Client`]
+#[derive(Default)]
+struct State {
+    /// QUIC endpoints, keyed by whether they're bound to an IPv6 address
+    endpoints: Mutex<HashMap<bool, quinn::Endpoint>>,
+    /// HTTP/3 connections, keyed by authority. Concurrent requests to an authority share the
+    /// connection that's being established to it.
+    connections: Mutex<HashMap<String, Arc<OnceCell<SendRequest>>>>,
+    /// Authorities that a HTTP/3 connection couldn't be established with
+    fallback_authorities: Mutex<HashSet<String>>,
+}
+
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field(
+                "fallback_authorities",
+                &self.fallback_authorities.lock().unwrap(),
+            )
+            .finish()
+    }
+}
+
+impl State {
+    fn endpoint(&self, ipv6: bool) -> Result<quinn::Endpoint, BoxError> {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        if let Some(endpoint) = endpoints.get(&ipv6) {
+            return Ok(endpoint.clone());
+        }
+        let bind_address: SocketAddr = if ipv6 {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        };
+        let mut endpoint = quinn::Endpoint::client(bind_address)?;
+        endpoint.set_default_client_config(QUIC_CLIENT_CONFIG.clone());
+        endpoints.insert(ipv6, endpoint.clone());
+        Ok(endpoint)
+    }
+}
+
+#[derive(Debug)]
+struct Http3Client {
+    state: Arc<State>,
+    fallback: SharedHttpClient,
+}
+
+impl HttpClient for Http3Client {
+    fn http_connector(
+        &self,
+        settings: &HttpConnectorSettings,
+        components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        SharedHttpConnector::new(Http3Connector {
+            state: self.state.clone(),
+            fallback: self.fallback.http_connector(settings, components),
+            sleep_impl: components.sleep_impl().or_else(default_async_sleep),
+            spawn: components.spawn(),
+            connect_timeout: settings.connect_timeout(),
+            read_timeout: settings.read_timeout(),
+        })
+    }
+
+    fn release_idle_connections(&self) {
+        // Closing the connections is left to quinn once the last request using them completes.
+        // Hosts that previously fell back get another chance to use HTTP/3.
+        self.state.connections.lock().unwrap().clear();
+        self.state.fallback_authorities.lock().unwrap().clear();
+        self.fallback.release_idle_connections();
+    }
+}
+
+/// [`HttpConnector`] that sends requests over HTTP/3, and falls back to another connector when
+/// a HTTP/3 connection can't be established.
+///
+/// The connect timeout covers the QUIC handshake, which includes TLS negotiation. The read
+/// timeout covers sending the request and receiving the response headers.
+#[derive(Clone, Debug)]
+struct Http3Connector {
+    state: Arc<State>,
+    fallback: SharedHttpConnector,
+    sleep_impl: Option<SharedAsyncSleep>,
+    spawn: Option<SharedSpawn>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+}
+
+impl HttpConnector for Http3Connector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let uri = match request.uri().parse::<Uri>() {
+            Ok(uri) => uri,
+            Err(err) => {
+                return HttpConnectorFuture::ready(Err(ConnectorError::user(err.into())));
+            }
+        };
+        let (Some("https"), Some(host)) = (uri.scheme_str(), uri.host()) else {
+            return self.fallback.call(request);
+        };
+        let port = uri.port_u16().unwrap_or(443);
+        let authority = format!("{host}:{port}");
+        if self
+            .state
+            .fallback_authorities
+            .lock()
+            .unwrap()
+            .contains(&authority)
+        {
+            return self.fallback.call(request);
+        }
+
+        let connector = self.clone();
+        let host = host.to_string();
+        HttpConnectorFuture::new(async move {
+            let send_request = match connector.connection(&authority, &host, port).await {
+                Ok(send_request) => send_request,
+                Err(err) => {
+                    tracing::debug!(
+                        authority = %authority,
+                        err = %DisplayErrorContext(&*err),
+                        "failed to establish a HTTP/3 connection, so falling back to the fallback client"
+                    );
+                    return connector.fallback.call(request).await;
+                }
+            };
+            let result = connector.send(send_request, request).await;
+            if result.is_err() {
+                // The connection may have been closed, so open a new one for the next request
+                connector
+                    .state
+                    .connections
+                    .lock()
+                    .unwrap()
+                    .remove(&authority);
+            }
+            result
+        })
+    }
+}
+
+impl Http3Connector {
+    /// Returns an open HTTP/3 connection to the given host, connecting to it if necessary.
+    ///
+    /// Requests that are made while a connection is being established wait for that connection,
+    /// rather than establishing their own. When the connection can't be established, the host is
+    /// added to the hosts that use the fallback client.
+    async fn connection(
+        &self,
+        authority: &str,
+        host: &str,
+        port: u16,
+    ) -> Result<SendRequest, BoxError> {
+        let connection = self
+            .state
+            .connections
+            .lock()
+            .unwrap()
+            .entry(authority.to_string())
+            .or_default()
+            .clone();
+        let send_request = connection
+            .get_or_try_init(|| async {
+                // Requests that waited for a failed connection attempt don't try again
+                if self
+                    .state
+                    .fallback_authorities
+                    .lock()
+                    .unwrap()
+                    .contains(authority)
+                {
+                    return Err("a HTTP/3 connection couldn't be established with this host".into());
+                }
+                let connected = self.connect(host, port).await;
+                if connected.is_err() {
+                    self.state
+                        .fallback_authorities
+                        .lock()
+                        .unwrap()
+                        .insert(authority.to_string());
+                }
+                connected
+            })
+            .await?;
+        Ok(send_request.clone())
+    }
+
+    /// Establishes a new HTTP/3 connection to the given host, within the connect timeout.
+    async fn connect(&self, host: &str, port: u16) -> Result<SendRequest, BoxError> {
+        let connecting = connect(&self.state, self.spawn.as_ref(), host, port);
+        let send_request = match self.connect_timeout {
+            Some(duration) => {
+                let sleep_impl = self
+                    .sleep_impl
+                    .as_ref()
+                    .expect("a sleep impl must be provided in order to have a connect timeout");
+                Timeout::new(connecting, sleep_impl.sleep(duration))
+                    .await
+                    .map_err(|_| HttpTimeoutError {
+                        kind: CONNECT_TIMEOUT_KIND,
+                        duration,
+                    })??
+            }
+            None => connecting.await?,
+        };
+        Ok(send_request)
+    }
+
+    async fn send(
+        &self,
+        mut send_request: SendRequest,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, ConnectorError> {
+        let request = request
+            .try_into_http02x()
+            .map_err(|err| ConnectorError::other(err.into(), None))?;
+        let (parts, mut body) = hyper_1::to_http1x_request(request)
+            .map_err(|err| ConnectorError::other(err, None))?
+            .into_parts();
+        let request = http_1x::Request::from_parts(parts, ());
+        let exchange = async move {
+            let mut stream = send_request.send_request(request).await?;
+            while let Some(frame) =
+                poll_fn(|cx| http_body_1x::Body::poll_frame(Pin::new(&mut body), cx)).await
+            {
+                match frame?.into_data() {
+                    Ok(data) => stream.send_data(data).await?,
+                    Err(frame) => {
+                        if let Ok(trailers) = frame.into_trailers() {
+                            stream.send_trailers(trailers).await?;
+                        }
+                    }
+                }
+            }
+            stream.finish().await?;
+            let response = stream.recv_response().await?;
+            Ok::<_, BoxError>((response, stream))
+        };
+        let (response, stream) = match self.read_timeout {
+            Some(duration) => {
+                let sleep_impl = self
+                    .sleep_impl
+                    .as_ref()
+                    .expect("a sleep impl must be provided in order to have a read timeout");
+                match Timeout::new(exchange, sleep_impl.sleep(duration)).await {
+                    Ok(result) => result,
+                    Err(_timeout) => {
+                        return Err(ConnectorError::timeout(
+                            HttpTimeoutError {
+                                kind: READ_TIMEOUT_KIND,
+                                duration,
+                            }
+                            .into(),
+                        ))
+                    }
+                }
+            }
+            None => exchange.await,
+        }
+        .map_err(ConnectorError::io)?;
+        let (parts, ()) = response.into_parts();
+        let response = http_1x::Response::from_parts(
+            parts,
+            ResponseBody {
+                stream,
+                done: false,
+            },
+        );
+        hyper_1::to_smithy_response(response).map_err(|err| ConnectorError::other(err, None))
+    }
+}
+
+/// Establishes a QUIC connection to the given host, and starts a HTTP/3 session on it.
+///
+/// The task that drives the connection is spawned with `spawn`, or on the current Tokio runtime
+/// when there's no spawn implementation.
+fn connect<'a>(
+    state: &'a State,
+    spawn: Option<&'a SharedSpawn>,
+    host: &'a str,
+    port: u16,
+) -> impl Future<Output = Result<SendRequest, BoxError>> + Send + 'a {
+    async move {
+        let address = tokio::net::lookup_host((host, port))
+            .await?
+            .next()
+            .ok_or_else(|| format!("no addresses were found for `{host}`"))?;
+        let endpoint = state.endpoint(address.is_ipv6())?;
+        let connection = endpoint.connect(address, host)?.await?;
+        let (mut driver, send_request) =
+            h3::client::new(h3_quinn::Connection::new(connection)).await?;
+        // The driver must be polled for the connection to make progress
+        let drive = async move {
+            let _closed = poll_fn(|cx| driver.poll_close(cx)).await;
+            tracing::trace!("HTTP/3 connection closed");
+        };
+        match spawn {
+            Some(spawn) => spawn.spawn(Box::pin(drive)),
+            None => {
+                tokio::spawn(drive);
+            }
+        }
+        Ok(send_request)
+    }
+}
+
+/// Adapts a HTTP/3 response stream into a http-body 1.x body.
+///
+/// Response trailers aren't supported, and are ignored.
+struct ResponseBody {
+    stream: RequestStream,
+    done: bool,
+}
+
+impl http_body_1x::Body for ResponseBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body_1x::Frame<Self::Data>, Self::Error>>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        match ready!(self.stream.poll_recv_data(cx)) {
+            Ok(Some(mut data)) => {
+                let data = data.copy_to_bytes(data.remaining());
+                Poll::Ready(Some(Ok(http_body_1x::Frame::data(data))))
+            }
+            Ok(None) => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+            Err(err) => Poll::Ready(Some(Err(err.into()))),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod test {
+    use super::*;
+    use crate::client::http::test_util::infallible_client_fn;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_async::test_util::instant_time_and_sleep;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use std::time::UNIX_EPOCH;
+
+    fn client() -> SharedHttpClient {
+        Http3ClientBuilder::new()
+            .fallback_client(infallible_client_fn(|_req| {
+                http::Response::builder()
+                    .status(200)
+                    .header("x-fallback", "true")
+                    .body(SdkBody::empty())
+                    .unwrap()
+            }))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn http_requests_use_the_fallback_client() {
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(SharedAsyncSleep::new(TokioSleep::new())))
+            .build()
+            .unwrap();
+        let connector = client().http_connector(&HttpConnectorSettings::default(), &components);
+        let response = connector
+            .call(HttpRequest::get("http://localhost/").unwrap())
+            .await
+            .expect("success");
+        assert_eq!(Some("true"), response.headers().get("x-fallback"));
+    }
+
+    #[tokio::test]
+    async fn falls_back_when_http3_is_unavailable() {
+        // Nothing responds to QUIC on this port, so the handshake times out
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = socket.local_addr().unwrap();
+        let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time_source))
+            .with_sleep_impl(Some(sleep_impl.clone()))
+            .build()
+            .unwrap();
+        let http_client = client();
+        let connector = http_client.http_connector(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(1))
+                .build(),
+            &components,
+        );
+        let request = || HttpRequest::get(format!("https://{addr}/")).unwrap();
+        // Concurrent requests share the same connection attempt
+        let (first, second) = tokio::join!(connector.call(request()), connector.call(request()));
+        for response in [first, second] {
+            let response = response.expect("success");
+            assert_eq!(Some("true"), response.headers().get("x-fallback"));
+        }
+        assert_eq!(vec![Duration::from_secs(1)], sleep_impl.logs());
+
+        // Later requests to the same host go straight to the fallback client
+        let response = connector.call(request()).await.expect("success");
+        assert_eq!(Some("true"), response.headers().get("x-fallback"));
+        assert_eq!(vec![Duration::from_secs(1)], sleep_impl.logs());
+    }
+}
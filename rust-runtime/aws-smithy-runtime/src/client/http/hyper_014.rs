@@ -4,13 +4,22 @@
  */
 
 use crate::client::http::connection_poisoning::CaptureSmithyConnection;
+use crate::client::http::hyper_common::{
+    extract_smithy_connection, find_source, is_reused, is_stale_connection_error,
+    poison_if_expired, to_connector_error, try_clone_request, CapturedConnection,
+    ConnectionDetails, SpawnExecutor,
+};
+use crate::client::http::proxy::ProxyConfig;
+use crate::client::http::request_limit::RequestLimit;
+use crate::client::http::tls::{SessionResumption, TrustStore};
 use aws_smithy_async::future::timeout::TimedOutError;
 use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::rt::spawn::{SharedSpawn, Spawn};
 use aws_smithy_runtime_api::box_error::BoxError;
-use aws_smithy_runtime_api::client::connection::ConnectionMetadata;
+use aws_smithy_runtime_api::client::dns::{ResolveDns, SharedDnsResolver};
 use aws_smithy_runtime_api::client::http::{
-    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
-    SharedHttpConnector,
+    HappyEyeballs, Http2Settings, HttpClient, HttpConnector, HttpConnectorFuture,
+    HttpConnectorSettings, PoolSettings, SharedHttpClient, SharedHttpConnector,
 };
 use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
 use aws_smithy_runtime_api::client::result::ConnectorError;
@@ -18,32 +27,35 @@ use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::body::SdkBody;
 use aws_smithy_types::error::display::DisplayErrorContext;
-use aws_smithy_types::retry::ErrorKind;
 use http::{Extensions, Uri};
 use hyper_0_14::client::connect::{capture_connection, CaptureConnection, Connection, HttpInfo};
 use hyper_0_14::service::Service;
 use std::collections::HashMap;
-use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
+use std::net::IpAddr;
 use std::sync::RwLock;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncRead, AsyncWrite};
 
 #[cfg(feature = "tls-rustls")]
 mod default_connector {
+    use crate::client::http::tls::{SessionResumption, TrustStore};
     use aws_smithy_async::rt::sleep::SharedAsyncSleep;
+    use aws_smithy_runtime_api::client::dns::SharedDnsResolver;
     use aws_smithy_runtime_api::client::http::HttpConnectorSettings;
+    use std::net::IpAddr;
 
-    // Creating a `with_native_roots` HTTP client takes 300ms on OS X. Cache this so that we
+    // Creating a `with_native_roots` TLS config takes 300ms on OS X. Cache this so that we
     // don't need to repeatedly incur that cost.
-    static HTTPS_NATIVE_ROOTS: once_cell::sync::Lazy<
-        hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector>,
-    > = once_cell::sync::Lazy::new(|| {
-        use hyper_rustls::ConfigBuilderExt;
-        hyper_rustls::HttpsConnectorBuilder::new()
-        .with_tls_config(
-            rustls::ClientConfig::builder()
+    static TLS_CONFIG: once_cell::sync::Lazy<rustls::ClientConfig> =
+        once_cell::sync::Lazy::new(|| {
+            use hyper_rustls::ConfigBuilderExt;
+            config_builder().with_native_roots().with_no_client_auth()
+        });
+
+    fn config_builder() -> rustls::ConfigBuilder<rustls::ClientConfig, rustls::WantsVerifier> {
+        rustls::ClientConfig::builder()
                 .with_cipher_suites(&[
                     // TLS1.3 suites
                     rustls::cipher_suite::TLS13_AES_256_GCM_SHA384,
@@ -58,13 +70,48 @@ mod default_connector {
                 .with_safe_default_kx_groups()
                 .with_safe_default_protocol_versions()
                 .expect("Error with the TLS configuration. Please file a bug report under https://github.com/smithy-lang/smithy-rs/issues.")
-                .with_native_roots()
-                .with_no_client_auth()
-        )
-        .https_or_http()
-        .enable_http1()
-        .enable_http2()
-        .build()
+    }
+
+    /// Return the root certificates in `trust_store`.
+    ///
+    /// Certificates that can't be parsed are skipped, so they can only make the trust store
+    /// smaller.
+    fn root_certificates(trust_store: &TrustStore) -> rustls::RootCertStore {
+        let mut roots = rustls::RootCertStore::empty();
+        if trust_store.native_roots() {
+            match rustls_native_certs::load_native_certs() {
+                Ok(certificates) => {
+                    let certificates: Vec<_> = certificates
+                        .into_iter()
+                        .map(|certificate| certificate.0)
+                        .collect();
+                    roots.add_parsable_certificates(&certificates);
+                }
+                Err(err) => {
+                    tracing::warn!(err = %err, "failed to load the platform's native root certificates")
+                }
+            }
+        }
+        let certificates: Vec<_> = trust_store.certificates().collect();
+        let (_, invalid) = roots.add_parsable_certificates(&certificates);
+        if invalid > 0 {
+            tracing::warn!(
+                invalid = invalid,
+                "some of the trust store's certificates are invalid and were skipped"
+            );
+        }
+        roots
+    }
+
+    static HTTPS_NATIVE_ROOTS: once_cell::sync::Lazy<
+        hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector>,
+    > = once_cell::sync::Lazy::new(|| {
+        hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(TLS_CONFIG.clone())
+            .https_or_http()
+            .enable_http1()
+            .enable_http2()
+            .build()
     });
 
     pub(super) fn base(
@@ -85,6 +132,94 @@ mod default_connector {
     pub(super) fn https() -> hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector> {
         HTTPS_NATIVE_ROOTS.clone()
     }
+
+    /// The TCP connector used by the default HTTPS connector.
+    pub(super) type TcpConnector = super::socket_middleware::Keepalive<
+        hyper_0_14::client::HttpConnector<super::dns_middleware::Resolver>,
+    >;
+
+    /// Return an HTTPS connector that negotiates TLS over connections from `tcp`.
+    ///
+    /// When `server_name` is given, it's used for SNI and certificate verification instead of
+    /// the host of the URL.
+    pub(super) fn https_with<T>(
+        tcp: T,
+        tls_config: std::sync::Arc<rustls::ClientConfig>,
+        server_name: Option<String>,
+    ) -> hyper_rustls::HttpsConnector<T> {
+        let Some(server_name) = server_name else {
+            return hyper_rustls::HttpsConnector::from((tcp, tls_config));
+        };
+        // The builder sets the ALPN protocols for the enabled HTTP versions itself
+        let mut tls_config = (*tls_config).clone();
+        tls_config.alpn_protocols.clear();
+        hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(tls_config)
+            .https_or_http()
+            .with_server_name(server_name)
+            .enable_http1()
+            .enable_http2()
+            .wrap_connector(tcp)
+    }
+
+    /// Return the TCP connector used by the default HTTPS connector.
+    ///
+    /// Names are resolved with `dns_resolver` when one is given, and with `getaddrinfo` otherwise.
+    pub(super) fn http(
+        dns_resolver: Option<SharedDnsResolver>,
+        local_address: Option<IpAddr>,
+        settings: Option<&HttpConnectorSettings>,
+    ) -> TcpConnector {
+        let mut http = hyper_0_14::client::HttpConnector::new_with_resolver(
+            super::dns_middleware::Resolver::new(dns_resolver),
+        );
+        http.enforce_http(false);
+        http.set_local_address(local_address);
+        if let Some(happy_eyeballs) = settings.and_then(|s| s.happy_eyeballs()) {
+            http.set_happy_eyeballs_timeout(happy_eyeballs.delay());
+        }
+        let tcp = settings.and_then(|s| s.tcp_settings());
+        if let Some(tcp) = tcp {
+            if let Some(nodelay) = tcp.nodelay() {
+                http.set_nodelay(nodelay);
+            }
+            http.set_send_buffer_size(tcp.send_buffer_size());
+            http.set_recv_buffer_size(tcp.recv_buffer_size());
+        }
+        super::socket_middleware::Keepalive::new(http, tcp)
+    }
+
+    /// Return the TLS configuration used by the default HTTPS connector.
+    ///
+    /// The platform's native root certificates are trusted unless a trust store is given.
+    pub(super) fn tls_config(
+        trust_store: Option<&TrustStore>,
+        session_resumption: Option<SessionResumption>,
+        tls_key_log: bool,
+    ) -> std::sync::Arc<rustls::ClientConfig> {
+        let mut tls_config = match trust_store {
+            Some(trust_store) => config_builder()
+                .with_root_certificates(root_certificates(trust_store))
+                .with_no_client_auth(),
+            None => TLS_CONFIG.clone(),
+        };
+        // Without a setting, the session cache of the default TLS config is shared
+        if let Some(session_resumption) = session_resumption {
+            tls_config.resumption = if session_resumption.is_enabled() {
+                rustls::client::Resumption::in_memory_sessions(session_resumption.max_sessions())
+            } else {
+                rustls::client::Resumption::disabled()
+            };
+        }
+        if tls_key_log {
+            tracing::warn!(
+                "TLS key logging is enabled, secrets are written to the file in `SSLKEYLOGFILE`"
+            );
+            tls_config.key_log = std::sync::Arc::new(rustls::KeyLogFile::new());
+        }
+        tls_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        std::sync::Arc::new(tls_config)
+    }
 }
 
 /// Given `HttpConnectorSettings` and an `SharedAsyncSleep`, create a `SharedHttpConnector` from defaults depending on what cargo features are activated.
@@ -172,7 +307,8 @@ pub fn default_client() -> Option<SharedHttpClient> {
 /// ```
 #[derive(Debug)]
 pub struct HyperConnector {
-    adapter: Box<dyn HttpConnector>,
+    adapter: SharedHttpConnector,
+    request_limit: Option<RequestLimit>,
 }
 
 impl HyperConnector {
@@ -184,7 +320,14 @@ impl HyperConnector {
 
 impl HttpConnector for HyperConnector {
     fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
-        self.adapter.call(request)
+        match &self.request_limit {
+            Some(request_limit) => {
+                let uri = request.uri().to_string();
+                let adapter = self.adapter.clone();
+                request_limit.limit(&uri, move || adapter.call(request))
+            }
+            None => self.adapter.call(request),
+        }
     }
 }
 
@@ -193,12 +336,20 @@ impl HttpConnector for HyperConnector {
 pub struct HyperConnectorBuilder {
     connector_settings: Option<HttpConnectorSettings>,
     sleep_impl: Option<SharedAsyncSleep>,
+    spawn: Option<SharedSpawn>,
     client_builder: Option<hyper_0_14::client::Builder>,
+    proxy_config: Option<ProxyConfig>,
+    dns_resolver: Option<SharedDnsResolver>,
+    trust_store: Option<TrustStore>,
+    session_resumption: Option<SessionResumption>,
+    tls_key_log: bool,
+    tls_server_name: Option<String>,
+    local_address: Option<IpAddr>,
 }
 
 impl HyperConnectorBuilder {
     /// Create a [`HyperConnector`] from this builder and a given connector.
-    pub fn build<C>(self, tcp_connector: C) -> HyperConnector
+    pub fn build<C>(mut self, tcp_connector: C) -> HyperConnector
     where
         C: Clone + Send + Sync + 'static,
         C: Service<Uri>,
@@ -206,12 +357,56 @@ impl HyperConnectorBuilder {
         C::Future: Unpin + Send + 'static,
         C::Error: Into<BoxError>,
     {
-        let client_builder = self.client_builder.unwrap_or_default();
-        let sleep_impl = self.sleep_impl.or_else(default_async_sleep);
-        let (connect_timeout, read_timeout) = self
+        // Custom connectors are responsible for their own proxying, name resolution, and TLS
+        self.proxy_config = None;
+        self.dns_resolver = None;
+        self.trust_store = None;
+        self.session_resumption = None;
+        self.tls_key_log = false;
+        self.tls_server_name = None;
+        self.local_address = None;
+        let connect_timeout = self
+            .connector_settings
+            .as_ref()
+            .and_then(|c| c.connect_timeout());
+        self.build_with_connect_timeout(tcp_connector, connect_timeout)
+    }
+
+    fn build_with_connect_timeout<C>(
+        self,
+        tcp_connector: C,
+        connect_timeout: Option<Duration>,
+    ) -> HyperConnector
+    where
+        C: Clone + Send + Sync + 'static,
+        C: Service<Uri>,
+        C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        C::Future: Unpin + Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        let mut client_builder = self.client_builder.unwrap_or_default();
+        if let Some(spawn) = self.spawn {
+            client_builder.executor(SpawnExecutor(spawn));
+        }
+        let http2 = self
+            .connector_settings
+            .as_ref()
+            .and_then(|c| c.http2_settings())
+            .cloned();
+        if let Some(http2) = &http2 {
+            apply_http2_settings(&mut client_builder, http2);
+        }
+        let pool = self
             .connector_settings
-            .map(|c| (c.connect_timeout(), c.read_timeout()))
-            .unwrap_or((None, None));
+            .as_ref()
+            .and_then(|c| c.pool_settings())
+            .cloned()
+            .unwrap_or_default();
+        apply_pool_settings(&mut client_builder, &pool);
+        let tcp_connector =
+            pool_middleware::ConnectionLimit::new(tcp_connector, pool.max_connections_per_host());
+        let sleep_impl = self.sleep_impl.or_else(default_async_sleep);
+        let read_timeout = self.connector_settings.and_then(|c| c.read_timeout());
 
         let connector = match connect_timeout {
             Some(duration) => timeout_middleware::ConnectTimeout::new(
@@ -224,6 +419,8 @@ impl HyperConnectorBuilder {
             None => timeout_middleware::ConnectTimeout::no_timeout(tcp_connector),
         };
         let base = client_builder.build(connector);
+        let request_limit =
+            RequestLimit::from_settings(Some(&pool), http2.as_ref(), sleep_impl.clone());
         let read_timeout = match read_timeout {
             Some(duration) => timeout_middleware::HttpReadTimeout::new(
                 base,
@@ -233,16 +430,100 @@ impl HyperConnectorBuilder {
             None => timeout_middleware::HttpReadTimeout::no_timeout(base),
         };
         HyperConnector {
-            adapter: Box::new(Adapter {
+            adapter: SharedHttpConnector::new(Adapter {
                 client: read_timeout,
+                proxy_config: self.proxy_config,
+                max_connection_lifetime: pool.max_connection_lifetime(),
             }),
+            request_limit,
         }
     }
 
     /// Create a [`HyperConnector`] with the default rustls HTTPS implementation.
+    ///
+    /// When a TLS negotiation timeout is set in the connector settings, the connect timeout only
+    /// covers establishing the TCP connection, and the TLS handshake is timed separately.
+    /// Otherwise, the connect timeout covers both.
+    ///
+    /// When a proxy is configured, connecting includes connecting to the proxy and, for `https`
+    /// URLs, establishing a tunnel through it.
     #[cfg(feature = "tls-rustls")]
     pub fn build_https(self) -> HyperConnector {
-        self.build(default_connector::https())
+        let settings = self.connector_settings.as_ref();
+        let has_tls_negotiation_timeout =
+            settings.and_then(|c| c.tls_negotiation_timeout()).is_some();
+        // The cached HTTPS connector can only be used when the TCP connector has its defaults
+        let is_default_tcp_connector = self.dns_resolver.is_none()
+            && self.local_address.is_none()
+            && settings.and_then(|c| c.happy_eyeballs()).is_none()
+            && settings.and_then(|c| c.tcp_settings()).is_none();
+        let is_default_tls_config = self.trust_store.is_none()
+            && self.session_resumption.is_none()
+            && !self.tls_key_log
+            && self.tls_server_name.is_none();
+        let http = default_connector::http(self.dns_resolver.clone(), self.local_address, settings);
+        match self.proxy_config.clone() {
+            Some(proxy_config) => {
+                self.build_https_with(proxy_middleware::ProxyConnector::new(http, proxy_config))
+            }
+            None if !has_tls_negotiation_timeout
+                && is_default_tcp_connector
+                && is_default_tls_config =>
+            {
+                self.build(tls_middleware::TlsInfo::new(default_connector::https()))
+            }
+            None => self.build_https_with(http),
+        }
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    fn build_https_with<C>(self, tcp_connector: C) -> HyperConnector
+    where
+        C: Clone + Send + Sync + 'static,
+        C: Service<Uri>,
+        C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        C::Future: Unpin + Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        let settings = self.connector_settings.as_ref();
+        let connect_timeout = settings.and_then(|c| c.connect_timeout());
+        let Some(tls_negotiation_timeout) = settings.and_then(|c| c.tls_negotiation_timeout())
+        else {
+            let https = tls_middleware::TlsInfo::new(default_connector::https_with(
+                tcp_connector,
+                default_connector::tls_config(
+                    self.trust_store.as_ref(),
+                    self.session_resumption,
+                    self.tls_key_log,
+                ),
+                self.tls_server_name.clone(),
+            ));
+            return self.build_with_connect_timeout(https, connect_timeout);
+        };
+        let sleep_impl = self
+            .sleep_impl
+            .clone()
+            .or_else(default_async_sleep)
+            .expect("a sleep impl must be provided in order to have a TLS negotiation timeout");
+        let tcp_connector = match connect_timeout {
+            Some(duration) => {
+                timeout_middleware::ConnectTimeout::new(tcp_connector, sleep_impl.clone(), duration)
+            }
+            None => timeout_middleware::ConnectTimeout::no_timeout(tcp_connector),
+        };
+        let connector =
+            tls_middleware::TlsInfo::new(timeout_middleware::TlsNegotiationTimeout::new(
+                tcp_connector,
+                default_connector::tls_config(
+                    self.trust_store.as_ref(),
+                    self.session_resumption,
+                    self.tls_key_log,
+                ),
+                self.tls_server_name.clone(),
+                sleep_impl,
+                tls_negotiation_timeout,
+            ));
+        self.build_with_connect_timeout(connector, None)
     }
 
     /// Set the async sleep implementation used for timeouts
@@ -263,6 +544,24 @@ impl HyperConnectorBuilder {
         self
     }
 
+    /// Set the implementation that the tasks that drive connections are spawned with
+    ///
+    /// This replaces the executor of the [`hyper_builder`](Self::hyper_builder). When neither is
+    /// set, the tasks are spawned onto Tokio.
+    pub fn spawn(mut self, spawn: impl Spawn + 'static) -> Self {
+        self.spawn = Some(spawn.into_shared());
+        self
+    }
+
+    /// Set the implementation that the tasks that drive connections are spawned with
+    ///
+    /// This replaces the executor of the [`hyper_builder`](Self::hyper_builder). When neither is
+    /// set, the tasks are spawned onto Tokio.
+    pub fn set_spawn(&mut self, spawn: Option<SharedSpawn>) -> &mut Self {
+        self.spawn = spawn;
+        self
+    }
+
     /// Configure the HTTP settings for the `HyperAdapter`
     pub fn connector_settings(mut self, connector_settings: HttpConnectorSettings) -> Self {
         self.connector_settings = Some(connector_settings);
@@ -296,6 +595,185 @@ impl HyperConnectorBuilder {
         self.client_builder = hyper_builder;
         self
     }
+
+    /// Configure the proxies that requests are sent through.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own proxying.
+    pub fn proxy_config(mut self, proxy_config: ProxyConfig) -> Self {
+        self.proxy_config = Some(proxy_config);
+        self
+    }
+
+    /// Configure the proxies that requests are sent through.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own proxying.
+    pub fn set_proxy_config(&mut self, proxy_config: Option<ProxyConfig>) -> &mut Self {
+        self.proxy_config = proxy_config;
+        self
+    }
+
+    /// Configure the DNS resolver used to resolve the names of the hosts that are connected to.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own name resolution. When no resolver is
+    /// set, names are resolved with the system's `getaddrinfo` on a blocking thread.
+    pub fn dns_resolver(mut self, dns_resolver: impl ResolveDns + 'static) -> Self {
+        self.dns_resolver = Some(dns_resolver.into_shared());
+        self
+    }
+
+    /// Configure the DNS resolver used to resolve the names of the hosts that are connected to.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own name resolution. When no resolver is
+    /// set, names are resolved with the system's `getaddrinfo` on a blocking thread.
+    pub fn set_dns_resolver(&mut self, dns_resolver: Option<SharedDnsResolver>) -> &mut Self {
+        self.dns_resolver = dns_resolver;
+        self
+    }
+
+    /// Configure the root certificates that server certificates are verified against.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own TLS. When no trust store is set, the
+    /// platform's native root certificates are trusted.
+    pub fn trust_store(mut self, trust_store: TrustStore) -> Self {
+        self.trust_store = Some(trust_store);
+        self
+    }
+
+    /// Configure the root certificates that server certificates are verified against.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own TLS. When no trust store is set, the
+    /// platform's native root certificates are trusted.
+    pub fn set_trust_store(&mut self, trust_store: Option<TrustStore>) -> &mut Self {
+        self.trust_store = trust_store;
+        self
+    }
+
+    /// Configure how TLS sessions are resumed when reconnecting to a server.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own TLS. When this isn't set, up to 256
+    /// sessions are cached in memory, and the cache is shared with other default connectors.
+    pub fn session_resumption(mut self, session_resumption: SessionResumption) -> Self {
+        self.session_resumption = Some(session_resumption);
+        self
+    }
+
+    /// Configure how TLS sessions are resumed when reconnecting to a server.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own TLS. When this isn't set, up to 256
+    /// sessions are cached in memory, and the cache is shared with other default connectors.
+    pub fn set_session_resumption(
+        &mut self,
+        session_resumption: Option<SessionResumption>,
+    ) -> &mut Self {
+        self.session_resumption = session_resumption;
+        self
+    }
+
+    /// Configure whether TLS secrets are logged to the file in the `SSLKEYLOGFILE` environment
+    /// variable, so that captured traffic can be decrypted, e.g. with Wireshark.
+    ///
+    /// **Anyone who can read the file can decrypt the traffic, so this should only be enabled
+    /// when debugging against test endpoints.** Nothing is logged when the environment variable
+    /// isn't set. This only applies to connectors created with `build_https`.
+    #[cfg(feature = "tls-key-log")]
+    pub fn tls_key_log(mut self, enabled: bool) -> Self {
+        self.tls_key_log = enabled;
+        self
+    }
+
+    /// Configure whether TLS secrets are logged to the file in the `SSLKEYLOGFILE` environment
+    /// variable, so that captured traffic can be decrypted, e.g. with Wireshark.
+    ///
+    /// **Anyone who can read the file can decrypt the traffic, so this should only be enabled
+    /// when debugging against test endpoints.** Nothing is logged when the environment variable
+    /// isn't set. This only applies to connectors created with `build_https`.
+    #[cfg(feature = "tls-key-log")]
+    pub fn set_tls_key_log(&mut self, enabled: bool) -> &mut Self {
+        self.tls_key_log = enabled;
+        self
+    }
+
+    /// Configure the server name that's sent with SNI and that server certificates are verified
+    /// against, instead of the host of the URL.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own TLS.
+    pub fn tls_server_name(mut self, tls_server_name: impl Into<String>) -> Self {
+        self.tls_server_name = Some(tls_server_name.into());
+        self
+    }
+
+    /// Configure the server name that's sent with SNI and that server certificates are verified
+    /// against, instead of the host of the URL.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for their own TLS.
+    pub fn set_tls_server_name(&mut self, tls_server_name: Option<String>) -> &mut Self {
+        self.tls_server_name = tls_server_name;
+        self
+    }
+
+    /// Configure the local address that outgoing connections are bound to.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for binding their own connections. Connections to
+    /// hosts that only have addresses of the other IP version will fail.
+    pub fn local_address(mut self, local_address: IpAddr) -> Self {
+        self.local_address = Some(local_address);
+        self
+    }
+
+    /// Configure the local address that outgoing connections are bound to.
+    ///
+    /// This only applies to connectors created with `build_https`. Connectors created from a
+    /// custom TCP connector are responsible for binding their own connections. Connections to
+    /// hosts that only have addresses of the other IP version will fail.
+    pub fn set_local_address(&mut self, local_address: Option<IpAddr>) -> &mut Self {
+        self.local_address = local_address;
+        self
+    }
+}
+
+/// Applies the HTTP/2 settings to a hyper client builder.
+///
+/// hyper 0.14 doesn't support limiting the number of concurrent streams on the client side, so
+/// the max concurrent streams setting is enforced by [`RequestLimit`] instead.
+fn apply_http2_settings(builder: &mut hyper_0_14::client::Builder, settings: &Http2Settings) {
+    builder
+        .http2_only(settings.prior_knowledge())
+        .http2_initial_stream_window_size(settings.initial_stream_window_size())
+        .http2_initial_connection_window_size(settings.initial_connection_window_size())
+        .http2_adaptive_window(settings.adaptive_window());
+    if let Some(interval) = settings.keep_alive_interval() {
+        builder
+            .http2_keep_alive_interval(interval)
+            .http2_keep_alive_while_idle(true);
+    }
+    if let Some(timeout) = settings.keep_alive_timeout() {
+        builder.http2_keep_alive_timeout(timeout);
+    }
+}
+
+/// Applies the connection pool settings to a hyper client builder.
+///
+/// hyper doesn't limit the number of connections, their lifetime, or the number of requests in
+/// flight, so those settings are implemented by [`pool_middleware::ConnectionLimit`], the
+/// [`Adapter`], and [`RequestLimit`].
+fn apply_pool_settings(builder: &mut hyper_0_14::client::Builder, settings: &PoolSettings) {
+    if let Some(max_idle) = settings.max_idle_per_host() {
+        builder.pool_max_idle_per_host(max_idle);
+    }
+    if let Some(timeout) = settings.pool_idle_timeout() {
+        builder.pool_idle_timeout(timeout);
+    }
 }
 
 /// Adapter from a [`hyper_0_14::Client`] to [`HttpConnector`].
@@ -303,8 +781,13 @@ impl HyperConnectorBuilder {
 /// This adapter also enables TCP `CONNECT` and HTTP `READ` timeouts via [`HyperConnector::builder`].
 struct Adapter<C> {
     client: timeout_middleware::HttpReadTimeout<
-        hyper_0_14::Client<timeout_middleware::ConnectTimeout<C>, SdkBody>,
+        hyper_0_14::Client<
+            timeout_middleware::ConnectTimeout<pool_middleware::ConnectionLimit<C>>,
+            SdkBody,
+        >,
     >,
+    proxy_config: Option<ProxyConfig>,
+    max_connection_lifetime: Option<Duration>,
 }
 
 impl<C> fmt::Debug for Adapter<C> {
@@ -315,24 +798,38 @@ impl<C> fmt::Debug for Adapter<C> {
     }
 }
 
-/// Extract a smithy connection from a hyper CaptureConnection
-fn extract_smithy_connection(capture_conn: &CaptureConnection) -> Option<ConnectionMetadata> {
-    let capture_conn = capture_conn.clone();
-    if let Some(conn) = capture_conn.clone().connection_metadata().as_ref() {
+impl CapturedConnection for CaptureConnection {
+    fn details(&self) -> Option<ConnectionDetails> {
+        let conn = self.connection_metadata();
+        let conn = conn.as_ref()?;
         let mut extensions = Extensions::new();
         conn.get_extras(&mut extensions);
         let http_info = extensions.get::<HttpInfo>();
-        let smithy_connection = ConnectionMetadata::new(
-            conn.is_proxied(),
-            http_info.map(|info| info.remote_addr()),
-            move || match capture_conn.connection_metadata().as_ref() {
-                Some(conn) => conn.poison(),
-                None => tracing::trace!("no connection existed to poison"),
-            },
-        );
-        Some(smithy_connection)
-    } else {
-        None
+        let mut details = ConnectionDetails {
+            proxied: conn.is_proxied(),
+            remote_addr: http_info.map(|info| info.remote_addr()),
+            local_addr: http_info.map(|info| info.local_addr()),
+            connected_at: extensions
+                .get::<pool_middleware::ConnectedAt>()
+                .map(|connected_at| connected_at.0),
+            ..Default::default()
+        };
+        #[cfg(feature = "tls-rustls")]
+        if let Some(negotiated) = extensions.get::<tls_middleware::NegotiatedTls>() {
+            details.tls_version = negotiated.version;
+            details.alpn_protocol = negotiated.alpn_protocol.clone();
+        }
+        if conn.is_negotiated_h2() {
+            details.alpn_protocol = Some("h2".to_string());
+        }
+        Some(details)
+    }
+
+    fn poison(&self) {
+        match self.connection_metadata().as_ref() {
+            Some(conn) => conn.poison(),
+            None => tracing::trace!("no connection existed to poison"),
+        }
     }
 }
 
@@ -351,20 +848,61 @@ where
                 return HttpConnectorFuture::ready(Err(ConnectorError::other(err.into(), None)));
             }
         };
-        let capture_connection = capture_connection(&mut request);
-        if let Some(capture_smithy_connection) =
-            request.extensions().get::<CaptureSmithyConnection>()
-        {
-            capture_smithy_connection
-                .set_connection_retriever(move || extract_smithy_connection(&capture_connection));
+        // Requests to `https` URLs are tunneled, so only plain requests need to authorize with the proxy
+        let proxy_authorization = self
+            .proxy_config
+            .as_ref()
+            .filter(|_| request.uri().scheme_str() == Some("http"))
+            .and_then(|proxy_config| proxy_config.proxy_for(request.uri()))
+            .and_then(|proxy| proxy.authorization_header());
+        if let Some(authorization) = proxy_authorization {
+            request
+                .headers_mut()
+                .entry(http::header::PROXY_AUTHORIZATION)
+                .or_insert_with(|| authorization.clone());
         }
-        let mut client = self.client.clone();
-        let fut = client.call(request);
+        // Kept so that the request can be replayed if it's sent on a stale pooled connection
+        let replay = try_clone_request(&request);
+        let max_connection_lifetime = self.max_connection_lifetime;
+        let client = self.client.clone();
+        let send = move |mut request: http::Request<SdkBody>| {
+            let capture_connection = capture_connection(&mut request);
+            // Compared with when the connection was established, which uses the monotonic clock
+            #[allow(clippy::disallowed_methods)]
+            let sent_at = Instant::now();
+            if let Some(capture_smithy_connection) =
+                request.extensions().get::<CaptureSmithyConnection>()
+            {
+                let capture_connection = capture_connection.clone();
+                capture_smithy_connection.set_connection_retriever(move || {
+                    extract_smithy_connection(&capture_connection, sent_at)
+                });
+            }
+            let response = client.clone().call(request);
+            (response, capture_connection, sent_at)
+        };
+        let (fut, mut used_connection, sent_at) = send(request);
         HttpConnectorFuture::new(async move {
-            let response = fut
-                .await
-                .map_err(downcast_error)?
-                .map(SdkBody::from_body_0_4);
+            let response = match (fut.await, replay) {
+                (Err(err), Some(replay))
+                    if is_reused(&used_connection, sent_at)
+                        && is_stale_connection_error(err.as_ref(), replay.method()) =>
+                {
+                    tracing::debug!(
+                        err = %DisplayErrorContext(err.as_ref()),
+                        "pooled connection was closed before a response was received; replaying the request on another connection"
+                    );
+                    let (fut, replay_connection, _) = send(replay);
+                    used_connection = replay_connection;
+                    fut.await
+                }
+                (response, _) => response,
+            }
+            .map_err(downcast_error)?
+            .map(SdkBody::from_body_0_4);
+            if let Some(max_connection_lifetime) = max_connection_lifetime {
+                poison_if_expired(&used_connection, max_connection_lifetime);
+            }
             match HttpResponse::try_from(response) {
                 Ok(response) => Ok(response),
                 Err(err) => Err(ConnectorError::other(err.into(), None)),
@@ -375,6 +913,13 @@ where
 
 /// Downcast errors coming out of hyper into an appropriate `ConnectorError`
 fn downcast_error(err: BoxError) -> ConnectorError {
+    // did the connect timeout middleware time out? if it did, this is a connect timeout
+    if find_source::<timeout_middleware::HttpTimeoutError>(err.as_ref())
+        .map(|err| err.is_connect())
+        .unwrap_or_default()
+    {
+        return ConnectorError::connect_timeout(err);
+    }
     // is a `TimedOutError` (from aws_smithy_async::timeout) in the chain? if it is, this is a timeout
     if find_source::<TimedOutError>(err.as_ref()).is_some() {
         return ConnectorError::timeout(err);
@@ -387,7 +932,10 @@ fn downcast_error(err: BoxError) -> ConnectorError {
     // generally, the top of chain will probably be a hyper error. Go through a set of hyper specific
     // error classifications
     let err = match err.downcast::<hyper_0_14::Error>() {
-        Ok(hyper_error) => return to_connector_error(*hyper_error),
+        Ok(hyper_error) => {
+            let is_connect = hyper_error.is_connect();
+            return to_connector_error(*hyper_error, is_connect);
+        }
         Err(box_error) => box_error,
     };
 
@@ -395,47 +943,25 @@ fn downcast_error(err: BoxError) -> ConnectorError {
     ConnectorError::other(err, None)
 }
 
-/// Convert a [`hyper_0_14::Error`] into a [`ConnectorError`]
-fn to_connector_error(err: hyper_0_14::Error) -> ConnectorError {
-    if err.is_timeout() || find_source::<timeout_middleware::HttpTimeoutError>(&err).is_some() {
-        ConnectorError::timeout(err.into())
-    } else if err.is_user() {
-        ConnectorError::user(err.into())
-    } else if err.is_closed() || err.is_canceled() || find_source::<std::io::Error>(&err).is_some()
-    {
-        ConnectorError::io(err.into())
-    }
-    // We sometimes receive this from S3: hyper::Error(IncompleteMessage)
-    else if err.is_incomplete_message() {
-        ConnectorError::other(err.into(), Some(ErrorKind::TransientError))
-    } else {
-        tracing::warn!(err = %DisplayErrorContext(&err), "unrecognized error from Hyper. If this error should be retried, please file an issue.");
-        ConnectorError::other(err.into(), None)
-    }
-}
-
-fn find_source<'a, E: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a E> {
-    let mut next = Some(err);
-    while let Some(err) = next {
-        if let Some(matching_err) = err.downcast_ref::<E>() {
-            return Some(matching_err);
-        }
-        next = err.source();
-    }
-    None
-}
-
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 struct CacheKey {
     connect_timeout: Option<Duration>,
+    tls_negotiation_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
+    http2: Option<Http2Settings>,
+    happy_eyeballs: Option<HappyEyeballs>,
+    pool: Option<PoolSettings>,
 }
 
 impl From<&HttpConnectorSettings> for CacheKey {
     fn from(value: &HttpConnectorSettings) -> Self {
         Self {
             connect_timeout: value.connect_timeout(),
+            tls_negotiation_timeout: value.tls_negotiation_timeout(),
             read_timeout: value.read_timeout(),
+            http2: value.http2_settings().cloned(),
+            happy_eyeballs: value.happy_eyeballs(),
+            pool: value.pool_settings().cloned(),
         }
     }
 }
@@ -443,7 +969,14 @@ impl From<&HttpConnectorSettings> for CacheKey {
 struct HyperClient<F> {
     connector_cache: RwLock<HashMap<CacheKey, SharedHttpConnector>>,
     client_builder: hyper_0_14::client::Builder,
-    tcp_connector_fn: F,
+    proxy_config: Option<ProxyConfig>,
+    dns_resolver: Option<SharedDnsResolver>,
+    trust_store: Option<TrustStore>,
+    session_resumption: Option<SessionResumption>,
+    tls_key_log: bool,
+    tls_server_name: Option<String>,
+    local_address: Option<IpAddr>,
+    build_connector: F,
 }
 
 impl<F> fmt::Debug for HyperClient<F> {
@@ -451,18 +984,20 @@ impl<F> fmt::Debug for HyperClient<F> {
         f.debug_struct("HyperClient")
             .field("connector_cache", &self.connector_cache)
             .field("client_builder", &self.client_builder)
+            .field("proxy_config", &self.proxy_config)
+            .field("dns_resolver", &self.dns_resolver)
+            .field("trust_store", &self.trust_store)
+            .field("session_resumption", &self.session_resumption)
+            .field("tls_key_log", &self.tls_key_log)
+            .field("tls_server_name", &self.tls_server_name)
+            .field("local_address", &self.local_address)
             .finish()
     }
 }
 
-impl<C, F> HttpClient for HyperClient<F>
+impl<F> HttpClient for HyperClient<F>
 where
-    F: Fn() -> C + Send + Sync,
-    C: Clone + Send + Sync + 'static,
-    C: Service<Uri>,
-    C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
-    C::Future: Unpin + Send + 'static,
-    C::Error: Into<BoxError>,
+    F: Fn(HyperConnectorBuilder) -> HyperConnector + Send + Sync,
 {
     fn http_connector(
         &self,
@@ -479,9 +1014,16 @@ where
                     .hyper_builder(self.client_builder.clone())
                     .connector_settings(settings.clone());
                 builder.set_sleep_impl(components.sleep_impl());
-
-                let tcp_connector = (self.tcp_connector_fn)();
-                let connector = SharedHttpConnector::new(builder.build(tcp_connector));
+                builder.set_spawn(components.spawn());
+                builder.set_proxy_config(self.proxy_config.clone());
+                builder.set_dns_resolver(self.dns_resolver.clone());
+                builder.set_trust_store(self.trust_store.clone());
+                builder.set_session_resumption(self.session_resumption);
+                builder.tls_key_log = self.tls_key_log;
+                builder.set_tls_server_name(self.tls_server_name.clone());
+                builder.set_local_address(self.local_address);
+
+                let connector = SharedHttpConnector::new((self.build_connector)(builder));
                 cache.insert(key.clone(), connector);
             }
             connector = cache.get(&key).cloned();
@@ -489,6 +1031,12 @@ where
 
         connector.expect("cache populated above")
     }
+
+    fn release_idle_connections(&self) {
+        // Each connector owns its own connection pool, and dropping the pool closes its idle
+        // connections. Requests that are in flight hold on to their connector until they complete.
+        self.connector_cache.write().unwrap().clear();
+    }
 }
 
 /// Builder for a hyper-backed [`HttpClient`] implementation.
@@ -498,6 +1046,13 @@ where
 #[derive(Clone, Default, Debug)]
 pub struct HyperClientBuilder {
     client_builder: Option<hyper_0_14::client::Builder>,
+    proxy_config: Option<ProxyConfig>,
+    dns_resolver: Option<SharedDnsResolver>,
+    trust_store: Option<TrustStore>,
+    session_resumption: Option<SessionResumption>,
+    tls_key_log: bool,
+    tls_server_name: Option<String>,
+    local_address: Option<IpAddr>,
 }
 
 impl HyperClientBuilder {
@@ -525,71 +1080,968 @@ impl HyperClientBuilder {
         self
     }
 
-    /// Create a [`HyperConnector`] with the default rustls HTTPS implementation.
-    #[cfg(feature = "tls-rustls")]
-    pub fn build_https(self) -> SharedHttpClient {
-        self.build(default_connector::https())
+    /// Configure the proxies that requests are sent through.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own proxying.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls-rustls")]
+    /// # fn example() {
+    /// use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
+    /// use aws_smithy_runtime::client::http::proxy::ProxyConfig;
+    ///
+    /// // Use the proxies from the `HTTP_PROXY`, `HTTPS_PROXY`, and `NO_PROXY` environment variables
+    /// let http_client = HyperClientBuilder::new()
+    ///     .proxy_config(ProxyConfig::from_env())
+    ///     .build_https();
+    /// # }
+    /// ```
+    pub fn proxy_config(mut self, proxy_config: ProxyConfig) -> Self {
+        self.proxy_config = Some(proxy_config);
+        self
     }
 
-    /// Create a [`SharedHttpClient`] from this builder and a given connector.
+    /// Configure the proxies that requests are sent through.
     ///
-    #[cfg_attr(
-        feature = "tls-rustls",
-        doc = "Use [`build_https`](HyperClientBuilder::build_https) if you don't want to provide a custom TCP connector."
-    )]
-    pub fn build<C>(self, tcp_connector: C) -> SharedHttpClient
-    where
-        C: Clone + Send + Sync + 'static,
-        C: Service<Uri>,
-        C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
-        C::Future: Unpin + Send + 'static,
-        C::Error: Into<BoxError>,
-    {
-        SharedHttpClient::new(HyperClient {
-            connector_cache: RwLock::new(HashMap::new()),
-            client_builder: self.client_builder.unwrap_or_default(),
-            tcp_connector_fn: move || tcp_connector.clone(),
-        })
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own proxying.
+    pub fn set_proxy_config(&mut self, proxy_config: Option<ProxyConfig>) -> &mut Self {
+        self.proxy_config = proxy_config;
+        self
     }
 
-    #[cfg(all(test, feature = "test-util"))]
-    fn build_with_fn<C, F>(self, tcp_connector_fn: F) -> SharedHttpClient
-    where
-        F: Fn() -> C + Send + Sync + 'static,
-        C: Clone + Send + Sync + 'static,
-        C: Service<Uri>,
-        C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
-        C::Future: Unpin + Send + 'static,
-        C::Error: Into<BoxError>,
-    {
-        SharedHttpClient::new(HyperClient {
-            connector_cache: RwLock::new(HashMap::new()),
-            client_builder: self.client_builder.unwrap_or_default(),
-            tcp_connector_fn,
-        })
+    /// Configure the DNS resolver used to resolve the names of the hosts that are connected to.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own name resolution. When no resolver is set,
+    /// names are resolved with the system's `getaddrinfo` on a blocking thread.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls-rustls")]
+    /// # fn example() {
+    /// use aws_smithy_runtime::client::dns::TokioDnsResolver;
+    /// use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
+    ///
+    /// let http_client = HyperClientBuilder::new()
+    ///     .dns_resolver(TokioDnsResolver::new())
+    ///     .build_https();
+    /// # }
+    /// ```
+    pub fn dns_resolver(mut self, dns_resolver: impl ResolveDns + 'static) -> Self {
+        self.dns_resolver = Some(dns_resolver.into_shared());
+        self
     }
-}
 
-mod timeout_middleware {
-    use aws_smithy_async::future::timeout::{TimedOutError, Timeout};
-    use aws_smithy_async::rt::sleep::Sleep;
-    use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
-    use aws_smithy_runtime_api::box_error::BoxError;
-    use http::Uri;
-    use pin_project_lite::pin_project;
-    use std::error::Error;
-    use std::fmt::Formatter;
-    use std::future::Future;
-    use std::pin::Pin;
-    use std::task::{Context, Poll};
-    use std::time::Duration;
+    /// Configure the DNS resolver used to resolve the names of the hosts that are connected to.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own name resolution. When no resolver is set,
+    /// names are resolved with the system's `getaddrinfo` on a blocking thread.
+    pub fn set_dns_resolver(&mut self, dns_resolver: Option<SharedDnsResolver>) -> &mut Self {
+        self.dns_resolver = dns_resolver;
+        self
+    }
 
-    #[derive(Debug)]
-    pub(crate) struct HttpTimeoutError {
+    /// Configure the root certificates that server certificates are verified against.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own TLS. When no trust store is set, the
+    /// platform's native root certificates are trusted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls-rustls")]
+    /// # fn example() -> Result<(), aws_smithy_runtime::client::http::tls::InvalidCertificateError> {
+    /// use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
+    /// use aws_smithy_runtime::client::http::tls::TrustStore;
+    ///
+    /// // Trust an internal CA in addition to the platform's native root certificates
+    /// let http_client = HyperClientBuilder::new()
+    ///     .trust_store(TrustStore::default().with_pem_certificate_file("internal-ca.pem")?)
+    ///     .build_https();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn trust_store(mut self, trust_store: TrustStore) -> Self {
+        self.trust_store = Some(trust_store);
+        self
+    }
+
+    /// Configure the root certificates that server certificates are verified against.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own TLS. When no trust store is set, the
+    /// platform's native root certificates are trusted.
+    pub fn set_trust_store(&mut self, trust_store: Option<TrustStore>) -> &mut Self {
+        self.trust_store = trust_store;
+        self
+    }
+
+    /// Configure how TLS sessions are resumed when reconnecting to a server.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own TLS. When this isn't set, up to 256 sessions
+    /// are cached in memory, and the cache is shared with other default clients.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls-rustls")]
+    /// # fn example() {
+    /// use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
+    /// use aws_smithy_runtime::client::http::tls::SessionResumption;
+    ///
+    /// // Cache more sessions for a client that connects to many hosts
+    /// let http_client = HyperClientBuilder::new()
+    ///     .session_resumption(SessionResumption::in_memory(4096))
+    ///     .build_https();
+    /// # }
+    /// ```
+    pub fn session_resumption(mut self, session_resumption: SessionResumption) -> Self {
+        self.session_resumption = Some(session_resumption);
+        self
+    }
+
+    /// Configure how TLS sessions are resumed when reconnecting to a server.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own TLS. When this isn't set, up to 256 sessions
+    /// are cached in memory, and the cache is shared with other default clients.
+    pub fn set_session_resumption(
+        &mut self,
+        session_resumption: Option<SessionResumption>,
+    ) -> &mut Self {
+        self.session_resumption = session_resumption;
+        self
+    }
+
+    /// Configure whether TLS secrets are logged to the file in the `SSLKEYLOGFILE` environment
+    /// variable, so that captured traffic can be decrypted, e.g. with Wireshark.
+    ///
+    /// **Anyone who can read the file can decrypt the traffic, so this should only be enabled
+    /// when debugging against test endpoints.** Nothing is logged when the environment variable
+    /// isn't set. This only applies to clients created with `build_https`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls-rustls")]
+    /// # fn example() {
+    /// use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
+    ///
+    /// // Run with `SSLKEYLOGFILE=/tmp/keys.log` and load the file into Wireshark
+    /// let http_client = HyperClientBuilder::new().tls_key_log(true).build_https();
+    /// # }
+    /// ```
+    #[cfg(feature = "tls-key-log")]
+    pub fn tls_key_log(mut self, enabled: bool) -> Self {
+        self.tls_key_log = enabled;
+        self
+    }
+
+    /// Configure whether TLS secrets are logged to the file in the `SSLKEYLOGFILE` environment
+    /// variable, so that captured traffic can be decrypted, e.g. with Wireshark.
+    ///
+    /// **Anyone who can read the file can decrypt the traffic, so this should only be enabled
+    /// when debugging against test endpoints.** Nothing is logged when the environment variable
+    /// isn't set. This only applies to clients created with `build_https`.
+    #[cfg(feature = "tls-key-log")]
+    pub fn set_tls_key_log(&mut self, enabled: bool) -> &mut Self {
+        self.tls_key_log = enabled;
+        self
+    }
+
+    /// Configure the server name that's sent with SNI and that server certificates are verified
+    /// against, instead of the host of the URL.
+    ///
+    /// This allows requests to be sent to a TLS-passthrough (L4) proxy, or to a staging IP
+    /// address, while still verifying the production certificate. Only the TLS handshake is
+    /// affected; the `Host` header is signed with the request, so it always has the host of the
+    /// URL. To also send the production `Host` header, use the production URL with a
+    /// [`dns_resolver`](Self::dns_resolver) that resolves it to the address to connect to.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own TLS.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls-rustls")]
+    /// # fn example() {
+    /// use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
+    ///
+    /// // Connect through `https://l4-proxy.internal`, and verify the service's certificate
+    /// let http_client = HyperClientBuilder::new()
+    ///     .tls_server_name("dynamodb.us-east-1.amazonaws.com")
+    ///     .build_https();
+    /// # }
+    /// ```
+    pub fn tls_server_name(mut self, tls_server_name: impl Into<String>) -> Self {
+        self.tls_server_name = Some(tls_server_name.into());
+        self
+    }
+
+    /// Configure the server name that's sent with SNI and that server certificates are verified
+    /// against, instead of the host of the URL.
+    ///
+    /// This only applies to clients created with `build_https`. Clients created from a custom
+    /// TCP connector are responsible for their own TLS.
+    pub fn set_tls_server_name(&mut self, tls_server_name: Option<String>) -> &mut Self {
+        self.tls_server_name = tls_server_name;
+        self
+    }
+
+    /// Configure the local address that outgoing connections are bound to.
+    ///
+    /// On hosts with multiple network interfaces, this pins traffic to the interface with the
+    /// given address. This only applies to clients created with `build_https`. Clients created
+    /// from a custom TCP connector are responsible for binding their own connections.
+    /// Connections to hosts that only have addresses of the other IP version will fail.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "tls-rustls")]
+    /// # fn example() {
+    /// use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let http_client = HyperClientBuilder::new()
+    ///     .local_address(IpAddr::V4(Ipv4Addr::new(10, 0, 1, 15)))
+    ///     .build_https();
+    /// # }
+    /// ```
+    pub fn local_address(mut self, local_address: IpAddr) -> Self {
+        self.local_address = Some(local_address);
+        self
+    }
+
+    /// Configure the local address that outgoing connections are bound to.
+    ///
+    /// On hosts with multiple network interfaces, this pins traffic to the interface with the
+    /// given address. This only applies to clients created with `build_https`. Clients created
+    /// from a custom TCP connector are responsible for binding their own connections.
+    /// Connections to hosts that only have addresses of the other IP version will fail.
+    pub fn set_local_address(&mut self, local_address: Option<IpAddr>) -> &mut Self {
+        self.local_address = local_address;
+        self
+    }
+
+    /// Create a [`HyperConnector`] with the default rustls HTTPS implementation.
+    #[cfg(feature = "tls-rustls")]
+    pub fn build_https(self) -> SharedHttpClient {
+        self.build_with_connector_fn(|builder| builder.build_https())
+    }
+
+    /// Create a [`SharedHttpClient`] from this builder and a given connector.
+    ///
+    #[cfg_attr(
+        feature = "tls-rustls",
+        doc = "Use [`build_https`](HyperClientBuilder::build_https) if you don't want to provide a custom TCP connector."
+    )]
+    pub fn build<C>(self, tcp_connector: C) -> SharedHttpClient
+    where
+        C: Clone + Send + Sync + 'static,
+        C: Service<Uri>,
+        C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        C::Future: Unpin + Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        self.build_with_connector_fn(move |builder| builder.build(tcp_connector.clone()))
+    }
+
+    #[cfg(all(test, feature = "test-util"))]
+    fn build_with_fn<C, F>(self, tcp_connector_fn: F) -> SharedHttpClient
+    where
+        F: Fn() -> C + Send + Sync + 'static,
+        C: Clone + Send + Sync + 'static,
+        C: Service<Uri>,
+        C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        C::Future: Unpin + Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        self.build_with_connector_fn(move |builder| builder.build(tcp_connector_fn()))
+    }
+
+    fn build_with_connector_fn<F>(self, build_connector: F) -> SharedHttpClient
+    where
+        F: Fn(HyperConnectorBuilder) -> HyperConnector + Send + Sync + 'static,
+    {
+        SharedHttpClient::new(HyperClient {
+            connector_cache: RwLock::new(HashMap::new()),
+            client_builder: self.client_builder.unwrap_or_default(),
+            proxy_config: self.proxy_config,
+            dns_resolver: self.dns_resolver,
+            trust_store: self.trust_store,
+            session_resumption: self.session_resumption,
+            tls_key_log: self.tls_key_log,
+            tls_server_name: self.tls_server_name,
+            local_address: self.local_address,
+            build_connector,
+        })
+    }
+}
+
+#[cfg(feature = "tls-rustls")]
+mod dns_middleware {
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::dns::{ResolveDns, ResolveDnsError, SharedDnsResolver};
+    use hyper_0_14::client::connect::dns::{GaiResolver, Name};
+    use hyper_0_14::service::Service;
+    use std::future::Future;
+    use std::net::SocketAddr;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// Adapts a [`ResolveDns`] implementation to hyper's resolver interface, falling back to
+    /// hyper's `getaddrinfo` resolver when there isn't one.
+    ///
+    /// Failures from either are reported as [`ResolveDnsError`]s, so that they can be told apart
+    /// from failures to connect.
+    #[derive(Clone, Debug)]
+    pub(crate) enum Resolver {
+        Gai(GaiResolver),
+        Custom(SharedDnsResolver),
+    }
+
+    impl Resolver {
+        pub(crate) fn new(dns_resolver: Option<SharedDnsResolver>) -> Self {
+            match dns_resolver {
+                Some(dns_resolver) => Self::Custom(dns_resolver),
+                None => Self::Gai(GaiResolver::new()),
+            }
+        }
+    }
+
+    impl Service<Name> for Resolver {
+        // hyper sets the port of the addresses when it connects
+        type Response = std::vec::IntoIter<SocketAddr>;
+        type Error = BoxError;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            match self {
+                Self::Gai(gai) => gai.poll_ready(cx).map_err(|err| err.into()),
+                Self::Custom(_) => Poll::Ready(Ok(())),
+            }
+        }
+
+        fn call(&mut self, name: Name) -> Self::Future {
+            match self {
+                Self::Gai(gai) => {
+                    let resolving = gai.call(name);
+                    Box::pin(async move {
+                        let addresses = resolving.await.map_err(ResolveDnsError::new)?;
+                        Ok(addresses.collect::<Vec<_>>().into_iter())
+                    })
+                }
+                Self::Custom(dns_resolver) => {
+                    let dns_resolver = dns_resolver.clone();
+                    Box::pin(async move {
+                        let mut addresses = dns_resolver.resolve_dns(name.as_str()).await?;
+                        // hyper races the family of the first address against the other one, and
+                        // RFC 8305 prefers IPv6
+                        addresses.sort_by_key(|ip| ip.is_ipv4());
+                        Ok(addresses
+                            .into_iter()
+                            .map(|ip| SocketAddr::new(ip, 0))
+                            .collect::<Vec<_>>()
+                            .into_iter())
+                    })
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use aws_smithy_runtime_api::client::dns::{DnsFuture, ResolveDnsError};
+        use http::Uri;
+        use hyper_0_14::client::HttpConnector;
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        #[derive(Debug)]
+        struct StaticResolver(Vec<IpAddr>);
+
+        impl ResolveDns for StaticResolver {
+            fn resolve_dns<'a>(&'a self, name: &'a str) -> DnsFuture<'a> {
+                assert_eq!("s3.amazonaws.com", name);
+                DnsFuture::ready(if self.0.is_empty() {
+                    Err(ResolveDnsError::new("no records"))
+                } else {
+                    Ok(self.0.clone())
+                })
+            }
+        }
+
+        #[tokio::test]
+        async fn prefers_ipv6_addresses() {
+            let mut resolver = Resolver::new(Some(SharedDnsResolver::new(StaticResolver(vec![
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(Ipv6Addr::LOCALHOST),
+                IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            ]))));
+            let addresses: Vec<_> = resolver
+                .call("s3.amazonaws.com".parse().unwrap())
+                .await
+                .expect("success")
+                .map(|address| address.ip())
+                .collect();
+            assert_eq!(
+                vec![
+                    IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                ],
+                addresses
+            );
+        }
+
+        #[tokio::test]
+        async fn connects_to_the_resolved_address() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let mut connector = HttpConnector::new_with_resolver(Resolver::new(Some(
+                SharedDnsResolver::new(StaticResolver(vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])),
+            )));
+            let uri: Uri = format!("http://s3.amazonaws.com:{port}/").parse().unwrap();
+            let stream = connector.call(uri).await.expect("success");
+            assert_eq!(listener.local_addr().unwrap(), stream.peer_addr().unwrap());
+        }
+
+        #[tokio::test]
+        async fn reports_dns_failures() {
+            let mut connector = HttpConnector::new_with_resolver(Resolver::new(Some(
+                SharedDnsResolver::new(StaticResolver(Vec::new())),
+            )));
+            let err = connector
+                .call("http://s3.amazonaws.com/".parse().unwrap())
+                .await
+                .expect_err("resolution failed");
+            assert!(
+                crate::client::http::hyper_common::is_dns_error(&err),
+                "{err:?}"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "tls-rustls")]
+mod socket_middleware {
+    use aws_smithy_runtime_api::client::http::TcpSettings;
+    use http::Uri;
+    use hyper_0_14::service::Service;
+    use socket2::{SockRef, TcpKeepalive};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::net::TcpStream;
+
+    /// TCP connector that applies TCP keep-alive settings to the connections it makes.
+    ///
+    /// hyper's connector only supports configuring the keep-alive time, so the keep-alive
+    /// settings are applied to the socket once it's connected.
+    #[derive(Clone, Debug)]
+    pub(crate) struct Keepalive<C> {
+        inner: C,
+        keepalive: Option<TcpKeepalive>,
+    }
+
+    impl<C> Keepalive<C> {
+        pub(crate) fn new(inner: C, settings: Option<&TcpSettings>) -> Self {
+            let keepalive = settings
+                .filter(|settings| settings.keepalive_enabled())
+                .map(to_keepalive);
+            Self { inner, keepalive }
+        }
+    }
+
+    /// Converts the keep-alive settings to the socket's keep-alive configuration.
+    pub(crate) fn to_keepalive(settings: &TcpSettings) -> TcpKeepalive {
+        let mut keepalive = TcpKeepalive::new();
+        if let Some(time) = settings.keepalive_time() {
+            keepalive = keepalive.with_time(time);
+        }
+        #[cfg(any(
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "windows",
+        ))]
+        if let Some(interval) = settings.keepalive_interval() {
+            keepalive = keepalive.with_interval(interval);
+        }
+        #[cfg(any(
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "netbsd",
+        ))]
+        if let Some(retries) = settings.keepalive_retries() {
+            keepalive = keepalive.with_retries(retries);
+        }
+        keepalive
+    }
+
+    impl<C> Service<Uri> for Keepalive<C>
+    where
+        C: Service<Uri, Response = TcpStream>,
+        C::Future: Send + 'static,
+    {
+        type Response = TcpStream;
+        type Error = C::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<TcpStream, C::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, uri: Uri) -> Self::Future {
+            let connecting = self.inner.call(uri);
+            let keepalive = self.keepalive.clone();
+            Box::pin(async move {
+                let stream = connecting.await?;
+                if let Some(keepalive) = keepalive {
+                    set_keepalive(&stream, &keepalive);
+                }
+                Ok(stream)
+            })
+        }
+    }
+
+    /// Enables TCP keep-alive on `stream`, logging a warning if it can't be configured.
+    pub(crate) fn set_keepalive(stream: &TcpStream, keepalive: &TcpKeepalive) {
+        if let Err(err) = SockRef::from(stream).set_tcp_keepalive(keepalive) {
+            tracing::warn!(err = %err, "failed to configure TCP keep-alive");
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use std::time::Duration;
+
+        #[tokio::test]
+        async fn configures_keepalive() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let uri: Uri = format!("http://{}", listener.local_addr().unwrap())
+                .parse()
+                .unwrap();
+            let settings = TcpSettings::builder()
+                .keepalive_time(Duration::from_secs(42))
+                .build();
+            let stream = Keepalive::new(hyper_0_14::client::HttpConnector::new(), Some(&settings))
+                .call(uri.clone())
+                .await
+                .expect("success");
+            let socket = SockRef::from(&stream);
+            assert!(socket.keepalive().unwrap());
+            #[cfg(target_os = "linux")]
+            assert_eq!(Duration::from_secs(42), socket.keepalive_time().unwrap());
+
+            let stream = Keepalive::new(hyper_0_14::client::HttpConnector::new(), None)
+                .call(uri)
+                .await
+                .expect("success");
+            assert!(!SockRef::from(&stream).keepalive().unwrap());
+        }
+    }
+}
+
+#[cfg(feature = "tls-rustls")]
+mod tls_middleware {
+    use http::Uri;
+    use hyper_0_14::client::connect::{Connected, Connection};
+    use hyper_0_14::service::Service;
+    use hyper_rustls::MaybeHttpsStream;
+    use std::future::Future;
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+    /// The TLS version and ALPN protocol that were negotiated for a connection.
+    ///
+    /// This is added to the extras of every TLS connection made by a [`TlsInfo`] connector.
+    #[derive(Clone, Debug)]
+    pub(crate) struct NegotiatedTls {
+        pub(crate) version: Option<&'static str>,
+        pub(crate) alpn_protocol: Option<String>,
+    }
+
+    impl NegotiatedTls {
+        fn from_connection(connection: &rustls::ClientConnection) -> Self {
+            let version = match connection.protocol_version() {
+                Some(rustls::ProtocolVersion::TLSv1_2) => Some("TLSv1.2"),
+                Some(rustls::ProtocolVersion::TLSv1_3) => Some("TLSv1.3"),
+                _ => None,
+            };
+            Self {
+                version,
+                alpn_protocol: connection
+                    .alpn_protocol()
+                    .map(|protocol| String::from_utf8_lossy(protocol).into_owned()),
+            }
+        }
+    }
+
+    /// HTTPS connector that records the TLS parameters that were negotiated for its connections.
+    #[derive(Clone, Debug)]
+    pub(crate) struct TlsInfo<C> {
+        inner: C,
+    }
+
+    impl<C> TlsInfo<C> {
+        pub(crate) fn new(inner: C) -> Self {
+            Self { inner }
+        }
+    }
+
+    impl<C, T> Service<Uri> for TlsInfo<C>
+    where
+        C: Service<Uri, Response = MaybeHttpsStream<T>>,
+        C::Future: Send + 'static,
+    {
+        type Response = TlsInfoStream<T>;
+        type Error = C::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, C::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, uri: Uri) -> Self::Future {
+            let connecting = self.inner.call(uri);
+            Box::pin(async move {
+                let inner = connecting.await?;
+                let negotiated = match &inner {
+                    MaybeHttpsStream::Https(tls) => {
+                        Some(NegotiatedTls::from_connection(tls.get_ref().1))
+                    }
+                    MaybeHttpsStream::Http(_) => None,
+                };
+                Ok(TlsInfoStream { inner, negotiated })
+            })
+        }
+    }
+
+    /// A connection that reports the TLS parameters that were negotiated for it.
+    #[derive(Debug)]
+    pub(crate) struct TlsInfoStream<T> {
+        inner: MaybeHttpsStream<T>,
+        negotiated: Option<NegotiatedTls>,
+    }
+
+    impl<T: Connection + AsyncRead + AsyncWrite + Unpin> Connection for TlsInfoStream<T> {
+        fn connected(&self) -> Connected {
+            let connected = self.inner.connected();
+            match &self.negotiated {
+                Some(negotiated) => connected.extra(negotiated.clone()),
+                None => connected,
+            }
+        }
+    }
+
+    impl<T: AsyncRead + AsyncWrite + Unpin> AsyncRead for TlsInfoStream<T> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    impl<T: AsyncRead + AsyncWrite + Unpin> AsyncWrite for TlsInfoStream<T> {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write(cx, buf)
+        }
+
+        fn poll_write_vectored(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &[io::IoSlice<'_>],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.inner.is_write_vectored()
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_shutdown(cx)
+        }
+    }
+}
+
+mod pool_middleware {
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use http::Uri;
+    use hyper_0_14::client::connect::{Connected, Connection};
+    use hyper_0_14::service::Service;
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::io;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    use std::time::Instant;
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+    /// The time at which a connection was established.
+    ///
+    /// This is added to the extras of every connection made by a [`ConnectionLimit`].
+    #[derive(Clone, Copy, Debug)]
+    pub(crate) struct ConnectedAt(pub(crate) Instant);
+
+    /// TCP connector that limits the number of open connections to each host.
+    #[derive(Clone, Debug)]
+    pub(crate) struct ConnectionLimit<C> {
+        inner: C,
+        max_connections_per_host: Option<usize>,
+        hosts: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+    }
+
+    impl<C> ConnectionLimit<C> {
+        pub(crate) fn new(inner: C, max_connections_per_host: Option<usize>) -> Self {
+            Self {
+                inner,
+                max_connections_per_host,
+                hosts: Default::default(),
+            }
+        }
+
+        fn semaphore(&self, uri: &Uri) -> Option<Arc<Semaphore>> {
+            let max_connections = self.max_connections_per_host?;
+            let host = format!(
+                "{}://{}",
+                uri.scheme_str().unwrap_or_default(),
+                uri.authority().map(|a| a.as_str()).unwrap_or_default()
+            );
+            let mut hosts = self.hosts.lock().unwrap();
+            Some(
+                hosts
+                    .entry(host)
+                    .or_insert_with(|| Arc::new(Semaphore::new(max_connections)))
+                    .clone(),
+            )
+        }
+    }
+
+    impl<C> Service<Uri> for ConnectionLimit<C>
+    where
+        C: Service<Uri> + Clone + Send + 'static,
+        C::Response: Send + 'static,
+        C::Future: Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        type Response = LimitedStream<C::Response>;
+        type Error = BoxError;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx).map_err(|err| err.into())
+        }
+
+        fn call(&mut self, uri: Uri) -> Self::Future {
+            let semaphore = self.semaphore(&uri);
+            // The connector that was made ready must be the one that's called
+            let clone = self.inner.clone();
+            let mut inner = std::mem::replace(&mut self.inner, clone);
+            Box::pin(async move {
+                let permit = match semaphore {
+                    Some(semaphore) => Some(
+                        semaphore
+                            .acquire_owned()
+                            .await
+                            .expect("the semaphore is never closed"),
+                    ),
+                    None => None,
+                };
+                let inner = inner.call(uri).await.map_err(|err| err.into())?;
+                // Only used to measure the connection's age, so it doesn't need the time source
+                #[allow(clippy::disallowed_methods)]
+                let connected_at = Instant::now();
+                Ok(LimitedStream {
+                    inner,
+                    connected_at,
+                    _permit: permit,
+                })
+            })
+        }
+    }
+
+    /// A connection that counts against the connection limit of its host until it's closed.
+    #[derive(Debug)]
+    pub(crate) struct LimitedStream<T> {
+        inner: T,
+        connected_at: Instant,
+        _permit: Option<OwnedSemaphorePermit>,
+    }
+
+    impl<T: Connection> Connection for LimitedStream<T> {
+        fn connected(&self) -> Connected {
+            self.inner.connected().extra(ConnectedAt(self.connected_at))
+        }
+    }
+
+    impl<T: AsyncRead + Unpin> AsyncRead for LimitedStream<T> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    impl<T: AsyncWrite + Unpin> AsyncWrite for LimitedStream<T> {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write(cx, buf)
+        }
+
+        fn poll_write_vectored(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &[io::IoSlice<'_>],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.inner.is_write_vectored()
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_shutdown(cx)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use std::time::Duration;
+
+        #[tokio::test]
+        async fn limits_connections_per_host() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let uri: Uri = format!("http://{}", listener.local_addr().unwrap())
+                .parse()
+                .unwrap();
+            let mut connector =
+                ConnectionLimit::new(hyper_0_14::client::HttpConnector::new(), Some(1));
+
+            let first = connector.call(uri.clone()).await.expect("success");
+            let mut second = connector.call(uri.clone());
+            assert!(
+                tokio::time::timeout(Duration::from_millis(100), &mut second)
+                    .await
+                    .is_err(),
+                "the second connection must wait for the first one to close"
+            );
+            // Other hosts have their own limit
+            let other_host: Uri = format!("http://localhost:{}", uri.port_u16().unwrap())
+                .parse()
+                .unwrap();
+            connector.call(other_host).await.expect("success");
+
+            drop(first);
+            second.await.expect("success");
+        }
+
+        #[tokio::test]
+        async fn records_when_connections_were_established() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let uri: Uri = format!("http://{}", listener.local_addr().unwrap())
+                .parse()
+                .unwrap();
+            #[allow(clippy::disallowed_methods)]
+            let before = Instant::now();
+            let stream = ConnectionLimit::new(hyper_0_14::client::HttpConnector::new(), None)
+                .call(uri)
+                .await
+                .expect("success");
+            let mut extensions = http::Extensions::new();
+            stream.connected().get_extras(&mut extensions);
+            let connected_at = extensions.get::<ConnectedAt>().expect("set");
+            assert!(connected_at.0 >= before);
+        }
+    }
+}
+
+mod timeout_middleware {
+    use aws_smithy_async::future::timeout::{TimedOutError, Timeout};
+    use aws_smithy_async::rt::sleep::Sleep;
+    use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use http::Uri;
+    use pin_project_lite::pin_project;
+    use std::error::Error;
+    use std::fmt::Formatter;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+    #[cfg(feature = "tls-rustls")]
+    use {
+        hyper_0_14::client::connect::Connection,
+        std::future::poll_fn,
+        std::sync::atomic::{AtomicBool, Ordering},
+        std::sync::Arc,
+        tokio::io::{AsyncRead, AsyncWrite},
+    };
+
+    const CONNECT_TIMEOUT_KIND: &str = "HTTP connect";
+    #[cfg(feature = "tls-rustls")]
+    const TLS_NEGOTIATION_TIMEOUT_KIND: &str = "TLS negotiation";
+
+    #[derive(Debug)]
+    pub(crate) struct HttpTimeoutError {
         kind: &'static str,
         duration: Duration,
     }
 
+    impl HttpTimeoutError {
+        /// Returns true if this timeout occurred while establishing a connection.
+        pub(crate) fn is_connect(&self) -> bool {
+            #[cfg(feature = "tls-rustls")]
+            if self.kind == TLS_NEGOTIATION_TIMEOUT_KIND {
+                return true;
+            }
+            self.kind == CONNECT_TIMEOUT_KIND
+        }
+    }
+
     impl std::fmt::Display for HttpTimeoutError {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             write!(
@@ -630,10 +2082,51 @@ mod timeout_middleware {
             }
         }
 
-        pub(crate) fn no_timeout(inner: I) -> Self {
+        pub(crate) fn no_timeout(inner: I) -> Self {
+            Self {
+                inner,
+                timeout: None,
+            }
+        }
+    }
+
+    /// Timeout wrapper that establishes a TLS connection on top of a TCP connection, and times out
+    /// the TLS handshake separately from the TCP connection
+    #[cfg(feature = "tls-rustls")]
+    #[derive(Clone)]
+    pub(super) struct TlsNegotiationTimeout<I> {
+        tcp: ConnectTimeout<I>,
+        tls_config: Arc<rustls::ClientConfig>,
+        server_name: Option<String>,
+        sleep: SharedAsyncSleep,
+        timeout: Duration,
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    impl<I> std::fmt::Debug for TlsNegotiationTimeout<I> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("TlsNegotiationTimeout")
+                .field("timeout", &self.timeout)
+                .finish()
+        }
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    impl<I> TlsNegotiationTimeout<I> {
+        /// Create a new `TlsNegotiationTimeout` that negotiates TLS over connections from `tcp`.
+        pub(crate) fn new(
+            tcp: ConnectTimeout<I>,
+            tls_config: Arc<rustls::ClientConfig>,
+            server_name: Option<String>,
+            sleep: SharedAsyncSleep,
+            timeout: Duration,
+        ) -> Self {
             Self {
-                inner,
-                timeout: None,
+                tcp,
+                tls_config,
+                server_name,
+                sleep,
+                timeout,
             }
         }
     }
@@ -730,7 +2223,7 @@ mod timeout_middleware {
                     let sleep = sleep.sleep(*duration);
                     MaybeTimeoutFuture::Timeout {
                         timeout: Timeout::new(self.inner.call(req), sleep),
-                        error_type: "HTTP connect",
+                        error_type: CONNECT_TIMEOUT_KIND,
                         duration: *duration,
                     }
                 }
@@ -741,6 +2234,92 @@ mod timeout_middleware {
         }
     }
 
+    /// TCP connector that records when its connection has been established
+    #[cfg(feature = "tls-rustls")]
+    #[derive(Clone)]
+    struct SignalConnected<I> {
+        inner: ConnectTimeout<I>,
+        connected: Arc<AtomicBool>,
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    impl<I> hyper_0_14::service::Service<Uri> for SignalConnected<I>
+    where
+        I: hyper_0_14::service::Service<Uri>,
+        I::Error: Into<BoxError>,
+        I::Future: Send + 'static,
+    {
+        type Response = I::Response;
+        type Error = BoxError;
+        type Future = Pin<Box<dyn Future<Output = Result<I::Response, BoxError>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: Uri) -> Self::Future {
+            let connecting = self.inner.call(req);
+            let connected = self.connected.clone();
+            Box::pin(async move {
+                let stream = connecting.await?;
+                connected.store(true, Ordering::Release);
+                Ok(stream)
+            })
+        }
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    impl<I> hyper_0_14::service::Service<Uri> for TlsNegotiationTimeout<I>
+    where
+        I: hyper_0_14::service::Service<Uri> + Clone + Send + 'static,
+        I::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        I::Error: Into<BoxError>,
+        I::Future: Send + 'static,
+    {
+        type Response = hyper_rustls::MaybeHttpsStream<I::Response>;
+        type Error = BoxError;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.tcp.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: Uri) -> Self::Future {
+            let connected = Arc::new(AtomicBool::new(false));
+            let mut https = super::default_connector::https_with(
+                SignalConnected {
+                    inner: self.tcp.clone(),
+                    connected: connected.clone(),
+                },
+                self.tls_config.clone(),
+                self.server_name.clone(),
+            );
+            let mut connecting = https.call(req);
+            let (sleep, duration) = (self.sleep.clone(), self.timeout);
+            let mut tls_timeout: Option<Sleep> = None;
+            Box::pin(poll_fn(move |cx| {
+                if let Poll::Ready(result) = connecting.as_mut().poll(cx) {
+                    return Poll::Ready(result);
+                }
+                // The TLS handshake starts as soon as the TCP connection has been established
+                if tls_timeout.is_none() && connected.load(Ordering::Acquire) {
+                    tls_timeout = Some(sleep.sleep(duration));
+                }
+                match tls_timeout
+                    .as_mut()
+                    .map(|timeout| Pin::new(timeout).poll(cx))
+                {
+                    Some(Poll::Ready(())) => Poll::Ready(Err(HttpTimeoutError {
+                        kind: TLS_NEGOTIATION_TIMEOUT_KIND,
+                        duration,
+                    }
+                    .into())),
+                    _ => Poll::Pending,
+                }
+            }))
+        }
+    }
+
     impl<I, B> hyper_0_14::service::Service<http::Request<B>> for HttpReadTimeout<I>
     where
         I: hyper_0_14::service::Service<http::Request<B>, Error = hyper_0_14::Error>,
@@ -895,6 +2474,11 @@ mod timeout_middleware {
                 "expected resp.is_timeout() to be true but it was false, resp == {:?}",
                 resp
             );
+            assert!(
+                resp.is_connect_timeout(),
+                "expected resp.is_connect_timeout() to be true but it was false, resp == {:?}",
+                resp
+            );
             let message = DisplayErrorContext(&resp).to_string();
             let expected =
                 "timeout: error trying to connect: HTTP connect timeout occurred after 1s";
@@ -905,6 +2489,44 @@ mod timeout_middleware {
             assert_elapsed!(now, Duration::from_secs(1));
         }
 
+        #[cfg(feature = "tls-rustls")]
+        #[tokio::test]
+        async fn tls_negotiation_timeout_works() {
+            // Accept TCP connections, but never respond to the TLS handshake
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let _server = tokio::spawn(async move {
+                let mut streams = Vec::new();
+                while let Ok((stream, _)) = listener.accept().await {
+                    streams.push(stream);
+                }
+            });
+
+            let connector_settings = HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .tls_negotiation_timeout(Duration::from_millis(100))
+                .build();
+            let hyper = HyperConnector::builder()
+                .connector_settings(connector_settings)
+                .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+                .build_https()
+                .adapter;
+            let err = hyper
+                .call(HttpRequest::get(format!("https://{addr}")).unwrap())
+                .await
+                .unwrap_err();
+            assert!(
+                err.is_connect_timeout(),
+                "expected err.is_connect_timeout() to be true but it was false, err == {err:?}",
+            );
+            let message = DisplayErrorContext(&err).to_string();
+            let expected = "TLS negotiation timeout occurred after 100ms";
+            assert!(
+                message.contains(expected),
+                "expected '{message}' to contain '{expected}'"
+            );
+        }
+
         #[tokio::test]
         async fn http_read_timeout_works() {
             let tcp_connector = NeverReplies::default();
@@ -927,6 +2549,10 @@ mod timeout_middleware {
                 err.is_timeout(),
                 "expected err.is_timeout() to be true but it was false, err == {err:?}",
             );
+            assert!(
+                !err.is_connect_timeout(),
+                "expected err.is_connect_timeout() to be false but it was true, err == {err:?}",
+            );
             let message = format!("{}", DisplayErrorContext(&err));
             let expected = "timeout: HTTP read timeout occurred after 2s";
             assert!(
@@ -938,10 +2564,361 @@ mod timeout_middleware {
     }
 }
 
+#[cfg(feature = "tls-rustls")]
+mod proxy_middleware {
+    use crate::client::http::proxy::{connect, Proxy, ProxyConfig};
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::http::TcpSettings;
+    use http::Uri;
+    use hyper_0_14::client::connect::{Connected, Connection};
+    use hyper_0_14::service::Service;
+    use std::future::Future;
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use tokio::net::TcpStream;
+
+    /// TCP connector that connects through a proxy when the [`ProxyConfig`] has one for the URL.
+    ///
+    /// Connections that requests are forwarded over, rather than tunneled through, are marked as
+    /// proxied so that hyper sends requests to the proxy in absolute form.
+    ///
+    /// When the proxy has a tunnel keep-alive, it's applied to every connection to the proxy.
+    #[derive(Clone, Debug)]
+    pub(crate) struct ProxyConnector<C> {
+        inner: C,
+        proxy_config: ProxyConfig,
+    }
+
+    impl<C> ProxyConnector<C> {
+        pub(crate) fn new(inner: C, proxy_config: ProxyConfig) -> Self {
+            Self {
+                inner,
+                proxy_config,
+            }
+        }
+    }
+
+    impl<C> Service<Uri> for ProxyConnector<C>
+    where
+        C: Service<Uri, Response = TcpStream> + Clone + Send + 'static,
+        C::Future: Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        type Response = ProxyStream<C::Response>;
+        type Error = BoxError;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx).map_err(|err| err.into())
+        }
+
+        fn call(&mut self, uri: Uri) -> Self::Future {
+            let Some(proxy) = self.proxy_config.proxy_for(&uri).cloned() else {
+                let connecting = self.inner.call(uri);
+                return Box::pin(async move {
+                    Ok(ProxyStream {
+                        inner: connecting.await.map_err(|err| err.into())?,
+                        proxied: false,
+                    })
+                });
+            };
+            // hyper has already waited for the inner connector to be ready for the first connection
+            let mut connecting = Some(self.inner.call(proxy.address()));
+            let inner = self.inner.clone();
+            let connect_proxy = proxy.clone();
+            Box::pin(async move {
+                let proxied = connect::is_forwarded(&proxy, &uri);
+                let stream = connect::connect(&proxy, &uri, move || {
+                    let connecting = connecting.take();
+                    let mut inner = inner.clone();
+                    let proxy = connect_proxy.clone();
+                    async move {
+                        let connecting = match connecting {
+                            Some(connecting) => connecting,
+                            None => {
+                                std::future::poll_fn(|cx| inner.poll_ready(cx))
+                                    .await
+                                    .map_err(|err| err.into())?;
+                                inner.call(proxy.address())
+                            }
+                        };
+                        connect_to_proxy(connecting, &proxy).await
+                    }
+                })
+                .await?;
+                Ok(ProxyStream {
+                    inner: stream,
+                    proxied,
+                })
+            })
+        }
+    }
+
+    /// Waits for a connection to the proxy, and applies the proxy's tunnel keep-alive to it.
+    async fn connect_to_proxy<F, E>(connecting: F, proxy: &Proxy) -> Result<TcpStream, BoxError>
+    where
+        F: Future<Output = Result<TcpStream, E>>,
+        E: Into<BoxError>,
+    {
+        let stream = connecting.await.map_err(|err| err.into())?;
+        if let Some(interval) = proxy.keepalive_interval() {
+            let settings = TcpSettings::builder()
+                .keepalive_time(interval)
+                .keepalive_interval(interval)
+                .build();
+            super::socket_middleware::set_keepalive(
+                &stream,
+                &super::socket_middleware::to_keepalive(&settings),
+            );
+        }
+        Ok(stream)
+    }
+
+    /// A connection that may be to a proxy.
+    #[derive(Debug)]
+    pub(crate) struct ProxyStream<T> {
+        inner: T,
+        proxied: bool,
+    }
+
+    impl<T: Connection> Connection for ProxyStream<T> {
+        fn connected(&self) -> Connected {
+            self.inner.connected().proxy(self.proxied)
+        }
+    }
+
+    impl<T: AsyncRead + Unpin> AsyncRead for ProxyStream<T> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    impl<T: AsyncWrite + Unpin> AsyncWrite for ProxyStream<T> {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write(cx, buf)
+        }
+
+        fn poll_write_vectored(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &[io::IoSlice<'_>],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.inner.is_write_vectored()
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_shutdown(cx)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::client::http::proxy::Proxy;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        /// Accepts a single connection, and returns the request head that the client sent
+        async fn proxy(response: &'static str) -> (Uri, tokio::task::JoinHandle<String>) {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let received = tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                stream.write_all(response.as_bytes()).await.unwrap();
+                String::from_utf8(request).unwrap()
+            });
+            (format!("http://{addr}").parse().unwrap(), received)
+        }
+
+        fn connector(proxy: &Uri) -> ProxyConnector<hyper_0_14::client::HttpConnector> {
+            let proxy = Proxy::new(proxy.to_string())
+                .unwrap()
+                .basic_auth("user", "pass");
+            ProxyConnector::new(
+                hyper_0_14::client::HttpConnector::new(),
+                ProxyConfig::new().all(proxy).no_proxy(["localhost"]),
+            )
+        }
+
+        #[tokio::test]
+        async fn tunnels_https_connections() {
+            let (proxy, received) = proxy("HTTP/1.1 200 Connection established\r\n\r\n").await;
+            let stream = connector(&proxy)
+                .call("https://s3.amazonaws.com/bucket".parse().unwrap())
+                .await
+                .expect("success");
+            assert!(!stream.proxied);
+            assert_eq!(
+                "CONNECT s3.amazonaws.com:443 HTTP/1.1\r\n\
+                 Host: s3.amazonaws.com:443\r\n\
+                 Proxy-Authorization: Basic dXNlcjpwYXNz\r\n\r\n",
+                received.await.unwrap()
+            );
+        }
+
+        #[tokio::test]
+        async fn reports_tunnel_failures() {
+            let (proxy, _received) =
+                proxy("HTTP/1.1 407 Proxy Authentication Required\r\n\r\n").await;
+            let err = connector(&proxy)
+                .call("https://s3.amazonaws.com/".parse().unwrap())
+                .await
+                .expect_err("the proxy rejected the tunnel");
+            assert!(
+                err.to_string().contains("the proxy requires authorization"),
+                "{err}"
+            );
+        }
+
+        #[tokio::test]
+        async fn resends_credentials_on_authorization_challenges() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let received = tokio::spawn(async move {
+                let mut requests = Vec::new();
+                for response in [
+                    "HTTP/1.1 407 Proxy Authentication Required\r\n\
+                     Proxy-Authenticate: Basic realm=\"proxy\"\r\n\
+                     Connection: close\r\n\r\n",
+                    "HTTP/1.1 200 Connection established\r\n\r\n",
+                ] {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let mut request = Vec::new();
+                    let mut buf = [0; 1024];
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        let read = stream.read(&mut buf).await.unwrap();
+                        request.extend_from_slice(&buf[..read]);
+                    }
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                    requests.push(String::from_utf8(request).unwrap());
+                }
+                requests
+            });
+
+            let proxy = Proxy::new(format!("http://user:pass@{addr}"))
+                .unwrap()
+                .preemptive_auth(false);
+            ProxyConnector::new(
+                hyper_0_14::client::HttpConnector::new(),
+                ProxyConfig::new().all(proxy),
+            )
+            .call("https://s3.amazonaws.com/".parse().unwrap())
+            .await
+            .expect("success");
+            let requests = received.await.unwrap();
+            assert!(!requests[0].contains("Proxy-Authorization"), "{requests:?}");
+            assert!(
+                requests[1].contains("Proxy-Authorization: Basic dXNlcjpwYXNz\r\n"),
+                "{requests:?}"
+            );
+        }
+
+        #[tokio::test]
+        async fn applies_tunnel_keepalive() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let proxy = Proxy::new(format!("http://{}", listener.local_addr().unwrap()))
+                .unwrap()
+                .tunnel_keepalive(std::time::Duration::from_secs(42));
+            let stream = ProxyConnector::new(
+                hyper_0_14::client::HttpConnector::new(),
+                ProxyConfig::new().all(proxy),
+            )
+            .call("http://s3.amazonaws.com/".parse().unwrap())
+            .await
+            .expect("success");
+            let socket = socket2::SockRef::from(&stream.inner);
+            assert!(socket.keepalive().unwrap());
+            #[cfg(target_os = "linux")]
+            assert_eq!(
+                std::time::Duration::from_secs(42),
+                socket.keepalive_time().unwrap()
+            );
+        }
+
+        #[tokio::test]
+        async fn connects_through_socks5_proxies() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let proxy = tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut greeting = [0; 3];
+                stream.read_exact(&mut greeting).await.unwrap();
+                assert_eq!([0x05, 0x01, 0x02], greeting);
+                stream.write_all(&[0x05, 0x02]).await.unwrap();
+
+                let mut auth = [0; 11];
+                stream.read_exact(&mut auth).await.unwrap();
+                assert_eq!(b"\x01\x04user\x04pass", &auth);
+                stream.write_all(&[0x01, 0x00]).await.unwrap();
+
+                // The host name is resolved by the proxy
+                let mut connect = [0; 23];
+                stream.read_exact(&mut connect).await.unwrap();
+                assert_eq!(b"\x05\x01\x00\x03\x10s3.amazonaws.com\x01\xbb", &connect);
+                stream
+                    .write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0x04, 0x38])
+                    .await
+                    .unwrap();
+                stream.write_all(b"tunneled").await.unwrap();
+            });
+
+            let proxy_config =
+                ProxyConfig::new().all(Proxy::new(format!("socks5h://user:pass@{addr}")).unwrap());
+            let mut stream =
+                ProxyConnector::new(hyper_0_14::client::HttpConnector::new(), proxy_config)
+                    .call("https://s3.amazonaws.com/".parse().unwrap())
+                    .await
+                    .expect("success");
+            assert!(!stream.connected().is_proxied());
+            let mut tunneled = [0; 8];
+            stream.read_exact(&mut tunneled).await.unwrap();
+            assert_eq!(b"tunneled", &tunneled);
+            proxy.await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn plain_connections_are_marked_as_proxied() {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let proxy: Uri = format!("http://{}", listener.local_addr().unwrap())
+                .parse()
+                .unwrap();
+            let stream = connector(&proxy)
+                .call("http://s3.amazonaws.com/".parse().unwrap())
+                .await
+                .expect("success");
+            assert!(stream.connected().is_proxied());
+        }
+    }
+}
+
 #[cfg(all(test, feature = "test-util"))]
 mod test {
     use super::*;
     use crate::client::http::test_util::NeverTcpConnector;
+    use aws_smithy_runtime_api::client::dns::ResolveDnsError;
     use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
     use http::Uri;
     use hyper_0_14::client::connect::{Connected, Connection};
@@ -964,7 +2941,7 @@ mod test {
             }
         });
 
-        // This configuration should result in 4 separate connectors with different timeout settings
+        // This configuration should result in 5 separate connectors with different settings
         let settings = [
             HttpConnectorSettings::builder()
                 .connect_timeout(Duration::from_secs(3))
@@ -980,6 +2957,18 @@ mod test {
                 .connect_timeout(Duration::from_secs(5))
                 .read_timeout(Duration::from_secs(3))
                 .build(),
+            HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .read_timeout(Duration::from_secs(3))
+                .http2_settings(
+                    Http2Settings::builder()
+                        .prior_knowledge(true)
+                        .initial_stream_window_size(1024 * 1024)
+                        .keep_alive_interval(Duration::from_secs(30))
+                        .keep_alive_timeout(Duration::from_secs(5))
+                        .build(),
+                )
+                .build(),
         ];
 
         // Kick off thousands of parallel tasks that will try to create a connector
@@ -1001,8 +2990,217 @@ mod test {
             handle.await.unwrap();
         }
 
-        // Verify only 4 connectors were created amidst the chaos
-        assert_eq!(4, creation_count.load(Ordering::Relaxed));
+        // Verify only 5 connectors were created amidst the chaos
+        assert_eq!(5, creation_count.load(Ordering::Relaxed));
+
+        // Releasing idle connections drops the cached connectors, so they're created again
+        http_client.release_idle_connections();
+        let _ = http_client.http_connector(&settings[0], &components);
+        assert_eq!(6, creation_count.load(Ordering::Relaxed));
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    #[test]
+    fn custom_trust_stores_skip_invalid_certificates() {
+        let trust_store = TrustStore::empty().with_der_certificate(vec![1, 2, 3]);
+        let tls_config = default_connector::tls_config(Some(&trust_store), None, false);
+        assert_eq!(
+            vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+            tls_config.alpn_protocols
+        );
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    #[tokio::test]
+    async fn binds_to_the_local_address() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri: Uri = format!("http://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let local_address = std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+        let stream = default_connector::http(None, Some(local_address), None)
+            .call(uri)
+            .await
+            .expect("success");
+        let (_, peer) = listener.accept().await.unwrap();
+        assert_eq!(local_address, peer.ip());
+        assert_eq!(stream.local_addr().unwrap(), peer);
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    #[tokio::test]
+    async fn overrides_the_tls_server_name() {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri: Uri = format!("https://{}/", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let mut https = default_connector::https_with(
+            default_connector::http(None, None, None),
+            default_connector::tls_config(Some(&TrustStore::empty()), None, false),
+            Some("s3.amazonaws.com".to_string()),
+        );
+        let _connecting = tokio::spawn(https.call(uri));
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut client_hello = Vec::new();
+        let mut buf = [0; 1024];
+        while !client_hello
+            .windows(b"s3.amazonaws.com".len())
+            .any(|window| window == b"s3.amazonaws.com")
+        {
+            let read = stream.read(&mut buf).await.unwrap();
+            assert_ne!(0, read, "the ClientHello didn't contain the server name");
+            client_hello.extend_from_slice(&buf[..read]);
+        }
+    }
+
+    #[tokio::test]
+    async fn captures_connection_metadata() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Responds to two requests on a single connection
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            for _ in 0..2 {
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let connector = HyperConnector::builder().build(hyper_0_14::client::HttpConnector::new());
+        let mut captured = Vec::new();
+        for _ in 0..2 {
+            let capture = CaptureSmithyConnection::new();
+            let mut request = HttpRequest::get(format!("http://{addr}/")).unwrap();
+            request.add_extension(capture.clone());
+            let mut response = connector.call(request).await.expect("success");
+            // Read the body so that the connection is returned to the pool
+            let _ = hyper_0_14::body::to_bytes(response.take_body()).await;
+            captured.push(capture.get().expect("the connection was captured"));
+        }
+        for metadata in &captured {
+            assert!(!metadata.is_proxied());
+            assert_eq!(Some(addr), metadata.remote_addr());
+            assert!(metadata.local_addr().is_some());
+            assert_eq!(None, metadata.tls_version());
+        }
+        assert_eq!(Some(false), captured[0].is_reused());
+        assert_eq!(Some(true), captured[1].is_reused());
+    }
+
+    async fn read_request(stream: &mut tokio::net::TcpStream) {
+        use tokio::io::AsyncReadExt;
+
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            let read = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..read]);
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_requests_on_stale_pooled_connections() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Closes the first connection instead of responding to its second request, like a server
+        // that closed an idle keep-alive connection would, and then responds on a new connection
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            read_request(&mut stream).await;
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                .await
+                .unwrap();
+            read_request(&mut stream).await;
+            drop(stream);
+            let (mut stream, _) = listener.accept().await.unwrap();
+            read_request(&mut stream).await;
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 8\r\n\r\nreplayed")
+                .await
+                .unwrap();
+        });
+
+        let connector = HyperConnector::builder().build(hyper_0_14::client::HttpConnector::new());
+        let mut response = connector
+            .call(HttpRequest::get(format!("http://{addr}/")).unwrap())
+            .await
+            .expect("success");
+        // Read the body so that the connection is returned to the pool
+        let _ = hyper_0_14::body::to_bytes(response.take_body()).await;
+
+        let capture = CaptureSmithyConnection::new();
+        let mut request = HttpRequest::get(format!("http://{addr}/")).unwrap();
+        request.add_extension(capture.clone());
+        let mut response = connector.call(request).await.expect("replayed");
+        let body = hyper_0_14::body::to_bytes(response.take_body())
+            .await
+            .unwrap();
+        assert_eq!(&b"replayed"[..], &body[..]);
+        assert_eq!(
+            Some(false),
+            capture
+                .get()
+                .expect("the connection was captured")
+                .is_reused()
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_replay_non_idempotent_requests_that_were_sent() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Closes the first connection after reading its second request, which may have been
+        // processed by then, and would respond to a replay on a new connection
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            read_request(&mut stream).await;
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                .await
+                .unwrap();
+            read_request(&mut stream).await;
+            drop(stream);
+            let (mut stream, _) = listener.accept().await.unwrap();
+            read_request(&mut stream).await;
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 8\r\n\r\nreplayed")
+                .await
+                .unwrap();
+        });
+
+        let connector = HyperConnector::builder().build(hyper_0_14::client::HttpConnector::new());
+        let mut response = connector
+            .call(HttpRequest::get(format!("http://{addr}/")).unwrap())
+            .await
+            .expect("success");
+        let _ = hyper_0_14::body::to_bytes(response.take_body()).await;
+
+        let request = http::Request::builder()
+            .method("POST")
+            .uri(format!("http://{addr}/"))
+            .body(SdkBody::from("not idempotent"))
+            .unwrap();
+        connector
+            .call(HttpRequest::try_from(request).unwrap())
+            .await
+            .expect_err("the request must not be replayed");
     }
 
     #[tokio::test]
@@ -1018,6 +3216,40 @@ mod test {
         assert!(err.is_io(), "{:?}", err);
     }
 
+    #[tokio::test]
+    async fn hyper_dns_error() {
+        let connector = FailsDns;
+        let adapter = HyperConnector::builder().build(connector).adapter;
+        let err = adapter
+            .call(HttpRequest::get("https://does-not-resolve.com").unwrap())
+            .await
+            .expect_err("dns failure");
+        assert!(err.is_dns(), "{:?}", err);
+        assert!(!err.is_io(), "{:?}", err);
+    }
+
+    /// A TCP connector that always fails to resolve the host name
+    #[derive(Clone)]
+    struct FailsDns;
+
+    impl hyper_0_14::service::Service<Uri> for FailsDns {
+        type Response = HangupStream;
+        type Error = BoxError;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: Uri) -> Self::Future {
+            std::future::ready(Err(ResolveDnsError::new(Error::new(
+                ErrorKind::Other,
+                "failed to lookup address information",
+            ))
+            .into()))
+        }
+    }
+
     // ---- machinery to make a Hyper connector that responds with an IO Error
     #[derive(Clone)]
     struct HangupStream;
@@ -0,0 +1,2134 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::client::http::connection_poisoning::CaptureSmithyConnection;
+use crate::client::http::hyper_common::{
+    self, extract_smithy_connection, find_source, is_reused, is_stale_connection_error,
+    poison_if_expired, try_clone_request, CapturedConnection, ConnectionDetails, SpawnExecutor,
+};
+use crate::client::http::proxy::{connect, ProxyConfig};
+use crate::client::http::request_limit::RequestLimit;
+use crate::client::http::tls::{CryptoMode, SessionResumption, TlsProvider, TrustStore};
+use aws_smithy_async::future::timeout::{TimedOutError, Timeout};
+use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::rt::spawn::SharedSpawn;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::dns::ResolveDnsError;
+use aws_smithy_runtime_api::client::http::{
+    HappyEyeballs, Http2Settings, HttpClient, HttpConnector, HttpConnectorFuture,
+    HttpConnectorSettings, PoolSettings, SharedHttpClient, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use bytes::Bytes;
+use http_1x::Uri;
+use hyper_rustls_0_27 as hyper_rustls;
+use hyper_util::client::legacy::connect::dns::{GaiAddrs, Name};
+use hyper_util::client::legacy::connect::{
+    capture_connection, CaptureConnection, Connected, Connection, HttpInfo,
+};
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use pin_project_lite::pin_project;
+use rustls_0_23 as rustls;
+use rustls_native_certs_0_8 as rustls_native_certs;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower_service::Service;
+
+#[cfg(feature = "tls-native-tls")]
+mod native_tls_connector;
+#[cfg(feature = "tls-s2n-tls")]
+mod s2n_tls_connector;
+
+pub(super) const CONNECT_TIMEOUT_KIND: &str = "HTTP connect";
+const TLS_NEGOTIATION_TIMEOUT_KIND: &str = "TLS negotiation";
+pub(super) const READ_TIMEOUT_KIND: &str = "HTTP read";
+
+// Creating a `with_native_roots` TLS config is expensive. Cache this so that we
+// don't need to repeatedly incur that cost.
+static TLS_CONFIG: once_cell::sync::Lazy<Arc<rustls::ClientConfig>> =
+    once_cell::sync::Lazy::new(|| {
+        use hyper_rustls::ConfigBuilderExt;
+        let mut tls_config = config_builder(CryptoMode::Ring)
+            .with_native_roots()
+            .expect("failed to load the platform's native root certificates")
+            .with_no_client_auth();
+        tls_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Arc::new(tls_config)
+    });
+
+/// Returns the rustls cryptography provider for the given cryptography mode.
+fn crypto_provider(crypto_mode: CryptoMode) -> rustls::crypto::CryptoProvider {
+    match crypto_mode {
+        CryptoMode::Ring => rustls::crypto::CryptoProvider {
+            cipher_suites: vec![
+                // TLS1.3 suites
+                rustls::crypto::ring::cipher_suite::TLS13_AES_256_GCM_SHA384,
+                rustls::crypto::ring::cipher_suite::TLS13_AES_128_GCM_SHA256,
+                // TLS1.2 suites
+                rustls::crypto::ring::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+                rustls::crypto::ring::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+                rustls::crypto::ring::cipher_suite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+                rustls::crypto::ring::cipher_suite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+                rustls::crypto::ring::cipher_suite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+            ],
+            ..rustls::crypto::ring::default_provider()
+        },
+        #[cfg(feature = "rustls-aws-lc")]
+        CryptoMode::AwsLc => rustls::crypto::CryptoProvider {
+            cipher_suites: vec![
+                // TLS1.3 suites
+                rustls::crypto::aws_lc_rs::cipher_suite::TLS13_AES_256_GCM_SHA384,
+                rustls::crypto::aws_lc_rs::cipher_suite::TLS13_AES_128_GCM_SHA256,
+                // TLS1.2 suites
+                rustls::crypto::aws_lc_rs::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+                rustls::crypto::aws_lc_rs::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+                rustls::crypto::aws_lc_rs::cipher_suite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+                rustls::crypto::aws_lc_rs::cipher_suite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+                rustls::crypto::aws_lc_rs::cipher_suite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+            ],
+            ..rustls::crypto::aws_lc_rs::default_provider()
+        },
+        // The FIPS provider's cipher suites and key exchange groups are all FIPS approved
+        #[cfg(feature = "rustls-aws-lc-fips")]
+        CryptoMode::AwsLcFips => rustls::crypto::default_fips_provider(),
+    }
+}
+
+fn config_builder(
+    crypto_mode: CryptoMode,
+) -> rustls::ConfigBuilder<rustls::ClientConfig, rustls::WantsVerifier> {
+    rustls::ClientConfig::builder_with_provider(Arc::new(crypto_provider(crypto_mode)))
+        .with_safe_default_protocol_versions()
+        .expect("Error with the TLS configuration. Please file a bug report under https://github.com/smithy-lang/smithy-rs/issues.")
+}
+
+/// The TLS implementation that TLS is negotiated with for `https` URLs.
+#[derive(Clone)]
+enum TlsConnector {
+    Rustls(Arc<rustls::ClientConfig>),
+    #[cfg(feature = "tls-native-tls")]
+    NativeTls(tokio_native_tls::TlsConnector),
+    #[cfg(feature = "tls-s2n-tls")]
+    S2nTls(s2n_tls_tokio::TlsConnector),
+}
+
+/// Returns the TLS connector for the given TLS provider, trust store, session resumption, and key
+/// logging.
+fn tls_connector(
+    tls_provider: &TlsProvider,
+    trust_store: Option<&TrustStore>,
+    session_resumption: Option<SessionResumption>,
+    tls_key_log: bool,
+) -> TlsConnector {
+    match tls_provider {
+        TlsProvider::Rustls(crypto_mode) => TlsConnector::Rustls(tls_config(
+            *crypto_mode,
+            trust_store,
+            session_resumption,
+            tls_key_log,
+        )),
+        #[cfg(feature = "tls-native-tls")]
+        TlsProvider::NativeTls => {
+            if tls_key_log {
+                tracing::warn!(
+                    "TLS key logging isn't supported by native-tls, so no secrets are logged"
+                );
+            }
+            TlsConnector::NativeTls(native_tls_connector::tls_connector(trust_store))
+        }
+        #[cfg(feature = "tls-s2n-tls")]
+        TlsProvider::S2nTls => {
+            if tls_key_log {
+                tracing::warn!(
+                    "TLS key logging isn't supported by s2n-tls, so no secrets are logged"
+                );
+            }
+            TlsConnector::S2nTls(s2n_tls_connector::tls_connector(trust_store))
+        }
+    }
+}
+
+/// Returns the rustls configuration for the given cryptography mode, trust store, session
+/// resumption, and key logging.
+///
+/// The platform's native root certificates are trusted unless a trust store is given.
+/// Certificates that can't be parsed are skipped, so they can only make the trust store smaller.
+fn tls_config(
+    crypto_mode: CryptoMode,
+    trust_store: Option<&TrustStore>,
+    session_resumption: Option<SessionResumption>,
+    tls_key_log: bool,
+) -> Arc<rustls::ClientConfig> {
+    if trust_store.is_none()
+        && session_resumption.is_none()
+        && !tls_key_log
+        && crypto_mode == CryptoMode::Ring
+    {
+        return TLS_CONFIG.clone();
+    }
+    let default_trust_store = TrustStore::default();
+    let trust_store = trust_store.unwrap_or(&default_trust_store);
+    let mut roots = rustls::RootCertStore::empty();
+    if trust_store.native_roots() {
+        let native = rustls_native_certs::load_native_certs();
+        for err in &native.errors {
+            tracing::warn!(err = %err, "failed to load some of the platform's native root certificates");
+        }
+        roots.add_parsable_certificates(native.certs);
+    }
+    let (_, invalid) = roots.add_parsable_certificates(
+        trust_store
+            .certificates()
+            .map(|certificate| rustls::pki_types::CertificateDer::from(certificate.to_vec())),
+    );
+    if invalid > 0 {
+        tracing::warn!(
+            invalid = invalid,
+            "some of the trust store's certificates are invalid and were skipped"
+        );
+    }
+    let mut tls_config = config_builder(crypto_mode)
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    tls_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let session_resumption = session_resumption.unwrap_or_default();
+    tls_config.resumption = if session_resumption.is_enabled() {
+        rustls::client::Resumption::in_memory_sessions(session_resumption.max_sessions())
+    } else {
+        rustls::client::Resumption::disabled()
+    };
+    if tls_key_log {
+        tracing::warn!(
+            "TLS key logging is enabled, secrets are written to the file in `SSLKEYLOGFILE`"
+        );
+        tls_config.key_log = Arc::new(rustls::KeyLogFile::new());
+    }
+    Arc::new(tls_config)
+}
+
+/// Given `HttpConnectorSettings` and an `SharedAsyncSleep`, create a `SharedHttpConnector` that uses hyper 1.x and rustls.
+pub fn default_connector(
+    settings: &HttpConnectorSettings,
+    sleep: Option<SharedAsyncSleep>,
+) -> Option<SharedHttpConnector> {
+    tracing::trace!(settings = ?settings, sleep = ?sleep, "creating a new default hyper 1.x connector");
+    Some(SharedHttpConnector::new(HyperConnector::new(
+        settings,
+        sleep,
+        None,
+        TlsConnector::Rustls(TLS_CONFIG.clone()),
+        None,
+        &LocalBinding::default(),
+        None,
+    )))
+}
+
+/// Creates a HTTPS client that uses hyper 1.x and rustls.
+pub fn default_client() -> Option<SharedHttpClient> {
+    tracing::trace!("creating a new default hyper 1.x client");
+    Some(HyperClientBuilder::new().build_https())
+}
+
+/// Builder for a [`HttpClient`] implementation that uses hyper 1.x.
+///
+/// The client pools connections, supports both HTTP/1.1 and HTTP/2 (negotiated with ALPN), and
+/// uses rustls for TLS with the platform's native root certificates, unless another
+/// [`TlsProvider`] is configured.
+///
+/// # Examples
+///
+/// ```no_run,ignore
+/// use aws_smithy_runtime::client::http::hyper_1::HyperClientBuilder;
+///
+/// let http_client = HyperClientBuilder::new().build_https();
+///
+/// // This client can then be given to a generated service Config
+/// let config = my_service_client::Config::builder()
+///     .http_client(http_client)
+///     .build();
+/// let client = my_service_client::Client::from_conf(config);
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct HyperClientBuilder {
+    trust_store: Option<TrustStore>,
+    tls_provider: Option<TlsProvider>,
+    session_resumption: Option<SessionResumption>,
+    tls_key_log: bool,
+    tls_server_name: Option<String>,
+    local_binding: LocalBinding,
+    proxy_config: Option<ProxyConfig>,
+}
+
+impl HyperClientBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configure the root certificates that server certificates are verified against.
+    ///
+    /// When no trust store is set, the platform's native root certificates are trusted.
+    pub fn trust_store(mut self, trust_store: TrustStore) -> Self {
+        self.trust_store = Some(trust_store);
+        self
+    }
+
+    /// Configure the root certificates that server certificates are verified against.
+    ///
+    /// When no trust store is set, the platform's native root certificates are trusted.
+    pub fn set_trust_store(&mut self, trust_store: Option<TrustStore>) -> &mut Self {
+        self.trust_store = trust_store;
+        self
+    }
+
+    /// Configure the TLS implementation that's used to connect to `https` URLs.
+    ///
+    /// Defaults to rustls with the ring cryptography provider. With the `tls-native-tls`
+    /// feature, [`TlsProvider::NativeTls`] uses the platform's TLS implementation instead, and
+    /// with the `tls-s2n-tls` feature, [`TlsProvider::S2nTls`] uses s2n-tls.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "rustls-aws-lc-fips")]
+    /// # fn example() {
+    /// use aws_smithy_runtime::client::http::hyper_1::HyperClientBuilder;
+    /// use aws_smithy_runtime::client::http::tls::{CryptoMode, TlsProvider};
+    ///
+    /// // Only negotiate TLS with FIPS-approved cryptography
+    /// let http_client = HyperClientBuilder::new()
+    ///     .tls_provider(TlsProvider::Rustls(CryptoMode::AwsLcFips))
+    ///     .build_https();
+    /// # }
+    /// ```
+    pub fn tls_provider(mut self, tls_provider: TlsProvider) -> Self {
+        self.tls_provider = Some(tls_provider);
+        self
+    }
+
+    /// Configure the TLS implementation that's used to connect to `https` URLs.
+    ///
+    /// Defaults to rustls with the ring cryptography provider.
+    pub fn set_tls_provider(&mut self, tls_provider: Option<TlsProvider>) -> &mut Self {
+        self.tls_provider = tls_provider;
+        self
+    }
+
+    /// Configure how TLS sessions are resumed when reconnecting to a server.
+    ///
+    /// When this isn't set, up to 256 sessions are cached in memory.
+    pub fn session_resumption(mut self, session_resumption: SessionResumption) -> Self {
+        self.session_resumption = Some(session_resumption);
+        self
+    }
+
+    /// Configure how TLS sessions are resumed when reconnecting to a server.
+    ///
+    /// When this isn't set, up to 256 sessions are cached in memory.
+    pub fn set_session_resumption(
+        &mut self,
+        session_resumption: Option<SessionResumption>,
+    ) -> &mut Self {
+        self.session_resumption = session_resumption;
+        self
+    }
+
+    /// Configure whether TLS secrets are logged to the file in the `SSLKEYLOGFILE` environment
+    /// variable, so that captured traffic can be decrypted, e.g. with Wireshark.
+    ///
+    /// **Anyone who can read the file can decrypt the traffic, so this should only be enabled
+    /// when debugging against test endpoints.** Nothing is logged when the environment variable
+    /// isn't set.
+    #[cfg(feature = "tls-key-log")]
+    pub fn tls_key_log(mut self, enabled: bool) -> Self {
+        self.tls_key_log = enabled;
+        self
+    }
+
+    /// Configure whether TLS secrets are logged to the file in the `SSLKEYLOGFILE` environment
+    /// variable, so that captured traffic can be decrypted, e.g. with Wireshark.
+    ///
+    /// **Anyone who can read the file can decrypt the traffic, so this should only be enabled
+    /// when debugging against test endpoints.** Nothing is logged when the environment variable
+    /// isn't set.
+    #[cfg(feature = "tls-key-log")]
+    pub fn set_tls_key_log(&mut self, enabled: bool) -> &mut Self {
+        self.tls_key_log = enabled;
+        self
+    }
+
+    /// Configure the server name that's sent with SNI and that server certificates are verified
+    /// against, instead of the host of the URL.
+    ///
+    /// This allows requests to be sent to a TLS-passthrough (L4) proxy, or to a staging IP
+    /// address, while still verifying the production certificate. Only the TLS handshake is
+    /// affected; the `Host` header is signed with the request, so it always has the host of the
+    /// URL. Connections fail if the server name isn't a valid DNS name or IP address.
+    pub fn tls_server_name(mut self, tls_server_name: impl Into<String>) -> Self {
+        self.tls_server_name = Some(tls_server_name.into());
+        self
+    }
+
+    /// Configure the server name that's sent with SNI and that server certificates are verified
+    /// against, instead of the host of the URL.
+    ///
+    /// This allows requests to be sent to a TLS-passthrough (L4) proxy, or to a staging IP
+    /// address, while still verifying the production certificate. Only the TLS handshake is
+    /// affected; the `Host` header is signed with the request, so it always has the host of the
+    /// URL. Connections fail if the server name isn't a valid DNS name or IP address.
+    pub fn set_tls_server_name(&mut self, tls_server_name: Option<String>) -> &mut Self {
+        self.tls_server_name = tls_server_name;
+        self
+    }
+
+    /// Configure the local address that outgoing connections are bound to.
+    ///
+    /// On hosts with multiple network interfaces, this pins traffic to the interface with the
+    /// given address. Connections to hosts that only have addresses of the other IP version
+    /// will fail.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use aws_smithy_runtime::client::http::hyper_1::HyperClientBuilder;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let http_client = HyperClientBuilder::new()
+    ///     .local_address(IpAddr::V4(Ipv4Addr::new(10, 0, 1, 15)))
+    ///     .build_https();
+    /// ```
+    pub fn local_address(mut self, local_address: IpAddr) -> Self {
+        self.local_binding.local_address = Some(local_address);
+        self
+    }
+
+    /// Configure the local address that outgoing connections are bound to.
+    ///
+    /// On hosts with multiple network interfaces, this pins traffic to the interface with the
+    /// given address. Connections to hosts that only have addresses of the other IP version
+    /// will fail.
+    pub fn set_local_address(&mut self, local_address: Option<IpAddr>) -> &mut Self {
+        self.local_binding.local_address = local_address;
+        self
+    }
+
+    /// Configure the network interface that outgoing connections are bound to, like `eth1`.
+    ///
+    /// This uses `SO_BINDTODEVICE`, which usually requires the `CAP_NET_RAW` capability. It's
+    /// only supported on Linux, Android, and Fuchsia.
+    #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+    pub fn interface(mut self, interface: impl Into<String>) -> Self {
+        self.local_binding.interface = Some(interface.into());
+        self
+    }
+
+    /// Configure the network interface that outgoing connections are bound to, like `eth1`.
+    ///
+    /// This uses `SO_BINDTODEVICE`, which usually requires the `CAP_NET_RAW` capability. It's
+    /// only supported on Linux, Android, and Fuchsia.
+    #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+    pub fn set_interface(&mut self, interface: Option<String>) -> &mut Self {
+        self.local_binding.interface = interface;
+        self
+    }
+
+    /// Configure the proxies that requests are sent through.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use aws_smithy_runtime::client::http::hyper_1::HyperClientBuilder;
+    /// use aws_smithy_runtime::client::http::proxy::ProxyConfig;
+    ///
+    /// // Use the proxies from the `HTTP_PROXY`, `HTTPS_PROXY`, and `NO_PROXY` environment variables
+    /// let http_client = HyperClientBuilder::new()
+    ///     .proxy_config(ProxyConfig::from_env())
+    ///     .build_https();
+    /// ```
+    pub fn proxy_config(mut self, proxy_config: ProxyConfig) -> Self {
+        self.proxy_config = Some(proxy_config);
+        self
+    }
+
+    /// Configure the proxies that requests are sent through.
+    pub fn set_proxy_config(&mut self, proxy_config: Option<ProxyConfig>) -> &mut Self {
+        self.proxy_config = proxy_config;
+        self
+    }
+
+    /// Create a [`SharedHttpClient`] that supports both `http` and `https` URLs.
+    pub fn build_https(self) -> SharedHttpClient {
+        let tls_provider = self.tls_provider.unwrap_or_default();
+        SharedHttpClient::new(HyperClient {
+            connector_cache: RwLock::new(HashMap::new()),
+            tls_connector: tls_connector(
+                &tls_provider,
+                self.trust_store.as_ref(),
+                self.session_resumption,
+                self.tls_key_log,
+            ),
+            tls_server_name: self.tls_server_name,
+            local_binding: self.local_binding,
+            proxy_config: self.proxy_config,
+        })
+    }
+}
+
+/// The local address and interface that outgoing connections are bound to.
+#[derive(Clone, Debug, Default)]
+struct LocalBinding {
+    local_address: Option<IpAddr>,
+    #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+    interface: Option<String>,
+}
+
+impl LocalBinding {
+    fn apply(&self, tcp: &mut TcpConnector) {
+        tcp.set_local_address(self.local_address);
+        #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+        if let Some(interface) = &self.interface {
+            tcp.set_interface(interface.as_str());
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct CacheKey {
+    connect_timeout: Option<Duration>,
+    tls_negotiation_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    http2: Option<Http2Settings>,
+    happy_eyeballs: Option<HappyEyeballs>,
+    pool: Option<PoolSettings>,
+}
+
+impl From<&HttpConnectorSettings> for CacheKey {
+    fn from(value: &HttpConnectorSettings) -> Self {
+        Self {
+            connect_timeout: value.connect_timeout(),
+            tls_negotiation_timeout: value.tls_negotiation_timeout(),
+            read_timeout: value.read_timeout(),
+            http2: value.http2_settings().cloned(),
+            happy_eyeballs: value.happy_eyeballs(),
+            pool: value.pool_settings().cloned(),
+        }
+    }
+}
+
+struct HyperClient {
+    connector_cache: RwLock<HashMap<CacheKey, SharedHttpConnector>>,
+    tls_connector: TlsConnector,
+    tls_server_name: Option<String>,
+    local_binding: LocalBinding,
+    proxy_config: Option<ProxyConfig>,
+}
+
+impl fmt::Debug for HyperClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyperClient")
+            .field("connector_cache", &self.connector_cache)
+            .field("tls_server_name", &self.tls_server_name)
+            .field("local_binding", &self.local_binding)
+            .field("proxy_config", &self.proxy_config)
+            .finish()
+    }
+}
+
+impl HttpClient for HyperClient {
+    fn http_connector(
+        &self,
+        settings: &HttpConnectorSettings,
+        components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        let key = CacheKey::from(settings);
+        let mut connector = self.connector_cache.read().unwrap().get(&key).cloned();
+        if connector.is_none() {
+            let mut cache = self.connector_cache.write().unwrap();
+            // Short-circuit if another thread already wrote a connector to the cache for this key
+            if !cache.contains_key(&key) {
+                let connector = SharedHttpConnector::new(HyperConnector::new(
+                    settings,
+                    components.sleep_impl(),
+                    components.spawn(),
+                    self.tls_connector.clone(),
+                    self.tls_server_name.as_deref(),
+                    &self.local_binding,
+                    self.proxy_config.clone(),
+                ));
+                cache.insert(key.clone(), connector);
+            }
+            connector = cache.get(&key).cloned();
+        }
+
+        connector.expect("cache populated above")
+    }
+
+    fn release_idle_connections(&self) {
+        // Each connector owns its own connection pool, and dropping the pool closes its idle
+        // connections. Requests that are in flight hold on to their connector until they complete.
+        self.connector_cache.write().unwrap().clear();
+    }
+}
+
+/// [`HttpConnector`] that uses hyper 1.x to make HTTP requests.
+///
+/// This connector implements connect, TLS negotiation, and read timeouts.
+struct HyperConnector {
+    client: Client<Connector, Http1Body>,
+    read_timeout: Option<(SharedAsyncSleep, Duration)>,
+    request_limit: Option<RequestLimit>,
+    proxy_config: Option<ProxyConfig>,
+    max_connection_lifetime: Option<Duration>,
+}
+
+impl fmt::Debug for HyperConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyperConnector")
+            .field("client", &"** hyper client **")
+            .field("read_timeout", &self.read_timeout)
+            .field("request_limit", &self.request_limit)
+            .field("proxy_config", &self.proxy_config)
+            .field("max_connection_lifetime", &self.max_connection_lifetime)
+            .finish()
+    }
+}
+
+impl HyperConnector {
+    fn new(
+        settings: &HttpConnectorSettings,
+        sleep_impl: Option<SharedAsyncSleep>,
+        spawn: Option<SharedSpawn>,
+        tls_connector: TlsConnector,
+        tls_server_name: Option<&str>,
+        local_binding: &LocalBinding,
+        proxy_config: Option<ProxyConfig>,
+    ) -> Self {
+        let sleep_impl = sleep_impl.or_else(default_async_sleep);
+        let timeouts = match (
+            settings.connect_timeout(),
+            settings.tls_negotiation_timeout(),
+        ) {
+            (None, None) => None,
+            (connect, tls_negotiation) => Some(ConnectTimeouts {
+                sleep_impl: sleep_impl
+                    .clone()
+                    .expect("a sleep impl must be provided in order to have a connect timeout"),
+                connect,
+                tls_negotiation,
+            }),
+        };
+        let mut tcp = TcpConnector::new_with_resolver(GaiResolver::new());
+        tcp.enforce_http(false);
+        local_binding.apply(&mut tcp);
+        if let Some(happy_eyeballs) = settings.happy_eyeballs() {
+            tcp.set_happy_eyeballs_timeout(happy_eyeballs.delay());
+        }
+        if let Some(tcp_settings) = settings.tcp_settings() {
+            if tcp_settings.keepalive_enabled() {
+                tcp.set_keepalive(tcp_settings.keepalive_time());
+                tcp.set_keepalive_interval(tcp_settings.keepalive_interval());
+                tcp.set_keepalive_retries(tcp_settings.keepalive_retries());
+            }
+            if let Some(nodelay) = tcp_settings.nodelay() {
+                tcp.set_nodelay(nodelay);
+            }
+            tcp.set_send_buffer_size(tcp_settings.send_buffer_size());
+            tcp.set_recv_buffer_size(tcp_settings.recv_buffer_size());
+        }
+        // The tasks that drive connections are spawned onto Tokio unless a spawn is configured
+        let mut builder = match spawn {
+            Some(spawn) => Client::builder(SpawnExecutor(spawn)),
+            None => Client::builder(TokioExecutor::new()),
+        };
+        builder
+            .pool_timer(TokioTimer::new())
+            .timer(TokioTimer::new());
+        // hyper doesn't limit the number of connections, their lifetime, or the number of requests
+        // in flight, so those settings are implemented by `ConnectionLimit`, `poison_if_expired`,
+        // and `RequestLimit`.
+        if let Some(pool) = settings.pool_settings() {
+            if let Some(max_idle) = pool.max_idle_per_host() {
+                builder.pool_max_idle_per_host(max_idle);
+            }
+            if let Some(timeout) = pool.pool_idle_timeout() {
+                builder.pool_idle_timeout(timeout);
+            }
+        }
+        if let Some(http2) = settings.http2_settings() {
+            builder
+                .http2_only(http2.prior_knowledge())
+                .http2_initial_stream_window_size(http2.initial_stream_window_size())
+                .http2_initial_connection_window_size(http2.initial_connection_window_size())
+                // This only limits the streams until the server advertises its own limit, so
+                // the maximum is enforced by `RequestLimit` as well
+                .http2_initial_max_send_streams(
+                    http2.max_concurrent_streams().map(|max| max as usize),
+                )
+                .http2_adaptive_window(http2.adaptive_window());
+            if let Some(interval) = http2.keep_alive_interval() {
+                builder
+                    .http2_keep_alive_interval(interval)
+                    .http2_keep_alive_while_idle(true);
+            }
+            if let Some(timeout) = http2.keep_alive_timeout() {
+                builder.http2_keep_alive_timeout(timeout);
+            }
+        }
+        let client = builder.build(Connector {
+            tcp,
+            tls_connector,
+            tls_server_name: tls_server_name.map(str::to_string),
+            timeouts,
+            proxy_config: proxy_config.clone(),
+            connection_limit: ConnectionLimit::new(
+                settings
+                    .pool_settings()
+                    .and_then(PoolSettings::max_connections_per_host),
+            ),
+        });
+        let request_limit = RequestLimit::from_settings(
+            settings.pool_settings(),
+            settings.http2_settings(),
+            sleep_impl.clone(),
+        );
+        let read_timeout = settings.read_timeout().map(|duration| {
+            (
+                sleep_impl.expect("a sleep impl must be provided in order to have a read timeout"),
+                duration,
+            )
+        });
+        Self {
+            client,
+            read_timeout,
+            request_limit,
+            proxy_config,
+            max_connection_lifetime: settings
+                .pool_settings()
+                .and_then(PoolSettings::max_connection_lifetime),
+        }
+    }
+}
+
+impl HttpConnector for HyperConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        match &self.request_limit {
+            Some(request_limit) => {
+                let uri = request.uri().to_string();
+                // The request isn't sent, and its read timeout doesn't start, until it's polled
+                let sending = self.send(request);
+                request_limit.limit(&uri, move || sending)
+            }
+            None => self.send(request),
+        }
+    }
+}
+
+impl HyperConnector {
+    fn send(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let mut request = match request.try_into_http02x() {
+            Ok(request) => request,
+            Err(err) => {
+                return HttpConnectorFuture::ready(Err(ConnectorError::other(err.into(), None)));
+            }
+        };
+        // Only requests that are forwarded by a proxy, rather than tunneled, authorize with it
+        let proxy_authorization = self
+            .proxy_config
+            .as_ref()
+            .and_then(|proxy_config| proxy_config.proxy_for(request.uri()))
+            .filter(|proxy| connect::is_forwarded(proxy, request.uri()))
+            .and_then(|proxy| proxy.authorization_header());
+        if let Some(authorization) = proxy_authorization {
+            request
+                .headers_mut()
+                .entry(http::header::PROXY_AUTHORIZATION)
+                .or_insert_with(|| authorization.clone());
+        }
+        // Kept so that the request can be replayed if it's sent on a stale pooled connection
+        let replay = try_clone_request(&request);
+        let client = self.client.clone();
+        let send = move |request: http::Request<SdkBody>| {
+            let capture_smithy_connection = request
+                .extensions()
+                .get::<CaptureSmithyConnection>()
+                .cloned();
+            let mut request = to_http1x_request(request)?;
+            let capture_connection = capture_connection(&mut request);
+            // Compared with when the connection was established, which uses the monotonic clock
+            #[allow(clippy::disallowed_methods)]
+            let sent_at = Instant::now();
+            if let Some(capture_smithy_connection) = capture_smithy_connection {
+                let capture_connection = capture_connection.clone();
+                capture_smithy_connection.set_connection_retriever(move || {
+                    extract_smithy_connection(&capture_connection, sent_at)
+                });
+            }
+            Ok::<_, BoxError>((client.request(request), capture_connection, sent_at))
+        };
+        let (response, used_connection, sent_at) = match send(request) {
+            Ok(sent) => sent,
+            Err(err) => return HttpConnectorFuture::ready(Err(ConnectorError::other(err, None))),
+        };
+        let max_connection_lifetime = self.max_connection_lifetime;
+        let receiving = async move {
+            let (response, used_connection) = match (response.await, replay) {
+                (Err(err), Some(replay))
+                    if is_reused(&used_connection, sent_at)
+                        && is_stale_connection_error(&err, replay.method()) =>
+                {
+                    tracing::debug!(
+                        err = %DisplayErrorContext(&err),
+                        "pooled connection was closed before a response was received; replaying the request on another connection"
+                    );
+                    let (response, replay_connection, _) =
+                        send(replay).map_err(|err| ConnectorError::other(err, None))?;
+                    (response.await, replay_connection)
+                }
+                (response, _) => (response, used_connection),
+            };
+            let response = response.map_err(to_connector_error)?;
+            if let Some(max_connection_lifetime) = max_connection_lifetime {
+                poison_if_expired(&used_connection, max_connection_lifetime);
+            }
+            Ok(response)
+        };
+        let read_timeout = self.read_timeout.clone();
+        HttpConnectorFuture::new(async move {
+            // The read timeout includes the replay, if there is one
+            let response = match read_timeout {
+                Some((sleep, duration)) => {
+                    match Timeout::new(receiving, sleep.sleep(duration)).await {
+                        Ok(response) => response,
+                        Err(_timeout) => {
+                            return Err(ConnectorError::timeout(
+                                HttpTimeoutError {
+                                    kind: READ_TIMEOUT_KIND,
+                                    duration,
+                                }
+                                .into(),
+                            ))
+                        }
+                    }
+                }
+                None => receiving.await,
+            }?;
+            to_smithy_response(response).map_err(|err| ConnectorError::other(err, None))
+        })
+    }
+}
+
+impl CapturedConnection for CaptureConnection {
+    fn details(&self) -> Option<ConnectionDetails> {
+        let metadata = self.connection_metadata();
+        let conn = metadata.as_ref()?;
+        let mut extensions = http_1x::Extensions::new();
+        conn.get_extras(&mut extensions);
+        let http_info = extensions.get::<HttpInfo>();
+        let connection_info = extensions.get::<ConnectionInfo>();
+        Some(ConnectionDetails {
+            proxied: conn.is_proxied(),
+            remote_addr: http_info.map(|info| info.remote_addr()),
+            local_addr: http_info.map(|info| info.local_addr()),
+            connected_at: connection_info.map(|info| info.connected_at),
+            tls_version: connection_info.and_then(|info| info.tls_version),
+            alpn_protocol: connection_info.and_then(|info| info.alpn_protocol.clone()),
+            connect_duration: connection_info.and_then(|info| info.connect_duration),
+            tls_handshake_duration: connection_info.and_then(|info| info.tls_handshake_duration),
+        })
+    }
+
+    fn poison(&self) {
+        match self.connection_metadata().as_ref() {
+            Some(conn) => conn.poison(),
+            None => tracing::trace!("no connection existed to poison"),
+        }
+    }
+}
+
+pub(super) fn to_http1x_request(
+    request: http::Request<SdkBody>,
+) -> Result<http_1x::Request<Http1Body>, BoxError> {
+    let (parts, body) = request.into_parts();
+    let mut builder = http_1x::Request::builder()
+        .method(parts.method.as_str())
+        .uri(parts.uri.to_string());
+    for (name, value) in parts.headers.iter() {
+        builder = builder.header(name.as_str(), value.as_bytes());
+    }
+    Ok(builder.body(Http1Body::new(body))?)
+}
+
+pub(super) fn to_smithy_response<B>(
+    response: http_1x::Response<B>,
+) -> Result<HttpResponse, BoxError>
+where
+    B: http_body_1x::Body<Data = Bytes> + Send + Sync + 'static,
+    B::Error: Into<BoxError>,
+{
+    let (parts, body) = response.into_parts();
+    let mut builder = http::Response::builder().status(parts.status.as_u16());
+    for (name, value) in parts.headers.iter() {
+        builder = builder.header(name.as_str(), value.as_bytes());
+    }
+    let response = builder.body(SdkBody::from_body_0_4(Http0Body::new(body)))?;
+    Ok(HttpResponse::try_from(response)?)
+}
+
+fn to_http0x_uri(uri: &Uri) -> Option<http::Uri> {
+    uri.to_string().parse().ok()
+}
+
+fn to_http0x_headers(headers: &http_1x::HeaderMap) -> Result<http::HeaderMap, BoxError> {
+    let mut converted = http::HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers.iter() {
+        converted.append(
+            http::HeaderName::from_bytes(name.as_str().as_bytes())?,
+            http::HeaderValue::from_bytes(value.as_bytes())?,
+        );
+    }
+    Ok(converted)
+}
+
+fn to_http1x_headers(headers: &http::HeaderMap) -> Result<http_1x::HeaderMap, BoxError> {
+    let mut converted = http_1x::HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers.iter() {
+        converted.append(
+            http_1x::HeaderName::from_bytes(name.as_str().as_bytes())?,
+            http_1x::HeaderValue::from_bytes(value.as_bytes())?,
+        );
+    }
+    Ok(converted)
+}
+
+/// Convert a hyper 1.x client error into a [`ConnectorError`]
+fn to_connector_error(err: hyper_util::client::legacy::Error) -> ConnectorError {
+    match find_source::<HttpTimeoutError>(&err) {
+        Some(timeout) if timeout.is_connect() => ConnectorError::connect_timeout(err.into()),
+        _ => {
+            let is_connect = err.is_connect();
+            hyper_common::to_connector_error(err, is_connect)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct HttpTimeoutError {
+    pub(super) kind: &'static str,
+    pub(super) duration: Duration,
+}
+
+impl HttpTimeoutError {
+    /// Returns true if this timeout occurred while establishing a connection.
+    pub(super) fn is_connect(&self) -> bool {
+        self.kind == CONNECT_TIMEOUT_KIND || self.kind == TLS_NEGOTIATION_TIMEOUT_KIND
+    }
+}
+
+impl fmt::Display for HttpTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} timeout occurred after {:?}",
+            self.kind, self.duration
+        )
+    }
+}
+
+impl Error for HttpTimeoutError {
+    // Returning a `TimedOutError` as the source makes this recognizable as a timeout-related error.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&TimedOutError)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ConnectTimeouts {
+    sleep_impl: SharedAsyncSleep,
+    connect: Option<Duration>,
+    tls_negotiation: Option<Duration>,
+}
+
+/// Establishes the TCP connections that [`Connector`] negotiates TLS on.
+type TcpConnector = hyper_util::client::legacy::connect::HttpConnector<GaiResolver>;
+
+/// Resolves names with `getaddrinfo`, reporting failures as [`ResolveDnsError`]s so that they can
+/// be told apart from failures to connect.
+#[derive(Clone, Debug)]
+struct GaiResolver(hyper_util::client::legacy::connect::dns::GaiResolver);
+
+impl GaiResolver {
+    fn new() -> Self {
+        Self(hyper_util::client::legacy::connect::dns::GaiResolver::new())
+    }
+}
+
+impl Service<Name> for GaiResolver {
+    type Response = GaiAddrs;
+    type Error = ResolveDnsError;
+    type Future = Pin<Box<dyn Future<Output = Result<GaiAddrs, ResolveDnsError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx).map_err(ResolveDnsError::new)
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let resolving = self.0.call(name);
+        Box::pin(async move { resolving.await.map_err(ResolveDnsError::new) })
+    }
+}
+
+/// Connector that establishes TCP connections, negotiates TLS for `https` URLs, and applies the
+/// connect and TLS negotiation timeouts.
+///
+/// When there's no TLS negotiation timeout, the connect timeout covers the TLS handshake as well.
+#[derive(Clone)]
+struct Connector {
+    tcp: TcpConnector,
+    tls_connector: TlsConnector,
+    tls_server_name: Option<String>,
+    timeouts: Option<ConnectTimeouts>,
+    proxy_config: Option<ProxyConfig>,
+    connection_limit: ConnectionLimit,
+}
+
+impl Service<Uri> for Connector {
+    type Response = TrackedStream;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        // Connection timings are durations, so they're measured with the monotonic clock
+        #[allow(clippy::disallowed_methods)]
+        let started_at = Instant::now();
+        let tcp_connected_at = Arc::new(Mutex::new(None));
+        // Requests that are forwarded by a proxy are sent to it in absolute form
+        let proxied = self
+            .proxy_config
+            .as_ref()
+            .zip(to_http0x_uri(&uri))
+            .and_then(|(proxy_config, uri)| {
+                proxy_config
+                    .proxy_for(&uri)
+                    .map(|proxy| connect::is_forwarded(proxy, &uri))
+            })
+            .unwrap_or_default();
+        let permit = self.connection_limit.acquire(&uri);
+        let connecting = self.connect(uri, tcp_connected_at.clone());
+        // Waiting for a connection to the host to close counts against the connect timeout
+        let connecting = self.with_timeouts(
+            Box::pin(async move {
+                let permit = permit.await;
+                Ok((connecting.await?, permit))
+            }),
+            tcp_connected_at.clone(),
+        );
+        Box::pin(async move {
+            let (stream, permit) = connecting.await?;
+            let tcp_connected_at = *tcp_connected_at.lock().unwrap();
+            Ok(TrackedStream::new(
+                stream,
+                started_at,
+                tcp_connected_at,
+                proxied,
+                permit,
+            ))
+        })
+    }
+}
+
+/// A connection, with TLS negotiated by the configured TLS implementation for `https` URLs.
+// rustls connections are the larger and the more common, so they aren't boxed
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum HttpsStream {
+    Rustls(hyper_rustls::MaybeHttpsStream<TokioIo<TcpStream>>),
+    #[cfg(feature = "tls-native-tls")]
+    NativeTls(native_tls_connector::MaybeTlsStream),
+    #[cfg(feature = "tls-s2n-tls")]
+    S2nTls(s2n_tls_connector::MaybeTlsStream),
+}
+
+impl HttpsStream {
+    /// Returns true if TLS was negotiated on this connection.
+    fn is_https(&self) -> bool {
+        match self {
+            Self::Rustls(stream) => matches!(stream, hyper_rustls::MaybeHttpsStream::Https(_)),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => stream.is_https(),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => stream.is_https(),
+        }
+    }
+
+    /// Returns the negotiated TLS version and ALPN protocol, if TLS was negotiated.
+    fn tls_parameters(&self) -> (Option<&'static str>, Option<String>) {
+        match self {
+            Self::Rustls(hyper_rustls::MaybeHttpsStream::Https(tls)) => {
+                let connection = tls.inner().get_ref().1;
+                let tls_version = match connection.protocol_version() {
+                    Some(rustls::ProtocolVersion::TLSv1_2) => Some("TLSv1.2"),
+                    Some(rustls::ProtocolVersion::TLSv1_3) => Some("TLSv1.3"),
+                    _ => None,
+                };
+                let alpn_protocol = connection
+                    .alpn_protocol()
+                    .map(|protocol| String::from_utf8_lossy(protocol).into_owned());
+                (tls_version, alpn_protocol)
+            }
+            Self::Rustls(hyper_rustls::MaybeHttpsStream::Http(_)) => (None, None),
+            // native-tls doesn't expose the negotiated TLS version
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => (None, stream.alpn_protocol()),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => (stream.tls_version(), stream.alpn_protocol()),
+        }
+    }
+}
+
+impl Connection for HttpsStream {
+    fn connected(&self) -> Connected {
+        match self {
+            Self::Rustls(stream) => stream.connected(),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => stream.connected(),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => stream.connected(),
+        }
+    }
+}
+
+impl hyper_1::rt::Read for HttpsStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper_1::rt::ReadBufCursor<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Rustls(stream) => Pin::new(stream).poll_read(cx, buf),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => Pin::new(stream).poll_read(cx, buf),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl hyper_1::rt::Write for HttpsStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Rustls(stream) => Pin::new(stream).poll_write(cx, buf),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => Pin::new(stream).poll_write(cx, buf),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Rustls(stream) => Pin::new(stream).poll_write_vectored(cx, bufs),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => Pin::new(stream).poll_write_vectored(cx, bufs),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => Pin::new(stream).poll_write_vectored(cx, bufs),
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        match self {
+            Self::Rustls(stream) => stream.is_write_vectored(),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => stream.is_write_vectored(),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => stream.is_write_vectored(),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Rustls(stream) => Pin::new(stream).poll_flush(cx),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => Pin::new(stream).poll_flush(cx),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Rustls(stream) => Pin::new(stream).poll_shutdown(cx),
+            #[cfg(feature = "tls-native-tls")]
+            Self::NativeTls(stream) => Pin::new(stream).poll_shutdown(cx),
+            #[cfg(feature = "tls-s2n-tls")]
+            Self::S2nTls(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+impl Connector {
+    /// Connects to the host of `uri`, and negotiates TLS with the configured TLS implementation if
+    /// it's an `https` URL.
+    fn connect(
+        &mut self,
+        uri: Uri,
+        connected_at: Arc<Mutex<Option<Instant>>>,
+    ) -> Pin<Box<dyn Future<Output = Result<HttpsStream, BoxError>> + Send>> {
+        let tcp = SignalConnected {
+            inner: self.tcp.clone(),
+            connected_at: connected_at.clone(),
+            proxy_config: self.proxy_config.clone(),
+        };
+        match &self.tls_connector {
+            TlsConnector::Rustls(tls_config) => self.connect_rustls(tls_config, tcp, uri),
+            #[cfg(feature = "tls-native-tls")]
+            TlsConnector::NativeTls(tls_connector) => {
+                let connecting = native_tls_connector::connect(
+                    tls_connector.clone(),
+                    self.tls_server_name.clone(),
+                    tcp,
+                    uri,
+                );
+                Box::pin(async move { Ok(HttpsStream::NativeTls(connecting.await?)) })
+            }
+            #[cfg(feature = "tls-s2n-tls")]
+            TlsConnector::S2nTls(tls_connector) => {
+                let connecting = s2n_tls_connector::connect(
+                    tls_connector.clone(),
+                    self.tls_server_name.clone(),
+                    tcp,
+                    uri,
+                );
+                Box::pin(async move { Ok(HttpsStream::S2nTls(connecting.await?)) })
+            }
+        }
+    }
+
+    /// Connects to the host of `uri`, and negotiates TLS with rustls if it's an `https` URL.
+    fn connect_rustls(
+        &self,
+        tls_config: &Arc<rustls::ClientConfig>,
+        tcp: SignalConnected,
+        uri: Uri,
+    ) -> Pin<Box<dyn Future<Output = Result<HttpsStream, BoxError>> + Send>> {
+        let mut https = match &self.tls_server_name {
+            Some(tls_server_name) => {
+                let server_name =
+                    match rustls::pki_types::ServerName::try_from(tls_server_name.clone()) {
+                        Ok(server_name) => server_name,
+                        Err(err) => {
+                            let err = format!("invalid TLS server name `{tls_server_name}`: {err}");
+                            return Box::pin(async move { Err(err.into()) });
+                        }
+                    };
+                // The builder sets the ALPN protocols for the enabled HTTP versions itself
+                let mut tls_config = (**tls_config).clone();
+                tls_config.alpn_protocols.clear();
+                hyper_rustls::HttpsConnectorBuilder::new()
+                    .with_tls_config(tls_config)
+                    .https_or_http()
+                    .with_server_name_resolver(hyper_rustls::FixedServerNameResolver::new(
+                        server_name,
+                    ))
+                    .enable_http1()
+                    .enable_http2()
+                    .wrap_connector(tcp)
+            }
+            None => hyper_rustls::HttpsConnector::from((tcp, tls_config.clone())),
+        };
+        let connecting = https.call(uri);
+        Box::pin(async move { Ok(HttpsStream::Rustls(connecting.await?)) })
+    }
+
+    /// Applies the connect and TLS negotiation timeouts to establishing a connection.
+    fn with_timeouts<T: Send + 'static>(
+        &self,
+        mut connecting: Pin<Box<dyn Future<Output = Result<T, BoxError>> + Send>>,
+        connected_at: Arc<Mutex<Option<Instant>>>,
+    ) -> Pin<Box<dyn Future<Output = Result<T, BoxError>> + Send>> {
+        let Some(ConnectTimeouts {
+            sleep_impl,
+            connect,
+            mut tls_negotiation,
+        }) = self.timeouts.clone()
+        else {
+            return connecting;
+        };
+        let mut timeout =
+            connect.map(|duration| (sleep_impl.sleep(duration), CONNECT_TIMEOUT_KIND, duration));
+        Box::pin(poll_fn(move |cx| {
+            if let Poll::Ready(result) = connecting.as_mut().poll(cx) {
+                return Poll::Ready(result);
+            }
+            // Once the TCP connection has been established, the TLS handshake gets its own timeout
+            if connected_at.lock().unwrap().is_some() {
+                if let Some(duration) = tls_negotiation.take() {
+                    timeout = Some((
+                        sleep_impl.sleep(duration),
+                        TLS_NEGOTIATION_TIMEOUT_KIND,
+                        duration,
+                    ));
+                }
+            }
+            if let Some((sleep, kind, duration)) = timeout.as_mut() {
+                if Pin::new(sleep).poll(cx).is_ready() {
+                    return Poll::Ready(Err(HttpTimeoutError {
+                        kind: *kind,
+                        duration: *duration,
+                    }
+                    .into()));
+                }
+            }
+            Poll::Pending
+        }))
+    }
+}
+
+/// Limits the number of open connections to each host.
+#[derive(Clone, Debug)]
+struct ConnectionLimit {
+    max_connections_per_host: Option<usize>,
+    hosts: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl ConnectionLimit {
+    fn new(max_connections_per_host: Option<usize>) -> Self {
+        Self {
+            max_connections_per_host,
+            hosts: Default::default(),
+        }
+    }
+
+    /// Waits until another connection can be opened to the host of `uri`.
+    ///
+    /// The connection counts against the limit until the returned permit is dropped.
+    fn acquire(&self, uri: &Uri) -> impl Future<Output = Option<OwnedSemaphorePermit>> + Send {
+        let semaphore = self.max_connections_per_host.map(|max_connections| {
+            let host = format!(
+                "{}://{}",
+                uri.scheme_str().unwrap_or_default(),
+                uri.authority().map(|a| a.as_str()).unwrap_or_default()
+            );
+            let mut hosts = self.hosts.lock().unwrap();
+            hosts
+                .entry(host)
+                .or_insert_with(|| Arc::new(Semaphore::new(max_connections)))
+                .clone()
+        });
+        async move {
+            match semaphore {
+                Some(semaphore) => Some(
+                    semaphore
+                        .acquire_owned()
+                        .await
+                        .expect("the semaphore is never closed"),
+                ),
+                None => None,
+            }
+        }
+    }
+}
+
+/// Facts about a connection that are added to its extras.
+#[derive(Clone, Debug)]
+struct ConnectionInfo {
+    connected_at: Instant,
+    // Name resolution is done by the TCP connector, so it's included in the connect duration
+    connect_duration: Option<Duration>,
+    tls_handshake_duration: Option<Duration>,
+    tls_version: Option<&'static str>,
+    alpn_protocol: Option<String>,
+}
+
+/// A connection that reports when it was established and the TLS parameters negotiated for it.
+///
+/// It counts against the [`ConnectionLimit`] of its host until it's closed.
+#[derive(Debug)]
+struct TrackedStream {
+    inner: HttpsStream,
+    info: ConnectionInfo,
+    proxied: bool,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl TrackedStream {
+    fn new(
+        inner: HttpsStream,
+        started_at: Instant,
+        tcp_connected_at: Option<Instant>,
+        proxied: bool,
+        permit: Option<OwnedSemaphorePermit>,
+    ) -> Self {
+        #[allow(clippy::disallowed_methods)]
+        let connected_at = Instant::now();
+        let connect_duration =
+            tcp_connected_at.map(|tcp_connected_at| tcp_connected_at - started_at);
+        let tls_handshake_duration = if inner.is_https() {
+            tcp_connected_at.map(|tcp_connected_at| connected_at - tcp_connected_at)
+        } else {
+            None
+        };
+        let (tls_version, alpn_protocol) = inner.tls_parameters();
+        Self {
+            inner,
+            info: ConnectionInfo {
+                connected_at,
+                connect_duration,
+                tls_handshake_duration,
+                tls_version,
+                alpn_protocol,
+            },
+            proxied,
+            _permit: permit,
+        }
+    }
+}
+
+impl Connection for TrackedStream {
+    fn connected(&self) -> Connected {
+        self.inner
+            .connected()
+            .proxy(self.proxied)
+            .extra(self.info.clone())
+    }
+}
+
+impl hyper_1::rt::Read for TrackedStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper_1::rt::ReadBufCursor<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl hyper_1::rt::Write for TrackedStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// TCP connector that records when its connection has been established, and that connects
+/// through a proxy when the [`ProxyConfig`] has one for the URL.
+#[derive(Clone)]
+struct SignalConnected {
+    inner: TcpConnector,
+    connected_at: Arc<Mutex<Option<Instant>>>,
+    proxy_config: Option<ProxyConfig>,
+}
+
+impl Service<Uri> for SignalConnected {
+    type Response = TokioIo<TcpStream>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(|err| err.into())
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let proxy = self
+            .proxy_config
+            .as_ref()
+            .zip(to_http0x_uri(&uri))
+            .and_then(|(proxy_config, uri)| Some((proxy_config.proxy_for(&uri)?.clone(), uri)));
+        let connecting: Pin<Box<dyn Future<Output = Result<_, BoxError>> + Send>> = match proxy {
+            Some((proxy, uri)) => {
+                let mut tcp = self.inner.clone();
+                if let Some(interval) = proxy.keepalive_interval() {
+                    tcp.set_keepalive(Some(interval));
+                    tcp.set_keepalive_interval(Some(interval));
+                }
+                Box::pin(async move {
+                    let address: Uri = proxy.address().to_string().parse()?;
+                    let stream = connect::connect(&proxy, &uri, move || {
+                        let mut tcp = tcp.clone();
+                        let address = address.clone();
+                        async move {
+                            poll_fn(|cx| tcp.poll_ready(cx)).await?;
+                            Ok(tcp.call(address).await?.into_inner())
+                        }
+                    })
+                    .await?;
+                    Ok(TokioIo::new(stream))
+                })
+            }
+            None => {
+                let connecting = self.inner.call(uri);
+                Box::pin(async move { Ok(connecting.await?) })
+            }
+        };
+        let connected_at = self.connected_at.clone();
+        Box::pin(async move {
+            let stream = connecting.await?;
+            #[allow(clippy::disallowed_methods)]
+            let now = Instant::now();
+            *connected_at.lock().unwrap() = Some(now);
+            Ok(stream)
+        })
+    }
+}
+
+pin_project! {
+    /// Adapts an [`SdkBody`] into a http-body 1.x request body
+    pub(super) struct Http1Body {
+        #[pin]
+        inner: SdkBody,
+        data_done: bool,
+        done: bool,
+    }
+}
+
+impl Http1Body {
+    fn new(inner: SdkBody) -> Self {
+        Self {
+            inner,
+            data_done: false,
+            done: false,
+        }
+    }
+}
+
+impl http_body_1x::Body for Http1Body {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body_1x::Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+        if !*this.data_done {
+            match ready!(http_body_0_4::Body::poll_data(this.inner.as_mut(), cx)) {
+                Some(Ok(data)) => return Poll::Ready(Some(Ok(http_body_1x::Frame::data(data)))),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => *this.data_done = true,
+            }
+        }
+        let trailers = ready!(http_body_0_4::Body::poll_trailers(this.inner, cx));
+        *this.done = true;
+        match trailers {
+            Ok(Some(trailers)) => Poll::Ready(Some(
+                to_http1x_headers(&trailers).map(http_body_1x::Frame::trailers),
+            )),
+            Ok(None) => Poll::Ready(None),
+            Err(err) => Poll::Ready(Some(Err(err))),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done || http_body_0_4::Body::is_end_stream(&self.inner)
+    }
+
+    fn size_hint(&self) -> http_body_1x::SizeHint {
+        let hint = http_body_0_4::Body::size_hint(&self.inner);
+        let mut converted = http_body_1x::SizeHint::new();
+        converted.set_lower(hint.lower());
+        if let Some(upper) = hint.upper() {
+            converted.set_upper(upper);
+        }
+        converted
+    }
+}
+
+pin_project! {
+    /// Adapts a http-body 1.x response body into a http-body 0.4 body so that it can be
+    /// wrapped in an [`SdkBody`]
+    struct Http0Body<B> {
+        #[pin]
+        inner: B,
+        trailers: Option<http::HeaderMap>,
+    }
+}
+
+impl<B> Http0Body<B> {
+    fn new(inner: B) -> Self {
+        Self {
+            inner,
+            trailers: None,
+        }
+    }
+}
+
+impl<B> http_body_0_4::Body for Http0Body<B>
+where
+    B: http_body_1x::Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let mut this = self.project();
+        loop {
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => return Poll::Ready(Some(Ok(data))),
+                    Err(frame) => {
+                        if let Ok(trailers) = frame.into_trailers() {
+                            *this.trailers = Some(to_http0x_headers(&trailers)?);
+                        }
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let mut this = self.project();
+        // Trailers come after the data, so skip any data that hasn't been read yet
+        loop {
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        *this.trailers = Some(to_http0x_headers(&trailers)?);
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Err(err.into())),
+                None => return Poll::Ready(Ok(this.trailers.take())),
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        let hint = self.inner.size_hint();
+        let mut converted = http_body_0_4::SizeHint::new();
+        converted.set_lower(hint.lower());
+        if let Some(upper) = hint.upper() {
+            converted.set_upper(upper);
+        }
+        converted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::http::proxy::Proxy;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_types::byte_stream::ByteStream;
+
+    #[test]
+    fn default_tls_config_is_cached() {
+        let default = tls_config(CryptoMode::Ring, None, None, false);
+        assert!(Arc::ptr_eq(&TLS_CONFIG, &default));
+
+        let custom = tls_config(CryptoMode::Ring, Some(&TrustStore::empty()), None, false);
+        assert!(!Arc::ptr_eq(&TLS_CONFIG, &custom));
+        assert_eq!(default.alpn_protocols, custom.alpn_protocols);
+
+        let custom = tls_config(
+            CryptoMode::Ring,
+            None,
+            Some(SessionResumption::disabled()),
+            false,
+        );
+        assert!(!Arc::ptr_eq(&TLS_CONFIG, &custom));
+
+        let custom = tls_config(CryptoMode::Ring, None, None, true);
+        assert!(!Arc::ptr_eq(&TLS_CONFIG, &custom));
+    }
+
+    /// Serves a single canned HTTP/1.1 response on a local port
+    async fn serve_once(response: &'static str) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            // Wait for the request before responding
+            stream.readable().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.try_read(&mut request);
+            let mut remaining = response.as_bytes();
+            while !remaining.is_empty() {
+                stream.writable().await.unwrap();
+                if let Ok(written) = stream.try_write(remaining) {
+                    remaining = &remaining[written..];
+                }
+            }
+            // Keep the connection open until the client is done with it
+            let _ = stream.readable().await;
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn sends_request_and_reads_response() {
+        let addr =
+            serve_once("HTTP/1.1 200 OK\r\ncontent-length: 5\r\nx-custom: value\r\n\r\nhello")
+                .await;
+        let connector = HyperConnector::new(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .read_timeout(Duration::from_secs(5))
+                .build(),
+            Some(SharedAsyncSleep::new(TokioSleep::new())),
+            None,
+            TlsConnector::Rustls(TLS_CONFIG.clone()),
+            None,
+            &LocalBinding::default(),
+            None,
+        );
+        let response = connector
+            .call(HttpRequest::get(format!("http://{addr}/")).unwrap())
+            .await
+            .expect("success");
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(Some("value"), response.headers().get("x-custom"));
+        let body = ByteStream::new(response.into_body())
+            .collect()
+            .await
+            .unwrap()
+            .into_bytes();
+        assert_eq!(&b"hello"[..], &body[..]);
+    }
+
+    #[tokio::test]
+    async fn spawns_connection_tasks_with_the_spawn_component() {
+        use aws_smithy_async::rt::spawn::{BlockingTask, Spawn, SpawnedFuture, TokioSpawn};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Debug, Default)]
+        struct CountingSpawn(Arc<AtomicUsize>);
+
+        impl Spawn for CountingSpawn {
+            fn spawn(&self, future: SpawnedFuture) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                TokioSpawn::new().spawn(future)
+            }
+
+            fn spawn_blocking(&self, task: BlockingTask) {
+                TokioSpawn::new().spawn_blocking(task)
+            }
+        }
+
+        let addr = serve_once("HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok").await;
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let connector = HyperConnector::new(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .build(),
+            Some(SharedAsyncSleep::new(TokioSleep::new())),
+            Some(SharedSpawn::new(CountingSpawn(spawned.clone()))),
+            TlsConnector::Rustls(TLS_CONFIG.clone()),
+            None,
+            &LocalBinding::default(),
+            None,
+        );
+        let response = connector
+            .call(HttpRequest::get(format!("http://{addr}/")).unwrap())
+            .await
+            .expect("success");
+        assert_eq!(200, response.status().as_u16());
+        assert!(spawned.load(Ordering::SeqCst) > 0);
+    }
+
+    /// Accepts a single connection, responds to its request, and returns the request's head
+    async fn proxy(response: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let received = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (format!("http://{addr}"), received)
+    }
+
+    fn proxied_connector(proxy: &str) -> HyperConnector {
+        let proxy = Proxy::new(proxy).unwrap().basic_auth("user", "pass");
+        HyperConnector::new(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .build(),
+            Some(SharedAsyncSleep::new(TokioSleep::new())),
+            None,
+            TlsConnector::Rustls(TLS_CONFIG.clone()),
+            None,
+            &LocalBinding::default(),
+            Some(ProxyConfig::new().all(proxy)),
+        )
+    }
+
+    #[tokio::test]
+    async fn forwards_plain_requests_to_proxies() {
+        let (proxy, received) = proxy("HTTP/1.1 200 OK\r\ncontent-length: 7\r\n\r\nproxied").await;
+        let response = proxied_connector(&proxy)
+            .call(HttpRequest::get("http://s3.amazonaws.com/bucket").unwrap())
+            .await
+            .expect("success");
+        assert_eq!(200, response.status().as_u16());
+        let request = received.await.unwrap();
+        assert!(
+            request.starts_with("GET http://s3.amazonaws.com/bucket HTTP/1.1\r\n"),
+            "{request}"
+        );
+        assert!(
+            request.contains("proxy-authorization: Basic dXNlcjpwYXNz\r\n"),
+            "{request}"
+        );
+    }
+
+    #[tokio::test]
+    async fn tunnels_https_requests_through_proxies() {
+        let (proxy, received) = proxy("HTTP/1.1 403 Forbidden\r\n\r\n").await;
+        let err = proxied_connector(&proxy)
+            .call(HttpRequest::get("https://s3.amazonaws.com/bucket").unwrap())
+            .await
+            .expect_err("the proxy refused to tunnel");
+        assert!(
+            format!("{}", DisplayErrorContext(&err))
+                .contains("the proxy failed to tunnel to `s3.amazonaws.com:443`"),
+            "{err:?}"
+        );
+        assert_eq!(
+            "CONNECT s3.amazonaws.com:443 HTTP/1.1\r\n\
+             Host: s3.amazonaws.com:443\r\n\
+             Proxy-Authorization: Basic dXNlcjpwYXNz\r\n\r\n",
+            received.await.unwrap()
+        );
+    }
+
+    #[cfg(feature = "tls-native-tls")]
+    fn native_tls_connector(tls_server_name: Option<&str>) -> HyperConnector {
+        HyperConnector::new(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .build(),
+            Some(SharedAsyncSleep::new(TokioSleep::new())),
+            None,
+            tls_connector(&TlsProvider::NativeTls, None, None, false),
+            tls_server_name,
+            &LocalBinding::default(),
+            None,
+        )
+    }
+
+    #[cfg(feature = "tls-native-tls")]
+    #[tokio::test]
+    async fn native_tls_sends_plain_requests_for_http_urls() {
+        let (server, received) = proxy("HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok").await;
+        let response = native_tls_connector(None)
+            .call(HttpRequest::get(format!("{server}/bucket")).unwrap())
+            .await
+            .expect("success");
+        assert_eq!(200, response.status().as_u16());
+        assert!(received
+            .await
+            .unwrap()
+            .starts_with("GET /bucket HTTP/1.1\r\n"),);
+    }
+
+    #[cfg(feature = "tls-native-tls")]
+    #[tokio::test]
+    async fn native_tls_negotiates_tls_for_https_urls() {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client_hello = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut client_hello = vec![0; 4096];
+            let read = stream.read(&mut client_hello).await.unwrap();
+            client_hello.truncate(read);
+            client_hello
+        });
+        let err = native_tls_connector(Some("s3.amazonaws.com"))
+            .call(HttpRequest::get(format!("https://{addr}/bucket")).unwrap())
+            .await
+            .expect_err("the server doesn't complete the handshake");
+        assert!(err.is_io(), "{err:?}");
+
+        let client_hello = client_hello.await.unwrap();
+        // A TLS handshake record, with the overridden server name and ALPN protocols
+        assert_eq!(Some(&0x16), client_hello.first());
+        let contains = |needle: &[u8]| {
+            client_hello
+                .windows(needle.len())
+                .any(|window| window == needle)
+        };
+        assert!(contains(b"s3.amazonaws.com"));
+        assert!(contains(b"h2") && contains(b"http/1.1"));
+    }
+
+    #[cfg(feature = "tls-s2n-tls")]
+    fn s2n_tls_connector(tls_server_name: Option<&str>) -> HyperConnector {
+        HyperConnector::new(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .build(),
+            Some(SharedAsyncSleep::new(TokioSleep::new())),
+            None,
+            tls_connector(&TlsProvider::S2nTls, None, None, false),
+            tls_server_name,
+            &LocalBinding::default(),
+            None,
+        )
+    }
+
+    #[cfg(feature = "tls-s2n-tls")]
+    #[tokio::test]
+    async fn s2n_tls_sends_plain_requests_for_http_urls() {
+        let (server, received) = proxy("HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok").await;
+        let response = s2n_tls_connector(None)
+            .call(HttpRequest::get(format!("{server}/bucket")).unwrap())
+            .await
+            .expect("success");
+        assert_eq!(200, response.status().as_u16());
+        assert!(received
+            .await
+            .unwrap()
+            .starts_with("GET /bucket HTTP/1.1\r\n"),);
+    }
+
+    #[cfg(feature = "tls-s2n-tls")]
+    #[tokio::test]
+    async fn s2n_tls_negotiates_tls_for_https_urls() {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client_hello = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut client_hello = vec![0; 4096];
+            let read = stream.read(&mut client_hello).await.unwrap();
+            client_hello.truncate(read);
+            client_hello
+        });
+        let err = s2n_tls_connector(Some("s3.amazonaws.com"))
+            .call(HttpRequest::get(format!("https://{addr}/bucket")).unwrap())
+            .await
+            .expect_err("the server doesn't complete the handshake");
+        assert!(err.is_io(), "{err:?}");
+
+        let client_hello = client_hello.await.unwrap();
+        // A TLS handshake record, with the overridden server name and ALPN protocols
+        assert_eq!(Some(&0x16), client_hello.first());
+        let contains = |needle: &[u8]| {
+            client_hello
+                .windows(needle.len())
+                .any(|window| window == needle)
+        };
+        assert!(contains(b"s3.amazonaws.com"));
+        assert!(contains(b"h2") && contains(b"http/1.1"));
+    }
+
+    #[tokio::test]
+    async fn reports_dns_failures() {
+        let connector = HyperConnector::new(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .build(),
+            Some(SharedAsyncSleep::new(TokioSleep::new())),
+            None,
+            TlsConnector::Rustls(TLS_CONFIG.clone()),
+            None,
+            &LocalBinding::default(),
+            None,
+        );
+        // The `invalid` top-level domain is reserved, so it never resolves
+        let err = connector
+            .call(HttpRequest::get("http://does-not-resolve.invalid/").unwrap())
+            .await
+            .expect_err("resolution failed");
+        assert!(err.is_dns(), "{err:?}");
+    }
+
+    fn pooled_connector(pool: PoolSettings) -> HyperConnector {
+        HyperConnector::new(
+            &HttpConnectorSettings::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .pool_settings(pool)
+                .build(),
+            Some(SharedAsyncSleep::new(TokioSleep::new())),
+            None,
+            TlsConnector::Rustls(TLS_CONFIG.clone()),
+            None,
+            &LocalBinding::default(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn limits_connections_per_host() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        async fn respond_and_close(mut stream: TcpStream) {
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connector =
+            pooled_connector(PoolSettings::builder().max_connections_per_host(1).build());
+        let first =
+            tokio::spawn(connector.call(HttpRequest::get(format!("http://{addr}/")).unwrap()));
+        let second =
+            tokio::spawn(connector.call(HttpRequest::get(format!("http://{addr}/")).unwrap()));
+
+        let (stream, _) = listener.accept().await.unwrap();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), listener.accept())
+                .await
+                .is_err(),
+            "the second connection must wait for the first one to close"
+        );
+        respond_and_close(stream).await;
+        let (stream, _) = listener.accept().await.unwrap();
+        respond_and_close(stream).await;
+        for response in [first, second] {
+            assert_eq!(
+                200,
+                response.await.unwrap().expect("success").status().as_u16()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn closes_connections_that_outlived_their_lifetime() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let (closed_tx, mut closed_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn({
+            let accepted = accepted.clone();
+            async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let connection = accepted.fetch_add(1, Ordering::SeqCst);
+                    let closed_tx = closed_tx.clone();
+                    tokio::spawn(async move {
+                        // Respond to every request until the client closes the connection
+                        let mut request = [0; 1024];
+                        while stream.read(&mut request).await.unwrap_or_default() > 0 {
+                            stream
+                                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                                .await
+                                .unwrap();
+                        }
+                        let _ = closed_tx.send(connection);
+                    });
+                }
+            }
+        });
+
+        let connector = pooled_connector(
+            PoolSettings::builder()
+                .max_connection_lifetime(Duration::ZERO)
+                .build(),
+        );
+        for _ in 0..2 {
+            let response = connector
+                .call(HttpRequest::get(format!("http://{addr}/")).unwrap())
+                .await
+                .expect("success");
+            ByteStream::new(response.into_body())
+                .collect()
+                .await
+                .unwrap();
+        }
+        // The first connection expired, so it was closed instead of being reused
+        let closed = tokio::time::timeout(Duration::from_secs(5), closed_rx.recv()).await;
+        assert_eq!(Some(0), closed.expect("the connection is closed"));
+        assert_eq!(2, accepted.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn request_body_round_trips_through_adapter() {
+        use http_body_1x::Body as _;
+        let mut body = Http1Body::new(SdkBody::from("hello"));
+        assert_eq!(Some(5), body.size_hint().exact());
+        let frame = poll_fn(|cx| Pin::new(&mut body).poll_frame(cx))
+            .await
+            .expect("one frame")
+            .expect("success");
+        assert_eq!(&b"hello"[..], &frame.into_data().unwrap()[..]);
+        assert!(poll_fn(|cx| Pin::new(&mut body).poll_frame(cx))
+            .await
+            .is_none());
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! TLS negotiation with the platform's TLS implementation, through native-tls.
+
+use super::SignalConnected;
+use crate::client::http::tls::TrustStore;
+use aws_smithy_runtime_api::box_error::BoxError;
+use http_1x::Uri;
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use hyper_util::rt::TokioIo;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::net::TcpStream;
+use tower_service::Service;
+
+/// Returns the native-tls connector that verifies server certificates against the given trust
+/// store, or against the platform's native root certificates when there's no trust store.
+///
+/// Certificates that can't be parsed are skipped, so they can only make the trust store smaller.
+pub(super) fn tls_connector(trust_store: Option<&TrustStore>) -> tokio_native_tls::TlsConnector {
+    let default_trust_store = TrustStore::default();
+    let trust_store = trust_store.unwrap_or(&default_trust_store);
+    let mut builder = native_tls::TlsConnector::builder();
+    builder
+        .min_protocol_version(Some(native_tls::Protocol::Tlsv12))
+        .disable_built_in_roots(!trust_store.native_roots())
+        .request_alpns(&["h2", "http/1.1"]);
+    let mut invalid = 0;
+    for certificate in trust_store.certificates() {
+        match native_tls::Certificate::from_der(certificate) {
+            Ok(certificate) => {
+                builder.add_root_certificate(certificate);
+            }
+            Err(_) => invalid += 1,
+        }
+    }
+    if invalid > 0 {
+        tracing::warn!(
+            invalid = invalid,
+            "some of the trust store's certificates are invalid and were skipped"
+        );
+    }
+    builder
+        .build()
+        .expect("failed to initialize the platform's TLS implementation")
+        .into()
+}
+
+/// Connects to the host of `uri`, and negotiates TLS if it's an `https` URL.
+///
+/// The server certificate is verified against `tls_server_name` if it's set, and against the host
+/// of the URL otherwise.
+pub(super) fn connect(
+    tls_connector: tokio_native_tls::TlsConnector,
+    tls_server_name: Option<String>,
+    mut tcp: SignalConnected,
+    uri: Uri,
+) -> impl Future<Output = Result<MaybeTlsStream, BoxError>> + Send {
+    let is_https = uri.scheme() == Some(&http_1x::uri::Scheme::HTTPS);
+    // The host of an IPv6 URL is in brackets, which aren't part of the server name
+    let server_name = tls_server_name.or_else(|| {
+        uri.host().map(|host| {
+            host.trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string()
+        })
+    });
+    let connecting = tcp.call(uri);
+    async move {
+        let stream = connecting.await?;
+        if !is_https {
+            return Ok(MaybeTlsStream::Http(stream));
+        }
+        let server_name = server_name.ok_or("missing host in the URL to connect to")?;
+        let tls = tls_connector
+            .connect(&server_name, stream.into_inner())
+            .await?;
+        Ok(MaybeTlsStream::Https(TokioIo::new(tls)))
+    }
+}
+
+/// A connection that TLS was negotiated on with native-tls, if it's for an `https` URL.
+#[derive(Debug)]
+pub(super) enum MaybeTlsStream {
+    Http(TokioIo<TcpStream>),
+    Https(TokioIo<tokio_native_tls::TlsStream<TcpStream>>),
+}
+
+impl MaybeTlsStream {
+    /// Returns true if TLS was negotiated on this connection.
+    pub(super) fn is_https(&self) -> bool {
+        matches!(self, Self::Https(_))
+    }
+
+    /// Returns the negotiated ALPN protocol, if any.
+    pub(super) fn alpn_protocol(&self) -> Option<String> {
+        match self {
+            Self::Https(tls) => tls
+                .inner()
+                .get_ref()
+                .negotiated_alpn()
+                .ok()
+                .flatten()
+                .map(|protocol| String::from_utf8_lossy(&protocol).into_owned()),
+            Self::Http(_) => None,
+        }
+    }
+}
+
+impl Connection for MaybeTlsStream {
+    fn connected(&self) -> Connected {
+        match self {
+            Self::Https(tls) => {
+                let connected = tls.inner().get_ref().get_ref().get_ref().connected();
+                if self.alpn_protocol().as_deref() == Some("h2") {
+                    connected.negotiated_h2()
+                } else {
+                    connected
+                }
+            }
+            Self::Http(tcp) => tcp.connected(),
+        }
+    }
+}
+
+impl hyper_1::rt::Read for MaybeTlsStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper_1::rt::ReadBufCursor<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_read(cx, buf),
+            Self::Https(tls) => Pin::new(tls).poll_read(cx, buf),
+        }
+    }
+}
+
+impl hyper_1::rt::Write for MaybeTlsStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_write(cx, buf),
+            Self::Https(tls) => Pin::new(tls).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_write_vectored(cx, bufs),
+            Self::Https(tls) => Pin::new(tls).poll_write_vectored(cx, bufs),
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        match self {
+            Self::Http(tcp) => tcp.is_write_vectored(),
+            Self::Https(tls) => tls.is_write_vectored(),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_flush(cx),
+            Self::Https(tls) => Pin::new(tls).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_shutdown(cx),
+            Self::Https(tls) => Pin::new(tls).poll_shutdown(cx),
+        }
+    }
+}
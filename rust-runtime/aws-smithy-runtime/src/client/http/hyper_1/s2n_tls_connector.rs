@@ -0,0 +1,212 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! TLS negotiation with [s2n-tls](https://github.com/aws/s2n-tls).
+
+use super::SignalConnected;
+use crate::client::http::tls::TrustStore;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::base64;
+use http_1x::Uri;
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use hyper_util::rt::TokioIo;
+use s2n_tls::connection::Connection as S2nConnection;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::net::TcpStream;
+use tower_service::Service;
+
+/// Returns the s2n-tls connector that verifies server certificates against the given trust
+/// store, or against the platform's native root certificates when there's no trust store.
+///
+/// TLS 1.2 or later is negotiated with the cipher suites of s2n-tls's `default_tls13` security
+/// policy. Certificates that can't be loaded are skipped, so they can only make the trust store
+/// smaller.
+pub(super) fn tls_connector(trust_store: Option<&TrustStore>) -> s2n_tls_tokio::TlsConnector {
+    let default_trust_store = TrustStore::default();
+    let trust_store = trust_store.unwrap_or(&default_trust_store);
+    let mut builder = s2n_tls::config::Config::builder();
+    builder
+        .set_security_policy(&s2n_tls::security::DEFAULT_TLS13)
+        .expect("the default_tls13 security policy is valid")
+        .set_application_protocol_preference(["h2", "http/1.1"])
+        .expect("the ALPN protocols are valid")
+        .with_system_certs(trust_store.native_roots())
+        .expect("failed to load the platform's native root certificates");
+    let mut invalid = 0;
+    for certificate in trust_store.certificates() {
+        // s2n-tls only loads PEM-encoded certificates
+        let pem = format!(
+            "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n",
+            base64::encode(certificate)
+        );
+        if builder.trust_pem(pem.as_bytes()).is_err() {
+            invalid += 1;
+        }
+    }
+    if invalid > 0 {
+        tracing::warn!(
+            invalid = invalid,
+            "some of the trust store's certificates are invalid and were skipped"
+        );
+    }
+    let config = builder.build().expect("failed to initialize s2n-tls");
+    s2n_tls_tokio::TlsConnector::new(config)
+}
+
+/// Connects to the host of `uri`, and negotiates TLS if it's an `https` URL.
+///
+/// The server certificate is verified against `tls_server_name` if it's set, and against the host
+/// of the URL otherwise.
+pub(super) fn connect(
+    tls_connector: s2n_tls_tokio::TlsConnector,
+    tls_server_name: Option<String>,
+    mut tcp: SignalConnected,
+    uri: Uri,
+) -> impl Future<Output = Result<MaybeTlsStream, BoxError>> + Send {
+    let is_https = uri.scheme() == Some(&http_1x::uri::Scheme::HTTPS);
+    // The host of an IPv6 URL is in brackets, which aren't part of the server name
+    let server_name = tls_server_name.or_else(|| {
+        uri.host().map(|host| {
+            host.trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string()
+        })
+    });
+    let connecting = tcp.call(uri);
+    async move {
+        let stream = connecting.await?;
+        if !is_https {
+            return Ok(MaybeTlsStream::Http(stream));
+        }
+        let server_name = server_name.ok_or("missing host in the URL to connect to")?;
+        let tls = tls_connector
+            .connect(&server_name, stream.into_inner())
+            .await?;
+        Ok(MaybeTlsStream::Https(TokioIo::new(tls)))
+    }
+}
+
+/// A connection that TLS was negotiated on with s2n-tls, if it's for an `https` URL.
+pub(super) enum MaybeTlsStream {
+    Http(TokioIo<TcpStream>),
+    Https(TokioIo<s2n_tls_tokio::TlsStream<TcpStream>>),
+}
+
+impl fmt::Debug for MaybeTlsStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(tcp) => f.debug_tuple("Http").field(tcp).finish(),
+            Self::Https(_) => f.write_str("Https(..)"),
+        }
+    }
+}
+
+impl MaybeTlsStream {
+    /// Returns true if TLS was negotiated on this connection.
+    pub(super) fn is_https(&self) -> bool {
+        matches!(self, Self::Https(_))
+    }
+
+    /// Returns the s2n-tls connection, if TLS was negotiated.
+    fn tls_connection(&self) -> Option<&S2nConnection> {
+        match self {
+            Self::Https(tls) => Some(tls.inner().as_ref()),
+            Self::Http(_) => None,
+        }
+    }
+
+    /// Returns the negotiated TLS version, if TLS was negotiated.
+    pub(super) fn tls_version(&self) -> Option<&'static str> {
+        match self.tls_connection()?.actual_protocol_version().ok()? {
+            s2n_tls::enums::Version::TLS12 => Some("TLSv1.2"),
+            s2n_tls::enums::Version::TLS13 => Some("TLSv1.3"),
+            _ => None,
+        }
+    }
+
+    /// Returns the negotiated ALPN protocol, if any.
+    pub(super) fn alpn_protocol(&self) -> Option<String> {
+        self.tls_connection()?
+            .application_protocol()
+            .map(|protocol| String::from_utf8_lossy(protocol).into_owned())
+    }
+}
+
+impl Connection for MaybeTlsStream {
+    fn connected(&self) -> Connected {
+        match self {
+            Self::Https(tls) => {
+                let connected = tls.inner().get_ref().connected();
+                if self.alpn_protocol().as_deref() == Some("h2") {
+                    connected.negotiated_h2()
+                } else {
+                    connected
+                }
+            }
+            Self::Http(tcp) => tcp.connected(),
+        }
+    }
+}
+
+impl hyper_1::rt::Read for MaybeTlsStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper_1::rt::ReadBufCursor<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_read(cx, buf),
+            Self::Https(tls) => Pin::new(tls).poll_read(cx, buf),
+        }
+    }
+}
+
+impl hyper_1::rt::Write for MaybeTlsStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_write(cx, buf),
+            Self::Https(tls) => Pin::new(tls).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_write_vectored(cx, bufs),
+            Self::Https(tls) => Pin::new(tls).poll_write_vectored(cx, bufs),
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        match self {
+            Self::Http(tcp) => tcp.is_write_vectored(),
+            Self::Https(tls) => tls.is_write_vectored(),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_flush(cx),
+            Self::Https(tls) => Pin::new(tls).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Http(tcp) => Pin::new(tcp).poll_shutdown(cx),
+            Self::Https(tls) => Pin::new(tls).poll_shutdown(cx),
+        }
+    }
+}
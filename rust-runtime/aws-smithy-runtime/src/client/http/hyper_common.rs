@@ -0,0 +1,267 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Functionality that's shared by the hyper 0.14.x and hyper 1.x HTTP clients.
+
+use crate::client::http::body::minimum_throughput;
+use crate::client::http::connection_poisoning::CaptureSmithyConnection;
+use aws_smithy_async::future::timeout::TimedOutError;
+use aws_smithy_async::rt::spawn::{SharedSpawn, Spawn};
+use aws_smithy_runtime_api::client::connection::ConnectionMetadata;
+use aws_smithy_runtime_api::client::dns::ResolveDnsError;
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::retry::ErrorKind;
+use std::error::Error;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// Spawns the tasks that drive a hyper client's connections with a [`SharedSpawn`].
+#[derive(Clone, Debug)]
+pub(super) struct SpawnExecutor(pub(super) SharedSpawn);
+
+#[cfg(feature = "connector-hyper-0-14-x")]
+impl<F> hyper_0_14::rt::Executor<F> for SpawnExecutor
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    fn execute(&self, future: F) {
+        self.0.spawn(Box::pin(future))
+    }
+}
+
+#[cfg(feature = "connector-hyper-1-x")]
+impl<F> hyper_1::rt::Executor<F> for SpawnExecutor
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    fn execute(&self, future: F) {
+        self.0.spawn(Box::pin(future))
+    }
+}
+
+/// A handle to the connection that a hyper client used for a request.
+pub(super) trait CapturedConnection: Clone + Send + Sync + 'static {
+    /// Returns the details of the connection, or `None` if no connection was established.
+    fn details(&self) -> Option<ConnectionDetails>;
+
+    /// Prevents the connection from being reused.
+    fn poison(&self);
+}
+
+/// Details of a connection, gathered from what the connector recorded on it.
+#[derive(Debug, Default)]
+pub(super) struct ConnectionDetails {
+    pub(super) proxied: bool,
+    pub(super) remote_addr: Option<SocketAddr>,
+    pub(super) local_addr: Option<SocketAddr>,
+    /// When the connection was established, if the connector recorded it
+    pub(super) connected_at: Option<Instant>,
+    pub(super) tls_version: Option<&'static str>,
+    pub(super) alpn_protocol: Option<String>,
+    pub(super) connect_duration: Option<Duration>,
+    pub(super) tls_handshake_duration: Option<Duration>,
+}
+
+/// Copies a request so that it can be sent again, if its body can be copied.
+pub(super) fn try_clone_request(
+    request: &http::Request<SdkBody>,
+) -> Option<http::Request<SdkBody>> {
+    let mut clone = http::Request::new(request.body().try_clone()?);
+    *clone.method_mut() = request.method().clone();
+    *clone.uri_mut() = request.uri().clone();
+    *clone.version_mut() = request.version();
+    *clone.headers_mut() = request.headers().clone();
+    if let Some(capture_smithy_connection) = request.extensions().get::<CaptureSmithyConnection>() {
+        clone
+            .extensions_mut()
+            .insert(capture_smithy_connection.clone());
+    }
+    Some(clone)
+}
+
+/// Returns true if the request was sent on a connection that was reused from the pool.
+pub(super) fn is_reused(conn: &impl CapturedConnection, sent_at: Instant) -> bool {
+    conn.details()
+        .and_then(|details| details.connected_at)
+        .map(|connected_at| connected_at < sent_at)
+        .unwrap_or_default()
+}
+
+/// Poisons the connection if it has been open for longer than `max_connection_lifetime`, so that
+/// the pool closes it instead of reusing it once the response has been read.
+pub(super) fn poison_if_expired(conn: &impl CapturedConnection, max_connection_lifetime: Duration) {
+    let Some(connected_at) = conn.details().and_then(|details| details.connected_at) else {
+        return;
+    };
+    // A connection's age is measured with the monotonic clock that it was established with
+    #[allow(clippy::disallowed_methods)]
+    let age = connected_at.elapsed();
+    if age >= max_connection_lifetime {
+        tracing::debug!(max_connection_lifetime = ?max_connection_lifetime, "connection expired and won't be reused");
+        conn.poison();
+    }
+}
+
+/// Returns true if a request that failed on a connection from the pool can be sent again on
+/// another connection.
+///
+/// Servers close keep-alive connections that have been idle for a while, and when that races
+/// with the connection being picked from the pool, the request fails without a response.
+/// Requests that hyper reports as canceled were never written to the connection, so they can
+/// always be sent again. Otherwise the server may have already processed the request, so it's
+/// only sent again if its method is idempotent.
+pub(super) fn is_stale_connection_error(
+    err: &(dyn Error + 'static),
+    method: &http::Method,
+) -> bool {
+    let hyper_error = hyper_error_kind(err).unwrap_or_default();
+    if hyper_error.is_canceled {
+        return true;
+    }
+    let closed = hyper_error.is_incomplete_message
+        || matches!(
+            find_source::<std::io::Error>(err).map(|err| err.kind()),
+            Some(
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+            )
+        );
+    closed && method.is_idempotent()
+}
+
+/// Returns true if the error chain contains a hyper error for a message that was cut short,
+/// because the connection was closed or timed out before the whole message was received.
+pub(super) fn is_interrupted_message_error(err: &(dyn Error + 'static)) -> bool {
+    hyper_error_kind(err)
+        .map(|kind| kind.is_incomplete_message || kind.is_closed || kind.is_timeout)
+        .unwrap_or_default()
+}
+
+/// Extract a smithy connection from a hyper CaptureConnection
+pub(super) fn extract_smithy_connection(
+    conn: &impl CapturedConnection,
+    sent_at: Instant,
+) -> Option<ConnectionMetadata> {
+    let details = conn.details()?;
+    let is_reused = details
+        .connected_at
+        .map(|connected_at| connected_at < sent_at);
+    let conn = conn.clone();
+    let mut builder = ConnectionMetadata::builder()
+        .proxied(details.proxied)
+        .poison_fn(move || conn.poison());
+    builder
+        .set_remote_addr(details.remote_addr)
+        .set_local_addr(details.local_addr)
+        // A connection that was established before the request was sent came from the pool
+        .set_reused(is_reused)
+        .set_tls_version(details.tls_version.map(str::to_string))
+        .set_alpn_protocol(details.alpn_protocol);
+    // Time spent establishing the connection only applies to the request that established it
+    if is_reused == Some(false) {
+        builder
+            .set_connect_duration(details.connect_duration)
+            .set_tls_handshake_duration(details.tls_handshake_duration);
+    }
+    Some(builder.build())
+}
+
+/// Convert an error from a hyper client into a [`ConnectorError`]
+///
+/// `is_connect` is true if the error occurred while establishing a connection.
+pub(super) fn to_connector_error<E>(err: E, is_connect: bool) -> ConnectorError
+where
+    E: Error + Send + Sync + 'static,
+{
+    let hyper_error = hyper_error_kind(&err).unwrap_or_default();
+    if is_connect && is_dns_error(&err) {
+        ConnectorError::dns(err.into())
+    } else if hyper_error.is_timeout
+        || find_source::<TimedOutError>(&err).is_some()
+        || find_source::<minimum_throughput::Error>(&err).is_some()
+    {
+        ConnectorError::timeout(err.into())
+    } else if hyper_error.is_user {
+        ConnectorError::user(err.into())
+    } else if hyper_error.is_closed
+        || hyper_error.is_canceled
+        || is_connect
+        || find_source::<std::io::Error>(&err).is_some()
+    {
+        ConnectorError::io(err.into())
+    }
+    // We sometimes receive this from S3: hyper::Error(IncompleteMessage)
+    else if hyper_error.is_incomplete_message {
+        ConnectorError::other(err.into(), Some(ErrorKind::TransientError))
+    } else {
+        tracing::warn!(err = %DisplayErrorContext(&err), "unrecognized error from Hyper. If this error should be retried, please file an issue.");
+        ConnectorError::other(err.into(), None)
+    }
+}
+
+/// The classification of a hyper error, for either version of hyper.
+#[derive(Debug, Default)]
+struct HyperErrorKind {
+    is_timeout: bool,
+    is_user: bool,
+    is_closed: bool,
+    is_canceled: bool,
+    is_incomplete_message: bool,
+}
+
+/// Classifies the first hyper error in the error chain, if there is one.
+fn hyper_error_kind(err: &(dyn Error + 'static)) -> Option<HyperErrorKind> {
+    #[cfg(feature = "connector-hyper-0-14-x")]
+    if let Some(err) = find_source::<hyper_0_14::Error>(err) {
+        return Some(HyperErrorKind {
+            is_timeout: err.is_timeout(),
+            is_user: err.is_user(),
+            is_closed: err.is_closed(),
+            is_canceled: err.is_canceled(),
+            is_incomplete_message: err.is_incomplete_message(),
+        });
+    }
+    #[cfg(feature = "connector-hyper-1-x")]
+    if let Some(err) = find_source::<hyper_1::Error>(err) {
+        return Some(HyperErrorKind {
+            is_timeout: err.is_timeout(),
+            is_user: err.is_user(),
+            is_closed: err.is_closed(),
+            is_canceled: err.is_canceled(),
+            is_incomplete_message: err.is_incomplete_message(),
+        });
+    }
+    None
+}
+
+/// Returns true if the error chain contains an error from a DNS resolver.
+///
+/// The resolvers that the hyper clients are built with report failures as [`ResolveDnsError`]s,
+/// including when they fall back to `getaddrinfo`.
+pub(super) fn is_dns_error(err: &(dyn Error + 'static)) -> bool {
+    if find_source::<ResolveDnsError>(err).is_some() {
+        return true;
+    }
+    #[cfg(feature = "dns-hickory")]
+    if find_source::<hickory_resolver::error::ResolveError>(err).is_some() {
+        return true;
+    }
+    false
+}
+
+pub(super) fn find_source<'a, E: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a E> {
+    let mut next = Some(err);
+    while let Some(err) = next {
+        if let Some(matching_err) = err.downcast_ref::<E>() {
+            return Some(matching_err);
+        }
+        next = err.source();
+    }
+    None
+}
@@ -0,0 +1,249 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Connecting through proxies, which is shared by the HTTP clients that are built on tokio.
+
+use super::{Proxy, ProxyKind};
+use aws_smithy_runtime_api::box_error::BoxError;
+use http::{HeaderValue, Uri};
+use std::future::Future;
+use std::net::IpAddr;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// The largest response to a `CONNECT` request that will be read from a proxy
+const MAX_CONNECT_RESPONSE_SIZE: usize = 8 * 1024;
+
+/// Returns true if requests to `uri` are sent to `proxy` in absolute form, rather than tunneled
+/// through it.
+pub(crate) fn is_forwarded(proxy: &Proxy, uri: &Uri) -> bool {
+    proxy.kind() == ProxyKind::Http && uri.scheme_str() != Some("https")
+}
+
+/// Connects to the host of `uri` through `proxy`.
+///
+/// For HTTP proxies, connections for `https` URLs are tunneled through the proxy with `CONNECT`,
+/// so that TLS can be negotiated with the server. Connections for `http` URLs are made to the
+/// proxy, which requests are then [forwarded](is_forwarded) to. For SOCKS5 proxies, every
+/// connection is tunneled.
+///
+/// `connect_to_proxy` opens a new connection to the proxy. Proxies commonly close the connection
+/// after challenging a `CONNECT` request that didn't have credentials, so it's called again to
+/// request the tunnel with credentials.
+pub(crate) async fn connect<T, F, Fut>(
+    proxy: &Proxy,
+    uri: &Uri,
+    mut connect_to_proxy: F,
+) -> Result<T, BoxError>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, BoxError>>,
+{
+    let stream = connect_to_proxy().await?;
+    match proxy.kind() {
+        ProxyKind::Socks5 { remote_dns } => {
+            socks5_connect(stream, uri, remote_dns, proxy.socks_credentials()).await
+        }
+        ProxyKind::Http if is_forwarded(proxy, uri) => Ok(stream),
+        ProxyKind::Http => {
+            let authorization = proxy
+                .authorization_header()
+                .filter(|_| proxy.is_preemptive_auth());
+            match tunnel(stream, uri, authorization).await? {
+                Tunnel::Established(stream) => Ok(stream),
+                Tunnel::AuthorizationRequired(_)
+                    if authorization.is_none() && proxy.authorization_header().is_some() =>
+                {
+                    let stream = connect_to_proxy().await?;
+                    tunnel(stream, uri, proxy.authorization_header())
+                        .await?
+                        .established()
+                }
+                tunnel => tunnel.established(),
+            }
+        }
+    }
+}
+
+/// The outcome of asking a proxy for a tunnel.
+enum Tunnel<T> {
+    Established(T),
+    /// The proxy responded with a `407` challenge, with the given status line
+    AuthorizationRequired(String),
+}
+
+impl<T> Tunnel<T> {
+    fn established(self) -> Result<T, BoxError> {
+        match self {
+            Tunnel::Established(stream) => Ok(stream),
+            Tunnel::AuthorizationRequired(status_line) => {
+                Err(format!("the proxy requires authorization ({status_line})").into())
+            }
+        }
+    }
+}
+
+/// Asks the proxy to tunnel the connection to the host of `uri`.
+async fn tunnel<T>(
+    mut stream: T,
+    uri: &Uri,
+    authorization: Option<&HeaderValue>,
+) -> Result<Tunnel<T>, BoxError>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    let host = uri.host().ok_or("the URL to connect to has no host")?;
+    let authority = format!("{host}:{}", uri.port_u16().unwrap_or(443));
+    let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n").into_bytes();
+    if let Some(authorization) = authorization {
+        request.extend_from_slice(b"Proxy-Authorization: ");
+        request.extend_from_slice(authorization.as_bytes());
+        request.extend_from_slice(b"\r\n");
+    }
+    request.extend_from_slice(b"\r\n");
+    stream.write_all(&request).await?;
+
+    // The server doesn't send anything until the TLS handshake starts, so the proxy's response
+    // is the only thing to read
+    let mut response = Vec::new();
+    let mut buf = [0; 1024];
+    while !response.windows(4).any(|window| window == b"\r\n\r\n") {
+        if response.len() > MAX_CONNECT_RESPONSE_SIZE {
+            return Err("the proxy's response to `CONNECT` was too large".into());
+        }
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            return Err("the proxy closed the connection before responding to `CONNECT`".into());
+        }
+        response.extend_from_slice(&buf[..read]);
+    }
+    let status_line = response.split(|&b| b == b'\r').next().unwrap_or_default();
+    let status_line = String::from_utf8_lossy(status_line);
+    match status_line.split_whitespace().nth(1) {
+        Some("200") => Ok(Tunnel::Established(stream)),
+        Some("407") => Ok(Tunnel::AuthorizationRequired(status_line.into_owned())),
+        _ => Err(format!("the proxy failed to tunnel to `{authority}` ({status_line})").into()),
+    }
+}
+
+/// Asks the SOCKS5 proxy to connect to the host of `uri`, following RFC 1928 and RFC 1929.
+async fn socks5_connect<T>(
+    mut stream: T,
+    uri: &Uri,
+    remote_dns: bool,
+    credentials: Option<(&str, &str)>,
+) -> Result<T, BoxError>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    const VERSION: u8 = 0x05;
+    const NO_AUTHENTICATION: u8 = 0x00;
+    const USERNAME_PASSWORD: u8 = 0x02;
+
+    let host = uri
+        .host()
+        .ok_or("the URL to connect to has no host")?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let default_port = if uri.scheme_str() == Some("https") {
+        443
+    } else {
+        80
+    };
+    let port = uri.port_u16().unwrap_or(default_port);
+
+    let method = if credentials.is_some() {
+        USERNAME_PASSWORD
+    } else {
+        NO_AUTHENTICATION
+    };
+    stream.write_all(&[VERSION, 1, method]).await?;
+    let mut reply = [0; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != VERSION {
+        return Err("the proxy isn't a SOCKS5 proxy".into());
+    }
+    if reply[1] != method {
+        return Err("the SOCKS5 proxy doesn't accept the authentication method".into());
+    }
+    if let Some((username, password)) = credentials {
+        let (username, password) = (username.as_bytes(), password.as_bytes());
+        let (Ok(username_len), Ok(password_len)) =
+            (u8::try_from(username.len()), u8::try_from(password.len()))
+        else {
+            return Err("SOCKS5 usernames and passwords can't be longer than 255 bytes".into());
+        };
+        let mut request = vec![0x01, username_len];
+        request.extend_from_slice(username);
+        request.push(password_len);
+        request.extend_from_slice(password);
+        stream.write_all(&request).await?;
+        stream.read_exact(&mut reply).await?;
+        if reply[1] != 0x00 {
+            return Err("the SOCKS5 proxy rejected the username and password".into());
+        }
+    }
+
+    let mut request = vec![VERSION, 0x01, 0x00];
+    let address = match host.parse::<IpAddr>() {
+        Ok(address) => Some(address),
+        Err(_) if remote_dns => None,
+        Err(_) => Some(
+            tokio::net::lookup_host((host, port))
+                .await?
+                .next()
+                .ok_or_else(|| format!("no addresses were found for `{host}`"))?
+                .ip(),
+        ),
+    };
+    match address {
+        Some(IpAddr::V4(address)) => {
+            request.push(0x01);
+            request.extend_from_slice(&address.octets());
+        }
+        Some(IpAddr::V6(address)) => {
+            request.push(0x04);
+            request.extend_from_slice(&address.octets());
+        }
+        None => {
+            let host_len = u8::try_from(host.len())
+                .map_err(|_| "host names can't be longer than 255 bytes")?;
+            request.push(0x03);
+            request.push(host_len);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0x00 {
+        let reason = match reply[1] {
+            0x01 => "general failure",
+            0x02 => "connection not allowed by ruleset",
+            0x03 => "network unreachable",
+            0x04 => "host unreachable",
+            0x05 => "connection refused",
+            0x06 => "TTL expired",
+            0x07 => "command not supported",
+            0x08 => "address type not supported",
+            _ => "unknown error",
+        };
+        return Err(
+            format!("the SOCKS5 proxy failed to connect to `{host}:{port}` ({reason})").into(),
+        );
+    }
+    // Skip the address that the proxy bound to
+    let bound_address_len = match reply[3] {
+        0x01 => 4,
+        0x04 => 16,
+        0x03 => usize::from(stream.read_u8().await?),
+        _ => return Err("the SOCKS5 proxy replied with an unknown address type".into()),
+    };
+    let mut bound_address = vec![0; bound_address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+    Ok(stream)
+}
@@ -0,0 +1,330 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Limits the number of requests that connectors have in flight to each host.
+
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::http::{Http2Settings, HttpConnectorFuture, PoolSettings};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Queues requests once the maximum number of requests are in flight to their host.
+#[derive(Clone, Debug)]
+pub(crate) struct RequestLimit {
+    max_requests_per_host: usize,
+    queue_timeout: Option<(SharedAsyncSleep, Duration)>,
+    hosts: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl RequestLimit {
+    pub(crate) fn new(
+        max_requests_per_host: usize,
+        queue_timeout: Option<(SharedAsyncSleep, Duration)>,
+    ) -> Self {
+        Self {
+            max_requests_per_host,
+            queue_timeout,
+            hosts: Default::default(),
+        }
+    }
+
+    /// Returns the limit for the given settings, if they limit the requests in flight.
+    ///
+    /// Every request to a host is multiplexed over the same HTTP/2 connection, so the maximum
+    /// number of concurrent HTTP/2 streams is enforced by limiting the requests in flight too.
+    pub(crate) fn from_settings(
+        pool: Option<&PoolSettings>,
+        http2: Option<&Http2Settings>,
+        sleep_impl: Option<SharedAsyncSleep>,
+    ) -> Option<Self> {
+        let max_requests_per_host = [
+            pool.and_then(PoolSettings::max_requests_per_host),
+            http2
+                .and_then(Http2Settings::max_concurrent_streams)
+                .map(|max| max as usize),
+        ]
+        .into_iter()
+        .flatten()
+        .min()?;
+        let queue_timeout = pool
+            .and_then(PoolSettings::request_queue_timeout)
+            .map(|duration| {
+                let sleep_impl = sleep_impl.expect(
+                    "a sleep impl must be provided in order to have a request queue timeout",
+                );
+                (sleep_impl, duration)
+            });
+        Some(Self::new(max_requests_per_host, queue_timeout))
+    }
+
+    fn semaphore(&self, uri: &str) -> Option<Arc<Semaphore>> {
+        let uri: http::Uri = uri.parse().ok()?;
+        let host = format!(
+            "{}://{}",
+            uri.scheme_str().unwrap_or_default(),
+            uri.authority()?.as_str()
+        );
+        let mut hosts = self.hosts.lock().unwrap();
+        Some(
+            hosts
+                .entry(host)
+                .or_insert_with(|| Arc::new(Semaphore::new(self.max_requests_per_host)))
+                .clone(),
+        )
+    }
+
+    /// Waits for the host of `uri` to have capacity before calling `send` to send the request.
+    ///
+    /// The capacity is held until the response body has been read completely, or dropped. Time
+    /// spent queued doesn't count against the connector's timeouts, since the request isn't sent
+    /// until `send` is called.
+    pub(crate) fn limit<F>(&self, uri: &str, send: F) -> HttpConnectorFuture
+    where
+        F: FnOnce() -> HttpConnectorFuture + Send + 'static,
+    {
+        let Some(semaphore) = self.semaphore(uri) else {
+            return send();
+        };
+        let queue_timeout = self.queue_timeout.clone();
+        HttpConnectorFuture::new(async move {
+            let acquiring = semaphore.acquire_owned();
+            let permit = match queue_timeout {
+                Some((sleep, duration)) => Timeout::new(acquiring, sleep.sleep(duration))
+                    .await
+                    .map_err(|_| ConnectorError::timeout(QueueTimeoutError { duration }.into()))?,
+                None => acquiring.await,
+            }
+            .expect("the semaphore is never closed");
+            let mut response = send().await?;
+            let body = mem::replace(response.body_mut(), SdkBody::taken());
+            *response.body_mut() = hold_until_read(body, permit);
+            Ok(response)
+        })
+    }
+}
+
+/// Holds `permit` until `body` has been read completely, or dropped.
+fn hold_until_read(body: SdkBody, permit: OwnedSemaphorePermit) -> SdkBody {
+    if http_body_0_4::Body::is_end_stream(&body) {
+        return body;
+    }
+    SdkBody::from_body_0_4(PermitBody {
+        permit: Some(permit),
+        inner: body,
+    })
+}
+
+pin_project_lite::pin_project! {
+    /// A response body that holds a request limit permit until it has been read completely.
+    struct PermitBody<B> {
+        permit: Option<OwnedSemaphorePermit>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> http_body_0_4::Body for PermitBody<B>
+where
+    B: http_body_0_4::Body<Data = Bytes, Error = BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        let poll = this.inner.poll_data(cx);
+        if let Poll::Ready(None | Some(Err(_))) = &poll {
+            this.permit.take();
+        }
+        poll
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Error returned when a request was queued for longer than the request queue timeout.
+#[derive(Debug)]
+struct QueueTimeoutError {
+    duration: Duration,
+}
+
+impl fmt::Display for QueueTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the request was queued for longer than the request queue timeout ({:?}) waiting for other requests to the same host to complete",
+            self.duration
+        )
+    }
+}
+
+impl Error for QueueTimeoutError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn response() -> HttpConnectorFuture {
+        HttpConnectorFuture::ready(Ok(HttpResponse::new(
+            200.try_into().unwrap(),
+            SdkBody::empty(),
+        )))
+    }
+
+    #[tokio::test]
+    async fn queues_requests_over_the_limit() {
+        let limit = RequestLimit::new(1, None);
+        let sent = Arc::new(AtomicUsize::new(0));
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        let first = limit.limit("https://s3.amazonaws.com/a", {
+            let sent = sent.clone();
+            move || {
+                sent.fetch_add(1, Ordering::Relaxed);
+                HttpConnectorFuture::new(async move {
+                    let _ = released.await;
+                    response().await
+                })
+            }
+        });
+        let first = tokio::spawn(first);
+        tokio::task::yield_now().await;
+        let second = tokio::spawn(limit.limit("https://s3.amazonaws.com/b", {
+            let sent = sent.clone();
+            move || {
+                sent.fetch_add(1, Ordering::Relaxed);
+                response()
+            }
+        }));
+        // Requests to other hosts aren't limited
+        limit
+            .limit("https://sts.amazonaws.com/", response)
+            .await
+            .expect("success");
+        tokio::task::yield_now().await;
+        assert_eq!(1, sent.load(Ordering::Relaxed));
+
+        release.send(()).unwrap();
+        first.await.unwrap().expect("success");
+        second.await.unwrap().expect("success");
+        assert_eq!(2, sent.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn holds_the_limit_until_the_response_body_is_read() {
+        let limit = RequestLimit::new(1, None);
+        let sent = Arc::new(AtomicUsize::new(0));
+        let send = {
+            let sent = sent.clone();
+            move || {
+                sent.fetch_add(1, Ordering::Relaxed);
+                HttpConnectorFuture::ready(Ok(HttpResponse::new(
+                    200.try_into().unwrap(),
+                    SdkBody::from("response body"),
+                )))
+            }
+        };
+        let first = limit
+            .limit("https://s3.amazonaws.com/a", send.clone())
+            .await
+            .expect("success");
+        let second = tokio::spawn(limit.limit("https://s3.amazonaws.com/b", send));
+        tokio::task::yield_now().await;
+        // The first response was received, but its body hasn't been read yet
+        assert_eq!(1, sent.load(Ordering::Relaxed));
+
+        let body = ByteStream::new(first.into_body()).collect().await.unwrap();
+        assert_eq!(&b"response body"[..], body.into_bytes());
+        second.await.unwrap().expect("success");
+        assert_eq!(2, sent.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn releases_the_limit_when_the_response_body_is_dropped() {
+        let limit = RequestLimit::new(1, None);
+        let first = limit
+            .limit("https://s3.amazonaws.com/", || {
+                HttpConnectorFuture::ready(Ok(HttpResponse::new(
+                    200.try_into().unwrap(),
+                    SdkBody::from("response body"),
+                )))
+            })
+            .await
+            .expect("success");
+        let semaphore = limit.semaphore("https://s3.amazonaws.com/").unwrap();
+        assert_eq!(0, semaphore.available_permits());
+        drop(first);
+        assert_eq!(1, semaphore.available_permits());
+    }
+
+    #[test]
+    fn limit_from_settings() {
+        let pool = PoolSettings::builder().max_requests_per_host(10).build();
+        let http2 = Http2Settings::builder().max_concurrent_streams(4).build();
+        let unlimited_http2 = Http2Settings::builder().build();
+        let max_requests_per_host = |pool, http2| {
+            RequestLimit::from_settings(pool, http2, None).map(|limit| limit.max_requests_per_host)
+        };
+        assert_eq!(None, max_requests_per_host(None, None));
+        assert_eq!(None, max_requests_per_host(None, Some(&unlimited_http2)));
+        assert_eq!(Some(10), max_requests_per_host(Some(&pool), None));
+        assert_eq!(Some(4), max_requests_per_host(None, Some(&http2)));
+        assert_eq!(Some(4), max_requests_per_host(Some(&pool), Some(&http2)));
+    }
+
+    #[tokio::test]
+    async fn times_out_queued_requests() {
+        let limit = RequestLimit::new(
+            1,
+            Some((
+                SharedAsyncSleep::new(TokioSleep::new()),
+                Duration::from_millis(10),
+            )),
+        );
+        let _in_flight = tokio::spawn(limit.limit("https://s3.amazonaws.com/", || {
+            HttpConnectorFuture::new(std::future::pending())
+        }));
+        tokio::task::yield_now().await;
+        let err = limit
+            .limit("https://s3.amazonaws.com/", response)
+            .await
+            .expect_err("timed out");
+        assert!(err.is_timeout(), "{err:?}");
+        let message = DisplayErrorContext(&err).to_string();
+        assert!(message.contains("request queue timeout"), "{message}");
+    }
+}
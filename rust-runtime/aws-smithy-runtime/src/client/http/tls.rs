@@ -0,0 +1,350 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! TLS configuration for HTTP clients.
+//!
+//! # Examples
+//!
+//! Trust an internal CA in addition to the platform's native root certificates:
+//!
+//! ```no_run
+//! use aws_smithy_runtime::client::http::tls::TrustStore;
+//!
+//! let trust_store = TrustStore::default()
+//!     .with_pem_certificate_file("/etc/ssl/internal-ca.pem")
+//!     .unwrap();
+//! ```
+//!
+//! Only trust certificates issued by an internal CA:
+//!
+//! ```no_run
+//! use aws_smithy_runtime::client::http::tls::TrustStore;
+//!
+//! let trust_store = TrustStore::empty()
+//!     .with_pem_certificate_file("/etc/ssl/internal-ca.pem")
+//!     .unwrap();
+//! ```
+
+use aws_smithy_types::base64;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+const PEM_CERTIFICATE_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const PEM_CERTIFICATE_END: &str = "-----END CERTIFICATE-----";
+
+/// The TLS implementation used by an HTTP client.
+///
+/// Only the hyper 1.x client supports selecting a TLS provider. The hyper 0.14.x client
+/// always uses rustls with the ring cryptography provider.
+#[cfg(feature = "connector-hyper-1-x")]
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TlsProvider {
+    /// [rustls](https://github.com/rustls/rustls), with the given cryptography provider.
+    Rustls(CryptoMode),
+    /// The platform's TLS implementation, through [native-tls](https://github.com/sfackler/rust-native-tls):
+    /// SChannel on Windows, Security.framework on macOS and iOS, and OpenSSL elsewhere.
+    ///
+    /// TLS 1.2 or later is negotiated, with the cipher suites that the platform enables. The
+    /// platform also decides how sessions are resumed, so [`SessionResumption`] and TLS key
+    /// logging don't apply to it. Requires the `tls-native-tls` feature.
+    #[cfg(feature = "tls-native-tls")]
+    NativeTls,
+    /// [s2n-tls](https://github.com/aws/s2n-tls), with the cipher suites of its `default_tls13`
+    /// security policy.
+    ///
+    /// s2n-tls uses AWS-LC or OpenSSL for cryptography, depending on how `s2n-tls-sys` is built,
+    /// so it can negotiate TLS with a FIPS validated library. It's only supported on Unix
+    /// platforms. [`SessionResumption`] and TLS key logging don't apply to it. Requires the
+    /// `tls-s2n-tls` feature.
+    #[cfg(feature = "tls-s2n-tls")]
+    S2nTls,
+}
+
+#[cfg(feature = "connector-hyper-1-x")]
+impl Default for TlsProvider {
+    fn default() -> Self {
+        Self::Rustls(CryptoMode::Ring)
+    }
+}
+
+/// The cryptography provider used by rustls.
+#[cfg(feature = "connector-hyper-1-x")]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CryptoMode {
+    /// Cryptography from [ring](https://github.com/briansmith/ring).
+    Ring,
+    /// Cryptography from [AWS-LC](https://github.com/aws/aws-lc-rs).
+    ///
+    /// Requires the `rustls-aws-lc` feature.
+    #[cfg(feature = "rustls-aws-lc")]
+    AwsLc,
+    /// Cryptography from the FIPS 140-3 validated build of [AWS-LC](https://github.com/aws/aws-lc-rs).
+    ///
+    /// Only FIPS-approved cipher suites and key exchange groups are negotiated. Building it
+    /// requires additional tooling, see the `aws-lc-fips-sys` documentation. Requires the
+    /// `rustls-aws-lc-fips` feature.
+    #[cfg(feature = "rustls-aws-lc-fips")]
+    AwsLcFips,
+}
+
+/// How TLS sessions are resumed when reconnecting to a server.
+///
+/// Resuming a session skips the key exchange and certificate verification of a full TLS
+/// handshake, which cuts the latency of reconnecting to hosts that were recently connected to.
+/// Sessions are resumed with TLS 1.3 session tickets, and TLS 1.2 session IDs and tickets.
+///
+/// By default, up to 256 sessions are cached in memory.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct SessionResumption {
+    max_sessions: usize,
+}
+
+impl Default for SessionResumption {
+    fn default() -> Self {
+        Self::in_memory(Self::DEFAULT_MAX_SESSIONS)
+    }
+}
+
+impl SessionResumption {
+    /// The number of sessions that are cached by default.
+    pub const DEFAULT_MAX_SESSIONS: usize = 256;
+
+    /// Caches up to `max_sessions` sessions in memory, so that they can be resumed.
+    ///
+    /// The least recently used sessions are evicted once the cache is full. A `max_sessions`
+    /// of zero disables session resumption.
+    pub fn in_memory(max_sessions: usize) -> Self {
+        Self { max_sessions }
+    }
+
+    /// Disables session resumption, so that every connection makes a full TLS handshake.
+    pub fn disabled() -> Self {
+        Self::in_memory(0)
+    }
+
+    /// Returns the number of sessions that are cached.
+    pub fn max_sessions(&self) -> usize {
+        self.max_sessions
+    }
+
+    /// Returns true if sessions are resumed.
+    pub fn is_enabled(&self) -> bool {
+        self.max_sessions > 0
+    }
+}
+
+/// The root certificates that server certificates are verified against.
+///
+/// The default trust store contains the platform's native root certificates. Additional root
+/// certificates can be added to it, and [`TrustStore::empty`] creates a trust store that only
+/// contains the root certificates that are added to it.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct TrustStore {
+    native_roots: bool,
+    certificates: Vec<Vec<u8>>,
+}
+
+impl Default for TrustStore {
+    fn default() -> Self {
+        Self {
+            native_roots: true,
+            certificates: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for TrustStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrustStore")
+            .field("native_roots", &self.native_roots)
+            .field("certificates", &self.certificates.len())
+            .finish()
+    }
+}
+
+impl TrustStore {
+    /// Creates a trust store that doesn't contain any root certificates.
+    pub fn empty() -> Self {
+        Self {
+            native_roots: false,
+            certificates: Vec::new(),
+        }
+    }
+
+    /// Sets whether the platform's native root certificates are trusted.
+    pub fn with_native_roots(mut self, native_roots: bool) -> Self {
+        self.native_roots = native_roots;
+        self
+    }
+
+    /// Adds the root certificates in a PEM-encoded bundle.
+    ///
+    /// Returns an error if the bundle doesn't contain any certificates, or if one of them isn't
+    /// validly encoded. Anything outside of `CERTIFICATE` sections is ignored.
+    pub fn with_pem_certificate(
+        mut self,
+        pem: impl AsRef<[u8]>,
+    ) -> Result<Self, InvalidCertificateError> {
+        let certificates = parse_pem_certificates(pem.as_ref())
+            .map_err(|reason| InvalidCertificateError { path: None, reason })?;
+        self.certificates.extend(certificates);
+        Ok(self)
+    }
+
+    /// Adds the root certificates in a PEM-encoded bundle file, like a CA bundle.
+    ///
+    /// Returns an error if the file can't be read, if it doesn't contain any certificates, or
+    /// if one of them isn't validly encoded.
+    pub fn with_pem_certificate_file(
+        self,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, InvalidCertificateError> {
+        let path = path.as_ref();
+        let pem = std::fs::read(path).map_err(|err| InvalidCertificateError {
+            path: Some(path.to_path_buf()),
+            reason: format!("failed to read the file: {err}"),
+        })?;
+        self.with_pem_certificate(pem)
+            .map_err(|err| InvalidCertificateError {
+                path: Some(path.to_path_buf()),
+                ..err
+            })
+    }
+
+    /// Adds a DER-encoded root certificate.
+    ///
+    /// The certificate is parsed when the TLS configuration is created, and it's skipped with a
+    /// warning if it isn't valid.
+    pub fn with_der_certificate(mut self, der: impl Into<Vec<u8>>) -> Self {
+        self.certificates.push(der.into());
+        self
+    }
+
+    /// Returns true if the platform's native root certificates are trusted.
+    pub fn native_roots(&self) -> bool {
+        self.native_roots
+    }
+
+    /// Returns the DER-encoded root certificates that were added to this trust store.
+    pub fn certificates(&self) -> impl Iterator<Item = &[u8]> {
+        self.certificates
+            .iter()
+            .map(|certificate| certificate.as_slice())
+    }
+}
+
+fn parse_pem_certificates(pem: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    let pem = std::str::from_utf8(pem).map_err(|_| "the PEM isn't valid UTF-8".to_string())?;
+    let mut certificates = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = rest.find(PEM_CERTIFICATE_BEGIN) {
+        rest = &rest[start + PEM_CERTIFICATE_BEGIN.len()..];
+        let end = rest
+            .find(PEM_CERTIFICATE_END)
+            .ok_or_else(|| format!("a certificate is missing `{PEM_CERTIFICATE_END}`"))?;
+        let encoded: String = rest[..end]
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+        let certificate = base64::decode(encoded)
+            .map_err(|err| format!("a certificate isn't valid base64: {err}"))?;
+        certificates.push(certificate);
+        rest = &rest[end + PEM_CERTIFICATE_END.len()..];
+    }
+    if certificates.is_empty() {
+        return Err("no certificates were found".into());
+    }
+    Ok(certificates)
+}
+
+/// Error returned when root certificates can't be added to a [`TrustStore`].
+#[derive(Debug)]
+pub struct InvalidCertificateError {
+    path: Option<PathBuf>,
+    reason: String,
+}
+
+impl fmt::Display for InvalidCertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(
+                f,
+                "invalid PEM certificates in `{}`: {}",
+                path.display(),
+                self.reason
+            ),
+            None => write!(f, "invalid PEM certificates: {}", self.reason),
+        }
+    }
+}
+
+impl Error for InvalidCertificateError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_pem_bundles() {
+        let pem = "\
+            # First certificate\n\
+            -----BEGIN CERTIFICATE-----\n\
+            AQID\n\
+            BAU=\n\
+            -----END CERTIFICATE-----\n\
+            -----BEGIN CERTIFICATE-----\n\
+            Bgc=\n\
+            -----END CERTIFICATE-----\n";
+        let trust_store = TrustStore::empty().with_pem_certificate(pem).unwrap();
+        assert!(!trust_store.native_roots());
+        assert_eq!(
+            vec![&[1, 2, 3, 4, 5][..], &[6, 7][..]],
+            trust_store.certificates().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn session_resumption_can_be_disabled() {
+        assert!(SessionResumption::default().is_enabled());
+        assert_eq!(
+            SessionResumption::DEFAULT_MAX_SESSIONS,
+            SessionResumption::default().max_sessions()
+        );
+        assert!(!SessionResumption::disabled().is_enabled());
+        assert!(!SessionResumption::in_memory(0).is_enabled());
+    }
+
+    #[test]
+    fn rejects_invalid_pem() {
+        let err = TrustStore::default()
+            .with_pem_certificate("not a certificate")
+            .expect_err("no certificates");
+        assert_eq!(
+            "invalid PEM certificates: no certificates were found",
+            err.to_string()
+        );
+        TrustStore::default()
+            .with_pem_certificate("-----BEGIN CERTIFICATE-----\nAQID\n")
+            .expect_err("unterminated certificate");
+        TrustStore::default()
+            .with_pem_certificate("-----BEGIN CERTIFICATE-----\n!!!!\n-----END CERTIFICATE-----")
+            .expect_err("invalid base64");
+    }
+
+    #[test]
+    fn reports_the_file_that_failed() {
+        let err = TrustStore::default()
+            .with_pem_certificate_file("/does/not/exist.pem")
+            .expect_err("missing file");
+        assert!(
+            err.to_string()
+                .starts_with("invalid PEM certificates in `/does/not/exist.pem`: failed to read"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Adapters between HTTP clients and [tower](https://docs.rs/tower) services.
+//!
+//! [`TowerHttpClient`] sends requests with a tower [`Service`], and [`HttpConnectorService`]
+//! exposes a [`HttpConnector`] as a tower [`Service`]. Together, they allow tower middleware,
+//! like load shedding, metrics, or tracing, to be layered around a connector:
+//!
+//! ```no_run
+//! # #[cfg(feature = "tls-rustls")]
+//! # fn example() {
+//! use aws_smithy_runtime::client::http::hyper_014::HyperConnector;
+//! use aws_smithy_runtime::client::http::tower::{HttpConnectorService, TowerHttpClient};
+//!
+//! let connector = HyperConnector::builder().build_https();
+//! // Layer tower middleware around the service, with `tower::ServiceBuilder` for example
+//! let service = HttpConnectorService::new(connector);
+//! let http_client = TowerHttpClient::new(service);
+//! # }
+//! ```
+
+use aws_smithy_async::future::BoxFuture;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use std::fmt;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// A [`HttpClient`] that sends requests with a tower [`Service`].
+///
+/// The service is cloned for every request, so services that can't be cheaply cloned should
+/// be wrapped in a buffer, like `tower::buffer::Buffer`. Service errors that are a
+/// [`ConnectorError`] are returned as is, and other errors are returned as
+/// [`ConnectorError::other`].
+///
+/// The service is used regardless of the [`HttpConnectorSettings`] that the client is asked
+/// for, so connector timeouts need to be configured on the service itself.
+#[derive(Clone)]
+pub struct TowerHttpClient<S> {
+    service: S,
+}
+
+impl<S> fmt::Debug for TowerHttpClient<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TowerHttpClient").finish()
+    }
+}
+
+impl<S> TowerHttpClient<S> {
+    /// Creates a HTTP client that sends requests with `service`.
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, B> HttpConnector for TowerHttpClient<S>
+where
+    S: Service<http::Request<SdkBody>, Response = http::Response<B>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+    B: Into<SdkBody>,
+{
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let request = match request.try_into_http02x() {
+            Ok(request) => request,
+            Err(err) => {
+                return HttpConnectorFuture::ready(Err(ConnectorError::other(err.into(), None)));
+            }
+        };
+        let mut service = self.service.clone();
+        HttpConnectorFuture::new(async move {
+            std::future::poll_fn(|cx| service.poll_ready(cx))
+                .await
+                .map_err(downcast_error)?;
+            let response = service.call(request).await.map_err(downcast_error)?;
+            HttpResponse::try_from(response.map(Into::into))
+                .map_err(|err| ConnectorError::other(err.into(), None))
+        })
+    }
+}
+
+impl<S, B> HttpClient for TowerHttpClient<S>
+where
+    S: Service<http::Request<SdkBody>, Response = http::Response<B>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+    B: Into<SdkBody>,
+{
+    fn http_connector(
+        &self,
+        _settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        self.clone().into_shared()
+    }
+}
+
+fn downcast_error(err: impl Into<BoxError>) -> ConnectorError {
+    match err.into().downcast::<ConnectorError>() {
+        Ok(connector_error) => *connector_error,
+        Err(err) => ConnectorError::other(err, None),
+    }
+}
+
+/// A tower [`Service`] that sends requests with a [`HttpConnector`].
+///
+/// The service is always ready, and it returns the connector's errors as is.
+#[derive(Clone, Debug)]
+pub struct HttpConnectorService {
+    connector: SharedHttpConnector,
+}
+
+impl HttpConnectorService {
+    /// Creates a service that sends requests with `connector`.
+    pub fn new(connector: impl HttpConnector + 'static) -> Self {
+        Self {
+            connector: connector.into_shared(),
+        }
+    }
+}
+
+impl Service<http::Request<SdkBody>> for HttpConnectorService {
+    type Response = http::Response<SdkBody>;
+    type Error = ConnectorError;
+    type Future = BoxFuture<'static, Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<SdkBody>) -> Self::Future {
+        let connector = self.connector.clone();
+        Box::pin(async move {
+            let request = HttpRequest::try_from(request)
+                .map_err(|err| ConnectorError::other(err.into(), None))?;
+            connector
+                .call(request)
+                .await?
+                .try_into_http02x()
+                .map_err(|err| ConnectorError::other(err.into(), None))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use std::future::{ready, Ready};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts the requests that pass through it, like a metrics middleware would.
+    #[derive(Clone)]
+    struct CountRequests<S> {
+        inner: S,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl<S, R> Service<R> for CountRequests<S>
+    where
+        S: Service<R>,
+    {
+        type Response = S::Response;
+        type Error = S::Error;
+        type Future = S::Future;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, request: R) -> Self::Future {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.inner.call(request)
+        }
+    }
+
+    #[derive(Debug)]
+    struct EchoConnector;
+
+    impl HttpConnector for EchoConnector {
+        fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+            let mut response = HttpResponse::new(200.try_into().unwrap(), SdkBody::empty());
+            response
+                .headers_mut()
+                .insert("x-uri", request.uri().to_string());
+            HttpConnectorFuture::ready(Ok(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn layers_tower_middleware_around_a_connector() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let client = TowerHttpClient::new(CountRequests {
+            inner: HttpConnectorService::new(EchoConnector),
+            count: count.clone(),
+        });
+        let connector = client.http_connector(
+            &HttpConnectorSettings::default(),
+            &RuntimeComponentsBuilder::for_tests().build().unwrap(),
+        );
+        for _ in 0..2 {
+            let request = HttpRequest::get("https://example.com/path").unwrap();
+            let response = connector.call(request).await.expect("success");
+            assert_eq!(200, response.status().as_u16());
+            assert_eq!(
+                Some("https://example.com/path"),
+                response.headers().get("x-uri")
+            );
+        }
+        assert_eq!(2, count.load(Ordering::Relaxed));
+    }
+
+    #[derive(Clone)]
+    struct FailingService(fn() -> BoxError);
+
+    impl Service<http::Request<SdkBody>> for FailingService {
+        type Response = http::Response<SdkBody>;
+        type Error = BoxError;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<SdkBody>) -> Self::Future {
+            ready(Err((self.0)()))
+        }
+    }
+
+    #[tokio::test]
+    async fn converts_service_errors() {
+        let timeout = TowerHttpClient::new(FailingService(|| {
+            ConnectorError::timeout("took too long".into()).into()
+        }));
+        let err = HttpConnector::call(&timeout, HttpRequest::get("https://example.com").unwrap())
+            .await
+            .expect_err("failed");
+        assert!(err.is_timeout(), "{err:?}");
+
+        let other = TowerHttpClient::new(FailingService(|| "overloaded".into()));
+        let err = HttpConnector::call(&other, HttpRequest::get("https://example.com").unwrap())
+            .await
+            .expect_err("failed");
+        assert!(err.is_other(), "{err:?}");
+        assert!(DisplayErrorContext(&err).to_string().contains("overloaded"));
+    }
+}
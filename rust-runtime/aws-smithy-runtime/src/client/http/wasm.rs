@@ -0,0 +1,518 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! HTTP client for WebAssembly.
+//!
+//! On `wasm32-unknown-unknown`, requests are sent with JavaScript's `fetch`, which is available in
+//! browsers, web workers, and most edge runtimes. On WASI components, like `wasm32-wasip2`,
+//! requests are sent with [wasi-http](https://github.com/WebAssembly/wasi-http). Response bodies
+//! are streamed in both cases.
+//!
+//! When the `connector-wasm` feature is enabled, this client is the default HTTP client on
+//! WebAssembly targets.
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
+    SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use std::error::Error;
+use std::fmt;
+use std::pin::pin;
+use std::time::Duration;
+
+/// Creates a HTTP client for the WebAssembly target that's being built for.
+pub fn default_client() -> Option<SharedHttpClient> {
+    tracing::trace!("creating a new default WebAssembly HTTP client");
+    Some(WasmHttpClient::new().into_shared())
+}
+
+/// [`HttpClient`] for WebAssembly.
+///
+/// With `fetch`, connect and read timeouts aren't supported, so operation timeouts should be
+/// used to bound how long requests take, and requests with streaming bodies are buffered before
+/// they're sent. With wasi-http, connect timeouts and read timeouts are passed on to the host.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct WasmHttpClient;
+
+impl WasmHttpClient {
+    /// Creates a new WebAssembly HTTP client.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl HttpClient for WasmHttpClient {
+    fn http_connector(
+        &self,
+        settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        WasmConnector {
+            connect_timeout: settings.connect_timeout(),
+            read_timeout: settings.read_timeout(),
+        }
+        .into_shared()
+    }
+}
+
+#[derive(Debug)]
+struct WasmConnector {
+    #[cfg_attr(target_os = "unknown", allow(dead_code))]
+    connect_timeout: Option<Duration>,
+    #[cfg_attr(target_os = "unknown", allow(dead_code))]
+    read_timeout: Option<Duration>,
+}
+
+impl HttpConnector for WasmConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        #[cfg(target_os = "unknown")]
+        let future = HttpConnectorFuture::new(fetch::SendFuture(fetch::send(request)));
+        #[cfg(target_os = "wasi")]
+        let future = HttpConnectorFuture::new(wasi_http::send(
+            request,
+            self.connect_timeout,
+            self.read_timeout,
+        ));
+        future
+    }
+}
+
+/// Error reported by the WebAssembly host.
+#[derive(Debug)]
+struct HostError(String);
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for HostError {}
+
+/// Calls `write` with each chunk of a request body.
+async fn for_each_chunk(
+    body: SdkBody,
+    mut write: impl FnMut(&[u8]) -> Result<(), ConnectorError>,
+) -> Result<(), ConnectorError> {
+    use http_body_0_4::Body;
+
+    if let Some(bytes) = body.bytes() {
+        return write(bytes);
+    }
+    let mut body = pin!(body);
+    while let Some(chunk) = std::future::poll_fn(|cx| body.as_mut().poll_data(cx)).await {
+        write(&chunk.map_err(ConnectorError::user)?)?;
+    }
+    Ok(())
+}
+
+fn into_response(
+    status: u16,
+    headers: impl IntoIterator<Item = (String, Vec<u8>)>,
+    body: SdkBody,
+) -> Result<HttpResponse, ConnectorError> {
+    let mut builder = http::Response::builder().status(status);
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .body(body)
+        .map_err(|err| ConnectorError::other(err.into(), None))?;
+    HttpResponse::try_from(response).map_err(|err| ConnectorError::other(err.into(), None))
+}
+
+#[cfg(target_os = "unknown")]
+mod fetch {
+    use super::*;
+    use js_sys::{Array, Promise, Reflect, Uint8Array};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{ready, Context, Poll};
+    use wasm_bindgen::prelude::wasm_bindgen;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{AbortController, Headers, ReadableStreamDefaultReader, Request, RequestInit};
+
+    #[wasm_bindgen]
+    extern "C" {
+        // Bound on the global object so that it works in browsers, web workers, and edge runtimes
+        #[wasm_bindgen(js_name = fetch)]
+        fn fetch_with_request(request: &Request) -> Promise;
+    }
+
+    /// Makes a future that holds JavaScript values `Send`.
+    pub(super) struct SendFuture<F>(pub(super) F);
+
+    // SAFETY: the `rt-wasm` feature of aws-smithy-async rejects multi-threaded WebAssembly with a
+    // compile error, so JavaScript values are only ever accessed from the thread that created them.
+    unsafe impl<F> Send for SendFuture<F> {}
+
+    impl<F: Future> Future for SendFuture<F> {
+        type Output = F::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            // SAFETY: the inner future is never moved out of `self`
+            unsafe { self.map_unchecked_mut(|this| &mut this.0) }.poll(cx)
+        }
+    }
+
+    fn js_error(value: JsValue) -> BoxError {
+        let message = match value.dyn_ref::<js_sys::Error>() {
+            Some(err) => String::from(err.message()),
+            None => format!("{value:?}"),
+        };
+        HostError(message).into()
+    }
+
+    /// Aborts the request when it's dropped, which also cancels reading the response body.
+    struct AbortOnDrop(AbortController);
+
+    impl Drop for AbortOnDrop {
+        fn drop(&mut self) {
+            // Aborting a request that has completed does nothing
+            self.0.abort();
+        }
+    }
+
+    pub(super) async fn send(request: HttpRequest) -> Result<HttpResponse, ConnectorError> {
+        let headers = Headers::new().map_err(|err| ConnectorError::other(js_error(err), None))?;
+        for (name, value) in request.headers().iter() {
+            headers
+                .append(name, value)
+                .map_err(|err| ConnectorError::user(js_error(err)))?;
+        }
+        let controller =
+            AbortController::new().map_err(|err| ConnectorError::other(js_error(err), None))?;
+        let init = RequestInit::new();
+        init.set_method(request.method());
+        init.set_headers(&headers);
+        init.set_signal(Some(&controller.signal()));
+        let uri = request.uri().to_string();
+
+        // Streaming request bodies aren't widely supported by `fetch`, so they're buffered
+        let mut body = Vec::new();
+        for_each_chunk(request.into_body(), |chunk| {
+            body.extend_from_slice(chunk);
+            Ok(())
+        })
+        .await?;
+        if !body.is_empty() {
+            init.set_body(&Uint8Array::from(body.as_slice()));
+        }
+        let request = Request::new_with_str_and_init(&uri, &init)
+            .map_err(|err| ConnectorError::user(js_error(err)))?;
+
+        let abort = AbortOnDrop(controller);
+        // `fetch` only rejects when the request couldn't be sent, or when its response couldn't
+        // be received
+        let response: web_sys::Response = JsFuture::from(fetch_with_request(&request))
+            .await
+            .map_err(|err| ConnectorError::io(js_error(err)))?
+            .dyn_into()
+            .map_err(|err| ConnectorError::other(js_error(err), None))?;
+
+        let mut response_headers = Vec::new();
+        let entries = js_sys::try_iter(response.headers().as_ref())
+            .map_err(|err| ConnectorError::other(js_error(err), None))?
+            .into_iter()
+            .flatten();
+        for entry in entries {
+            let entry: Array = entry
+                .map_err(|err| ConnectorError::other(js_error(err), None))?
+                .into();
+            if let (Some(name), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string())
+            {
+                response_headers.push((name, value.into_bytes()));
+            }
+        }
+        let reader = match response.body() {
+            Some(stream) => Some(
+                stream
+                    .get_reader()
+                    .dyn_into::<ReadableStreamDefaultReader>()
+                    .map_err(|err| ConnectorError::other(js_error(err), None))?,
+            ),
+            None => None,
+        };
+        let body = SdkBody::from_body_0_4(FetchBody {
+            reader,
+            pending: None,
+            _abort: abort,
+        });
+        into_response(response.status(), response_headers, body)
+    }
+
+    /// Streams a response body from a `ReadableStream`.
+    struct FetchBody {
+        reader: Option<ReadableStreamDefaultReader>,
+        pending: Option<JsFuture>,
+        _abort: AbortOnDrop,
+    }
+
+    // SAFETY: see `SendFuture`
+    unsafe impl Send for FetchBody {}
+    unsafe impl Sync for FetchBody {}
+
+    impl http_body_0_4::Body for FetchBody {
+        type Data = Bytes;
+        type Error = ConnectorError;
+
+        fn poll_data(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            let this = self.get_mut();
+            loop {
+                let Some(reader) = &this.reader else {
+                    return Poll::Ready(None);
+                };
+                let pending = this
+                    .pending
+                    .get_or_insert_with(|| JsFuture::from(reader.read()));
+                let result = ready!(Pin::new(pending).poll(cx));
+                this.pending = None;
+                let result = match result {
+                    Ok(result) => result,
+                    Err(err) => {
+                        this.reader = None;
+                        return Poll::Ready(Some(Err(ConnectorError::io(js_error(err)))));
+                    }
+                };
+                let done = Reflect::get(&result, &JsValue::from_str("done"))
+                    .ok()
+                    .and_then(|done| done.as_bool())
+                    .unwrap_or(true);
+                if done {
+                    this.reader = None;
+                    return Poll::Ready(None);
+                }
+                let chunk = Reflect::get(&result, &JsValue::from_str("value"))
+                    .map(|value| Uint8Array::new(&value).to_vec())
+                    .unwrap_or_default();
+                if !chunk.is_empty() {
+                    return Poll::Ready(Some(Ok(Bytes::from(chunk))));
+                }
+            }
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+
+        fn is_end_stream(&self) -> bool {
+            self.reader.is_none()
+        }
+    }
+}
+
+#[cfg(target_os = "wasi")]
+mod wasi_http {
+    use super::*;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use wasi::http::outgoing_handler;
+    use wasi::http::types::{
+        ErrorCode, Fields, IncomingBody, IncomingResponse, Method, OutgoingBody, OutgoingRequest,
+        RequestOptions, Scheme,
+    };
+    use wasi::io::streams::{InputStream, StreamError};
+
+    /// The maximum number of bytes that can be written to a stream in one call.
+    const MAX_WRITE_SIZE: usize = 4096;
+
+    /// The maximum number of bytes that are read from a response body stream in one call.
+    const READ_SIZE: u64 = 8 * 1024;
+
+    fn host_error(err: impl fmt::Debug) -> BoxError {
+        HostError(format!("{err:?}")).into()
+    }
+
+    fn to_connector_error(err: ErrorCode) -> ConnectorError {
+        match err {
+            ErrorCode::DnsTimeout | ErrorCode::DnsError(_) => ConnectorError::dns(host_error(err)),
+            ErrorCode::ConnectionTimeout => ConnectorError::connect_timeout(host_error(err)),
+            ErrorCode::ConnectionReadTimeout
+            | ErrorCode::ConnectionWriteTimeout
+            | ErrorCode::HttpResponseTimeout => ConnectorError::timeout(host_error(err)),
+            ErrorCode::ConnectionRefused
+            | ErrorCode::ConnectionTerminated
+            | ErrorCode::ConnectionLimitReached => ConnectorError::io(host_error(err)),
+            ErrorCode::HttpRequestDenied
+            | ErrorCode::HttpRequestMethodInvalid
+            | ErrorCode::HttpRequestUriInvalid => ConnectorError::user(host_error(err)),
+            err => ConnectorError::other(host_error(err), None),
+        }
+    }
+
+    fn method(method: &str) -> Method {
+        match method {
+            "GET" => Method::Get,
+            "HEAD" => Method::Head,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "CONNECT" => Method::Connect,
+            "OPTIONS" => Method::Options,
+            "TRACE" => Method::Trace,
+            "PATCH" => Method::Patch,
+            other => Method::Other(other.to_string()),
+        }
+    }
+
+    fn outgoing_request(request: &HttpRequest) -> Result<OutgoingRequest, ConnectorError> {
+        let invalid = |what: &str| ConnectorError::user(format!("invalid request {what}").into());
+        let uri: http::Uri = request
+            .uri()
+            .parse()
+            .map_err(|err: http::uri::InvalidUri| ConnectorError::user(err.into()))?;
+        let headers: Vec<(String, Vec<u8>)> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+            .collect();
+        let headers =
+            Fields::from_list(&headers).map_err(|err| ConnectorError::user(host_error(err)))?;
+        let outgoing = OutgoingRequest::new(headers);
+        outgoing
+            .set_method(&method(request.method()))
+            .map_err(|_| invalid("method"))?;
+        let scheme = match uri.scheme_str() {
+            Some("http") => Scheme::Http,
+            Some("https") | None => Scheme::Https,
+            Some(other) => Scheme::Other(other.to_string()),
+        };
+        outgoing
+            .set_scheme(Some(&scheme))
+            .map_err(|_| invalid("scheme"))?;
+        outgoing
+            .set_authority(uri.authority().map(|authority| authority.as_str()))
+            .map_err(|_| invalid("authority"))?;
+        outgoing
+            .set_path_with_query(uri.path_and_query().map(|path| path.as_str()))
+            .map_err(|_| invalid("path"))?;
+        Ok(outgoing)
+    }
+
+    fn duration_nanos(duration: Duration) -> u64 {
+        duration.as_nanos().min(u64::MAX as u128) as u64
+    }
+
+    pub(super) async fn send(
+        request: HttpRequest,
+        connect_timeout: Option<Duration>,
+        read_timeout: Option<Duration>,
+    ) -> Result<HttpResponse, ConnectorError> {
+        let outgoing = outgoing_request(&request)?;
+        let options = RequestOptions::new();
+        // Hosts that don't support a timeout reject setting it, in which case it's left unset
+        let _ = options.set_connect_timeout(connect_timeout.map(duration_nanos));
+        let _ = options.set_between_bytes_timeout(read_timeout.map(duration_nanos));
+
+        let outgoing_body = outgoing.body().map_err(|_| {
+            ConnectorError::other("the request body was already taken".into(), None)
+        })?;
+        let future_response =
+            outgoing_handler::handle(outgoing, Some(options)).map_err(to_connector_error)?;
+        {
+            let stream = outgoing_body.write().map_err(|_| {
+                ConnectorError::other("the request body was already taken".into(), None)
+            })?;
+            for_each_chunk(request.into_body(), |chunk| {
+                for chunk in chunk.chunks(MAX_WRITE_SIZE) {
+                    stream
+                        .blocking_write_and_flush(chunk)
+                        .map_err(|err| ConnectorError::io(host_error(err)))?;
+                }
+                Ok(())
+            })
+            .await?;
+        }
+        OutgoingBody::finish(outgoing_body, None).map_err(to_connector_error)?;
+
+        // wasi-http is blocking, since there's no reactor to drive the host's pollables
+        future_response.subscribe().block();
+        let response = future_response
+            .get()
+            .ok_or_else(|| ConnectorError::other("the response wasn't ready".into(), None))?
+            .map_err(|_| ConnectorError::other("the response was already taken".into(), None))?
+            .map_err(to_connector_error)?;
+
+        let status = response.status();
+        let headers = response.headers().entries();
+        let body = response.consume().map_err(|_| {
+            ConnectorError::other("the response body was already taken".into(), None)
+        })?;
+        let stream = body.stream().map_err(|_| {
+            ConnectorError::other("the response body was already taken".into(), None)
+        })?;
+        let body = SdkBody::from_body_0_4(WasiBody {
+            stream: Some(stream),
+            _body: body,
+            _response: response,
+        });
+        into_response(status, headers, body)
+    }
+
+    /// Streams a response body from a wasi-http input stream.
+    struct WasiBody {
+        // Child resources must be dropped before their parents, and fields are dropped in order
+        stream: Option<InputStream>,
+        _body: IncomingBody,
+        _response: IncomingResponse,
+    }
+
+    impl http_body_0_4::Body for WasiBody {
+        type Data = Bytes;
+        type Error = ConnectorError;
+
+        fn poll_data(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            let this = self.get_mut();
+            loop {
+                let Some(stream) = &this.stream else {
+                    return Poll::Ready(None);
+                };
+                match stream.blocking_read(READ_SIZE) {
+                    Ok(chunk) if chunk.is_empty() => continue,
+                    Ok(chunk) => return Poll::Ready(Some(Ok(Bytes::from(chunk)))),
+                    Err(StreamError::Closed) => {
+                        this.stream = None;
+                        return Poll::Ready(None);
+                    }
+                    Err(StreamError::LastOperationFailed(err)) => {
+                        this.stream = None;
+                        return Poll::Ready(Some(Err(ConnectorError::io(
+                            HostError(err.to_debug_string()).into(),
+                        ))));
+                    }
+                }
+            }
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+
+        fn is_end_stream(&self) -> bool {
+            self.stream.is_none()
+        }
+    }
+}
@@ -8,3 +8,6 @@ pub use cache::{IdentityCache, LazyCacheBuilder};
 
 /// Identity resolver implementation for "no auth".
 pub mod no_auth;
+
+/// Identity resolver for short-lived sessions that are scoped to a resource.
+pub mod session;
@@ -5,7 +5,7 @@
 
 use crate::expiring_cache::ExpiringCache;
 use aws_smithy_async::future::timeout::Timeout;
-use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
 use aws_smithy_async::time::{SharedTimeSource, TimeSource};
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::identity::{
@@ -15,10 +15,12 @@ use aws_smithy_runtime_api::client::identity::{
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::error::display::DisplayErrorContext;
 use std::collections::HashMap;
 use std::fmt;
-use std::sync::RwLock;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
 use tracing::Instrument;
 
 const DEFAULT_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
@@ -26,8 +28,10 @@ const DEFAULT_EXPIRATION: Duration = Duration::from_secs(15 * 60);
 const DEFAULT_BUFFER_TIME: Duration = Duration::from_secs(10);
 const DEFAULT_BUFFER_TIME_JITTER_FRACTION: fn() -> f64 = fastrand::f64;
 
+type IdentityHook = Arc<dyn Fn(&Identity) + Send + Sync>;
+
 /// Builder for lazy identity caching.
-#[derive(Default, Debug)]
+#[derive(Default)]
 pub struct LazyCacheBuilder {
     time_source: Option<SharedTimeSource>,
     sleep_impl: Option<SharedAsyncSleep>,
@@ -35,6 +39,30 @@ pub struct LazyCacheBuilder {
     buffer_time: Option<Duration>,
     buffer_time_jitter_fraction: Option<fn() -> f64>,
     default_expiration: Option<Duration>,
+    refresh_window: Option<f64>,
+    on_expiring: Option<IdentityHook>,
+    on_refreshed: Option<IdentityHook>,
+}
+
+impl fmt::Debug for LazyCacheBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyCacheBuilder")
+            .field("time_source", &self.time_source)
+            .field("sleep_impl", &self.sleep_impl)
+            .field("load_timeout", &self.load_timeout)
+            .field("buffer_time", &self.buffer_time)
+            .field("default_expiration", &self.default_expiration)
+            .field("refresh_window", &self.refresh_window)
+            .field(
+                "on_expiring",
+                &self.on_expiring.as_ref().map(|_| "** hook **"),
+            )
+            .field(
+                "on_refreshed",
+                &self.on_refreshed.as_ref().map(|_| "** hook **"),
+            )
+            .finish()
+    }
 }
 
 impl LazyCacheBuilder {
@@ -160,8 +188,85 @@ impl LazyCacheBuilder {
         self
     }
 
+    /// Refreshes identities ahead of their expiration, during the given fraction of their lifetime.
+    ///
+    /// For example, with a refresh window of `0.25`, an identity that is valid for an hour is
+    /// refreshed once it has less than 15 minutes left. The first request in the refresh window
+    /// resolves a new identity, while concurrent requests keep using the cached identity, so that
+    /// identities don't expire under load. If the refresh fails, the cached identity is used
+    /// until it expires.
+    ///
+    /// The refresh window should be larger than the [buffer time](Self::buffer_time), or
+    /// identities expire before they're refreshed.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fraction isn't greater than 0.0, and at most 1.0.
+    pub fn refresh_window(mut self, fraction: f64) -> Self {
+        self.set_refresh_window(Some(fraction));
+        self
+    }
+
+    /// Refreshes identities ahead of their expiration, during the given fraction of their lifetime.
+    ///
+    /// See [`refresh_window`](Self::refresh_window) for details.
+    pub fn set_refresh_window(&mut self, fraction: Option<f64>) -> &mut Self {
+        if let Some(fraction) = fraction {
+            assert!(
+                fraction > 0.0 && fraction <= 1.0,
+                "refresh_window must be greater than 0.0, and at most 1.0"
+            );
+        }
+        self.refresh_window = fraction;
+        self
+    }
+
+    /// Calls `hook` with a cached identity that is about to expire.
+    ///
+    /// The identity is about to expire once it's within the [buffer time](Self::buffer_time)
+    /// of its expiration. The hook is called when a request finds it in that state, right before
+    /// a new identity is resolved to replace it. Identities that are discarded with
+    /// [`invalidate`](ResolveCachedIdentity::invalidate) aren't passed to this hook.
+    ///
+    /// The hook is called while requests wait on the cache, so it shouldn't block.
+    pub fn on_identity_expiring(
+        mut self,
+        hook: impl Fn(&Identity) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_expiring = Some(Arc::new(hook));
+        self
+    }
+
+    /// Calls `hook` with every new identity that is resolved and added to the cache.
+    ///
+    /// The hook is called while requests wait on the cache, so it shouldn't block.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use aws_smithy_runtime::client::identity::IdentityCache;
+    ///
+    /// let identity_cache = IdentityCache::lazy()
+    ///     .on_identity_refreshed(|identity| {
+    ///         tracing::info!(expiration = ?identity.expiration(), "credentials were refreshed");
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn on_identity_refreshed(
+        mut self,
+        hook: impl Fn(&Identity) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_refreshed = Some(Arc::new(hook));
+        self
+    }
+
     /// Builds a [`SharedIdentityCache`] from this builder.
     ///
+    /// Keep a clone of the cache to [`invalidate`](ResolveCachedIdentity::invalidate) it later,
+    /// for example, after credentials were rotated.
+    ///
     /// # Panics
     ///
     /// This builder will panic if required fields are not given, or if given values are not valid.
@@ -177,6 +282,9 @@ impl LazyCacheBuilder {
             self.buffer_time_jitter_fraction
                 .unwrap_or(DEFAULT_BUFFER_TIME_JITTER_FRACTION),
             default_expiration,
+            self.refresh_window,
+            self.on_expiring,
+            self.on_refreshed,
         )
         .into_shared()
     }
@@ -184,7 +292,7 @@ impl LazyCacheBuilder {
 
 #[derive(Debug)]
 struct CachePartitions {
-    partitions: RwLock<HashMap<IdentityCachePartition, ExpiringCache<Identity, BoxError>>>,
+    partitions: RwLock<HashMap<IdentityCachePartition, Partition>>,
     buffer_time: Duration,
 }
 
@@ -196,32 +304,93 @@ impl CachePartitions {
         }
     }
 
-    fn partition(&self, key: IdentityCachePartition) -> ExpiringCache<Identity, BoxError> {
+    fn partition(&self, key: IdentityCachePartition) -> Partition {
         let mut partition = self.partitions.read().unwrap().get(&key).cloned();
         // Add the partition to the cache if it doesn't already exist.
-        // Partitions will never be removed.
+        // Partitions are only removed when the cache is invalidated.
         if partition.is_none() {
             let mut partitions = self.partitions.write().unwrap();
             // Another thread could have inserted the partition before we acquired the lock,
             // so double check before inserting it.
             partitions
                 .entry(key)
-                .or_insert_with(|| ExpiringCache::new(self.buffer_time));
+                .or_insert_with(|| Partition::new(self.buffer_time));
             drop(partitions);
 
             partition = self.partitions.read().unwrap().get(&key).cloned();
         }
         partition.expect("inserted above if not present")
     }
+
+    fn clear(&self) {
+        // Loads that are in progress finish into the removed partitions, so their identities
+        // are never returned from the cache.
+        self.partitions.write().unwrap().clear();
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Partition {
+    cache: ExpiringCache<Identity, BoxError>,
+    refresh: Arc<RefreshState>,
+}
+
+impl Partition {
+    fn new(buffer_time: Duration) -> Self {
+        Self {
+            cache: ExpiringCache::new(buffer_time),
+            refresh: Default::default(),
+        }
+    }
+}
+
+/// When the cached identity of a partition is refreshed ahead of its expiration.
+#[derive(Debug, Default)]
+struct RefreshState {
+    refresh_at: Mutex<Option<SystemTime>>,
+    refreshing: AtomicBool,
+}
+
+impl RefreshState {
+    fn schedule(&self, refresh_at: Option<SystemTime>) {
+        *self.refresh_at.lock().unwrap() = refresh_at;
+    }
+
+    /// Returns true if the caller should refresh the identity, in which case it must call `finish`.
+    fn start(&self, now: SystemTime) -> bool {
+        let due = matches!(*self.refresh_at.lock().unwrap(), Some(refresh_at) if now >= refresh_at);
+        due && self
+            .refreshing
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    fn finish(&self) {
+        self.refreshing.store(false, Ordering::Release);
+    }
 }
 
-#[derive(Debug)]
 struct LazyCache {
     partitions: CachePartitions,
     load_timeout: Duration,
     buffer_time: Duration,
     buffer_time_jitter_fraction: fn() -> f64,
     default_expiration: Duration,
+    refresh_window: Option<f64>,
+    on_expiring: Option<IdentityHook>,
+    on_refreshed: Option<IdentityHook>,
+}
+
+impl fmt::Debug for LazyCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyCache")
+            .field("partitions", &self.partitions)
+            .field("load_timeout", &self.load_timeout)
+            .field("buffer_time", &self.buffer_time)
+            .field("default_expiration", &self.default_expiration)
+            .field("refresh_window", &self.refresh_window)
+            .finish()
+    }
 }
 
 impl LazyCache {
@@ -230,6 +399,9 @@ impl LazyCache {
         buffer_time: Duration,
         buffer_time_jitter_fraction: fn() -> f64,
         default_expiration: Duration,
+        refresh_window: Option<f64>,
+        on_expiring: Option<IdentityHook>,
+        on_refreshed: Option<IdentityHook>,
     ) -> Self {
         Self {
             partitions: CachePartitions::new(buffer_time),
@@ -237,8 +409,98 @@ impl LazyCache {
             buffer_time,
             buffer_time_jitter_fraction,
             default_expiration,
+            refresh_window,
+            on_expiring,
+            on_refreshed,
         }
     }
+
+    /// Resolves a new identity, and returns it with the time that it should be evicted at.
+    async fn load_identity(
+        &self,
+        partition: &Partition,
+        resolver: &SharedIdentityResolver,
+        runtime_components: &RuntimeComponents,
+        config_bag: &ConfigBag,
+        timeout_future: Sleep,
+        now: SystemTime,
+    ) -> Result<(Identity, SystemTime), BoxError> {
+        let fut = Timeout::new(
+            resolver.resolve_identity(runtime_components, config_bag),
+            timeout_future,
+        );
+        let identity = match fut.await {
+            Ok(result) => result?,
+            Err(_err) => match resolver.fallback_on_interrupt() {
+                Some(identity) => identity,
+                None => return Err(BoxError::from(TimedOutError(self.load_timeout))),
+            },
+        };
+        // If the identity don't have an expiration time, then create a default one
+        let expiration = identity
+            .expiration()
+            .unwrap_or(now + self.default_expiration);
+
+        let jitter = self
+            .buffer_time
+            .mul_f64((self.buffer_time_jitter_fraction)());
+
+        partition
+            .refresh
+            .schedule(self.refresh_window.map(|fraction| {
+                let lifetime = expiration.duration_since(now).unwrap_or_default();
+                expiration - lifetime.mul_f64(fraction)
+            }));
+        if let Some(on_refreshed) = &self.on_refreshed {
+            on_refreshed(&identity);
+        }
+
+        Ok((identity, expiration + jitter))
+    }
+
+    /// Replaces the cached identity of a partition before it expires.
+    ///
+    /// Returns the new identity, or the cached identity if the refresh failed.
+    #[allow(clippy::too_many_arguments)]
+    async fn refresh_ahead(
+        &self,
+        partition: &Partition,
+        cached: Identity,
+        resolver: &SharedIdentityResolver,
+        runtime_components: &RuntimeComponents,
+        config_bag: &ConfigBag,
+        timeout_future: Sleep,
+        now: SystemTime,
+    ) -> Identity {
+        let result = self
+            .load_identity(
+                partition,
+                resolver,
+                runtime_components,
+                config_bag,
+                timeout_future,
+                now,
+            )
+            .instrument(tracing::info_span!("refresh_identity_ahead"))
+            .await;
+        let identity = match result {
+            Ok((identity, expiration)) => {
+                tracing::info!(cached_expiration=?cached.expiration(), "refreshed identity ahead of its expiration");
+                partition.cache.set(identity.clone(), expiration).await;
+                identity
+            }
+            Err(err) => {
+                tracing::warn!(
+                    error = %DisplayErrorContext(&*err),
+                    "failed to refresh identity ahead of its expiration; using the cached identity until it expires"
+                );
+                partition.refresh.schedule(None);
+                cached
+            }
+        };
+        partition.refresh.finish();
+        identity
+    }
 }
 
 macro_rules! required_err {
@@ -302,72 +564,86 @@ impl ResolveCachedIdentity for LazyCache {
 
         let now = time_source.now();
         let timeout_future = sleep_impl.sleep(self.load_timeout);
-        let load_timeout = self.load_timeout;
-        let cache = self.partitions.partition(resolver.cache_partition());
-        let default_expiration = self.default_expiration;
+        let partition = self.partitions.partition(resolver.cache_partition());
 
         IdentityFuture::new(async move {
             // Attempt to get cached identity, or clear the cache if they're expired
-            if let Some(identity) = cache.yield_or_clear_if_expired(now).await {
-                tracing::debug!(
-                    buffer_time=?self.buffer_time,
-                    cached_expiration=?identity.expiration(),
-                    now=?now,
-                    "loaded identity from cache"
-                );
-                Ok(identity)
-            } else {
-                // If we didn't get identity from the cache, then we need to try and load.
-                // There may be other threads also loading simultaneously, but this is OK
-                // since the futures are not eagerly executed, and the cache will only run one
-                // of them.
-                let start_time = time_source.now();
-                let result = cache
-                    .get_or_load(|| {
-                        let span = tracing::info_span!("lazy_load_identity");
-                        async move {
-                            let fut = Timeout::new(
-                                resolver.resolve_identity(runtime_components, config_bag),
+            let expired = match partition.cache.yield_or_take_if_expired(now).await {
+                Ok(identity) => {
+                    // Only one request refreshes the identity ahead of its expiration,
+                    // while other requests keep using the cached identity.
+                    if partition.refresh.start(now) {
+                        return Ok(self
+                            .refresh_ahead(
+                                &partition,
+                                identity,
+                                &resolver,
+                                runtime_components,
+                                config_bag,
                                 timeout_future,
-                            );
-                            let identity = match fut.await {
-                                Ok(result) => result?,
-                                Err(_err) => match resolver.fallback_on_interrupt() {
-                                    Some(identity) => identity,
-                                    None => {
-                                        return Err(BoxError::from(TimedOutError(load_timeout)))
-                                    }
-                                },
-                            };
-                            // If the identity don't have an expiration time, then create a default one
-                            let expiration =
-                                identity.expiration().unwrap_or(now + default_expiration);
-
-                            let jitter = self
-                                .buffer_time
-                                .mul_f64((self.buffer_time_jitter_fraction)());
-
-                            // Logging for cache miss should be emitted here as opposed to after the call to
-                            // `cache.get_or_load` above. In the case of multiple threads concurrently executing
-                            // `cache.get_or_load`, logging inside `cache.get_or_load` ensures that it is emitted
-                            // only once for the first thread that succeeds in populating a cache value.
-                            tracing::info!(
-                                "identity cache miss occurred; added new identity (took {:?})",
-                                time_source.now().duration_since(start_time)
-                            );
-
-                            Ok((identity, expiration + jitter))
-                        }
-                        // Only instrument the the actual load future so that no span
-                        // is opened if the cache decides not to execute it.
-                        .instrument(span)
-                    })
-                    .await;
-                tracing::debug!("loaded identity");
-                result
+                                now,
+                            )
+                            .await);
+                    }
+                    tracing::debug!(
+                        buffer_time=?self.buffer_time,
+                        cached_expiration=?identity.expiration(),
+                        now=?now,
+                        "loaded identity from cache"
+                    );
+                    return Ok(identity);
+                }
+                Err(expired) => expired,
+            };
+            if let (Some(expired), Some(on_expiring)) = (expired, &self.on_expiring) {
+                on_expiring(&expired);
             }
+            // If we didn't get identity from the cache, then we need to try and load.
+            // There may be other threads also loading simultaneously, but this is OK
+            // since the futures are not eagerly executed, and the cache will only run one
+            // of them.
+            let start_time = time_source.now();
+            let result = partition
+                .cache
+                .get_or_load(|| {
+                    let span = tracing::info_span!("lazy_load_identity");
+                    async {
+                        let loaded = self
+                            .load_identity(
+                                &partition,
+                                &resolver,
+                                runtime_components,
+                                config_bag,
+                                timeout_future,
+                                now,
+                            )
+                            .await?;
+
+                        // Logging for cache miss should be emitted here as opposed to after the call to
+                        // `cache.get_or_load` above. In the case of multiple threads concurrently executing
+                        // `cache.get_or_load`, logging inside `cache.get_or_load` ensures that it is emitted
+                        // only once for the first thread that succeeds in populating a cache value.
+                        tracing::info!(
+                            "identity cache miss occurred; added new identity (took {:?})",
+                            time_source.now().duration_since(start_time)
+                        );
+
+                        Ok(loaded)
+                    }
+                    // Only instrument the the actual load future so that no span
+                    // is opened if the cache decides not to execute it.
+                    .instrument(span)
+                })
+                .await;
+            tracing::debug!("loaded identity");
+            result
         })
     }
+
+    fn invalidate(&self) {
+        tracing::debug!("invalidating the identity cache");
+        self.partitions.clear();
+    }
 }
 
 #[derive(Debug)]
@@ -452,6 +728,9 @@ mod tests {
             DEFAULT_BUFFER_TIME,
             buffer_time_jitter_fraction,
             DEFAULT_EXPIRATION,
+            None,
+            None,
+            None,
         );
         (cache, identity_resolver)
     }
@@ -497,6 +776,9 @@ mod tests {
             DEFAULT_BUFFER_TIME,
             BUFFER_TIME_NO_JITTER,
             DEFAULT_EXPIRATION,
+            None,
+            None,
+            None,
         );
         assert_eq!(
             epoch_secs(1000),
@@ -635,6 +917,9 @@ mod tests {
             DEFAULT_BUFFER_TIME,
             BUFFER_TIME_NO_JITTER,
             DEFAULT_EXPIRATION,
+            None,
+            None,
+            None,
         );
 
         let err: BoxError = cache
@@ -761,4 +1046,91 @@ mod tests {
         assert_eq!(1, resolver_a_calls.load(Ordering::Relaxed));
         assert_eq!(1, resolver_b_calls.load(Ordering::Relaxed));
     }
+
+    #[tokio::test]
+    async fn hooks_and_invalidation() {
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let expiring = Arc::new(Mutex::new(Vec::new()));
+        let refreshed = Arc::new(Mutex::new(Vec::new()));
+        let (mut cache, resolver) = test_cache(
+            BUFFER_TIME_NO_JITTER,
+            vec![
+                Ok(test_identity(1000)),
+                Ok(test_identity(2000)),
+                Ok(test_identity(3000)),
+            ],
+        );
+        cache.on_expiring = Some({
+            let expiring = expiring.clone();
+            Arc::new(move |identity: &Identity| {
+                expiring
+                    .lock()
+                    .unwrap()
+                    .push(identity.expiration().unwrap())
+            })
+        });
+        cache.on_refreshed = Some({
+            let refreshed = refreshed.clone();
+            Arc::new(move |identity: &Identity| {
+                refreshed
+                    .lock()
+                    .unwrap()
+                    .push(identity.expiration().unwrap())
+            })
+        });
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        assert_eq!(vec![epoch_secs(1000)], *refreshed.lock().unwrap());
+
+        time.set_time(epoch_secs(995));
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+        assert_eq!(vec![epoch_secs(1000)], *expiring.lock().unwrap());
+
+        // Invalidating the cache resolves a new identity without calling the expiring hook
+        cache.invalidate();
+        expect_identity(3000, &cache, &components, resolver.clone()).await;
+        assert_eq!(vec![epoch_secs(1000)], *expiring.lock().unwrap());
+        assert_eq!(
+            vec![epoch_secs(1000), epoch_secs(2000), epoch_secs(3000)],
+            *refreshed.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_ahead_of_expiration() {
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let (mut cache, resolver) = test_cache(
+            BUFFER_TIME_NO_JITTER,
+            vec![
+                Ok(test_identity(1000)),
+                Ok(test_identity(2000)),
+                Err("failed".into()),
+            ],
+        );
+        cache.refresh_window = Some(0.5);
+
+        // The identity is valid for 900 seconds, so it's refreshed after 550 seconds
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(500));
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(600));
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+
+        // A failed refresh keeps using the cached identity until it expires, without retrying
+        time.set_time(epoch_secs(1500));
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(1600));
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+    }
 }
@@ -0,0 +1,440 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Identity resolver for short-lived sessions that are scoped to a resource.
+//!
+//! Some services authenticate requests to a resource, such as a bucket, with a session that is
+//! created by a bootstrap operation for that resource. The [`SessionIdentityResolver`] creates
+//! sessions on demand, and caches them per [`SessionScope`] until they're about to expire.
+
+use crate::expiring_cache::ExpiringCache;
+use aws_smithy_async::future::BoxFuture;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::identity::{
+    Identity, IdentityCacheLocation, IdentityFuture, ResolveIdentity,
+};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::error::display::DisplayErrorContext;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::Instrument;
+
+const DEFAULT_BUFFER_TIME: Duration = Duration::from_secs(10);
+const DEFAULT_REFRESH_WINDOW: Duration = Duration::from_secs(60);
+const DEFAULT_SESSION_DURATION: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_MAX_SESSIONS: usize = 100;
+
+/// The resource that the session of a request is scoped to, such as a bucket.
+///
+/// The [`SessionIdentityResolver`] loads the scope from the config bag, so it must be stored
+/// there for every request that is authenticated with a session, usually by an interceptor.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SessionScope(Cow<'static, str>);
+
+impl SessionScope {
+    /// Creates a new `SessionScope`.
+    pub fn new(scope: impl Into<Cow<'static, str>>) -> Self {
+        Self(scope.into())
+    }
+
+    /// Returns the scope as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Storable for SessionScope {
+    type Storer = StoreReplace<Self>;
+}
+
+type CreateSession =
+    Arc<dyn Fn(SessionScope) -> BoxFuture<'static, Identity, BoxError> + Send + Sync>;
+
+/// Identity resolver for short-lived sessions that are scoped to a resource.
+///
+/// Sessions are created with the given bootstrap function the first time that a request is
+/// sent to a [scope](SessionScope), and then cached for that scope. Concurrent requests to a
+/// scope without a session wait for a single session to be created.
+///
+/// Sessions are refreshed ahead of their expiration: the first request within the
+/// [refresh window](Self::refresh_window) creates a new session, while other requests keep
+/// using the cached session, so requests aren't held up while sessions are refreshed. If the
+/// refresh fails, the cached session is used until it expires.
+///
+/// This resolver caches identities itself, so the identity cache in runtime components
+/// is bypassed for it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::identity::session::SessionIdentityResolver;
+/// use aws_smithy_runtime_api::client::identity::Identity;
+/// use std::time::{Duration, SystemTime};
+///
+/// let resolver = SessionIdentityResolver::new(|scope| async move {
+///     // Call the bootstrap operation for `scope` here
+///     let token = format!("session-token-for-{}", scope.as_str());
+///     Ok(Identity::new(token, Some(SystemTime::now() + Duration::from_secs(300))))
+/// })
+/// .refresh_window(Duration::from_secs(60));
+/// ```
+#[derive(Clone)]
+pub struct SessionIdentityResolver {
+    create_session: CreateSession,
+    sessions: Arc<Sessions>,
+    buffer_time: Duration,
+    refresh_window: Duration,
+}
+
+impl fmt::Debug for SessionIdentityResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionIdentityResolver")
+            .field("sessions", &self.sessions)
+            .field("buffer_time", &self.buffer_time)
+            .field("refresh_window", &self.refresh_window)
+            .finish()
+    }
+}
+
+impl SessionIdentityResolver {
+    /// Creates a new `SessionIdentityResolver` that creates sessions with `create_session`.
+    ///
+    /// Sessions that don't have an expiration are cached for five minutes.
+    pub fn new<F, Fut>(create_session: F) -> Self
+    where
+        F: Fn(SessionScope) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Identity, BoxError>> + Send + 'static,
+    {
+        Self {
+            create_session: Arc::new(move |scope| Box::pin(create_session(scope))),
+            sessions: Arc::new(Sessions::new(DEFAULT_MAX_SESSIONS)),
+            buffer_time: DEFAULT_BUFFER_TIME,
+            refresh_window: DEFAULT_REFRESH_WINDOW,
+        }
+    }
+
+    /// Sets how long before their expiration that sessions are no longer used.
+    ///
+    /// Defaults to 10 seconds.
+    pub fn buffer_time(mut self, buffer_time: Duration) -> Self {
+        self.buffer_time = buffer_time;
+        self
+    }
+
+    /// Sets how long before their expiration that sessions are refreshed.
+    ///
+    /// The refresh window should be larger than the [buffer time](Self::buffer_time), or
+    /// sessions expire before they're refreshed. Defaults to one minute.
+    pub fn refresh_window(mut self, refresh_window: Duration) -> Self {
+        self.refresh_window = refresh_window;
+        self
+    }
+
+    /// Sets how many scopes that sessions are cached for.
+    ///
+    /// When the cache is full, the session of the least recently used scope is evicted.
+    /// Defaults to 100.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_sessions` is zero.
+    pub fn max_sessions(mut self, max_sessions: usize) -> Self {
+        assert!(max_sessions > 0, "max_sessions must be greater than zero");
+        self.sessions = Arc::new(Sessions::new(max_sessions));
+        self
+    }
+
+    /// Creates a new session, and returns it with the time that it should be evicted at.
+    async fn create_session(
+        &self,
+        scope: SessionScope,
+        now: SystemTime,
+    ) -> Result<(Identity, SystemTime), BoxError> {
+        let identity = (self.create_session)(scope).await?;
+        let expiration = identity
+            .expiration()
+            .unwrap_or(now + DEFAULT_SESSION_DURATION);
+        Ok((identity, expiration))
+    }
+
+    /// Replaces the cached session of a scope before it expires.
+    ///
+    /// Returns the new session, or the cached session if the refresh failed.
+    async fn refresh_ahead(
+        &self,
+        session: &Session,
+        cached: Identity,
+        scope: SessionScope,
+        now: SystemTime,
+    ) -> Identity {
+        let result = self
+            .create_session(scope, now)
+            .instrument(tracing::info_span!("refresh_session_ahead"))
+            .await;
+        let identity = match result {
+            Ok((identity, expiration)) => {
+                tracing::debug!(cached_expiration = ?cached.expiration(), "refreshed session ahead of its expiration");
+                session.cache.set(identity.clone(), expiration).await;
+                identity
+            }
+            Err(err) => {
+                tracing::warn!(
+                    error = %DisplayErrorContext(&*err),
+                    "failed to refresh session ahead of its expiration; using the cached session until it expires"
+                );
+                cached
+            }
+        };
+        session.refreshing.store(false, Ordering::Release);
+        identity
+    }
+}
+
+impl ResolveIdentity for SessionIdentityResolver {
+    fn resolve_identity<'a>(
+        &'a self,
+        runtime_components: &'a RuntimeComponents,
+        config_bag: &'a ConfigBag,
+    ) -> IdentityFuture<'a> {
+        let scope = match config_bag.load::<SessionScope>() {
+            Some(scope) => scope.clone(),
+            None => {
+                return IdentityFuture::ready(Err(
+                    "a `SessionScope` must be in the config bag to resolve a session identity"
+                        .into(),
+                ))
+            }
+        };
+        let now = runtime_components.time_source().unwrap_or_default().now();
+        let session = self.sessions.session(&scope, self.buffer_time);
+
+        IdentityFuture::new(async move {
+            if let Some(identity) = session.cache.yield_or_clear_if_expired(now).await {
+                let refresh_due = matches!(
+                    identity.expiration(),
+                    Some(expiration) if now + self.refresh_window >= expiration
+                );
+                // Only one request refreshes the session ahead of its expiration,
+                // while other requests keep using the cached session.
+                if refresh_due
+                    && session
+                        .refreshing
+                        .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                {
+                    return Ok(self.refresh_ahead(&session, identity, scope, now).await);
+                }
+                return Ok(identity);
+            }
+            session
+                .cache
+                .get_or_load(|| {
+                    self.create_session(scope, now)
+                        .instrument(tracing::debug_span!("create_session"))
+                })
+                .await
+        })
+    }
+
+    fn cache_location(&self) -> IdentityCacheLocation {
+        IdentityCacheLocation::IdentityResolver
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Session {
+    cache: ExpiringCache<Identity, BoxError>,
+    refreshing: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct Sessions {
+    max_sessions: usize,
+    inner: Mutex<SessionsInner>,
+}
+
+#[derive(Debug, Default)]
+struct SessionsInner {
+    /// Sessions by scope, with the tick that they were last used at.
+    sessions: HashMap<SessionScope, (Session, u64)>,
+    tick: u64,
+}
+
+impl Sessions {
+    fn new(max_sessions: usize) -> Self {
+        Self {
+            max_sessions,
+            inner: Default::default(),
+        }
+    }
+
+    /// Returns the session of the given scope, and adds it to the cache if it isn't cached yet.
+    fn session(&self, scope: &SessionScope, buffer_time: Duration) -> Session {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tick += 1;
+        let tick = inner.tick;
+        if let Some((session, last_used)) = inner.sessions.get_mut(scope) {
+            *last_used = tick;
+            return session.clone();
+        }
+
+        if inner.sessions.len() >= self.max_sessions {
+            let least_recently_used = inner
+                .sessions
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(scope, _)| scope.clone());
+            if let Some(scope) = least_recently_used {
+                inner.sessions.remove(&scope);
+            }
+        }
+        let session = Session {
+            cache: ExpiringCache::new(buffer_time),
+            refreshing: Default::default(),
+        };
+        inner
+            .sessions
+            .insert(scope.clone(), (session.clone(), tick));
+        session
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_async::time::TimeSource;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::Layer;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::UNIX_EPOCH;
+
+    fn epoch_secs(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    /// Returns a resolver whose sessions expire five minutes after the given time source's
+    /// current time, and a counter of the sessions that it created.
+    fn test_resolver(time_source: ManualTimeSource) -> (SessionIdentityResolver, Arc<AtomicUsize>) {
+        let created = Arc::new(AtomicUsize::new(0));
+        let resolver = SessionIdentityResolver::new({
+            let created = created.clone();
+            move |scope: SessionScope| {
+                let count = created.fetch_add(1, Ordering::SeqCst) + 1;
+                let expiration = time_source.now() + Duration::from_secs(300);
+                async move {
+                    Ok(Identity::new(
+                        format!("{}-{}", scope.as_str(), count),
+                        Some(expiration),
+                    ))
+                }
+            }
+        });
+        (resolver, created)
+    }
+
+    fn runtime_components(time_source: &ManualTimeSource) -> RuntimeComponents {
+        RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time_source.clone()))
+            .build()
+            .unwrap()
+    }
+
+    fn config_bag(scope: &'static str) -> ConfigBag {
+        let mut layer = Layer::new("test");
+        layer.store_put(SessionScope::new(scope));
+        ConfigBag::of_layers(vec![layer])
+    }
+
+    async fn resolve(
+        resolver: &SessionIdentityResolver,
+        components: &RuntimeComponents,
+        scope: &'static str,
+    ) -> String {
+        let identity = resolver
+            .resolve_identity(components, &config_bag(scope))
+            .await
+            .expect("success");
+        identity.data::<String>().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn caches_sessions_per_scope() {
+        let time_source = ManualTimeSource::new(epoch_secs(100));
+        let components = runtime_components(&time_source);
+        let (resolver, created) = test_resolver(time_source.clone());
+
+        let (first, second) = tokio::join!(
+            resolve(&resolver, &components, "bucket-a"),
+            resolve(&resolver, &components, "bucket-a"),
+        );
+        assert_eq!("bucket-a-1", first);
+        assert_eq!("bucket-a-1", second);
+        assert_eq!(
+            "bucket-b-2",
+            resolve(&resolver, &components, "bucket-b").await
+        );
+        assert_eq!(
+            "bucket-a-1",
+            resolve(&resolver, &components, "bucket-a").await
+        );
+        assert_eq!(2, created.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn refreshes_sessions_ahead_of_expiration() {
+        let time_source = ManualTimeSource::new(epoch_secs(100));
+        let components = runtime_components(&time_source);
+        let (resolver, created) = test_resolver(time_source.clone());
+
+        assert_eq!("bucket-1", resolve(&resolver, &components, "bucket").await);
+        time_source.set_time(epoch_secs(100 + 250));
+        let components = runtime_components(&time_source);
+        assert_eq!("bucket-2", resolve(&resolver, &components, "bucket").await);
+        assert_eq!("bucket-2", resolve(&resolver, &components, "bucket").await);
+        assert_eq!(2, created.load(Ordering::SeqCst));
+
+        // Expired sessions are never returned
+        time_source.set_time(epoch_secs(100 + 250 + 295));
+        let components = runtime_components(&time_source);
+        assert_eq!("bucket-3", resolve(&resolver, &components, "bucket").await);
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_used_sessions() {
+        let time_source = ManualTimeSource::new(epoch_secs(100));
+        let components = runtime_components(&time_source);
+        let (resolver, created) = test_resolver(time_source.clone());
+        let resolver = resolver.max_sessions(2);
+
+        resolve(&resolver, &components, "a").await;
+        resolve(&resolver, &components, "b").await;
+        resolve(&resolver, &components, "a").await;
+        resolve(&resolver, &components, "c").await;
+        assert_eq!(3, created.load(Ordering::SeqCst));
+
+        assert_eq!("a-1", resolve(&resolver, &components, "a").await);
+        assert_eq!("b-4", resolve(&resolver, &components, "b").await);
+    }
+
+    #[tokio::test]
+    async fn fails_without_a_scope() {
+        let time_source = ManualTimeSource::new(epoch_secs(100));
+        let components = runtime_components(&time_source);
+        let (resolver, _created) = test_resolver(time_source);
+
+        let err = resolver
+            .resolve_identity(&components, &ConfigBag::base())
+            .await
+            .expect_err("no scope");
+        assert!(format!("{}", err).contains("SessionScope"), "{}", err);
+    }
+}
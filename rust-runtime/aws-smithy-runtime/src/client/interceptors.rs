@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::client::telemetry::OperationTelemetry;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::interceptors::context::{
     BeforeSerializationInterceptorContextRef, BeforeTransmitInterceptorContextMut,
@@ -37,12 +38,16 @@ macro_rules! interceptor_impl_fn {
                 "` interceptors"
             ));
             let mut result: Result<(), (&str, BoxError)> = Ok(());
+            let telemetry = cfg.load::<OperationTelemetry>().cloned();
             let mut ctx = ctx.into();
             for interceptor in self.into_iter() {
                 if let Some(interceptor) = interceptor.if_enabled(cfg) {
-                    if let Err(new_error) =
-                        interceptor.$interceptor(&mut ctx, runtime_components, cfg)
-                    {
+                    if let Err(new_error) = run_hook(
+                        telemetry.as_ref(),
+                        interceptor,
+                        stringify!($interceptor),
+                        || interceptor.$interceptor(&mut ctx, runtime_components, cfg),
+                    ) {
                         if let Err(last_error) = result {
                             tracing::debug!(
                                 "{}::{}: {}",
@@ -71,11 +76,16 @@ macro_rules! interceptor_impl_fn {
                 "` interceptors"
             ));
             let mut result: Result<(), (&str, BoxError)> = Ok(());
+            let telemetry = cfg.load::<OperationTelemetry>().cloned();
             let ctx = ctx.into();
             for interceptor in self.into_iter() {
                 if let Some(interceptor) = interceptor.if_enabled(cfg) {
-                    if let Err(new_error) = interceptor.$interceptor(&ctx, runtime_components, cfg)
-                    {
+                    if let Err(new_error) = run_hook(
+                        telemetry.as_ref(),
+                        interceptor,
+                        stringify!($interceptor),
+                        || interceptor.$interceptor(&ctx, runtime_components, cfg),
+                    ) {
                         if let Err(last_error) = result {
                             tracing::debug!(
                                 "{}::{}: {}",
@@ -121,10 +131,16 @@ where
             if operation { "operation" } else { "client" }
         );
         let mut result: Result<(), (&str, BoxError)> = Ok(());
+        let telemetry = cfg.load::<OperationTelemetry>().cloned();
         let ctx: BeforeSerializationInterceptorContextRef<'_> = ctx.into();
         for interceptor in self.into_iter() {
             if let Some(interceptor) = interceptor.if_enabled(cfg) {
-                if let Err(new_error) = interceptor.read_before_execution(&ctx, cfg) {
+                if let Err(new_error) = run_hook(
+                    telemetry.as_ref(),
+                    interceptor,
+                    "read_before_execution",
+                    || interceptor.read_before_execution(&ctx, cfg),
+                ) {
                     if let Err(last_error) = result {
                         tracing::debug!(
                             "{}::{}: {}",
@@ -163,12 +179,22 @@ where
     ) -> Result<(), InterceptorError> {
         tracing::trace!("running `modify_before_attempt_completion` interceptors");
         let mut result: Result<(), (&str, BoxError)> = Ok(());
+        let telemetry = cfg.load::<OperationTelemetry>().cloned();
         let mut ctx: FinalizerInterceptorContextMut<'_> = ctx.into();
         for interceptor in self.into_iter() {
             if let Some(interceptor) = interceptor.if_enabled(cfg) {
-                if let Err(new_error) =
-                    interceptor.modify_before_attempt_completion(&mut ctx, runtime_components, cfg)
-                {
+                if let Err(new_error) = run_hook(
+                    telemetry.as_ref(),
+                    interceptor,
+                    "modify_before_attempt_completion",
+                    || {
+                        interceptor.modify_before_attempt_completion(
+                            &mut ctx,
+                            runtime_components,
+                            cfg,
+                        )
+                    },
+                ) {
                     if let Err(last_error) = result {
                         tracing::debug!(
                             "{}::{}: {}",
@@ -192,12 +218,16 @@ where
     ) -> Result<(), InterceptorError> {
         tracing::trace!("running `read_after_attempt` interceptors");
         let mut result: Result<(), (&str, BoxError)> = Ok(());
+        let telemetry = cfg.load::<OperationTelemetry>().cloned();
         let ctx: FinalizerInterceptorContextRef<'_> = ctx.into();
         for interceptor in self.into_iter() {
             if let Some(interceptor) = interceptor.if_enabled(cfg) {
-                if let Err(new_error) =
-                    interceptor.read_after_attempt(&ctx, runtime_components, cfg)
-                {
+                if let Err(new_error) = run_hook(
+                    telemetry.as_ref(),
+                    interceptor,
+                    "read_after_attempt",
+                    || interceptor.read_after_attempt(&ctx, runtime_components, cfg),
+                ) {
                     if let Err(last_error) = result {
                         tracing::debug!(
                             "{}::{}: {}",
@@ -221,12 +251,16 @@ where
     ) -> Result<(), InterceptorError> {
         tracing::trace!("running `modify_before_completion` interceptors");
         let mut result: Result<(), (&str, BoxError)> = Ok(());
+        let telemetry = cfg.load::<OperationTelemetry>().cloned();
         let mut ctx: FinalizerInterceptorContextMut<'_> = ctx.into();
         for interceptor in self.into_iter() {
             if let Some(interceptor) = interceptor.if_enabled(cfg) {
-                if let Err(new_error) =
-                    interceptor.modify_before_completion(&mut ctx, runtime_components, cfg)
-                {
+                if let Err(new_error) = run_hook(
+                    telemetry.as_ref(),
+                    interceptor,
+                    "modify_before_completion",
+                    || interceptor.modify_before_completion(&mut ctx, runtime_components, cfg),
+                ) {
                     if let Err(last_error) = result {
                         tracing::debug!(
                             "{}::{}: {}",
@@ -250,12 +284,16 @@ where
     ) -> Result<(), InterceptorError> {
         tracing::trace!("running `read_after_execution` interceptors");
         let mut result: Result<(), (&str, BoxError)> = Ok(());
+        let telemetry = cfg.load::<OperationTelemetry>().cloned();
         let ctx: FinalizerInterceptorContextRef<'_> = ctx.into();
         for interceptor in self.into_iter() {
             if let Some(interceptor) = interceptor.if_enabled(cfg) {
-                if let Err(new_error) =
-                    interceptor.read_after_execution(&ctx, runtime_components, cfg)
-                {
+                if let Err(new_error) = run_hook(
+                    telemetry.as_ref(),
+                    interceptor,
+                    "read_after_execution",
+                    || interceptor.read_after_execution(&ctx, runtime_components, cfg),
+                ) {
                     if let Err(last_error) = result {
                         tracing::debug!(
                             "{}::{}: {}",
@@ -272,6 +310,20 @@ where
     }
 }
 
+/// Runs an interceptor hook, recording its duration and whether it failed when the operation's
+/// telemetry is being recorded.
+fn run_hook(
+    telemetry: Option<&OperationTelemetry>,
+    interceptor: &dyn Intercept,
+    hook: &'static str,
+    run: impl FnOnce() -> Result<(), BoxError>,
+) -> Result<(), BoxError> {
+    match telemetry {
+        Some(telemetry) => telemetry.record_interceptor_hook(interceptor.name(), hook, run),
+        None => run(),
+    }
+}
+
 /// A interceptor wrapper to conditionally enable the interceptor based on
 /// [`DisableInterceptor`](aws_smithy_runtime_api::client::interceptors::DisableInterceptor)
 struct ConditionallyEnabledInterceptor(SharedInterceptor);
@@ -0,0 +1,319 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client-generated invocation IDs, for services that accept a correlation ID from clients.
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use fastrand::Rng;
+use http::{HeaderName, HeaderValue};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "test-util")]
+pub use test_util::{NoInvocationIdGenerator, PredefinedInvocationIdGenerator};
+
+/// A generator for returning new invocation IDs on demand.
+pub trait InvocationIdGenerator: Debug + Send + Sync {
+    /// Call this function to receive a new [`InvocationId`] or an error explaining why one couldn't
+    /// be provided.
+    fn generate(&self) -> Result<Option<InvocationId>, BoxError>;
+}
+
+/// Dynamic dispatch implementation of [`InvocationIdGenerator`]
+#[derive(Clone, Debug)]
+pub struct SharedInvocationIdGenerator(Arc<dyn InvocationIdGenerator>);
+
+impl SharedInvocationIdGenerator {
+    /// Creates a new [`SharedInvocationIdGenerator`].
+    pub fn new(gen: impl InvocationIdGenerator + 'static) -> Self {
+        Self(Arc::new(gen))
+    }
+}
+
+impl InvocationIdGenerator for SharedInvocationIdGenerator {
+    fn generate(&self) -> Result<Option<InvocationId>, BoxError> {
+        self.0.generate()
+    }
+}
+
+impl Storable for SharedInvocationIdGenerator {
+    type Storer = StoreReplace<Self>;
+}
+
+/// An invocation ID generator that uses random (version 4) UUIDs for the invocation ID.
+#[derive(Debug, Default)]
+pub struct DefaultInvocationIdGenerator {
+    rng: Mutex<Rng>,
+}
+
+impl DefaultInvocationIdGenerator {
+    /// Creates a new [`DefaultInvocationIdGenerator`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a [`DefaultInvocationIdGenerator`] with the given seed.
+    ///
+    /// Generators with the same seed generate the same invocation IDs, which is useful in tests.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(Rng::with_seed(seed)),
+        }
+    }
+}
+
+impl InvocationIdGenerator for DefaultInvocationIdGenerator {
+    fn generate(&self) -> Result<Option<InvocationId>, BoxError> {
+        let mut bytes = [0u8; 16];
+        self.rng.lock().unwrap().fill(&mut bytes);
+        // Set the version (4) and the variant (RFC 4122) of the UUID
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        Ok(Some(InvocationId::new(format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        ))))
+    }
+}
+
+/// Sends a client-generated invocation ID in a request header.
+///
+/// An invocation ID is generated for each operation, and every attempt of the operation sends the
+/// same one, so that the service can tell that the attempts are retries of the same request.
+/// Invocation IDs are random UUIDs, unless a [`SharedInvocationIdGenerator`] is put into the
+/// config bag.
+#[derive(Debug)]
+pub struct InvocationIdHeaderInterceptor {
+    header_name: HeaderName,
+    default: DefaultInvocationIdGenerator,
+}
+
+impl InvocationIdHeaderInterceptor {
+    /// Creates an interceptor that sends the invocation ID in the header with the given name.
+    ///
+    /// # Panics
+    /// This panics if the given name is not a valid HTTP header name.
+    pub fn new(header_name: impl AsRef<str>) -> Self {
+        Self {
+            header_name: HeaderName::from_bytes(header_name.as_ref().as_bytes())
+                .expect("invocation ID header name must be a valid HTTP header name"),
+            default: DefaultInvocationIdGenerator::new(),
+        }
+    }
+}
+
+impl Intercept for InvocationIdHeaderInterceptor {
+    fn name(&self) -> &'static str {
+        "InvocationIdHeaderInterceptor"
+    }
+
+    fn modify_before_retry_loop(
+        &self,
+        _ctx: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let gen = cfg
+            .load::<SharedInvocationIdGenerator>()
+            .map(|gen| gen as &dyn InvocationIdGenerator)
+            .unwrap_or(&self.default);
+        if let Some(id) = gen.generate()? {
+            cfg.interceptor_state().store_put::<InvocationId>(id);
+        }
+        Ok(())
+    }
+
+    fn modify_before_transmit(
+        &self,
+        ctx: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if let Some(id) = cfg.load::<InvocationId>() {
+            ctx.request_mut()
+                .headers_mut()
+                .insert(self.header_name.clone(), id.0.clone());
+        }
+        Ok(())
+    }
+}
+
+/// InvocationId provides a consistent ID across retries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvocationId(HeaderValue);
+
+impl InvocationId {
+    /// Create an invocation ID with the given value.
+    ///
+    /// # Panics
+    /// This constructor will panic if the given invocation ID is not a valid HTTP header value.
+    pub fn new(invocation_id: String) -> Self {
+        Self(
+            HeaderValue::try_from(invocation_id)
+                .expect("invocation ID must be a valid HTTP header value"),
+        )
+    }
+}
+
+impl Storable for InvocationId {
+    type Storer = StoreReplace<Self>;
+}
+
+#[cfg(feature = "test-util")]
+mod test_util {
+    use super::*;
+
+    impl InvocationId {
+        /// Create a new invocation ID from a `&'static str`.
+        pub fn new_from_str(uuid: &'static str) -> Self {
+            InvocationId(HeaderValue::from_static(uuid))
+        }
+    }
+
+    /// A "generator" that returns [`InvocationId`]s from a predefined list.
+    #[derive(Debug)]
+    pub struct PredefinedInvocationIdGenerator {
+        pre_generated_ids: Arc<Mutex<Vec<InvocationId>>>,
+    }
+
+    impl PredefinedInvocationIdGenerator {
+        /// Given a `Vec<InvocationId>`, create a new [`PredefinedInvocationIdGenerator`].
+        pub fn new(mut invocation_ids: Vec<InvocationId>) -> Self {
+            // We're going to pop ids off of the end of the list, so we need to reverse the list or else
+            // we'll be popping the ids in reverse order, confusing the poor test writer.
+            invocation_ids.reverse();
+
+            Self {
+                pre_generated_ids: Arc::new(Mutex::new(invocation_ids)),
+            }
+        }
+    }
+
+    impl InvocationIdGenerator for PredefinedInvocationIdGenerator {
+        fn generate(&self) -> Result<Option<InvocationId>, BoxError> {
+            Ok(Some(
+                self.pre_generated_ids
+                    .lock()
+                    .expect("this will never be under contention")
+                    .pop()
+                    .expect("testers will provide enough invocation IDs"),
+            ))
+        }
+    }
+
+    /// A "generator" that always returns `None`.
+    #[derive(Debug, Default)]
+    pub struct NoInvocationIdGenerator;
+
+    impl NoInvocationIdGenerator {
+        /// Create a new [`NoInvocationIdGenerator`].
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    impl InvocationIdGenerator for NoInvocationIdGenerator {
+        fn generate(&self) -> Result<Option<InvocationId>, BoxError> {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::Layer;
+
+    #[test]
+    fn every_attempt_sends_the_same_invocation_id() {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        ctx.set_request(HttpRequest::empty());
+        let _ = ctx.take_input();
+        ctx.enter_before_transmit_phase();
+
+        let mut cfg = ConfigBag::base();
+        let interceptor = InvocationIdHeaderInterceptor::new("x-client-request-id");
+        let mut ctx = Into::into(&mut ctx);
+        interceptor
+            .modify_before_retry_loop(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+        interceptor
+            .modify_before_transmit(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+        let first = ctx
+            .request()
+            .headers()
+            .get("x-client-request-id")
+            .expect("invocation ID was sent")
+            .to_owned();
+        // UUIDs have 32 hex digits and 4 dashes
+        assert_eq!(36, first.len());
+        assert_eq!(Some('4'), first.chars().nth(14));
+
+        interceptor
+            .modify_before_transmit(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+        let headers = ctx.request().headers();
+        assert_eq!(1, headers.get_all("x-client-request-id").count());
+        assert_eq!(Some(first.as_str()), headers.get("x-client-request-id"));
+    }
+
+    #[test]
+    fn seeded_generators_generate_the_same_ids() {
+        let first = DefaultInvocationIdGenerator::with_seed(1234);
+        let second = DefaultInvocationIdGenerator::with_seed(1234);
+        let first_id = first.generate().unwrap().expect("generated");
+        assert_eq!(first_id, second.generate().unwrap().expect("generated"));
+        assert_ne!(first_id, first.generate().unwrap().expect("generated"));
+    }
+
+    #[test]
+    fn custom_id_generator() {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        ctx.set_request(HttpRequest::empty());
+        let _ = ctx.take_input();
+        ctx.enter_before_transmit_phase();
+
+        let mut cfg = ConfigBag::base();
+        let mut layer = Layer::new("test");
+        layer.store_put(SharedInvocationIdGenerator::new(
+            DefaultInvocationIdGenerator::with_seed(1234),
+        ));
+        cfg.push_layer(layer);
+        let interceptor = InvocationIdHeaderInterceptor::new("x-client-request-id");
+        let mut ctx = Into::into(&mut ctx);
+        interceptor
+            .modify_before_retry_loop(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+        interceptor
+            .modify_before_transmit(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+
+        let expected = DefaultInvocationIdGenerator::with_seed(1234)
+            .generate()
+            .unwrap()
+            .expect("generated");
+        assert_eq!(
+            expected.0.to_str().ok(),
+            ctx.request().headers().get("x-client-request-id")
+        );
+    }
+}
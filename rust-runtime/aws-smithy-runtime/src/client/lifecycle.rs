@@ -0,0 +1,111 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Tells the [`SharedLifecycleObserver`] from the config bag when operations and their attempts
+//! start and end.
+
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::client::error_report::FailureKind;
+use aws_smithy_runtime_api::client::interceptors::context::{Error, InterceptorContext};
+use aws_smithy_runtime_api::client::lifecycle::{
+    AttemptEnd, AttemptStart, ObserveLifecycle, OperationEnd, OperationStart,
+    SharedLifecycleObserver,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// The observer of an operation, along with what it's told about the operation.
+#[derive(Clone, Debug)]
+pub(crate) struct OperationLifecycle {
+    observer: SharedLifecycleObserver,
+    service: Arc<str>,
+    operation: Arc<str>,
+    time_source: SharedTimeSource,
+    start: SystemTime,
+}
+
+impl Storable for OperationLifecycle {
+    type Storer = StoreReplace<Self>;
+}
+
+impl OperationLifecycle {
+    /// Tells the observer that the operation is starting, or returns `None` if no observer is
+    /// configured.
+    pub(crate) fn start(
+        cfg: &ConfigBag,
+        time_source: SharedTimeSource,
+        service_name: &str,
+        operation_name: &str,
+    ) -> Option<Self> {
+        let observer = cfg.load::<SharedLifecycleObserver>()?.clone();
+        observer.on_operation_start(&OperationStart::new(service_name, operation_name));
+        Some(Self {
+            observer,
+            service: service_name.into(),
+            operation: operation_name.into(),
+            start: time_source.now(),
+            time_source,
+        })
+    }
+
+    /// Tells the observer that the given attempt is starting.
+    pub(crate) fn start_attempt(&self, attempt: u32) -> AttemptLifecycle {
+        self.observer
+            .on_attempt_start(&AttemptStart::new(&self.service, &self.operation, attempt));
+        AttemptLifecycle {
+            attempt,
+            start: self.time_source.now(),
+        }
+    }
+
+    /// Tells the observer how long the operation took, and whether it failed.
+    pub(crate) fn finish(
+        &self,
+        attempts: u32,
+        result: &Result<InterceptorContext, SdkError<Error, HttpResponse>>,
+    ) {
+        let failure = match result {
+            Ok(ctx) => ctx.failure_kind(),
+            Err(err) => Some(FailureKind::from(err)),
+        };
+        self.observer.on_operation_end(&OperationEnd::new(
+            &self.service,
+            &self.operation,
+            attempts,
+            self.elapsed(self.start),
+            failure,
+        ));
+    }
+
+    fn elapsed(&self, start: SystemTime) -> Duration {
+        self.time_source
+            .now()
+            .duration_since(start)
+            .unwrap_or_default()
+    }
+}
+
+/// The number and start time of a single attempt.
+#[derive(Debug)]
+pub(crate) struct AttemptLifecycle {
+    attempt: u32,
+    start: SystemTime,
+}
+
+impl AttemptLifecycle {
+    /// Tells the observer how long the attempt took, and whether it failed.
+    pub(crate) fn finish(self, operation: &OperationLifecycle, failure: Option<FailureKind>) {
+        operation.observer.on_attempt_end(&AttemptEnd::new(
+            &operation.service,
+            &operation.operation,
+            self.attempt,
+            operation.elapsed(self.start),
+            failure,
+        ));
+    }
+}
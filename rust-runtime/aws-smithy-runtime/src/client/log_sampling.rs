@@ -0,0 +1,118 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Sampling for the detailed logs that the orchestrator emits for each operation.
+//!
+//! High-volume clients can emit a lot of `debug` and `trace` logs. When [`LogSampling`] is set in a
+//! client's config, only one in every N operations is fully logged. The other operations only emit
+//! logs at the [unsampled level](LogSampling::with_unsampled_level) or above, which is `info` by
+//! default, so warnings and errors are always logged. When an operation that wasn't sampled fails,
+//! its error is logged at the `debug` level once the operation completes.
+
+use crate::client::filtered_dispatch::FilterSpans;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::level_filters::LevelFilter;
+use tracing::{Level, Metadata};
+
+/// Logs one in every N operations in full, and only less verbose logs for the rest.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_runtime::client::log_sampling::LogSampling;
+/// use tracing::Level;
+///
+/// // Log every 100th operation in full, and only warnings and errors for the rest
+/// let log_sampling = LogSampling::one_in(100).with_unsampled_level(Level::WARN);
+/// assert_eq!(100, log_sampling.rate());
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogSampling {
+    rate: u32,
+    unsampled_level: LevelFilter,
+    operations: Arc<AtomicU64>,
+}
+
+impl LogSampling {
+    /// Fully logs one in every `rate` operations, starting with the first one.
+    ///
+    /// A rate of 0 or 1 fully logs every operation.
+    pub fn one_in(rate: u32) -> Self {
+        Self {
+            rate: rate.max(1),
+            unsampled_level: LevelFilter::INFO,
+            operations: Default::default(),
+        }
+    }
+
+    /// Sets the most verbose level that's still logged for operations that weren't sampled.
+    ///
+    /// Defaults to `info`.
+    pub fn with_unsampled_level(mut self, level: Level) -> Self {
+        self.unsampled_level = LevelFilter::from_level(level);
+        self
+    }
+
+    /// Returns how many operations there are for every one that's fully logged.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Decides whether the next operation is fully logged.
+    ///
+    /// Returns `None` when it is, or the filter that the operation's logs should go through when
+    /// it isn't.
+    pub(crate) fn sample(&self) -> Option<Arc<dyn FilterSpans>> {
+        let operation = self.operations.fetch_add(1, Ordering::Relaxed);
+        if operation % u64::from(self.rate) == 0 {
+            return None;
+        }
+        Some(Arc::new(Unsampled {
+            max_level: self.unsampled_level,
+        }))
+    }
+}
+
+impl Storable for LogSampling {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Allows the spans and events that aren't more verbose than `max_level`.
+#[derive(Debug)]
+struct Unsampled {
+    max_level: LevelFilter,
+}
+
+impl FilterSpans for Unsampled {
+    fn allows(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= &self.max_level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogSampling;
+    use tracing::Level;
+
+    #[test]
+    fn samples_one_in_every_n_operations() {
+        let log_sampling = LogSampling::one_in(3);
+        let sampled = (0..7)
+            .map(|_| log_sampling.sample().is_none())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![true, false, false, true, false, false, true], sampled);
+
+        // Clones share the count, since the config bag is cloned for every operation
+        let clone = log_sampling.clone();
+        assert!(clone.sample().is_some());
+        assert!(log_sampling.sample().is_some());
+        assert!(clone.sample().is_none());
+
+        let every_operation = LogSampling::one_in(0).with_unsampled_level(Level::WARN);
+        assert_eq!(1, every_operation.rate());
+        assert!((0..3).all(|_| every_operation.sample().is_none()));
+    }
+}
@@ -0,0 +1,232 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Metrics that the orchestrator records with the [`SharedMeterProvider`] from the config bag.
+
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_observability::attributes::Attributes;
+use aws_smithy_observability::meter::{
+    Instrument, InstrumentDescriptor, InstrumentKind, MeterProvider, SharedMeterProvider,
+};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::buffered_memory;
+use aws_smithy_types::retry::ErrorKind;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const SCOPE: &str = "aws-smithy-runtime";
+
+const CALL_DURATION: InstrumentDescriptor =
+    InstrumentDescriptor::new("smithy.client.call.duration", InstrumentKind::Histogram)
+        .with_unit("s")
+        .with_description("Overall call duration, including retries and backoff");
+
+const ATTEMPT_DURATION: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.call.attempt.duration",
+    InstrumentKind::Histogram,
+)
+.with_unit("s")
+.with_description("Duration of a single attempt, from endpoint resolution to deserialization");
+
+const CALL_ERRORS: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.call.errors",
+    InstrumentKind::MonotonicCounter,
+)
+.with_unit("{error}")
+.with_description("Number of calls that failed, by the kind of error");
+
+const ATTEMPTS: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.call.attempts",
+    InstrumentKind::MonotonicCounter,
+)
+.with_unit("{attempt}")
+.with_description("Number of attempts made, including the initial request");
+
+const RETRIES: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.call.retries",
+    InstrumentKind::MonotonicCounter,
+)
+.with_unit("{retry}")
+.with_description("Number of retries, by the kind of error that was retried");
+
+const THROTTLES: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.call.throttles",
+    InstrumentKind::MonotonicCounter,
+)
+.with_unit("{error}")
+.with_description("Number of attempts that failed with a throttling error");
+
+const RETRY_QUOTA_EXHAUSTED: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.call.retry_quota_exhausted",
+    InstrumentKind::MonotonicCounter,
+)
+.with_unit("{error}")
+.with_description("Number of retries that weren't attempted because the retry quota was empty");
+
+const BACKOFF_DURATION: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.call.backoff.duration",
+    InstrumentKind::Histogram,
+)
+.with_unit("s")
+.with_description("Time spent backing off before a retry");
+
+const INTERCEPTOR_DURATION: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.interceptor.duration",
+    InstrumentKind::Histogram,
+)
+.with_unit("s")
+.with_description("Duration of a single interceptor hook, by interceptor and hook");
+
+const INTERCEPTOR_ERRORS: InstrumentDescriptor = InstrumentDescriptor::new(
+    "smithy.client.interceptor.errors",
+    InstrumentKind::MonotonicCounter,
+)
+.with_unit("{error}")
+.with_description("Number of interceptor hooks that returned an error, by interceptor and hook");
+
+const BUFFERED_MEMORY: InstrumentDescriptor =
+    InstrumentDescriptor::new("smithy.client.buffered_memory", InstrumentKind::Gauge)
+        .with_unit("By")
+        .with_description("Bytes currently buffered in memory by the runtime, across all clients");
+
+pub(crate) const ERROR_TYPE_ATTRIBUTE: &str = "error.type";
+const INTERCEPTOR_ATTRIBUTE: &str = "smithy.client.interceptor";
+const HOOK_ATTRIBUTE: &str = "smithy.client.interceptor.hook";
+
+/// The instruments that the orchestrator records an operation's metrics with.
+#[derive(Clone, Debug)]
+pub(crate) struct OperationMetrics {
+    attributes: Attributes,
+    time_source: SharedTimeSource,
+    call_duration: Arc<dyn Instrument>,
+    attempt_duration: Arc<dyn Instrument>,
+    call_errors: Arc<dyn Instrument>,
+    attempts: Arc<dyn Instrument>,
+    retries: Arc<dyn Instrument>,
+    throttles: Arc<dyn Instrument>,
+    retry_quota_exhausted: Arc<dyn Instrument>,
+    backoff_duration: Arc<dyn Instrument>,
+    interceptor_duration: Arc<dyn Instrument>,
+    interceptor_errors: Arc<dyn Instrument>,
+    buffered_memory: Arc<dyn Instrument>,
+}
+
+impl OperationMetrics {
+    /// Creates the instruments of an operation, which record measurements with the given
+    /// attributes.
+    pub(crate) fn new(
+        meter_provider: &SharedMeterProvider,
+        time_source: SharedTimeSource,
+        attributes: Attributes,
+    ) -> Self {
+        let meter = meter_provider.meter(SCOPE);
+        Self {
+            attributes,
+            time_source,
+            call_duration: meter.instrument(&CALL_DURATION),
+            attempt_duration: meter.instrument(&ATTEMPT_DURATION),
+            call_errors: meter.instrument(&CALL_ERRORS),
+            attempts: meter.instrument(&ATTEMPTS),
+            retries: meter.instrument(&RETRIES),
+            throttles: meter.instrument(&THROTTLES),
+            retry_quota_exhausted: meter.instrument(&RETRY_QUOTA_EXHAUSTED),
+            backoff_duration: meter.instrument(&BACKOFF_DURATION),
+            interceptor_duration: meter.instrument(&INTERCEPTOR_DURATION),
+            interceptor_errors: meter.instrument(&INTERCEPTOR_ERRORS),
+            buffered_memory: meter.instrument(&BUFFERED_MEMORY),
+        }
+    }
+
+    /// Records the duration of a call that started at `start`, and the kind of error that it
+    /// failed with, if any.
+    pub(crate) fn record_call(&self, start: SystemTime, error_kind: Option<&'static str>) {
+        self.call_duration
+            .record(self.elapsed_secs(start), &self.attributes);
+        self.record_buffered_memory();
+        if let Some(kind) = error_kind {
+            let attributes = self.attributes.clone().with(ERROR_TYPE_ATTRIBUTE, kind);
+            self.call_errors.record(1.0, &attributes);
+        }
+    }
+
+    /// Records that an attempt was started.
+    pub(crate) fn record_attempt_start(&self) {
+        self.attempts.record(1.0, &self.attributes);
+    }
+
+    /// Records the duration of an attempt that started at `start`.
+    pub(crate) fn record_attempt(&self, start: SystemTime) {
+        self.attempt_duration
+            .record(self.elapsed_secs(start), &self.attributes);
+        self.record_buffered_memory();
+    }
+
+    /// Records a retry of an error of the given kind, after the given backoff.
+    pub(crate) fn record_retry(&self, kind: ErrorKind, backoff: Duration) {
+        let attributes = self
+            .attributes
+            .clone()
+            .with(ERROR_TYPE_ATTRIBUTE, retry_error_kind(kind));
+        self.retries.record(1.0, &attributes);
+        self.backoff_duration
+            .record(backoff.as_secs_f64(), &self.attributes);
+    }
+
+    /// Records an attempt that failed with a throttling error.
+    pub(crate) fn record_throttle(&self) {
+        self.throttles.record(1.0, &self.attributes);
+    }
+
+    /// Records a retry that wasn't attempted because there were no retry permits left.
+    pub(crate) fn record_retry_quota_exhausted(&self) {
+        self.retry_quota_exhausted.record(1.0, &self.attributes);
+    }
+
+    /// Runs a hook of the named interceptor, and records how long it took and whether it failed.
+    pub(crate) fn record_interceptor_hook(
+        &self,
+        interceptor: &'static str,
+        hook: &'static str,
+        run: impl FnOnce() -> Result<(), BoxError>,
+    ) -> Result<(), BoxError> {
+        let start = self.time_source.now();
+        let result = run();
+        let attributes = self
+            .attributes
+            .clone()
+            .with(INTERCEPTOR_ATTRIBUTE, interceptor)
+            .with(HOOK_ATTRIBUTE, hook);
+        self.interceptor_duration
+            .record(self.elapsed_secs(start), &attributes);
+        if result.is_err() {
+            self.interceptor_errors.record(1.0, &attributes);
+        }
+        result
+    }
+
+    /// Records the number of bytes that are buffered by the whole process, so it has no attributes.
+    fn record_buffered_memory(&self) {
+        self.buffered_memory
+            .record(buffered_memory::buffered_bytes() as f64, &Attributes::new());
+    }
+
+    fn elapsed_secs(&self, start: SystemTime) -> f64 {
+        self.time_source
+            .now()
+            .duration_since(start)
+            .unwrap_or_default()
+            .as_secs_f64()
+    }
+}
+
+fn retry_error_kind(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::ThrottlingError => "throttling",
+        ErrorKind::TransientError => "transient",
+        ErrorKind::ServerError => "server",
+        ErrorKind::ClientError => "client",
+        _ => "other",
+    }
+}
@@ -7,19 +7,41 @@
 #![allow(unknown_lints)]
 
 use self::auth::orchestrate_auth;
+use self::cancellation::Cancellation;
+use crate::client::filtered_dispatch::{self, FilterSpans, FilteredFuture};
+use crate::client::http::body::counting::{count_bytes, Direction};
+use crate::client::http::body::idle_timeout::IdleTimeoutBody;
+use crate::client::http::connection_poisoning::CaptureSmithyConnection;
 use crate::client::interceptors::Interceptors;
+use crate::client::lifecycle::OperationLifecycle;
+use crate::client::log_sampling::LogSampling;
 use crate::client::orchestrator::endpoints::orchestrate_endpoint;
 use crate::client::orchestrator::http::{log_response_body, read_body};
-use crate::client::timeout::{MaybeTimeout, MaybeTimeoutConfig, TimeoutKind};
-use aws_smithy_async::rt::sleep::AsyncSleep;
+use crate::client::retries::strategy::DeadlineExceededError;
+use crate::client::subsystem_tracing::SubsystemTracing;
+use crate::client::telemetry::{AttemptTelemetry, OperationTelemetry};
+use crate::client::timeout::{MaybeTimeout, MaybeTimeoutConfig, Phase, PhaseTimings, TimeoutKind};
+use aws_smithy_async::rt::sleep::{AsyncSleep, Sleep};
 use aws_smithy_runtime_api::box_error::BoxError;
-use aws_smithy_runtime_api::client::http::{HttpClient, HttpConnector, HttpConnectorSettings};
+use aws_smithy_runtime_api::client::buffered_memory::BufferedMemory;
+use aws_smithy_runtime_api::client::cancellation::CancellationToken;
+use aws_smithy_runtime_api::client::error_report::{
+    ErrorReport, FailureKind, OnError, OperationErrorMetadata,
+};
+use aws_smithy_runtime_api::client::http::{
+    BytesTransferred, HappyEyeballs, Http2Settings, HttpClient, HttpConnector,
+    HttpConnectorSettings, OperationBytesTransferred, PoolSettings, TcpSettings,
+};
 use aws_smithy_runtime_api::client::interceptors::context::{
     Error, Input, InterceptorContext, Output, RewindResult,
 };
+use aws_smithy_runtime_api::client::latency::{
+    AttemptLatency, AttemptLatencyBuilder, LatencyBreakdown,
+};
 use aws_smithy_runtime_api::client::orchestrator::{
-    HttpResponse, LoadedRequestBody, OrchestratorError,
+    BufferStreamingResponse, HttpResponse, LoadedRequestBody, OrchestratorError,
 };
+use aws_smithy_runtime_api::client::request_id::{RequestId, RequestIdExtractor};
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::client::retries::{RequestAttempts, RetryStrategy, ShouldAttempt};
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
@@ -27,15 +49,23 @@ use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugins;
 use aws_smithy_runtime_api::client::ser_de::{
     DeserializeResponse, SerializeRequest, SharedRequestSerializer, SharedResponseDeserializer,
 };
+use aws_smithy_runtime_api::client::shutdown::ClientShutdown;
 use aws_smithy_types::body::SdkBody;
 use aws_smithy_types::byte_stream::ByteStream;
 use aws_smithy_types::config_bag::ConfigBag;
-use aws_smithy_types::timeout::TimeoutConfig;
+use aws_smithy_types::endpoint::Endpoint;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::timeout::{Deadline, OperationTimeoutConfig, TimeoutConfig};
+use std::error::Error as StdError;
 use std::mem;
-use tracing::{debug, debug_span, instrument, trace, Instrument};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, debug_span, field, instrument, trace, Instrument, Span};
 
 mod auth;
 
+mod cancellation;
+
 /// Defines types that implement a trait for endpoint resolution
 pub mod endpoints;
 
@@ -99,6 +129,38 @@ macro_rules! run_interceptors {
 /// This orchestration handles retries, endpoint resolution, identity resolution, and signing.
 /// Each of these are configurable via the config and runtime components given by the runtime
 /// plugins.
+///
+/// # Tracing spans
+///
+/// Each operation runs in a `debug` span named `invoke`, and each of its attempts runs in a child
+/// `debug` span named `attempt`. The names and values of their fields are stable, so that log
+/// pipelines and APMs can rely on them:
+///
+/// | Span      | Field            | Value                                                                    |
+/// |-----------|------------------|--------------------------------------------------------------------------|
+/// | `invoke`  | `service`        | The name of the service                                                  |
+/// | `invoke`  | `operation`      | The name of the operation                                                |
+/// | `invoke`  | `attempts`       | The number of attempts that were made, once the operation completes      |
+/// | `attempt` | `attempt`        | The number of the attempt, starting at 1                                 |
+/// | `attempt` | `retry_delay_ms` | How long the attempt was delayed by the retry strategy, in milliseconds  |
+/// | `attempt` | `status_code`    | The HTTP status code of the response, once one is received              |
+/// | `attempt` | `request_id`     | The request ID of the response, once one is received                    |
+///
+/// Fields are left empty when their value isn't known, such as `status_code` for an attempt that
+/// failed before it received a response. The request ID is read from the headers of the
+/// [`RequestIdExtractor`] in the config bag, or from the default request ID headers.
+///
+/// When [`LogSampling`] is in the config bag, operations that aren't sampled only emit the spans
+/// and events at its unsampled level or above once their configuration has been applied.
+/// Likewise, when [`SubsystemTracing`] is in the config bag, the spans and events of retries,
+/// signing, endpoint resolution, and the connector are filtered by their own levels.
+///
+/// When an [`OnError`] hook is in the config bag, it's called with an [`ErrorReport`] of every
+/// operation that fails once its configuration has been applied.
+///
+/// When a [`SharedLifecycleObserver`](aws_smithy_runtime_api::client::lifecycle::SharedLifecycleObserver)
+/// is in the config bag, it's told when the operation and each of its attempts start and end,
+/// along with how long they took and how they failed.
 pub async fn invoke(
     service_name: &str,
     operation_name: &str,
@@ -150,25 +212,204 @@ pub async fn invoke_with_stop_point(
             .map_err(SdkError::construction_failure)?;
         trace!(runtime_components = ?runtime_components);
 
+        cfg.interceptor_state()
+            .store_put(OperationBytesTransferred::new());
+
+        // Track where the time goes so that timeout errors can report it
+        let has_timeouts = cfg
+            .load::<TimeoutConfig>()
+            .map(|timeout_config| OperationTimeoutConfig::from(timeout_config).has_timeouts())
+            .unwrap_or_default()
+            || cfg.load::<Deadline>().is_some();
+        if let (true, Some(time_source)) = (has_timeouts, runtime_components.time_source()) {
+            cfg.interceptor_state()
+                .store_put(PhaseTimings::new(time_source));
+        }
         let operation_timeout_config =
             MaybeTimeoutConfig::new(&runtime_components, cfg, TimeoutKind::Operation);
         trace!(operation_timeout_config = ?operation_timeout_config);
-        async {
+        // Let the retry strategy know how long it has to work with, by replacing the deadline with
+        // whichever of the deadline and the operation timeout ends first
+        if let (Some(timeout), Some(time_source)) = (
+            operation_timeout_config.timeout(),
+            runtime_components.time_source(),
+        ) {
+            cfg.interceptor_state()
+                .store_put(Deadline::new(time_source.now() + timeout));
+        }
+        // Keep the client from shutting down until this operation completes
+        let in_flight = match cfg.load::<ClientShutdown>() {
+            Some(shutdown) => match shutdown.start_operation() {
+                Some(in_flight) => Some(in_flight),
+                None => {
+                    let result = Err(SdkError::construction_failure(
+                        "the client has been shut down, so no new operations can be started",
+                    ));
+                    report_error(cfg, service_name, operation_name, &result);
+                    return result;
+                }
+            },
+            None => None,
+        };
+        // Let the operation be cancelled by its cancellation token, or by its client shutting down
+        let mut cancellation = Cancellation::default();
+        if let Some(token) = cfg.load::<CancellationToken>() {
+            cancellation.add(
+                token.clone(),
+                "the operation was cancelled by its cancellation token",
+            );
+        }
+        if let Some(in_flight) = &in_flight {
+            cancellation.add(
+                in_flight.cancellation_token().clone(),
+                "the operation was cancelled because its client was shut down",
+            );
+        }
+        cfg.interceptor_state().store_put(cancellation.clone());
+        let time_source = runtime_components.time_source().unwrap_or_default();
+        let started_at = time_source.now();
+        // Record the call and attempt telemetry when a meter or tracer provider is configured
+        let telemetry =
+            OperationTelemetry::start(cfg, time_source.clone(), service_name, operation_name);
+        if let Some(telemetry) = &telemetry {
+            cfg.interceptor_state().store_put(telemetry.clone());
+        }
+        // Tell the lifecycle observer, if there is one, as the operation and its attempts progress
+        let lifecycle =
+            OperationLifecycle::start(cfg, time_source.clone(), service_name, operation_name);
+        if let Some(lifecycle) = &lifecycle {
+            cfg.interceptor_state().store_put(lifecycle.clone());
+        }
+        // Operations that weren't sampled only log at the configured unsampled level or above
+        let unsampled = cfg.load::<LogSampling>().and_then(LogSampling::sample);
+        // Each subsystem only logs at its configured level or above
+        let subsystems = cfg
+            .load::<SubsystemTracing>()
+            .map(|subsystem_tracing| Arc::new(subsystem_tracing.clone()) as Arc<dyn FilterSpans>);
+        let operation = async {
             // If running the pre-execution interceptors failed, then we skip running the op and run the
             // final interceptors instead.
             if !ctx.is_failed() {
-                try_op(&mut ctx, cfg, &runtime_components, stop_point).await;
+                // Cancelled operations still run the final interceptors
+                let cancelled = match cancellation.check() {
+                    Ok(()) => {
+                        cancellation
+                            .run(try_op(&mut ctx, cfg, &runtime_components, stop_point))
+                            .await
+                    }
+                    Err(err) => Err(err),
+                };
+                continue_on_err!([ctx] => cancelled);
             }
             finally_op(&mut ctx, cfg, &runtime_components).await;
             Ok(ctx)
         }
-        .maybe_timeout(operation_timeout_config)
-        .await
+        .maybe_timeout(operation_timeout_config);
+        let result = FilteredFuture::new(
+            operation,
+            filtered_dispatch::both(unsampled.clone(), subsystems),
+        )
+        .await;
+        if unsampled.is_some() {
+            log_unsampled_failure(&result);
+        }
+        if let Some(telemetry) = telemetry {
+            telemetry.finish(&result);
+        }
+        if let Some(lifecycle) = lifecycle {
+            let attempts = cfg
+                .load::<RequestAttempts>()
+                .map(RequestAttempts::attempts)
+                .unwrap_or_default();
+            lifecycle.finish(attempts, &result);
+        }
+        report_error(cfg, service_name, operation_name, &result);
+        // Add the latency breakdown to the final response so that outputs and errors can expose it
+        let result = result.map(|mut ctx| {
+            let latency = cfg.get_mut_or_default::<LatencyBreakdown>();
+            latency.set_total(time_source.now().duration_since(started_at).ok());
+            if let Some(response) = ctx.response_mut() {
+                response.add_extension(latency.clone());
+            }
+            ctx
+        });
+        if let Some(attempts) = cfg.load::<RequestAttempts>() {
+            Span::current().record("attempts", attempts.attempts());
+        }
+        result
     }
-    .instrument(debug_span!("invoke", service = %service_name, operation = %operation_name))
+    .instrument(debug_span!(
+        "invoke",
+        service = %service_name,
+        operation = %operation_name,
+        attempts = field::Empty,
+    ))
     .await
 }
 
+/// Logs why an operation that wasn't sampled failed, so that failures are logged in full.
+fn log_unsampled_failure(result: &Result<InterceptorContext, SdkError<Error, HttpResponse>>) {
+    match result {
+        Ok(ctx) => {
+            if let Some(Err(err)) = ctx.output_or_error() {
+                debug!(error = %DisplayErrorContext(err), "operation failed");
+            }
+        }
+        Err(err) => debug!(error = %DisplayErrorContext(err), "operation failed"),
+    }
+}
+
+/// Calls the [`OnError`] hook in the config bag, if there is one, when the operation failed.
+fn report_error(
+    cfg: &ConfigBag,
+    service_name: &str,
+    operation_name: &str,
+    result: &Result<InterceptorContext, SdkError<Error, HttpResponse>>,
+) {
+    let on_error = match cfg.load::<OnError>() {
+        Some(on_error) => on_error,
+        None => return,
+    };
+    let (kind, error, operation_error, response): (_, &(dyn StdError + 'static), _, _) =
+        match result {
+            Ok(ctx) => match (ctx.output_or_error(), ctx.failure_kind()) {
+                (Some(Err(err)), Some(kind)) => {
+                    (kind, err, err.as_operation_error(), ctx.response())
+                }
+                _ => return,
+            },
+            Err(err) => {
+                let operation_error = match err {
+                    SdkError::ServiceError(context) => Some(context.err()),
+                    _ => None,
+                };
+                (
+                    FailureKind::from(err),
+                    err,
+                    operation_error,
+                    err.raw_response(),
+                )
+            }
+        };
+
+    let mut report = ErrorReport::new(service_name, operation_name, kind, error)
+        .with_request_id(response.and_then(RequestId::request_id))
+        .with_attempts(
+            cfg.load::<RequestAttempts>()
+                .map(RequestAttempts::attempts)
+                .unwrap_or_default(),
+        )
+        .with_endpoint(cfg.load::<Endpoint>().map(Endpoint::url));
+    let metadata = operation_error.and_then(|err| {
+        cfg.load::<OperationErrorMetadata>()
+            .and_then(|metadata| metadata.error_metadata(err))
+    });
+    if let Some(metadata) = metadata {
+        report = report.with_error_metadata(metadata);
+    }
+    on_error.report(&report);
+}
+
 /// Apply configuration is responsible for apply runtime plugins to the config bag, as well as running
 /// `read_before_execution` interceptors. If a failure occurs due to config construction, `invoke`
 /// will raise it to the user. If an interceptor fails, then `invoke`
@@ -209,6 +450,7 @@ async fn try_op(
 
     // Serialization
     ctx.enter_serialization_phase();
+    enter_phase(cfg, Phase::Serialization);
     {
         let _span = debug_span!("serialization").entered();
         let request_serializer = cfg
@@ -237,6 +479,7 @@ async fn try_op(
 
     // Before transmit
     ctx.enter_before_transmit_phase();
+    exit_phase(cfg);
     run_interceptors!(halt_on_err: {
         read_after_serialization(ctx, runtime_components, cfg);
         modify_before_retry_loop(ctx, runtime_components, cfg);
@@ -269,7 +512,14 @@ async fn try_op(
     // Save a request checkpoint before we make the request. This will allow us to "rewind"
     // the request in the case of retry attempts.
     ctx.save_checkpoint();
-    let mut retry_delay = None;
+    // Request bodies that are in memory are kept there until the last attempt, so they can be retried
+    let _retry_buffer = halt_on_err!([ctx] => BufferedMemory::reserve(
+        ctx.request()
+            .and_then(|request| request.body().bytes())
+            .map_or(0, <[u8]>::len)
+    )
+    .map_err(OrchestratorError::other));
+    let mut retry_delay: Option<(Duration, Sleep)> = None;
     for i in 1u32.. {
         // Break from the loop if we can't rewind the request's state. This will always succeed the
         // first time, but will fail on subsequent iterations if the request body wasn't retryable.
@@ -281,33 +531,116 @@ async fn try_op(
         // Track which attempt we're currently on.
         cfg.interceptor_state()
             .store_put::<RequestAttempts>(i.into());
+        let attempt_span = debug_span!(
+            "attempt",
+            attempt = i,
+            retry_delay_ms = field::Empty,
+            status_code = field::Empty,
+            request_id = field::Empty,
+        );
         // Backoff time should not be included in the attempt timeout
         if let Some((delay, sleep)) = retry_delay.take() {
+            attempt_span.record("retry_delay_ms", delay.as_millis() as u64);
             debug!("delaying for {delay:?}");
+            enter_phase(cfg, Phase::RetryBackoff);
             sleep.await;
+            exit_phase(cfg);
         }
         let attempt_timeout_config =
             MaybeTimeoutConfig::new(runtime_components, cfg, TimeoutKind::OperationAttempt);
         trace!(attempt_timeout_config = ?attempt_timeout_config);
-        let maybe_timeout = async {
+        let telemetry = cfg.load::<OperationTelemetry>().cloned();
+        let attempt_telemetry = telemetry
+            .as_ref()
+            .map(|telemetry| telemetry.start_attempt(i));
+        // Let interceptors propagate the attempt's span to downstream services
+        if let Some(span_context) = attempt_telemetry
+            .as_ref()
+            .and_then(AttemptTelemetry::span_context)
+        {
+            cfg.interceptor_state().store_put(span_context);
+        }
+        let lifecycle = cfg.load::<OperationLifecycle>().cloned();
+        let attempt_lifecycle = lifecycle
+            .as_ref()
+            .map(|lifecycle| lifecycle.start_attempt(i));
+        let time_source = runtime_components.time_source().unwrap_or_default();
+        let attempt_started_at = time_source.now();
+        let mut attempt_latency = AttemptLatency::builder();
+        let cancellation = cfg.load::<Cancellation>().cloned().unwrap_or_default();
+        let attempt = async {
             debug!("beginning attempt #{i}");
-            try_attempt(ctx, cfg, runtime_components, stop_point).await;
+            try_attempt(
+                ctx,
+                cfg,
+                runtime_components,
+                stop_point,
+                &mut attempt_latency,
+            )
+            .await;
+            exit_phase(cfg);
             finally_attempt(ctx, cfg, runtime_components).await;
+            if let Some(response) = ctx.response() {
+                let span = Span::current();
+                span.record("status_code", response.status().as_u16());
+                if let Some(request_id) = response.request_id() {
+                    span.record("request_id", request_id);
+                }
+            }
+            debug!("ending attempt #{i}");
             Result::<_, SdkError<Error, HttpResponse>>::Ok(())
         }
-        .maybe_timeout(attempt_timeout_config)
-        .await
-        .map_err(|err| OrchestratorError::timeout(err.into_source().unwrap()));
-
-        // We continue when encountering a timeout error. The retry classifier will decide what to do with it.
-        continue_on_err!([ctx] => maybe_timeout);
+        .instrument(attempt_span)
+        .maybe_timeout(attempt_timeout_config);
+        // Cancellation aborts the attempt rather than the whole operation, so that the attempt is
+        // still accounted for
+        let maybe_timeout = cancellation.run(attempt).await.and_then(|result| {
+            result.map_err(|err| OrchestratorError::timeout(err.into_source().unwrap()))
+        });
+        if let (Some(telemetry), Some(attempt_telemetry)) = (telemetry, attempt_telemetry) {
+            let error = match &maybe_timeout {
+                Err(err) => Some(err),
+                Ok(()) => ctx.output_or_error().and_then(Result::err),
+            };
+            attempt_telemetry.finish(&telemetry, error);
+        }
+        if let (Some(lifecycle), Some(attempt_lifecycle)) = (lifecycle, attempt_lifecycle) {
+            let failure = match &maybe_timeout {
+                Err(err) if err.is_cancelled_error() => Some(FailureKind::Cancelled),
+                Err(_) => Some(FailureKind::TimeoutError),
+                Ok(()) => ctx.failure_kind(),
+            };
+            attempt_lifecycle.finish(&lifecycle, failure);
+        }
+        attempt_latency.set_total(time_source.now().duration_since(attempt_started_at).ok());
+        cfg.get_mut_or_default::<LatencyBreakdown>()
+            .push_attempt(attempt_latency.build());
+
+        match maybe_timeout {
+            // Cancelled attempts aren't retried, but their final interceptors still run
+            Err(err) if err.is_cancelled_error() => {
+                ctx.fail(err);
+                finally_attempt(ctx, cfg, runtime_components).await;
+                return;
+            }
+            // We continue when encountering a timeout error. The retry classifier will decide what to do with it.
+            maybe_timeout => continue_on_err!([ctx] => maybe_timeout),
+        }
 
         // If we got a retry strategy from the bag, ask it what to do.
         // If no strategy was set, we won't retry.
-        let should_attempt = halt_on_err!([ctx] => runtime_components
-            .retry_strategy()
-            .should_attempt_retry(ctx, runtime_components, cfg)
-            .map_err(OrchestratorError::other));
+        let should_attempt =
+            runtime_components
+                .retry_strategy()
+                .should_attempt_retry(ctx, runtime_components, cfg);
+        let should_attempt = match should_attempt {
+            Ok(should_attempt) => should_attempt,
+            Err(err) => {
+                debug!("encountered orchestrator error; halting");
+                fail_with_retry_strategy_error(ctx, err);
+                return;
+            }
+        };
         match should_attempt {
             // Yes, let's retry the request
             ShouldAttempt::Yes => continue,
@@ -327,15 +660,40 @@ async fn try_op(
     }
 }
 
+/// Fails the operation with an error returned by the retry strategy.
+///
+/// When the operation deadline prevents a retry, the error of the last attempt becomes the source
+/// of the [`DeadlineExceededError`], so that the reason that the attempt failed isn't lost.
+fn fail_with_retry_strategy_error(ctx: &mut InterceptorContext, err: BoxError) {
+    let deadline_exceeded = match err.downcast::<DeadlineExceededError>() {
+        Ok(deadline_exceeded) => *deadline_exceeded,
+        Err(err) => {
+            ctx.fail(OrchestratorError::other(err));
+            return;
+        }
+    };
+    let err = match ctx.output_or_error_mut() {
+        Some(Err(last_error)) => {
+            // The last error is replaced right away, so it's only moved into the deadline error
+            let placeholder = OrchestratorError::other(deadline_exceeded.without_source());
+            deadline_exceeded.with_source(mem::replace(last_error, placeholder))
+        }
+        _ => deadline_exceeded,
+    };
+    ctx.set_output_or_error(Err(OrchestratorError::other(err)));
+}
+
 #[instrument(skip_all, level = "debug")]
 async fn try_attempt(
     ctx: &mut InterceptorContext,
     cfg: &mut ConfigBag,
     runtime_components: &RuntimeComponents,
     stop_point: StopPoint,
+    latency: &mut AttemptLatencyBuilder,
 ) {
     run_interceptors!(halt_on_err: read_before_attempt(ctx, runtime_components, cfg));
 
+    enter_phase(cfg, Phase::RequestPreparation);
     halt_on_err!([ctx] => orchestrate_endpoint(ctx, runtime_components, cfg).await.map_err(OrchestratorError::other));
 
     run_interceptors!(halt_on_err: {
@@ -357,11 +715,25 @@ async fn try_attempt(
         return;
     }
 
+    // Count the bytes of this attempt, and add them to the operation's counts
+    let bytes_transferred = BytesTransferred::new();
+    cfg.interceptor_state().store_put(bytes_transferred.clone());
+    let byte_counters: Vec<_> = std::iter::once(bytes_transferred)
+        .chain(
+            cfg.load::<OperationBytesTransferred>()
+                .map(|op| op.bytes().clone()),
+        )
+        .collect();
+
     // The connection consumes the request but we need to keep a copy of it
     // within the interceptor context, so we clone it here.
     ctx.enter_transmit_phase();
-    let response = halt_on_err!([ctx] => {
-        let request = ctx.take_request().expect("set during serialization");
+    let time_source = runtime_components.time_source().unwrap_or_default();
+    let mut response = halt_on_err!([ctx] => {
+        let mut request = ctx.take_request().expect("set during serialization");
+        let body = request.take_body();
+        let body = count_bytes(body, Direction::Sent, byte_counters.clone());
+        *request.body_mut() = enter_transmit_phase(cfg, body);
         trace!(request = ?request, "transmitting request");
         let http_client = halt_on_err!([ctx] => runtime_components.http_client().ok_or_else(||
             OrchestratorError::other("No HTTP client was available to send this request. \
@@ -371,15 +743,45 @@ async fn try_attempt(
         let settings = {
             let mut builder = HttpConnectorSettings::builder();
             builder.set_connect_timeout(timeout_config.connect_timeout());
+            builder.set_tls_negotiation_timeout(timeout_config.tls_negotiation_timeout());
             builder.set_read_timeout(timeout_config.read_timeout());
+            builder.set_http2_settings(cfg.load::<Http2Settings>().cloned());
+            builder.set_happy_eyeballs(cfg.load::<HappyEyeballs>().copied());
+            builder.set_pool_settings(cfg.load::<PoolSettings>().cloned());
+            builder.set_tcp_settings(cfg.load::<TcpSettings>().cloned());
             builder.build()
         };
         let connector = http_client.http_connector(&settings, runtime_components);
-        connector.call(request).await.map_err(OrchestratorError::connector)
+        let sent_at = time_source.now();
+        let mut response = halt_on_err!([ctx] => connector.call(request).await.map_err(OrchestratorError::connector));
+        latency.set_time_to_first_byte(time_source.now().duration_since(sent_at).ok());
+        let body = mem::replace(response.body_mut(), SdkBody::taken());
+        *response.body_mut() = count_bytes(body, Direction::Received, byte_counters);
+        if let (Some(timeout), Some(sleep_impl)) = (timeout_config.body_read_timeout(), runtime_components.sleep_impl()) {
+            trace!(timeout = ?timeout, "adding a body read timeout to the response body");
+            let body = mem::replace(response.body_mut(), SdkBody::taken());
+            *response.body_mut() = SdkBody::from_body_0_4(IdleTimeoutBody::new(sleep_impl, body, timeout));
+        }
+        Ok::<_, OrchestratorError<_>>(response)
     });
     trace!(response = ?response, "received response from service");
+    // Read the request ID from the configured headers, if the service doesn't use the default ones
+    if let Some(extractor) = cfg.load::<RequestIdExtractor>() {
+        extractor.apply(&mut response);
+    }
     ctx.set_response(response);
+    // Connection timings are only known when the HTTP client reports them
+    if let Some(connection) = cfg
+        .load::<CaptureSmithyConnection>()
+        .and_then(CaptureSmithyConnection::get)
+    {
+        latency
+            .set_dns(connection.dns_duration())
+            .set_connect(connection.connect_duration())
+            .set_tls_handshake(connection.tls_handshake_duration());
+    }
     ctx.enter_before_deserialization_phase();
+    enter_phase(cfg, Phase::ResponseRead);
 
     run_interceptors!(halt_on_err: {
         read_after_transmit(ctx, runtime_components, cfg);
@@ -388,11 +790,21 @@ async fn try_attempt(
     });
 
     ctx.enter_deserialization_phase();
+    let deserialization_started_at = time_source.now();
     let output_or_error = async {
         let response = ctx.response_mut().expect("set during transmit");
         let response_deserializer = cfg
             .load::<SharedResponseDeserializer>()
             .expect("a request deserializer must be in the config bag");
+        // Load the response body into memory if configured to do so, so that failures while
+        // reading a streaming body become retryable response errors
+        if cfg.load::<BufferStreamingResponse>().is_some() {
+            debug!("loading response body into memory");
+            read_body(response)
+                .instrument(debug_span!("read_body"))
+                .await
+                .map_err(OrchestratorError::response)?;
+        }
         let maybe_deserialized = {
             let _span = debug_span!("deserialize_streaming").entered();
             response_deserializer.deserialize_streaming(response)
@@ -412,6 +824,12 @@ async fn try_attempt(
     }
     .instrument(debug_span!("deserialization"))
     .await;
+    latency.set_deserialization(
+        time_source
+            .now()
+            .duration_since(deserialization_started_at)
+            .ok(),
+    );
     trace!(output_or_error = ?output_or_error);
     ctx.set_output_or_error(output_or_error);
 
@@ -419,6 +837,36 @@ async fn try_attempt(
     run_interceptors!(halt_on_err: read_after_deserialization(ctx, runtime_components, cfg));
 }
 
+/// Starts timing `phase` of the current attempt, ending the previous phase.
+fn enter_phase(cfg: &ConfigBag, phase: Phase) {
+    if let Some(phase_timings) = cfg.load::<PhaseTimings>() {
+        let attempt = match phase {
+            Phase::Serialization | Phase::RetryBackoff => None,
+            _ => cfg.load::<RequestAttempts>().map(RequestAttempts::attempts),
+        };
+        phase_timings.enter(phase, attempt);
+    }
+}
+
+/// Starts timing the transmission of a request with the given body, and returns the body to send.
+fn enter_transmit_phase(cfg: &ConfigBag, body: SdkBody) -> SdkBody {
+    match cfg.load::<PhaseTimings>() {
+        Some(phase_timings) => phase_timings.enter_transmit(
+            cfg.load::<RequestAttempts>().map(RequestAttempts::attempts),
+            cfg.load::<CaptureSmithyConnection>().cloned(),
+            body,
+        ),
+        None => body,
+    }
+}
+
+/// Ends timing of the current phase.
+fn exit_phase(cfg: &ConfigBag) {
+    if let Some(phase_timings) = cfg.load::<PhaseTimings>() {
+        phase_timings.exit();
+    }
+}
+
 #[instrument(skip_all, level = "debug")]
 async fn finally_attempt(
     ctx: &mut InterceptorContext,
@@ -453,6 +901,7 @@ mod tests {
         deserializer::CannedResponseDeserializer, serializer::CannedRequestSerializer,
     };
     use ::http::{Response, StatusCode};
+    use aws_smithy_async::time::SharedTimeSource;
     use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
     use aws_smithy_runtime_api::client::auth::{
         AuthSchemeOptionResolverParams, SharedAuthSchemeOptionResolver,
@@ -461,7 +910,7 @@ mod tests {
         EndpointResolverParams, SharedEndpointResolver,
     };
     use aws_smithy_runtime_api::client::http::{
-        http_client_fn, HttpConnector, HttpConnectorFuture,
+        http_client_fn, HttpConnector, HttpConnectorFuture, SharedHttpConnector,
     };
     use aws_smithy_runtime_api::client::interceptors::context::{
         AfterDeserializationInterceptorContextRef, BeforeDeserializationInterceptorContextMut,
@@ -474,7 +923,9 @@ mod tests {
     use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
     use aws_smithy_runtime_api::client::retries::SharedRetryStrategy;
     use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
-    use aws_smithy_runtime_api::client::runtime_plugin::{RuntimePlugin, RuntimePlugins};
+    use aws_smithy_runtime_api::client::runtime_plugin::{
+        RuntimePlugin, RuntimePlugins, StaticRuntimePlugin,
+    };
     use aws_smithy_runtime_api::shared::IntoShared;
     use aws_smithy_types::config_bag::{ConfigBag, FrozenLayer, Layer};
     use std::borrow::Cow;
@@ -509,6 +960,7 @@ mod tests {
         fn call(&self, _request: HttpRequest) -> HttpConnectorFuture {
             HttpConnectorFuture::ready(Ok(::http::Response::builder()
                 .status(200)
+                .header("x-amzn-requestid", "test-request-id")
                 .body(SdkBody::empty())
                 .expect("OK response is valid")
                 .try_into()
@@ -525,6 +977,7 @@ mod tests {
         fn new() -> Self {
             Self {
                 builder: RuntimeComponentsBuilder::for_tests()
+                    .with_time_source(Some(SharedTimeSource::default()))
                     .with_retry_strategy(Some(SharedRetryStrategy::new(NeverRetryStrategy::new())))
                     .with_endpoint_resolver(Some(SharedEndpointResolver::new(
                         StaticUriEndpointResolver::http_localhost(8080),
@@ -1314,4 +1767,756 @@ mod tests {
             .read_after_execution_called
             .load(Ordering::Relaxed));
     }
+
+    #[tokio::test]
+    async fn test_cancellation_aborts_in_flight_attempt() {
+        use aws_smithy_runtime_api::client::lifecycle::{
+            AttemptEnd, ObserveLifecycle, SharedLifecycleObserver,
+        };
+        use std::sync::Mutex;
+
+        // Cancels the token once the request is in flight, and then never responds
+        #[derive(Debug)]
+        struct CancellingConnector(CancellationToken);
+        impl HttpConnector for CancellingConnector {
+            fn call(&self, _request: HttpRequest) -> HttpConnectorFuture {
+                self.0.cancel();
+                HttpConnectorFuture::new(async {
+                    aws_smithy_async::future::never::Never::new().await;
+                    unreachable!("the connector never responds")
+                })
+            }
+        }
+
+        // Records the hooks that run once the operation was cancelled
+        #[derive(Debug)]
+        struct RecordingInterceptor(Arc<Mutex<Vec<String>>>);
+        impl Intercept for RecordingInterceptor {
+            fn name(&self) -> &'static str {
+                "RecordingInterceptor"
+            }
+
+            fn read_after_attempt(
+                &self,
+                _ctx: &FinalizerInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                self.0.lock().unwrap().push("read_after_attempt".into());
+                Ok(())
+            }
+
+            fn modify_before_completion(
+                &self,
+                _ctx: &mut FinalizerInterceptorContextMut<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push("modify_before_completion".into());
+                Ok(())
+            }
+
+            fn read_after_execution(
+                &self,
+                ctx: &FinalizerInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                let cancelled = matches!(
+                    ctx.output_or_error(),
+                    Some(Err(err)) if err.is_cancelled_error()
+                );
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("read_after_execution cancelled={cancelled}"));
+                Ok(())
+            }
+        }
+
+        #[derive(Debug)]
+        struct RecordingObserver(Arc<Mutex<Vec<String>>>);
+        impl ObserveLifecycle for RecordingObserver {
+            fn on_attempt_end(&self, attempt: &AttemptEnd<'_>) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("end attempt {:?}", attempt.failure()));
+            }
+        }
+
+        let token = CancellationToken::new();
+        let connector = SharedHttpConnector::new(CancellingConnector(token.clone()));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut layer = Layer::new("cancellation");
+        layer.store_put(token);
+        layer.store_put(SharedLifecycleObserver::new(RecordingObserver(
+            events.clone(),
+        )));
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new())
+            .with_operation_plugin(
+                StaticRuntimePlugin::new()
+                    .with_config(layer.freeze())
+                    .with_runtime_components(
+                        RuntimeComponentsBuilder::new("test")
+                            .with_http_client(Some(http_client_fn(move |_, _| connector.clone())))
+                            .with_interceptor(SharedInterceptor::new(RecordingInterceptor(
+                                events.clone(),
+                            ))),
+                    ),
+            );
+
+        let err = invoke("test", "test", Input::doesnt_matter(), &runtime_plugins)
+            .await
+            .expect_err("the operation was cancelled");
+        assert!(matches!(err, SdkError::Cancelled(_)), "{err:?}");
+        assert_eq!(
+            vec![
+                "end attempt Some(Cancelled)",
+                "read_after_attempt",
+                "modify_before_completion",
+                "read_after_execution cancelled=true",
+            ],
+            *events.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_operations_fail_after_shutdown() {
+        let shutdown = ClientShutdown::new();
+        let mut layer = Layer::new("shutdown");
+        layer.store_put(shutdown.clone());
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new())
+            .with_operation_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()));
+
+        invoke("test", "test", Input::doesnt_matter(), &runtime_plugins)
+            .await
+            .expect("the client hasn't been shut down yet");
+        assert_eq!(0, shutdown.in_flight());
+
+        shutdown
+            .shutdown(std::time::Duration::from_secs(1), None)
+            .await
+            .expect("nothing is in flight");
+        let err = invoke("test", "test", Input::doesnt_matter(), &runtime_plugins)
+            .await
+            .expect_err("the client was shut down");
+        assert!(matches!(err, SdkError::ConstructionFailure(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_attempt_spans_have_semantic_fields() {
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new());
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &runtime_plugins,
+        )
+        .await
+        .expect("success");
+
+        assert!(logs_contain(
+            "invoke{service=test-service operation=TestOperation}"
+        ));
+        assert!(logs_contain("attempt{attempt=1"));
+        assert!(logs_contain("status_code=200"));
+        assert!(logs_contain("request_id=\"test-request-id\""));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_only_sampled_operations_are_fully_logged() {
+        #[derive(Debug)]
+        struct FailingInterceptor;
+        impl Intercept for FailingInterceptor {
+            fn name(&self) -> &'static str {
+                "FailingInterceptor"
+            }
+
+            fn read_before_transmit(
+                &self,
+                _ctx: &BeforeTransmitInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                Err("failed on purpose".into())
+            }
+        }
+
+        let mut layer = Layer::new("log_sampling");
+        layer.store_put(LogSampling::one_in(2));
+        let config = layer.freeze();
+        let runtime_plugins = || {
+            RuntimePlugins::new()
+                .with_operation_plugin(TestOperationRuntimePlugin::new())
+                .with_operation_plugin(NoAuthRuntimePlugin::new())
+                .with_operation_plugin(StaticRuntimePlugin::new().with_config(config.clone()))
+        };
+        // Every other operation is fully logged, starting with the first one
+        for _ in 0..3 {
+            invoke(
+                "test-service",
+                "TestOperation",
+                Input::doesnt_matter(),
+                &runtime_plugins(),
+            )
+            .await
+            .expect("success");
+        }
+        let failing_plugins = runtime_plugins().with_operation_plugin(
+            StaticRuntimePlugin::new().with_runtime_components(
+                RuntimeComponentsBuilder::new("test")
+                    .with_interceptor(SharedInterceptor::new(FailingInterceptor)),
+            ),
+        );
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &failing_plugins,
+        )
+        .await
+        .expect_err("the interceptor failed");
+
+        logs_assert(|lines: &[&str]| {
+            let logged = lines
+                .iter()
+                .filter(|line| line.contains("beginning attempt #1"))
+                .count();
+            if logged != 2 {
+                return Err(format!("expected 2 fully logged operations, got {logged}"));
+            }
+            if !lines
+                .iter()
+                .any(|line| line.contains("operation failed") && line.contains("failed on purpose"))
+            {
+                return Err("expected the failure of the unsampled operation to be logged".into());
+            }
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_observer_is_told_about_operations_and_attempts() {
+        use aws_smithy_runtime_api::client::lifecycle::{
+            AttemptEnd, AttemptStart, ObserveLifecycle, OperationEnd, OperationStart,
+            SharedLifecycleObserver,
+        };
+        use std::sync::Mutex;
+
+        #[derive(Debug)]
+        struct RecordingObserver(Arc<Mutex<Vec<String>>>);
+        impl ObserveLifecycle for RecordingObserver {
+            fn on_operation_start(&self, operation: &OperationStart<'_>) {
+                self.0.lock().unwrap().push(format!(
+                    "start {}.{}",
+                    operation.service(),
+                    operation.operation()
+                ));
+            }
+
+            fn on_attempt_start(&self, attempt: &AttemptStart<'_>) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("start attempt {}", attempt.attempt()));
+            }
+
+            fn on_attempt_end(&self, attempt: &AttemptEnd<'_>) {
+                self.0.lock().unwrap().push(format!(
+                    "end attempt {} {:?}",
+                    attempt.attempt(),
+                    attempt.failure()
+                ));
+            }
+
+            fn on_operation_end(&self, operation: &OperationEnd<'_>) {
+                self.0.lock().unwrap().push(format!(
+                    "end after {} attempts {:?}",
+                    operation.attempts(),
+                    operation.failure()
+                ));
+            }
+        }
+
+        #[derive(Debug)]
+        struct FailingInterceptor;
+        impl Intercept for FailingInterceptor {
+            fn name(&self) -> &'static str {
+                "FailingInterceptor"
+            }
+
+            fn read_after_transmit(
+                &self,
+                _ctx: &BeforeDeserializationInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                Err("failed on purpose".into())
+            }
+        }
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut layer = Layer::new("lifecycle");
+        layer.store_put(SharedLifecycleObserver::new(RecordingObserver(
+            events.clone(),
+        )));
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new())
+            .with_operation_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()));
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &runtime_plugins,
+        )
+        .await
+        .expect("success");
+        assert_eq!(
+            vec![
+                "start test-service.TestOperation",
+                "start attempt 1",
+                "end attempt 1 None",
+                "end after 1 attempts None",
+            ],
+            events.lock().unwrap().drain(..).collect::<Vec<_>>()
+        );
+
+        let failing_plugins = runtime_plugins.with_operation_plugin(
+            StaticRuntimePlugin::new().with_runtime_components(
+                RuntimeComponentsBuilder::new("test")
+                    .with_interceptor(SharedInterceptor::new(FailingInterceptor)),
+            ),
+        );
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &failing_plugins,
+        )
+        .await
+        .expect_err("the interceptor failed");
+        assert_eq!(
+            Some("end after 1 attempts Some(ResponseError)"),
+            events.lock().unwrap().last().map(String::as_str)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_on_error_is_called_with_a_report_of_the_failure() {
+        use std::sync::Mutex;
+
+        #[derive(Debug)]
+        struct FailingInterceptor;
+        impl Intercept for FailingInterceptor {
+            fn name(&self) -> &'static str {
+                "FailingInterceptor"
+            }
+
+            fn read_after_transmit(
+                &self,
+                _ctx: &BeforeDeserializationInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                Err("failed on purpose".into())
+            }
+        }
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let mut layer = Layer::new("on_error");
+        layer.store_put(OnError::new({
+            let reports = reports.clone();
+            move |report| {
+                reports.lock().unwrap().push((
+                    report.service().to_string(),
+                    report.operation().to_string(),
+                    report.kind(),
+                    report.attempts(),
+                    report.endpoint().map(str::to_string),
+                    DisplayErrorContext(report.error()).to_string(),
+                ))
+            }
+        }));
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new())
+            .with_operation_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()));
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &runtime_plugins,
+        )
+        .await
+        .expect("success");
+        assert!(reports.lock().unwrap().is_empty());
+
+        let failing_plugins = runtime_plugins.with_operation_plugin(
+            StaticRuntimePlugin::new().with_runtime_components(
+                RuntimeComponentsBuilder::new("test")
+                    .with_interceptor(SharedInterceptor::new(FailingInterceptor)),
+            ),
+        );
+        let err = invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &failing_plugins,
+        )
+        .await
+        .expect_err("the interceptor failed");
+        assert!(matches!(err, SdkError::ResponseError(_)), "{err:?}");
+
+        let reports = reports.lock().unwrap();
+        let (service, operation, kind, attempts, endpoint, error) = &reports[0];
+        assert_eq!(1, reports.len());
+        assert_eq!("test-service", service);
+        assert_eq!("TestOperation", operation);
+        assert_eq!(FailureKind::ResponseError, *kind);
+        assert_eq!(1, *attempts);
+        assert_eq!(Some("http://localhost:8080"), endpoint.as_deref());
+        assert!(error.contains("failed on purpose"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_are_recorded_with_the_configured_meter_provider() {
+        use aws_smithy_observability::attributes::{AttributeValue, Attributes};
+        use aws_smithy_observability::meter::{
+            Instrument, InstrumentDescriptor, Meter, MeterProvider, SharedMeterProvider,
+        };
+        use std::sync::Mutex;
+
+        type Measurements = Arc<Mutex<Vec<(&'static str, Attributes)>>>;
+
+        #[derive(Debug, Default)]
+        struct RecordingMeterProvider(Measurements);
+        impl MeterProvider for RecordingMeterProvider {
+            fn meter(&self, _scope: &'static str) -> Arc<dyn Meter> {
+                Arc::new(RecordingMeterProvider(self.0.clone()))
+            }
+        }
+        impl Meter for RecordingMeterProvider {
+            fn instrument(&self, descriptor: &InstrumentDescriptor) -> Arc<dyn Instrument> {
+                Arc::new(RecordingInstrument(descriptor.name(), self.0.clone()))
+            }
+        }
+
+        #[derive(Debug)]
+        struct RecordingInstrument(&'static str, Measurements);
+        impl Instrument for RecordingInstrument {
+            fn record(&self, _value: f64, attributes: &Attributes) {
+                self.1.lock().unwrap().push((self.0, attributes.clone()));
+            }
+        }
+
+        #[derive(Debug)]
+        struct FailingInterceptor;
+        impl Intercept for FailingInterceptor {
+            fn name(&self) -> &'static str {
+                "FailingInterceptor"
+            }
+
+            fn read_before_execution(
+                &self,
+                _ctx: &BeforeSerializationInterceptorContextRef<'_>,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                Err("failed".into())
+            }
+        }
+
+        let measurements = Measurements::default();
+        let mut layer = Layer::new("metrics");
+        layer.store_put(SharedMeterProvider::new(RecordingMeterProvider(
+            measurements.clone(),
+        )));
+        let config = layer.freeze();
+        let runtime_plugins = || {
+            RuntimePlugins::new()
+                .with_operation_plugin(TestOperationRuntimePlugin::new())
+                .with_operation_plugin(NoAuthRuntimePlugin::new())
+                .with_operation_plugin(StaticRuntimePlugin::new().with_config(config.clone()))
+        };
+
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &runtime_plugins(),
+        )
+        .await
+        .expect("success");
+        let recorded: Vec<_> = measurements.lock().unwrap().drain(..).collect();
+        assert_eq!(
+            vec![
+                "smithy.client.call.attempts",
+                "smithy.client.call.attempt.duration",
+                "smithy.client.buffered_memory",
+                "smithy.client.call.duration",
+                "smithy.client.buffered_memory"
+            ],
+            recorded.iter().map(|(name, _)| *name).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&AttributeValue::from("TestOperation")),
+            recorded[3].1.get("rpc.method")
+        );
+
+        let failing_plugins = runtime_plugins().with_operation_plugin(
+            StaticRuntimePlugin::new().with_runtime_components(
+                RuntimeComponentsBuilder::new("test")
+                    .with_interceptor(SharedInterceptor::new(FailingInterceptor)),
+            ),
+        );
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &failing_plugins,
+        )
+        .await
+        .expect_err("the interceptor failed");
+        let recorded: Vec<_> = measurements.lock().unwrap().drain(..).collect();
+        let (_, error_attributes) = recorded
+            .iter()
+            .find(|(name, _)| *name == "smithy.client.call.errors")
+            .expect("the error was counted");
+        assert_eq!(
+            Some(&AttributeValue::from("interceptor")),
+            error_attributes.get("error.type")
+        );
+
+        #[derive(Debug)]
+        struct FailingTransmitInterceptor;
+        impl Intercept for FailingTransmitInterceptor {
+            fn name(&self) -> &'static str {
+                "FailingTransmitInterceptor"
+            }
+
+            fn read_before_transmit(
+                &self,
+                _ctx: &BeforeTransmitInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                Err("failed".into())
+            }
+        }
+
+        let failing_plugins = runtime_plugins().with_operation_plugin(
+            StaticRuntimePlugin::new().with_runtime_components(
+                RuntimeComponentsBuilder::new("test")
+                    .with_interceptor(SharedInterceptor::new(FailingTransmitInterceptor)),
+            ),
+        );
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &failing_plugins,
+        )
+        .await
+        .expect_err("the interceptor failed");
+        let recorded = measurements.lock().unwrap();
+        let hooks: Vec<_> = recorded
+            .iter()
+            .filter(|(name, _)| *name == "smithy.client.interceptor.duration")
+            .filter_map(|(_, attributes)| attributes.get("smithy.client.interceptor.hook"))
+            .collect();
+        assert!(hooks.contains(&&AttributeValue::from("read_before_transmit")));
+        assert!(hooks.contains(&&AttributeValue::from("read_after_execution")));
+        let (_, error_attributes) = recorded
+            .iter()
+            .find(|(name, _)| *name == "smithy.client.interceptor.errors")
+            .expect("the interceptor error was counted");
+        assert_eq!(
+            Some(&AttributeValue::from("FailingTransmitInterceptor")),
+            error_attributes.get("smithy.client.interceptor")
+        );
+        assert_eq!(
+            Some(&AttributeValue::from("read_before_transmit")),
+            error_attributes.get("smithy.client.interceptor.hook")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latency_breakdown_is_added_to_the_response() {
+        use aws_smithy_runtime_api::client::latency::ProvideLatency;
+
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new());
+        let ctx = invoke_with_stop_point(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &runtime_plugins,
+            StopPoint::None,
+        )
+        .await
+        .expect("success");
+
+        let latency = ctx
+            .response()
+            .and_then(ProvideLatency::latency)
+            .expect("the latency breakdown is added to the response");
+        assert!(latency.total().is_some());
+        assert_eq!(1, latency.attempts().len());
+        let attempt = latency.last_attempt().unwrap();
+        assert!(attempt.time_to_first_byte().is_some());
+        assert!(attempt.deserialization().is_some());
+        assert!(attempt.total().is_some());
+        // The test connector doesn't report connection timings
+        assert_eq!(None, attempt.connect());
+    }
+
+    #[tokio::test]
+    async fn test_spans_are_started_with_the_configured_tracer_provider() {
+        use aws_smithy_observability::attributes::{AttributeValue, Attributes};
+        use aws_smithy_observability::tracer::{
+            SharedTracerProvider, Span, Tracer, TracerProvider,
+        };
+        use std::sync::Mutex;
+
+        type Events = Arc<Mutex<Vec<String>>>;
+
+        #[derive(Debug, Default)]
+        struct RecordingTracerProvider(Events);
+        impl TracerProvider for RecordingTracerProvider {
+            fn tracer(&self, _scope: &'static str) -> Arc<dyn Tracer> {
+                Arc::new(RecordingTracerProvider(self.0.clone()))
+            }
+        }
+        impl Tracer for RecordingTracerProvider {
+            fn start_span(&self, name: &'static str, attributes: &Attributes) -> Arc<dyn Span> {
+                let method = match attributes.get("rpc.method") {
+                    Some(AttributeValue::String(method)) => method.to_string(),
+                    _ => String::new(),
+                };
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("start {name} {method}"));
+                Arc::new(RecordingSpan(name, self.0.clone()))
+            }
+        }
+
+        #[derive(Debug)]
+        struct RecordingSpan(&'static str, Events);
+        impl Span for RecordingSpan {
+            fn start_child(&self, name: &'static str, attributes: &Attributes) -> Arc<dyn Span> {
+                let attempt = match attributes.get("smithy.client.attempt") {
+                    Some(AttributeValue::I64(attempt)) => *attempt,
+                    _ => 0,
+                };
+                self.1
+                    .lock()
+                    .unwrap()
+                    .push(format!("start {name} {attempt}"));
+                Arc::new(RecordingSpan(name, self.1.clone()))
+            }
+
+            fn set_attribute(&self, _key: &'static str, _value: AttributeValue) {}
+
+            fn set_error(&self, description: &str) {
+                self.1
+                    .lock()
+                    .unwrap()
+                    .push(format!("error {} {description}", self.0));
+            }
+
+            fn end(&self) {
+                self.1.lock().unwrap().push(format!("end {}", self.0));
+            }
+        }
+
+        let events = Events::default();
+        let mut layer = Layer::new("traces");
+        layer.store_put(SharedTracerProvider::new(RecordingTracerProvider(
+            events.clone(),
+        )));
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new())
+            .with_operation_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()));
+
+        invoke(
+            "test-service",
+            "TestOperation",
+            Input::doesnt_matter(),
+            &runtime_plugins,
+        )
+        .await
+        .expect("success");
+        assert_eq!(
+            vec![
+                "start smithy.client.call TestOperation",
+                "start smithy.client.attempt 1",
+                "end smithy.client.attempt",
+                "end smithy.client.call",
+            ],
+            *events.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn deadline_errors_keep_the_last_attempt_error_as_their_source() {
+        use crate::client::retries::strategy::StandardRetryStrategy;
+        use aws_smithy_async::time::StaticTimeSource;
+        use aws_smithy_runtime_api::client::retries::classifiers::SharedRetryClassifier;
+        use aws_smithy_runtime_api::client::retries::AlwaysRetry;
+        use aws_smithy_types::retry::{ErrorKind, RetryConfig};
+        use std::time::SystemTime;
+
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.fail(OrchestratorError::other("the last attempt failed"));
+        let started_at = SystemTime::UNIX_EPOCH;
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_retry_classifier(SharedRetryClassifier::new(AlwaysRetry(
+                ErrorKind::ServerError,
+            )))
+            .with_time_source(Some(StaticTimeSource::new(
+                started_at + Duration::from_secs(4),
+            )))
+            .build()
+            .unwrap();
+        let mut layer = Layer::new("test");
+        layer.store_put(RequestAttempts::new(2));
+        layer.store_put(
+            RetryConfig::standard()
+                .with_use_static_exponential_base(true)
+                .with_max_attempts(3),
+        );
+        // Retrying requires a 2s backoff, which would end after the deadline
+        layer.store_put(Deadline::new(started_at + Duration::from_secs(5)));
+        let cfg = ConfigBag::of_layers(vec![layer]);
+
+        let err = StandardRetryStrategy::new()
+            .should_attempt_retry(&ctx, &runtime_components, &cfg)
+            .expect_err("the deadline would be exceeded");
+        fail_with_retry_strategy_error(&mut ctx, err);
+        let err = ctx
+            .output_or_error()
+            .and_then(Result::err)
+            .expect("the operation failed");
+        let message = format!("{}", DisplayErrorContext(err));
+        assert!(
+            message.contains("the operation deadline would be exceeded"),
+            "{message}"
+        );
+        assert!(message.contains("the last attempt failed"), "{message}");
+    }
 }
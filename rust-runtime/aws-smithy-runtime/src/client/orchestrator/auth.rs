@@ -6,19 +6,22 @@
 use crate::client::auth::no_auth::NO_AUTH_SCHEME_ID;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::{
-    AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, AuthSchemeOptionResolverParams,
-    ResolveAuthSchemeOptions,
+    AnonymousAuth, AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId,
+    AuthSchemeOptionResolverParams, AuthSchemePreference, ResolveAuthSchemeOptions,
+};
+use aws_smithy_runtime_api::client::identity::{
+    Identity, IdentityCacheLocation, ResolveCachedIdentity, ResolveIdentity, SharedIdentityResolver,
 };
-use aws_smithy_runtime_api::client::identity::ResolveCachedIdentity;
 use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::ConfigBag;
 use aws_smithy_types::endpoint::Endpoint;
+use aws_smithy_types::error::display::DisplayErrorContext;
 use aws_smithy_types::Document;
 use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt;
-use tracing::trace;
+use tracing::{debug, trace};
 
 #[derive(Debug)]
 enum AuthOrchestrationError {
@@ -39,6 +42,74 @@ impl fmt::Display for AuthOrchestrationError {
 
 impl StdError for AuthOrchestrationError {}
 
+/// Returns the auth scheme options for the operation, in the order they should be tried.
+fn auth_scheme_options<'a>(
+    runtime_components: &'a RuntimeComponents,
+    cfg: &'a ConfigBag,
+) -> Result<Cow<'a, [AuthSchemeId]>, BoxError> {
+    let params = cfg
+        .load::<AuthSchemeOptionResolverParams>()
+        .expect("auth scheme option resolver params must be set");
+    if cfg.load::<AnonymousAuth>().map(AnonymousAuth::is_enabled) == Some(true) {
+        trace!("anonymous auth is enabled, so the request won't be signed");
+        return Ok(Cow::Borrowed(&[NO_AUTH_SCHEME_ID][..]));
+    }
+    let resolver = runtime_components.auth_scheme_option_resolver();
+    let options = resolver.resolve_auth_scheme_options(params)?;
+    Ok(Cow::Owned(match cfg.load::<AuthSchemePreference>() {
+        Some(preference) => apply_preference(&options, preference),
+        None => options.into_owned(),
+    }))
+}
+
+async fn resolve_identity(
+    identity_resolver: SharedIdentityResolver,
+    runtime_components: &RuntimeComponents,
+    cfg: &ConfigBag,
+) -> Result<Identity, BoxError> {
+    // Resolvers that cache identities themselves bypass the identity cache
+    if identity_resolver.cache_location() == IdentityCacheLocation::IdentityResolver {
+        identity_resolver
+            .resolve_identity(runtime_components, cfg)
+            .await
+    } else {
+        runtime_components
+            .identity_cache()
+            .resolve_cached_identity(identity_resolver, runtime_components, cfg)
+            .await
+    }
+}
+
+/// Resolves the identity of the first auth scheme option that has an identity resolver, so that
+/// endpoint resolution can depend on it.
+///
+/// Errors aren't returned, since the auth scheme that's eventually selected may not need this
+/// identity. They're returned by [`orchestrate_auth`] instead if it does.
+pub(super) async fn resolve_identity_for_endpoint(
+    runtime_components: &RuntimeComponents,
+    cfg: &ConfigBag,
+) -> Option<Identity> {
+    let options = match auth_scheme_options(runtime_components, cfg) {
+        Ok(options) => options,
+        Err(err) => {
+            debug!(error = %DisplayErrorContext(&*err), "failed to resolve auth scheme options");
+            return None;
+        }
+    };
+    let identity_resolver = options.iter().find_map(|&scheme_id| {
+        runtime_components
+            .auth_scheme(scheme_id)?
+            .identity_resolver(runtime_components)
+    })?;
+    match resolve_identity(identity_resolver, runtime_components, cfg).await {
+        Ok(identity) => Some(identity),
+        Err(err) => {
+            debug!(error = %DisplayErrorContext(&*err), "failed to resolve identity for endpoint resolution");
+            None
+        }
+    }
+}
+
 pub(super) async fn orchestrate_auth(
     ctx: &mut InterceptorContext,
     runtime_components: &RuntimeComponents,
@@ -47,8 +118,7 @@ pub(super) async fn orchestrate_auth(
     let params = cfg
         .load::<AuthSchemeOptionResolverParams>()
         .expect("auth scheme option resolver params must be set");
-    let option_resolver = runtime_components.auth_scheme_option_resolver();
-    let options = option_resolver.resolve_auth_scheme_options(params)?;
+    let options = auth_scheme_options(runtime_components, cfg)?;
     let endpoint = cfg
         .load::<Endpoint>()
         .expect("endpoint added to config bag by endpoint orchestrator");
@@ -79,9 +149,8 @@ pub(super) async fn orchestrate_auth(
                     Ok(auth_scheme_endpoint_config) => {
                         trace!(auth_scheme_endpoint_config = ?auth_scheme_endpoint_config, "extracted auth scheme endpoint config");
 
-                        let identity = identity_cache
-                            .resolve_cached_identity(identity_resolver, runtime_components, cfg)
-                            .await?;
+                        let identity =
+                            resolve_identity(identity_resolver, runtime_components, cfg).await?;
                         trace!(identity = ?identity, "resolved identity");
 
                         trace!("signing request");
@@ -107,6 +176,20 @@ pub(super) async fn orchestrate_auth(
     Err(AuthOrchestrationError::NoMatchingAuthScheme.into())
 }
 
+/// Moves the preferred auth scheme options to the front, in the order of preference.
+fn apply_preference(
+    options: &[AuthSchemeId],
+    preference: &AuthSchemePreference,
+) -> Vec<AuthSchemeId> {
+    let mut ordered: Vec<AuthSchemeId> = Vec::with_capacity(options.len());
+    for scheme_id in preference.iter().chain(options) {
+        if options.contains(scheme_id) && !ordered.contains(scheme_id) {
+            ordered.push(*scheme_id);
+        }
+    }
+    ordered
+}
+
 fn extract_endpoint_auth_scheme_config(
     endpoint: &Endpoint,
     scheme_id: AuthSchemeId,
@@ -327,6 +410,127 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "http-auth")]
+    #[tokio::test]
+    async fn prefers_schemes_in_the_configured_order() {
+        use crate::client::auth::http::{BasicAuthScheme, BearerAuthScheme};
+        use aws_smithy_runtime_api::client::auth::http::{
+            HTTP_BASIC_AUTH_SCHEME_ID, HTTP_BEARER_AUTH_SCHEME_ID,
+        };
+        use aws_smithy_runtime_api::client::identity::http::{Login, Token};
+
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        ctx.set_request(HttpRequest::empty());
+        let _ = ctx.take_input();
+        ctx.enter_before_transmit_phase();
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_auth_scheme(SharedAuthScheme::new(BasicAuthScheme::new()))
+            .with_auth_scheme(SharedAuthScheme::new(BearerAuthScheme::new()))
+            .with_auth_scheme_option_resolver(Some(SharedAuthSchemeOptionResolver::new(
+                StaticAuthSchemeOptionResolver::new(vec![
+                    HTTP_BASIC_AUTH_SCHEME_ID,
+                    HTTP_BEARER_AUTH_SCHEME_ID,
+                ]),
+            )))
+            .with_identity_resolver(
+                HTTP_BASIC_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(Login::new("a", "b", None)),
+            )
+            .with_identity_resolver(
+                HTTP_BEARER_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(Token::new("t", None)),
+            )
+            .build()
+            .unwrap();
+
+        let mut layer = Layer::new("test");
+        layer.store_put(Endpoint::builder().url("dontcare").build());
+        layer.store_put(AuthSchemeOptionResolverParams::new("doesntmatter"));
+        layer.store_put(AuthSchemePreference::new([
+            AuthSchemeId::new("unsupported"),
+            HTTP_BEARER_AUTH_SCHEME_ID,
+        ]));
+        let cfg = ConfigBag::of_layers(vec![layer]);
+
+        orchestrate_auth(&mut ctx, &runtime_components, &cfg)
+            .await
+            .expect("success");
+        assert_eq!(
+            "Bearer t",
+            ctx.request()
+                .expect("request is set")
+                .headers()
+                .get("Authorization")
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "http-auth")]
+    #[tokio::test]
+    async fn anonymous_auth_skips_signing() {
+        use crate::client::auth::http::BearerAuthScheme;
+        use crate::client::auth::no_auth::NoAuthScheme;
+        use crate::client::identity::no_auth::NoAuthIdentityResolver;
+        use aws_smithy_runtime_api::client::auth::http::HTTP_BEARER_AUTH_SCHEME_ID;
+        use aws_smithy_runtime_api::client::identity::http::Token;
+
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        ctx.set_request(HttpRequest::empty());
+        let _ = ctx.take_input();
+        ctx.enter_before_transmit_phase();
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_auth_scheme(SharedAuthScheme::new(BearerAuthScheme::new()))
+            .with_auth_scheme(SharedAuthScheme::new(NoAuthScheme::new()))
+            .with_auth_scheme_option_resolver(Some(SharedAuthSchemeOptionResolver::new(
+                StaticAuthSchemeOptionResolver::new(vec![HTTP_BEARER_AUTH_SCHEME_ID]),
+            )))
+            .with_identity_resolver(
+                HTTP_BEARER_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(Token::new("t", None)),
+            )
+            .with_identity_resolver(
+                NO_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(NoAuthIdentityResolver::new()),
+            )
+            .build()
+            .unwrap();
+
+        let mut layer = Layer::new("test");
+        layer.store_put(Endpoint::builder().url("dontcare").build());
+        layer.store_put(AuthSchemeOptionResolverParams::new("doesntmatter"));
+        layer.store_put(AnonymousAuth::new(true));
+        let cfg = ConfigBag::of_layers(vec![layer]);
+
+        orchestrate_auth(&mut ctx, &runtime_components, &cfg)
+            .await
+            .expect("success");
+        assert!(ctx
+            .request()
+            .expect("request is set")
+            .headers()
+            .get("Authorization")
+            .is_none());
+    }
+
+    #[test]
+    fn preferred_options_are_moved_to_the_front() {
+        let (a, b, c) = (
+            AuthSchemeId::new("a"),
+            AuthSchemeId::new("b"),
+            AuthSchemeId::new("c"),
+        );
+        let preference = AuthSchemePreference::new([c, AuthSchemeId::new("d"), b, c]);
+        assert_eq!(vec![c, b, a], apply_preference(&[a, b, c], &preference));
+        assert_eq!(
+            vec![a, b],
+            apply_preference(&[a, b], &AuthSchemePreference::default())
+        );
+    }
+
     #[test]
     fn extract_endpoint_auth_scheme_config_no_config() {
         let endpoint = Endpoint::builder()
@@ -0,0 +1,77 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::cancellation::CancellationToken;
+use aws_smithy_runtime_api::client::interceptors::context::Error;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::future::{pending, poll_fn, Future};
+use std::pin::{pin, Pin};
+use std::task::Poll;
+use tracing::debug;
+
+/// The tokens that cancel an operation, along with the reason that each of them gives.
+///
+/// Cancelling an operation aborts the step that it's on, such as an in-flight attempt, and fails
+/// it with a cancellation error. The operation still goes through its completion phase, so that
+/// every interceptor sees how it ended.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Cancellation {
+    tokens: Vec<(CancellationToken, &'static str)>,
+}
+
+impl Storable for Cancellation {
+    type Storer = StoreReplace<Self>;
+}
+
+impl Cancellation {
+    /// Adds a token that cancels the operation for the given reason.
+    pub(super) fn add(&mut self, token: CancellationToken, reason: &'static str) {
+        self.tokens.push((token, reason));
+    }
+
+    /// Returns a cancellation error if any of the tokens has been cancelled.
+    pub(super) fn check(&self) -> Result<(), OrchestratorError<Error>> {
+        match self.tokens.iter().find(|(token, _)| token.is_cancelled()) {
+            Some((_, reason)) => Err(cancelled(reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs `future` to completion, unless one of the tokens is cancelled first.
+    ///
+    /// When a token is cancelled, `future` is dropped, which aborts the in-flight attempt, if
+    /// there is one, and releases its connection. `future` is polled before the tokens are
+    /// checked, so that when cancellable futures are nested, the innermost one sees the
+    /// cancellation first, and gets to clean up after itself.
+    pub(super) async fn run<F: Future>(
+        &self,
+        future: F,
+    ) -> Result<F::Output, OrchestratorError<Error>> {
+        let mut future = pin!(future);
+        let mut tokens: Vec<_> = self
+            .tokens
+            .iter()
+            .map(|(token, reason)| (token.run_until_cancelled(pending::<()>()), *reason))
+            .collect();
+        poll_fn(|cx| {
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+            for (token, reason) in &mut tokens {
+                if Pin::new(token).poll(cx).is_ready() {
+                    return Poll::Ready(Err(cancelled(reason)));
+                }
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+fn cancelled(reason: &'static str) -> OrchestratorError<Error> {
+    debug!("{reason}");
+    OrchestratorError::cancelled(reason.into())
+}
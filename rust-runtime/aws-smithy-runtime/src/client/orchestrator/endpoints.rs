@@ -3,15 +3,18 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::client::orchestrator::auth::resolve_identity_for_endpoint;
 use aws_smithy_http::endpoint::error::ResolveEndpointError;
 use aws_smithy_http::endpoint::EndpointPrefix;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::endpoint::{
-    EndpointFuture, EndpointResolverParams, ResolveEndpoint,
+    DisableHostPrefix, EndpointFuture, EndpointResolverParams, ResolveEndpoint,
+    ResolveIdentityBeforeEndpoint,
 };
 use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
 use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::client::shutdown::ClientShutdown;
 use aws_smithy_types::config_bag::ConfigBag;
 use aws_smithy_types::endpoint::Endpoint;
 use http::header::HeaderName;
@@ -76,10 +79,28 @@ pub(super) async fn orchestrate_endpoint(
 ) -> Result<(), BoxError> {
     trace!("orchestrating endpoint resolution");
 
+    if cfg.load::<ResolveIdentityBeforeEndpoint>().is_some() {
+        if let Some(identity) = resolve_identity_for_endpoint(runtime_components, cfg).await {
+            if let Some(params) = cfg.get_mut::<EndpointResolverParams>() {
+                params.set_property(identity);
+            }
+        }
+    }
+
+    // Let endpoint resolvers that refresh in the background stop once the client is shut down
+    if let Some(shutdown) = cfg.load::<ClientShutdown>().cloned() {
+        if let Some(params) = cfg.get_mut::<EndpointResolverParams>() {
+            params.set_property(shutdown);
+        }
+    }
+
     let params = cfg
         .load::<EndpointResolverParams>()
         .expect("endpoint resolver params must be set");
-    let endpoint_prefix = cfg.load::<EndpointPrefix>();
+    let endpoint_prefix = match cfg.load::<DisableHostPrefix>() {
+        Some(_) => None,
+        None => cfg.load::<EndpointPrefix>(),
+    };
     tracing::debug!(endpoint_params = ?params, endpoint_prefix = ?endpoint_prefix, "resolving endpoint");
     let request = ctx.request_mut().expect("set during serialization");
 
@@ -150,9 +171,167 @@ fn apply_endpoint(
 #[cfg(test)]
 mod test {
     use aws_smithy_http::endpoint::EndpointPrefix;
+    use aws_smithy_runtime_api::client::endpoint::{
+        EndpointFuture, EndpointResolverParams, ResolveEndpoint,
+    };
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
     use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::{ConfigBag, Layer};
     use aws_smithy_types::endpoint::Endpoint;
 
+    #[tokio::test]
+    async fn endpoint_resolvers_can_await() {
+        #[derive(Debug)]
+        struct AsyncResolver;
+        impl ResolveEndpoint for AsyncResolver {
+            fn resolve_endpoint<'a>(
+                &'a self,
+                _params: &'a EndpointResolverParams,
+            ) -> EndpointFuture<'a> {
+                EndpointFuture::new(async {
+                    // Stand-in for a service discovery lookup
+                    tokio::task::yield_now().await;
+                    Ok(Endpoint::builder()
+                        .url("https://discovered.example.com")
+                        .build())
+                })
+            }
+        }
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(AsyncResolver))
+            .build()
+            .unwrap();
+        let mut layer = Layer::new("test");
+        layer.store_put(EndpointResolverParams::new(()));
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let mut req = HttpRequest::empty();
+        req.set_uri("/foo").unwrap();
+        ctx.set_request(req);
+
+        super::orchestrate_endpoint(&mut ctx, &runtime_components, &mut cfg)
+            .await
+            .expect("success");
+        assert_eq!(
+            "https://discovered.example.com/foo",
+            ctx.request().unwrap().uri()
+        );
+    }
+
+    #[tokio::test]
+    async fn endpoint_resolvers_can_read_properties_set_by_interceptors() {
+        #[derive(Clone, Debug)]
+        struct TenantId(&'static str);
+
+        #[derive(Debug)]
+        struct TenantResolver;
+        impl ResolveEndpoint for TenantResolver {
+            fn resolve_endpoint<'a>(
+                &'a self,
+                params: &'a EndpointResolverParams,
+            ) -> EndpointFuture<'a> {
+                let tenant = params.get_property::<TenantId>().expect("set").0;
+                EndpointFuture::ready(Ok(Endpoint::builder()
+                    .url(format!("https://{tenant}.example.com"))
+                    .build()))
+            }
+        }
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(TenantResolver))
+            .build()
+            .unwrap();
+        let mut layer = Layer::new("test");
+        layer.store_put(EndpointResolverParams::new(()));
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        // What an interceptor does from `modify_before_serialization`
+        cfg.get_mut::<EndpointResolverParams>()
+            .expect("params are set")
+            .set_property(TenantId("tenant-1"));
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let mut req = HttpRequest::empty();
+        req.set_uri("/foo").unwrap();
+        ctx.set_request(req);
+
+        super::orchestrate_endpoint(&mut ctx, &runtime_components, &mut cfg)
+            .await
+            .expect("success");
+        assert_eq!(
+            "https://tenant-1.example.com/foo",
+            ctx.request().unwrap().uri()
+        );
+    }
+
+    #[cfg(feature = "http-auth")]
+    #[tokio::test]
+    async fn identity_is_resolved_before_the_endpoint_when_requested() {
+        use crate::client::auth::http::BearerAuthScheme;
+        use aws_smithy_runtime_api::client::auth::http::HTTP_BEARER_AUTH_SCHEME_ID;
+        use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
+        use aws_smithy_runtime_api::client::auth::{
+            AuthSchemeOptionResolverParams, SharedAuthScheme, SharedAuthSchemeOptionResolver,
+        };
+        use aws_smithy_runtime_api::client::endpoint::ResolveIdentityBeforeEndpoint;
+        use aws_smithy_runtime_api::client::identity::http::Token;
+        use aws_smithy_runtime_api::client::identity::{Identity, SharedIdentityResolver};
+
+        #[derive(Debug)]
+        struct IdentityResolver;
+        impl ResolveEndpoint for IdentityResolver {
+            fn resolve_endpoint<'a>(
+                &'a self,
+                params: &'a EndpointResolverParams,
+            ) -> EndpointFuture<'a> {
+                let host = match params.get_property::<Identity>() {
+                    Some(identity) => identity.data::<Token>().expect("token").token(),
+                    None => "anonymous",
+                };
+                EndpointFuture::ready(Ok(Endpoint::builder()
+                    .url(format!("https://{host}.example.com"))
+                    .build()))
+            }
+        }
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(IdentityResolver))
+            .with_auth_scheme(SharedAuthScheme::new(BearerAuthScheme::new()))
+            .with_auth_scheme_option_resolver(Some(SharedAuthSchemeOptionResolver::new(
+                StaticAuthSchemeOptionResolver::new(vec![HTTP_BEARER_AUTH_SCHEME_ID]),
+            )))
+            .with_identity_resolver(
+                HTTP_BEARER_AUTH_SCHEME_ID,
+                SharedIdentityResolver::new(Token::new("tenant-1", None)),
+            )
+            .build()
+            .unwrap();
+        let resolve = |resolve_identity: bool| {
+            let runtime_components = runtime_components.clone();
+            async move {
+                let mut layer = Layer::new("test");
+                layer.store_put(EndpointResolverParams::new(()));
+                layer.store_put(AuthSchemeOptionResolverParams::new(()));
+                if resolve_identity {
+                    layer.store_put(ResolveIdentityBeforeEndpoint::new());
+                }
+                let mut cfg = ConfigBag::of_layers(vec![layer]);
+                let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+                ctx.enter_serialization_phase();
+                ctx.set_request(HttpRequest::empty());
+                super::orchestrate_endpoint(&mut ctx, &runtime_components, &mut cfg)
+                    .await
+                    .expect("success");
+                ctx.request().unwrap().uri().to_string()
+            }
+        };
+
+        assert_eq!("https://anonymous.example.com/", resolve(false).await);
+        assert_eq!("https://tenant-1.example.com/", resolve(true).await);
+    }
+
     #[test]
     fn test_apply_endpoint() {
         let mut req = HttpRequest::empty();
@@ -165,4 +344,62 @@ mod test {
             "https://prefix.subdomain.s3.amazon.com/foo?bar=1"
         );
     }
+
+    #[test]
+    fn apply_endpoint_preserves_base_path() {
+        let mut req = HttpRequest::empty();
+        req.set_uri("/foo?bar=1").unwrap();
+        let endpoint = Endpoint::builder()
+            .url("https://gateway.example.com/api/v3")
+            .build();
+        let prefix = EndpointPrefix::new("data.").unwrap();
+        super::apply_endpoint(&mut req, &endpoint, Some(&prefix)).expect("should succeed");
+        assert_eq!(
+            req.uri(),
+            "https://data.gateway.example.com/api/v3/foo?bar=1"
+        );
+    }
+
+    #[tokio::test]
+    async fn host_prefix_can_be_disabled() {
+        use aws_smithy_runtime_api::client::endpoint::DisableHostPrefix;
+
+        #[derive(Debug)]
+        struct LocalResolver;
+        impl ResolveEndpoint for LocalResolver {
+            fn resolve_endpoint<'a>(
+                &'a self,
+                _params: &'a EndpointResolverParams,
+            ) -> EndpointFuture<'a> {
+                EndpointFuture::ready(Ok(Endpoint::builder().url("http://localhost:4566").build()))
+            }
+        }
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_endpoint_resolver(Some(LocalResolver))
+            .build()
+            .unwrap();
+        let resolve = |disable_host_prefix: bool| {
+            let runtime_components = runtime_components.clone();
+            async move {
+                let mut layer = Layer::new("test");
+                layer.store_put(EndpointResolverParams::new(()));
+                layer.store_put(EndpointPrefix::new("data.").unwrap());
+                if disable_host_prefix {
+                    layer.store_put(DisableHostPrefix::new());
+                }
+                let mut cfg = ConfigBag::of_layers(vec![layer]);
+                let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+                ctx.enter_serialization_phase();
+                ctx.set_request(HttpRequest::empty());
+                super::orchestrate_endpoint(&mut ctx, &runtime_components, &mut cfg)
+                    .await
+                    .expect("success");
+                ctx.request().unwrap().uri().to_string()
+            }
+        };
+
+        assert_eq!("http://data.localhost:4566/", resolve(false).await);
+        assert_eq!("http://localhost:4566/", resolve(true).await);
+    }
 }
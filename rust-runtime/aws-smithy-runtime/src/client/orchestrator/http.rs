@@ -3,22 +3,28 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_runtime_api::client::buffered_memory::BufferedMemory;
 use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, SensitiveOutput};
 use aws_smithy_types::body::SdkBody;
 use aws_smithy_types::config_bag::ConfigBag;
 use bytes::{Buf, Bytes};
 use http_body_0_4::Body;
 use pin_utils::pin_mut;
+use std::sync::Arc;
 use tracing::trace;
 
 const LOG_SENSITIVE_BODIES: &str = "LOG_SENSITIVE_BODIES";
 
-async fn body_to_bytes(body: SdkBody) -> Result<Bytes, <SdkBody as Body>::Error> {
+async fn body_to_bytes(
+    body: SdkBody,
+    buffered_memory: &mut BufferedMemory,
+) -> Result<Bytes, <SdkBody as Body>::Error> {
     let mut output = Vec::new();
     pin_mut!(body);
     while let Some(buf) = body.data().await {
         let mut buf = buf?;
         while buf.has_remaining() {
+            buffered_memory.grow(buf.chunk().len())?;
             output.extend_from_slice(buf.chunk());
             buf.advance(buf.chunk().len())
         }
@@ -31,9 +37,12 @@ pub(crate) async fn read_body(response: &mut HttpResponse) -> Result<(), <SdkBod
     let mut body = SdkBody::taken();
     std::mem::swap(&mut body, response.body_mut());
 
-    let bytes = body_to_bytes(body).await?;
+    // The body counts as buffered until the response is dropped
+    let mut buffered_memory = BufferedMemory::new();
+    let bytes = body_to_bytes(body, &mut buffered_memory).await?;
     let mut body = SdkBody::from(bytes);
     std::mem::swap(&mut body, response.body_mut());
+    response.add_extension(Arc::new(buffered_memory));
 
     Ok(())
 }
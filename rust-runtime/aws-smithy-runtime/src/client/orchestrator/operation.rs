@@ -388,7 +388,7 @@ impl<I, O, E> OperationBuilder<I, O, E> {
 mod tests {
     use super::*;
     use crate::client::http::test_util::{capture_request, ReplayEvent, StaticReplayClient};
-    use crate::client::retries::classifiers::HttpStatusCodeClassifier;
+    use crate::client::retries::classifiers::{HttpStatusCodeClassifier, TransientErrorClassifier};
     use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
     use aws_smithy_runtime_api::client::result::ConnectorError;
     use aws_smithy_types::body::SdkBody;
@@ -487,4 +487,101 @@ mod tests {
 
         connector.assert_requests_match(&[]);
     }
+
+    #[tokio::test]
+    async fn buffered_streaming_response_retries_on_body_failure() {
+        use aws_smithy_runtime_api::client::orchestrator::BufferStreamingResponse;
+        use bytes::Bytes;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        /// A body that fails after yielding its first chunk
+        struct FailsMidStream(bool);
+        impl http_body_0_4::Body for FailsMidStream {
+            type Data = Bytes;
+            type Error = BoxError;
+
+            fn poll_data(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+                if self.0 {
+                    Poll::Ready(Some(Err("connection reset".into())))
+                } else {
+                    self.0 = true;
+                    Poll::Ready(Some(Ok(Bytes::from_static(b"I'm a "))))
+                }
+            }
+
+            fn poll_trailers(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+                Poll::Ready(Ok(None))
+            }
+        }
+
+        #[derive(Debug)]
+        struct StreamingDeserializer;
+        impl DeserializeResponse for StreamingDeserializer {
+            fn deserialize_streaming(
+                &self,
+                response: &mut HttpResponse,
+            ) -> Option<Result<Output, OrchestratorError<Error>>> {
+                Some(Ok(Output::erase(response.take_body())))
+            }
+
+            fn deserialize_nonstreaming(
+                &self,
+                _response: &HttpResponse,
+            ) -> Result<Output, OrchestratorError<Error>> {
+                unreachable!("the response is streaming")
+            }
+        }
+
+        let request = || {
+            http::Request::builder()
+                .uri("http://localhost:1234/")
+                .body(SdkBody::empty())
+                .unwrap()
+        };
+        let connector = StaticReplayClient::new(vec![
+            ReplayEvent::new(
+                request(),
+                http::Response::builder()
+                    .status(200)
+                    .body(SdkBody::from_body_0_4(FailsMidStream(false)))
+                    .unwrap(),
+            ),
+            ReplayEvent::new(
+                request(),
+                http::Response::builder()
+                    .status(200)
+                    .body(SdkBody::from(&b"I'm a teapot!"[..]))
+                    .unwrap(),
+            ),
+        ]);
+        let mut layer = Layer::new("streaming");
+        layer.store_put(SharedResponseDeserializer::new(StreamingDeserializer));
+        layer.store_put(BufferStreamingResponse::new());
+        let operation = Operation::builder()
+            .service_name("test")
+            .operation_name("test")
+            .http_client(connector.clone())
+            .endpoint_url("http://localhost:1234")
+            .no_auth()
+            .standard_retry(&RetryConfig::standard())
+            .retry_classifier(TransientErrorClassifier::<Infallible>::new())
+            .timeout_config(TimeoutConfig::disabled())
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .serializer(|_input: ()| Ok(HttpRequest::empty()))
+            .deserializer::<SdkBody, Infallible>(|_| unreachable!("overridden below"))
+            .runtime_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()))
+            .build();
+
+        let output = operation.invoke(()).await.expect("success");
+        assert_eq!(b"I'm a teapot!", output.bytes().unwrap());
+
+        connector.assert_requests_match(&[]);
+    }
 }
@@ -10,12 +10,14 @@ pub mod classifiers;
 pub mod strategy;
 
 mod client_rate_limiter;
+mod rate_limiter;
 mod token_bucket;
 
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use std::fmt;
 
 pub use client_rate_limiter::ClientRateLimiter;
+pub use rate_limiter::RateLimiter;
 pub use token_bucket::TokenBucket;
 
 #[doc(hidden)]
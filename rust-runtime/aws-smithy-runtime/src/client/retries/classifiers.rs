@@ -99,7 +99,7 @@ where
         if error.is_response_error() || error.is_timeout_error() {
             RetryAction::transient_error()
         } else if let Some(error) = error.as_connector_error() {
-            if error.is_timeout() || error.is_io() {
+            if error.is_timeout() || error.is_io() || error.is_dns() {
                 RetryAction::transient_error()
             } else {
                 error
@@ -146,6 +146,39 @@ impl HttpStatusCodeClassifier {
         }
     }
 
+    /// Treat HTTP responses with the given status codes as retryable, in addition to the
+    /// status codes this classifier already retries.
+    ///
+    /// This is useful for services that return non-standard status codes for transient
+    /// conditions, such as the 520 and 530 codes returned by some CDNs:
+    ///
+    /// ```rust
+    /// use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+    ///
+    /// let classifier = HttpStatusCodeClassifier::default().with_additional_status_codes([520, 530]);
+    /// assert_eq!(&[500, 502, 503, 504, 520, 530], classifier.retryable_status_codes());
+    /// ```
+    ///
+    /// The resulting classifier can be registered on a generated client's config with
+    /// `retry_classifier`, where it will run alongside the default classifiers.
+    pub fn with_additional_status_codes(
+        mut self,
+        status_codes: impl IntoIterator<Item = u16>,
+    ) -> Self {
+        let retryable_status_codes = self.retryable_status_codes.to_mut();
+        for status_code in status_codes {
+            if !retryable_status_codes.contains(&status_code) {
+                retryable_status_codes.push(status_code);
+            }
+        }
+        self
+    }
+
+    /// Returns the status codes that this classifier treats as retryable.
+    pub fn retryable_status_codes(&self) -> &[u16] {
+        &self.retryable_status_codes
+    }
+
     /// Return the priority of this retry classifier.
     pub fn priority() -> RetryClassifierPriority {
         RetryClassifierPriority::http_status_code_classifier()
@@ -220,6 +253,7 @@ mod test {
     };
     use aws_smithy_runtime_api::client::interceptors::context::{Error, Input, InterceptorContext};
     use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+    use aws_smithy_runtime_api::client::result::ConnectorError;
     use aws_smithy_runtime_api::client::retries::classifiers::{ClassifyRetry, RetryAction};
     use aws_smithy_types::body::SdkBody;
     use aws_smithy_types::retry::{ErrorKind, ProvideErrorKind};
@@ -264,6 +298,26 @@ mod test {
         assert_eq!(policy.classify_retry(&ctx), RetryAction::NoActionIndicated);
     }
 
+    #[test]
+    fn classify_by_additional_response_status() {
+        let policy = HttpStatusCodeClassifier::default().with_additional_status_codes([520, 530]);
+        for (status, expected) in [
+            (500, RetryAction::transient_error()),
+            (520, RetryAction::transient_error()),
+            (530, RetryAction::transient_error()),
+            (521, RetryAction::NoActionIndicated),
+        ] {
+            let res = http::Response::builder()
+                .status(status)
+                .body("error!")
+                .unwrap()
+                .map(SdkBody::from);
+            let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+            ctx.set_response(res.try_into().unwrap());
+            assert_eq!(policy.classify_retry(&ctx), expected, "status {status}");
+        }
+    }
+
     #[test]
     fn classify_by_error_kind() {
         #[derive(Debug)]
@@ -316,4 +370,17 @@ mod test {
         )));
         assert_eq!(policy.classify_retry(&ctx), RetryAction::transient_error(),);
     }
+
+    #[test]
+    fn classify_dns_and_connect_timeout_errors() {
+        let policy = TransientErrorClassifier::<UnmodeledError>::new();
+        for err in [
+            ConnectorError::dns("failed to lookup address information".into()),
+            ConnectorError::connect_timeout("HTTP connect timeout occurred after 1s".into()),
+        ] {
+            let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+            ctx.set_output_or_error(Err(OrchestratorError::connector(err)));
+            assert_eq!(policy.classify_retry(&ctx), RetryAction::transient_error());
+        }
+    }
 }
@@ -0,0 +1,185 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A general-purpose rate limiter built on the [`TokenBucket`] that limits retries.
+
+use crate::client::retries::TokenBucket;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Default)]
+struct Refill {
+    last_refill: Option<SystemTime>,
+    /// The fraction of a permit that has been replenished, but not yet put into the bucket.
+    partial_permit: f64,
+}
+
+/// A rate limiter that's driven by an [`AsyncSleep`] implementation and a [`TimeSource`], so that
+/// it works with any async runtime and can be tested with fake time.
+///
+/// The permits are held in a [`TokenBucket`], which is replenished continuously at a fixed rate,
+/// up to a maximum burst size. Clones of a rate limiter share the same bucket, so a single limiter
+/// can be shared by every task that should be limited together, such as all of the requests for a
+/// tenant.
+///
+/// Callers of [`acquire`](RateLimiter::acquire) that have to wait aren't guaranteed to be granted
+/// permits in the order they called it.
+///
+/// # Examples
+///
+/// Cap requests for each tenant at 10 per second, allowing bursts of up to 20:
+///
+/// ```no_run
+/// # #[cfg(feature = "rt-tokio")]
+/// # async fn example() {
+/// use aws_smithy_async::rt::sleep::TokioSleep;
+/// use aws_smithy_runtime::client::retries::RateLimiter;
+/// use std::time::Duration;
+///
+/// let limiter = RateLimiter::new(10, Duration::from_secs(1), TokioSleep::new()).with_burst(20);
+/// // Waits until a permit is available
+/// limiter.acquire(1).await;
+/// // Returns immediately
+/// if !limiter.try_acquire(1) {
+///     println!("rate limit exceeded");
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    bucket: TokenBucket,
+    /// Permits replenished per second.
+    rate: f64,
+    sleep_impl: SharedAsyncSleep,
+    time_source: SharedTimeSource,
+    refill: Arc<Mutex<Refill>>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter that allows `permits` permits to be acquired every `per`.
+    ///
+    /// The limiter starts full, and the burst size defaults to `permits`. The system clock is used
+    /// to measure time unless a time source is set with [`with_time_source`](Self::with_time_source).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` or `per` is zero.
+    pub fn new(permits: u32, per: Duration, sleep_impl: impl AsyncSleep + 'static) -> Self {
+        assert!(permits > 0, "the number of permits must be non-zero");
+        assert!(!per.is_zero(), "the rate limiting period must be non-zero");
+        Self {
+            bucket: TokenBucket::new(permits as usize),
+            rate: f64::from(permits) / per.as_secs_f64(),
+            sleep_impl: SharedAsyncSleep::new(sleep_impl),
+            time_source: SharedTimeSource::default(),
+            refill: Default::default(),
+        }
+    }
+
+    /// Sets the maximum number of permits that can be acquired at once after the limiter has been idle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "the burst size must be non-zero");
+        self.bucket = TokenBucket::new(burst as usize);
+        self
+    }
+
+    /// Sets the time source used to measure how many permits have been replenished.
+    pub fn with_time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = SharedTimeSource::new(time_source);
+        self
+    }
+
+    /// Returns the token bucket that holds this limiter's permits.
+    pub fn token_bucket(&self) -> &TokenBucket {
+        &self.bucket
+    }
+
+    /// Acquires `permits` permits if they're available now, returning `true` if they were acquired.
+    pub fn try_acquire(&self, permits: u32) -> bool {
+        self.replenish();
+        self.bucket.try_take(permits)
+    }
+
+    /// Acquires `permits` permits, waiting until they're available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is larger than the burst size, since they could never be acquired.
+    pub async fn acquire(&self, permits: u32) {
+        assert!(
+            permits as usize <= self.bucket.capacity(),
+            "can't acquire more permits than the burst size"
+        );
+        while !self.try_acquire(permits) {
+            let missing = (permits as usize).saturating_sub(self.bucket.available_permits());
+            let partial_permit = self.refill.lock().unwrap().partial_permit;
+            let wait = ((missing as f64 - partial_permit) / self.rate).max(0.0);
+            self.sleep_impl.sleep(Duration::from_secs_f64(wait)).await;
+        }
+    }
+
+    /// Puts the permits that were replenished since the last refill into the bucket.
+    fn replenish(&self) {
+        let now = self.time_source.now();
+        let mut refill = self.refill.lock().unwrap();
+        if let Some(last_refill) = refill.last_refill {
+            let elapsed = now.duration_since(last_refill).unwrap_or_default();
+            let replenished = refill.partial_permit + elapsed.as_secs_f64() * self.rate;
+            self.bucket.refill(replenished as usize);
+            refill.partial_permit = if self.bucket.available_permits() < self.bucket.capacity() {
+                replenished.fract()
+            } else {
+                0.0
+            };
+        }
+        refill.last_refill = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::test_util::instant_time_and_sleep;
+    use std::time::UNIX_EPOCH;
+
+    #[tokio::test]
+    async fn acquire_waits_for_permits() {
+        let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+        let limiter = RateLimiter::new(2, Duration::from_secs(1), sleep_impl.clone())
+            .with_time_source(time_source);
+
+        // The first two permits are available immediately, and each one after that takes half a second
+        for _ in 0..4 {
+            limiter.acquire(1).await;
+        }
+        assert_eq!(
+            vec![Duration::from_millis(500), Duration::from_millis(500)],
+            sleep_impl.logs()
+        );
+    }
+
+    #[test]
+    fn try_acquire_respects_burst() {
+        let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+        let limiter = RateLimiter::new(1, Duration::from_secs(1), sleep_impl)
+            .with_burst(3)
+            .with_time_source(time_source.clone());
+
+        assert!(limiter.try_acquire(3));
+        assert!(!limiter.try_acquire(1));
+
+        // Idle time doesn't accumulate more permits than the burst size
+        time_source.advance(Duration::from_secs(10));
+        assert!(limiter.try_acquire(3));
+        assert!(!limiter.try_acquire(1));
+        assert_eq!(3, limiter.token_bucket().capacity());
+    }
+}
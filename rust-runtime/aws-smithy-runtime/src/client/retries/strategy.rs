@@ -7,4 +7,4 @@ mod never;
 pub(crate) mod standard;
 
 pub use never::NeverRetryStrategy;
-pub use standard::StandardRetryStrategy;
+pub use standard::{DeadlineExceededError, StandardRetryStrategy};
@@ -10,14 +10,18 @@ use crate::client::retries::strategy::standard::ReleaseResult::{
 };
 use crate::client::retries::token_bucket::TokenBucket;
 use crate::client::retries::{ClientRateLimiterPartition, RetryPartition};
+use crate::client::telemetry::OperationTelemetry;
 use crate::static_partition_map::StaticPartitionMap;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
+use aws_smithy_runtime_api::client::latency::{AttemptLatency, LatencyBreakdown};
 use aws_smithy_runtime_api::client::retries::classifiers::{RetryAction, RetryReason};
 use aws_smithy_runtime_api::client::retries::{RequestAttempts, RetryStrategy, ShouldAttempt};
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 use aws_smithy_types::retry::{ErrorKind, RetryConfig, RetryMode};
+use aws_smithy_types::timeout::Deadline;
+use std::fmt;
 use std::sync::Mutex;
 use std::time::{Duration, SystemTime};
 use tokio::sync::OwnedSemaphorePermit;
@@ -100,6 +104,7 @@ impl StandardRetryStrategy {
             .expect("at least one request attempt is made before any retry is attempted")
             .attempts();
         let token_bucket = cfg.load::<TokenBucket>();
+        let telemetry = cfg.load::<OperationTelemetry>();
 
         match retry_reason {
             RetryAction::RetryIndicated(RetryReason::RetryableError { kind, retry_after }) => {
@@ -108,6 +113,11 @@ impl StandardRetryStrategy {
                     cfg,
                     *kind == ErrorKind::ThrottlingError,
                 );
+                if *kind == ErrorKind::ThrottlingError {
+                    if let Some(telemetry) = telemetry {
+                        telemetry.record_throttle();
+                    }
+                }
 
                 if let Some(delay) = *retry_after {
                     let delay = delay.min(retry_cfg.max_backoff());
@@ -125,6 +135,9 @@ impl StandardRetryStrategy {
                             Some(permit) => self.set_retry_permit(permit),
                             None => {
                                 debug!("attempt #{request_attempts} failed with {kind:?}; However, no retry permits are available, so no retry will be attempted.");
+                                if let Some(telemetry) = telemetry {
+                                    telemetry.record_retry_quota_exhausted();
+                                }
                                 return Err(ShouldAttempt::No);
                             }
                         }
@@ -161,6 +174,73 @@ impl StandardRetryStrategy {
     }
 }
 
+/// Error returned by [`StandardRetryStrategy`] when a retry wouldn't complete before the
+/// operation timeout.
+///
+/// The time a retry will take is estimated as the backoff delay plus the average time taken
+/// by previous attempts. The source of this error is the error of the last attempt.
+#[derive(Debug)]
+pub struct DeadlineExceededError {
+    attempts: u32,
+    remaining: Duration,
+    backoff: Duration,
+    estimated_attempt_duration: Duration,
+    source: Option<BoxError>,
+}
+
+impl DeadlineExceededError {
+    /// Returns the number of attempts made before giving up.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns the time that was remaining until the operation deadline.
+    pub fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    /// Returns the backoff delay that would have preceded the retry.
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Returns the estimated duration of the retry attempt.
+    pub fn estimated_attempt_duration(&self) -> Duration {
+        self.estimated_attempt_duration
+    }
+
+    /// Sets the error of the last attempt as the source of this error.
+    pub(crate) fn with_source(mut self, source: impl Into<BoxError>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns a copy of this error without its source.
+    pub(crate) fn without_source(&self) -> Self {
+        Self {
+            source: None,
+            ..*self
+        }
+    }
+}
+
+impl fmt::Display for DeadlineExceededError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the operation deadline would be exceeded by another attempt: attempt #{} failed with {:?} remaining, \
+            but retrying requires a {:?} backoff and an estimated {:?} attempt",
+            self.attempts, self.remaining, self.backoff, self.estimated_attempt_duration
+        )
+    }
+}
+
+impl std::error::Error for DeadlineExceededError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|err| err.as_ref() as _)
+    }
+}
+
 enum ReleaseResult {
     APermitWasReleased,
     NoPermitWasReleased,
@@ -243,10 +323,24 @@ impl RetryStrategy for StandardRetryStrategy {
                 // In some cases, backoff calculation will decide that we shouldn't retry at all.
                 Err(value) => return Ok(value),
             };
+
+        // Don't start an attempt that would be cut short by the operation timeout.
+        if let Err(err) = check_deadline(runtime_components, cfg, request_attempts, backoff) {
+            self.release_retry_permit();
+            debug!("not retrying because {err}");
+            return Err(err.into());
+        }
         debug!(
             "attempt #{request_attempts} failed with {:?}; retrying after {:?}",
             classifier_result, backoff,
         );
+        if let (
+            Some(telemetry),
+            RetryAction::RetryIndicated(RetryReason::RetryableError { kind, .. }),
+        ) = (cfg.load::<OperationTelemetry>(), &classifier_result)
+        {
+            telemetry.record_retry(*kind, backoff);
+        }
 
         Ok(ShouldAttempt::YesAfterDelay(backoff))
     }
@@ -285,6 +379,47 @@ fn check_rate_limiter_for_delay(
     None
 }
 
+fn check_deadline(
+    runtime_components: &RuntimeComponents,
+    cfg: &ConfigBag,
+    request_attempts: u32,
+    backoff: Duration,
+) -> Result<(), DeadlineExceededError> {
+    let (Some(deadline), Some(time_source)) =
+        (cfg.load::<Deadline>(), runtime_components.time_source())
+    else {
+        return Ok(());
+    };
+    let now = time_source.now();
+    let estimated_attempt_duration = estimated_attempt_duration(cfg);
+    if now + backoff + estimated_attempt_duration >= deadline.as_system_time() {
+        Err(DeadlineExceededError {
+            attempts: request_attempts,
+            remaining: deadline.remaining(now),
+            backoff,
+            estimated_attempt_duration,
+            source: None,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Estimates how long an attempt takes, as the average duration of the previous attempts.
+fn estimated_attempt_duration(cfg: &ConfigBag) -> Duration {
+    let durations: Vec<_> = cfg
+        .load::<LatencyBreakdown>()
+        .map(LatencyBreakdown::attempts)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(AttemptLatency::total)
+        .collect();
+    match durations.len() {
+        0 => Duration::ZERO,
+        count => durations.iter().sum::<Duration>() / count as u32,
+    }
+}
+
 fn calculate_exponential_backoff(base: f64, initial_backoff: f64, retry_attempts: u32) -> f64 {
     base * initial_backoff * 2_u32.pow(retry_attempts) as f64
 }
@@ -303,6 +438,7 @@ fn get_seconds_since_unix_epoch(runtime_components: &RuntimeComponents) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aws_smithy_async::time::StaticTimeSource;
     use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
     use aws_smithy_runtime_api::client::retries::classifiers::{
         ClassifyRetry, RetryAction, SharedRetryClassifier,
@@ -410,6 +546,80 @@ mod tests {
         assert_eq!(ShouldAttempt::No, actual);
     }
 
+    fn test_deadline(
+        elapsed: Duration,
+        timeout: Duration,
+        attempt_durations: &[Duration],
+    ) -> Result<ShouldAttempt, BoxError> {
+        let (ctx, _, mut cfg) = set_up_cfg_and_context(
+            ErrorKind::ServerError,
+            2,
+            RetryConfig::standard()
+                .with_use_static_exponential_base(true)
+                .with_max_attempts(3),
+        );
+        let started_at = SystemTime::UNIX_EPOCH;
+        let rc = RuntimeComponentsBuilder::for_tests()
+            .with_retry_classifier(SharedRetryClassifier::new(AlwaysRetry(
+                ErrorKind::ServerError,
+            )))
+            .with_time_source(Some(StaticTimeSource::new(started_at + elapsed)))
+            .build()
+            .unwrap();
+        cfg.interceptor_state()
+            .store_put(Deadline::new(started_at + timeout));
+        let latency = cfg.get_mut_or_default::<LatencyBreakdown>();
+        for duration in attempt_durations {
+            latency.push_attempt(AttemptLatency::builder().total(*duration).build());
+        }
+        StandardRetryStrategy::new().should_attempt_retry(&ctx, &rc, &cfg)
+    }
+
+    #[test]
+    fn retry_when_deadline_allows() {
+        // The retry starts after a 2s backoff, and the attempts took 1.5s on average
+        let actual = test_deadline(
+            Duration::from_secs(4),
+            Duration::from_secs(8),
+            &[Duration::from_secs(1), Duration::from_secs(2)],
+        )
+        .unwrap();
+        assert_eq!(ShouldAttempt::YesAfterDelay(Duration::from_secs(2)), actual);
+    }
+
+    #[test]
+    fn dont_retry_when_deadline_would_be_exceeded() {
+        // The deadline passes by the time that the 2s backoff is over
+        let err = test_deadline(Duration::from_secs(4), Duration::from_secs(6), &[]).unwrap_err();
+        let err = err
+            .downcast_ref::<DeadlineExceededError>()
+            .expect("deadline error");
+        assert_eq!(2, err.attempts());
+        assert_eq!(Duration::from_secs(2), err.remaining());
+        assert_eq!(Duration::from_secs(2), err.backoff());
+        assert_eq!(Duration::ZERO, err.estimated_attempt_duration());
+    }
+
+    #[test]
+    fn dont_retry_when_the_attempt_would_exceed_the_deadline() {
+        // The 2s backoff ends before the deadline, but the attempt wouldn't complete before it
+        let err = test_deadline(
+            Duration::from_secs(4),
+            Duration::from_secs(7),
+            &[Duration::from_secs(1), Duration::from_secs(2)],
+        )
+        .unwrap_err();
+        let err = err
+            .downcast_ref::<DeadlineExceededError>()
+            .expect("deadline error");
+        assert_eq!(Duration::from_secs(3), err.remaining());
+        assert_eq!(Duration::from_secs(2), err.backoff());
+        assert_eq!(
+            Duration::from_millis(1500),
+            err.estimated_attempt_duration()
+        );
+    }
+
     #[derive(Debug)]
     struct ServerError;
     impl fmt::Display for ServerError {
@@ -579,6 +789,95 @@ mod tests {
         assert_eq!(token_bucket.available_permits(), 0);
     }
 
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn retries_and_throttles_are_recorded_as_metrics() {
+        use crate::client::telemetry::OperationTelemetry;
+        use aws_smithy_async::time::SharedTimeSource;
+        use aws_smithy_observability::attributes::{AttributeValue, Attributes};
+        use aws_smithy_observability::meter::{
+            Instrument, InstrumentDescriptor, Meter, MeterProvider, SharedMeterProvider,
+        };
+        use std::sync::Arc;
+
+        type Measurements = Arc<Mutex<Vec<(&'static str, f64, Attributes)>>>;
+
+        #[derive(Debug, Default)]
+        struct RecordingMeterProvider(Measurements);
+        impl MeterProvider for RecordingMeterProvider {
+            fn meter(&self, _scope: &'static str) -> Arc<dyn Meter> {
+                Arc::new(RecordingMeterProvider(self.0.clone()))
+            }
+        }
+        impl Meter for RecordingMeterProvider {
+            fn instrument(&self, descriptor: &InstrumentDescriptor) -> Arc<dyn Instrument> {
+                Arc::new(RecordingInstrument(descriptor.name(), self.0.clone()))
+            }
+        }
+
+        #[derive(Debug)]
+        struct RecordingInstrument(&'static str, Measurements);
+        impl Instrument for RecordingInstrument {
+            fn record(&self, value: f64, attributes: &Attributes) {
+                self.1
+                    .lock()
+                    .unwrap()
+                    .push((self.0, value, attributes.clone()));
+            }
+        }
+
+        let (mut cfg, rc, ctx) = setup_test(
+            vec![RetryAction::throttling_error()],
+            RetryConfig::standard()
+                .with_use_static_exponential_base(true)
+                .with_max_attempts(5),
+        );
+        let measurements = Measurements::default();
+        cfg.interceptor_state()
+            .store_put(SharedMeterProvider::new(RecordingMeterProvider(
+                measurements.clone(),
+            )));
+        let telemetry =
+            OperationTelemetry::start(&cfg, SharedTimeSource::default(), "svc", "Op").unwrap();
+        cfg.interceptor_state().store_put(telemetry);
+        cfg.interceptor_state().store_put(TokenBucket::new(5));
+        let strategy = StandardRetryStrategy::new();
+
+        cfg.interceptor_state().store_put(RequestAttempts::new(1));
+        let should_retry = strategy.should_attempt_retry(&ctx, &rc, &cfg).unwrap();
+        assert_eq!(Duration::from_secs(1), should_retry.expect_delay());
+        let recorded: Vec<_> = measurements.lock().unwrap().drain(..).collect();
+        assert_eq!(
+            vec![
+                "smithy.client.call.throttles",
+                "smithy.client.call.retries",
+                "smithy.client.call.backoff.duration"
+            ],
+            recorded.iter().map(|(name, ..)| *name).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&AttributeValue::from("throttling")),
+            recorded[1].2.get("error.type")
+        );
+        assert_eq!(
+            Some(&AttributeValue::from("Op")),
+            recorded[1].2.get("rpc.method")
+        );
+        assert_eq!(1.0, recorded[2].1);
+
+        cfg.interceptor_state().store_put(RequestAttempts::new(2));
+        let no_retry = strategy.should_attempt_retry(&ctx, &rc, &cfg).unwrap();
+        assert_eq!(ShouldAttempt::No, no_retry);
+        let recorded: Vec<_> = measurements.lock().unwrap().drain(..).collect();
+        assert_eq!(
+            vec![
+                "smithy.client.call.throttles",
+                "smithy.client.call.retry_quota_exhausted"
+            ],
+            recorded.iter().map(|(name, ..)| *name).collect::<Vec<_>>()
+        );
+    }
+
     #[cfg(feature = "test-util")]
     #[test]
     fn quota_replenishes_on_success() {
@@ -5,6 +5,7 @@
 
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use aws_smithy_types::retry::ErrorKind;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::trace;
@@ -15,12 +16,16 @@ const RETRY_TIMEOUT_COST: u32 = RETRY_COST * 2;
 const PERMIT_REGENERATION_AMOUNT: usize = 1;
 
 /// Token bucket used for standard and adaptive retry.
+///
+/// Clones of a `TokenBucket` share the same permits. To keep the retry quota across clients that
+/// are frequently recreated, create one token bucket up front and give each client a clone of it.
 #[derive(Clone, Debug)]
 pub struct TokenBucket {
     semaphore: Arc<Semaphore>,
     max_permits: usize,
     timeout_retry_cost: u32,
     retry_cost: u32,
+    regenerated_permits: Arc<AtomicU64>,
 }
 
 impl Storable for TokenBucket {
@@ -34,6 +39,7 @@ impl Default for TokenBucket {
             max_permits: DEFAULT_CAPACITY,
             timeout_retry_cost: RETRY_TIMEOUT_COST,
             retry_cost: RETRY_COST,
+            regenerated_permits: Default::default(),
         }
     }
 }
@@ -46,6 +52,7 @@ impl TokenBucket {
             max_permits: initial_quota,
             retry_cost: RETRY_COST,
             timeout_retry_cost: RETRY_TIMEOUT_COST,
+            regenerated_permits: Default::default(),
         }
     }
 
@@ -62,15 +69,86 @@ impl TokenBucket {
             .ok()
     }
 
+    /// Takes `permits` permits out of the bucket if they're available, returning `true` if they were.
+    pub(crate) fn try_take(&self, permits: u32) -> bool {
+        match self.semaphore.try_acquire_many(permits) {
+            Ok(permit) => {
+                permit.forget();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Puts up to `permits` permits back into the bucket, without exceeding its capacity.
+    pub(crate) fn refill(&self, permits: usize) {
+        let missing = self
+            .max_permits
+            .saturating_sub(self.semaphore.available_permits());
+        self.semaphore.add_permits(permits.min(missing));
+    }
+
     pub(crate) fn regenerate_a_token(&self) {
         if self.semaphore.available_permits() < (self.max_permits) {
             trace!("adding {PERMIT_REGENERATION_AMOUNT} back into the bucket");
-            self.semaphore.add_permits(PERMIT_REGENERATION_AMOUNT)
+            self.semaphore.add_permits(PERMIT_REGENERATION_AMOUNT);
+            self.regenerated_permits
+                .fetch_add(PERMIT_REGENERATION_AMOUNT as u64, Ordering::Relaxed);
         }
     }
 
-    #[cfg(all(test, feature = "test-util"))]
-    pub(crate) fn available_permits(&self) -> usize {
+    /// Returns the number of permits currently available for retries.
+    ///
+    /// When this drops below the cost of a retry, the retry strategy will stop retrying
+    /// failed requests until successful requests refill the bucket.
+    pub fn available_permits(&self) -> usize {
         self.semaphore.available_permits()
     }
+
+    /// Returns the maximum number of permits this bucket can hold.
+    pub fn capacity(&self) -> usize {
+        self.max_permits
+    }
+
+    /// Returns the total number of permits that have been regenerated into this bucket
+    /// by successful requests since it was created.
+    ///
+    /// This counter only ever increases. Sampling it periodically and dividing the difference
+    /// by the sampling interval yields the recent refill rate.
+    pub fn regenerated_permits(&self) -> u64 {
+        self.regenerated_permits.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn introspection() {
+        let bucket = TokenBucket::new(10);
+        assert_eq!(10, bucket.capacity());
+        assert_eq!(10, bucket.available_permits());
+        assert_eq!(0, bucket.regenerated_permits());
+
+        let permit = bucket.acquire(&ErrorKind::ServerError).unwrap();
+        assert_eq!(5, bucket.available_permits());
+        permit.forget();
+
+        // Clones share the same underlying state
+        let clone = bucket.clone();
+        clone.regenerate_a_token();
+        clone.regenerate_a_token();
+        assert_eq!(7, bucket.available_permits());
+        assert_eq!(2, bucket.regenerated_permits());
+        assert_eq!(10, bucket.capacity());
+    }
+
+    #[test]
+    fn regeneration_stops_at_capacity() {
+        let bucket = TokenBucket::new(3);
+        bucket.regenerate_a_token();
+        assert_eq!(3, bucket.available_permits());
+        assert_eq!(0, bucket.regenerated_permits());
+    }
 }
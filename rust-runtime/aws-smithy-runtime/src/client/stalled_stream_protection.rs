@@ -0,0 +1,237 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::client::http::body::minimum_throughput::MinimumThroughputBody;
+use aws_smithy_async::rt::sleep::SharedAsyncSleep;
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeDeserializationInterceptorContextMut, BeforeTransmitInterceptorContextMut,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::client::stalled_stream_protection::StalledStreamProtectionConfig;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::mem;
+use std::time::Duration;
+
+/// Adds stalled stream protection to request and response bodies.
+///
+/// This interceptor does nothing unless a [`StalledStreamProtectionConfig`] that enables
+/// protection is in the config bag. When a stream stalls, reading it fails with an error that's
+/// classified as transient, so the attempt can be retried.
+#[non_exhaustive]
+#[derive(Debug, Default)]
+pub struct StalledStreamProtectionInterceptor {}
+
+impl StalledStreamProtectionInterceptor {
+    /// Creates a new `StalledStreamProtectionInterceptor`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Intercept for StalledStreamProtectionInterceptor {
+    fn name(&self) -> &'static str {
+        "StalledStreamProtectionInterceptor"
+    }
+
+    fn modify_before_transmit(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(config) = cfg.load::<StalledStreamProtectionConfig>() else {
+            return Ok(());
+        };
+        // In-memory request bodies are sent as a single chunk, so they can't stall on our end
+        let body = context.request_mut().body_mut();
+        if config.upload_enabled() && body.bytes().is_none() {
+            tracing::trace!("adding stalled stream protection to the request body");
+            let wrap = wrap_fn(config, runtime_components)?;
+            let inner = mem::replace(body, SdkBody::taken());
+            *body = inner.map_preserve_contents(wrap);
+        }
+        Ok(())
+    }
+
+    fn modify_before_deserialization(
+        &self,
+        context: &mut BeforeDeserializationInterceptorContextMut<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(config) = cfg.load::<StalledStreamProtectionConfig>() else {
+            return Ok(());
+        };
+        if config.download_enabled() {
+            tracing::trace!("adding stalled stream protection to the response body");
+            let wrap = wrap_fn(config, runtime_components)?;
+            let body = context.response_mut().body_mut();
+            let inner = mem::replace(body, SdkBody::taken());
+            *body = wrap(inner);
+        }
+        Ok(())
+    }
+}
+
+fn wrap_fn(
+    config: &StalledStreamProtectionConfig,
+    runtime_components: &RuntimeComponents,
+) -> Result<impl Fn(SdkBody) -> SdkBody + Send + Sync + 'static, BoxError> {
+    let (time_source, sleep_impl) = time_source_and_sleep_impl(runtime_components)?;
+    let minimum_throughput = (config.minimum_bytes(), config.window());
+    Ok(move |body| wrap_body(body, &time_source, &sleep_impl, minimum_throughput))
+}
+
+fn wrap_body(
+    body: SdkBody,
+    time_source: &SharedTimeSource,
+    sleep_impl: &SharedAsyncSleep,
+    minimum_throughput: (u64, Duration),
+) -> SdkBody {
+    SdkBody::from_body_0_4(MinimumThroughputBody::new(
+        time_source.clone(),
+        sleep_impl.clone(),
+        body,
+        minimum_throughput,
+    ))
+}
+
+fn time_source_and_sleep_impl(
+    runtime_components: &RuntimeComponents,
+) -> Result<(SharedTimeSource, SharedAsyncSleep), BoxError> {
+    let time_source = runtime_components
+        .time_source()
+        .ok_or("a time source is required for stalled stream protection")?;
+    let sleep_impl = runtime_components
+        .sleep_impl()
+        .ok_or("an async sleep implementation is required for stalled stream protection")?;
+    Ok((time_source, sleep_impl))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::test_util::instant_time_and_sleep;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use aws_smithy_types::config_bag::Layer;
+    use bytes::Bytes;
+    use http_body_0_4::Body;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::UNIX_EPOCH;
+
+    struct NeverBody;
+
+    impl Body for NeverBody {
+        type Data = Bytes;
+        type Error = BoxError;
+
+        fn poll_data(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Pending
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn set_up(config: StalledStreamProtectionConfig) -> (RuntimeComponents, ConfigBag) {
+        let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+        let rc = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time_source))
+            .with_sleep_impl(Some(sleep_impl))
+            .build()
+            .unwrap();
+        let mut layer = Layer::new("test");
+        layer.store_put(config);
+        (rc, ConfigBag::of_layers(vec![layer]))
+    }
+
+    #[tokio::test]
+    async fn stalled_download_fails() {
+        let (rc, mut cfg) = set_up(StalledStreamProtectionConfig::enabled().build());
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let _ = ctx.take_input();
+        ctx.set_request(HttpRequest::empty());
+        ctx.enter_before_transmit_phase();
+        ctx.enter_transmit_phase();
+        let _ = ctx.take_request();
+        ctx.set_response(
+            http::Response::builder()
+                .status(200)
+                .body(SdkBody::from_body_0_4(NeverBody))
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        ctx.enter_before_deserialization_phase();
+
+        StalledStreamProtectionInterceptor::new()
+            .modify_before_deserialization(&mut (&mut ctx).into(), &rc, &mut cfg)
+            .unwrap();
+
+        let body = mem::replace(ctx.response_mut().unwrap().body_mut(), SdkBody::taken());
+        let err = ByteStream::new(body).collect().await.unwrap_err();
+        assert!(
+            format!(
+                "{}",
+                aws_smithy_types::error::display::DisplayErrorContext(&err)
+            )
+            .contains("minimum throughput"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn only_streaming_uploads_are_protected() {
+        let (rc, mut cfg) = set_up(
+            StalledStreamProtectionConfig::enabled()
+                .download_enabled(false)
+                .build(),
+        );
+        let interceptor = StalledStreamProtectionInterceptor::new();
+        for (body, expect_wrapped) in [
+            (SdkBody::from("in-memory"), false),
+            (SdkBody::from_body_0_4(NeverBody), true),
+        ] {
+            let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+            ctx.enter_serialization_phase();
+            let _ = ctx.take_input();
+            ctx.set_request(HttpRequest::new(body));
+            ctx.enter_before_transmit_phase();
+            interceptor
+                .modify_before_transmit(&mut (&mut ctx).into(), &rc, &mut cfg)
+                .unwrap();
+            let body = ctx.request().unwrap().body();
+            assert_eq!(!expect_wrapped, body.bytes().is_some());
+        }
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let config = StalledStreamProtectionConfig::disabled();
+        assert!(!config.is_enabled());
+        let config = StalledStreamProtectionConfig::enabled()
+            .upload_enabled(false)
+            .build();
+        assert!(config.is_enabled());
+        assert!(!config.upload_enabled());
+        assert!(config.download_enabled());
+    }
+}
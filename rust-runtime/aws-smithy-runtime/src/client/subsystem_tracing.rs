@@ -0,0 +1,284 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Separate tracing levels for the subsystems that an operation runs through.
+//!
+//! Retries, signing, endpoint resolution, and the connector each log under their own tracing
+//! targets. When [`SubsystemTracing`] is set in a client's config, the spans and events of each
+//! [`Subsystem`] are filtered by their own level while an operation runs, so that retries can be
+//! traced in detail without also logging everything the connector does.
+//!
+//! This filter is applied on top of the subscriber that's already installed, so it can only make
+//! logging less verbose. The subscriber's own filter needs to allow the most verbose level that's
+//! configured here.
+
+use crate::client::filtered_dispatch::FilterSpans;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::borrow::Cow;
+use std::sync::Arc;
+use tracing::level_filters::LevelFilter;
+use tracing::Metadata;
+
+/// A subsystem of the client whose tracing level can be set separately
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Subsystem {
+    /// Retry strategies, retry classifiers, token buckets, and client rate limiting
+    Retries,
+    /// Auth scheme resolution, identity resolution, and request signing
+    Signing,
+    /// Endpoint resolution, including the evaluation of endpoint rules
+    EndpointResolution,
+    /// The HTTP client and connector, along with the libraries that they're built on
+    Connector,
+}
+
+impl Subsystem {
+    /// Returns the tracing targets that the subsystem logs under by default.
+    ///
+    /// A target matches when it's equal to one of these, or is a module beneath one of them.
+    pub fn default_targets(self) -> &'static [&'static str] {
+        match self {
+            Subsystem::Retries => &["aws_smithy_runtime::client::retries"],
+            Subsystem::Signing => &[
+                "aws_smithy_runtime::client::orchestrator::auth",
+                "aws_runtime::auth",
+                "aws_sigv4",
+            ],
+            Subsystem::EndpointResolution => &[
+                "aws_smithy_runtime::client::orchestrator::endpoints",
+                "aws_smithy_runtime::client::endpoint",
+                "endpoint_rules",
+            ],
+            Subsystem::Connector => &[
+                "aws_smithy_runtime::client::http",
+                "aws_smithy_runtime::client::orchestrator::http",
+                "aws_smithy_http_client",
+                "hyper",
+                "hyper_util",
+                "h2",
+                "rustls",
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SubsystemFilter {
+    subsystem: Subsystem,
+    targets: Vec<Cow<'static, str>>,
+    level: LevelFilter,
+}
+
+/// Filters the tracing spans and events of each [`Subsystem`] by its own level.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_runtime::client::subsystem_tracing::{Subsystem, SubsystemTracing};
+/// use tracing::level_filters::LevelFilter;
+///
+/// // Trace retries, but only log warnings from the connector and the rest of the client
+/// let subsystem_tracing = SubsystemTracing::builder()
+///     .level(Subsystem::Retries, LevelFilter::TRACE)
+///     .level(Subsystem::Connector, LevelFilter::WARN)
+///     .default_level(LevelFilter::WARN)
+///     .build();
+/// assert_eq!(Some(LevelFilter::TRACE), subsystem_tracing.level(Subsystem::Retries));
+/// assert_eq!(None, subsystem_tracing.level(Subsystem::Signing));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SubsystemTracing {
+    filters: Arc<[SubsystemFilter]>,
+    default_level: Option<LevelFilter>,
+}
+
+impl SubsystemTracing {
+    /// Returns a builder for `SubsystemTracing`.
+    pub fn builder() -> SubsystemTracingBuilder {
+        SubsystemTracingBuilder::default()
+    }
+
+    /// Returns the level that the subsystem is filtered by, or `None` if it wasn't set.
+    pub fn level(&self, subsystem: Subsystem) -> Option<LevelFilter> {
+        self.filter(subsystem).map(|filter| filter.level)
+    }
+
+    /// Returns the tracing targets of the subsystem, if its level was set.
+    pub fn targets(&self, subsystem: Subsystem) -> Option<impl Iterator<Item = &str>> {
+        self.filter(subsystem)
+            .map(|filter| filter.targets.iter().map(|target| target.as_ref()))
+    }
+
+    /// Returns the level that spans and events outside of the configured subsystems are filtered
+    /// by, or `None` if they aren't filtered.
+    pub fn default_level(&self) -> Option<LevelFilter> {
+        self.default_level
+    }
+
+    fn filter(&self, subsystem: Subsystem) -> Option<&SubsystemFilter> {
+        self.filters
+            .iter()
+            .find(|filter| filter.subsystem == subsystem)
+    }
+
+    /// Returns the level that spans and events with the given target are filtered by, if any.
+    fn level_for_target(&self, target: &str) -> Option<LevelFilter> {
+        self.filters
+            .iter()
+            .find(|filter| {
+                filter
+                    .targets
+                    .iter()
+                    .any(|prefix| target_matches(target, prefix))
+            })
+            .map(|filter| filter.level)
+            .or(self.default_level)
+    }
+}
+
+impl FilterSpans for SubsystemTracing {
+    fn allows(&self, metadata: &Metadata<'_>) -> bool {
+        self.level_for_target(metadata.target())
+            .map_or(true, |level| metadata.level() <= &level)
+    }
+}
+
+impl Storable for SubsystemTracing {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Builder for [`SubsystemTracing`]
+#[derive(Clone, Debug, Default)]
+pub struct SubsystemTracingBuilder {
+    filters: Vec<SubsystemFilter>,
+    default_level: Option<LevelFilter>,
+}
+
+impl SubsystemTracingBuilder {
+    /// Sets the most verbose level that's logged for the subsystem.
+    ///
+    /// Subsystems whose level isn't set are filtered by the [default level](Self::default_level).
+    pub fn level(mut self, subsystem: Subsystem, level: LevelFilter) -> Self {
+        match self
+            .filters
+            .iter_mut()
+            .find(|filter| filter.subsystem == subsystem)
+        {
+            Some(filter) => filter.level = level,
+            None => self.filters.push(SubsystemFilter {
+                subsystem,
+                targets: subsystem
+                    .default_targets()
+                    .iter()
+                    .map(|&target| target.into())
+                    .collect(),
+                level,
+            }),
+        }
+        self
+    }
+
+    /// Replaces the tracing targets of the subsystem, and sets its level.
+    ///
+    /// This is useful when a custom HTTP client or signer logs under its own targets.
+    pub fn level_for_targets(
+        mut self,
+        subsystem: Subsystem,
+        targets: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+        level: LevelFilter,
+    ) -> Self {
+        self.filters.retain(|filter| filter.subsystem != subsystem);
+        self.filters.push(SubsystemFilter {
+            subsystem,
+            targets: targets.into_iter().map(Into::into).collect(),
+            level,
+        });
+        self
+    }
+
+    /// Sets the most verbose level that's logged outside of the subsystems whose level was set.
+    ///
+    /// They aren't filtered by default.
+    pub fn default_level(mut self, level: LevelFilter) -> Self {
+        self.default_level = Some(level);
+        self
+    }
+
+    /// Builds the `SubsystemTracing`.
+    pub fn build(self) -> SubsystemTracing {
+        SubsystemTracing {
+            filters: self.filters.into(),
+            default_level: self.default_level,
+        }
+    }
+}
+
+/// Returns true if `target` is `prefix`, or a module beneath it.
+fn target_matches(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subsystems_are_filtered_by_their_own_level() {
+        let subsystem_tracing = SubsystemTracing::builder()
+            .level(Subsystem::Retries, LevelFilter::TRACE)
+            .level(Subsystem::Connector, LevelFilter::INFO)
+            .level(Subsystem::Connector, LevelFilter::WARN)
+            .level_for_targets(Subsystem::Signing, ["my_signer"], LevelFilter::OFF)
+            .default_level(LevelFilter::INFO)
+            .build();
+        assert_eq!(
+            Some(LevelFilter::WARN),
+            subsystem_tracing.level(Subsystem::Connector)
+        );
+        assert_eq!(
+            vec!["my_signer"],
+            subsystem_tracing
+                .targets(Subsystem::Signing)
+                .unwrap()
+                .collect::<Vec<_>>()
+        );
+        assert!(subsystem_tracing
+            .targets(Subsystem::EndpointResolution)
+            .is_none());
+
+        assert_eq!(
+            Some(LevelFilter::TRACE),
+            subsystem_tracing
+                .level_for_target("aws_smithy_runtime::client::retries::strategy::standard")
+        );
+        assert_eq!(
+            Some(LevelFilter::WARN),
+            subsystem_tracing.level_for_target("hyper")
+        );
+        assert_eq!(
+            Some(LevelFilter::WARN),
+            subsystem_tracing.level_for_target("hyper::client::pool")
+        );
+        assert_eq!(
+            Some(LevelFilter::OFF),
+            subsystem_tracing.level_for_target("my_signer")
+        );
+        // Targets that aren't in a configured subsystem get the default level
+        assert_eq!(
+            Some(LevelFilter::INFO),
+            subsystem_tracing.level_for_target("aws_sigv4")
+        );
+        // Only whole module names match
+        assert_eq!(
+            Some(LevelFilter::INFO),
+            subsystem_tracing.level_for_target("hyperlocal")
+        );
+
+        let unfiltered = SubsystemTracing::builder().build();
+        assert_eq!(None, unfiltered.level_for_target("hyper"));
+    }
+}
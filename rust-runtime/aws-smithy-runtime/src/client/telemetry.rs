@@ -0,0 +1,214 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Metrics and spans that the orchestrator records with the [`SharedMeterProvider`] and
+//! [`SharedTracerProvider`] from the config bag.
+
+use crate::client::metrics::{OperationMetrics, ERROR_TYPE_ATTRIBUTE};
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_observability::attributes::Attributes;
+use aws_smithy_observability::meter::SharedMeterProvider;
+use aws_smithy_observability::tracer::{SharedTracerProvider, Span, SpanContext, TracerProvider};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{Error, InterceptorContext};
+use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::retry::ErrorKind;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const SCOPE: &str = "aws-smithy-runtime";
+
+const SERVICE_ATTRIBUTE: &str = "rpc.service";
+const METHOD_ATTRIBUTE: &str = "rpc.method";
+const ATTEMPT_ATTRIBUTE: &str = "smithy.client.attempt";
+
+/// The metrics and span that the orchestrator records an operation's telemetry with.
+#[derive(Clone, Debug)]
+pub(crate) struct OperationTelemetry {
+    attributes: Attributes,
+    time_source: SharedTimeSource,
+    start: SystemTime,
+    metrics: Option<OperationMetrics>,
+    span: Option<Arc<dyn Span>>,
+}
+
+impl Storable for OperationTelemetry {
+    type Storer = StoreReplace<Self>;
+}
+
+impl OperationTelemetry {
+    /// Starts recording an operation's telemetry, or returns `None` if neither a meter provider
+    /// nor a tracer provider is configured.
+    pub(crate) fn start(
+        cfg: &ConfigBag,
+        time_source: SharedTimeSource,
+        service_name: &str,
+        operation_name: &str,
+    ) -> Option<Self> {
+        let meter_provider = cfg.load::<SharedMeterProvider>();
+        let tracer_provider = cfg.load::<SharedTracerProvider>();
+        if meter_provider.is_none() && tracer_provider.is_none() {
+            return None;
+        }
+        let attributes = Attributes::new()
+            .with(SERVICE_ATTRIBUTE, service_name.to_string())
+            .with(METHOD_ATTRIBUTE, operation_name.to_string());
+        let metrics = meter_provider.map(|meter_provider| {
+            OperationMetrics::new(meter_provider, time_source.clone(), attributes.clone())
+        });
+        let span = tracer_provider.map(|tracer_provider| {
+            tracer_provider
+                .tracer(SCOPE)
+                .start_span("smithy.client.call", &attributes)
+        });
+        Some(Self {
+            start: time_source.now(),
+            attributes,
+            time_source,
+            metrics,
+            span,
+        })
+    }
+
+    /// Starts recording the telemetry of the given attempt.
+    pub(crate) fn start_attempt(&self, attempt: u32) -> AttemptTelemetry {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_attempt_start();
+        }
+        let span = self.span.as_ref().map(|span| {
+            let attributes = self
+                .attributes
+                .clone()
+                .with(ATTEMPT_ATTRIBUTE, i64::from(attempt));
+            span.start_child("smithy.client.attempt", &attributes)
+        });
+        AttemptTelemetry {
+            start: self.time_source.now(),
+            span,
+        }
+    }
+
+    /// Records a retry of an error of the given kind, after the given backoff.
+    pub(crate) fn record_retry(&self, kind: ErrorKind, backoff: Duration) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_retry(kind, backoff);
+        }
+    }
+
+    /// Records an attempt that failed with a throttling error.
+    pub(crate) fn record_throttle(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_throttle();
+        }
+    }
+
+    /// Records a retry that wasn't attempted because there were no retry permits left.
+    pub(crate) fn record_retry_quota_exhausted(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_retry_quota_exhausted();
+        }
+    }
+
+    /// Runs a hook of the named interceptor, and records how long it took and whether it failed.
+    pub(crate) fn record_interceptor_hook(
+        &self,
+        interceptor: &'static str,
+        hook: &'static str,
+        run: impl FnOnce() -> Result<(), BoxError>,
+    ) -> Result<(), BoxError> {
+        match &self.metrics {
+            Some(metrics) => metrics.record_interceptor_hook(interceptor, hook, run),
+            None => run(),
+        }
+    }
+
+    /// Records the duration and outcome of the operation, and ends its span.
+    pub(crate) fn finish(
+        &self,
+        result: &Result<InterceptorContext, SdkError<Error, HttpResponse>>,
+    ) {
+        let error_kind = error_kind(result);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_call(self.start, error_kind);
+        }
+        if let Some(span) = &self.span {
+            if let Some(kind) = error_kind {
+                span.set_attribute(ERROR_TYPE_ATTRIBUTE, kind.into());
+                span.set_error(kind);
+            }
+            span.end();
+        }
+    }
+}
+
+/// The start time and span of a single attempt.
+#[derive(Debug)]
+pub(crate) struct AttemptTelemetry {
+    start: SystemTime,
+    span: Option<Arc<dyn Span>>,
+}
+
+impl AttemptTelemetry {
+    /// Returns the identity of the attempt's span, if it can be propagated.
+    pub(crate) fn span_context(&self) -> Option<SpanContext> {
+        self.span.as_ref().and_then(|span| span.context())
+    }
+
+    /// Records the duration and outcome of the attempt, and ends its span.
+    pub(crate) fn finish(
+        self,
+        operation: &OperationTelemetry,
+        error: Option<&OrchestratorError<Error>>,
+    ) {
+        if let Some(metrics) = &operation.metrics {
+            metrics.record_attempt(self.start);
+        }
+        if let Some(span) = self.span {
+            if let Some(err) = error {
+                let kind = orchestrator_error_kind(err);
+                span.set_attribute(ERROR_TYPE_ATTRIBUTE, kind.into());
+                span.set_error(kind);
+            }
+            span.end();
+        }
+    }
+}
+
+/// Returns the kind of error that the call failed with, or `None` if it succeeded.
+fn error_kind(
+    result: &Result<InterceptorContext, SdkError<Error, HttpResponse>>,
+) -> Option<&'static str> {
+    match result {
+        Ok(ctx) => match ctx.output_or_error() {
+            Some(Err(err)) => Some(orchestrator_error_kind(err)),
+            _ => None,
+        },
+        Err(SdkError::ConstructionFailure(_)) => Some("construction"),
+        Err(SdkError::TimeoutError(_)) => Some("timeout"),
+        Err(SdkError::DispatchFailure(_)) => Some("dispatch"),
+        Err(SdkError::ResponseError(_)) => Some("response"),
+        Err(SdkError::ServiceError(_)) => Some("service"),
+        Err(SdkError::Cancelled(_)) => Some("cancelled"),
+        Err(_) => Some("other"),
+    }
+}
+
+fn orchestrator_error_kind(err: &OrchestratorError<Error>) -> &'static str {
+    if err.is_operation_error() {
+        "service"
+    } else if err.is_timeout_error() {
+        "timeout"
+    } else if err.is_connector_error() {
+        "dispatch"
+    } else if err.is_response_error() {
+        "response"
+    } else if err.is_interceptor_error() {
+        "interceptor"
+    } else {
+        "other"
+    }
+}
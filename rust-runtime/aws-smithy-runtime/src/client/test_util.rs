@@ -8,3 +8,6 @@ pub mod deserializer;
 
 /// Test request serializer implementations.
 pub mod serializer;
+
+/// Runtime plugin for deterministically failing request attempts.
+pub mod fault_injection;
@@ -0,0 +1,238 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
+    SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::{Order, RuntimePlugin};
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::retry::ErrorKind;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// The class of error to inject into an attempt.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InjectedFault {
+    /// Fail the attempt with a connector error classified as a throttling error.
+    Throttling,
+    /// Fail the attempt with an IO error, which is classified as a transient error.
+    Transient,
+    /// Fail the attempt with a timeout error.
+    Timeout,
+}
+
+impl InjectedFault {
+    fn into_connector_error(self, attempt: u32) -> ConnectorError {
+        let source = InjectedFaultError {
+            fault: self,
+            attempt,
+        };
+        match self {
+            InjectedFault::Throttling => {
+                ConnectorError::other(source.into(), Some(ErrorKind::ThrottlingError))
+            }
+            InjectedFault::Transient => ConnectorError::io(source.into()),
+            InjectedFault::Timeout => ConnectorError::timeout(source.into()),
+        }
+    }
+}
+
+/// The error returned for attempts failed by [`FaultInjectionPlugin`].
+#[derive(Debug)]
+pub struct InjectedFaultError {
+    fault: InjectedFault,
+    attempt: u32,
+}
+
+impl InjectedFaultError {
+    /// Returns the fault that was injected.
+    pub fn fault(&self) -> InjectedFault {
+        self.fault
+    }
+
+    /// Returns the attempt number the fault was injected into.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+impl fmt::Display for InjectedFaultError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "injected {:?} fault into attempt #{}",
+            self.fault, self.attempt
+        )
+    }
+}
+
+impl StdError for InjectedFaultError {}
+
+/// Runtime plugin that deterministically fails chosen request attempts.
+///
+/// Faulted attempts fail with a [`ConnectorError`] before the configured HTTP client is called,
+/// so retry classification, backoff, and anything that must remain the same across attempts
+/// (such as idempotency tokens) can be asserted in unit tests. Attempts that aren't faulted are
+/// passed through to the HTTP client configured by previous runtime plugins.
+///
+/// Attempts are numbered starting at 1, and are counted across every request dispatched by
+/// clients using this plugin. Use a fresh plugin for each operation under test.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use aws_smithy_runtime::client::test_util::fault_injection::{FaultInjectionPlugin, InjectedFault};
+///
+/// // Throttle the first two attempts, and let the third one through
+/// let plugin = FaultInjectionPlugin::new().fail_first_attempts(2, InjectedFault::Throttling);
+/// let config = my_service_client::Config::builder()
+///     .http_client(http_client)
+///     .runtime_plugin(plugin.clone())
+///     .build();
+/// let client = my_service_client::Client::from_conf(config);
+/// client.some_operation().send().await.expect("third attempt succeeds");
+/// assert_eq!(3, plugin.num_attempts());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FaultInjectionPlugin {
+    faults: Arc<HashMap<u32, InjectedFault>>,
+    attempts: Arc<AtomicU32>,
+}
+
+impl FaultInjectionPlugin {
+    /// Creates a new `FaultInjectionPlugin` that doesn't fail any attempts.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Fails the given attempt (starting at 1) with the given fault.
+    pub fn fail_attempt(mut self, attempt: u32, fault: InjectedFault) -> Self {
+        Arc::make_mut(&mut self.faults).insert(attempt, fault);
+        self
+    }
+
+    /// Fails the first `count` attempts with the given fault.
+    pub fn fail_first_attempts(mut self, count: u32, fault: InjectedFault) -> Self {
+        for attempt in 1..=count {
+            self = self.fail_attempt(attempt, fault);
+        }
+        self
+    }
+
+    /// Returns the number of attempts that have been made through this plugin so far.
+    pub fn num_attempts(&self) -> u32 {
+        self.attempts.load(Ordering::SeqCst)
+    }
+}
+
+impl RuntimePlugin for FaultInjectionPlugin {
+    fn order(&self) -> Order {
+        Order::NestedComponents
+    }
+
+    fn runtime_components(
+        &self,
+        current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        let inner = current_components.http_client();
+        Cow::Owned(
+            RuntimeComponentsBuilder::new("FaultInjectionPlugin").with_http_client(inner.map(
+                |inner| FaultInjectingClient {
+                    inner,
+                    plugin: self.clone(),
+                },
+            )),
+        )
+    }
+}
+
+#[derive(Debug)]
+struct FaultInjectingClient {
+    inner: SharedHttpClient,
+    plugin: FaultInjectionPlugin,
+}
+
+impl HttpClient for FaultInjectingClient {
+    fn http_connector(
+        &self,
+        settings: &HttpConnectorSettings,
+        components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        FaultInjectingConnector {
+            inner: self.inner.http_connector(settings, components),
+            plugin: self.plugin.clone(),
+        }
+        .into_shared()
+    }
+}
+
+#[derive(Debug)]
+struct FaultInjectingConnector {
+    inner: SharedHttpConnector,
+    plugin: FaultInjectionPlugin,
+}
+
+impl HttpConnector for FaultInjectingConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let attempt = self.plugin.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        match self.plugin.faults.get(&attempt) {
+            Some(fault) => {
+                tracing::debug!(attempt = attempt, fault = ?fault, "injecting fault");
+                HttpConnectorFuture::ready(Err(fault.into_connector_error(attempt)))
+            }
+            None => self.inner.call(request),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::http::test_util::infallible_client_fn;
+
+    #[tokio::test]
+    async fn fails_chosen_attempts() {
+        let plugin = FaultInjectionPlugin::new()
+            .fail_first_attempts(2, InjectedFault::Throttling)
+            .fail_attempt(4, InjectedFault::Timeout);
+        let current = RuntimeComponentsBuilder::for_tests().with_http_client(Some(
+            infallible_client_fn(|_req| http::Response::builder().status(200).body("").unwrap()),
+        ));
+        let components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let connector = plugin
+            .runtime_components(&current)
+            .http_client()
+            .expect("http client wrapped")
+            .http_connector(&HttpConnectorSettings::default(), &components);
+
+        let mut results = Vec::new();
+        for _ in 0..5 {
+            results.push(
+                connector
+                    .call(HttpRequest::get("https://example.com").unwrap())
+                    .await,
+            );
+        }
+
+        let err = results[0].as_ref().unwrap_err();
+        assert_eq!(Some(ErrorKind::ThrottlingError), err.as_other());
+        assert!(results[1].as_ref().unwrap_err().is_other());
+        assert_eq!(200, results[2].as_ref().unwrap().status().as_u16());
+        assert!(results[3].as_ref().unwrap_err().is_timeout());
+        assert!(results[4].is_ok());
+        assert_eq!(5, plugin.num_attempts());
+    }
+}
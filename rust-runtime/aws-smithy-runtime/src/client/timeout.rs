@@ -3,28 +3,39 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::client::http::connection_poisoning::CaptureSmithyConnection;
 use aws_smithy_async::future::timeout::Timeout;
 use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
-use aws_smithy_types::config_bag::ConfigBag;
-use aws_smithy_types::timeout::TimeoutConfig;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::timeout::{Deadline, TimeoutConfig};
+use bytes::Bytes;
 use pin_project_lite::pin_project;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug)]
 struct MaybeTimeoutError {
     kind: TimeoutKind,
     duration: Duration,
+    phases: Option<PhaseTimingsSnapshot>,
 }
 
 impl MaybeTimeoutError {
-    fn new(kind: TimeoutKind, duration: Duration) -> Self {
-        Self { kind, duration }
+    fn new(kind: TimeoutKind, duration: Duration, phases: Option<PhaseTimingsSnapshot>) -> Self {
+        Self {
+            kind,
+            duration,
+            phases,
+        }
     }
 }
 
@@ -36,9 +47,14 @@ impl std::fmt::Display for MaybeTimeoutError {
             match self.kind {
                 TimeoutKind::Operation => "operation timeout (all attempts including retries)",
                 TimeoutKind::OperationAttempt => "operation attempt timeout (single attempt)",
+                TimeoutKind::Deadline => "operation deadline (all attempts including retries)",
             },
             self.duration
-        )
+        )?;
+        match &self.phases {
+            Some(phases) if !phases.0.is_empty() => write!(f, " (time spent: {phases})"),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -62,6 +78,7 @@ pin_project! {
             future: Timeout<F, Sleep>,
             timeout_kind: TimeoutKind,
             duration: Duration,
+            phase_timings: Option<PhaseTimings>,
         },
         /// A thin wrapper around an inner future that will never time out
         NoTimeout {
@@ -78,19 +95,24 @@ where
     type Output = Result<T, SdkError<E, HttpResponse>>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let (future, kind, duration) = match self.project() {
+        let (future, kind, duration, phase_timings) = match self.project() {
             MaybeTimeoutFutureProj::NoTimeout { future } => return future.poll(cx),
             MaybeTimeoutFutureProj::Timeout {
                 future,
                 timeout_kind,
                 duration,
-            } => (future, timeout_kind, duration),
+                phase_timings,
+            } => (future, timeout_kind, duration, phase_timings),
         };
         match future.poll(cx) {
             Poll::Ready(Ok(response)) => Poll::Ready(response),
-            Poll::Ready(Err(_timeout)) => Poll::Ready(Err(SdkError::timeout_error(
-                MaybeTimeoutError::new(*kind, *duration),
-            ))),
+            Poll::Ready(Err(_timeout)) => {
+                Poll::Ready(Err(SdkError::timeout_error(MaybeTimeoutError::new(
+                    *kind,
+                    *duration,
+                    phase_timings.as_ref().map(PhaseTimings::snapshot),
+                ))))
+            }
             Poll::Pending => Poll::Pending,
         }
     }
@@ -100,6 +122,8 @@ where
 pub(super) enum TimeoutKind {
     Operation,
     OperationAttempt,
+    /// The operation's [`Deadline`] is closer than its operation timeout (if any).
+    Deadline,
 }
 
 #[derive(Clone, Debug)]
@@ -107,6 +131,7 @@ pub(super) struct MaybeTimeoutConfig {
     sleep_impl: Option<SharedAsyncSleep>,
     timeout: Option<Duration>,
     timeout_kind: TimeoutKind,
+    phase_timings: Option<PhaseTimings>,
 }
 
 impl MaybeTimeoutConfig {
@@ -115,27 +140,359 @@ impl MaybeTimeoutConfig {
         cfg: &ConfigBag,
         timeout_kind: TimeoutKind,
     ) -> MaybeTimeoutConfig {
-        if let Some(timeout_config) = cfg.load::<TimeoutConfig>() {
-            let sleep_impl = runtime_components.sleep_impl();
-            let timeout = match (sleep_impl.as_ref(), timeout_kind) {
-                (None, _) => None,
-                (Some(_), TimeoutKind::Operation) => timeout_config.operation_timeout(),
-                (Some(_), TimeoutKind::OperationAttempt) => {
-                    timeout_config.operation_attempt_timeout()
+        // The deadline applies even when no timeout config is set
+        let timeout_config = cfg.load::<TimeoutConfig>();
+        let (timeout, timeout_kind) = match timeout_kind {
+            TimeoutKind::Operation | TimeoutKind::Deadline => {
+                // An absolute deadline can only be enforced if there's a time source to measure it with
+                let remaining = cfg
+                    .load::<Deadline>()
+                    .zip(runtime_components.time_source())
+                    .map(|(deadline, time_source)| deadline.remaining(time_source.now()));
+                match (
+                    timeout_config.and_then(TimeoutConfig::operation_timeout),
+                    remaining,
+                ) {
+                    (Some(timeout), Some(remaining)) if timeout <= remaining => {
+                        (Some(timeout), TimeoutKind::Operation)
+                    }
+                    (_, Some(remaining)) => (Some(remaining), TimeoutKind::Deadline),
+                    (timeout, None) => (timeout, TimeoutKind::Operation),
                 }
-            };
-            MaybeTimeoutConfig {
-                sleep_impl,
-                timeout,
-                timeout_kind,
             }
-        } else {
-            MaybeTimeoutConfig {
-                sleep_impl: None,
-                timeout: None,
-                timeout_kind,
+            TimeoutKind::OperationAttempt => (
+                timeout_config.and_then(TimeoutConfig::operation_attempt_timeout),
+                TimeoutKind::OperationAttempt,
+            ),
+        };
+        MaybeTimeoutConfig {
+            sleep_impl: runtime_components.sleep_impl(),
+            timeout,
+            timeout_kind,
+            phase_timings: cfg.load::<PhaseTimings>().cloned(),
+        }
+    }
+
+    /// Returns the timeout if one is set and can be enforced.
+    pub(super) fn timeout(&self) -> Option<Duration> {
+        self.sleep_impl.as_ref().and(self.timeout)
+    }
+}
+
+/// A phase of an operation that's timed so that timeout errors can say where the time went.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(super) enum Phase {
+    /// Serializing the input into a request.
+    Serialization,
+    /// Waiting before making a retry attempt.
+    RetryBackoff,
+    /// Resolving the endpoint and signing the request for an attempt.
+    RequestPreparation,
+    /// Resolving the host name and establishing the TCP connection.
+    Connect,
+    /// Negotiating TLS on a new connection.
+    TlsHandshake,
+    /// Writing the request to the connection.
+    RequestWrite,
+    /// Waiting for the response headers once the request was written.
+    TimeToFirstByte,
+    /// Reading and deserializing the response body.
+    ResponseRead,
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Phase::Serialization => "serialization",
+            Phase::RetryBackoff => "retry backoff",
+            Phase::RequestPreparation => "endpoint resolution and signing",
+            Phase::Connect => "connect",
+            Phase::TlsHandshake => "TLS handshake",
+            Phase::RequestWrite => "request write",
+            Phase::TimeToFirstByte => "time to first byte",
+            Phase::ResponseRead => "response read",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PhaseTiming {
+    phase: Phase,
+    attempt: Option<u32>,
+    elapsed: Duration,
+    in_progress: bool,
+}
+
+/// The phase that's currently being timed.
+#[derive(Debug)]
+enum CurrentPhase {
+    Phase(Phase),
+    /// Sending the request and waiting for the response, which is split into phases once it ends.
+    Transmit(Transmit),
+}
+
+/// What's known about the progress of sending a request.
+#[derive(Debug)]
+struct Transmit {
+    connection: Option<CaptureSmithyConnection>,
+    request_write: RequestWrite,
+}
+
+impl Transmit {
+    /// Splits the time from `started_at` until `now` into phases, with the connection timings
+    /// reported by the HTTP client and the time that the request body was written.
+    ///
+    /// The time before the HTTP client reports the connection, and before the request is written,
+    /// is counted as connecting.
+    fn phases(
+        &self,
+        attempt: Option<u32>,
+        started_at: SystemTime,
+        now: SystemTime,
+    ) -> Vec<PhaseTiming> {
+        let connection = self
+            .connection
+            .as_ref()
+            .and_then(CaptureSmithyConnection::get);
+        let written_at = self.request_write.written_at();
+        let mut phases = Vec::new();
+        let mut at = started_at;
+        let mut push = |phase, until: SystemTime| {
+            let until = until.clamp(at, now.max(at));
+            phases.push(PhaseTiming {
+                phase,
+                attempt,
+                elapsed: until.duration_since(at).unwrap_or_default(),
+                in_progress: false,
+            });
+            at = until;
+        };
+        match &connection {
+            None if written_at.is_none() => push(Phase::Connect, now),
+            None => {}
+            Some(connection) => {
+                // A reused connection doesn't have connection timings
+                let (dns, connect) = (connection.dns_duration(), connection.connect_duration());
+                let connected_at =
+                    started_at + dns.unwrap_or_default() + connect.unwrap_or_default();
+                if dns.is_some() || connect.is_some() {
+                    push(Phase::Connect, connected_at);
+                }
+                if let Some(tls_handshake) = connection.tls_handshake_duration() {
+                    push(Phase::TlsHandshake, connected_at + tls_handshake);
+                }
+            }
+        }
+        match written_at {
+            Some(written_at) => {
+                push(Phase::RequestWrite, written_at);
+                push(Phase::TimeToFirstByte, now);
+            }
+            None if connection.is_none() => {}
+            None if self.request_write.empty => push(Phase::TimeToFirstByte, now),
+            None => push(Phase::RequestWrite, now),
+        }
+        phases
+    }
+}
+
+/// Records when the request body of an attempt has been completely written.
+#[derive(Clone, Debug)]
+struct RequestWrite {
+    time_source: SharedTimeSource,
+    /// Empty bodies aren't read, since only the request headers are written
+    empty: bool,
+    written_at: Arc<Mutex<Option<SystemTime>>>,
+}
+
+impl RequestWrite {
+    fn written_at(&self) -> Option<SystemTime> {
+        *self.written_at.lock().unwrap()
+    }
+
+    fn record_written(&self) {
+        let now = self.time_source.now();
+        self.written_at.lock().unwrap().get_or_insert(now);
+    }
+}
+
+pin_project! {
+    /// A request body that records when the HTTP client has read all of it.
+    struct RequestWriteBody<B> {
+        request_write: RequestWrite,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> http_body_0_4::Body for RequestWriteBody<B>
+where
+    B: http_body_0_4::Body<Data = Bytes, Error = BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        let poll = this.inner.poll_data(cx);
+        if let Poll::Ready(None) = &poll {
+            this.request_write.record_written();
+        }
+        poll
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[derive(Debug, Default)]
+struct PhaseTimingsInner {
+    completed: Vec<PhaseTiming>,
+    current: Option<(CurrentPhase, Option<u32>, SystemTime)>,
+}
+
+/// Records how long each phase of an operation takes.
+///
+/// This is stored in the config bag by the orchestrator, and shared with the timeout futures so that
+/// the time spent in each phase can be included in timeout errors.
+#[derive(Clone, Debug)]
+pub(super) struct PhaseTimings {
+    time_source: SharedTimeSource,
+    inner: Arc<Mutex<PhaseTimingsInner>>,
+}
+
+impl Storable for PhaseTimings {
+    type Storer = StoreReplace<Self>;
+}
+
+impl PhaseTimings {
+    pub(super) fn new(time_source: SharedTimeSource) -> Self {
+        Self {
+            time_source,
+            inner: Default::default(),
+        }
+    }
+
+    /// Ends the current phase (if any), and starts timing `phase`.
+    pub(super) fn enter(&self, phase: Phase, attempt: Option<u32>) {
+        self.enter_current(CurrentPhase::Phase(phase), attempt);
+    }
+
+    /// Ends the current phase (if any), and starts timing the transmission of a request with the
+    /// given body.
+    ///
+    /// The time spent is split into the connect, TLS handshake, request write, and time to first
+    /// byte phases, with the timings of the captured `connection`. Returns the body to send, which
+    /// records when it has been written.
+    pub(super) fn enter_transmit(
+        &self,
+        attempt: Option<u32>,
+        connection: Option<CaptureSmithyConnection>,
+        body: SdkBody,
+    ) -> SdkBody {
+        let request_write = RequestWrite {
+            time_source: self.time_source.clone(),
+            empty: http_body_0_4::Body::is_end_stream(&body),
+            written_at: Default::default(),
+        };
+        let transmit = Transmit {
+            connection,
+            request_write: request_write.clone(),
+        };
+        self.enter_current(CurrentPhase::Transmit(transmit), attempt);
+        body.map_preserve_contents(move |body| {
+            SdkBody::from_body_0_4(RequestWriteBody {
+                request_write: request_write.clone(),
+                inner: body,
+            })
+        })
+    }
+
+    fn enter_current(&self, phase: CurrentPhase, attempt: Option<u32>) {
+        let now = self.time_source.now();
+        let mut inner = self.inner.lock().unwrap();
+        Self::end_current(&mut inner, now);
+        inner.current = Some((phase, attempt, now));
+    }
+
+    /// Ends the current phase (if any).
+    pub(super) fn exit(&self) {
+        let now = self.time_source.now();
+        Self::end_current(&mut self.inner.lock().unwrap(), now);
+    }
+
+    fn end_current(inner: &mut PhaseTimingsInner, now: SystemTime) {
+        if let Some((phase, attempt, started_at)) = inner.current.take() {
+            let timings = Self::timings(&phase, attempt, started_at, now);
+            inner.completed.extend(timings);
+        }
+    }
+
+    fn timings(
+        phase: &CurrentPhase,
+        attempt: Option<u32>,
+        started_at: SystemTime,
+        now: SystemTime,
+    ) -> Vec<PhaseTiming> {
+        match phase {
+            CurrentPhase::Phase(phase) => vec![PhaseTiming {
+                phase: *phase,
+                attempt,
+                elapsed: now.duration_since(started_at).unwrap_or_default(),
+                in_progress: false,
+            }],
+            CurrentPhase::Transmit(transmit) => transmit.phases(attempt, started_at, now),
+        }
+    }
+
+    fn snapshot(&self) -> PhaseTimingsSnapshot {
+        let now = self.time_source.now();
+        let inner = self.inner.lock().unwrap();
+        let mut timings = inner.completed.clone();
+        if let Some((phase, attempt, started_at)) = &inner.current {
+            let mut current = Self::timings(phase, *attempt, *started_at, now);
+            if let Some(last) = current.last_mut() {
+                last.in_progress = true;
             }
+            timings.extend(current);
         }
+        PhaseTimingsSnapshot(timings)
+    }
+}
+
+#[derive(Debug)]
+struct PhaseTimingsSnapshot(Vec<PhaseTiming>);
+
+impl std::fmt::Display for PhaseTimingsSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, timing) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            if let Some(attempt) = timing.attempt {
+                write!(f, "attempt #{attempt} ")?;
+            }
+            write!(f, "{}: {:?}", timing.phase, timing.elapsed)?;
+            if timing.in_progress {
+                f.write_str(" (in progress)")?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -155,10 +512,12 @@ where
                 sleep_impl: Some(sleep_impl),
                 timeout: Some(timeout),
                 timeout_kind,
+                phase_timings,
             } => MaybeTimeoutFuture::Timeout {
                 future: Timeout::new(self, sleep_impl.sleep(timeout)),
                 timeout_kind,
                 duration: timeout,
+                phase_timings,
             },
             _ => MaybeTimeoutFuture::NoTimeout { future: self },
         }
@@ -171,12 +530,15 @@ mod tests {
     use aws_smithy_async::assert_elapsed;
     use aws_smithy_async::future::never::Never;
     use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, TokioSleep};
+    use aws_smithy_async::test_util::{instant_time_and_sleep, ManualTimeSource};
+    use aws_smithy_runtime_api::client::connection::ConnectionMetadata;
     use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
     use aws_smithy_runtime_api::client::result::SdkError;
     use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::byte_stream::ByteStream;
     use aws_smithy_types::config_bag::{CloneableLayer, ConfigBag};
-    use aws_smithy_types::timeout::TimeoutConfig;
-    use std::time::Duration;
+    use aws_smithy_types::timeout::{Deadline, TimeoutConfig};
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[tokio::test]
     async fn test_no_timeout() {
@@ -238,7 +600,223 @@ mod tests {
         let result = underlying_future.maybe_timeout(maybe_timeout).await;
         let err = result.expect_err("should have timed out");
 
-        assert_eq!(format!("{:?}", err), "TimeoutError(TimeoutError { source: MaybeTimeoutError { kind: Operation, duration: 250ms } })");
+        assert_eq!(format!("{:?}", err), "TimeoutError(TimeoutError { source: MaybeTimeoutError { kind: Operation, duration: 250ms, phases: None } })");
         assert_elapsed!(now, Duration::from_secs_f32(0.25));
     }
+
+    #[tokio::test]
+    async fn test_deadline_before_operation_timeout() {
+        let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(sleep_impl))
+            .with_time_source(Some(time_source))
+            .build()
+            .unwrap();
+        let mut layer = CloneableLayer::new("timeout");
+        layer.store_put(
+            TimeoutConfig::builder()
+                .operation_timeout(Duration::from_secs(10))
+                .build(),
+        );
+        layer.store_put(Deadline::new(UNIX_EPOCH + Duration::from_secs(2)));
+        let cfg = ConfigBag::of_layers(vec![layer.into()]);
+
+        let maybe_timeout =
+            MaybeTimeoutConfig::new(&runtime_components, &cfg, TimeoutKind::Operation);
+        assert_eq!(Some(Duration::from_secs(2)), maybe_timeout.timeout());
+        let err = async {
+            Never::new().await;
+            Result::<(), SdkError<(), HttpResponse>>::Ok(())
+        }
+        .maybe_timeout(maybe_timeout)
+        .await
+        .expect_err("should have timed out");
+        assert_eq!(format!("{:?}", err), "TimeoutError(TimeoutError { source: MaybeTimeoutError { kind: Deadline, duration: 2s, phases: None } })");
+    }
+
+    #[test]
+    fn test_deadline_without_timeout_config() {
+        let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(sleep_impl))
+            .with_time_source(Some(time_source))
+            .build()
+            .unwrap();
+        let mut layer = CloneableLayer::new("deadline");
+        layer.store_put(Deadline::new(UNIX_EPOCH + Duration::from_secs(2)));
+        let cfg = ConfigBag::of_layers(vec![layer.into()]);
+
+        let maybe_timeout =
+            MaybeTimeoutConfig::new(&runtime_components, &cfg, TimeoutKind::Operation);
+        assert_eq!(Some(Duration::from_secs(2)), maybe_timeout.timeout());
+        assert_eq!(TimeoutKind::Deadline, maybe_timeout.timeout_kind);
+        let maybe_timeout =
+            MaybeTimeoutConfig::new(&runtime_components, &cfg, TimeoutKind::OperationAttempt);
+        assert_eq!(None, maybe_timeout.timeout());
+    }
+
+    #[test]
+    fn test_operation_timeout_before_deadline() {
+        let (time_source, sleep_impl) = instant_time_and_sleep(UNIX_EPOCH);
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(sleep_impl))
+            .with_time_source(Some(time_source))
+            .build()
+            .unwrap();
+        let mut layer = CloneableLayer::new("timeout");
+        layer.store_put(
+            TimeoutConfig::builder()
+                .operation_timeout(Duration::from_secs(1))
+                .build(),
+        );
+        layer.store_put(Deadline::new(UNIX_EPOCH + Duration::from_secs(2)));
+        let cfg = ConfigBag::of_layers(vec![layer.into()]);
+
+        let maybe_timeout =
+            MaybeTimeoutConfig::new(&runtime_components, &cfg, TimeoutKind::Operation);
+        assert_eq!(Some(Duration::from_secs(1)), maybe_timeout.timeout());
+        assert_eq!(TimeoutKind::Operation, maybe_timeout.timeout_kind);
+    }
+
+    #[test]
+    fn test_timeout_error_includes_phase_timings() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let phase_timings = PhaseTimings::new(SharedTimeSource::new(time_source.clone()));
+        phase_timings.enter(Phase::Serialization, None);
+        time_source.advance(Duration::from_millis(5));
+        let _body = phase_timings.enter_transmit(Some(1), None, SdkBody::empty());
+        time_source.advance(Duration::from_millis(300));
+        phase_timings.exit();
+        phase_timings.enter(Phase::RetryBackoff, None);
+        time_source.advance(Duration::from_millis(100));
+        phase_timings.enter(Phase::ResponseRead, Some(2));
+        time_source.advance(Duration::from_millis(95));
+
+        let err = MaybeTimeoutError::new(
+            TimeoutKind::Operation,
+            Duration::from_millis(500),
+            Some(phase_timings.snapshot()),
+        );
+        assert_eq!(
+            "operation timeout (all attempts including retries) occurred after 500ms \
+            (time spent: serialization: 5ms, \
+            attempt #1 connect: 300ms, \
+            retry backoff: 100ms, \
+            attempt #2 response read: 95ms (in progress))",
+            err.to_string()
+        );
+    }
+
+    fn captured_connection(connection: ConnectionMetadata) -> CaptureSmithyConnection {
+        let capture = CaptureSmithyConnection::new();
+        capture.set_connection_retriever(move || Some(connection.clone()));
+        capture
+    }
+
+    fn phases(phase_timings: &PhaseTimings) -> Vec<(Phase, Duration, bool)> {
+        phase_timings
+            .snapshot()
+            .0
+            .into_iter()
+            .map(|timing| (timing.phase, timing.elapsed, timing.in_progress))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_transmit_phase_is_split_with_connection_timings() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let phase_timings = PhaseTimings::new(SharedTimeSource::new(time_source.clone()));
+        let connection = ConnectionMetadata::builder()
+            .proxied(false)
+            .dns_duration(Duration::from_millis(10))
+            .connect_duration(Duration::from_millis(20))
+            .tls_handshake_duration(Duration::from_millis(30))
+            .poison_fn(|| {})
+            .build();
+        let body = phase_timings.enter_transmit(
+            Some(1),
+            Some(captured_connection(connection)),
+            SdkBody::from("request"),
+        );
+        time_source.advance(Duration::from_millis(100));
+        ByteStream::new(body).collect().await.unwrap();
+        time_source.advance(Duration::from_millis(200));
+        assert_eq!(
+            vec![
+                (Phase::Connect, Duration::from_millis(30), false),
+                (Phase::TlsHandshake, Duration::from_millis(30), false),
+                (Phase::RequestWrite, Duration::from_millis(40), false),
+                (Phase::TimeToFirstByte, Duration::from_millis(200), true),
+            ],
+            phases(&phase_timings)
+        );
+
+        phase_timings.enter(Phase::ResponseRead, Some(1));
+        let err = MaybeTimeoutError::new(
+            TimeoutKind::Operation,
+            Duration::from_millis(300),
+            Some(phase_timings.snapshot()),
+        );
+        assert_eq!(
+            "operation timeout (all attempts including retries) occurred after 300ms \
+            (time spent: attempt #1 connect: 30ms, \
+            attempt #1 TLS handshake: 30ms, \
+            attempt #1 request write: 40ms, \
+            attempt #1 time to first byte: 200ms, \
+            attempt #1 response read: 0ns (in progress))",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_transmit_phase_on_a_reused_connection() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let phase_timings = PhaseTimings::new(SharedTimeSource::new(time_source.clone()));
+        let connection = ConnectionMetadata::builder()
+            .proxied(false)
+            .reused(true)
+            .poison_fn(|| {})
+            .build();
+
+        // Empty bodies are only written with the request headers
+        let _body = phase_timings.enter_transmit(
+            Some(2),
+            Some(captured_connection(connection.clone())),
+            SdkBody::empty(),
+        );
+        time_source.advance(Duration::from_millis(50));
+        assert_eq!(
+            vec![(Phase::TimeToFirstByte, Duration::from_millis(50), true)],
+            phases(&phase_timings)
+        );
+
+        let phase_timings = PhaseTimings::new(SharedTimeSource::new(time_source.clone()));
+        let _body = phase_timings.enter_transmit(
+            Some(2),
+            Some(captured_connection(connection)),
+            SdkBody::from("request"),
+        );
+        time_source.advance(Duration::from_millis(50));
+        phase_timings.exit();
+        assert_eq!(
+            vec![(Phase::RequestWrite, Duration::from_millis(50), false)],
+            phases(&phase_timings)
+        );
+    }
+
+    #[test]
+    fn test_transmit_phase_before_the_connection_is_established() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let phase_timings = PhaseTimings::new(SharedTimeSource::new(time_source.clone()));
+        let _body = phase_timings.enter_transmit(
+            Some(1),
+            Some(CaptureSmithyConnection::new()),
+            SdkBody::from("request"),
+        );
+        time_source.advance(Duration::from_millis(75));
+        assert_eq!(
+            vec![(Phase::Connect, Duration::from_millis(75), true)],
+            phases(&phase_timings)
+        );
+    }
 }
@@ -0,0 +1,211 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that propagates the active span to downstream services in trace headers.
+//!
+//! The [`TraceContextInterceptor`] isn't added to clients by default. Add it to a client's config
+//! with `.interceptor(TraceContextInterceptor::new())` to send the
+//! [W3C Trace Context](https://www.w3.org/TR/trace-context/) `traceparent` and `tracestate`
+//! headers with every request, so that downstream services and service meshes can stitch their
+//! traces together with the client's.
+//!
+//! The headers are built from the [`SpanContext`] in the config bag. The orchestrator stores the
+//! context of each attempt's span there when a tracer provider is configured whose spans
+//! implement [`Span::context`](aws_smithy_observability::tracer::Span::context). A `SpanContext`
+//! can also be set on the config directly, to continue a trace that was started elsewhere.
+
+use aws_smithy_observability::tracer::SpanContext;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::fmt::Write;
+
+const TRACEPARENT_HEADER: &str = "traceparent";
+const TRACESTATE_HEADER: &str = "tracestate";
+const XRAY_TRACE_ID_HEADER: &str = "x-amzn-trace-id";
+
+/// The version of the `traceparent` header format that's sent.
+const TRACEPARENT_VERSION: &str = "00";
+/// The version of the X-Ray trace ID format that's sent.
+const XRAY_VERSION: &str = "1";
+
+/// Interceptor that injects trace headers into requests from the [`SpanContext`] in the config bag.
+///
+/// Requests that already have a `traceparent` header are left as they are. Requests are sent
+/// without trace headers when there's no `SpanContext`, or when its IDs aren't valid.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_runtime::client::trace_context::TraceContextInterceptor;
+///
+/// // Send the `X-Amzn-Trace-Id` header as well as the W3C headers
+/// let interceptor = TraceContextInterceptor::new().with_xray_header(true);
+/// ```
+#[derive(Debug, Default)]
+pub struct TraceContextInterceptor {
+    xray_header: bool,
+}
+
+impl TraceContextInterceptor {
+    /// Creates a new `TraceContextInterceptor` that sends the W3C Trace Context headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the `X-Amzn-Trace-Id` header is sent, in the format of AWS X-Ray.
+    ///
+    /// It isn't sent by default. When the request already has an `X-Amzn-Trace-Id` header, such
+    /// as one set by recursion detection in AWS Lambda, it's left as it is.
+    pub fn with_xray_header(mut self, xray_header: bool) -> Self {
+        self.xray_header = xray_header;
+        self
+    }
+}
+
+impl Intercept for TraceContextInterceptor {
+    fn name(&self) -> &'static str {
+        "TraceContextInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let span_context = match cfg.load::<SpanContext>() {
+            Some(span_context) if span_context.is_valid() => span_context,
+            _ => return Ok(()),
+        };
+        let headers = context.request_mut().headers_mut();
+        if !headers.contains_key(TRACEPARENT_HEADER) {
+            headers.insert(TRACEPARENT_HEADER, traceparent(span_context));
+            if let Some(trace_state) = span_context.trace_state().filter(|s| !s.is_empty()) {
+                headers.try_insert(TRACESTATE_HEADER, trace_state.to_string())?;
+            }
+        }
+        if self.xray_header && !headers.contains_key(XRAY_TRACE_ID_HEADER) {
+            headers.insert(XRAY_TRACE_ID_HEADER, xray_trace_id(span_context));
+        }
+        Ok(())
+    }
+}
+
+/// Formats a span context as a `traceparent` header, such as
+/// `00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01`.
+fn traceparent(span_context: &SpanContext) -> String {
+    format!(
+        "{TRACEPARENT_VERSION}-{}-{}-{:02x}",
+        hex(&span_context.trace_id()),
+        hex(&span_context.span_id()),
+        u8::from(span_context.is_sampled()),
+    )
+}
+
+/// Formats a span context as an X-Ray trace header, such as
+/// `Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1`.
+///
+/// X-Ray trace IDs start with the time the trace started, which is the first 4 bytes of the
+/// W3C trace ID when the trace was started with an X-Ray compatible ID generator.
+fn xray_trace_id(span_context: &SpanContext) -> String {
+    let trace_id = hex(&span_context.trace_id());
+    let (epoch, unique) = trace_id.split_at(8);
+    format!(
+        "Root={XRAY_VERSION}-{epoch}-{unique};Parent={};Sampled={}",
+        hex(&span_context.span_id()),
+        u8::from(span_context.is_sampled()),
+    )
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::Layer;
+
+    const TRACE_ID: [u8; 16] = [
+        0x57, 0x59, 0xe9, 0x88, 0xbd, 0x86, 0x2e, 0x3f, 0xe1, 0xbe, 0x46, 0xa9, 0x94, 0x27, 0x27,
+        0x93,
+    ];
+    const SPAN_ID: [u8; 8] = [0x53, 0x99, 0x5c, 0x3f, 0x42, 0xcd, 0x8a, 0xd8];
+
+    fn intercept(
+        interceptor: TraceContextInterceptor,
+        span_context: Option<SpanContext>,
+    ) -> HttpRequest {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.enter_serialization_phase();
+        context.set_request(HttpRequest::new(SdkBody::empty()));
+        let _ = context.take_input();
+        context.enter_before_transmit_phase();
+        let mut layer = Layer::new("test");
+        layer.store_or_unset(span_context);
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+
+        let mut ctx = Into::into(&mut context);
+        interceptor
+            .modify_before_signing(&mut ctx, &rc, &mut cfg)
+            .expect("interceptor must succeed");
+        context.take_request().expect("request is set")
+    }
+
+    #[test]
+    fn injects_w3c_trace_context_headers() {
+        let request = intercept(
+            TraceContextInterceptor::new(),
+            Some(SpanContext::new(TRACE_ID, SPAN_ID).with_trace_state("vendor=value")),
+        );
+        assert_eq!(
+            Some("00-5759e988bd862e3fe1be46a994272793-53995c3f42cd8ad8-01"),
+            request.headers().get("traceparent")
+        );
+        assert_eq!(Some("vendor=value"), request.headers().get("tracestate"));
+        assert_eq!(None, request.headers().get("x-amzn-trace-id"));
+    }
+
+    #[test]
+    fn injects_xray_header_when_enabled() {
+        let request = intercept(
+            TraceContextInterceptor::new().with_xray_header(true),
+            Some(SpanContext::new(TRACE_ID, SPAN_ID).with_sampled(false)),
+        );
+        assert_eq!(
+            Some("00-5759e988bd862e3fe1be46a994272793-53995c3f42cd8ad8-00"),
+            request.headers().get("traceparent")
+        );
+        assert_eq!(None, request.headers().get("tracestate"));
+        assert_eq!(
+            Some("Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=0"),
+            request.headers().get("x-amzn-trace-id")
+        );
+    }
+
+    #[test]
+    fn no_headers_without_a_valid_span_context() {
+        let request = intercept(TraceContextInterceptor::new().with_xray_header(true), None);
+        assert_eq!(0, request.headers().iter().count());
+
+        let request = intercept(
+            TraceContextInterceptor::new(),
+            Some(SpanContext::new([0; 16], SPAN_ID)),
+        );
+        assert_eq!(None, request.headers().get("traceparent"));
+    }
+}
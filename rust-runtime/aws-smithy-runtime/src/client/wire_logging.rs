@@ -0,0 +1,467 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that logs requests and responses as they're sent and received.
+//!
+//! The [`WireLoggingInterceptor`] isn't added to clients by default. Add it to a client's config
+//! with `.interceptor(WireLoggingInterceptor::new())`, and enable the `debug` level for the
+//! `aws_smithy_runtime::client::wire_logging` tracing target to see its output.
+
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    AfterDeserializationInterceptorContextRef, BeforeTransmitInterceptorContextRef,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::SensitiveOutput;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::Headers;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::sensitive;
+use aws_smithy_types::Number;
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::sync::Arc;
+
+const REDACTED: &str = "** REDACTED **";
+const DEFAULT_MAX_BODY_BYTES: usize = 4096;
+
+/// Headers that are redacted by the default [`RedactionRules`].
+const DEFAULT_SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+    "x-amz-security-token",
+    "x-amz-server-side-encryption-customer-key",
+    "x-amz-copy-source-server-side-encryption-customer-key",
+];
+
+/// JSON fields that are redacted by the default [`RedactionRules`].
+const DEFAULT_SENSITIVE_JSON_FIELDS: &[&str] = &[
+    "accesstoken",
+    "clientsecret",
+    "password",
+    "refreshtoken",
+    "secretaccesskey",
+    "secretstring",
+    "sessiontoken",
+];
+
+/// Decides which parts of a request or response are redacted before they're logged.
+pub trait Redact: Send + Sync + fmt::Debug {
+    /// Returns `true` if the value of the header with the given name should be redacted.
+    fn redact_header(&self, name: &str) -> bool;
+
+    /// Returns `true` if the value of the JSON object field with the given name should be
+    /// redacted, including any objects or arrays nested in it.
+    fn redact_json_field(&self, name: &str) -> bool;
+}
+
+/// Redacts headers and JSON fields by name, ignoring case.
+///
+/// The default rules redact credentials, such as the `Authorization` header and `password`
+/// fields. Use [`RedactionRules::empty`] to start from a blank slate instead.
+#[derive(Clone, Debug)]
+pub struct RedactionRules {
+    headers: Vec<Cow<'static, str>>,
+    json_fields: Vec<Cow<'static, str>>,
+}
+
+impl Default for RedactionRules {
+    fn default() -> Self {
+        Self {
+            headers: DEFAULT_SENSITIVE_HEADERS
+                .iter()
+                .map(|&name| name.into())
+                .collect(),
+            json_fields: DEFAULT_SENSITIVE_JSON_FIELDS
+                .iter()
+                .map(|&name| name.into())
+                .collect(),
+        }
+    }
+}
+
+impl RedactionRules {
+    /// Creates the default redaction rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates redaction rules that don't redact anything.
+    pub fn empty() -> Self {
+        Self {
+            headers: Vec::new(),
+            json_fields: Vec::new(),
+        }
+    }
+
+    /// Redacts the value of the header with the given name.
+    pub fn with_header(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.headers.push(name.into());
+        self
+    }
+
+    /// Redacts the value of JSON object fields with the given name.
+    pub fn with_json_field(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.json_fields.push(name.into());
+        self
+    }
+}
+
+impl Redact for RedactionRules {
+    fn redact_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case(name))
+    }
+
+    fn redact_json_field(&self, name: &str) -> bool {
+        self.json_fields
+            .iter()
+            .any(|field| field.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Logs the request line, headers, and optionally the body of each request and response at the
+/// `debug` level.
+///
+/// Header values and JSON fields are redacted according to the configured [`Redact`]
+/// implementation, which defaults to [`RedactionRules::default`]. JSON fields named after a member
+/// that was [marked as sensitive at runtime](aws_smithy_types::sensitive::mark_sensitive) are
+/// redacted too. Response bodies of operations with sensitive output are never logged.
+#[derive(Clone, Debug)]
+pub struct WireLoggingInterceptor {
+    log_bodies: bool,
+    max_body_bytes: usize,
+    redactor: Arc<dyn Redact>,
+}
+
+impl Default for WireLoggingInterceptor {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl WireLoggingInterceptor {
+    /// Creates a `WireLoggingInterceptor` that logs headers, but not bodies, with the default
+    /// redaction rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a builder for `WireLoggingInterceptor`.
+    pub fn builder() -> WireLoggingInterceptorBuilder {
+        WireLoggingInterceptorBuilder::default()
+    }
+
+    fn format_body(&self, headers: &Headers, body: &SdkBody) -> String {
+        let Some(bytes) = body.bytes() else {
+            return "<streaming body>".into();
+        };
+        if bytes.is_empty() {
+            return "<empty body>".into();
+        }
+        let is_json = headers
+            .get("content-type")
+            .map(|content_type| content_type.contains("json"))
+            .unwrap_or_default();
+        let mut body = if is_json {
+            match redact_json(bytes, self.redactor.as_ref()) {
+                Some(json) => json,
+                None => return format!("{REDACTED} (the body isn't valid JSON)"),
+            }
+        } else {
+            match std::str::from_utf8(bytes) {
+                Ok(text) => text.to_string(),
+                Err(_) => return format!("<{} bytes of binary data>", bytes.len()),
+            }
+        };
+        if body.len() > self.max_body_bytes {
+            let mut end = self.max_body_bytes;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            let omitted = body.len() - end;
+            body.truncate(end);
+            let _ = write!(body, "... <{omitted} more bytes>");
+        }
+        body
+    }
+
+    fn format_headers(&self, headers: &Headers, out: &mut String) {
+        for (name, value) in headers.iter() {
+            let value = if self.redactor.redact_header(name) {
+                REDACTED
+            } else {
+                value
+            };
+            let _ = write!(out, "\n{name}: {value}");
+        }
+    }
+}
+
+impl Intercept for WireLoggingInterceptor {
+    fn name(&self) -> &'static str {
+        "WireLoggingInterceptor"
+    }
+
+    fn read_before_transmit(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let request = context.request();
+        let mut message = format!("{} {}", request.method(), request.uri());
+        self.format_headers(request.headers(), &mut message);
+        if self.log_bodies {
+            let body = self.format_body(request.headers(), request.body());
+            let _ = write!(message, "\n\n{body}");
+        }
+        tracing::debug!("sending request:\n{message}");
+        Ok(())
+    }
+
+    fn read_after_deserialization(
+        &self,
+        context: &AfterDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let response = context.response();
+        let mut message = response.status().as_u16().to_string();
+        self.format_headers(response.headers(), &mut message);
+        if self.log_bodies {
+            let body = if cfg.load::<SensitiveOutput>().is_some() {
+                Cow::Borrowed(REDACTED)
+            } else {
+                self.format_body(response.headers(), response.body()).into()
+            };
+            let _ = write!(message, "\n\n{body}");
+        }
+        tracing::debug!("received response:\n{message}");
+        Ok(())
+    }
+}
+
+/// Builder for [`WireLoggingInterceptor`]
+#[derive(Clone, Debug, Default)]
+pub struct WireLoggingInterceptorBuilder {
+    log_bodies: Option<bool>,
+    max_body_bytes: Option<usize>,
+    redactor: Option<Arc<dyn Redact>>,
+}
+
+impl WireLoggingInterceptorBuilder {
+    /// Sets whether request and response bodies are logged. Defaults to `false`.
+    ///
+    /// Only bodies that are already in memory are logged. Streaming bodies are never read by
+    /// this interceptor.
+    pub fn log_bodies(mut self, log_bodies: bool) -> Self {
+        self.log_bodies = Some(log_bodies);
+        self
+    }
+
+    /// Sets the maximum number of bytes of each body that are logged. Defaults to 4 KiB.
+    pub fn max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = Some(max_body_bytes);
+        self
+    }
+
+    /// Sets the rules that decide which headers and JSON fields are redacted.
+    pub fn redactor(mut self, redactor: impl Redact + 'static) -> Self {
+        self.redactor = Some(Arc::new(redactor));
+        self
+    }
+
+    /// Builds the interceptor.
+    pub fn build(self) -> WireLoggingInterceptor {
+        WireLoggingInterceptor {
+            log_bodies: self.log_bodies.unwrap_or_default(),
+            max_body_bytes: self.max_body_bytes.unwrap_or(DEFAULT_MAX_BODY_BYTES),
+            redactor: self
+                .redactor
+                .unwrap_or_else(|| Arc::new(RedactionRules::default())),
+        }
+    }
+}
+
+/// Re-serializes a JSON document with the values of redacted fields replaced, or returns `None`
+/// if it isn't valid JSON.
+fn redact_json(json: &[u8], redactor: &dyn Redact) -> Option<String> {
+    let mut out = String::with_capacity(json.len());
+    // Whether the next value in each enclosing object or array is its first
+    let mut first = vec![true];
+    let mut after_key = false;
+    let mut redact_next = false;
+    // The depth of the object or array being redacted, if one is
+    let mut skip_depth = 0;
+    for token in json_token_iter(json) {
+        let token = token.ok()?;
+        if skip_depth > 0 {
+            match token {
+                Token::StartArray { .. } | Token::StartObject { .. } => skip_depth += 1,
+                Token::EndArray { .. } | Token::EndObject { .. } => skip_depth -= 1,
+                _ => {}
+            }
+            continue;
+        }
+        let is_end = matches!(token, Token::EndArray { .. } | Token::EndObject { .. });
+        if !is_end {
+            if after_key {
+                after_key = false;
+            } else {
+                let first = first.last_mut()?;
+                if !*first {
+                    out.push(',');
+                }
+                *first = false;
+            }
+        }
+        if redact_next {
+            redact_next = false;
+            let _ = write!(out, "\"{REDACTED}\"");
+            if matches!(token, Token::StartArray { .. } | Token::StartObject { .. }) {
+                skip_depth = 1;
+            }
+            continue;
+        }
+        match token {
+            Token::StartArray { .. } => {
+                out.push('[');
+                first.push(true);
+            }
+            Token::EndArray { .. } => {
+                out.push(']');
+                first.pop();
+            }
+            Token::StartObject { .. } => {
+                out.push('{');
+                first.push(true);
+            }
+            Token::EndObject { .. } => {
+                out.push('}');
+                first.pop();
+            }
+            Token::ObjectKey { key, .. } => {
+                let _ = write!(out, "\"{}\":", key.as_escaped_str());
+                after_key = true;
+                let key = key.to_unescaped().ok()?;
+                redact_next =
+                    redactor.redact_json_field(&key) || sensitive::is_sensitive_member(&key);
+            }
+            Token::ValueBool { value, .. } => {
+                let _ = write!(out, "{value}");
+            }
+            Token::ValueNull { .. } => out.push_str("null"),
+            Token::ValueNumber { value, .. } => {
+                let _ = match value {
+                    Number::PosInt(value) => write!(out, "{value}"),
+                    Number::NegInt(value) => write!(out, "{value}"),
+                    Number::Float(value) => write!(out, "{value}"),
+                };
+            }
+            Token::ValueString { value, .. } => {
+                let _ = write!(out, "\"{}\"", value.as_escaped_str());
+            }
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use tracing_test::traced_test;
+
+    #[test]
+    fn sensitive_json_fields_are_redacted() {
+        let rules = RedactionRules::default().with_json_field("Nested");
+        let json = br#"{"Name":"a \"b\"","Password":"hunter2","Nested":{"a":[1,2]},"List":[{"SessionToken":"x","n":-1.5},null,true]}"#;
+        assert_eq!(
+            r#"{"Name":"a \"b\"","Password":"** REDACTED **","Nested":"** REDACTED **","List":[{"SessionToken":"** REDACTED **","n":-1.5},null,true]}"#,
+            redact_json(json, &rules).unwrap()
+        );
+        assert_eq!(None, redact_json(b"{\"unterminated\":", &rules));
+    }
+
+    #[test]
+    fn members_marked_sensitive_at_runtime_are_redacted() {
+        sensitive::mark_sensitive(sensitive::MemberPath::new(
+            "WireLoggingTestOutput",
+            "AccountNumber",
+        ));
+        assert_eq!(
+            r#"{"accountNumber":"** REDACTED **","Name":"a"}"#,
+            redact_json(
+                br#"{"accountNumber":"12345","Name":"a"}"#,
+                &RedactionRules::empty()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn bodies_are_truncated() {
+        let interceptor = WireLoggingInterceptor::builder()
+            .log_bodies(true)
+            .max_body_bytes(5)
+            .build();
+        let headers = Headers::new();
+        assert_eq!(
+            "hello... <6 more bytes>",
+            interceptor.format_body(&headers, &SdkBody::from("hello world"))
+        );
+        assert_eq!(
+            "<3 bytes of binary data>",
+            interceptor.format_body(&headers, &SdkBody::from(vec![0xff, 0xfe, 0xfd]))
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn sensitive_headers_are_redacted() {
+        let mut request = HttpRequest::new(SdkBody::from(r#"{"Password":"hunter2"}"#));
+        request
+            .headers_mut()
+            .insert("Authorization", "AWS4-HMAC-SHA256 Credential=secret");
+        request
+            .headers_mut()
+            .insert("Content-Type", "application/x-amz-json-1.1");
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        let _ = ctx.take_input();
+        ctx.set_request(request);
+        ctx.enter_before_transmit_phase();
+
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::base();
+        let interceptor = WireLoggingInterceptor::builder().log_bodies(true).build();
+        interceptor
+            .read_before_transmit(&(&ctx).into(), &rc, &mut cfg)
+            .unwrap();
+        assert!(logs_contain("sending request:"));
+
+        // The message spans several lines, but only its first line is attributed to this test's
+        // span in the captured logs, so check the rest of it directly
+        let request = ctx.request().unwrap();
+        let mut headers = String::new();
+        interceptor.format_headers(request.headers(), &mut headers);
+        assert_eq!(
+            "\nauthorization: ** REDACTED **\ncontent-type: application/x-amz-json-1.1",
+            headers
+        );
+        assert_eq!(
+            r#"{"Password":"** REDACTED **"}"#,
+            interceptor.format_body(request.headers(), request.body())
+        );
+    }
+}
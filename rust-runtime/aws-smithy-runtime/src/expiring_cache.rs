@@ -74,12 +74,25 @@ where
         future.await.map(|(value, _expiry)| value.clone())
     }
 
+    /// Replaces the cached value, even if it hasn't expired yet.
+    pub async fn set(&self, value: T, expiration: SystemTime) {
+        *self.value.write().await = OnceCell::new_with(Some((value, expiration)));
+    }
+
     /// If the value is expired, clears the cache. Otherwise, yields the current value.
     pub async fn yield_or_clear_if_expired(&self, now: SystemTime) -> Option<T> {
+        self.yield_or_take_if_expired(now).await.ok()
+    }
+
+    /// If the value is expired, clears the cache and returns the expired value as an error.
+    /// Otherwise, yields the current value.
+    ///
+    /// The error is `None` if the cache was empty, or if another thread cleared it first.
+    pub async fn yield_or_take_if_expired(&self, now: SystemTime) -> Result<T, Option<T>> {
         // Short-circuit if the value is not expired
         if let Some((value, expiry)) = self.value.read().await.get() {
             if !expired(*expiry, self.buffer_time, now) {
-                return Some(value.clone());
+                return Ok(value.clone());
             }
         }
 
@@ -91,10 +104,11 @@ where
             // Also check that we're clearing the expired value and not a value
             // that has been refreshed by another thread.
             if expired(*expiration, self.buffer_time, now) {
-                *lock = OnceCell::new();
+                let expired = std::mem::take(&mut *lock);
+                return Err(expired.into_inner().map(|(value, _expiry)| value));
             }
         }
-        None
+        Err(None)
     }
 }
 
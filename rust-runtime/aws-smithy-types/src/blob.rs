@@ -6,6 +6,9 @@
 /// Binary Blob Type
 ///
 /// Blobs represent protocol-agnostic binary content.
+///
+/// With the unstable `serde-serialize` and `serde-deserialize` features enabled, a `Blob` is
+/// serialized as a base64 string in human-readable formats such as JSON, and as bytes otherwise.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Blob {
     inner: Vec<u8>,
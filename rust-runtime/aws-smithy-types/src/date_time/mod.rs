@@ -54,6 +54,10 @@ const NANOS_PER_SECOND_U32: u32 = 1_000_000_000;
 /// The [`aws-smithy-types-convert`](https://crates.io/crates/aws-smithy-types-convert) crate
 /// can be used for conversions to/from other libraries, such as
 /// [`time`](https://crates.io/crates/time) or [`chrono`](https://crates.io/crates/chrono).
+///
+/// With the unstable `serde-serialize` and `serde-deserialize` features enabled, a `DateTime` is
+/// serialized as an RFC-3339 string in human-readable formats such as JSON, and as a tuple of
+/// seconds and sub-second nanos otherwise.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DateTime {
     pub(crate) seconds: i64,
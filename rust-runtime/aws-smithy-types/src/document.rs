@@ -21,6 +21,12 @@ use serde;
 /// Open content is useful for modeling unstructured data that has no schema, data that can't be
 /// modeled using rigid types, or data that has a schema that evolves outside of the purview of a model.
 /// The serialization format of a document is an implementation detail of a protocol.
+///
+/// With the unstable `serde-serialize` and `serde-deserialize` features enabled, `Document`
+/// implements `Serialize` and `Deserialize` in the shape of the JSON data that it represents. A
+/// document can then be converted to or from any type that implements them, for example by
+/// serializing it with `serde_json::to_value` and deserializing the result with
+/// `serde_json::from_value`.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(
     all(aws_sdk_unstable, feature = "serde-serialize"),
@@ -107,6 +113,32 @@ impl Document {
         }
     }
 
+    /// Returns the inner string value if this `Document` is a string.
+    ///
+    /// This is the same as [`as_string`](Document::as_string).
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_string()
+    }
+
+    /// Returns the number as an `i64` if this `Document` is a number that fits in one without loss.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number()
+            .and_then(|number| i64::try_from(*number).ok())
+    }
+
+    /// Returns the number as a `u64` if this `Document` is a number that fits in one without loss.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_number()
+            .and_then(|number| u64::try_from(*number).ok())
+    }
+
+    /// Returns the number as an `f64` if this `Document` is a number.
+    ///
+    /// Integers that don't fit in an `f64` are rounded to the nearest one that does.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_number().map(|number| number.to_f64_lossy())
+    }
+
     /// Returns the inner boolean value if this `Document` is a boolean.
     pub fn as_bool(&self) -> Option<bool> {
         if let Self::Bool(boolean) = self {
@@ -125,6 +157,56 @@ impl Document {
         }
     }
 
+    /// Looks up a value nested in this `Document` with a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901).
+    ///
+    /// A pointer is a string of reference tokens that each start with `/`. Each token is the key
+    /// of an object member or the index of an array element, with `~` escaped as `~0` and `/`
+    /// escaped as `~1`. The empty pointer refers to the whole document. Returns `None` if the
+    /// pointer isn't valid or the value doesn't exist.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use aws_smithy_types::Document;
+    /// use std::collections::HashMap;
+    ///
+    /// let rule = Document::Object(HashMap::from([(
+    ///     "conditions".to_string(),
+    ///     Document::Array(vec![Document::Object(HashMap::from([(
+    ///         "fn".to_string(),
+    ///         Document::from("isSet"),
+    ///     )]))]),
+    /// )]));
+    /// assert_eq!(Some("isSet"), rule.pointer("/conditions/0/fn").and_then(Document::as_str));
+    /// assert_eq!(None, rule.pointer("/conditions/1/fn"));
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Document> {
+        let mut document = self;
+        for token in pointer_tokens(pointer)? {
+            document = match document {
+                Self::Object(object) => object.get(token.as_ref())?,
+                Self::Array(array) => array.get(array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(document)
+    }
+
+    /// Looks up a value nested in this `Document` with a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901),
+    /// and returns a mutable reference to it.
+    ///
+    /// See [`pointer`](Document::pointer) for the syntax of the pointer.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Document> {
+        let mut document = self;
+        for token in pointer_tokens(pointer)? {
+            document = match document {
+                Self::Object(object) => object.get_mut(token.as_ref())?,
+                Self::Array(array) => array.get_mut(array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(document)
+    }
+
     /// Returns `true` if this `Document` is an object.
     pub fn is_object(&self) -> bool {
         matches!(self, Self::Object(_))
@@ -156,6 +238,32 @@ impl Document {
     }
 }
 
+/// Splits a JSON Pointer into its unescaped reference tokens, or returns `None` if it isn't valid.
+fn pointer_tokens(pointer: &str) -> Option<impl Iterator<Item = Cow<'_, str>>> {
+    let tokens = match pointer {
+        "" => None,
+        _ => Some(pointer.strip_prefix('/')?.split('/')),
+    };
+    Some(tokens.into_iter().flatten().map(|token| {
+        if token.contains('~') {
+            Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+        } else {
+            Cow::Borrowed(token)
+        }
+    }))
+}
+
+/// Parses a reference token as an array index, which can't have a sign or leading zeros.
+fn array_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || !token.bytes().all(|b| b.is_ascii_digit())
+        || (token.len() > 1 && token.starts_with('0'))
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
 /// The default value is `Document::Null`.
 impl Default for Document {
     fn default() -> Self {
@@ -233,6 +341,55 @@ impl From<Number> for Document {
 
 #[cfg(test)]
 mod test {
+    use crate::{Document, Number};
+    use std::collections::HashMap;
+
+    #[test]
+    fn pointer() {
+        let mut doc = Document::Object(HashMap::from([
+            (
+                "a".to_string(),
+                Document::Object(HashMap::from([(
+                    "b".to_string(),
+                    Document::Array(vec![
+                        Document::Object(HashMap::from([("c".to_string(), "d".into())])),
+                        Document::Number(Number::NegInt(-5)),
+                    ]),
+                )])),
+            ),
+            ("x/y~z".to_string(), true.into()),
+            ("".to_string(), Document::Null),
+        ]));
+
+        assert_eq!(Some(&doc), doc.pointer(""));
+        assert_eq!(
+            Some("d"),
+            doc.pointer("/a/b/0/c").and_then(Document::as_str)
+        );
+        assert_eq!(Some(-5), doc.pointer("/a/b/1").and_then(Document::as_i64));
+        assert_eq!(None, doc.pointer("/a/b/1").and_then(Document::as_u64));
+        assert_eq!(Some(-5.0), doc.pointer("/a/b/1").and_then(Document::as_f64));
+        assert_eq!(
+            Some(true),
+            doc.pointer("/x~1y~0z").and_then(Document::as_bool)
+        );
+        assert_eq!(Some(&Document::Null), doc.pointer("/"));
+
+        // Missing values, invalid indices, and pointers that don't start with `/`
+        assert_eq!(None, doc.pointer("/a/b/2"));
+        assert_eq!(None, doc.pointer("/a/b/01"));
+        assert_eq!(None, doc.pointer("/a/b/+1"));
+        assert_eq!(None, doc.pointer("/a/b/0/c/d"));
+        assert_eq!(None, doc.pointer("a/b"));
+
+        *doc.pointer_mut("/a/b/0/c").unwrap() = "e".into();
+        assert_eq!(
+            Some("e"),
+            doc.pointer("/a/b/0/c").and_then(Document::as_str)
+        );
+        assert!(doc.pointer_mut("/a/c").is_none());
+    }
+
     /// checks if a) serialization of json suceeds and b) it is compatible with serde_json
     #[test]
     #[cfg(all(
@@ -241,9 +398,6 @@ mod test {
         feature = "serde-deserialize"
     ))]
     fn serialize_json() {
-        use crate::Document;
-        use crate::Number;
-        use std::collections::HashMap;
         let mut map: HashMap<String, Document> = HashMap::new();
         // string
         map.insert("hello".into(), "world".to_string().into());
@@ -284,4 +438,41 @@ mod test {
         let doc: Result<Document, _> = serde_json::from_str(target_file);
         assert_eq!(obj, doc.unwrap());
     }
+
+    #[test]
+    #[cfg(all(
+        aws_sdk_unstable,
+        feature = "serde-serialize",
+        feature = "serde-deserialize"
+    ))]
+    fn convert_to_and_from_user_types() {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Rule {
+            name: String,
+            priority: u32,
+            tags: Vec<String>,
+            fallback: Option<Box<Rule>>,
+        }
+
+        let rule = Rule {
+            name: "primary".into(),
+            priority: 1,
+            tags: vec!["a".into()],
+            fallback: None,
+        };
+        let doc: Document = serde_json::from_value(serde_json::to_value(&rule).unwrap()).unwrap();
+        assert_eq!(
+            Some(&Document::Number(Number::PosInt(1))),
+            doc.as_object().unwrap().get("priority")
+        );
+        assert_eq!(
+            Some(&Document::Null),
+            doc.as_object().unwrap().get("fallback")
+        );
+
+        let converted: Rule = serde_json::from_value(serde_json::to_value(&doc).unwrap()).unwrap();
+        assert_eq!(rule, converted);
+    }
 }
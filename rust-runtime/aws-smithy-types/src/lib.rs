@@ -25,6 +25,7 @@ pub mod error;
 pub mod event_stream;
 pub mod primitive;
 pub mod retry;
+pub mod sensitive;
 pub mod timeout;
 
 /// Utilities for type erasure.
@@ -0,0 +1,219 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A process-wide registry of members that are redacted in addition to the model's `@sensitive` ones.
+//!
+//! Members that the model marks with the `@sensitive` trait are always redacted. When compliance
+//! rules require more than that, applications can mark other members as sensitive at runtime with
+//! [`mark_sensitive`]. Generated `Debug` impls and error messages redact the values of those members,
+//! and wire logging redacts JSON fields with their names.
+//!
+//! Members are identified by the name of their generated Rust type and their name in the model,
+//! such as `GetSecretValueOutput$Name`, or `*$Name` for a member of any shape. Since the registry is
+//! process-wide, members should be marked once, before any clients are used.
+//!
+//! # Examples
+//! ```rust
+//! use aws_smithy_types::sensitive::{is_sensitive, mark_sensitive, MemberPath};
+//!
+//! mark_sensitive("GetSecretValueOutput$Name".parse::<MemberPath>().unwrap());
+//! mark_sensitive(MemberPath::any_shape("AccountNumber"));
+//!
+//! assert!(is_sensitive("GetSecretValueOutput", "Name"));
+//! assert!(is_sensitive("DescribeAccountOutput", "AccountNumber"));
+//! assert!(!is_sensitive("ListSecretsOutput", "Name"));
+//! ```
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+/// The value that sensitive data is replaced with.
+pub const REDACTED: &str = "*** Sensitive Data Redacted ***";
+
+const ANY_SHAPE: &str = "*";
+
+// Checked first so that `Debug` impls don't need to take the lock when nothing was marked
+static ANY_MARKED: AtomicBool = AtomicBool::new(false);
+static SENSITIVE_MEMBERS: RwLock<Vec<MemberPath>> = RwLock::new(Vec::new());
+
+/// The name of a shape and one of its members, as they appear in the model
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberPath {
+    shape: Cow<'static, str>,
+    member: Cow<'static, str>,
+}
+
+impl MemberPath {
+    /// Creates a path to the given member of the given shape.
+    pub fn new(shape: impl Into<Cow<'static, str>>, member: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            shape: shape.into(),
+            member: member.into(),
+        }
+    }
+
+    /// Creates a path to the member with the given name in every shape.
+    pub fn any_shape(member: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(ANY_SHAPE, member)
+    }
+
+    /// Returns the name of the shape, or `*` if this path matches every shape.
+    pub fn shape(&self) -> &str {
+        &self.shape
+    }
+
+    /// Returns the name of the member.
+    pub fn member(&self) -> &str {
+        &self.member
+    }
+
+    fn matches(&self, shape: &str, member: &str) -> bool {
+        self.member == member && (self.shape == ANY_SHAPE || self.shape == shape)
+    }
+}
+
+impl fmt::Display for MemberPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}${}", self.shape, self.member)
+    }
+}
+
+impl FromStr for MemberPath {
+    type Err = InvalidMemberPath;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        match path.trim().split_once('$') {
+            Some((shape, member)) if !shape.is_empty() && !member.is_empty() => {
+                Ok(Self::new(shape.to_string(), member.to_string()))
+            }
+            _ => Err(InvalidMemberPath {
+                path: path.to_string(),
+            }),
+        }
+    }
+}
+
+/// Failure to parse a [`MemberPath`]
+#[derive(Debug)]
+pub struct InvalidMemberPath {
+    path: String,
+}
+
+impl fmt::Display for InvalidMemberPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid member path; expected `Shape$member` or `*$member`",
+            self.path
+        )
+    }
+}
+
+impl Error for InvalidMemberPath {}
+
+/// Marks a member as sensitive, so that its value is redacted from now on.
+pub fn mark_sensitive(path: MemberPath) {
+    let mut members = SENSITIVE_MEMBERS.write().unwrap();
+    if !members.contains(&path) {
+        members.push(path);
+    }
+    ANY_MARKED.store(true, Ordering::Release);
+}
+
+/// Returns `true` if the given member of the given shape was marked as sensitive at runtime.
+///
+/// This doesn't take the `@sensitive` trait into account, since generated code already redacts
+/// those members.
+pub fn is_sensitive(shape: &str, member: &str) -> bool {
+    ANY_MARKED.load(Ordering::Acquire)
+        && SENSITIVE_MEMBERS
+            .read()
+            .unwrap()
+            .iter()
+            .any(|path| path.matches(shape, member))
+}
+
+/// Returns `true` if a member with the given name was marked as sensitive for any shape, ignoring case.
+///
+/// This is for serialized data, such as a logged request body, where the shape isn't known.
+pub fn is_sensitive_member(member: &str) -> bool {
+    ANY_MARKED.load(Ordering::Acquire)
+        && SENSITIVE_MEMBERS
+            .read()
+            .unwrap()
+            .iter()
+            .any(|path| path.member.eq_ignore_ascii_case(member))
+}
+
+/// Returns the value to print in a `Debug` impl for the given member.
+///
+/// This is used by generated code, and returns a redacted placeholder when the member was marked as
+/// sensitive at runtime.
+#[doc(hidden)]
+pub fn redact_if_sensitive<'a>(
+    shape: &str,
+    member: &str,
+    value: &'a dyn fmt::Debug,
+) -> &'a dyn fmt::Debug {
+    if is_sensitive(shape, member) {
+        &REDACTED
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The registry is shared by every test in the process, so each test uses its own shape names.
+    #[test]
+    fn marked_members_are_redacted() {
+        assert!(!is_sensitive("RedactTestOutput", "Secret"));
+        mark_sensitive(MemberPath::new("RedactTestOutput", "Secret"));
+        mark_sensitive(MemberPath::any_shape("RedactTestAnyShape"));
+
+        assert!(is_sensitive("RedactTestOutput", "Secret"));
+        assert!(!is_sensitive("RedactTestOutput", "secret"));
+        assert!(!is_sensitive("RedactTestInput", "Secret"));
+        assert!(is_sensitive("RedactTestInput", "RedactTestAnyShape"));
+        assert!(is_sensitive_member("secret"));
+        assert!(is_sensitive_member("redacttestanyshape"));
+
+        let value = Some("hunter2");
+        assert_eq!(
+            format!("{:?}", REDACTED),
+            format!(
+                "{:?}",
+                redact_if_sensitive("RedactTestOutput", "Secret", &value)
+            )
+        );
+        assert_eq!(
+            "Some(\"hunter2\")",
+            format!(
+                "{:?}",
+                redact_if_sensitive("RedactTestOutput", "Public", &value)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_member_paths() {
+        let path: MemberPath = "GetSecretValueOutput$Name".parse().unwrap();
+        assert_eq!(MemberPath::new("GetSecretValueOutput", "Name"), path);
+        assert_eq!("GetSecretValueOutput$Name", path.to_string());
+        assert_eq!(
+            MemberPath::any_shape("Name"),
+            " *$Name ".parse::<MemberPath>().unwrap()
+        );
+        for invalid in ["", "Name", "$Name", "Shape$"] {
+            assert!(invalid.parse::<MemberPath>().is_err(), "{invalid}");
+        }
+    }
+}
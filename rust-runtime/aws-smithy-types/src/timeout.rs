@@ -7,14 +7,16 @@
 //! Smithy networking stack.
 
 use crate::config_bag::{Storable, StoreReplace};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 /// Builder for [`TimeoutConfig`].
 #[non_exhaustive]
 #[derive(Clone, Debug, Default)]
 pub struct TimeoutConfigBuilder {
     connect_timeout: Option<Duration>,
+    tls_negotiation_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
+    body_read_timeout: Option<Duration>,
     operation_timeout: Option<Duration>,
     operation_attempt_timeout: Option<Duration>,
 }
@@ -41,6 +43,29 @@ impl TimeoutConfigBuilder {
         self
     }
 
+    /// Sets the TLS negotiation timeout.
+    ///
+    /// The TLS negotiation timeout is a limit on the amount of time it takes to complete the TLS
+    /// handshake once a socket connection has been established. When it isn't set, the connect
+    /// timeout covers both establishing the socket connection and the TLS handshake.
+    pub fn tls_negotiation_timeout(mut self, tls_negotiation_timeout: Duration) -> Self {
+        self.tls_negotiation_timeout = Some(tls_negotiation_timeout);
+        self
+    }
+
+    /// Sets the TLS negotiation timeout.
+    ///
+    /// The TLS negotiation timeout is a limit on the amount of time it takes to complete the TLS
+    /// handshake once a socket connection has been established. When it isn't set, the connect
+    /// timeout covers both establishing the socket connection and the TLS handshake.
+    pub fn set_tls_negotiation_timeout(
+        &mut self,
+        tls_negotiation_timeout: Option<Duration>,
+    ) -> &mut Self {
+        self.tls_negotiation_timeout = tls_negotiation_timeout;
+        self
+    }
+
     /// Sets the read timeout.
     ///
     /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
@@ -59,6 +84,28 @@ impl TimeoutConfigBuilder {
         self
     }
 
+    /// Sets the body read timeout.
+    ///
+    /// The body read timeout is the limit on the amount of time to wait for the next chunk of a
+    /// response body while it's being read. Unlike the operation timeout, it doesn't limit how long
+    /// it takes to read the entire body, so long running downloads aren't cut short as long as data
+    /// keeps arriving.
+    pub fn body_read_timeout(mut self, body_read_timeout: Duration) -> Self {
+        self.body_read_timeout = Some(body_read_timeout);
+        self
+    }
+
+    /// Sets the body read timeout.
+    ///
+    /// The body read timeout is the limit on the amount of time to wait for the next chunk of a
+    /// response body while it's being read. Unlike the operation timeout, it doesn't limit how long
+    /// it takes to read the entire body, so long running downloads aren't cut short as long as data
+    /// keeps arriving.
+    pub fn set_body_read_timeout(&mut self, body_read_timeout: Option<Duration>) -> &mut Self {
+        self.body_read_timeout = body_read_timeout;
+        self
+    }
+
     /// Sets the operation timeout.
     ///
     /// An operation represents the full request/response lifecycle of a call to a service.
@@ -140,7 +187,11 @@ impl TimeoutConfigBuilder {
     pub fn take_unset_from(self, other: Self) -> Self {
         Self {
             connect_timeout: self.connect_timeout.or(other.connect_timeout),
+            tls_negotiation_timeout: self
+                .tls_negotiation_timeout
+                .or(other.tls_negotiation_timeout),
             read_timeout: self.read_timeout.or(other.read_timeout),
+            body_read_timeout: self.body_read_timeout.or(other.body_read_timeout),
             operation_timeout: self.operation_timeout.or(other.operation_timeout),
             operation_attempt_timeout: self
                 .operation_attempt_timeout
@@ -152,7 +203,9 @@ impl TimeoutConfigBuilder {
     pub fn build(self) -> TimeoutConfig {
         TimeoutConfig {
             connect_timeout: self.connect_timeout,
+            tls_negotiation_timeout: self.tls_negotiation_timeout,
             read_timeout: self.read_timeout,
+            body_read_timeout: self.body_read_timeout,
             operation_timeout: self.operation_timeout,
             operation_attempt_timeout: self.operation_attempt_timeout,
         }
@@ -163,7 +216,9 @@ impl From<TimeoutConfig> for TimeoutConfigBuilder {
     fn from(timeout_config: TimeoutConfig) -> Self {
         TimeoutConfigBuilder {
             connect_timeout: timeout_config.connect_timeout,
+            tls_negotiation_timeout: timeout_config.tls_negotiation_timeout,
             read_timeout: timeout_config.read_timeout,
+            body_read_timeout: timeout_config.body_read_timeout,
             operation_timeout: timeout_config.operation_timeout,
             operation_attempt_timeout: timeout_config.operation_attempt_timeout,
         }
@@ -204,7 +259,9 @@ impl From<TimeoutConfig> for TimeoutConfigBuilder {
 #[derive(Clone, PartialEq, Debug)]
 pub struct TimeoutConfig {
     connect_timeout: Option<Duration>,
+    tls_negotiation_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
+    body_read_timeout: Option<Duration>,
     operation_timeout: Option<Duration>,
     operation_attempt_timeout: Option<Duration>,
 }
@@ -233,7 +290,9 @@ impl TimeoutConfig {
     pub fn disabled() -> TimeoutConfig {
         TimeoutConfig {
             connect_timeout: None,
+            tls_negotiation_timeout: None,
             read_timeout: None,
+            body_read_timeout: None,
             operation_timeout: None,
             operation_attempt_timeout: None,
         }
@@ -246,6 +305,14 @@ impl TimeoutConfig {
         self.connect_timeout
     }
 
+    /// Returns this config's TLS negotiation timeout.
+    ///
+    /// The TLS negotiation timeout is a limit on the amount of time it takes to complete the TLS
+    /// handshake once a socket connection has been established.
+    pub fn tls_negotiation_timeout(&self) -> Option<Duration> {
+        self.tls_negotiation_timeout
+    }
+
     /// Returns this config's read timeout.
     ///
     /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
@@ -254,6 +321,14 @@ impl TimeoutConfig {
         self.read_timeout
     }
 
+    /// Returns this config's body read timeout.
+    ///
+    /// The body read timeout is the limit on the amount of time to wait for the next chunk of a
+    /// response body while it's being read.
+    pub fn body_read_timeout(&self) -> Option<Duration> {
+        self.body_read_timeout
+    }
+
     /// Returns this config's operation timeout.
     ///
     /// An operation represents the full request/response lifecycle of a call to a service.
@@ -275,6 +350,8 @@ impl TimeoutConfig {
     /// Returns true if any of the possible timeouts are set.
     pub fn has_timeouts(&self) -> bool {
         self.connect_timeout.is_some()
+            || self.tls_negotiation_timeout.is_some()
+            || self.body_read_timeout.is_some()
             || self.operation_timeout.is_some()
             || self.operation_attempt_timeout.is_some()
     }
@@ -327,3 +404,80 @@ impl From<TimeoutConfig> for OperationTimeoutConfig {
         OperationTimeoutConfig::from(&cfg)
     }
 }
+
+/// An absolute point in time by which an operation must complete.
+///
+/// Unlike the operation timeout, which starts counting when the operation starts, a deadline
+/// is fixed ahead of time. This makes it possible to bound the total time spent across several
+/// operations by giving all of them the same deadline. When both are set, the operation fails
+/// with a timeout error at whichever comes first.
+///
+/// Deadlines are measured against the client's time source, so they're expressed as a [`SystemTime`].
+///
+/// While an operation is in flight, the deadline in its config bag is whichever of its deadline
+/// and the end of its operation timeout comes first. Retry strategies and interceptors can load it
+/// to find out how much time is left.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::time::{Duration, SystemTime};
+/// use aws_smithy_types::timeout::Deadline;
+///
+/// let now = SystemTime::now();
+/// let deadline = Deadline::new(now + Duration::from_secs(2));
+/// assert_eq!(deadline.remaining(now), Duration::from_secs(2));
+/// assert_eq!(deadline.remaining(now + Duration::from_secs(5)), Duration::ZERO);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Deadline(SystemTime);
+
+impl Deadline {
+    /// Creates a new deadline at the given point in time.
+    pub fn new(deadline: SystemTime) -> Self {
+        Self(deadline)
+    }
+
+    /// Returns the point in time by which the operation must complete.
+    pub fn as_system_time(&self) -> SystemTime {
+        self.0
+    }
+
+    /// Returns the time remaining until the deadline, or zero if it has already passed.
+    pub fn remaining(&self, now: SystemTime) -> Duration {
+        self.0.duration_since(now).unwrap_or_default()
+    }
+}
+
+impl From<SystemTime> for Deadline {
+    fn from(deadline: SystemTime) -> Self {
+        Self::new(deadline)
+    }
+}
+
+impl Storable for Deadline {
+    type Storer = StoreReplace<Deadline>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeoutConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn has_timeouts() {
+        assert!(!TimeoutConfig::disabled().has_timeouts());
+        let timeout = Duration::from_secs(1);
+        let configs = [
+            TimeoutConfig::builder().connect_timeout(timeout),
+            TimeoutConfig::builder().tls_negotiation_timeout(timeout),
+            TimeoutConfig::builder().body_read_timeout(timeout),
+            TimeoutConfig::builder().operation_timeout(timeout),
+            TimeoutConfig::builder().operation_attempt_timeout(timeout),
+        ];
+        for config in configs {
+            let config = config.build();
+            assert!(config.has_timeouts(), "{config:?}");
+        }
+    }
+}
@@ -4,14 +4,26 @@
  */
 
 use std::error::Error;
+use std::fmt;
+
+/// Tracing target for the endpoint rules evaluation trace
+///
+/// The trace is only recorded when this target is enabled at `DEBUG` level, for example with
+/// `RUST_LOG=endpoint_rules=debug`.
+const TRACE_TARGET: &str = "endpoint_rules";
 
 /// Diagnostic collector for endpoint resolution
 ///
 /// Endpoint functions return `Option<T>`—to enable diagnostic information to flow, we capture the
 /// last error that occurred.
+///
+/// When the `endpoint_rules` tracing target is enabled, the collector also records the conditions
+/// that matched while the rules were evaluated, so that the endpoint a request was sent to can be
+/// explained from the logs.
 #[derive(Debug, Default)]
 pub(crate) struct DiagnosticCollector {
     last_error: Option<Box<dyn Error + Send + Sync>>,
+    matched_conditions: Option<Vec<&'static str>>,
 }
 
 impl DiagnosticCollector {
@@ -40,8 +52,64 @@ impl DiagnosticCollector {
         self.last_error.take()
     }
 
+    #[allow(unused)]
+    /// Record that a rule condition matched, if the evaluation is being traced
+    pub(crate) fn record_condition(&mut self, condition: &'static str) {
+        if let Some(matched_conditions) = self.matched_conditions.as_mut() {
+            matched_conditions.push(condition);
+        }
+    }
+
+    /// Emit the evaluation trace along with the resolved endpoint or error, if the evaluation is
+    /// being traced
+    pub(crate) fn emit_trace<T: fmt::Debug, E: fmt::Display>(&self, result: &Result<T, E>) {
+        if let Some(matched_conditions) = self.matched_conditions.as_ref() {
+            match result {
+                Ok(endpoint) => tracing::debug!(
+                    target: TRACE_TARGET,
+                    matched_conditions = ?matched_conditions,
+                    endpoint = ?endpoint,
+                    "endpoint rules resolved an endpoint"
+                ),
+                Err(err) => tracing::debug!(
+                    target: TRACE_TARGET,
+                    matched_conditions = ?matched_conditions,
+                    error = %err,
+                    "endpoint rules failed to resolve an endpoint"
+                ),
+            }
+        }
+    }
+
     /// Create a new diagnostic collector
     pub(crate) fn new() -> Self {
-        Self { last_error: None }
+        let traced = tracing::enabled!(target: TRACE_TARGET, tracing::Level::DEBUG);
+        Self {
+            last_error: None,
+            matched_conditions: traced.then(Vec::new),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DiagnosticCollector;
+
+    #[test]
+    fn conditions_are_only_recorded_when_traced() {
+        let mut collector = DiagnosticCollector::default();
+        collector.record_condition("isSet(Region)");
+        assert_eq!(None, collector.matched_conditions);
+
+        let mut collector = DiagnosticCollector {
+            matched_conditions: Some(Vec::new()),
+            ..Default::default()
+        };
+        collector.record_condition("isSet(Region)");
+        collector.record_condition("booleanEquals(UseFIPS, true)");
+        assert_eq!(
+            Some(vec!["isSet(Region)", "booleanEquals(UseFIPS, true)"]),
+            collector.matched_conditions
+        );
     }
 }
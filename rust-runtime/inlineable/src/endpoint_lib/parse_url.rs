@@ -12,6 +12,7 @@ pub(crate) struct Url<'a> {
     uri: Uri,
     url: ParsedUrl,
     raw: &'a str,
+    normalized_path: String,
 }
 
 // individual methods on parse_url might not be used (although the [`parse_url`] itself _MUST_ be used
@@ -29,11 +30,9 @@ impl<'a> Url<'a> {
         self.uri.authority().unwrap().as_str()
     }
 
+    /// The path of the URL, always ending in `/` so that it can be used as a base path prefix.
     pub(crate) fn normalized_path(&self) -> &str {
-        match self.uri.path() {
-            path if !path.is_empty() => path,
-            _ => "/",
-        }
+        &self.normalized_path
     }
 
     pub(crate) fn path(&self) -> &str {
@@ -63,7 +62,16 @@ pub(crate) fn parse_url<'a>(url: &'a str, e: &mut DiagnosticCollector) -> Option
         ));
         return None;
     }
-    Some(Url { url, uri, raw })
+    let normalized_path = match uri.path() {
+        path if path.ends_with('/') => path.to_owned(),
+        path => format!("{path}/"),
+    };
+    Some(Url {
+        url,
+        uri,
+        raw,
+        normalized_path,
+    })
 }
 
 #[cfg(test)]
@@ -99,12 +107,30 @@ mod test {
         let url = "http://localhost:8000/path";
         let url = parse_url(url, &mut DiagnosticCollector::new()).expect("valid url");
         assert_eq!(url.path(), "/path");
-        assert_eq!(url.normalized_path(), "/path");
+        assert_eq!(url.normalized_path(), "/path/");
         assert_eq!(url.is_ip(), false);
         assert_eq!(url.scheme(), "http");
         assert_eq!(url.authority(), "localhost:8000");
     }
 
+    #[test]
+    fn normalized_path_has_trailing_slash() {
+        for (url, path, normalized_path) in [
+            ("https://gateway.example.com", "", "/"),
+            ("https://gateway.example.com/", "/", "/"),
+            ("https://gateway.example.com/api/v3", "/api/v3", "/api/v3/"),
+            (
+                "https://gateway.example.com/api/v3/",
+                "/api/v3/",
+                "/api/v3/",
+            ),
+        ] {
+            let parsed = parse_url(url, &mut DiagnosticCollector::new()).expect("valid url");
+            assert_eq!(parsed.path(), path, "{url}");
+            assert_eq!(parsed.normalized_path(), normalized_path, "{url}");
+        }
+    }
+
     #[test]
     fn only_http_https_supported() {
         let url = "wss://localhost:8443/path";